@@ -0,0 +1,157 @@
+use std::io::BufRead;
+
+use regex::Regex;
+
+use {Node, NodeID, Screen};
+use export::node_path;
+
+// lines longer than this many characters are cut short
+pub const MAX_LINE: usize = 1000;
+
+// what `void add` did
+#[derive(Debug, Default, PartialEq)]
+pub struct Added {
+    pub nodes: usize,
+    // line numbers, from 1, of the lines that were cut short
+    pub truncated: Vec<usize>,
+}
+
+// one child of the node whose content is parent for each line of input,
+// without drawing anything. the parent becomes a new anchor if there's no
+// such node, and it's an error if there's more than one.
+pub fn add_lines<R>(screen: &mut Screen, input: R, parent: &str) -> Result<Added, String>
+    where R: BufRead
+{
+    let parent_id = match find_parent(screen, parent)? {
+        Some(parent_id) => parent_id,
+        None => new_anchor(screen, parent),
+    };
+
+    let mut added = Added::default();
+    for (idx, line) in input.lines().enumerate() {
+        let line = line.map_err(|e| format!("failed to read line {}: {}", idx + 1, e))?;
+        let mut content = line.trim().to_owned();
+        if content.is_empty() {
+            continue;
+        }
+        if content.chars().count() > MAX_LINE {
+            content = content.chars().take(MAX_LINE).collect::<String>().trim_end().to_owned();
+            warn!("line {} was cut short to {} characters", idx + 1, MAX_LINE);
+            added.truncated.push(idx + 1);
+        }
+        add_child(screen, parent_id, content);
+        added.nodes += 1;
+    }
+    Ok(added)
+}
+
+pub fn find_parent(screen: &Screen, parent: &str) -> Result<Option<NodeID>, String> {
+    let mut candidates: Vec<NodeID> = screen.nodes
+        .values()
+        .filter(|n| n.id != 0 && n.content == parent)
+        .map(|n| n.id)
+        .collect();
+    candidates.sort();
+    match candidates.len() {
+        0 => Ok(None),
+        1 => Ok(Some(candidates[0])),
+        _ => {
+            let listed: Vec<String> = candidates.iter()
+                .map(|&id| format!("  {}: {}", id, node_path(screen, id)))
+                .collect();
+            Err(format!("more than one node is \"{}\":\n{}", parent, listed.join("\n")))
+        }
+    }
+}
+
+// below everything that's already on the map
+fn new_anchor(screen: &mut Screen, content: &str) -> NodeID {
+    let anchors = screen.nodes[&0].children.clone();
+    let bottom = anchors.iter()
+        .map(|&id| screen.nodes[&id].rooted_coords.1 + subtree_size(screen, id) as u16)
+        .max()
+        .unwrap_or(1);
+    let node_id = add_child(screen, 0, content.to_owned());
+    screen.nodes.get_mut(&node_id).unwrap().rooted_coords = (1, bottom + 1);
+    node_id
+}
+
+fn subtree_size(screen: &Screen, node_id: NodeID) -> usize {
+    screen.nodes[&node_id].children.iter().map(|&c| subtree_size(screen, c)).sum::<usize>() + 1
+}
+
+fn add_child(screen: &mut Screen, parent_id: NodeID, content: String) -> NodeID {
+    lazy_static! {
+        static ref RE_TAG: Regex = Regex::new(r"#(\S+)").unwrap();
+    }
+
+    let node_id = screen.next_id();
+    let mut node = Node {
+        id: node_id,
+        parent_id,
+        content: content.clone(),
+        ..Node::default()
+    };
+    // `#tag` and `#key=value` go into the meta too
+    for cap in RE_TAG.captures_iter(&content) {
+        let tag = cap.at(1).unwrap();
+        let (key, value) = match tag.find('=') {
+            Some(idx) => (&tag[..idx], &tag[idx + 1..]),
+            None => (tag, ""),
+        };
+        node.meta.tags.insert(key.to_owned(), value.to_owned());
+    }
+    screen.nodes.insert(node_id, node);
+    screen.nodes.get_mut(&parent_id).unwrap().children.push(node_id);
+    screen.tag_db.reindex(node_id, content);
+    node_id
+}
+
+#[test]
+fn test_add_lines() {
+    use std::env;
+    use std::fs::{File, remove_file};
+    use std::io::Read;
+
+    use libc::getpid;
+    use serialization::deserialize_screen;
+    use session;
+
+    let path = env::temp_dir().join(format!("void-add-test-{}", unsafe { getpid() }));
+    let path = path.to_str().unwrap().to_owned();
+    let mut screen = Screen::default();
+    screen.work_path = Some(path.clone());
+
+    let long = "x".repeat(MAX_LINE + 10);
+    let input = format!("  buy milk #errand\n\n\t\ncall bob #who=bob #call \n{}\n", long);
+    let added = add_lines(&mut screen, input.as_bytes(), "Inbox").unwrap();
+    assert_eq!(added, Added { nodes: 3, truncated: vec![5] });
+    screen.save().unwrap();
+    // the parent is only made once
+    let added = add_lines(&mut screen, &b"later"[..], "Inbox").unwrap();
+    assert_eq!(added.nodes, 1);
+    screen.save().unwrap();
+
+    let mut data = vec![];
+    File::open(&path).unwrap().read_to_end(&mut data).unwrap();
+    let loaded = deserialize_screen(data).unwrap();
+    let inbox = loaded.nodes[&0].children[0];
+    assert_eq!(loaded.nodes[&inbox].content, "Inbox");
+    let children: Vec<&Node> =
+        loaded.nodes[&inbox].children.iter().map(|c| &loaded.nodes[c]).collect();
+    assert_eq!(children[0].content, "buy milk #errand");
+    assert_eq!(children[0].meta.tags["errand"], "");
+    assert_eq!(children[1].content, "call bob #who=bob #call");
+    assert_eq!(children[1].meta.tags["who"], "bob");
+    assert_eq!(children[2].content.len(), MAX_LINE);
+    assert_eq!(children[3].content, "later");
+    assert_eq!(loaded.tag_db.tag_to_nodes("errand"), vec![children[0].id]);
+
+    // there's no telling which of two nodes was meant
+    let mut screen = loaded;
+    add_lines(&mut screen, &b"Inbox"[..], "Work").unwrap();
+    let err = add_lines(&mut screen, &b"more"[..], "Inbox").unwrap_err();
+    assert!(err.contains("Work › Inbox"), "{}", err);
+    remove_file(&path).unwrap();
+    remove_file(session::path(&path)).unwrap();
+}
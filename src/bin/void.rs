@@ -1,32 +1,318 @@
 extern crate getopts;
 extern crate fs2;
+extern crate termion;
 extern crate voidmap;
 
 #[macro_use]
 extern crate log;
 
-use std::fs::OpenOptions;
-use std::io::Read;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write, stdin, stdout};
+use std::path::Path;
 
 use fs2::FileExt;
+use termion::input::TermRead;
+use termion::is_tty;
+use termion::raw::IntoRawMode;
+use termion::screen::{ToAlternateScreen, ToMainScreen};
 
-use voidmap::{Screen, Config, deserialize_screen, init_screen_log};
+use voidmap::{Screen, Capabilities, Config, CommandNotifier, CryptoKey, ExportFormat, FixedSize,
+              Hook, Peer, Picked, Picker, Recent, Recorder, Rendering, Server, Startup, add_lines,
+              config_dir, deserialize_screen, deserialize_sealed_screen, expand_home, export_as,
+              find_root, init_screen_log, is_sealed, load_recording, outline_text,
+              recording_path, resolve_startup, run_hook, set_location_refresh};
 
-fn print_usage(program: &str) {
-    println!("Usage: {} /path/to/workfile", program);
+fn print_usage(program: &str) -> ! {
+    println!("Usage: {} [md|txt|html|ical|csv] [--root id-or-content] /path/to/workfile",
+             program);
+    println!("       {} [--sync host:port] [--encrypt|--decrypt] /path/to/workfile", program);
+    println!("       {} serve --listen host:port /path/to/workfile", program);
+    println!("       {} add --stdin --parent name [--file /path/to/workfile]", program);
+    println!("       {} cat [--layout outline|canvas] [--color] /path/to/workfile", program);
+    println!("       {} [--record] /path/to/workfile", program);
+    println!("       {} replay /path/to/workfile.events /path/to/workfile.events.db", program);
     std::process::exit(1)
 }
 
+// removes `flag value` from args, returning the value
+fn take_flag(args: &mut Vec<String>, flag: &str) -> Option<String> {
+    let idx = args.iter().position(|a| a == flag)?;
+    args.remove(idx);
+    if idx < args.len() {
+        Some(args.remove(idx))
+    } else {
+        None
+    }
+}
+
+// with no path given: the key file's `file:`, $VOID_FILE or ./void.db, else
+// a recent file, picked from a list when there are several
+fn startup_path(config: &Config, recent: &Recent, interactive: bool) -> Option<String> {
+    let env_file = std::env::var("VOID_FILE").ok();
+    let dir = std::env::current_dir().unwrap_or_default();
+    match resolve_startup(config.file.as_deref(), env_file.as_deref(), &dir, recent) {
+        Startup::Open(path) => Some(path),
+        Startup::Pick(files) if interactive => Some(pick_recent(files)),
+        _ => None,
+    }
+}
+
+// esc quits without opening anything
+fn pick_recent(files: Vec<String>) -> String {
+    let mut picker = Picker::new(files);
+    let stdin = stdin();
+    let mut stdout = stdout().into_raw_mode().unwrap();
+    let mut chosen = None;
+    write!(stdout, "{}{}", ToAlternateScreen, picker.draw()).unwrap();
+    stdout.flush().unwrap();
+    for key in stdin.keys() {
+        match picker.key(key.unwrap()) {
+            Picked::Waiting => write!(stdout, "{}", picker.draw()).unwrap(),
+            Picked::Chose(file) => {
+                chosen = Some(file);
+                break;
+            }
+            Picked::Cancelled => break,
+        }
+        stdout.flush().unwrap();
+    }
+    write!(stdout, "{}", ToMainScreen).unwrap();
+    drop(stdout);
+    chosen.unwrap_or_else(|| std::process::exit(0))
+}
+
+// void used to make a new file quietly wherever it was pointed, which a
+// typo turns into an empty map
+fn confirm_create(path: &str) -> bool {
+    if !is_tty(&stdin()) {
+        return true;
+    }
+    print!("{} doesn't exist. create it? (y/n) ", path);
+    stdout().flush().unwrap();
+    let mut answer = String::new();
+    stdin().read_line(&mut answer).is_ok() && answer.trim() == "y"
+}
+
+fn default_path() -> Option<String> {
+    std::env::home_dir().and_then(|mut h| {
+        h.push(".void.db");
+        h.to_str().map(|p| p.to_owned())
+    })
+}
+
+// `void cat path` prints the map and exits, never touching the terminal's
+// modes. it exits with 66 if the file can't be read and 65 if it isn't a
+// map.
+fn cat(program: &str, mut args: Vec<String>) {
+    let canvas = match take_flag(&mut args, "--layout").as_deref() {
+        None | Some("outline") => false,
+        Some("canvas") => true,
+        Some(_) => print_usage(program),
+    };
+    // escapes only go to a terminal, and only when asked for
+    let color = args.contains(&"--color".to_owned()) && is_tty(&stdout());
+    args.retain(|a| a != "--color");
+    let path = args.pop().or_else(default_path).unwrap_or_else(|| print_usage(program));
+
+    let mut screen = read_screen(&path);
+
+    // nothing is selected on paper
+    for node in screen.nodes.values_mut() {
+        node.selected = false;
+    }
+    screen.capabilities = Capabilities::detect();
+    screen.rendering = Rendering::from_env();
+    screen.rendering.color &= color;
+    if canvas {
+        print!("{}", screen.canvas(color));
+    } else {
+        print!("{}", outline_text(&screen));
+    }
+}
+
+// exits with 66 if the file can't be read and 65 if it isn't a map
+fn read_screen(path: &str) -> Screen {
+    let mut data = vec![];
+    if let Err(e) = File::open(path).and_then(|mut f| f.read_to_end(&mut data)) {
+        println!("failed to read {}: {}", path, e);
+        std::process::exit(66);
+    }
+    let loaded = if data.is_empty() {
+        // void leaves an empty file behind when nothing was ever saved
+        Ok(Screen::default())
+    } else if is_sealed(&data) {
+        deserialize_sealed_screen(&data, &read_passphrase("passphrase: "))
+    } else {
+        deserialize_screen(data).map_err(|e| format!("{:?}", e))
+    };
+    loaded.unwrap_or_else(|e| {
+        println!("failed to open {}: {}", path, e);
+        std::process::exit(65)
+    })
+}
+
+// `void replay events start` plays a recording made with --record back on
+// the copy of the work file it started from, printing the map it ends with
+// and anything found broken along the way. nothing is written.
+fn replay(program: &str, mut args: Vec<String>) {
+    if args.len() != 2 {
+        print_usage(program);
+    }
+    let start = args.pop().unwrap();
+    let events = args.pop().unwrap();
+    let entries = load_recording(&events).unwrap_or_else(|e| {
+        println!("{}", e);
+        std::process::exit(66)
+    });
+    let mut screen = read_screen(&start);
+    screen.is_test = true;
+    screen.work_path = Some(start);
+    screen.restore_session();
+    screen.work_path = None;
+    screen.config = Config::maybe_parsed_from_env().unwrap();
+    // sizes come from the recording, never from whatever terminal this is
+    screen.size_source = Box::new(FixedSize(screen.config.fallback_size));
+
+    let broken = screen.replay_recording(&entries);
+    for node in screen.nodes.values_mut() {
+        node.selected = false;
+    }
+    print!("{}", screen.canvas(false));
+    if broken.is_empty() {
+        println!("replayed {} entries, nothing broken", entries.len());
+    } else {
+        for e in &broken {
+            println!("{}", e);
+        }
+        std::process::exit(1);
+    }
+}
+
+// `--record` keeps every key and click in path.events, along with a copy of
+// the work file and its session as they were, which is where a recording
+// has to be played back from
+fn start_recording(path: &str) -> Recorder {
+    let events = recording_path(path);
+    let start = format!("{}.db", events);
+    let session = format!("{}.session", path);
+    let recorder = fs::copy(path, &start)
+        .and_then(|_| if Path::new(&session).exists() {
+            fs::copy(&session, format!("{}.session", start)).map(|_| ())
+        } else {
+            Ok(())
+        })
+        .and_then(|()| Recorder::create(&events));
+    recorder.unwrap_or_else(|e| {
+        println!("failed to start recording to {}: {}", events, e);
+        std::process::exit(1)
+    })
+}
+
+fn read_passphrase(prompt: &str) -> String {
+    print!("{}", prompt);
+    let passphrase = stdin().read_passwd(&mut stdout());
+    println!();
+    match passphrase {
+        Ok(Some(passphrase)) => passphrase,
+        _ => std::process::exit(1),
+    }
+}
+
+// syncing peers share a token, kept out of the arguments so that other
+// users can't read it from the process list
+fn sync_token() -> String {
+    std::env::var("SYNC_TOKEN").unwrap_or_else(|_| {
+        println!("SYNC_TOKEN must be set to the token shared by syncing voids");
+        std::process::exit(1)
+    })
+}
+
 fn main() {
     init_screen_log().unwrap();
 
     let mut args: Vec<String> = std::env::args().collect();
     let program = args.remove(0);
-    let default = std::env::home_dir().and_then(|mut h| {
-        h.push(".void.db");
-        h.to_str().map(|p| p.to_owned())
-    });
-    let path = args.pop().or(default);
+    let config = Config::maybe_parsed_from_env().unwrap();
+    // `void md path`, `void html path` and the like print an export and
+    // exit. with `--root node` it's only that node's subtree.
+    let export = match args.first().map(|a| a.as_str()) {
+        Some("md") => Some(ExportFormat::Markdown),
+        Some("txt") => Some(ExportFormat::Text),
+        Some("html") => Some(ExportFormat::Html),
+        Some("ical") => Some(ExportFormat::Ical),
+        Some("csv") => Some(ExportFormat::Csv),
+        _ => None,
+    };
+    let export_root = if export.is_some() {
+        args.remove(0);
+        take_flag(&mut args, "--root")
+    } else {
+        None
+    };
+    if args.first().map(|a| a.as_str()) == Some("cat") {
+        args.remove(0);
+        cat(&program, args);
+        return;
+    }
+    if args.first().map(|a| a.as_str()) == Some("replay") {
+        args.remove(0);
+        replay(&program, args);
+        return;
+    }
+    // `void serve --listen addr path` keeps path merged with the voids that
+    // sync with it, without drawing anything
+    let serve = if args.first().map(|a| a.as_str()) == Some("serve") {
+        args.remove(0);
+        let addr = take_flag(&mut args, "--listen");
+        if addr.is_none() {
+            print_usage(&program);
+        }
+        addr
+    } else {
+        None
+    };
+    // `some-command | void add --stdin --parent name` makes a child of name
+    // for each line, without drawing anything
+    let add = if args.first().map(|a| a.as_str()) == Some("add") {
+        args.remove(0);
+        let parent = take_flag(&mut args, "--parent");
+        if let Some(file) = take_flag(&mut args, "--file") {
+            args.push(file);
+        }
+        let from_stdin = args.contains(&"--stdin".to_owned());
+        args.retain(|a| a != "--stdin");
+        if parent.is_none() || !from_stdin {
+            print_usage(&program);
+        }
+        parent
+    } else {
+        None
+    };
+    let sync = take_flag(&mut args, "--sync");
+    let record = args.contains(&"--record".to_owned());
+    args.retain(|a| a != "--record");
+    // `--encrypt` sets a new passphrase for the work file, and `--decrypt`
+    // saves it unencrypted again
+    let encrypt = args.contains(&"--encrypt".to_owned());
+    let decrypt = args.contains(&"--decrypt".to_owned());
+    args.retain(|a| a != "--encrypt" && a != "--decrypt");
+    // only a run that draws gets to ask anything before opening
+    let interactive = export.is_none() && add.is_none() && serve.is_none();
+    let mut recent = config_dir().map(|dir| Recent::load(&dir)).unwrap_or_default();
+    let path = args.pop()
+        .map(|path| expand_home(&path))
+        .or_else(|| startup_path(&config, &recent, interactive))
+        .or_else(default_path);
+    if let Some(ref path) = path {
+        if interactive && !Path::new(path).exists() && !confirm_create(path) {
+            std::process::exit(1);
+        }
+    }
+
+    // a chance to pull the latest copy before it's read
+    if let Some(ref path) = path {
+        run_hook(&config, Hook::Open, path);
+    }
 
     // load from file if present
     let mut data = vec![];
@@ -38,8 +324,8 @@ fn main() {
                 .create(true)
                 .open(path)
                 .unwrap_or_else(|e| {
-                    print_usage(&*program);
-                    panic!("error opening file: {}", e);
+                    println!("error opening file: {}", e);
+                    print_usage(&*program)
                 })
         })
         .unwrap();
@@ -50,13 +336,151 @@ fn main() {
 
     f.read_to_end(&mut data).unwrap();
 
-    let saved_screen = deserialize_screen(data).ok();
+    // keys go into the recording in the clear, which would give away
+    // everything typed into an encrypted map
+    if record && (encrypt || decrypt || is_sealed(&data)) {
+        println!("can't record an encrypted work file");
+        std::process::exit(1);
+    }
+
+    let was_empty = data.is_empty();
+    let saved_screen = if is_sealed(&data) {
+        if add.is_some() {
+            // stdin is taken, so there's nowhere to read a passphrase from
+            println!("can't add to an encrypted work file from stdin");
+            std::process::exit(1);
+        }
+        let passphrase = read_passphrase("passphrase: ");
+        match deserialize_sealed_screen(&data, &passphrase) {
+            Ok(screen) => Some(screen),
+            Err(e) => {
+                println!("failed to open {}: {}", path.unwrap(), e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        deserialize_screen(data).ok()
+    };
+    if interactive && (was_empty || saved_screen.is_some()) {
+        recent.add(path.as_ref().unwrap());
+    }
 
     let mut screen = saved_screen.unwrap_or_else(Screen::default);
+
+    if let Some(format) = export {
+        let root = export_root.map(|spec| {
+            find_root(&screen, &spec).unwrap_or_else(|e| {
+                println!("can't export from {}: {}", spec, e);
+                std::process::exit(1);
+            })
+        });
+        print!("{}", export_as(&screen, format, root));
+        return;
+    }
+
     screen.work_path = path.clone();
+    screen.rendering = Rendering::from_env();
+    screen.capabilities = Capabilities::detect();
+
+    if encrypt {
+        let passphrase = read_passphrase("new passphrase: ");
+        if read_passphrase("again: ") != passphrase {
+            println!("the passphrases don't match");
+            std::process::exit(1);
+        }
+        screen.key = Some(CryptoKey::generate(&passphrase));
+    } else if decrypt {
+        screen.key = None;
+    }
+
+    if let Some(parent) = add {
+        // keep the session as it was for the next time void is opened
+        screen.restore_session();
+        let stdin = stdin();
+        match add_lines(&mut screen, stdin.lock(), &parent) {
+            Ok(added) => {
+                for line in added.truncated {
+                    println!("line {} was cut short", line);
+                }
+                if let Err(e) = screen.save() {
+                    println!("failed to save: {}", e);
+                    std::process::exit(1);
+                }
+                println!("added {} nodes under {}", added.nodes, parent);
+                return;
+            }
+            Err(e) => {
+                println!("{}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // maps are synced in the clear
+    if screen.key.is_some() && (serve.is_some() || sync.is_some()) {
+        println!("can't sync an encrypted work file");
+        std::process::exit(1);
+    }
+
+    if let Some(addr) = serve {
+        let server = Server::bind(&addr, &sync_token())
+            .unwrap_or_else(|e| panic!("failed to listen on {}: {}", addr, e));
+        println!("serving {} on {}", path.unwrap(), addr);
+        loop {
+            match server.answer(&mut screen) {
+                Ok((received, sent)) => {
+                    if let Err(e) = screen.save() {
+                        println!("failed to save: {}", e);
+                    }
+                    println!("synced: got {} bytes, sent {}", received, sent);
+                }
+                Err(e) => println!("sync failed: {}", e),
+            }
+        }
+    }
+    if let Some(addr) = sync {
+        screen.sync = Some(Peer::new(&addr, &sync_token()));
+    }
+
+    if let Some(days) = std::env::var("ARCHIVE_AFTER_DAYS")
+        .ok()
+        .and_then(|d| d.parse().ok())
+    {
+        screen.archive_stricken(days);
+    }
+
+    screen.restore_session();
 
-    let config = Config::maybe_parsed_from_env().unwrap();
     screen.config = config;
+    screen.recent = recent;
+    if record {
+        screen.recorder = Some(start_recording(path.as_ref().unwrap()));
+    }
+
+    if let Some(threshold) = std::env::var("DELETE_CONFIRM_THRESHOLD")
+        .ok()
+        .and_then(|t| t.parse().ok())
+    {
+        screen.delete_confirm_threshold = threshold;
+    }
+
+    if let Some(mins) = std::env::var("LOCATION_REFRESH_MINS")
+        .ok()
+        .and_then(|m| m.parse::<u64>().ok())
+    {
+        set_location_refresh(mins * 60);
+    }
+
+    if let Ok(command) = std::env::var("NOTIFY_COMMAND") {
+        screen.notifier = Box::new(CommandNotifier { command });
+    }
+
+    if let Ok(opener) = std::env::var("URL_OPENER") {
+        screen.url_opener = opener;
+    }
 
-    screen.run();
+    if let Err(e) = screen.run() {
+        println!("{}", e);
+        std::process::exit(1);
+    }
 }
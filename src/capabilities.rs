@@ -0,0 +1,203 @@
+use std::env;
+
+// what the terminal can do, guessed from the environment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Capabilities {
+    pub mouse: bool,
+    pub unicode: bool,
+}
+
+impl Default for Capabilities {
+    fn default() -> Capabilities {
+        Capabilities {
+            mouse: true,
+            unicode: true,
+        }
+    }
+}
+
+// terminals that show neither box drawing nor mouse reports. the linux
+// console's fonts lack most glyphs, and it only has a mouse through gpm.
+const BASIC_TERMS: &[&str] = &["linux", "dumb", "vt100", "vt102", "vt220", "cons25", "ansi"];
+
+impl Capabilities {
+    pub fn detect() -> Capabilities {
+        Capabilities::probe(|name| env::var(name).ok())
+    }
+
+    // TERM and the locale decide, unless GLYPHS is set to ascii or unicode,
+    // or MOUSE to on or off
+    pub fn probe<F>(var: F) -> Capabilities
+        where F: Fn(&str) -> Option<String>
+    {
+        let term = var("TERM").unwrap_or_default();
+        let basic = BASIC_TERMS.contains(&&*term);
+        // the first of these that's set decides, like for setlocale
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .filter_map(|name| var(name))
+            .find(|value| !value.is_empty());
+        let utf8 = match locale {
+            Some(locale) => {
+                let locale = locale.to_lowercase();
+                locale.contains("utf-8") || locale.contains("utf8")
+            }
+            // nothing to go on, so keep assuming unicode
+            None => true,
+        };
+
+        Capabilities {
+            mouse: match var("MOUSE").as_deref() {
+                Some("on") => true,
+                Some("off") => false,
+                _ => !basic,
+            },
+            unicode: match var("GLYPHS").as_deref() {
+                Some("unicode") => true,
+                Some("ascii") => false,
+                _ => utf8 && !basic,
+            },
+        }
+    }
+
+    pub fn glyphs(&self) -> &'static Glyphs {
+        if self.unicode { &UNICODE } else { &ASCII }
+    }
+}
+
+// every character that void draws besides node text
+pub struct Glyphs {
+    pub horizontal: char,
+    pub vertical: char,
+    pub top_left: char,
+    pub top_right: char,
+    pub bottom_left: char,
+    pub bottom_right: char,
+    pub tee: char,
+    pub scroll_thumb: char,
+    // the rest of the header and log separator rows
+    pub fill: char,
+    // an arrow from a node to itself
+    pub self_arrow: char,
+    pub stricken: char,
+    pub collapsed: char,
+    pub hide_stricken: char,
+    pub done: char,
+    pub ellipsis: char,
+    pub path_separator: char,
+    pub pointer: char,
+    pub timer_running: char,
+    pub timer_stopped: char,
+    pub link: char,
+    // after a node with files attached
+    pub attachment: char,
+    // before the name of an anchor's group
+    pub group: char,
+    // before the count of what an iconified anchor holds
+    pub iconified: char,
+    pub cursor: char,
+    pub minimap_node: char,
+    pub minimap_selected: char,
+    pub map_empty: char,
+    // control characters in content are shown as their control pictures,
+    // like ␛, or else as the replacement
+    pub control_pictures: bool,
+    pub replacement: char,
+    // from empty to full
+    pub bars: [char; 9],
+    pub shades: [char; 5],
+}
+
+pub const UNICODE: Glyphs = Glyphs {
+    horizontal: '─',
+    vertical: '│',
+    top_left: '┌',
+    top_right: '┐',
+    bottom_left: '└',
+    bottom_right: '┘',
+    tee: '├',
+    scroll_thumb: '┃',
+    fill: '█',
+    self_arrow: '↺',
+    stricken: '☠',
+    collapsed: '⊞',
+    hide_stricken: '⚔',
+    done: '✓',
+    ellipsis: '…',
+    path_separator: '›',
+    pointer: '→',
+    timer_running: '▶',
+    timer_stopped: '⏱',
+    link: '↗',
+    attachment: '📎',
+    group: '◆',
+    iconified: '▣',
+    cursor: '▏',
+    minimap_node: '▪',
+    minimap_selected: '●',
+    map_empty: '·',
+    control_pictures: true,
+    replacement: '�',
+    bars: [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'],
+    shades: [' ', '░', '▒', '▓', '█'],
+};
+
+pub const ASCII: Glyphs = Glyphs {
+    horizontal: '-',
+    vertical: '|',
+    top_left: '+',
+    top_right: '+',
+    bottom_left: '+',
+    bottom_right: '+',
+    tee: '+',
+    scroll_thumb: '#',
+    fill: '*',
+    self_arrow: '@',
+    stricken: 'x',
+    collapsed: '+',
+    hide_stricken: '%',
+    done: '+',
+    ellipsis: '~',
+    path_separator: '>',
+    pointer: '>',
+    timer_running: '>',
+    timer_stopped: '*',
+    link: '^',
+    attachment: '@',
+    group: '&',
+    iconified: '#',
+    cursor: '|',
+    minimap_node: '#',
+    minimap_selected: '@',
+    map_empty: '.',
+    control_pictures: false,
+    replacement: '?',
+    bars: [' ', '.', '.', '-', '-', '=', '=', '#', '#'],
+    shades: [' ', '.', ':', '+', '#'],
+};
+
+#[test]
+fn test_probe() {
+    fn probe(vars: &[(&str, &str)]) -> Capabilities {
+        Capabilities::probe(|name| {
+            vars.iter().find(|&&(n, _)| n == name).map(|&(_, value)| value.to_owned())
+        })
+    }
+
+    assert_eq!(probe(&[]), Capabilities::default());
+    assert_eq!(probe(&[("TERM", "xterm-256color"), ("LANG", "en_US.UTF-8")]),
+               Capabilities::default());
+    let console = probe(&[("TERM", "linux"), ("LANG", "en_US.UTF-8")]);
+    assert!(!console.mouse && !console.unicode);
+    let c_locale = probe(&[("TERM", "xterm"), ("LC_ALL", "C"), ("LANG", "en_US.utf8")]);
+    assert!(c_locale.mouse && !c_locale.unicode);
+    let empty_lc_all = probe(&[("LC_ALL", ""), ("LC_CTYPE", "de_DE.utf8")]);
+    assert!(empty_lc_all.unicode);
+
+    let forced = probe(&[("TERM", "linux"), ("MOUSE", "on"), ("GLYPHS", "unicode")]);
+    assert_eq!(forced, Capabilities::default());
+    let refused = probe(&[("TERM", "xterm"), ("MOUSE", "off"), ("GLYPHS", "ascii")]);
+    assert!(!refused.mouse && !refused.unicode);
+
+    assert!(ASCII.bars.iter().chain(&ASCII.shades).all(|c| c.is_ascii()));
+}
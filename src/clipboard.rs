@@ -0,0 +1,85 @@
+use std::env;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+// many terminals drop OSC 52 sequences longer than this
+pub const OSC52_LIMIT: usize = 100_000;
+
+const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub fn base64_encode(input: &[u8]) -> String {
+    let mut out = String::new();
+    for chunk in input.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as usize) << 16 | (b[1] as usize) << 8 | b[2] as usize;
+        out.push(BASE64[n >> 18 & 63] as char);
+        out.push(BASE64[n >> 12 & 63] as char);
+        out.push(if chunk.len() > 1 { BASE64[n >> 6 & 63] as char } else { '=' });
+        out.push(if chunk.len() > 2 { BASE64[n & 63] as char } else { '=' });
+    }
+    out
+}
+
+// the escape sequence asking the terminal to put text on the clipboard,
+// truncated to fit OSC52_LIMIT.  returns whether it had to truncate.
+pub fn osc52(text: &str) -> (String, bool) {
+    let mut end = text.len();
+    let max_raw = OSC52_LIMIT / 4 * 3;
+    if end > max_raw {
+        end = max_raw;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+    }
+    let seq = format!("\x1b]52;c;{}\x07", base64_encode(&text.as_bytes()[..end]));
+    (seq, end < text.len())
+}
+
+// hand text to a local clipboard tool, if there is one.  over ssh it
+// would fill the wrong machine's clipboard, so it isn't tried there.
+pub fn copy_with_tool(text: &str) -> bool {
+    if env::var("SSH_CONNECTION").is_ok() {
+        return false;
+    }
+    let tools: [(&str, &[&str]); 2] = [("pbcopy", &[]), ("xclip", &["-selection", "clipboard"])];
+    for &(tool, args) in &tools {
+        let child = Command::new(tool)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        if let Ok(mut child) = child {
+            let written = child.stdin.take().map(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+            if child.wait().map(|s| s.success()).unwrap_or(false) && written == Some(true) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+#[test]
+fn test_base64() {
+    assert_eq!(base64_encode(b""), "");
+    assert_eq!(base64_encode(b"f"), "Zg==");
+    assert_eq!(base64_encode(b"fo"), "Zm8=");
+    assert_eq!(base64_encode(b"foo"), "Zm9v");
+    assert_eq!(base64_encode(b"foob"), "Zm9vYg==");
+    assert_eq!(base64_encode(b"fooba"), "Zm9vYmE=");
+    assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    assert_eq!(base64_encode("→ ✓\n".as_bytes()), "4oaSIOKckwo=");
+    assert_eq!(base64_encode(&[0xff, 0xfe, 0x00]), "//4A");
+}
+
+#[test]
+fn test_osc52_truncation() {
+    assert_eq!(osc52("hi"), ("\x1b]52;c;aGk=\x07".to_owned(), false));
+
+    // a three byte char straddles the limit, so it's dropped whole
+    let prefix = "a".repeat(OSC52_LIMIT / 4 * 3 - 4);
+    let (seq, truncated) = osc52(&format!("{}✓✓", prefix));
+    assert!(truncated);
+    assert!(seq.len() - "\x1b]52;c;\x07".len() <= OSC52_LIMIT);
+    assert_eq!(seq, osc52(&format!("{}✓", prefix)).0);
+}
@@ -0,0 +1,41 @@
+use std::cell::Cell;
+
+use time;
+
+// the time as void sees it. it's the wall clock, except while it's held at
+// one moment: each input is handled at the time it was recorded with, so a
+// recording plays back against the same clock it was made on.
+thread_local! {
+    static HELD: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+// milliseconds since the epoch
+pub fn millis() -> u64 {
+    HELD.with(|held| held.get()).unwrap_or_else(|| {
+        let now = time::get_time();
+        now.sec as u64 * 1000 + now.nsec as u64 / 1_000_000
+    })
+}
+
+// seconds since the epoch, which is what nodes keep
+pub fn now() -> u64 {
+    millis() / 1000
+}
+
+// runs f with the clock held at the given millisecond
+pub fn held_at<T, F: FnOnce() -> T>(at: u64, f: F) -> T {
+    let before = HELD.with(|held| held.replace(Some(at)));
+    let ret = f();
+    HELD.with(|held| held.set(before));
+    ret
+}
+
+#[test]
+fn test_held_at() {
+    let inner = held_at(1_500_000_000_999, || {
+        assert_eq!(now(), 1_500_000_000);
+        held_at(7, millis)
+    });
+    assert_eq!(inner, 7);
+    assert!(now() > 1_500_000_000);
+}
@@ -1,3 +1,5 @@
+use std::env;
+
 use rand::{self, Rng};
 
 pub fn random_fg_color() -> String {
@@ -14,3 +16,232 @@ pub fn random_fg_color() -> String {
     let c = &*rand::thread_rng().choose(&*colors).unwrap();
     c.clone()
 }
+
+// the colors nodes can be given by name, which are the bright ones
+fn named_colors() -> Vec<(&'static str, String)> {
+    use termion::color::*;
+    vec![("red", format!("{}", Fg(LightRed))),
+         ("green", format!("{}", Fg(LightGreen))),
+         ("yellow", format!("{}", Fg(LightYellow))),
+         ("blue", format!("{}", Fg(LightBlue))),
+         ("magenta", format!("{}", Fg(LightMagenta))),
+         ("cyan", format!("{}", Fg(LightCyan))),
+         ("white", format!("{}", Fg(LightWhite)))]
+}
+
+// a node color from a name like `red`, or from a number of the 256 color
+// palette
+pub fn parse_color(text: &str) -> Option<String> {
+    use termion::color::{AnsiValue, Fg};
+    let text = text.trim();
+    if let Some(named) = named_colors().into_iter().find(|&(name, _)| name == text) {
+        return Some(named.1);
+    }
+    text.parse::<u8>().ok().map(|n| format!("{}", Fg(AnsiValue(n))))
+}
+
+// how nodes with some tag are drawn, like `red bold` or `underline`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Highlight {
+    // the words it was made from, as written in the key file
+    pub spec: String,
+    pub color: Option<String>,
+    pub attributes: String,
+}
+
+impl Highlight {
+    pub fn parse(spec: &str) -> Result<Highlight, String> {
+        use termion::style::*;
+        let mut highlight = Highlight {
+            spec: spec.split_whitespace().collect::<Vec<_>>().join(" "),
+            color: None,
+            attributes: String::new(),
+        };
+        if highlight.spec.is_empty() {
+            return Err("no color or style given".to_owned());
+        }
+        for word in spec.split_whitespace() {
+            let attribute = match word {
+                "bold" => format!("{}", Bold),
+                "italic" => format!("{}", Italic),
+                "underline" => format!("{}", Underline),
+                "invert" => format!("{}", Invert),
+                _ => {
+                    let color = parse_color(word).ok_or_else(|| format!("unknown color: {}", word));
+                    highlight.color = Some(color?);
+                    continue;
+                }
+            };
+            highlight.attributes.push_str(&attribute);
+        }
+        Ok(highlight)
+    }
+}
+
+// the other way around, for colors that parse_color could have made
+pub fn color_name(color: &str) -> Option<String> {
+    if let Some(named) = named_colors().into_iter().find(|named| named.1 == color) {
+        return Some(named.0.to_owned());
+    }
+    if color.starts_with("\x1b[38;5;") && color.ends_with('m') {
+        color[7..color.len() - 1].parse::<u8>().ok().map(|n| n.to_string())
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Theme {
+    Default,
+    // attributes like inversion and strikethrough, but no colors
+    Monochrome,
+    // bright colors, and bold in place of faint
+    HighContrast,
+}
+
+impl Theme {
+    pub fn from_name(name: &str) -> Option<Theme> {
+        match name {
+            "default" => Some(Theme::Default),
+            "monochrome" => Some(Theme::Monochrome),
+            "high-contrast" => Some(Theme::HighContrast),
+            _ => None,
+        }
+    }
+}
+
+// how styles are drawn. with color off, only inversion is left, and the
+// selected node is marked with `>` too.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rendering {
+    pub color: bool,
+    pub theme: Theme,
+}
+
+impl Default for Rendering {
+    fn default() -> Rendering {
+        Rendering {
+            color: true,
+            theme: Theme::Default,
+        }
+    }
+}
+
+impl Rendering {
+    // NO_COLOR (see no-color.org) turns colors off, and THEME picks a theme
+    pub fn from_env() -> Rendering {
+        Rendering {
+            color: env::var("NO_COLOR").map(|v| v.is_empty()).unwrap_or(true),
+            theme: env::var("THEME")
+                .ok()
+                .and_then(|name| Theme::from_name(&name))
+                .unwrap_or(Theme::Default),
+        }
+    }
+
+    // output with its style escapes changed to suit these settings, leaving
+    // cursor movement and the rest alone
+    pub fn restyle(&self, output: &str) -> String {
+        if *self == Rendering::default() {
+            return output.to_owned();
+        }
+        let mut restyled = String::with_capacity(output.len());
+        let mut rest = output;
+        while let Some(start) = rest.find("\x1b[") {
+            restyled.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let end = rest[2..]
+                .find(|c: char| ('@'..='~').contains(&c))
+                .map(|i| i + 3)
+                .unwrap_or(rest.len());
+            let (seq, after) = rest.split_at(end);
+            rest = after;
+            if !seq.ends_with('m') || seq.len() == 3 {
+                restyled.push_str(seq);
+                continue;
+            }
+            let kept = self.restyle_params(&seq[2..seq.len() - 1]);
+            if !kept.is_empty() {
+                restyled.push_str(&format!("\x1b[{}m", kept.join(";")));
+            }
+        }
+        restyled.push_str(rest);
+        restyled
+    }
+
+    fn restyle_params(&self, params: &str) -> Vec<String> {
+        let params: Vec<&str> = params.split(';').collect();
+        let mut kept = vec![];
+        let mut i = 0;
+        while i < params.len() {
+            let code: u32 = params[i].parse().unwrap_or(0);
+            // 38 and 48 are followed by 5;n or 2;r;g;b
+            let args = match (code, params.get(i + 1)) {
+                (38, Some(&"5")) | (48, Some(&"5")) => 2,
+                (38, Some(&"2")) | (48, Some(&"2")) => 4,
+                _ => 0,
+            };
+            let param = params[i..params.len().min(i + 1 + args)].join(";");
+            i += 1 + args;
+
+            let is_color = (30..50).contains(&code) || (90..98).contains(&code) ||
+                           (100..108).contains(&code);
+            match (self.color, self.theme) {
+                (false, _) if code == 0 || code == 7 || code == 27 => kept.push(param),
+                (false, _) => {}
+                (true, Theme::Monochrome) if is_color => {}
+                (true, Theme::HighContrast) if code == 2 => kept.push("1".to_owned()),
+                (true, Theme::HighContrast) if (30..38).contains(&code) => {
+                    kept.push((code + 60).to_string())
+                }
+                (true, Theme::HighContrast) if code == 90 => kept.push("97".to_owned()),
+                (true, Theme::HighContrast) if code == 38 && args == 2 => {
+                    // termion writes every color as one of the 256
+                    let n: u32 = params[i - 1].parse().unwrap_or(0);
+                    let n = match n {
+                        0..=7 => n + 8,
+                        8 => 15,
+                        _ => n,
+                    };
+                    kept.push(format!("38;5;{}", n))
+                }
+                (true, _) => kept.push(param),
+            }
+        }
+        kept
+    }
+}
+
+#[test]
+fn test_restyle() {
+    use termion::{color, cursor, style};
+    let output = format!("{}{}{}a{}{}b{}{}c{}",
+                         cursor::Goto(3, 4),
+                         color::Fg(color::LightRed),
+                         style::Faint,
+                         "\x1b[38;5;200;7m",
+                         color::Bg(color::Blue),
+                         style::Reset,
+                         "\x1b[9m",
+                         color::Fg(color::Reset));
+
+    assert_eq!(Rendering::default().restyle(&output), output);
+    let plain = Rendering {
+        color: false,
+        theme: Theme::Default,
+    };
+    assert_eq!(plain.restyle(&output), "\x1b[4;3Ha\x1b[7mb\x1b[mc");
+    let mono = Rendering {
+        color: true,
+        theme: Theme::Monochrome,
+    };
+    assert_eq!(mono.restyle(&output), "\x1b[4;3H\x1b[2ma\x1b[7mb\x1b[m\x1b[9mc");
+    let bright = Rendering {
+        color: true,
+        theme: Theme::HighContrast,
+    };
+    assert_eq!(bright.restyle(&format!("{}{}x", color::Fg(color::Red), style::Faint)),
+               "\x1b[38;5;9m\x1b[1mx");
+    assert_eq!(Theme::from_name("high-contrast"), Some(Theme::HighContrast));
+    assert_eq!(Theme::from_name("neon"), None);
+}
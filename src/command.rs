@@ -0,0 +1,393 @@
+use std::env;
+use std::fs;
+
+use log::LogLevelFilter;
+
+use Action;
+use colors::Highlight;
+use config::{ACTIONS, str_to_action};
+use sort::SortBy;
+use substitute::Substitution;
+
+// what can be typed after `:`, besides the name of any key file action
+const COMMANDS: &[&str] = &["w", "q", "q!", "open", "merge", "export", "set", "loglevel", "sort",
+                              "sort!", "archive", "debug", "anchor", "highlight", "perf",
+                              "stale", "substitute"];
+
+const EXPORT_FORMATS: &[&str] = &["md", "txt", "html", "ics", "csv"];
+
+const SETTINGS: &[&str] = &["hidestricken", "autoarrange", "logs", "modal", "numbers"];
+
+const SORT_ORDERS: &[&str] = &["name", "created", "due", "done"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Action(Action),
+    // save, to the given path instead of the work file if there is one
+    Write(Option<String>),
+    Quit { force: bool },
+    Open(String),
+    // another copy of the map, edited separately
+    Merge(String),
+    Export(ExportFormat, String),
+    Set(Setting, bool),
+    LogLevel(LogLevelFilter),
+    // stricken subtrees finished more than this many days ago
+    Archive(Option<u64>),
+    // the selected node's children, and theirs too when recursive
+    Sort {
+        by: SortBy,
+        reverse: bool,
+        recursive: bool,
+    },
+    // how nodes with a tag are drawn, or None to draw them as usual again
+    Highlight(String, Option<Highlight>),
+    // how long drawing, clicks, arrows and saving have been taking
+    Perf,
+    // the nodes that have gone untouched longest
+    Stale,
+    // text replaced in every node in view that has it, once confirmed
+    Substitute(Substitution),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Markdown,
+    Text,
+    Html,
+    Ical,
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Setting {
+    HideStricken,
+    AutoArrange,
+    Logs,
+    Modal,
+    Numbering,
+}
+
+// a command line, without the leading `:`, or a message saying what's wrong
+pub fn parse(line: &str) -> Result<Command, String> {
+    let mut words = line.split_whitespace();
+    let name = match words.next() {
+        Some(name) => name,
+        None => return Err("no command given".to_owned()),
+    };
+    // the pattern and replacement may have spaces in them, so they're
+    // taken whole
+    if name == "substitute" || name == "s" {
+        let spec = line.trim_start()[name.len()..].trim_start();
+        return Substitution::parse(spec).map(Command::Substitute);
+    }
+    let args: Vec<&str> = words.collect();
+    let usage = |usage: &str| Err(format!("usage: :{} {}", name, usage));
+
+    match (name, args.as_slice()) {
+        ("w", &[]) => Ok(Command::Write(None)),
+        ("w", &[path]) => Ok(Command::Write(Some(path.to_owned()))),
+        ("w", _) => usage("[path]"),
+        ("q", &[]) => Ok(Command::Quit { force: false }),
+        ("q!", &[]) => Ok(Command::Quit { force: true }),
+        ("open", &[path]) => Ok(Command::Open(path.to_owned())),
+        ("open", _) => usage("<path>"),
+        ("merge", &[path]) => Ok(Command::Merge(path.to_owned())),
+        ("merge", _) => usage("<path>"),
+        ("export", &[format, path]) => {
+            let format = match format {
+                "md" => ExportFormat::Markdown,
+                "txt" => ExportFormat::Text,
+                "html" => ExportFormat::Html,
+                "ics" => ExportFormat::Ical,
+                "csv" => ExportFormat::Csv,
+                other => return Err(format!("unknown export format: {}", other)),
+            };
+            Ok(Command::Export(format, path.to_owned()))
+        }
+        ("export", _) => usage("<md|txt|html|ics|csv> <path>"),
+        ("set", &[setting]) => {
+            // like vim, `set nofoo` turns foo off
+            let (name, on) = if setting.starts_with("no") && setting != "no" {
+                (&setting[2..], false)
+            } else {
+                (setting, true)
+            };
+            let setting = match name {
+                "hidestricken" => Setting::HideStricken,
+                "autoarrange" => Setting::AutoArrange,
+                "logs" => Setting::Logs,
+                "modal" => Setting::Modal,
+                "numbers" => Setting::Numbering,
+                _ => return Err(format!("unknown setting: {}", name)),
+            };
+            Ok(Command::Set(setting, on))
+        }
+        ("set", _) => usage("[no]<hidestricken|autoarrange|logs|modal|numbers>"),
+        ("loglevel", &[level]) => {
+            level
+                .parse()
+                .map(Command::LogLevel)
+                .map_err(|_| format!("unknown log level: {}", level))
+        }
+        ("loglevel", _) => usage("<off|error|warn|info|debug|trace>"),
+        ("debug", &[]) => Ok(Command::Action(Action::ToggleDebug)),
+        ("perf", &[]) => Ok(Command::Perf),
+        ("stale", &[]) => Ok(Command::Stale),
+        // a new anchor at screen coords, for terminals without a mouse
+        ("anchor", &[x, y]) => {
+            match (x.parse(), y.parse()) {
+                (Ok(x), Ok(y)) => Ok(Command::Action(Action::NewAnchor(x, y))),
+                _ => usage("<column> <row>"),
+            }
+        }
+        ("anchor", _) => usage("<column> <row>"),
+        ("archive", &[]) => Ok(Command::Archive(None)),
+        ("archive", &[days]) => {
+            days.parse()
+                .map(|days| Command::Archive(Some(days)))
+                .map_err(|_| format!("not a number of days: {}", days))
+        }
+        ("archive", _) => usage("[days]"),
+        ("sort", _) | ("sort!", _) if args.len() <= 2 => {
+            let by = match args.first().cloned().unwrap_or("name") {
+                "name" => SortBy::Content,
+                "created" => SortBy::Created,
+                "due" => SortBy::Due,
+                "done" => SortBy::StrickenLast,
+                other => return Err(format!("unknown sort order: {}", other)),
+            };
+            let reverse = match args.get(1) {
+                None => false,
+                Some(&"reverse") => true,
+                Some(_) => return usage("[name|created|due|done] [reverse]"),
+            };
+            Ok(Command::Sort {
+                by,
+                reverse,
+                recursive: name == "sort!",
+            })
+        }
+        ("sort", _) | ("sort!", _) => usage("[name|created|due|done] [reverse]"),
+        ("highlight", _) if args.len() > 1 && args[0].starts_with('#') && args[0].len() > 1 => {
+            let tag = args[0][1..].to_owned();
+            if args[1..] == ["none"] {
+                return Ok(Command::Highlight(tag, None));
+            }
+            Highlight::parse(&args[1..].join(" ")).map(|h| Command::Highlight(tag, Some(h)))
+        }
+        ("highlight", _) => usage("#<tag> <color and bold|italic|underline|invert, or none>"),
+        (_, &[]) => {
+            str_to_action(name)
+                .map(Command::Action)
+                .ok_or_else(|| format!("unknown command: {}", name))
+        }
+        _ => Err(format!("unknown command: {}", name)),
+    }
+}
+
+// ways to finish the command line, each one a whole line. `:open` offers
+// the recent files too.
+pub fn complete(line: &str, recent: &[String]) -> Vec<String> {
+    let words: Vec<&str> = line.split(' ').collect();
+    let last = words[words.len() - 1];
+    let before = &line[..line.len() - last.len()];
+    let candidates: Vec<String> = match (words[0], words.len()) {
+        (_, 1) => {
+            COMMANDS
+                .iter()
+                .cloned()
+                .chain(ACTIONS.iter().map(|&(name, _)| name))
+                .filter(|c| c.starts_with(last))
+                .map(|c| c.to_owned())
+                .collect()
+        }
+        ("export", 2) => words_starting_with(EXPORT_FORMATS, last),
+        ("set", 2) => {
+            let mut settings = words_starting_with(SETTINGS, last);
+            let negated: Vec<String> = SETTINGS.iter().map(|s| format!("no{}", s)).collect();
+            let negated: Vec<&str> = negated.iter().map(|s| s.as_str()).collect();
+            settings.extend(words_starting_with(&negated, last));
+            settings
+        }
+        ("loglevel", 2) => {
+            words_starting_with(&["off", "error", "warn", "info", "debug", "trace"], last)
+        }
+        ("sort", 2) | ("sort!", 2) => words_starting_with(SORT_ORDERS, last),
+        ("sort", 3) | ("sort!", 3) => words_starting_with(&["reverse"], last),
+        ("open", 2) => {
+            let mut paths = complete_path(last);
+            paths.extend(recent.iter().filter(|f| f.starts_with(last)).cloned());
+            paths
+        }
+        ("w", 2) | ("merge", 2) | ("export", 3) => complete_path(last),
+        _ => vec![],
+    };
+    let mut completions: Vec<String> =
+        candidates.into_iter().map(|c| format!("{}{}", before, c)).collect();
+    completions.sort();
+    completions.dedup();
+    completions
+}
+
+fn words_starting_with(words: &[&str], prefix: &str) -> Vec<String> {
+    words.iter().filter(|w| w.starts_with(prefix)).map(|w| (*w).to_owned()).collect()
+}
+
+// files next to what's been typed so far, with directories ending in `/`
+pub fn complete_path(partial: &str) -> Vec<String> {
+    let (dir, prefix) = match partial.rfind('/') {
+        Some(idx) => (&partial[..idx + 1], &partial[idx + 1..]),
+        None => ("", partial),
+    };
+    let entries = match fs::read_dir(if dir.is_empty() { "." } else { dir }) {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !name.starts_with(prefix) || (name.starts_with('.') && !prefix.starts_with('.')) {
+                return None;
+            }
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            let slash = if is_dir { "/" } else { "" };
+            Some(format!("{}{}{}", dir, name, slash))
+        })
+        .collect()
+}
+
+// the longest start shared by every candidate
+pub fn common_prefix(candidates: &[String]) -> String {
+    let mut prefix = match candidates.first() {
+        Some(first) => first.clone(),
+        None => return String::new(),
+    };
+    for candidate in &candidates[1..] {
+        while !candidate.starts_with(&prefix) {
+            prefix.pop();
+        }
+    }
+    prefix
+}
+
+// paths typed on the command line may start from the home directory
+pub fn expand_home(path: &str) -> String {
+    match (path.starts_with("~/"), env::home_dir()) {
+        (true, Some(home)) => home.join(&path[2..]).to_string_lossy().into_owned(),
+        _ => path.to_owned(),
+    }
+}
+
+#[test]
+fn test_parse() {
+    let cases = vec![
+        ("w", Ok(Command::Write(None))),
+        ("w  copy.db", Ok(Command::Write(Some("copy.db".to_owned())))),
+        ("q", Ok(Command::Quit { force: false })),
+        ("q!", Ok(Command::Quit { force: true })),
+        ("open ~/reading.db", Ok(Command::Open("~/reading.db".to_owned()))),
+        ("merge laptop.db", Ok(Command::Merge("laptop.db".to_owned()))),
+        ("export md out.md", Ok(Command::Export(ExportFormat::Markdown, "out.md".to_owned()))),
+        ("export ics", Err("usage: :export <md|txt|html|ics|csv> <path>".to_owned())),
+        ("export pdf out.pdf", Err("unknown export format: pdf".to_owned())),
+        ("set hidestricken", Ok(Command::Set(Setting::HideStricken, true))),
+        ("set nologs", Ok(Command::Set(Setting::Logs, false))),
+        ("set nonumbers", Ok(Command::Set(Setting::Numbering, false))),
+        ("set colors", Err("unknown setting: colors".to_owned())),
+        ("loglevel debug", Ok(Command::LogLevel(LogLevelFilter::Debug))),
+        ("loglevel loud", Err("unknown log level: loud".to_owned())),
+        ("toggle_collapsed", Ok(Command::Action(Action::ToggleCollapsed))),
+        ("toggle_collapsed now", Err("unknown command: toggle_collapsed".to_owned())),
+        ("sort", Ok(Command::Sort { by: SortBy::Content, reverse: false, recursive: false })),
+        ("sort! due reverse",
+         Ok(Command::Sort { by: SortBy::Due, reverse: true, recursive: true })),
+        ("sort size", Err("unknown sort order: size".to_owned())),
+        ("sort due up", Err("usage: :sort [name|created|due|done] [reverse]".to_owned())),
+        ("anchor 12 5", Ok(Command::Action(Action::NewAnchor(12, 5)))),
+        ("anchor here", Err("usage: :anchor <column> <row>".to_owned())),
+        ("archive 90", Ok(Command::Archive(Some(90)))),
+        ("archive soon", Err("not a number of days: soon".to_owned())),
+        ("highlight #urgent none", Ok(Command::Highlight("urgent".to_owned(), None))),
+        ("highlight #urgent red  bold",
+         Ok(Command::Highlight("urgent".to_owned(), Highlight::parse("red bold").ok()))),
+        ("highlight #urgent reddish", Err("unknown color: reddish".to_owned())),
+        ("highlight urgent red",
+         Err("usage: :highlight #<tag> <color and bold|italic|underline|invert, or none>"
+             .to_owned())),
+        ("perf", Ok(Command::Perf)),
+        ("stale", Ok(Command::Stale)),
+        ("substitute /Atlas/Project Aurora/",
+         Ok(Command::Substitute(Substitution {
+             pattern: "Atlas".to_owned(),
+             replacement: "Project Aurora".to_owned(),
+         }))),
+        ("s /a\\/b/ c/",
+         Ok(Command::Substitute(Substitution {
+             pattern: "a/b".to_owned(),
+             replacement: " c".to_owned(),
+         }))),
+        ("substitute", Err("usage: :substitute /pattern/replacement/".to_owned())),
+        ("wq", Err("unknown command: wq".to_owned())),
+        ("  ", Err("no command given".to_owned())),
+    ];
+    for (line, expected) in cases {
+        assert_eq!(parse(line), expected, "parsing {:?}", line);
+    }
+}
+
+#[test]
+fn test_complete() {
+    let cases: Vec<(&str, Vec<&str>)> = vec![
+        ("lo", vec!["load_theirs", "loglevel", "lower_selected"]),
+        ("exp", vec!["export", "export_html", "export_ical"]),
+        ("export h", vec!["export html"]),
+        ("set no",
+         vec!["set noautoarrange", "set nohidestricken", "set nologs", "set nomodal",
+              "set nonumbers"]),
+        ("loglevel d", vec!["loglevel debug"]),
+        ("q x", vec![]),
+    ];
+    for (line, expected) in cases {
+        assert_eq!(complete(line, &[]), expected, "completing {:?}", line);
+    }
+
+    assert_eq!(common_prefix(&complete("exp", &[])), "export");
+    assert_eq!(common_prefix(&complete("zzz", &[])), "");
+
+    let recent = vec!["/maps/work.db".to_owned(), "/maps/home.db".to_owned()];
+    assert_eq!(complete("open /maps/w", &recent), vec!["open /maps/work.db"]);
+    assert_eq!(common_prefix(&complete("open /ma", &recent)), "open /maps/");
+    assert_eq!(complete("merge /maps/w", &recent), Vec::<String>::new());
+}
+
+#[test]
+fn test_complete_path() {
+    use std::fs::{File, create_dir_all, remove_dir_all};
+
+    use libc::getpid;
+
+    let dir = env::temp_dir().join(format!("void-path-test-{}", unsafe { getpid() }));
+    create_dir_all(dir.join("nested/deeper")).unwrap();
+    for file in &["notes.txt", "notes-old.txt", ".hidden", "nested/plan.md"] {
+        File::create(dir.join(file)).unwrap();
+    }
+    let dir = dir.to_str().unwrap().to_owned();
+    let complete = |partial: &str| {
+        let mut paths = complete_path(&format!("{}/{}", dir, partial));
+        paths.sort();
+        paths.into_iter().map(|p| p[dir.len() + 1..].to_owned()).collect::<Vec<_>>()
+    };
+
+    assert_eq!(complete("no"), vec!["notes-old.txt", "notes.txt"]);
+    assert_eq!(complete("ne"), vec!["nested/"]);
+    assert_eq!(complete("nested/"), vec!["nested/deeper/", "nested/plan.md"]);
+    assert_eq!(complete("nested/p"), vec!["nested/plan.md"]);
+    // dotfiles only when a dot's been typed
+    assert_eq!(complete(""), vec!["nested/", "notes-old.txt", "notes.txt"]);
+    assert_eq!(complete("."), vec![".hidden"]);
+    assert_eq!(complete("missing/x"), Vec::<String>::new());
+    assert_eq!(common_prefix(&complete_path(&format!("{}/no", dir))), format!("{}/notes", dir));
+    remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,107 @@
+use {Coords, NodeID, TagDB};
+
+// no more are listed than this
+const MAX_ITEMS: usize = 8;
+
+// existing tags that could finish the `#tag` being typed at the end of a
+// node, listed below it
+#[derive(Debug, Clone)]
+pub struct Completion {
+    pub node: NodeID,
+    // where the `#` is in the node's content, in bytes
+    pub start: usize,
+    pub items: Vec<String>,
+    pub cursor: usize,
+}
+
+impl Completion {
+    // None unless content ends in a tag that some other node has a way to
+    // finish
+    pub fn new(node: NodeID, content: &str, tag_db: &TagDB) -> Option<Completion> {
+        let start = partial_tag(content)?;
+        let mut items = tag_db.complete(&content[start + 1..], node);
+        items.truncate(MAX_ITEMS);
+        if items.is_empty() {
+            return None;
+        }
+        Some(Completion {
+            node,
+            start,
+            items,
+            cursor: 0,
+        })
+    }
+
+    pub fn up(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn down(&mut self) {
+        if self.cursor + 1 < self.items.len() {
+            self.cursor += 1;
+        }
+    }
+
+    // content with the highlighted tag in place of the partial one, and
+    // whatever came after it left alone
+    pub fn accept(&self, content: &str) -> String {
+        // it changed some other way since
+        if !content.is_char_boundary(self.start) || !content[self.start..].starts_with('#') {
+            return content.to_owned();
+        }
+        let rest = &content[self.start + 1..];
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        format!("{}#{}{}", &content[..self.start], self.items[self.cursor], &rest[end..])
+    }
+
+    // top left corner and size of the box, bordered, kept on a screen of
+    // the given dims. it opens just below at if there's room, else above.
+    pub fn rect(&self, at: Coords, (width, height): Coords) -> (Coords, Coords) {
+        let inner = self.items.iter().map(|tag| tag.chars().count() + 1).max().unwrap_or(0);
+        let w = inner as u16 + 4;
+        let h = self.items.len() as u16 + 2;
+        let x = if at.0 + w <= width + 1 {
+            at.0
+        } else {
+            (width + 1).saturating_sub(w)
+        };
+        let y = if at.1 + h < height + 1 {
+            at.1 + 1
+        } else {
+            at.1.saturating_sub(h)
+        };
+        ((x.max(1), y.max(1)), (w, h))
+    }
+}
+
+// where the tag being typed at the end of content starts, if it is a tag:
+// a `#` at the start or after a space, with no space since
+fn partial_tag(content: &str) -> Option<usize> {
+    let start = content.rfind('#')?;
+    let before = &content[..start];
+    let after = &content[start + 1..];
+    if (before.is_empty() || before.ends_with(char::is_whitespace)) &&
+       !after.contains(char::is_whitespace) {
+        Some(start)
+    } else {
+        None
+    }
+}
+
+#[test]
+fn test_partial_tag() {
+    assert_eq!(partial_tag("#"), Some(0));
+    assert_eq!(partial_tag("café #tä"), Some(6));
+    assert_eq!(partial_tag("café #tä "), None);
+    assert_eq!(partial_tag("issue#12"), None);
+    assert_eq!(partial_tag("no tags"), None);
+
+    let completion = Completion {
+        node: 1,
+        start: 6,
+        items: vec!["täglich".to_owned()],
+        cursor: 0,
+    };
+    assert_eq!(completion.accept("café #tä"), "café #täglich");
+    assert_eq!(completion.accept("café #tä → später"), "café #täglich → später");
+}
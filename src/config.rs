@@ -1,18 +1,33 @@
 use std::fmt;
 use std::env;
-use std::fs::File;
+use std::fs::{self, File};
 use std::collections::HashMap;
 use std::io::{self, Error, ErrorKind, Read};
+use std::time::Duration;
 
 use regex::Regex;
 use termion::event::{Event, Key, MouseEvent};
 
+use Coords;
+use colors::Highlight;
+use hooks::Hook;
+use termsize;
+
+// termion has no keys with modifiers besides Ctrl and Alt letters, so these
+// arrive unparsed
+const CTRL_PAGE_UP: &[u8] = b"\x1b[5;5~";
+const CTRL_PAGE_DOWN: &[u8] = b"\x1b[6;5~";
 
 #[derive(Debug, Copy, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
 pub enum Action {
     LeftClick(u16, u16),
     RightClick(u16, u16),
+    MiddleClick(u16, u16),
+    // an empty anchor at screen coords, from a double click or the menu
+    NewAnchor(u16, u16),
     Release(u16, u16),
+    // the mouse moved with a button down, on terminals that report it
+    Drag(u16, u16),
     Char(char),
     UnselectRet,
     ScrollUp,
@@ -23,9 +38,13 @@ pub enum Action {
     SelectLeft,
     SelectRight,
     EraseChar,
+    InsertNewline,
     CreateSibling,
+    InsertSibling,
     CreateChild,
     CreateFreeNode,
+    // an empty anchor in the free space nearest the selection
+    PlaceAnchor,
     ExecSelected,
     DrillDown,
     PopUp,
@@ -35,6 +54,8 @@ pub enum Action {
     Arrow,
     AutoArrange,
     ToggleCollapsed,
+    // a new random color for the selected node
+    Recolor,
     Quit,
     Save,
     ToggleShowLogs,
@@ -44,45 +65,160 @@ pub enum Action {
     RaiseSelected,
     LowerSelected,
     Search,
+    // act on everything the last search found
+    SearchMatches,
+    // act on every child of the selected node at once
+    SelectChildren,
+    // tags the selected node, or every match or selected child at once
+    AddTag,
     UndoDelete,
+    ShowTrash,
+    ShowArchive,
+    HistoryBack,
+    HistoryForward,
+    SetBookmark,
+    JumpToBookmark(u8),
+    FocusMode,
+    ToggleTimer,
+    Pomodoro,
+    CancelPomodoro,
+    DismissNotices,
+    ToggleQueue,
+    ToggleBoard,
+    ToggleAgenda,
+    ToggleStale,
+    PickNext,
+    ToggleHeatmap,
+    ToggleCounts,
+    ToggleAges,
+    ToggleMinimap,
+    ToggleDebug,
+    SetTitle,
+    // in or out of a named group of anchors that are dragged together
+    ToggleGroup,
+    // an anchor drawn as its one line, with a count of what's under it
+    ToggleIconified,
+    ToggleGpsMap,
+    EditDetails,
+    CopySubtree,
+    OpenUrl,
+    AddAttachment,
+    OpenAttachment,
+    ExportIcal,
+    ExportHtml,
+    NewTab,
+    NextTab,
+    PrevTab,
+    RecordMacro,
+    ReplayMacro,
+    InsertMode,
+    NormalMode,
+    // deletes on the second press in a row, like vim's dd
+    DeleteTwice,
+    // the next key works on split panes, like vim's C-w
+    Window,
+    // when the work file changed on disk under unsaved changes: load it,
+    // dropping them, or keep them to overwrite it on the next save
+    LoadTheirs,
+    KeepMine,
     Help,
 }
 
-fn str_to_action(input: String) -> Option<Action> {
-    match &*input {
-        "unselect" => Some(Action::UnselectRet),
-        "scroll_up" => Some(Action::ScrollUp),
-        "scroll_down" => Some(Action::ScrollDown),
-        "delete" => Some(Action::DeleteSelected),
-        "select_up" => Some(Action::SelectUp),
-        "select_down" => Some(Action::SelectDown),
-        "select_left" => Some(Action::SelectLeft),
-        "select_right" => Some(Action::SelectRight),
-        "erase" => Some(Action::EraseChar),
-        "create_sibling" => Some(Action::CreateSibling),
-        "create_child" => Some(Action::CreateChild),
-        "create_free_node" => Some(Action::CreateFreeNode),
-        "execute" => Some(Action::ExecSelected),
-        "drill_down" => Some(Action::DrillDown),
-        "pop_up" => Some(Action::PopUp),
-        "jump" => Some(Action::PrefixJump),
-        "toggle_completed" => Some(Action::ToggleCompleted),
-        "toggle_hide_completed" => Some(Action::ToggleHideCompleted),
-        "arrow" => Some(Action::Arrow),
-        "auto_arrange" => Some(Action::AutoArrange),
-        "toggle_collapsed" => Some(Action::ToggleCollapsed),
-        "quit" => Some(Action::Quit),
-        "save" => Some(Action::Save),
-        "toggle_show_logs" => Some(Action::ToggleShowLogs),
-        "enter_command" => Some(Action::EnterCmd),
-        "find_task" => Some(Action::FindTask),
-        "yank_paste_node" => Some(Action::YankPasteNode),
-        "raise_selected" => Some(Action::RaiseSelected),
-        "lower_selected" => Some(Action::LowerSelected),
-        "search" => Some(Action::Search),
-        "help" => Some(Action::Help),
-        _ => None,
-    }
+// with modal editing, keys in normal mode go to `Config.normal` first and
+// only insert mode types into nodes
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Mode {
+    Normal,
+    Insert,
+}
+
+// names for actions in key files and on the command line
+pub const ACTIONS: &[(&str, Action)] = &[
+    ("unselect", Action::UnselectRet),
+    ("scroll_up", Action::ScrollUp),
+    ("scroll_down", Action::ScrollDown),
+    ("delete", Action::DeleteSelected),
+    ("select_up", Action::SelectUp),
+    ("select_down", Action::SelectDown),
+    ("select_left", Action::SelectLeft),
+    ("select_right", Action::SelectRight),
+    ("erase", Action::EraseChar),
+    ("insert_newline", Action::InsertNewline),
+    ("create_sibling", Action::CreateSibling),
+    ("insert_sibling", Action::InsertSibling),
+    ("create_child", Action::CreateChild),
+    ("create_free_node", Action::CreateFreeNode),
+    ("place_anchor", Action::PlaceAnchor),
+    ("execute", Action::ExecSelected),
+    ("drill_down", Action::DrillDown),
+    ("pop_up", Action::PopUp),
+    ("jump", Action::PrefixJump),
+    ("toggle_completed", Action::ToggleCompleted),
+    ("toggle_hide_completed", Action::ToggleHideCompleted),
+    ("arrow", Action::Arrow),
+    ("auto_arrange", Action::AutoArrange),
+    ("toggle_collapsed", Action::ToggleCollapsed),
+    ("recolor", Action::Recolor),
+    ("quit", Action::Quit),
+    ("save", Action::Save),
+    ("toggle_show_logs", Action::ToggleShowLogs),
+    ("enter_command", Action::EnterCmd),
+    ("find_task", Action::FindTask),
+    ("yank_paste_node", Action::YankPasteNode),
+    ("raise_selected", Action::RaiseSelected),
+    ("lower_selected", Action::LowerSelected),
+    ("search", Action::Search),
+    ("search_matches", Action::SearchMatches),
+    ("select_children", Action::SelectChildren),
+    ("add_tag", Action::AddTag),
+    ("undo_delete", Action::UndoDelete),
+    ("show_trash", Action::ShowTrash),
+    ("show_archive", Action::ShowArchive),
+    ("history_back", Action::HistoryBack),
+    ("history_forward", Action::HistoryForward),
+    ("set_bookmark", Action::SetBookmark),
+    ("focus_mode", Action::FocusMode),
+    ("toggle_timer", Action::ToggleTimer),
+    ("pomodoro", Action::Pomodoro),
+    ("cancel_pomodoro", Action::CancelPomodoro),
+    ("dismiss_notices", Action::DismissNotices),
+    ("toggle_queue", Action::ToggleQueue),
+    ("toggle_board", Action::ToggleBoard),
+    ("toggle_agenda", Action::ToggleAgenda),
+    ("toggle_stale", Action::ToggleStale),
+    ("pick_next", Action::PickNext),
+    ("toggle_heatmap", Action::ToggleHeatmap),
+    ("toggle_counts", Action::ToggleCounts),
+    ("toggle_ages", Action::ToggleAges),
+    ("toggle_minimap", Action::ToggleMinimap),
+    ("toggle_debug", Action::ToggleDebug),
+    ("set_title", Action::SetTitle),
+    ("toggle_group", Action::ToggleGroup),
+    ("toggle_iconified", Action::ToggleIconified),
+    ("toggle_gps_map", Action::ToggleGpsMap),
+    ("edit_details", Action::EditDetails),
+    ("copy_subtree", Action::CopySubtree),
+    ("open_url", Action::OpenUrl),
+    ("add_attachment", Action::AddAttachment),
+    ("open_attachment", Action::OpenAttachment),
+    ("export_ical", Action::ExportIcal),
+    ("export_html", Action::ExportHtml),
+    ("new_tab", Action::NewTab),
+    ("next_tab", Action::NextTab),
+    ("prev_tab", Action::PrevTab),
+    ("record_macro", Action::RecordMacro),
+    ("replay_macro", Action::ReplayMacro),
+    ("insert_mode", Action::InsertMode),
+    ("normal_mode", Action::NormalMode),
+    ("delete_twice", Action::DeleteTwice),
+    ("window", Action::Window),
+    ("load_theirs", Action::LoadTheirs),
+    ("keep_mine", Action::KeepMine),
+    ("help", Action::Help),
+];
+
+pub fn str_to_action(input: &str) -> Option<Action> {
+    ACTIONS.iter().find(|&&(name, _)| name == input).map(|&(_, action)| action)
 }
 
 fn str_to_key(input: String) -> Option<Key> {
@@ -90,6 +226,7 @@ fn str_to_key(input: String) -> Option<Key> {
 
     lazy_static! {
         static ref RE: Regex = Regex::new(r"C-(.)").unwrap();
+        static ref RE_ALT: Regex = Regex::new(r"M-(.)").unwrap();
     }
 
     match &*input {
@@ -103,20 +240,87 @@ fn str_to_key(input: String) -> Option<Key> {
         "right" => Some(Right),
         "backspace" => Some(Backspace),
         "enter" => Some(Char('\n')),
+        "M-enter" => Some(Alt('\r')),
         "tab" => Some(Char('\t')),
+        single if single.chars().count() == 1 => single.chars().next().map(Char),
         other => {
             RE.captures_iter(other)
                 .nth(0)
                 .and_then(|n| n.at(1))
                 .and_then(|r| r.chars().nth(0))
                 .map(|c| Ctrl(c))
+                .or_else(|| {
+                    RE_ALT.captures_iter(other)
+                        .next()
+                        .and_then(|n| n.at(1))
+                        .and_then(|r| r.chars().next())
+                        .map(Alt)
+                })
         }
     }
 }
 
+// a key the way key files write it
+pub fn key_to_str(key: Key) -> String {
+    use termion::event::Key::*;
+
+    match key {
+        Esc => "esc".to_owned(),
+        PageUp => "pgup".to_owned(),
+        PageDown => "pgdn".to_owned(),
+        Delete => "del".to_owned(),
+        Up => "up".to_owned(),
+        Down => "down".to_owned(),
+        Left => "left".to_owned(),
+        Right => "right".to_owned(),
+        Backspace => "backspace".to_owned(),
+        Char('\n') => "enter".to_owned(),
+        Alt('\r') => "M-enter".to_owned(),
+        Char('\t') => "tab".to_owned(),
+        Char(c) => c.to_string(),
+        Ctrl(c) => format!("C-{}", c),
+        Alt(c) => format!("M-{}", c),
+        other => format!("{:?}", other),
+    }
+}
+
+// what a parent's progress bar counts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Progress {
+    Children,
+    Descendants,
+}
+
 #[derive(Debug, Clone)]
 pub struct Config {
     config: HashMap<Key, Action>,
+    normal: HashMap<Key, Action>,
+    pub modal: bool,
+    // completed nodes are dimmed instead on terminals that can't do this
+    pub strikethrough: bool,
+    // outline numbers like 1.2.3 before each node, and in exports
+    pub numbering: bool,
+    // completed nodes hidden by hide_stricken are numbered anyway
+    pub number_hidden: bool,
+    // a bar after each parent of how much under it is done, if any
+    pub progress: Option<Progress>,
+    // how nodes with each tag are drawn, the first rule that matches winning
+    pub highlights: Vec<(String, Highlight)>,
+    // the tags of the board's columns, left to right
+    pub board: Vec<String>,
+    // the work file opened when none is named
+    pub file: Option<String>,
+    // drawn at when the terminal can't say how big it is
+    pub fallback_size: Coords,
+    // commands run with the work file's path around opening, saving and
+    // quitting, like `on_save: git -C ~/notes commit -qam void`
+    pub on_open: Option<String>,
+    pub on_save: Option<String>,
+    pub on_exit: Option<String>,
+    // how long each of them gets before being killed
+    pub hook_timeout: Duration,
+    // the key file this came from, which new highlights are saved into
+    path: Option<String>,
 }
 
 impl Default for Config {
@@ -133,19 +337,23 @@ impl Default for Config {
                 (Left, Action::SelectLeft),
                 (Right, Action::SelectRight),
                 (Backspace, Action::EraseChar),
+                (Alt('j'), Action::InsertNewline),
                 (F(1), Action::PrefixJump),
                 (Char('\n'), Action::CreateSibling),
+                (Alt('\r'), Action::InsertSibling),
                 (Char('\t'), Action::CreateChild),
                 (Ctrl('n'), Action::CreateFreeNode),
+                (Alt('N'), Action::PlaceAnchor),
                 (Ctrl('k'), Action::ExecSelected),
-                (Ctrl('w'), Action::DrillDown),
+                (Alt('z'), Action::DrillDown),
+                (Ctrl('w'), Action::Window),
                 (Ctrl('q'), Action::PopUp),
                 (Ctrl('f'), Action::PrefixJump),
                 (Ctrl('a'), Action::ToggleCompleted),
                 (Ctrl('h'), Action::ToggleHideCompleted),
                 (Ctrl('r'), Action::Arrow),
                 (Ctrl('p'), Action::AutoArrange),
-                (Ctrl('t'), Action::ToggleCollapsed),
+                (Ctrl('s'), Action::ToggleCollapsed),
                 (Ctrl('c'), Action::Quit),
                 (Ctrl('x'), Action::Save),
                 (Ctrl('l'), Action::ToggleShowLogs),
@@ -155,11 +363,79 @@ impl Default for Config {
                 (Ctrl('g'), Action::RaiseSelected),
                 (Ctrl('d'), Action::LowerSelected),
                 (Ctrl('u'), Action::Search),
+                (Alt('/'), Action::SearchMatches),
+                (Alt('C'), Action::SelectChildren),
+                (Alt('#'), Action::AddTag),
                 (Ctrl('z'), Action::UndoDelete),
+                (Alt('t'), Action::ShowTrash),
+                (Alt('a'), Action::ShowArchive),
+                (Ctrl('o'), Action::HistoryBack),
+                (Alt('i'), Action::HistoryForward),
+                (Ctrl('b'), Action::SetBookmark),
+                (Alt('f'), Action::FocusMode),
+                (Alt('s'), Action::ToggleTimer),
+                (Alt('p'), Action::Pomodoro),
+                (Alt('P'), Action::CancelPomodoro),
+                (Alt('n'), Action::DismissNotices),
+                (Alt('q'), Action::ToggleQueue),
+                (Alt('b'), Action::ToggleBoard),
+                (Alt('y'), Action::ToggleAgenda),
+                (Alt('u'), Action::ToggleStale),
+                (Alt('r'), Action::PickNext),
+                (Alt('h'), Action::ToggleHeatmap),
+                (Alt('W'), Action::ToggleCounts),
+                (Alt('A'), Action::ToggleAges),
+                (Alt('v'), Action::ToggleMinimap),
+                (Alt('d'), Action::ToggleDebug),
+                (Alt('T'), Action::SetTitle),
+                (Alt('G'), Action::ToggleGroup),
+                (Alt('I'), Action::ToggleIconified),
+                (Alt('g'), Action::ToggleGpsMap),
+                (Alt('D'), Action::EditDetails),
+                (Alt('c'), Action::CopySubtree),
+                (Alt('o'), Action::OpenUrl),
+                (Alt('F'), Action::AddAttachment),
+                (Alt('O'), Action::OpenAttachment),
+                (Alt('e'), Action::ExportIcal),
+                (Alt('w'), Action::ExportHtml),
+                (Ctrl('t'), Action::NewTab),
+                (Alt('m'), Action::RecordMacro),
+                (Alt('@'), Action::ReplayMacro),
+                (Alt('l'), Action::LoadTheirs),
+                (Alt('k'), Action::KeepMine),
                 (Ctrl('?'), Action::Help),
             ]
                 .into_iter()
                 .collect(),
+            normal: vec![
+                (Char('h'), Action::SelectLeft),
+                (Char('j'), Action::SelectDown),
+                (Char('k'), Action::SelectUp),
+                (Char('l'), Action::SelectRight),
+                (Char('x'), Action::ToggleCompleted),
+                (Char('d'), Action::DeleteTwice),
+                (Char('o'), Action::InsertSibling),
+                (Char('i'), Action::InsertMode),
+                (Char('a'), Action::InsertMode),
+                (Char('/'), Action::Search),
+                (Char(':'), Action::EnterCmd),
+            ]
+                .into_iter()
+                .collect(),
+            modal: false,
+            strikethrough: true,
+            numbering: false,
+            number_hidden: false,
+            progress: None,
+            highlights: vec![],
+            board: vec!["todo".to_owned(), "doing".to_owned(), "done".to_owned()],
+            file: None,
+            fallback_size: (80, 24),
+            on_open: None,
+            on_save: None,
+            on_exit: None,
+            hook_timeout: Duration::from_secs(10),
+            path: None,
         }
     }
 }
@@ -170,6 +446,12 @@ impl fmt::Display for Config {
         for (key, action) in &self.config {
             write!(f, "    {:?}: {:?}\n", action, key).unwrap();
         }
+        if self.modal {
+            write!(f, "Normal Mode Hotkeys:\n").unwrap();
+            for (key, action) in &self.normal {
+                write!(f, "    {:?}: {:?}\n", action, key).unwrap();
+            }
+        }
         Ok(())
     }
 }
@@ -185,13 +467,17 @@ impl Config {
 
     pub fn parse_file(p: String) -> io::Result<Config> {
         let mut buf = String::new();
-        let mut f = File::open(p)?;
+        let mut f = File::open(&p)?;
         f.read_to_string(&mut buf)?;
-        let mut config = Config::default();
+        let mut config = Config {
+            path: Some(p),
+            ..Config::default()
+        };
         for (line_number, line) in buf.lines().enumerate() {
             let e = format!("invalid config at line {}: {}", line_number, line);
 
-            let parts: Vec<_> = line.split(":").map(|p| p.trim()).collect();
+            // only the first colon splits, as commands may have more
+            let parts: Vec<_> = line.splitn(2, ':').map(|p| p.trim()).collect();
             if parts.len() != 2 {
                 error!("{}", e);
                 return Err(Error::new(ErrorKind::Other, e));
@@ -199,8 +485,118 @@ impl Config {
 
             let (raw_action, raw_key) = (parts[0], parts[1]);
 
+            // `highlight #tag: red bold`
+            if let Some(tag) = raw_action.strip_prefix("highlight ") {
+                match (tag.trim().strip_prefix('#'), Highlight::parse(raw_key)) {
+                    (Some(tag), Ok(highlight)) if !tag.is_empty() => {
+                        config.set_highlight(tag, Some(highlight));
+                        continue;
+                    }
+                    _ => {
+                        error!("{}", e);
+                        return Err(Error::new(ErrorKind::Other, e));
+                    }
+                }
+            }
+
+            // `board: todo doing done`
+            if raw_action == "board" {
+                let columns: Vec<String> = raw_key.split_whitespace()
+                    .map(|tag| tag.trim_start_matches('#').to_owned())
+                    .filter(|tag| !tag.is_empty())
+                    .collect();
+                if columns.len() < 2 {
+                    error!("{}", e);
+                    return Err(Error::new(ErrorKind::Other, e));
+                }
+                config.board = columns;
+                continue;
+            }
+
+            // `file: ~/notes.db`
+            if raw_action == "file" && !raw_key.is_empty() {
+                config.file = Some(raw_key.to_owned());
+                continue;
+            }
+
+            // `on_save: git -C ~/notes commit -qam void`
+            let hook = match raw_action {
+                "on_open" => Some(&mut config.on_open),
+                "on_save" => Some(&mut config.on_save),
+                "on_exit" => Some(&mut config.on_exit),
+                _ => None,
+            };
+            if let Some(hook) = hook {
+                *hook = Some(raw_key.to_owned()).filter(|command| !command.is_empty());
+                continue;
+            }
+
+            // `hook_timeout: 30`, in seconds
+            if raw_action == "hook_timeout" {
+                match raw_key.parse() {
+                    Ok(secs) => config.hook_timeout = Duration::from_secs(secs),
+                    Err(_) => {
+                        error!("{}", e);
+                        return Err(Error::new(ErrorKind::Other, e));
+                    }
+                }
+                continue;
+            }
+
+            // `progress: children`, `descendants` or `off`
+            if raw_action == "progress" {
+                config.progress = match raw_key {
+                    "children" => Some(Progress::Children),
+                    "descendants" => Some(Progress::Descendants),
+                    "off" => None,
+                    _ => {
+                        error!("{}", e);
+                        return Err(Error::new(ErrorKind::Other, e));
+                    }
+                };
+                continue;
+            }
+
+            // `fallback_size: 100x30`
+            if raw_action == "fallback_size" {
+                match termsize::parse(raw_key) {
+                    Some(size) => config.fallback_size = size,
+                    None => {
+                        error!("{}", e);
+                        return Err(Error::new(ErrorKind::Other, e));
+                    }
+                }
+                continue;
+            }
+
+            let flag = match raw_action {
+                "modal" => Some(&mut config.modal),
+                "strikethrough" => Some(&mut config.strikethrough),
+                "numbering" => Some(&mut config.numbering),
+                "number_hidden" => Some(&mut config.number_hidden),
+                _ => None,
+            };
+            if let Some(flag) = flag {
+                *flag = match raw_key {
+                    "true" => true,
+                    "false" => false,
+                    _ => {
+                        error!("{}", e);
+                        return Err(Error::new(ErrorKind::Other, e));
+                    }
+                };
+                continue;
+            }
+
+            // `normal <action>: <key>` binds a key in normal mode
+            let words: Vec<&str> = raw_action.split_whitespace().collect();
+            let (normal, raw_action) = match words.as_slice() {
+                &["normal", action] => (true, action),
+                _ => (false, raw_action),
+            };
+
             let key_opt = str_to_key(raw_key.to_owned());
-            let action_opt = str_to_action(raw_action.to_owned());
+            let action_opt = str_to_action(raw_action);
 
             if key_opt.is_none() || action_opt.is_none() {
                 error!("{}", e);
@@ -210,12 +606,86 @@ impl Config {
             let key = key_opt.unwrap();
             let action = action_opt.unwrap();
 
-            config.config.insert(key, action);
+            if normal {
+                config.normal.insert(key, action);
+            } else {
+                config.config.insert(key, action);
+            }
         }
 
         Ok(config)
     }
 
+    pub fn key_file(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    // a new rule for tag goes after the others, and None removes it
+    pub fn set_highlight(&mut self, tag: &str, highlight: Option<Highlight>) {
+        let existing = self.highlights.iter().position(|rule| rule.0 == tag);
+        match (existing, highlight) {
+            (Some(idx), Some(highlight)) => self.highlights[idx].1 = highlight,
+            (Some(idx), None) => {
+                self.highlights.remove(idx);
+            }
+            (None, Some(highlight)) => self.highlights.push((tag.to_owned(), highlight)),
+            (None, None) => {}
+        }
+    }
+
+    pub fn highlight(&self, tags: &[String]) -> Option<&Highlight> {
+        self.highlights.iter().find(|rule| tags.contains(&rule.0)).map(|rule| &rule.1)
+    }
+
+    // rewrite the key file with the current highlight rules in place of the
+    // ones it had, leaving the key bindings alone
+    pub fn save_highlights(&self) -> io::Result<()> {
+        let path = match self.path {
+            Some(ref path) => path,
+            None => return Ok(()),
+        };
+        let mut buf = String::new();
+        File::open(path)?.read_to_string(&mut buf)?;
+        let mut lines: Vec<String> = buf.lines()
+            .filter(|line| !line.trim_start().starts_with("highlight "))
+            .map(|line| line.to_owned())
+            .collect();
+        for rule in &self.highlights {
+            lines.push(format!("highlight #{}: {}", rule.0, rule.1.spec));
+        }
+        fs::write(path, lines.join("\n") + "\n")
+    }
+
+    pub fn hook(&self, hook: Hook) -> Option<&str> {
+        let command = match hook {
+            Hook::Open => &self.on_open,
+            Hook::Save => &self.on_save,
+            Hook::Exit => &self.on_exit,
+        };
+        command.as_ref().map(String::as_str)
+    }
+
+    // a key that does action, for telling people which one to press
+    pub fn key_for(&self, action: Action, mode: Mode) -> Option<String> {
+        let bound = |keys: &HashMap<Key, Action>| {
+            keys.iter().filter(|&(_, &a)| a == action).map(|(&key, _)| key_to_str(key)).min()
+        };
+        match mode {
+            Mode::Normal => bound(&self.normal).or_else(|| bound(&self.config)),
+            Mode::Insert => bound(&self.config),
+        }
+    }
+
+    pub fn map_in(&self, e: Event, mode: Mode) -> Option<Action> {
+        match (mode, e) {
+            (Mode::Normal, Event::Key(key)) if self.normal.contains_key(&key) => {
+                self.normal.get(&key).cloned()
+            }
+            (Mode::Insert, Event::Key(Key::Esc)) if self.modal => Some(Action::NormalMode),
+            (_, e) => self.map(e),
+        }
+    }
+
     pub fn map(&self, e: Event) -> Option<Action> {
         use termion::event::Key::*;
         use termion::event::MouseButton;
@@ -230,9 +700,15 @@ impl Config {
             Event::Mouse(MouseEvent::Press(MouseButton::Right, x, y)) => {
                 Some(Action::RightClick(x, y))
             }
+            Event::Mouse(MouseEvent::Press(MouseButton::Middle, x, y)) => {
+                Some(Action::MiddleClick(x, y))
+            }
             Event::Mouse(MouseEvent::Press(_, x, y)) => Some(Action::LeftClick(x, y)),
             Event::Mouse(MouseEvent::Release(x, y)) => Some(Action::Release(x, y)),
-            Event::Mouse(MouseEvent::Hold(_, _)) => None,
+            Event::Mouse(MouseEvent::Hold(x, y)) => Some(Action::Drag(x, y)),
+            Event::Key(Alt(c)) if c.is_ascii_digit() && !self.config.contains_key(&Alt(c)) => {
+                c.to_digit(10).map(|slot| Action::JumpToBookmark(slot as u8))
+            }
             Event::Key(other) => {
                 let lookup = self.config.get(&other).cloned();
                 if lookup.is_none() {
@@ -240,6 +716,12 @@ impl Config {
                 }
                 lookup
             }
+            Event::Unsupported(ref bytes) if bytes.as_slice() == CTRL_PAGE_UP => {
+                Some(Action::PrevTab)
+            }
+            Event::Unsupported(ref bytes) if bytes.as_slice() == CTRL_PAGE_DOWN => {
+                Some(Action::NextTab)
+            }
             other => {
                 warn!("Unknown event received: {:?}", other);
                 None
@@ -247,3 +729,84 @@ impl Config {
         }
     }
 }
+
+#[test]
+fn test_key_for() {
+    let mut config = Config::default();
+    assert_eq!(config.key_for(Action::Save, Mode::Insert), Some("C-x".to_owned()));
+    assert_eq!(config.key_for(Action::ToggleCompleted, Mode::Insert), Some("C-a".to_owned()));
+    assert_eq!(config.key_for(Action::ToggleCompleted, Mode::Normal), Some("x".to_owned()));
+    config.config.retain(|_, &mut action| action != Action::AddTag);
+    assert_eq!(config.key_for(Action::AddTag, Mode::Insert), None);
+    for name in &["esc", "del", "enter", "M-enter", "tab", "x", "C-x", "M-#"] {
+        assert_eq!(str_to_key(name.to_string()).map(key_to_str), Some(name.to_string()));
+    }
+}
+
+#[test]
+fn test_highlights() {
+    use std::fs::remove_file;
+
+    use libc::getpid;
+
+    let path = env::temp_dir().join(format!("void-keys-test-{}", unsafe { getpid() }));
+    let path = path.to_str().unwrap().to_owned();
+    fs::write(&path, "quit:C-q\nhighlight #urgent: red bold\nhighlight #later: blue\n").unwrap();
+    let mut config = Config::parse_file(path.clone()).unwrap();
+    let tags = |tags: &[&str]| tags.iter().map(|t| (*t).to_owned()).collect::<Vec<String>>();
+    assert_eq!(config.highlight(&tags(&["later", "urgent"])).unwrap().spec, "red bold");
+    assert_eq!(config.highlight(&tags(&["someday"])), None);
+
+    config.set_highlight("later", None);
+    config.set_highlight("waiting", Some(Highlight::parse("underline  yellow").unwrap()));
+    config.save_highlights().unwrap();
+    let saved = fs::read_to_string(&path).unwrap();
+    assert_eq!(saved,
+               "quit:C-q\nhighlight #urgent: red bold\nhighlight #waiting: underline yellow\n");
+    let reloaded = Config::parse_file(path.clone()).unwrap();
+    assert_eq!(reloaded.highlights, config.highlights);
+    assert_eq!(reloaded.map(Event::Key(Key::Ctrl('q'))), Some(Action::Quit));
+
+    fs::write(&path, "highlight #urgent: reddish\n").unwrap();
+    assert!(Config::parse_file(path.clone()).is_err());
+    fs::write(&path, "board: #backlog next  shipped\n").unwrap();
+    let board = Config::parse_file(path.clone()).unwrap().board;
+    assert_eq!(board, tags(&["backlog", "next", "shipped"]));
+    fs::write(&path, "board: todo\n").unwrap();
+    assert!(Config::parse_file(path.clone()).is_err());
+    fs::write(&path, "file: ~/notes.db\n").unwrap();
+    assert_eq!(Config::parse_file(path.clone()).unwrap().file, Some("~/notes.db".to_owned()));
+    fs::write(&path, "fallback_size: 100x30\n").unwrap();
+    assert_eq!(Config::parse_file(path.clone()).unwrap().fallback_size, (100, 30));
+    fs::write(&path, "fallback_size: huge\n").unwrap();
+    assert!(Config::parse_file(path.clone()).is_err());
+    fs::write(&path, "progress: children\n").unwrap();
+    assert_eq!(Config::parse_file(path.clone()).unwrap().progress, Some(Progress::Children));
+    fs::write(&path, "progress: off\n").unwrap();
+    assert_eq!(Config::parse_file(path.clone()).unwrap().progress, None);
+    fs::write(&path, "progress: some\n").unwrap();
+    assert!(Config::parse_file(path.clone()).is_err());
+    fs::write(&path, "on_save: git push origin HEAD:main\nhook_timeout: 30\n").unwrap();
+    let config = Config::parse_file(path.clone()).unwrap();
+    assert_eq!(config.hook(Hook::Save), Some("git push origin HEAD:main"));
+    assert_eq!(config.hook(Hook::Open), None);
+    assert_eq!(config.hook_timeout, Duration::from_secs(30));
+    remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_modal_keys() {
+    let insert_j = Event::Key(Key::Char('j'));
+    let mut config = Config::default();
+    assert_eq!(config.map_in(insert_j.clone(), Mode::Normal), Some(Action::SelectDown));
+    assert_eq!(config.map_in(insert_j.clone(), Mode::Insert), Some(Action::Char('j')));
+    assert_eq!(config.map_in(Event::Key(Key::Esc), Mode::Insert), Some(Action::UnselectRet));
+
+    config.modal = true;
+    assert_eq!(config.map_in(Event::Key(Key::Esc), Mode::Insert), Some(Action::NormalMode));
+    assert_eq!(config.map_in(Event::Key(Key::Esc), Mode::Normal), Some(Action::UnselectRet));
+    // keys without a normal binding fall through to the usual ones
+    assert_eq!(config.map_in(Event::Key(Key::Ctrl('a')), Mode::Normal),
+               Some(Action::ToggleCompleted));
+    assert_eq!(config.map_in(Event::Key(Key::Char('q')), Mode::Normal), Some(Action::Char('q')));
+}
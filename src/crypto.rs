@@ -0,0 +1,599 @@
+use std::fmt;
+
+use rand::{OsRng, Rng};
+
+// encrypted work files are sealed with xsalsa20-poly1305, as in nacl's
+// secretbox, under a key made from a passphrase with scrypt. everything is
+// done here rather than pulled in, and favors being easy to check over
+// being fast. a sealed file is:
+//
+//   "VOIDSEAL"  magic
+//   1 byte      format version
+//   3 bytes     scrypt log2(N), r and p
+//   16 bytes    salt
+//   24 bytes    nonce
+//   16 bytes    poly1305 tag
+//   the rest    the encrypted protobuf
+//
+// the header isn't covered by the tag, but changing the salt or parameters
+// changes the key and the nonce is part of the cipher, so any change to it
+// fails to open like a wrong passphrase would.
+const MAGIC: &[u8] = b"VOIDSEAL";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = 8 + 1 + 3 + 16 + 24;
+const TAG_LEN: usize = 16;
+
+// scrypt settings for new keys, taking around 32mb and a tenth of a second
+pub const DEFAULT_PARAMS: Params = Params {
+    log_n: 15,
+    r: 8,
+    p: 1,
+};
+
+// headers asking scrypt for more memory than this are refused, so that a
+// damaged file can't run us out of it
+const MAX_SCRYPT_MEMORY: u64 = 1 << 30;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Params {
+    pub log_n: u8,
+    pub r: u8,
+    pub p: u8,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    NotSealed,
+    Truncated,
+    UnknownVersion(u8),
+    BadParams(Params),
+    // the passphrase, or the file, is wrong
+    WrongPassphrase,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::NotSealed => write!(f, "not an encrypted map"),
+            Error::Truncated => write!(f, "encrypted map is cut short"),
+            Error::UnknownVersion(v) => write!(f, "encrypted with a newer void (format {})", v),
+            Error::BadParams(p) => write!(f, "unusable key parameters {:?}", p),
+            Error::WrongPassphrase => write!(f, "wrong passphrase, or the file is damaged"),
+        }
+    }
+}
+
+// a key made from a passphrase, kept while its file is open so that saving
+// doesn't ask again. the passphrase itself isn't kept.
+#[derive(Clone)]
+pub struct Key {
+    params: Params,
+    salt: [u8; 16],
+    key: [u8; 32],
+}
+
+impl fmt::Debug for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Key {{ params: {:?}, .. }}", self.params)
+    }
+}
+
+impl Key {
+    pub fn derive(passphrase: &str, params: Params, salt: [u8; 16]) -> Result<Key, Error> {
+        if params.log_n == 0 || params.log_n > 40 || params.r == 0 || params.p == 0 ||
+           128 * params.r as u64 * (1 << params.log_n) > MAX_SCRYPT_MEMORY {
+            return Err(Error::BadParams(params));
+        }
+        let derived = scrypt(passphrase.as_bytes(), &salt, params, 32);
+        let mut key = [0; 32];
+        key.copy_from_slice(&derived);
+        Ok(Key { params, salt, key })
+    }
+
+    // a key with a new salt, for a file that wasn't encrypted before
+    pub fn generate(passphrase: &str) -> Key {
+        let mut salt = [0; 16];
+        OsRng::new().expect("no randomness for a salt").fill_bytes(&mut salt);
+        Key::derive(passphrase, DEFAULT_PARAMS, salt).unwrap()
+    }
+}
+
+pub fn is_sealed(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+pub fn seal(key: &Key, plaintext: &[u8]) -> Vec<u8> {
+    let mut nonce = [0; 24];
+    OsRng::new().expect("no randomness for a nonce").fill_bytes(&mut nonce);
+    seal_with_nonce(key, &nonce, plaintext)
+}
+
+fn seal_with_nonce(key: &Key, nonce: &[u8; 24], plaintext: &[u8]) -> Vec<u8> {
+    let mut sealed = MAGIC.to_vec();
+    sealed.extend_from_slice(&[VERSION, key.params.log_n, key.params.r, key.params.p]);
+    sealed.extend_from_slice(&key.salt);
+    sealed.extend_from_slice(nonce);
+    sealed.extend_from_slice(&secretbox(&key.key, nonce, plaintext));
+    sealed
+}
+
+// the scrypt parameters, salt and nonce of a sealed file
+fn header(data: &[u8]) -> Result<(Params, [u8; 16], [u8; 24]), Error> {
+    if !is_sealed(data) {
+        return Err(Error::NotSealed);
+    }
+    if data.len() < HEADER_LEN + TAG_LEN {
+        return Err(Error::Truncated);
+    }
+    if data[8] != VERSION {
+        return Err(Error::UnknownVersion(data[8]));
+    }
+    let params = Params {
+        log_n: data[9],
+        r: data[10],
+        p: data[11],
+    };
+    let mut salt = [0; 16];
+    salt.copy_from_slice(&data[12..28]);
+    let mut nonce = [0; 24];
+    nonce.copy_from_slice(&data[28..HEADER_LEN]);
+    Ok((params, salt, nonce))
+}
+
+// decrypt a sealed file, returning the key too for saving it again
+pub fn open(data: &[u8], passphrase: &str) -> Result<(Vec<u8>, Key), Error> {
+    let (params, salt, _) = header(data)?;
+    let key = Key::derive(passphrase, params, salt)?;
+    let plaintext = open_with(data, &key)?;
+    Ok((plaintext, key))
+}
+
+// decrypt a sealed file with a key made for it earlier
+pub fn open_with(data: &[u8], key: &Key) -> Result<Vec<u8>, Error> {
+    let (params, salt, nonce) = header(data)?;
+    if params != key.params || salt != key.salt {
+        return Err(Error::WrongPassphrase);
+    }
+    secretbox_open(&key.key, &nonce, &data[HEADER_LEN..]).ok_or(Error::WrongPassphrase)
+}
+
+fn le32(b: &[u8]) -> u32 {
+    b[0] as u32 | (b[1] as u32) << 8 | (b[2] as u32) << 16 | (b[3] as u32) << 24
+}
+
+fn put_le32(out: &mut [u8], x: u32) {
+    for (i, byte) in out.iter_mut().take(4).enumerate() {
+        *byte = (x >> (8 * i)) as u8;
+    }
+}
+
+fn quarter_round(x: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    x[b] ^= x[a].wrapping_add(x[d]).rotate_left(7);
+    x[c] ^= x[b].wrapping_add(x[a]).rotate_left(9);
+    x[d] ^= x[c].wrapping_add(x[b]).rotate_left(13);
+    x[a] ^= x[d].wrapping_add(x[c]).rotate_left(18);
+}
+
+// the salsa20 permutation, without adding the input back in
+fn salsa20_rounds(input: &[u32; 16], rounds: usize) -> [u32; 16] {
+    let mut x = *input;
+    for _ in 0..rounds / 2 {
+        quarter_round(&mut x, 0, 4, 8, 12);
+        quarter_round(&mut x, 5, 9, 13, 1);
+        quarter_round(&mut x, 10, 14, 2, 6);
+        quarter_round(&mut x, 15, 3, 7, 11);
+        quarter_round(&mut x, 0, 1, 2, 3);
+        quarter_round(&mut x, 5, 6, 7, 4);
+        quarter_round(&mut x, 10, 11, 8, 9);
+        quarter_round(&mut x, 15, 12, 13, 14);
+    }
+    x
+}
+
+fn salsa20_block(input: &[u32; 16], rounds: usize) -> [u32; 16] {
+    let mut x = salsa20_rounds(input, rounds);
+    for (x, i) in x.iter_mut().zip(input) {
+        *x = x.wrapping_add(*i);
+    }
+    x
+}
+
+// the salsa20 state for a key and 16 bytes of nonce and counter
+fn salsa20_input(key: &[u8; 32], nonce: &[u8]) -> [u32; 16] {
+    let sigma = b"expand 32-byte k";
+    let mut x = [0; 16];
+    for i in 0..4 {
+        x[i * 5] = le32(&sigma[i * 4..]);
+        x[1 + i] = le32(&key[i * 4..]);
+        x[6 + i] = le32(&nonce[i * 4..]);
+        x[11 + i] = le32(&key[16 + i * 4..]);
+    }
+    x
+}
+
+// xsalsa20's keystream: hsalsa20 makes a subkey from the first 16 bytes of
+// the nonce, which is used with the last 8 as plain salsa20
+fn xsalsa20_stream(key: &[u8; 32], nonce: &[u8; 24], len: usize) -> Vec<u8> {
+    let x = salsa20_rounds(&salsa20_input(key, &nonce[..16]), 20);
+    let mut subkey = [0; 32];
+    for (i, &word) in [0, 5, 10, 15, 6, 7, 8, 9].iter().enumerate() {
+        put_le32(&mut subkey[i * 4..], x[word]);
+    }
+
+    let mut stream = vec![0; len];
+    let mut counter_nonce = [0; 16];
+    counter_nonce[..8].copy_from_slice(&nonce[16..]);
+    for (counter, chunk) in stream.chunks_mut(64).enumerate() {
+        put_le32(&mut counter_nonce[8..], counter as u32);
+        put_le32(&mut counter_nonce[12..], (counter as u64 >> 32) as u32);
+        let block = salsa20_block(&salsa20_input(&subkey, &counter_nonce), 20);
+        let mut bytes = [0; 64];
+        for (i, &word) in block.iter().enumerate() {
+            put_le32(&mut bytes[i * 4..], word);
+        }
+        chunk.copy_from_slice(&bytes[..chunk.len()]);
+    }
+    stream
+}
+
+// poly1305 with 26 bit limbs, after poly1305-donna
+fn poly1305(key: &[u8; 32], msg: &[u8]) -> [u8; 16] {
+    let r0 = le32(&key[0..]) & 0x3ff_ffff;
+    let r1 = (le32(&key[3..]) >> 2) & 0x3ff_ff03;
+    let r2 = (le32(&key[6..]) >> 4) & 0x3ff_c0ff;
+    let r3 = (le32(&key[9..]) >> 6) & 0x3f0_3fff;
+    let r4 = (le32(&key[12..]) >> 8) & 0x00f_ffff;
+    let (s1, s2, s3, s4) = (r1 * 5, r2 * 5, r3 * 5, r4 * 5);
+    let mut h = [0u32; 5];
+
+    for chunk in msg.chunks(16) {
+        // the block with a 1 after it, as a number below 2^130
+        let mut block = [0; 17];
+        block[..chunk.len()].copy_from_slice(chunk);
+        block[chunk.len()] = 1;
+        h[0] += le32(&block[0..]) & 0x3ff_ffff;
+        h[1] += (le32(&block[3..]) >> 2) & 0x3ff_ffff;
+        h[2] += (le32(&block[6..]) >> 4) & 0x3ff_ffff;
+        h[3] += (le32(&block[9..]) >> 6) & 0x3ff_ffff;
+        h[4] += (le32(&block[12..]) >> 8) | (block[16] as u32) << 24;
+
+        let m = |a: u32, b: u32| a as u64 * b as u64;
+        let d = [m(h[0], r0) + m(h[1], s4) + m(h[2], s3) + m(h[3], s2) + m(h[4], s1),
+                 m(h[0], r1) + m(h[1], r0) + m(h[2], s4) + m(h[3], s3) + m(h[4], s2),
+                 m(h[0], r2) + m(h[1], r1) + m(h[2], r0) + m(h[3], s4) + m(h[4], s3),
+                 m(h[0], r3) + m(h[1], r2) + m(h[2], r1) + m(h[3], r0) + m(h[4], s4),
+                 m(h[0], r4) + m(h[1], r3) + m(h[2], r2) + m(h[3], r1) + m(h[4], r0)];
+        let mut carry = 0;
+        for (h, d) in h.iter_mut().zip(&d) {
+            let d = d + carry;
+            *h = d as u32 & 0x3ff_ffff;
+            carry = d >> 26;
+        }
+        h[0] += carry as u32 * 5;
+        h[1] += h[0] >> 26;
+        h[0] &= 0x3ff_ffff;
+    }
+
+    // fully carry, then take off p = 2^130 - 5 if h is at least that
+    let mut carry = 0;
+    for h in h.iter_mut().skip(1) {
+        *h += carry;
+        carry = *h >> 26;
+        *h &= 0x3ff_ffff;
+    }
+    h[0] += carry * 5;
+    h[1] += h[0] >> 26;
+    h[0] &= 0x3ff_ffff;
+
+    let mut g = [0u32; 5];
+    let mut carry = 5;
+    for (g, h) in g.iter_mut().zip(&h) {
+        *g = h + carry;
+        carry = *g >> 26;
+        *g &= 0x3ff_ffff;
+    }
+    // all ones when h + 5 didn't reach 2^130
+    let under_p = carry.wrapping_sub(1);
+    let h: Vec<u32> = h.iter().zip(&g).map(|(&h, &g)| (h & under_p) | (g & !under_p)).collect();
+
+    // add s, the second half of the key, mod 2^128
+    let words = [h[0] | h[1] << 26,
+                 h[1] >> 6 | h[2] << 20,
+                 h[2] >> 12 | h[3] << 14,
+                 h[3] >> 18 | h[4] << 8];
+    let mut tag = [0; 16];
+    let mut carry = 0;
+    for (i, &word) in words.iter().enumerate() {
+        let sum = word as u64 + le32(&key[16 + i * 4..]) as u64 + carry;
+        put_le32(&mut tag[i * 4..], sum as u32);
+        carry = sum >> 32;
+    }
+    tag
+}
+
+fn secretbox(key: &[u8; 32], nonce: &[u8; 24], plaintext: &[u8]) -> Vec<u8> {
+    let stream = xsalsa20_stream(key, nonce, 32 + plaintext.len());
+    let mut mac_key = [0; 32];
+    mac_key.copy_from_slice(&stream[..32]);
+    let ciphertext: Vec<u8> = plaintext.iter().zip(&stream[32..]).map(|(p, s)| p ^ s).collect();
+    let mut sealed = poly1305(&mac_key, &ciphertext).to_vec();
+    sealed.extend(ciphertext);
+    sealed
+}
+
+fn secretbox_open(key: &[u8; 32], nonce: &[u8; 24], sealed: &[u8]) -> Option<Vec<u8>> {
+    if sealed.len() < TAG_LEN {
+        return None;
+    }
+    let (tag, ciphertext) = sealed.split_at(TAG_LEN);
+    let stream = xsalsa20_stream(key, nonce, 32 + ciphertext.len());
+    let mut mac_key = [0; 32];
+    mac_key.copy_from_slice(&stream[..32]);
+    // compared without stopping early, so timing doesn't tell how close a
+    // forged tag was
+    let expected = poly1305(&mac_key, ciphertext);
+    if tag.iter().zip(&expected).fold(0, |diff, (a, b)| diff | (a ^ b)) != 0 {
+        return None;
+    }
+    Some(ciphertext.iter().zip(&stream[32..]).map(|(c, s)| c ^ s).collect())
+}
+
+const SHA256_K: [u32; 64] =
+    [0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4,
+     0xab1c5ed5, 0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe,
+     0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f,
+     0x4a7484aa, 0x5cb0a9dc, 0x76f988da, 0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7,
+     0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc,
+     0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b,
+     0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070, 0x19a4c116,
+     0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+     0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7,
+     0xc67178f2];
+
+fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut state: [u32; 8] = [0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f,
+                               0x9b05688c, 0x1f83d9ab, 0x5be0cd19];
+    let mut padded = data.to_vec();
+    padded.push(0x80);
+    while padded.len() % 64 != 56 {
+        padded.push(0);
+    }
+    let bits = data.len() as u64 * 8;
+    padded.extend((0..8).rev().map(|i| (bits >> (8 * i)) as u8));
+
+    for block in padded.chunks(64) {
+        let mut w = [0u32; 64];
+        for (i, word) in block.chunks(4).enumerate() {
+            w[i] = (word[0] as u32) << 24 | (word[1] as u32) << 16 | (word[2] as u32) << 8 |
+                   word[3] as u32;
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+        for (k, w) in SHA256_K.iter().zip(&w) {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(*k).wrapping_add(*w);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+        for (s, x) in state.iter_mut().zip(&[a, b, c, d, e, f, g, h]) {
+            *s = s.wrapping_add(*x);
+        }
+    }
+
+    let mut digest = [0; 32];
+    for (out, word) in digest.chunks_mut(4).zip(&state) {
+        out.copy_from_slice(&[(word >> 24) as u8, (word >> 16) as u8, (word >> 8) as u8,
+                              *word as u8]);
+    }
+    digest
+}
+
+fn hmac_sha256(key: &[u8], msg: &[u8]) -> [u8; 32] {
+    let mut block_key = [0; 64];
+    if key.len() > 64 {
+        block_key[..32].copy_from_slice(&sha256(key));
+    } else {
+        block_key[..key.len()].copy_from_slice(key);
+    }
+    let mut inner: Vec<u8> = block_key.iter().map(|b| b ^ 0x36).collect();
+    inner.extend_from_slice(msg);
+    let mut outer: Vec<u8> = block_key.iter().map(|b| b ^ 0x5c).collect();
+    outer.extend_from_slice(&sha256(&inner));
+    sha256(&outer)
+}
+
+fn pbkdf2_sha256(passphrase: &[u8], salt: &[u8], rounds: usize, len: usize) -> Vec<u8> {
+    let mut out = Vec::with_capacity(len);
+    let mut block = 1u32;
+    while out.len() < len {
+        let mut msg = salt.to_vec();
+        msg.extend_from_slice(&[(block >> 24) as u8, (block >> 16) as u8, (block >> 8) as u8,
+                                block as u8]);
+        let mut u = hmac_sha256(passphrase, &msg);
+        let mut t = u;
+        for _ in 1..rounds {
+            u = hmac_sha256(passphrase, &u);
+            for (t, u) in t.iter_mut().zip(&u) {
+                *t ^= u;
+            }
+        }
+        let wanted = (len - out.len()).min(32);
+        out.extend_from_slice(&t[..wanted]);
+        block += 1;
+    }
+    out
+}
+
+fn scrypt(passphrase: &[u8], salt: &[u8], params: Params, len: usize) -> Vec<u8> {
+    let (n, r) = (1usize << params.log_n, params.r as usize);
+    let mut b = pbkdf2_sha256(passphrase, salt, 1, params.p as usize * 128 * r);
+    for chunk in b.chunks_mut(128 * r) {
+        romix(chunk, n, r);
+    }
+    pbkdf2_sha256(passphrase, &b, 1, len)
+}
+
+fn romix(b: &mut [u8], n: usize, r: usize) {
+    let words = 32 * r;
+    let mut x: Vec<u32> = b.chunks(4).map(le32).collect();
+    let mut v = Vec::with_capacity(n * words);
+    for _ in 0..n {
+        v.extend_from_slice(&x);
+        block_mix(&mut x, r);
+    }
+    for _ in 0..n {
+        let j = x[(2 * r - 1) * 16] as usize & (n - 1);
+        for (x, v) in x.iter_mut().zip(&v[j * words..(j + 1) * words]) {
+            *x ^= v;
+        }
+        block_mix(&mut x, r);
+    }
+    for (out, &word) in b.chunks_mut(4).zip(&x) {
+        put_le32(out, word);
+    }
+}
+
+// scrypt's BlockMix with salsa20/8, putting even blocks before odd ones
+fn block_mix(b: &mut [u32], r: usize) {
+    let mut x = [0; 16];
+    x.copy_from_slice(&b[(2 * r - 1) * 16..]);
+    let mut y = vec![0; b.len()];
+    for (i, block) in b.chunks(16).enumerate() {
+        for (x, word) in x.iter_mut().zip(block) {
+            *x ^= word;
+        }
+        x = salsa20_block(&x, 8);
+        let at = (i / 2 + (i % 2) * r) * 16;
+        y[at..at + 16].copy_from_slice(&x);
+    }
+    b.copy_from_slice(&y);
+}
+
+#[cfg(test)]
+fn unhex(hex: &str) -> Vec<u8> {
+    (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+}
+
+#[test]
+fn test_hashes() {
+    assert_eq!(sha256(b"abc").to_vec(),
+               unhex("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"));
+    assert_eq!(sha256(b"").to_vec(),
+               unhex("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"));
+    assert_eq!(sha256(&[b'a'; 1000]).to_vec(),
+               unhex("41edece42d63e8d9bf515a9ba6932e1c20cbc9f5a5d134645adb5db1b9737ea3"));
+
+    // rfc 7914 section 11
+    assert_eq!(pbkdf2_sha256(b"passwd", b"salt", 1, 64),
+               unhex("55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc\
+                      49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783"));
+}
+
+#[test]
+fn test_scrypt() {
+    // rfc 7914 section 12
+    let params = Params {
+        log_n: 4,
+        r: 1,
+        p: 1,
+    };
+    assert_eq!(scrypt(b"", b"", params, 64),
+               unhex("77d6576238657b203b19ca42c18a0497f16b4844e3074ae8dfdffa3fede21442\
+                      fcd0069ded0948f8326a753a0fc81f17e8d3e0fb2e0d3628cf35e20c38d18906"));
+    let params = Params {
+        log_n: 10,
+        r: 8,
+        p: 16,
+    };
+    assert_eq!(scrypt(b"password", b"NaCl", params, 64),
+               unhex("fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b373162\
+                      2eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640"));
+}
+
+#[test]
+fn test_secretbox() {
+    // rfc 8439 section 2.5.2
+    let mut key = [0; 32];
+    key.copy_from_slice(&unhex("85d6be7857556d337f4452fe42d506a8\
+                                0103808afb0db2fd4abff6af4149f51b"));
+    assert_eq!(poly1305(&key, b"Cryptographic Forum Research Group").to_vec(),
+               unhex("a8061dc1305136c6c22b8baf0c0127a9"));
+
+    // from nacl's tests/secretbox.c
+    key.copy_from_slice(&unhex("1b27556473e985d462cd51197a9a46c76009549eac6474f206c4ee0844f68389"));
+    let mut nonce = [0; 24];
+    nonce.copy_from_slice(&unhex("69696ee955b62b73cd62bda875fc73d68219e0036b7a0b37"));
+    let plaintext = unhex("be075fc53c81f2d5cf141316ebeb0c7b5228c52a4c62cbd44b66849b64244ffc\
+                           e5ecbaaf33bd751a1ac728d45e6c61296cdc3c01233561f41db66cce314adb31\
+                           0e3be8250c46f06dceea3a7fa1348057e2f6556ad6b1318a024a838f21af1fde\
+                           048977eb48f59ffd4924ca1c60902e52f0a089bc76897040e082f93776384864\
+                           5e0705");
+    let sealed = unhex("f3ffc7703f9400e52a7dfb4b3d3305d98e993b9f48681273c29650ba32fc76ce\
+                        48332ea7164d96a4476fb8c531a1186ac0dfc17c98dce87b4da7f011ec48c972\
+                        71d2c20f9b928fe2270d6fb863d51738b48eeee314a7cc8ab932164548e526ae\
+                        90224368517acfeabd6bb3732bc0e9da99832b61ca01b6de56244a9e88d5f9b3\
+                        7973f622a43d14a6599b1f654cb45a74e355a5");
+    assert_eq!(secretbox(&key, &nonce, &plaintext), sealed);
+    assert_eq!(secretbox_open(&key, &nonce, &sealed), Some(plaintext));
+
+    let mut forged = sealed.clone();
+    forged[40] ^= 1;
+    assert_eq!(secretbox_open(&key, &nonce, &forged), None);
+}
+
+#[test]
+fn test_sealed_format() {
+    let params = Params {
+        log_n: 4,
+        r: 1,
+        p: 1,
+    };
+    let key = Key::derive("correct horse", params, [1; 16]).unwrap();
+    let sealed = seal_with_nonce(&key, &[2; 24], b"hello");
+    assert_eq!(sealed,
+               unhex("564f49445345414c01040101\
+                      01010101010101010101010101010101\
+                      020202020202020202020202020202020202020202020202\
+                      56e21c8423b9504d10bc4a16b1e730e8\
+                      39d86662b4"));
+
+    assert!(is_sealed(&sealed));
+    assert_eq!(open(&sealed, "correct horse").unwrap().0, b"hello");
+    assert_eq!(open_with(&sealed, &key).unwrap(), b"hello");
+    assert_eq!(open(&sealed, "battery staple").unwrap_err(), Error::WrongPassphrase);
+    let other = Key::derive("correct horse", params, [3; 16]).unwrap();
+    assert_eq!(open_with(&sealed, &other).unwrap_err(), Error::WrongPassphrase);
+
+    // any change to the header or body is caught
+    for &at in &[12, 30, 55, 70] {
+        let mut damaged = sealed.clone();
+        damaged[at] ^= 0x80;
+        assert_eq!(open(&damaged, "correct horse").unwrap_err(), Error::WrongPassphrase);
+    }
+    let mut newer = sealed.clone();
+    newer[8] = 2;
+    assert_eq!(open(&newer, "correct horse").unwrap_err(), Error::UnknownVersion(2));
+    let mut greedy = sealed.clone();
+    greedy[9] = 40;
+    assert_eq!(open(&greedy, "correct horse").unwrap_err(), Error::BadParams(Params {
+        log_n: 40,
+        r: 1,
+        p: 1,
+    }));
+    assert_eq!(open(&sealed[..60], "correct horse").unwrap_err(), Error::Truncated);
+    assert_eq!(open(b"\x0a\x03abc", "correct horse").unwrap_err(), Error::NotSealed);
+}
@@ -1,7 +1,7 @@
-use time;
-
 use regex::Regex;
 
+use clock;
+
 pub fn dateparse(input: String) -> Option<u64> {
     lazy_static! {
         static ref YEARS: Regex = Regex::new(r"(?P<y>\d+)y").unwrap();
@@ -11,7 +11,7 @@ pub fn dateparse(input: String) -> Option<u64> {
         static ref HOURS: Regex = Regex::new(r"(?P<h>\d+)h").unwrap();
     }
 
-    let now_in_s = time::get_time().sec as u64;
+    let now_in_s = clock::now();
     let min_in_s = 60;
     let hour_in_s = min_in_s * 60;
     let day_in_s = hour_in_s * 24;
@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+
+use time;
+
+use {Coords, Meta, Node, NodeID};
+use clock;
+use colors::{color_name, parse_color};
+use estimate;
+use quickadd::parse_date;
+
+// times are shown and read in UTC, like in the exports
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+// the width of the labels and the space after them
+pub const LABEL_WIDTH: usize = 10;
+
+pub const HINT: &str = "enter saves, esc cancels";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Field {
+    Created,
+    Modified,
+    Finished,
+    Due,
+    Estimate,
+    Tags,
+    Color,
+    Attachments,
+}
+
+impl Field {
+    pub fn label(&self) -> &'static str {
+        match *self {
+            Field::Created => "created",
+            Field::Modified => "modified",
+            Field::Finished => "finished",
+            Field::Due => "due",
+            Field::Estimate => "estimate",
+            Field::Tags => "tags",
+            Field::Color => "color",
+            Field::Attachments => "files",
+        }
+    }
+
+    pub fn editable(&self) -> bool {
+        ![Field::Created, Field::Modified, Field::Finished, Field::Attachments].contains(self)
+    }
+}
+
+// what committing the panel does to its node
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edits {
+    pub due: Option<u64>,
+    pub estimate: Option<u64>,
+    pub tags: HashMap<String, String>,
+    // a blank color field leaves the color alone
+    pub color: Option<String>,
+}
+
+impl Edits {
+    pub fn apply(&self, node: &mut Node) {
+        node.meta.due = self.due;
+        node.meta.estimate = self.estimate;
+        node.meta.tags = self.tags.clone();
+        if let Some(ref color) = self.color {
+            node.color = color.clone();
+        }
+    }
+}
+
+// a form over one node's meta, with a text field for each part of it.
+// nothing changes until it's committed.
+#[derive(Debug, Clone)]
+pub struct Detail {
+    pub node: NodeID,
+    pub fields: Vec<(Field, String)>,
+    // always on an editable field
+    pub cursor: usize,
+    // why each field didn't parse at the last commit
+    pub errors: Vec<Option<String>>,
+}
+
+impl Detail {
+    pub fn new(node: NodeID, meta: &Meta, color: &str) -> Detail {
+        let mut tags: Vec<String> = meta.tags
+            .iter()
+            .map(|(k, v)| if v.is_empty() {
+                k.clone()
+            } else {
+                format!("{}={}", k, v)
+            })
+            .collect();
+        tags.sort();
+        let fields = vec![
+            (Field::Created, format_time(meta.ctime)),
+            (Field::Modified, format_time(meta.mtime)),
+            (Field::Finished, meta.finish_time.map(format_time).unwrap_or_default()),
+            (Field::Due, meta.due.map(format_time).unwrap_or_default()),
+            (Field::Estimate, meta.estimate.map(estimate::format).unwrap_or_default()),
+            (Field::Tags, tags.join(", ")),
+            (Field::Color, color_name(color).unwrap_or_default()),
+            (Field::Attachments, meta.attachments.join(", ")),
+        ];
+        Detail {
+            node,
+            cursor: fields.iter().position(|&(f, _)| f.editable()).unwrap(),
+            errors: vec![None; fields.len()],
+            fields,
+        }
+    }
+
+    pub fn up(&mut self) {
+        if let Some(idx) = (0..self.cursor).rev().find(|&i| self.fields[i].0.editable()) {
+            self.cursor = idx;
+        }
+    }
+
+    pub fn down(&mut self) {
+        if let Some(idx) = (self.cursor + 1..self.fields.len())
+            .find(|&i| self.fields[i].0.editable()) {
+            self.cursor = idx;
+        }
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.fields[self.cursor].1.push(c);
+        self.errors[self.cursor] = None;
+    }
+
+    pub fn pop(&mut self) {
+        self.fields[self.cursor].1.pop();
+        self.errors[self.cursor] = None;
+    }
+
+    // the parsed fields, or None after noting what's wrong with them and
+    // moving to the first bad one
+    pub fn edits(&mut self) -> Option<Edits> {
+        let mut edits = Edits {
+            due: None,
+            estimate: None,
+            tags: HashMap::new(),
+            color: None,
+        };
+        for (idx, &(field, ref text)) in self.fields.iter().enumerate() {
+            let text = text.trim();
+            let parsed = match field {
+                Field::Due => parse_due(text).map(|due| edits.due = due),
+                Field::Estimate => {
+                    parse_estimate(text).map(|estimate| edits.estimate = estimate)
+                }
+                Field::Tags => parse_tags(text).map(|tags| edits.tags = tags),
+                Field::Color if !text.is_empty() => {
+                    parse_color(text)
+                        .map(|color| edits.color = Some(color))
+                        .ok_or_else(|| "a color name like red, or 0 to 255".to_owned())
+                }
+                _ => Ok(()),
+            };
+            self.errors[idx] = parsed.err();
+        }
+        match self.errors.iter().position(|e| e.is_some()) {
+            Some(idx) => {
+                self.cursor = idx;
+                None
+            }
+            None => Some(edits),
+        }
+    }
+
+    // top left corner and size of the box, bordered, centered on a screen
+    // of the given dims. errors get a row each below their fields.
+    pub fn rect(&self, (width, height): Coords) -> (Coords, Coords) {
+        let values = self.fields
+            .iter()
+            .map(|field| field.1.chars().count() + 1)
+            .chain(self.errors.iter().filter_map(|e| e.as_ref()).map(|e| e.chars().count()))
+            .max()
+            .unwrap_or(0);
+        let inner = (LABEL_WIDTH + values).max(HINT.len()).max(30) as u16;
+        let w = (inner + 4).min(width);
+        let errors = self.errors.iter().filter(|e| e.is_some()).count();
+        let h = (self.fields.len() + errors + 3) as u16;
+        let x = (width.saturating_sub(w)) / 2 + 1;
+        let y = (height.saturating_sub(h)) / 2 + 1;
+        ((x, y), (w, h))
+    }
+}
+
+pub fn format_time(secs: u64) -> String {
+    let tm = time::at_utc(time::Timespec::new(secs as i64, 0));
+    time::strftime(TIME_FORMAT, &tm).unwrap()
+}
+
+// anything a `due:` token takes when typing a node. blank means no due
+// date.
+fn parse_due(text: &str) -> Result<Option<u64>, String> {
+    if text.is_empty() {
+        return Ok(None);
+    }
+    match parse_date(text, clock::now()) {
+        Some(due) => Ok(Some(due)),
+        None => Err("a date like 2024-05-01 14:00, a day like fri, or a span like 3d".to_owned()),
+    }
+}
+
+fn parse_estimate(text: &str) -> Result<Option<u64>, String> {
+    if text.is_empty() {
+        return Ok(None);
+    }
+    match estimate::parse(text) {
+        Some(0) => Ok(None),
+        Some(secs) => Ok(Some(secs)),
+        None => Err("a span like 30m, 2h or 1d4h".to_owned()),
+    }
+}
+
+// `errand, who=bob`
+fn parse_tags(text: &str) -> Result<HashMap<String, String>, String> {
+    let mut tags = HashMap::new();
+    for tag in text.split(',').map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        let (key, value) = match tag.find('=') {
+            Some(idx) => (tag[..idx].trim(), tag[idx + 1..].trim()),
+            None => (tag, ""),
+        };
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return Err(format!("\"{}\" needs a name without spaces", tag));
+        }
+        tags.insert(key.to_owned(), value.to_owned());
+    }
+    Ok(tags)
+}
+
+#[test]
+fn test_parse_fields() {
+    assert_eq!(parse_due(""), Ok(None));
+    assert_eq!(parse_due("1970-01-02"), Ok(Some(86400)));
+    assert_eq!(parse_due("1970-01-01 01:30"), Ok(Some(5400)));
+    assert!(parse_due("tomorrow-ish").is_err());
+    assert_eq!(parse_estimate("1h30m"), Ok(Some(5400)));
+    assert_eq!(parse_estimate("0m"), Ok(None));
+    assert!(parse_estimate("soon").is_err());
+
+    let tags = parse_tags(" errand,who = bob ,, ").unwrap();
+    assert_eq!(tags.len(), 2);
+    assert_eq!((&*tags["errand"], &*tags["who"]), ("", "bob"));
+    assert!(parse_tags("=bob").is_err());
+    assert!(parse_tags("two words").is_err());
+}
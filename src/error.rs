@@ -0,0 +1,76 @@
+use std::fmt;
+use std::io;
+use std::result;
+use std::string::FromUtf8Error;
+
+use protobuf::ProtobufError;
+
+// what can go wrong while void runs that isn't a bug in it: the disk, the
+// terminal and what's in files. these are logged and got past, unlike a
+// broken invariant, which still panics.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    // reading or writing the file at a path failed
+    File(String, io::Error),
+    Serialization(ProtobufError),
+    // raw mode couldn't be entered or left
+    Terminal(io::Error),
+    // a file held something it shouldn't, like text that isn't utf-8
+    Corrupt(String),
+    // an encrypted map couldn't be opened
+    Crypto(String),
+}
+
+pub type Result<T> = result::Result<T, Error>;
+
+impl Error {
+    // for an io::Result of doing something to a file
+    pub fn file(path: &str) -> impl FnOnce(io::Error) -> Error + '_ {
+        move |e| Error::File(path.to_owned(), e)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref e) => write!(f, "{}", e),
+            Error::File(ref path, ref e) => write!(f, "{}: {}", path, e),
+            Error::Serialization(ref e) => write!(f, "unreadable map: {}", e),
+            Error::Terminal(ref e) => write!(f, "terminal: {}", e),
+            Error::Corrupt(ref what) => write!(f, "corrupt: {}", what),
+            Error::Crypto(ref what) => write!(f, "{}", what),
+        }
+    }
+}
+
+impl ::std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error::Io(e)
+    }
+}
+
+impl From<ProtobufError> for Error {
+    fn from(e: ProtobufError) -> Error {
+        Error::Serialization(e)
+    }
+}
+
+impl From<FromUtf8Error> for Error {
+    fn from(e: FromUtf8Error) -> Error {
+        Error::Corrupt(e.to_string())
+    }
+}
+
+#[test]
+fn test_error_display() {
+    let denied = || io::Error::new(io::ErrorKind::PermissionDenied, "permission denied");
+    assert_eq!(Error::file("/ro/work.db")(denied()).to_string(),
+               "/ro/work.db: permission denied");
+    let e: Error = String::from_utf8(vec![0xff]).unwrap_err().into();
+    assert!(e.to_string().starts_with("corrupt: invalid utf-8"));
+    let e: Error = denied().into();
+    assert_eq!(e.to_string(), "permission denied");
+}
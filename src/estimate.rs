@@ -0,0 +1,72 @@
+// estimates are written into nodes as `est:2h` and kept in seconds. a day is
+// a working day of 8 hours, as in most sprint planning.
+const UNITS: &[(char, u64)] = &[('d', 8 * 60 * 60), ('h', 60 * 60), ('m', 60)];
+
+// `30m`, `2h`, `1d`, or combinations like `1d4h` and `1h30m`
+pub fn parse(text: &str) -> Option<u64> {
+    let mut total = 0;
+    let mut number = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let unit = UNITS.iter().find(|&&(u, _)| u == c).map(|&(_, secs)| secs)?;
+        let n: u64 = number.parse().ok()?;
+        total += n * unit;
+        number.clear();
+    }
+    if number.is_empty() && !text.is_empty() {
+        Some(total)
+    } else {
+        None
+    }
+}
+
+// the largest units first, leaving out the empty ones
+pub fn format(secs: u64) -> String {
+    let mut out = String::new();
+    let mut left = secs;
+    for &(unit, size) in UNITS {
+        if left >= size {
+            out.push_str(&format!("{}{}", left / size, unit));
+            left %= size;
+        }
+    }
+    if out.is_empty() {
+        "0m".to_owned()
+    } else {
+        out
+    }
+}
+
+// the estimates of the unfinished nodes of a subtree, next to the time
+// tracked on all of them, from (stricken, estimate, tracked) for each node
+pub fn rollup<I>(nodes: I) -> (u64, u64)
+    where I: IntoIterator<Item = (bool, Option<u64>, u64)>
+{
+    nodes.into_iter().fold((0, 0), |(estimated, tracked), (stricken, estimate, spent)| {
+        let estimate = if stricken { 0 } else { estimate.unwrap_or(0) };
+        (estimated + estimate, tracked + spent)
+    })
+}
+
+#[test]
+fn test_estimates() {
+    assert_eq!(parse("30m"), Some(30 * 60));
+    assert_eq!(parse("2h"), Some(2 * 60 * 60));
+    assert_eq!(parse("1d"), Some(8 * 60 * 60));
+    assert_eq!(parse("1d4h30m"), Some((12 * 60 + 30) * 60));
+    assert_eq!(parse("1h1h"), Some(2 * 60 * 60));
+    for bad in &["", "2", "h", "2x", "1.5h", "2h3"] {
+        assert_eq!(parse(bad), None);
+    }
+
+    assert_eq!(format(parse("1d4h30m").unwrap()), "1d4h30m");
+    assert_eq!(format(parse("10h").unwrap()), "1d2h");
+    assert_eq!(format(90), "1m");
+    assert_eq!(format(0), "0m");
+
+    let nodes = vec![(false, Some(3600), 600), (true, Some(7200), 1800), (false, None, 60)];
+    assert_eq!(rollup(nodes), (3600, 2460));
+}
@@ -0,0 +1,525 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use {Node, NodeID, Screen, estimate};
+use add::find_parent;
+use command::ExportFormat;
+use numbering::number_tree;
+use serialization::{export_csv, export_ical};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutlineStyle {
+    // plain indented lines, as pasted into an email
+    Text,
+    // a nested markdown list
+    Markdown,
+}
+
+// the subtree under root, one node per line, indented two spaces per level
+pub fn outline(screen: &Screen, root: NodeID, style: OutlineStyle) -> String {
+    let mut out = String::new();
+    write_outline(screen, root, 0, style, &numbers(screen), &HashSet::new(), &mut out);
+    out
+}
+
+// the outline numbers of the whole map, when it's shown with them
+fn numbers(screen: &Screen) -> HashMap<NodeID, String> {
+    if !screen.config.numbering {
+        return HashMap::new();
+    }
+    number_tree(screen, 0, screen.config.number_hidden).into_iter().collect()
+}
+
+// the whole map, or just the subtree under root as if it were the only
+// anchor. arrows leaving or entering the subtree are listed after it as
+// external references, in the formats that show arrows.
+pub fn export_as(screen: &Screen, format: ExportFormat, root: Option<NodeID>) -> String {
+    let (sliced, external) = match root {
+        Some(root) => {
+            let sliced = subtree(screen, root);
+            let external = external_arrows(screen, &sliced);
+            (sliced, external)
+        }
+        None => return export_sliced(screen, format, &[]),
+    };
+    export_sliced(&sliced, format, &external)
+}
+
+fn export_sliced(screen: &Screen, format: ExportFormat, external: &[(String, String)]) -> String {
+    let mut out = match format {
+        ExportFormat::Markdown => markdown(screen),
+        ExportFormat::Text => text(screen),
+        ExportFormat::Html => return write_html(screen, external),
+        ExportFormat::Ical => return export_ical(screen),
+        ExportFormat::Csv => return export_csv(screen),
+    };
+    if !external.is_empty() {
+        out.push_str(match format {
+            ExportFormat::Markdown => "\n## external references\n\n",
+            _ => "\nexternal references:\n",
+        });
+        for &(ref from, ref to) in external {
+            let bullet = if format == ExportFormat::Markdown { "- " } else { "  " };
+            writeln!(out, "{}{} → {}", bullet, from, to).unwrap();
+        }
+    }
+    out
+}
+
+// a copy of the map holding only root and what's below it, with root as
+// its only anchor and the arrows between the nodes in it
+pub fn subtree(screen: &Screen, root: NodeID) -> Screen {
+    let mut sliced = Screen::default();
+    sliced.max_id = screen.max_id;
+    sliced.config = screen.config.clone();
+    let mut home = screen.nodes[&0].clone();
+    home.children = vec![root];
+    sliced.nodes.insert(0, home);
+    let mut to_copy = vec![root];
+    while let Some(node_id) = to_copy.pop() {
+        if let Some(node) = screen.nodes.get(&node_id) {
+            let mut node = node.clone();
+            if node_id == root {
+                node.parent_id = 0;
+            }
+            to_copy.extend(node.children.iter().cloned());
+            sliced.tag_db.reindex(node_id, node.tag_text());
+            sliced.nodes.insert(node_id, node);
+        }
+    }
+    sliced.arrows = screen.arrows
+        .iter()
+        .cloned()
+        .filter(|arrow| sliced.nodes.contains_key(&arrow.0) && sliced.nodes.contains_key(&arrow.1))
+        .collect();
+    sliced
+}
+
+// the arrows with one end in the sliced map and the other outside it, as
+// paths. the inside ends are named from the slice's root.
+fn external_arrows(screen: &Screen, sliced: &Screen) -> Vec<(String, String)> {
+    let path = |node_id| if sliced.nodes.contains_key(&node_id) {
+        node_path(sliced, node_id)
+    } else {
+        node_path(screen, node_id)
+    };
+    screen.arrows
+        .iter()
+        .filter(|arrow| sliced.nodes.contains_key(&arrow.0) != sliced.nodes.contains_key(&arrow.1))
+        .map(|arrow| (path(arrow.0), path(arrow.1)))
+        .collect()
+}
+
+// a node given by its id, or by its content if exactly one node has it
+pub fn find_root(screen: &Screen, root: &str) -> Result<NodeID, String> {
+    if let Ok(node_id) = root.parse::<NodeID>() {
+        if node_id != 0 && screen.nodes.contains_key(&node_id) {
+            return Ok(node_id);
+        }
+    }
+    find_parent(screen, root)?.ok_or_else(|| format!("no node is \"{}\"", root))
+}
+
+// every anchor on the home screen, for saving the whole map as an outline,
+// then the arrows between nodes
+pub fn markdown(screen: &Screen) -> String {
+    outline_anchors(screen, OutlineStyle::Markdown)
+}
+
+pub fn text(screen: &Screen) -> String {
+    outline_anchors(screen, OutlineStyle::Text)
+}
+
+fn outline_anchors(screen: &Screen, style: OutlineStyle) -> String {
+    let anchors = screen.nodes.get(&0).map(|root| root.children.clone()).unwrap_or_default();
+    let numbers = numbers(screen);
+    // markdown links jump to the ends of arrows, which are marked to be
+    // jumped to
+    let linked = match style {
+        OutlineStyle::Markdown => linked(screen),
+        OutlineStyle::Text => HashSet::new(),
+    };
+    let mut out = String::new();
+    for anchor in anchors {
+        if style == OutlineStyle::Markdown {
+            if let Some(title) = screen.nodes.get(&anchor).and_then(|n| n.title.as_ref()) {
+                write!(out, "## {}\n\n", title).unwrap();
+            }
+        }
+        write_outline(screen, anchor, 0, style, &numbers, &linked, &mut out);
+    }
+    write_links(screen, style, &mut out);
+    out
+}
+
+// the nodes at either end of an arrow
+fn linked(screen: &Screen) -> HashSet<NodeID> {
+    screen.arrows.iter().flat_map(|&(from, to)| vec![from, to]).collect()
+}
+
+// where a link to node_id jumps to, in markdown and html
+fn node_anchor(node_id: NodeID) -> String {
+    format!("node-{}", node_id)
+}
+
+// one line per arrow, from the path of the node it starts at to the path
+// of the one it ends at. arrows have no kinds to label them with yet.
+fn write_links(screen: &Screen, style: OutlineStyle, out: &mut String) {
+    if screen.arrows.is_empty() {
+        return;
+    }
+    match style {
+        OutlineStyle::Markdown => out.push_str("\n## links\n\n"),
+        OutlineStyle::Text => out.push_str("\nlinks:\n"),
+    }
+    for &(from, to) in &screen.arrows {
+        let (from_path, to_path) = (node_path(screen, from), node_path(screen, to));
+        match style {
+            OutlineStyle::Markdown => {
+                writeln!(out,
+                         "- [{}](#{}) → [{}](#{})",
+                         from_path,
+                         node_anchor(from),
+                         to_path,
+                         node_anchor(to))
+                    .unwrap()
+            }
+            OutlineStyle::Text => writeln!(out, "  {} → {}", from_path, to_path).unwrap(),
+        }
+    }
+}
+
+fn write_outline(
+    screen: &Screen,
+    node_id: NodeID,
+    depth: usize,
+    style: OutlineStyle,
+    numbers: &HashMap<NodeID, String>,
+    linked: &HashSet<NodeID>,
+    out: &mut String,
+) {
+    let node = match screen.nodes.get(&node_id) {
+        Some(node) => node,
+        None => return,
+    };
+    for _ in 0..depth {
+        out.push_str("  ");
+    }
+    let mut line = outline_line(node, style, numbers.get(&node_id));
+    if linked.contains(&node_id) {
+        // right after the bullet
+        line.insert_str(2, &format!("<a id=\"{}\"></a>", node_anchor(node_id)));
+    }
+    out.push_str(&line);
+    out.push('\n');
+    for &child in &node.children {
+        write_outline(screen, child, depth + 1, style, numbers, linked, out);
+    }
+}
+
+fn outline_line(node: &Node, style: OutlineStyle, number: Option<&String>) -> String {
+    let content = match node.meta.estimate {
+        Some(secs) => format!("{} (est {})", node.content, estimate::format(secs)),
+        None => node.content.clone(),
+    };
+    let number = number.map(|n| format!("{} ", n)).unwrap_or_default();
+    match (style, node.stricken) {
+        (OutlineStyle::Text, false) => format!("{}{}", number, content),
+        (OutlineStyle::Text, true) => format!("[x] {}{}", number, content),
+        (OutlineStyle::Markdown, false) => format!("- {}{}", number, content),
+        (OutlineStyle::Markdown, true) => format!("- {}~~{}~~", number, content),
+    }
+}
+
+const HTML_STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; }
+ul { list-style: none; padding-left: 1.4em; }
+summary { cursor: pointer; }
+.done { text-decoration: line-through; color: #888; }
+.number { color: #888; }
+.tag { background: #e4e4f0; border-radius: 3px; padding: 0 4px; font-size: 85%; }
+table { border-collapse: collapse; }
+td, th { padding: 2px 10px; text-align: left; }
+";
+
+// a self-contained page with every anchor as a nested list that can be
+// folded, and the arrows between nodes as a table of links to their ends
+pub fn html(screen: &Screen) -> String {
+    write_html(screen, &[])
+}
+
+fn write_html(screen: &Screen, external: &[(String, String)]) -> String {
+    let title = screen.with_node(0, |n| n.content.clone()).unwrap_or_default();
+    let mut out = String::new();
+    write!(
+        out,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+         <style>{}</style>\n</head>\n<body>\n<h1>{}</h1>\n<ul>\n",
+        html_escape(&title),
+        HTML_STYLE,
+        html_escape(&title)
+    ).unwrap();
+    let anchors = screen.with_node(0, |n| n.children.clone()).unwrap_or_default();
+    let numbers = numbers(screen);
+    let linked = linked(screen);
+    for anchor in anchors {
+        write_html_node(screen, anchor, &numbers, &linked, &mut out);
+    }
+    out.push_str("</ul>\n");
+
+    if !screen.arrows.is_empty() {
+        out.push_str("<h2>links</h2>\n<table>\n<tr><th>from</th><th>to</th></tr>\n");
+        let link = |node_id| {
+            format!("<a href=\"#{}\">{}</a>",
+                    node_anchor(node_id),
+                    html_escape(&node_path(screen, node_id)))
+        };
+        for &(from, to) in &screen.arrows {
+            writeln!(out, "<tr><td>{}</td><td>{}</td></tr>", link(from), link(to)).unwrap();
+        }
+        out.push_str("</table>\n");
+    }
+    if !external.is_empty() {
+        out.push_str("<h2>external references</h2>\n<table>\n<tr><th>from</th><th>to</th></tr>\n");
+        for &(ref from, ref to) in external {
+            writeln!(out,
+                     "<tr><td>{}</td><td>{}</td></tr>",
+                     html_escape(from),
+                     html_escape(to))
+                .unwrap();
+        }
+        out.push_str("</table>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn write_html_node(
+    screen: &Screen,
+    node_id: NodeID,
+    numbers: &HashMap<NodeID, String>,
+    linked: &HashSet<NodeID>,
+    out: &mut String,
+) {
+    let node = match screen.nodes.get(&node_id) {
+        Some(node) => node,
+        None => return,
+    };
+    let li = if linked.contains(&node_id) {
+        format!("<li id=\"{}\">", node_anchor(node_id))
+    } else {
+        "<li>".to_owned()
+    };
+    let mut label = String::new();
+    for (i, word) in node.content.split(' ').enumerate() {
+        if i > 0 {
+            label.push(' ');
+        }
+        if word.len() > 1 && word.starts_with('#') {
+            write!(label, "<span class=\"tag\">{}</span>", html_escape(&word[1..])).unwrap();
+        } else {
+            label.push_str(&html_escape(word));
+        }
+    }
+    if node.stricken {
+        label = format!("<span class=\"done\">{}</span>", label);
+    }
+    if let Some(number) = numbers.get(&node_id) {
+        label = format!("<span class=\"number\">{}</span> {}", number, label);
+    }
+
+    if node.children.is_empty() {
+        writeln!(out, "{}{}</li>", li, label).unwrap();
+    } else {
+        let open = if node.collapsed { "" } else { " open" };
+        writeln!(out, "{}<details{}><summary>{}</summary>\n<ul>", li, open, label).unwrap();
+        for &child in &node.children {
+            write_html_node(screen, child, numbers, linked, out);
+        }
+        out.push_str("</ul></details></li>\n");
+    }
+}
+
+// names of the node and its ancestors below the root, outermost first
+pub fn node_path(screen: &Screen, node_id: NodeID) -> String {
+    let mut names = vec![];
+    let mut cursor = node_id;
+    while let Some(node) = screen.nodes.get(&cursor) {
+        if cursor == 0 {
+            break;
+        }
+        names.push(node.content.clone());
+        cursor = node.parent_id;
+    }
+    names.reverse();
+    names.join(" › ")
+}
+
+fn html_escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+fn outline_fixture() -> Screen {
+    let mut screen = Screen::default();
+    let nodes = [(0, 1, "plan"), (1, 2, "buy"), (2, 3, "milk"), (1, 4, "cook")];
+    for &(parent_id, id, content) in &nodes {
+        let node = Node {
+            id,
+            parent_id,
+            content: content.to_owned(),
+            stricken: id == 3,
+            ..Node::default()
+        };
+        screen.nodes.insert(id, node);
+        screen.nodes.get_mut(&parent_id).unwrap().children.push(id);
+    }
+    screen
+}
+
+#[test]
+fn test_outline() {
+    let screen = outline_fixture();
+    assert_eq!(outline(&screen, 1, OutlineStyle::Text),
+               "plan\n  buy\n    [x] milk\n  cook\n");
+    assert_eq!(outline(&screen, 2, OutlineStyle::Markdown),
+               "- buy\n  - ~~milk~~\n");
+
+    let mut screen = screen;
+    screen.nodes.get_mut(&4).unwrap().meta.estimate = Some(2 * 60 * 60);
+    assert_eq!(outline(&screen, 4, OutlineStyle::Markdown), "- cook (est 2h)\n");
+}
+
+#[test]
+fn test_markdown_titles() {
+    let mut screen = outline_fixture();
+    assert_eq!(markdown(&screen), "- plan\n  - buy\n    - ~~milk~~\n  - cook\n");
+    screen.nodes.get_mut(&1).unwrap().title = Some("this week".to_owned());
+    assert!(markdown(&screen).starts_with("## this week\n\n- plan\n"));
+    assert!(!text(&screen).contains("this week"));
+}
+
+#[test]
+fn test_numbered_exports() {
+    let mut screen = outline_fixture();
+    screen.config.numbering = true;
+    assert_eq!(markdown(&screen), "- 1 plan\n  - 1.1 buy\n    - 1.1.1 ~~milk~~\n  - 1.2 cook\n");
+    assert_eq!(outline(&screen, 4, OutlineStyle::Text), "1.2 cook\n");
+
+    // hidden completed nodes aren't counted, and the rest close up
+    screen.nodes.get_mut(&2).unwrap().hide_stricken = true;
+    screen.nodes.get_mut(&1).unwrap().children = vec![4, 2];
+    assert_eq!(text(&screen), "1 plan\n  1.1 cook\n  1.2 buy\n    [x] milk\n");
+    assert!(html(&screen).contains("<summary><span class=\"number\">1.2</span> buy</summary>"));
+
+    // a subtree is numbered as if it were the only anchor
+    assert_eq!(export_as(&screen, ExportFormat::Markdown, Some(2)),
+               "- 1 buy\n  - ~~milk~~\n");
+}
+
+#[test]
+fn test_html() {
+    let mut screen = outline_fixture();
+    screen.nodes.get_mut(&2).unwrap().collapsed = true;
+    screen.nodes.get_mut(&4).unwrap().content = "<script>alert('hi')</script> #x&y".to_owned();
+    screen.arrows.push((3, 4));
+
+    let html = html(&screen);
+    assert!(!html.contains("<script>"));
+    assert!(html.contains("&lt;script&gt;alert(&#39;hi&#39;)&lt;/script&gt; \
+                           <span class=\"tag\">x&amp;y</span>"));
+    assert!(html.contains("<li><details open><summary>plan</summary>"));
+    assert!(html.contains("<li><details><summary>buy</summary>"));
+    assert!(html.contains("<li id=\"node-3\"><span class=\"done\">milk</span></li>"));
+    assert!(html.contains("<tr><td><a href=\"#node-3\">plan › buy › milk</a></td>\
+                           <td><a href=\"#node-4\">plan › &lt;script&gt;"));
+}
+
+#[test]
+fn test_export_subtree() {
+    let mut screen = outline_fixture();
+    for &(parent_id, id, content) in &[(0, 5, "later"), (5, 6, "read")] {
+        let node = Node {
+            id,
+            parent_id,
+            content: content.to_owned(),
+            ..Node::default()
+        };
+        screen.nodes.insert(id, node);
+        screen.nodes.get_mut(&parent_id).unwrap().children.push(id);
+    }
+    screen.arrows.push((3, 4));
+    screen.arrows.push((3, 6));
+
+    let whole = export_as(&screen, ExportFormat::Markdown, None);
+    let (first, _) = whole.split_at(whole.find("- later").unwrap());
+    assert_eq!(export_as(&screen, ExportFormat::Markdown, Some(1)),
+               format!("{}\n## links\n\n- [plan › buy › milk](#node-3) → [plan › cook](#node-4)\n\
+                        \n## external references\n\n- plan › buy › milk → later › read\n",
+                       first));
+
+    // a deeper root is as it was in the whole outline, one level out
+    let text = export_as(&screen, ExportFormat::Text, Some(2));
+    let whole = export_as(&screen, ExportFormat::Text, None);
+    let slice: String = whole.lines()
+        .skip_while(|l| l.trim() != "buy")
+        .take(2)
+        .map(|l| format!("{}\n", &l[2..]))
+        .collect();
+    assert_eq!(text,
+               format!("{}\nexternal references:\n  buy › milk → plan › cook\n  \
+                        buy › milk → later › read\n",
+                       slice));
+
+    let html = export_as(&screen, ExportFormat::Html, Some(5));
+    assert!(html.contains("<summary>later</summary>"));
+    assert!(!html.contains("plan</summary>"));
+    assert!(html.contains("<h2>external references</h2>"));
+    assert!(html.contains("<tr><td>plan › buy › milk</td><td>later › read</td></tr>"));
+
+    screen.nodes.get_mut(&6).unwrap().meta.due = Some(1_700_000_000);
+    let csv = export_as(&screen, ExportFormat::Csv, None);
+    let sliced = export_as(&screen, ExportFormat::Csv, Some(5));
+    assert!(sliced.lines().all(|l| csv.lines().any(|w| w == l)));
+    assert!(sliced.contains("read") && !sliced.contains("milk"));
+
+    assert_eq!(find_root(&screen, "5"), Ok(5));
+    assert_eq!(find_root(&screen, "cook"), Ok(4));
+    assert!(find_root(&screen, "nothing").is_err());
+}
+
+#[test]
+fn test_links() {
+    let mut screen = outline_fixture();
+    for &(parent_id, id, content) in &[(0, 5, "later"), (5, 6, "read")] {
+        let node = Node {
+            id,
+            parent_id,
+            content: content.to_owned(),
+            ..Node::default()
+        };
+        screen.nodes.insert(id, node);
+        screen.nodes.get_mut(&parent_id).unwrap().children.push(id);
+    }
+    // buy and cook are both under plan
+    screen.arrows = vec![(3, 4), (2, 4), (6, 3)];
+    assert_eq!(markdown(&screen), include_str!("../test/golden/links.md"));
+    assert_eq!(text(&screen), include_str!("../test/golden/links.txt"));
+
+    let html = html(&screen);
+    assert!(html.contains("<li id=\"node-2\"><details open><summary>buy</summary>"));
+    assert!(html.contains("<tr><td><a href=\"#node-2\">plan › buy</a></td>\
+                           <td><a href=\"#node-4\">plan › cook</a></td></tr>"));
+    assert!(html.contains("<li><details open><summary>later</summary>"));
+}
@@ -0,0 +1,374 @@
+use std::cell::{Cell, RefCell};
+use std::cmp::max;
+
+use unicode_segmentation::UnicodeSegmentation;
+use termion::{cursor, style};
+
+use Coords;
+
+// everything the screen prints goes through here, so that a draw can be
+// collected into a grid and only the cells that changed sent to the terminal
+macro_rules! out {
+    ($($arg:tt)*) => { ::frame::emit(&format!($($arg)*)) }
+}
+
+// unchanged cells between two changed ones on a row are sent again rather
+// than moving the cursor past them when there are at most this many
+const GAP: usize = 4;
+
+thread_local! {
+    static CAPTURES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    static STALE: Cell<bool> = const { Cell::new(false) };
+}
+
+// run f, returning what it printed instead of printing it
+pub fn capture<F>(f: F) -> String
+    where F: FnOnce()
+{
+    CAPTURES.with(|c| c.borrow_mut().push(String::new()));
+    f();
+    CAPTURES.with(|c| c.borrow_mut().pop().unwrap_or_default())
+}
+
+// print, or add to the innermost capture. false if it was printed.
+pub fn write(text: &str) -> bool {
+    let captured = CAPTURES.with(|c| match c.borrow_mut().last_mut() {
+        Some(buf) => {
+            buf.push_str(text);
+            true
+        }
+        None => false,
+    });
+    if !captured {
+        print!("{}", text);
+    }
+    captured
+}
+
+// anything printed outside of a draw, like a prompt, leaves the terminal
+// looking different from the last frame
+pub fn emit(text: &str) {
+    if !write(text) {
+        STALE.with(|s| s.set(true));
+    }
+}
+
+// whether something was printed outside of a draw since this was last asked
+pub fn take_stale() -> bool {
+    STALE.with(|s| s.replace(false))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Glyph {
+    text: String,
+    // the escapes in effect since the last reset
+    style: String,
+}
+
+impl Glyph {
+    fn is_blank(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+}
+
+impl Default for Glyph {
+    fn default() -> Glyph {
+        Glyph {
+            text: " ".to_owned(),
+            style: String::new(),
+        }
+    }
+}
+
+// the characters on the terminal, as left by a frame's output
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid {
+    pub dims: Coords,
+    cells: Vec<Glyph>,
+    // escapes that aren't about cells, like hiding the cursor
+    controls: String,
+}
+
+impl Grid {
+    pub fn new(dims: Coords) -> Grid {
+        Grid {
+            dims,
+            cells: vec![Glyph::default(); dims.0 as usize * dims.1 as usize],
+            controls: String::new(),
+        }
+    }
+
+    // follow output the way the terminal would, leaving off anything drawn
+    // past the edges
+    pub fn paint(&mut self, output: &str) {
+        let (mut x, mut y) = (1, 1);
+        let mut pen = String::new();
+        let mut rest = output;
+        while let Some(c) = rest.chars().next() {
+            match c {
+                '\x1b' => {
+                    let len = escape_len(rest);
+                    let seq = &rest[..len];
+                    rest = &rest[len..];
+                    if !seq.starts_with("\x1b[") || len < 3 {
+                        self.controls.push_str(seq);
+                        continue;
+                    }
+                    let params = &seq[2..len - 1];
+                    match seq.chars().last().unwrap() {
+                        'H' => {
+                            let mut nums = params.split(';').map(|n| n.parse().unwrap_or(1));
+                            y = nums.next().unwrap_or(1).max(1);
+                            x = nums.next().unwrap_or(1).max(1);
+                        }
+                        'J' if params == "2" => self.blank(1, 1, usize::MAX, &pen),
+                        'J' if params.is_empty() || params == "0" => {
+                            self.blank(x, y, usize::MAX, &pen)
+                        }
+                        'K' if params == "2" => self.blank(1, y, self.dims.0 as usize, &pen),
+                        'K' if params.is_empty() || params == "0" => {
+                            let until = (self.dims.0 as usize + 1).saturating_sub(x);
+                            self.blank(x, y, until, &pen)
+                        }
+                        'm' if params.is_empty() || params == "0" => pen.clear(),
+                        'm' => pen.push_str(seq),
+                        _ => self.controls.push_str(seq),
+                    }
+                }
+                '\n' => {
+                    y += 1;
+                    rest = &rest[1..];
+                }
+                '\r' => {
+                    x = 1;
+                    rest = &rest[1..];
+                }
+                '\x07' => {
+                    self.controls.push(c);
+                    rest = &rest[1..];
+                }
+                c if c.is_control() => rest = &rest[c.len_utf8()..],
+                _ => {
+                    let end = rest.find(|c: char| c.is_control()).unwrap_or(rest.len());
+                    for grapheme in UnicodeSegmentation::graphemes(&rest[..end], true) {
+                        if let Some(idx) = self.index(x, y) {
+                            self.cells[idx] = Glyph {
+                                text: grapheme.to_owned(),
+                                style: pen.clone(),
+                            };
+                        }
+                        x += 1;
+                    }
+                    rest = &rest[end..];
+                }
+            }
+        }
+    }
+
+    // what to print to turn a terminal showing this grid into one showing
+    // next, with the cursor moved only to the start of each run of changes
+    pub fn diff(&self, next: &Grid) -> String {
+        let mut out = String::new();
+        if next.controls != self.controls {
+            out.push_str(&next.controls);
+        }
+        let width = next.dims.0 as usize;
+        let changed = |x: usize, y: usize| {
+            let idx = y * width + x;
+            self.cells.get(idx) != next.cells.get(idx)
+        };
+        let mut pen: Option<&str> = None;
+        for y in 0..next.dims.1 as usize {
+            let mut x = 0;
+            while x < width {
+                if !changed(x, y) {
+                    x += 1;
+                    continue;
+                }
+                out.push_str(&format!("{}", cursor::Goto(x as u16 + 1, y as u16 + 1)));
+                loop {
+                    let cell = &next.cells[y * width + x];
+                    if pen != Some(cell.style.as_str()) {
+                        out.push_str(&format!("{}{}", style::Reset, cell.style));
+                        pen = Some(cell.style.as_str());
+                    }
+                    out.push_str(&cell.text);
+                    x += 1;
+                    match (x..width).find(|&x| changed(x, y)) {
+                        Some(next_change) if next_change - x <= GAP => {}
+                        _ => break,
+                    }
+                }
+            }
+        }
+        if pen.is_some() {
+            out.push_str(&format!("{}", style::Reset));
+        }
+        out
+    }
+
+    // another grid's cells laid over this one's from column x on, for
+    // panes drawn on their own
+    pub fn blit(&mut self, other: &Grid, x: u16) {
+        let width = other.dims.0 as usize;
+        for (idx, cell) in other.cells.iter().enumerate() {
+            let (cx, cy) = (x as usize + idx % width.max(1), 1 + idx / width.max(1));
+            if let Some(to) = self.index(cx, cy) {
+                self.cells[to] = cell.clone();
+            }
+        }
+        self.controls.push_str(&other.controls);
+    }
+
+    // the smallest part of the grid that holds every visible character
+    pub fn crop(&self) -> Grid {
+        let width = self.dims.0 as usize;
+        let shown: Vec<(usize, usize)> = self.cells
+            .iter()
+            .enumerate()
+            .filter(|&(_, cell)| !cell.is_blank())
+            .map(|(idx, _)| (idx % width, idx / width))
+            .collect();
+        let left = shown.iter().map(|&(x, _)| x).min().unwrap_or(0);
+        let right = shown.iter().map(|&(x, _)| x + 1).max().unwrap_or(0);
+        let top = shown.iter().map(|&(_, y)| y).min().unwrap_or(0);
+        let bottom = shown.iter().map(|&(_, y)| y + 1).max().unwrap_or(0);
+        let mut cropped = Grid::new(((right - left) as u16, (bottom - top) as u16));
+        for y in top..bottom {
+            for x in left..right {
+                cropped.cells[(y - top) * (right - left) + x - left] =
+                    self.cells[y * width + x].clone();
+            }
+        }
+        cropped
+    }
+
+    // the rows as lines of text without trailing blanks, keeping their
+    // styles if styled
+    pub fn text(&self, styled: bool) -> String {
+        let mut out = String::new();
+        for row in self.cells.chunks(max(self.dims.0 as usize, 1)) {
+            let end = row.iter().rposition(|cell| !cell.is_blank()).map_or(0, |i| i + 1);
+            let mut pen = "";
+            for cell in &row[..end] {
+                if styled && pen != cell.style {
+                    out.push_str(&format!("{}{}", style::Reset, cell.style));
+                    pen = &cell.style;
+                }
+                out.push_str(&cell.text);
+            }
+            if !pen.is_empty() {
+                out.push_str(&format!("{}", style::Reset));
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    // the escapes that a cell was drawn with, since the last reset
+    #[cfg(test)]
+    pub fn style_at(&self, (x, y): Coords) -> Option<&str> {
+        self.index(x as usize, y as usize).map(|idx| self.cells[idx].style.as_str())
+    }
+
+    fn index(&self, x: usize, y: usize) -> Option<usize> {
+        let (width, height) = (self.dims.0 as usize, self.dims.1 as usize);
+        if (1..=width).contains(&x) && (1..=height).contains(&y) {
+            Some((y - 1) * width + x - 1)
+        } else {
+            None
+        }
+    }
+
+    // clear len cells from (x, y) onwards, wrapping onto the rows below
+    fn blank(&mut self, x: usize, y: usize, len: usize, pen: &str) {
+        let start = match self.index(x, y) {
+            Some(start) => start,
+            None => return,
+        };
+        for cell in self.cells.iter_mut().skip(start).take(len) {
+            *cell = Glyph {
+                text: " ".to_owned(),
+                style: pen.to_owned(),
+            };
+        }
+    }
+}
+
+// the length of the escape sequence at the start of text
+fn escape_len(text: &str) -> usize {
+    let mut chars = text.char_indices().skip(1);
+    match chars.next() {
+        Some((_, '[')) => {
+            chars.find(|&(_, c)| ('@'..='~').contains(&c))
+                .map(|(i, c)| i + c.len_utf8())
+                .unwrap_or_else(|| text.len())
+        }
+        Some((i, c)) => i + c.len_utf8(),
+        None => text.len(),
+    }
+}
+
+#[test]
+fn test_grid_diff() {
+    let mut last = Grid::new((10, 3));
+    last.paint(&format!("{}{}hello{}", cursor::Goto(2, 2), style::Invert, style::Reset));
+    assert_eq!(last.cells[11].text, "h");
+    assert_eq!(last.cells[11].style, format!("{}", style::Invert));
+    assert_eq!(last.cells[16].style, "");
+
+    // drawn past the right edge and cleared lines
+    let mut next = last.clone();
+    next.paint(&format!("{}abcdefghijkl\x1b[K", cursor::Goto(5, 1)));
+    assert_eq!(next.cells[9].text, "f");
+    assert_eq!(next.cells[10].text, " ");
+    next.paint(&format!("{}{}", cursor::Goto(1, 2), "\x1b[2K"));
+    assert!(next.cells[10..20].iter().all(|c| *c == Glyph::default()));
+
+    assert_eq!(last.diff(&last), "");
+    let mut one = last.clone();
+    one.paint(&format!("{}!", cursor::Goto(8, 2)));
+    assert_eq!(last.diff(&one), format!("{}{}!{}", cursor::Goto(8, 2), style::Reset, style::Reset));
+
+    // nearby changes share one cursor movement, far apart ones don't
+    let mut two = last.clone();
+    two.paint(&format!("{}a{}b", cursor::Goto(1, 1), cursor::Goto(4, 1)));
+    assert_eq!(last.diff(&two).matches("\x1b[1;").count(), 1);
+    two.paint(&format!("{}c", cursor::Goto(10, 1)));
+    assert_eq!(last.diff(&two).matches("\x1b[1;").count(), 2);
+
+    // controls are only sent again when they change
+    let mut hidden = last.clone();
+    hidden.paint(&format!("{}", cursor::Hide));
+    assert_eq!(last.diff(&hidden), format!("{}", cursor::Hide));
+    let mut again = Grid::new((10, 3));
+    again.paint(&format!("{}{}hello{}{}", cursor::Goto(2, 2), style::Invert, style::Reset,
+                         cursor::Hide));
+    assert_eq!(hidden.diff(&again), "");
+}
+
+#[test]
+fn test_grid_blit() {
+    let mut grid = Grid::new((7, 2));
+    grid.paint("abcdefg\r\nhijklmn");
+    let mut pane = Grid::new((3, 1));
+    pane.paint("\x1b[1mxyz\x1b[?25l");
+    grid.blit(&pane, 4);
+    assert_eq!(grid.text(false), "abcxyzg\nhijklmn\n");
+    assert_eq!(grid.style_at((5, 1)), Some("\x1b[1m"));
+    grid.blit(&pane, 6);
+    assert_eq!(grid.text(false), "abcxyxy\nhijklmn\n");
+}
+
+#[test]
+fn test_grid_crop() {
+    let mut grid = Grid::new((10, 4));
+    grid.paint(&format!("{}ab{}{}c{}", cursor::Goto(3, 2), cursor::Goto(4, 3), style::Bold,
+                        style::Reset));
+    let cropped = grid.crop();
+    assert_eq!(cropped.dims, (2, 2));
+    assert_eq!(cropped.text(false), "ab\n c\n");
+    assert_eq!(cropped.text(true), format!("ab\n {}{}c{}\n", style::Reset, style::Bold,
+                                           style::Reset));
+    assert_eq!(Grid::new((3, 3)).crop().text(true), "");
+}
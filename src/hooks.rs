@@ -0,0 +1,105 @@
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use Config;
+
+// when a command from the key file is run, with the work file's path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    // before the work file is read, as when opening it in a tab
+    Open,
+    // after the work file is written
+    Save,
+    // after the last save on the way out
+    Exit,
+}
+
+impl Hook {
+    // the key file line that sets it
+    pub fn name(self) -> &'static str {
+        match self {
+            Hook::Open => "on_open",
+            Hook::Save => "on_save",
+            Hook::Exit => "on_exit",
+        }
+    }
+}
+
+// runs the configured command, if there is one, logging what it prints. a
+// failure is only warned about, as whatever it was run around goes on.
+pub fn run_hook(config: &Config, hook: Hook, path: &str) {
+    let command = match config.hook(hook) {
+        Some(command) => command,
+        None => return,
+    };
+    debug!("running {} hook for {}", hook.name(), path);
+    if let Err(e) = run(command, path, config.hook_timeout) {
+        warn!("{} hook failed: {}", hook.name(), e);
+    }
+}
+
+// `sh -c "<command> <path>"`, with the path passed as an argument so it
+// needs no quoting. stdin is null so that it can't read keys meant for
+// void, and what it prints goes to the log instead of over the screen.
+pub fn run(command: &str, path: &str, timeout: Duration) -> Result<(), String> {
+    let deadline = Instant::now() + timeout;
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$1\"", command))
+        .arg("void")
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run {}: {}", command, e))?;
+    // read as it's printed, so a chatty command can't fill a pipe and stall
+    let readers = vec![child.stdout.take().map(read_all), child.stderr.take().map(read_all)];
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) => {}
+            Err(e) => return Err(format!("failed to wait for {}: {}", command, e)),
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            // what it started may still hold the pipes, so they're left
+            // unread
+            return Err(format!("{} timed out after {}s", command, timeout.as_secs()));
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    for reader in readers.into_iter().flatten() {
+        for line in reader.join().unwrap_or_default().lines() {
+            debug!("{}: {}", command, line);
+        }
+    }
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} exited with {}", command, status))
+    }
+}
+
+fn read_all<R: Read + Send + 'static>(mut from: R) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut text = String::new();
+        let _ = from.read_to_string(&mut text);
+        text
+    })
+}
+
+#[test]
+fn test_run() {
+    let timeout = Duration::from_secs(5);
+    assert_eq!(run("test \"$0\" = void && test", "/tmp/notes.db", timeout), Ok(()));
+    assert_eq!(run("false", "/tmp/notes.db", timeout),
+               Err("false exited with exit status: 1".to_owned()));
+
+    let start = Instant::now();
+    assert!(run("sleep 5; true", "x", Duration::from_millis(100)).is_err());
+    assert!(start.elapsed() < Duration::from_secs(4));
+}
@@ -0,0 +1,482 @@
+use std::io::{Error, ErrorKind, Read, Result, stdin};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use libc::{POLLIN, poll, pollfd};
+use termion::event::{self, Event, Key, MouseButton, MouseEvent};
+
+// sent by the terminal around pasted text once bracketed paste is enabled
+pub const ENABLE_BRACKETED_PASTE: &str = "\x1b[?2004h";
+pub const DISABLE_BRACKETED_PASTE: &str = "\x1b[?2004l";
+// undoes what termion's MouseTerminal turns on, for terminals without a mouse
+pub const DISABLE_MOUSE: &str = "\x1b[?1006l\x1b[?1015l\x1b[?1002l\x1b[?1000l";
+const PASTE_START: &[u8] = b"\x1b[200~";
+const PASTE_END: &[u8] = b"\x1b[201~";
+
+// how often the event loop wakes up without any input
+pub const TICK_MS: u64 = 250;
+// how long the reader thread waits on stdin before checking it should stop
+const POLL_MS: i32 = 50;
+
+lazy_static! {
+    static ref STDIN: Mutex<()> = Mutex::new(());
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Input {
+    Event(Event),
+    // a bracketed paste, kept whole rather than replayed as keypresses
+    Paste(String),
+    // sent after every batch of input, and on its own every TICK_MS when
+    // nothing arrives, for timers and anything else that runs on a clock
+    Tick,
+}
+
+#[derive(Debug, Default)]
+pub struct InputParser {
+    // bytes of a paste whose end marker hasn't arrived yet
+    paste: Option<Vec<u8>>,
+}
+
+impl InputParser {
+    pub fn feed(&mut self, bytes: &[u8]) -> Vec<Input> {
+        let mut inputs = vec![];
+        let mut bytes = bytes.to_vec();
+        loop {
+            if let Some(mut paste) = self.paste.take() {
+                paste.extend_from_slice(&bytes);
+                match find(&paste, PASTE_END) {
+                    Some(pos) => {
+                        let text = String::from_utf8_lossy(&paste[..pos]).into_owned();
+                        inputs.push(Input::Paste(text));
+                        bytes = paste[pos + PASTE_END.len()..].to_vec();
+                    }
+                    None => {
+                        self.paste = Some(paste);
+                        return inputs;
+                    }
+                }
+            } else {
+                let start = find(&bytes, PASTE_START);
+                let keys = &bytes[..start.unwrap_or(bytes.len())];
+                let mut pos = 0;
+                while pos < keys.len() {
+                    let mut iter = keys[pos + 1..].iter().map(|&b| Ok(b));
+                    let parsed = event::parse_event(keys[pos], &mut iter);
+                    let end = keys.len() - iter.len();
+                    let evt = match parsed {
+                        Ok(evt) => evt,
+                        // a lone escape is the key itself rather than the
+                        // start of a sequence
+                        Err(_) if &keys[pos..end] == b"\x1b" => Event::Key(Key::Esc),
+                        // sequences termion can't name are still passed
+                        // along, so they can be matched on
+                        Err(_) => Event::Unsupported(keys[pos..end].to_vec()),
+                    };
+                    inputs.push(Input::Event(evt));
+                    pos = end;
+                }
+                match start {
+                    Some(pos) => {
+                        self.paste = Some(vec![]);
+                        bytes = bytes[pos + PASTE_START.len()..].to_vec();
+                    }
+                    None => return inputs,
+                }
+            }
+        }
+    }
+}
+
+// starts an event no terminal could have sent, written out field by field.
+// it's never valid utf-8, so it can't be mistaken for typing.
+const EXACT: u8 = 0xff;
+
+// the bytes a terminal sends for an event, so recorded events can be saved
+// and read back with decode_event. events that terminals can't tell apart,
+// like C-i and Tab, are written in a form of void's own.
+pub fn encode_event(evt: &Event) -> Vec<u8> {
+    let bytes = terminal_bytes(evt);
+    if !bytes.is_empty() && decode_terminal_bytes(&bytes).as_ref() == Some(evt) {
+        bytes
+    } else {
+        exact_bytes(evt)
+    }
+}
+
+fn terminal_bytes(evt: &Event) -> Vec<u8> {
+    let csi = |rest: &str| format!("\x1b[{}", rest).into_bytes();
+    match *evt {
+        Event::Key(Key::Char(c)) => c.to_string().into_bytes(),
+        Event::Key(Key::Ctrl(c)) if c.is_ascii_lowercase() => vec![c as u8 - b'a' + 1],
+        Event::Key(Key::Alt(c)) => format!("\x1b{}", c).into_bytes(),
+        Event::Key(Key::Backspace) => vec![0x7f],
+        Event::Key(Key::Esc) => vec![0x1b],
+        Event::Key(Key::Null) => vec![0],
+        Event::Key(Key::Up) => csi("A"),
+        Event::Key(Key::Down) => csi("B"),
+        Event::Key(Key::Right) => csi("C"),
+        Event::Key(Key::Left) => csi("D"),
+        Event::Key(Key::Home) => csi("H"),
+        Event::Key(Key::End) => csi("F"),
+        Event::Key(Key::Insert) => csi("2~"),
+        Event::Key(Key::Delete) => csi("3~"),
+        Event::Key(Key::PageUp) => csi("5~"),
+        Event::Key(Key::PageDown) => csi("6~"),
+        Event::Key(Key::F(n @ 1..=4)) => format!("\x1bO{}", (b'O' + n) as char).into_bytes(),
+        Event::Key(Key::F(n @ 5..=12)) => {
+            // the numbering skips 16 and 22
+            let code = [15, 17, 18, 19, 20, 21, 23, 24][n as usize - 5];
+            csi(&format!("{}~", code))
+        }
+        Event::Key(_) => vec![],
+        Event::Mouse(MouseEvent::Press(button, x, y)) => {
+            let code = match button {
+                MouseButton::Left => 0,
+                MouseButton::Middle => 1,
+                MouseButton::Right => 2,
+                MouseButton::WheelUp => 64,
+                MouseButton::WheelDown => 65,
+            };
+            csi(&format!("<{};{};{}M", code, x, y))
+        }
+        Event::Mouse(MouseEvent::Release(x, y)) => csi(&format!("<0;{};{}m", x, y)),
+        Event::Mouse(MouseEvent::Hold(x, y)) => csi(&format!("<32;{};{}M", x, y)),
+        Event::Unsupported(ref bytes) => bytes.clone(),
+    }
+}
+
+fn exact_bytes(evt: &Event) -> Vec<u8> {
+    let mut bytes = vec![EXACT];
+    let coords = |bytes: &mut Vec<u8>, x: u16, y: u16| {
+        bytes.extend_from_slice(&[(x >> 8) as u8, x as u8, (y >> 8) as u8, y as u8]);
+    };
+    match *evt {
+        Event::Key(Key::Char(c)) => bytes.extend(format!("c{}", c).bytes()),
+        Event::Key(Key::Alt(c)) => bytes.extend(format!("a{}", c).bytes()),
+        Event::Key(Key::Ctrl(c)) => bytes.extend(format!("^{}", c).bytes()),
+        Event::Key(Key::F(n)) => bytes.extend_from_slice(&[b'f', n]),
+        // the rest of the keys always come out of a terminal the same way
+        Event::Key(_) => return vec![],
+        Event::Mouse(MouseEvent::Press(button, x, y)) => {
+            let button = match button {
+                MouseButton::Left => b'l',
+                MouseButton::Middle => b'm',
+                MouseButton::Right => b'r',
+                MouseButton::WheelUp => b'u',
+                MouseButton::WheelDown => b'd',
+            };
+            bytes.extend_from_slice(&[b'p', button]);
+            coords(&mut bytes, x, y);
+        }
+        Event::Mouse(MouseEvent::Release(x, y)) => {
+            bytes.push(b'r');
+            coords(&mut bytes, x, y);
+        }
+        Event::Mouse(MouseEvent::Hold(x, y)) => {
+            bytes.push(b'h');
+            coords(&mut bytes, x, y);
+        }
+        Event::Unsupported(ref unsupported) => {
+            bytes.push(b'u');
+            bytes.extend_from_slice(unsupported);
+        }
+    }
+    bytes
+}
+
+pub fn decode_event(bytes: &[u8]) -> Option<Event> {
+    match bytes.split_first() {
+        Some((&EXACT, rest)) => decode_exact_bytes(rest),
+        _ => decode_terminal_bytes(bytes),
+    }
+}
+
+fn decode_terminal_bytes(bytes: &[u8]) -> Option<Event> {
+    match InputParser::default().feed(bytes).pop() {
+        Some(Input::Event(evt)) => Some(evt),
+        _ => None,
+    }
+}
+
+fn decode_exact_bytes(bytes: &[u8]) -> Option<Event> {
+    let (&tag, rest) = bytes.split_first()?;
+    let only_char = || {
+        let text = ::std::str::from_utf8(rest).ok()?;
+        let mut chars = text.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Some(c),
+            _ => None,
+        }
+    };
+    let coords = |rest: &[u8]| match *rest {
+        [x1, x2, y1, y2] => {
+            Some(((u16::from(x1) << 8) | u16::from(x2), (u16::from(y1) << 8) | u16::from(y2)))
+        }
+        _ => None,
+    };
+    let evt = match tag {
+        b'c' => Event::Key(Key::Char(only_char()?)),
+        b'a' => Event::Key(Key::Alt(only_char()?)),
+        b'^' => Event::Key(Key::Ctrl(only_char()?)),
+        b'f' if rest.len() == 1 => Event::Key(Key::F(rest[0])),
+        b'p' => {
+            let (&button, rest) = rest.split_first()?;
+            let button = match button {
+                b'l' => MouseButton::Left,
+                b'm' => MouseButton::Middle,
+                b'r' => MouseButton::Right,
+                b'u' => MouseButton::WheelUp,
+                b'd' => MouseButton::WheelDown,
+                _ => return None,
+            };
+            let (x, y) = coords(rest)?;
+            Event::Mouse(MouseEvent::Press(button, x, y))
+        }
+        b'r' => {
+            let (x, y) = coords(rest)?;
+            Event::Mouse(MouseEvent::Release(x, y))
+        }
+        b'h' => {
+            let (x, y) = coords(rest)?;
+            Event::Mouse(MouseEvent::Hold(x, y))
+        }
+        b'u' => Event::Unsupported(rest.to_vec()),
+        _ => return None,
+    };
+    Some(evt)
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+// wait up to timeout_ms for input on stdin, returning whatever arrived
+pub fn read_input(parser: &mut InputParser, timeout_ms: i32) -> Result<Vec<Input>> {
+    let mut fds = [pollfd {
+        fd: 0,
+        events: POLLIN,
+        revents: 0,
+    }];
+    let ready = unsafe { poll(fds.as_mut_ptr(), 1, timeout_ms) };
+    if ready < 0 {
+        let err = Error::last_os_error();
+        return if err.kind() == ErrorKind::Interrupted {
+            Ok(vec![])
+        } else {
+            Err(err)
+        };
+    } else if ready == 0 {
+        return Ok(vec![]);
+    }
+
+    let mut buf = [0; 4096];
+    let n = stdin().read(&mut buf)?;
+    if n == 0 {
+        return Err(Error::new(ErrorKind::UnexpectedEof, "stdin closed"));
+    }
+    Ok(parser.feed(&buf[..n]))
+}
+
+// prompts read stdin directly, holding this so the reader thread doesn't
+// take their keys
+pub fn lock_stdin() -> MutexGuard<'static, ()> {
+    STDIN.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// input read on its own thread and sent over a channel, so the event loop
+// can wait for it with a timeout
+pub struct Reader {
+    inputs: Receiver<Input>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Reader {
+    pub fn stdin() -> Reader {
+        Reader::spawn(|parser| {
+            let _stdin = lock_stdin();
+            read_input(parser, POLL_MS)
+        })
+    }
+
+    // read is called over and over until it fails or the reader is dropped,
+    // so it should return now and then even without input
+    pub fn spawn<F>(mut read: F) -> Reader
+        where F: FnMut(&mut InputParser) -> Result<Vec<Input>> + Send + 'static
+    {
+        let (tx, rx) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+        let thread = thread::spawn(move || {
+            let mut parser = InputParser::default();
+            while !stopped.load(Ordering::SeqCst) {
+                let inputs = match read(&mut parser) {
+                    Ok(inputs) => inputs,
+                    Err(e) => {
+                        debug!("input reader stopping: {}", e);
+                        return;
+                    }
+                };
+                for input in inputs {
+                    if tx.send(input).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        Reader {
+            inputs: rx,
+            stop,
+            thread: Some(thread),
+        }
+    }
+
+    // waits up to tick for input, returning it along with anything else
+    // already waiting, then a Tick. None once the input has ended.
+    pub fn wait(&self, tick: Duration) -> Option<Vec<Input>> {
+        let mut inputs = match self.inputs.recv_timeout(tick) {
+            Ok(input) => vec![input],
+            Err(RecvTimeoutError::Timeout) => vec![],
+            Err(RecvTimeoutError::Disconnected) => return None,
+        };
+        inputs.extend(self.inputs.try_iter());
+        inputs.push(Input::Tick);
+        Some(inputs)
+    }
+}
+
+impl Drop for Reader {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                error!("input reader thread panicked");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_bracketed_paste_parsing() {
+    let mut parser = InputParser::default();
+    assert_eq!(
+        parser.feed(b"a\x1b[200~one\rtwo\x1b[201~b"),
+        vec![Input::Event(Event::Key(Key::Char('a'))),
+             Input::Paste("one\rtwo".to_owned()),
+             Input::Event(Event::Key(Key::Char('b')))]
+    );
+
+    // a paste spread over several reads, end marker included
+    assert_eq!(parser.feed(b"\x1b[200~x\ry\x1b[20"), vec![]);
+    assert_eq!(
+        parser.feed(b"1~\x1b[A"),
+        vec![Input::Paste("x\ry".to_owned()), Input::Event(Event::Key(Key::Up))]
+    );
+
+    assert_eq!(
+        parser.feed(b"\x1b[5;5~q"),
+        vec![Input::Event(Event::Unsupported(b"\x1b[5;5~".to_vec())),
+             Input::Event(Event::Key(Key::Char('q')))]
+    );
+    assert_eq!(parser.feed(b"\x1b"), vec![Input::Event(Event::Key(Key::Esc))]);
+}
+
+#[test]
+fn test_event_encoding() {
+    let events = vec![
+        Event::Key(Key::Char('x')),
+        Event::Key(Key::Char('é')),
+        Event::Key(Key::Char('\n')),
+        Event::Key(Key::Char('\t')),
+        Event::Key(Key::Ctrl('a')),
+        Event::Key(Key::Alt('p')),
+        // what Alt-Enter sends
+        Event::Key(Key::Alt('\r')),
+        Event::Key(Key::Esc),
+        Event::Key(Key::Backspace),
+        Event::Key(Key::Down),
+        Event::Key(Key::Delete),
+        Event::Key(Key::PageUp),
+        Event::Key(Key::F(1)),
+        Event::Key(Key::F(7)),
+        Event::Key(Key::F(12)),
+        Event::Mouse(MouseEvent::Press(MouseButton::Right, 3, 40)),
+        Event::Mouse(MouseEvent::Release(3, 40)),
+        Event::Mouse(MouseEvent::Hold(300, 2)),
+        Event::Unsupported(b"\x1b[5;5~".to_vec()),
+        // these can't be told apart from other keys in what a terminal sends
+        Event::Key(Key::Ctrl('i')),
+        Event::Key(Key::Ctrl('m')),
+        Event::Key(Key::Ctrl('?')),
+        Event::Key(Key::Ctrl('中')),
+        Event::Key(Key::Char('\r')),
+        Event::Key(Key::Alt('[')),
+        Event::Key(Key::F(20)),
+        Event::Key(Key::Null),
+        Event::Mouse(MouseEvent::Press(MouseButton::WheelDown, 0, 65535)),
+        Event::Mouse(MouseEvent::Release(0, 0)),
+        Event::Unsupported(vec![]),
+        Event::Unsupported(b"\x1b[A".to_vec()),
+    ];
+    for evt in events {
+        assert_eq!(decode_event(&encode_event(&evt)), Some(evt.clone()), "{:?}", evt);
+    }
+    // macros saved before the exact form are still read
+    assert_eq!(decode_event(b"\x09"), Some(Event::Key(Key::Char('\t'))));
+    assert_eq!(decode_event(&[EXACT, b'c']), None);
+}
+
+#[test]
+fn test_reader_ticks() {
+    use std::sync::mpsc::channel;
+    use std::time::Instant;
+
+    // a source with nothing to say still gets ticks through
+    let (bytes_tx, bytes_rx) = channel::<Vec<u8>>();
+    let reader = Reader::spawn(move |parser| {
+        match bytes_rx.recv_timeout(Duration::from_millis(5)) {
+            Ok(bytes) => Ok(parser.feed(&bytes)),
+            Err(RecvTimeoutError::Timeout) => Ok(vec![]),
+            Err(RecvTimeoutError::Disconnected) => {
+                Err(Error::new(ErrorKind::UnexpectedEof, "closed"))
+            }
+        }
+    });
+    let start = Instant::now();
+    for _ in 0..3 {
+        assert_eq!(reader.wait(Duration::from_millis(10)), Some(vec![Input::Tick]));
+    }
+    assert!(start.elapsed() >= Duration::from_millis(30));
+
+    // input arrives in order with a tick after each batch, without waiting
+    // out the tick
+    bytes_tx.send(b"ab".to_vec()).unwrap();
+    let start = Instant::now();
+    let mut events = vec![];
+    while events.len() < 2 {
+        let inputs = reader.wait(Duration::from_secs(10)).unwrap();
+        assert_eq!(inputs.last(), Some(&Input::Tick));
+        events.extend(inputs.into_iter().filter(|i| *i != Input::Tick));
+    }
+    assert!(start.elapsed() < Duration::from_secs(10));
+    assert_eq!(
+        events,
+        vec![Input::Event(Event::Key(Key::Char('a'))), Input::Event(Event::Key(Key::Char('b')))]
+    );
+
+    // dropping it stops the thread, which would otherwise keep polling
+    drop(reader);
+
+    // and when the source ends, so do the inputs
+    let (bytes_tx, bytes_rx) = channel::<Vec<u8>>();
+    let reader = Reader::spawn(move |parser| match bytes_rx.recv() {
+        Ok(bytes) => Ok(parser.feed(&bytes)),
+        Err(_) => Err(Error::new(ErrorKind::UnexpectedEof, "closed")),
+    });
+    drop(bytes_tx);
+    assert_eq!(reader.wait(Duration::from_secs(10)), None);
+}
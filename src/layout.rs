@@ -0,0 +1,113 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+use Coords;
+
+// paragraphs wrap at this many columns whatever the screen width, so a node
+// takes up as many rows when the map is arranged as when it's drawn
+pub const WRAP_WIDTH: usize = 60;
+
+// the rows a node's content is drawn on. a single line is left for drawing
+// to clip, while each line of multi-line content is wrapped.
+pub fn lines(content: &str) -> Vec<String> {
+    if !content.contains('\n') {
+        return vec![content.to_owned()];
+    }
+    content.split('\n').flat_map(|line| wrap(line, WRAP_WIDTH)).collect()
+}
+
+// break a line at the last space that fits, or mid-word when a single word
+// is wider than the whole row
+pub fn wrap(line: &str, width: usize) -> Vec<String> {
+    let graphemes: Vec<&str> = UnicodeSegmentation::graphemes(line, true).collect();
+    if width == 0 {
+        return vec![line.to_owned()];
+    }
+    let mut rows = vec![];
+    let mut start = 0;
+    while graphemes.len() - start > width {
+        let end = start + width;
+        match (start + 1..end + 1).rev().find(|&i| graphemes[i] == " ") {
+            Some(space) => {
+                rows.push(graphemes[start..space].concat());
+                start = space + 1;
+            }
+            None => {
+                rows.push(graphemes[start..end].concat());
+                start = end;
+            }
+        }
+    }
+    rows.push(graphemes[start..].concat());
+    rows
+}
+
+// the top left of the rect of size (width, height) closest to near, that
+// lies within the area from top_left to bottom_right inclusive and covers no
+// occupied cell
+pub fn free_spot<F>(
+    occupied: F,
+    (top_left, bottom_right): (Coords, Coords),
+    (width, height): Coords,
+    near: Coords,
+) -> Option<Coords>
+    where F: Fn(Coords) -> bool
+{
+    if width == 0 || height == 0 || bottom_right.0 < top_left.0 + width - 1 ||
+       bottom_right.1 < top_left.1 + height - 1 {
+        return None;
+    }
+    let (last_x, last_y) = (bottom_right.0 + 1 - width, bottom_right.1 + 1 - height);
+    let distance = |(x, y): Coords| {
+        let dx = x as i64 - near.0 as i64;
+        let dy = y as i64 - near.1 as i64;
+        dx * dx + dy * dy
+    };
+    (top_left.1..last_y + 1)
+        .flat_map(|y| (top_left.0..last_x + 1).map(move |x| (x, y)))
+        .filter(|&(x, y)| {
+            (y..y + height).all(|cy| (x..x + width).all(|cx| !occupied((cx, cy))))
+        })
+        .min_by_key(|&(x, y)| (distance((x + width / 2, y + height / 2)), y, x))
+}
+
+#[test]
+fn test_wrap() {
+    assert_eq!(wrap("the quick brown fox", 10), vec!["the quick", "brown fox"]);
+    assert_eq!(wrap("the quick", 9), vec!["the quick"]);
+    assert_eq!(wrap("the quick", 8), vec!["the", "quick"]);
+    assert_eq!(wrap("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+    assert_eq!(wrap("", 4), vec![""]);
+
+    assert_eq!(lines("title"), vec!["title"]);
+    assert_eq!(lines("title\n\nbody"), vec!["title", "", "body"]);
+    let long = "word ".repeat(20);
+    assert_eq!(lines(&format!("title\n{}", long)).len(), 3);
+}
+
+#[test]
+fn test_free_spot() {
+    use std::collections::HashSet;
+
+    let area = ((1, 2), (40, 20));
+    // nothing in the way, so right where it's wanted
+    assert_eq!(free_spot(|_| false, area, (5, 3), (20, 10)), Some((18, 9)));
+    // pushed back inside the area
+    assert_eq!(free_spot(|_| false, area, (5, 3), (0, 0)), Some((1, 2)));
+    assert_eq!(free_spot(|_| false, area, (41, 3), (0, 0)), None);
+
+    // a full canvas but for one gap, which is too short, and another that fits
+    let mut free: HashSet<Coords> = HashSet::new();
+    for x in 30..36 {
+        free.insert((x, 4));
+        free.insert((x, 5));
+    }
+    for x in 3..9 {
+        for y in 15..18 {
+            free.insert((x, y));
+        }
+    }
+    let occupied = |coords| !free.contains(&coords);
+    assert_eq!(free_spot(occupied, area, (5, 3), (33, 5)), Some((4, 15)));
+    assert_eq!(free_spot(occupied, area, (5, 2), (33, 5)), Some((31, 4)));
+    assert_eq!(free_spot(occupied, area, (7, 3), (33, 5)), None);
+}
@@ -15,6 +15,8 @@ extern crate termion;
 extern crate protobuf;
 extern crate libc;
 
+#[macro_use]
+mod frame;
 mod logging;
 mod serialization;
 mod screen;
@@ -28,22 +30,76 @@ mod pb;
 mod config;
 mod tagdb;
 mod dateparse;
+mod trash;
+mod pomodoro;
+mod notify;
+mod export;
+mod clipboard;
+mod input;
+mod url;
+mod session;
+mod command;
+mod sort;
+mod minimap;
+mod menu;
+mod detail;
+mod complete;
+mod layout;
+mod estimate;
+mod merge;
+mod sync;
+mod crypto;
+mod capabilities;
+mod add;
+mod quickadd;
+mod startup;
+mod pane;
+mod perf;
+mod clock;
+mod record;
+mod modal;
+mod numbering;
+mod termsize;
+mod hooks;
+mod error;
+mod substitute;
+mod repair;
 
 use std::cmp;
 use std::collections::HashMap;
 
 use regex::Regex;
 
-pub use serialization::{serialize_screen, deserialize_screen};
+pub use serialization::{serialize_screen, deserialize_screen, deserialize_sealed_screen,
+                        export_ical, export_csv};
+pub use session::Session;
+pub use sort::SortBy;
 pub use screen::Screen;
 pub use node::Node;
 pub use pack::Pack;
-pub use colors::random_fg_color;
+pub use colors::{Rendering, Theme, random_fg_color};
+pub use capabilities::{Capabilities, Glyphs};
 pub use config::{Config, Action};
 pub use logging::init_screen_log;
-pub use meta::Meta;
+pub use meta::{Meta, GpsError, LocationChain, LocationProvider, current_location,
+               format_duration, humanize_age, set_location_refresh};
 pub use tagdb::TagDB;
 pub use dateparse::dateparse;
+pub use trash::{Trash, TrashEntry};
+pub use export::{OutlineStyle, export_as, find_root, html, outline, text as outline_text};
+pub use command::{ExportFormat, expand_home};
+pub use notify::{CommandNotifier, Notifier};
+pub use pomodoro::{Pomodoro, Transition as PomodoroTransition};
+pub use sync::{Peer, Server, SYNC_SECS, exchange};
+pub use crypto::{Key as CryptoKey, is_sealed};
+pub use add::{Added, MAX_LINE, add_lines};
+pub use pane::{Layout as PaneLayout, Side as PaneSide};
+pub use record::{Entry as RecordedEntry, Recorder, load as load_recording,
+                 path as recording_path};
+pub use startup::{Picked, Picker, Recent, Startup, config_dir, resolve as resolve_startup};
+pub use termsize::{FixedSize, SizeSource, TerminalSize};
+pub use hooks::{Hook, run_hook};
+pub use error::Error;
 
 pub type Coords = (u16, u16);
 pub type NodeID = u64;
@@ -1,24 +1,57 @@
+use std::cmp;
 use std::sync::RwLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::env;
 use std::fs::OpenOptions;
 use std::io::Write;
 
-use log::{self, LogRecord, LogLevel, LogLevelFilter, LogMetadata, SetLoggerError};
+use log::{self, LogRecord, LogLevelFilter, LogMetadata, MaxLogLevelFilter, SetLoggerError};
 use time;
 
 struct ScreenLogger;
 
+// a line in the log pane, standing for every time it was logged in a row
+struct Entry {
+    at: String,
+    line: String,
+    repeats: usize,
+}
+
+impl Entry {
+    fn text(&self) -> String {
+        if self.repeats > 1 {
+            format!("{} {} ×{}", self.at, self.line, self.repeats)
+        } else {
+            format!("{} {}", self.at, self.line)
+        }
+    }
+}
+
+// newest first, and only as many as the pane holds. the same line logged
+// again counts up on the last entry instead of pushing the others out.
+fn record(logs: &mut Vec<Entry>, at: String, line: String) {
+    if let Some(last) = logs.first_mut() {
+        if last.line == line {
+            last.at = at;
+            last.repeats += 1;
+            return;
+        }
+    }
+    logs.insert(0, Entry { at, line, repeats: 1 });
+    logs.truncate(5);
+}
+
 impl log::Log for ScreenLogger {
     fn enabled(&self, metadata: &LogMetadata) -> bool {
-        metadata.level() <= LogLevel::Info
+        metadata.level() <= *PANE_LEVEL.read().unwrap()
     }
 
     fn log(&self, record: &LogRecord) {
         if self.enabled(record.metadata()) {
             let line = format!("{} - {}", record.level(), record.args());
-            let mut logs = LOGS.write().unwrap();
-            logs.insert(0, line);
-            logs.truncate(5);
+            let at = time::strftime("%H:%M:%S", &time::now()).unwrap();
+            self::record(&mut LOGS.write().unwrap(), at, line);
+            GENERATION.fetch_add(1, Ordering::SeqCst);
         }
         let line = format!("{} {} {}:{}] {}\n",
                            time::get_time().sec,
@@ -41,14 +74,64 @@ impl log::Log for ScreenLogger {
 pub fn init_screen_log() -> Result<(), SetLoggerError> {
     log::set_logger(|max_log_level| {
         max_log_level.set(LogLevelFilter::Debug);
+        *MAX_LEVEL.write().unwrap() = Some(max_log_level);
         Box::new(ScreenLogger)
     })
 }
 
+// what's shown in the log pane. the log file always gets debug and up, and
+// trace too once it's asked for here
+pub fn set_level(level: LogLevelFilter) {
+    *PANE_LEVEL.write().unwrap() = level;
+    if let Some(ref max_log_level) = *MAX_LEVEL.read().unwrap() {
+        max_log_level.set(cmp::max(level, LogLevelFilter::Debug));
+    }
+}
+
+// goes up whenever what read_logs returns changes, so the pane is only
+// formatted again then. read it before the logs.
+pub fn generation() -> usize {
+    GENERATION.load(Ordering::SeqCst)
+}
+
 pub fn read_logs() -> Vec<String> {
-    LOGS.read().unwrap().clone()
+    LOGS.read().unwrap().iter().map(Entry::text).collect()
 }
 
+static GENERATION: AtomicUsize = AtomicUsize::new(0);
+
 lazy_static! {
-    static ref LOGS: RwLock<Vec<String>> = RwLock::new(vec![]);
+    static ref LOGS: RwLock<Vec<Entry>> = RwLock::new(vec![]);
+    static ref PANE_LEVEL: RwLock<LogLevelFilter> = RwLock::new(LogLevelFilter::Info);
+    static ref MAX_LEVEL: RwLock<Option<MaxLogLevelFilter>> = RwLock::new(None);
+}
+
+#[test]
+fn test_record() {
+    let mut logs = vec![];
+    let texts = |logs: &Vec<Entry>| logs.iter().map(Entry::text).collect::<Vec<_>>();
+    record(&mut logs, "09:00:00".to_owned(), "WARN - no gps".to_owned());
+    assert_eq!(texts(&logs), vec!["09:00:00 WARN - no gps"]);
+
+    // repeats count up in place, taking the time of the latest
+    record(&mut logs, "09:00:01".to_owned(), "WARN - no gps".to_owned());
+    record(&mut logs, "09:00:02".to_owned(), "WARN - no gps".to_owned());
+    assert_eq!(texts(&logs), vec!["09:00:02 WARN - no gps ×3"]);
+
+    // only in a row
+    record(&mut logs, "09:01:00".to_owned(), "INFO - saved".to_owned());
+    record(&mut logs, "09:02:00".to_owned(), "WARN - no gps".to_owned());
+    assert_eq!(texts(&logs),
+               vec!["09:02:00 WARN - no gps", "09:01:00 INFO - saved", "09:00:02 WARN - no gps ×3"]);
+
+    for i in 0..10 {
+        record(&mut logs, "09:03:00".to_owned(), format!("INFO - {}", i));
+    }
+    assert_eq!(logs.len(), 5);
+    for _ in 0..100 {
+        record(&mut logs, "09:04:00".to_owned(), "INFO - 9".to_owned());
+    }
+    assert_eq!(logs.len(), 5);
+    assert_eq!(logs[0].text(), "09:04:00 INFO - 9 ×101");
+    assert_eq!(logs[1].text(), "09:03:00 INFO - 8");
 }
@@ -0,0 +1,126 @@
+use {Action, Coords, NodeID};
+
+// a right click menu, listing actions for the node or the blank spot
+// that was clicked
+#[derive(Debug, Clone)]
+pub struct Menu {
+    // selected before any of the items are performed
+    pub node: Option<NodeID>,
+    // screen coords of the click that opened it
+    pub at: Coords,
+    pub items: Vec<(&'static str, Action)>,
+    pub cursor: usize,
+}
+
+impl Menu {
+    pub fn for_node(node: NodeID, at: Coords, drawing_arrow: bool) -> Menu {
+        let arrow = if drawing_arrow {
+            "Draw arrow to here"
+        } else {
+            "Draw arrow from here"
+        };
+        Menu {
+            node: Some(node),
+            at,
+            items: vec![
+                ("Strike", Action::ToggleCompleted),
+                ("Collapse", Action::ToggleCollapsed),
+                ("Add child", Action::CreateChild),
+                ("Delete", Action::DeleteSelected),
+                (arrow, Action::Arrow),
+                ("Set color", Action::Recolor),
+            ],
+            cursor: 0,
+        }
+    }
+
+    pub fn for_space(at: Coords, can_paste: bool, focused: bool) -> Menu {
+        let mut items = vec![("New anchor here", Action::NewAnchor(at.0, at.1))];
+        if can_paste {
+            items.push(("Paste", Action::YankPasteNode));
+        }
+        if focused {
+            items.push(("Pop up", Action::PopUp));
+        }
+        Menu {
+            node: None,
+            at,
+            items,
+            cursor: 0,
+        }
+    }
+
+    pub fn up(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn down(&mut self) {
+        if self.cursor + 1 < self.items.len() {
+            self.cursor += 1;
+        }
+    }
+
+    // top left corner and size of the box, bordered, kept on a screen of
+    // the given dims. it opens below and right of the click if there's room.
+    pub fn rect(&self, (width, height): Coords) -> (Coords, Coords) {
+        let inner = self.items.iter().map(|&(label, _)| label.len()).max().unwrap_or(0);
+        let w = inner as u16 + 4;
+        let h = self.items.len() as u16 + 2;
+        let x = if self.at.0 + w <= width + 1 {
+            self.at.0
+        } else {
+            (width + 1).saturating_sub(w)
+        };
+        let y = if self.at.1 + h <= height + 1 {
+            self.at.1
+        } else {
+            (height + 1).saturating_sub(h)
+        };
+        ((x.max(1), y.max(1)), (w, h))
+    }
+
+    // the item drawn at screen coords, if any
+    pub fn item_at(&self, dims: Coords, (x, y): Coords) -> Option<usize> {
+        let ((left, top), (w, _)) = self.rect(dims);
+        if x <= left || x >= left + w - 1 || y <= top {
+            return None;
+        }
+        let idx = (y - top - 1) as usize;
+        if idx < self.items.len() {
+            Some(idx)
+        } else {
+            None
+        }
+    }
+
+    pub fn contains(&self, dims: Coords, (x, y): Coords) -> bool {
+        let ((left, top), (w, h)) = self.rect(dims);
+        x >= left && x < left + w && y >= top && y < top + h
+    }
+}
+
+#[test]
+fn test_menu_layout() {
+    let mut menu = Menu::for_node(3, (10, 5), false);
+    assert_eq!(menu.rect((80, 24)), ((10, 5), (24, 8)));
+    assert_eq!(menu.item_at((80, 24), (12, 6)), Some(0));
+    assert_eq!(menu.item_at((80, 24), (12, 11)), Some(5));
+    assert_eq!(menu.item_at((80, 24), (12, 12)), None);
+    assert_eq!(menu.item_at((80, 24), (10, 6)), None);
+    assert!(menu.contains((80, 24), (10, 12)));
+
+    // near the bottom right corner it opens up and to the left
+    menu.at = (75, 20);
+    assert_eq!(menu.rect((80, 24)), ((57, 17), (24, 8)));
+
+    menu.up();
+    assert_eq!(menu.cursor, 0);
+    for _ in 0..10 {
+        menu.down();
+    }
+    assert_eq!(menu.cursor, 5);
+
+    let menu = Menu::for_space((4, 4), false, false);
+    assert_eq!(menu.items.len(), 1);
+    assert_eq!(menu.items[0].1, Action::NewAnchor(4, 4));
+}
@@ -0,0 +1,387 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use {Node, NodeID, Screen};
+use serialization;
+
+// what's kept about a node that was deleted, by its id and ctime, with
+// when it was deleted
+pub type Tombstones = HashMap<(NodeID, u64), u64>;
+
+// the saved parts of two copies of a map combined. trash and archive stay
+// with the copy they were made in, like the rest of its history.
+#[derive(Debug, Default)]
+pub struct Merged {
+    pub max_id: u64,
+    pub nodes: HashMap<NodeID, Node>,
+    pub arrows: Vec<(NodeID, NodeID)>,
+    pub bookmarks: HashMap<u8, NodeID>,
+    pub groups: HashMap<NodeID, String>,
+    pub tombstones: Tombstones,
+}
+
+// one copy's nodes, arrows, bookmarks and groups, which may be renumbered
+// to make room for the other copy's
+struct Side {
+    nodes: HashMap<NodeID, Node>,
+    arrows: Vec<(NodeID, NodeID)>,
+    bookmarks: HashMap<u8, NodeID>,
+    groups: HashMap<NodeID, String>,
+}
+
+impl Side {
+    fn of(screen: &Screen) -> Side {
+        Side {
+            nodes: screen.nodes.clone(),
+            arrows: screen.arrows.clone(),
+            bookmarks: screen.bookmarks.clone(),
+            groups: screen.groups.clone(),
+        }
+    }
+
+    fn renumber(&mut self, from: NodeID, to: NodeID) {
+        let mut node = match self.nodes.remove(&from) {
+            Some(node) => node,
+            None => return,
+        };
+        node.id = to;
+        for child_id in &node.children {
+            if let Some(child) = self.nodes.get_mut(child_id) {
+                child.parent_id = to;
+            }
+        }
+        if let Some(parent) = self.nodes.get_mut(&node.parent_id) {
+            for child_id in &mut parent.children {
+                if *child_id == from {
+                    *child_id = to;
+                }
+            }
+        }
+        self.nodes.insert(to, node);
+        for arrow in &mut self.arrows {
+            if arrow.0 == from {
+                arrow.0 = to;
+            }
+            if arrow.1 == from {
+                arrow.1 = to;
+            }
+        }
+        for node_id in self.bookmarks.values_mut() {
+            if *node_id == from {
+                *node_id = to;
+            }
+        }
+        if let Some(name) = self.groups.remove(&from) {
+            self.groups.insert(to, name);
+        }
+    }
+}
+
+// combines two copies of a map that have been edited apart. a node is the
+// same node in both if it has the same id and ctime, and the copy edited
+// last wins, apart from its children, which are the children from both in
+// an order that agrees with both where it can. a node deleted in either
+// copy stays deleted unless the other copy changed it afterwards. which
+// copy is which makes no difference to the result.
+pub fn merge(a: &Screen, b: &Screen) -> Merged {
+    let (mut a_side, mut b_side) = (Side::of(a), Side::of(b));
+
+    // nodes made separately in each copy can have been given the same id.
+    // the one made later gets a new id.
+    let mut next_id = a.nodes
+        .keys()
+        .chain(b.nodes.keys())
+        .cloned()
+        .chain(vec![a.max_id, b.max_id])
+        .max()
+        .unwrap_or(0) + 1;
+    let mut clashes: Vec<NodeID> = a_side.nodes
+        .iter()
+        .filter(|&(&id, node)| {
+            id != 0 && b_side.nodes.get(&id).map(|other| other.meta.ctime != node.meta.ctime) ==
+                       Some(true)
+        })
+        .map(|(&id, _)| id)
+        .collect();
+    clashes.sort();
+    for id in clashes {
+        if a_side.nodes[&id].meta.ctime > b_side.nodes[&id].meta.ctime {
+            a_side.renumber(id, next_id);
+        } else {
+            b_side.renumber(id, next_id);
+        }
+        next_id += 1;
+    }
+
+    let mut tombstones = a.tombstones.clone();
+    for (&key, &deleted_at) in &b.tombstones {
+        let entry = tombstones.entry(key).or_insert(deleted_at);
+        *entry = (*entry).max(deleted_at);
+    }
+
+    // the copy edited last, unless it was deleted after that
+    let ids: BTreeSet<NodeID> = a_side.nodes.keys().chain(b_side.nodes.keys()).cloned().collect();
+    let mut nodes = HashMap::new();
+    for &id in &ids {
+        let winner = match (a_side.nodes.get(&id), b_side.nodes.get(&id)) {
+            (Some(x), Some(y)) if precedence(x, y) == Ordering::Less => y,
+            (Some(x), _) | (None, Some(x)) => x,
+            (None, None) => continue,
+        };
+        let key = (id, winner.meta.ctime);
+        let deleted = tombstones.get(&key).map(|&at| at >= winner.meta.mtime) == Some(true);
+        if deleted && id != 0 {
+            continue;
+        }
+        tombstones.remove(&key);
+        let mut node = winner.clone();
+        node.selected = false;
+        nodes.insert(id, node);
+    }
+
+    // children whose parents are gone become anchors, as do nodes that were
+    // moved under each other in the two copies, starting with the lowest id
+    let orphans: Vec<NodeID> = nodes.values()
+        .filter(|n| n.id != 0 && !nodes.contains_key(&n.parent_id))
+        .map(|n| n.id)
+        .collect();
+    for id in orphans {
+        nodes.get_mut(&id).unwrap().parent_id = 0;
+    }
+    while let Some(cycle) = find_cycle(&nodes) {
+        let lowest = *cycle.iter().min().unwrap();
+        nodes.get_mut(&lowest).unwrap().parent_id = 0;
+    }
+
+    let mut children: HashMap<NodeID, Vec<Vec<NodeID>>> = HashMap::new();
+    for side in &[&a_side, &b_side] {
+        for node in side.nodes.values() {
+            let placed: Vec<NodeID> = node.children
+                .iter()
+                .cloned()
+                .filter(|c| nodes.get(c).map(|c| c.parent_id) == Some(node.id))
+                .collect();
+            children.entry(node.id).or_default().push(placed);
+        }
+    }
+    let mut unlisted: Vec<&Node> = nodes.values()
+        .filter(|n| {
+            n.id != 0 &&
+            !children.get(&n.parent_id)
+                .map(|lists| lists.iter().any(|l| l.contains(&n.id)))
+                .unwrap_or(false)
+        })
+        .collect();
+    unlisted.sort_by_key(|n| n.id);
+    let unlisted: Vec<(NodeID, NodeID)> = unlisted.iter().map(|n| (n.parent_id, n.id)).collect();
+    for (parent_id, id) in unlisted {
+        children.entry(parent_id).or_default().push(vec![id]);
+    }
+    for node in nodes.values_mut() {
+        node.children = children.get(&node.id).map(|lists| merge_order(lists)).unwrap_or_default();
+    }
+
+    let mut arrows: Vec<(NodeID, NodeID)> = a_side.arrows
+        .iter()
+        .chain(&b_side.arrows)
+        .cloned()
+        .filter(|&(from, to)| nodes.contains_key(&from) && nodes.contains_key(&to))
+        .collect();
+    arrows.sort();
+    arrows.dedup();
+
+    let mut bookmarks = HashMap::new();
+    for (&slot, &node_id) in a_side.bookmarks.iter().chain(&b_side.bookmarks) {
+        if nodes.contains_key(&node_id) {
+            let entry = bookmarks.entry(slot).or_insert(node_id);
+            *entry = (*entry).min(node_id);
+        }
+    }
+
+    // an anchor grouped differently in each copy goes with the name that
+    // sorts first, so that it's the same whichever copy is passed first
+    let mut groups: HashMap<NodeID, String> = HashMap::new();
+    for (&node_id, name) in a_side.groups.iter().chain(&b_side.groups) {
+        if nodes.contains_key(&node_id) {
+            let entry = groups.entry(node_id).or_insert_with(|| name.clone());
+            if *name < *entry {
+                *entry = name.clone();
+            }
+        }
+    }
+
+    Merged {
+        max_id: next_id - 1,
+        nodes,
+        arrows,
+        bookmarks,
+        groups,
+        tombstones,
+    }
+}
+
+// which copy of a node wins: the one edited last, and after that whichever
+// sorts higher by its saved bytes and then its color, so that neither copy
+// is preferred for being passed first
+fn precedence(x: &Node, y: &Node) -> Ordering {
+    x.meta
+        .mtime
+        .cmp(&y.meta.mtime)
+        .then_with(|| serialization::node_bytes(x).cmp(&serialization::node_bytes(y)))
+        .then_with(|| x.color.cmp(&y.color))
+}
+
+// the ids on a loop of parents that never reaches the root, if any
+fn find_cycle(nodes: &HashMap<NodeID, Node>) -> Option<Vec<NodeID>> {
+    let mut ids: Vec<NodeID> = nodes.keys().cloned().collect();
+    ids.sort();
+    let mut rooted = HashSet::new();
+    rooted.insert(0);
+    for id in ids {
+        let mut path = vec![];
+        let mut cursor = id;
+        while !rooted.contains(&cursor) {
+            if let Some(pos) = path.iter().position(|&p| p == cursor) {
+                return Some(path[pos..].to_vec());
+            }
+            path.push(cursor);
+            cursor = nodes[&cursor].parent_id;
+        }
+        rooted.extend(path);
+    }
+    None
+}
+
+// one order for the ids in all of the lists, keeping each pair that's in
+// the same order in every list it's in. where the lists disagree, or don't
+// say, lower ids go first.
+fn merge_order(lists: &[Vec<NodeID>]) -> Vec<NodeID> {
+    let mut before: HashMap<NodeID, BTreeSet<NodeID>> = HashMap::new();
+    let mut after: HashMap<NodeID, BTreeSet<NodeID>> = HashMap::new();
+    let mut remaining = BTreeSet::new();
+    for list in lists {
+        remaining.extend(list.iter().cloned());
+        for pair in list.windows(2) {
+            if pair[0] != pair[1] {
+                before.entry(pair[1]).or_default().insert(pair[0]);
+                after.entry(pair[0]).or_default().insert(pair[1]);
+            }
+        }
+    }
+
+    let mut order = vec![];
+    while !remaining.is_empty() {
+        let next = remaining.iter()
+            .cloned()
+            .find(|id| before.get(id).map(|b| b.is_empty()).unwrap_or(true))
+            .unwrap_or_else(|| *remaining.iter().next().unwrap());
+        remaining.remove(&next);
+        for later in after.remove(&next).unwrap_or_default() {
+            if let Some(b) = before.get_mut(&later) {
+                b.remove(&next);
+            }
+        }
+        order.push(next);
+    }
+    order
+}
+
+#[test]
+fn test_merge_order() {
+    assert_eq!(merge_order(&[vec![1, 2, 3], vec![1, 2, 3]]), vec![1, 2, 3]);
+    assert_eq!(merge_order(&[vec![3, 1], vec![3, 1, 2]]), vec![3, 1, 2]);
+    assert_eq!(merge_order(&[vec![5, 9, 6], vec![5, 7, 6]]), vec![5, 7, 9, 6]);
+    assert_eq!(merge_order(&[vec![5, 7, 6], vec![5, 9, 6]]), vec![5, 7, 9, 6]);
+    assert_eq!(merge_order(&[vec![1, 2], vec![2, 1]]), vec![1, 2]);
+    assert_eq!(merge_order(&[vec![2, 1], vec![1, 2]]), vec![1, 2]);
+    assert_eq!(merge_order(&[]), Vec::<NodeID>::new());
+}
+
+#[test]
+fn test_merge() {
+    let copy = || {
+        let mut screen = Screen::default();
+        screen.max_id = 3;
+        for &(id, parent_id, content) in &[(1, 0, "plans"), (2, 1, "call"), (3, 1, "write")] {
+            let mut node = Node {
+                id,
+                parent_id,
+                content: content.to_owned(),
+                ..Node::default()
+            };
+            node.meta.ctime = 100;
+            node.meta.mtime = 100;
+            screen.nodes.insert(id, node);
+        }
+        screen.nodes.get_mut(&0).unwrap().children = vec![1];
+        screen.nodes.get_mut(&1).unwrap().children = vec![2, 3];
+        screen.nodes.get_mut(&0).unwrap().meta.ctime = 0;
+        screen
+    };
+    // colors aren't saved, and are picked again on load
+    let describe = |nodes: &HashMap<NodeID, Node>| {
+        let mut nodes: Vec<String> = nodes.values()
+            .map(|n| format!("{:?}", Node { color: String::new(), ..n.clone() }))
+            .collect();
+        nodes.sort();
+        nodes
+    };
+
+    // a deletes 3 and adds 4, b edits 2 and adds its own 4
+    let (mut a, mut b) = (copy(), copy());
+    a.nodes.remove(&3);
+    a.nodes.get_mut(&1).unwrap().children = vec![2];
+    a.tombstones.insert((3, 100), 200);
+    for (screen, ctime, content) in [(&mut a, 300, "mine"), (&mut b, 310, "theirs")] {
+        let mut node = Node {
+            id: 4,
+            parent_id: 1,
+            content: content.to_owned(),
+            ..Node::default()
+        };
+        node.meta.ctime = ctime;
+        node.meta.mtime = ctime;
+        screen.nodes.insert(4, node);
+        screen.nodes.get_mut(&1).unwrap().children.push(4);
+        screen.max_id = 4;
+    }
+    b.nodes.get_mut(&2).unwrap().content = "call back".to_owned();
+    b.nodes.get_mut(&2).unwrap().meta.mtime = 250;
+
+    let merged = merge(&a, &b);
+    assert_eq!(describe(&merged.nodes), describe(&merge(&b, &a).nodes));
+    assert!(!merged.nodes.contains_key(&3));
+    assert_eq!(merged.nodes[&2].content, "call back");
+    assert_eq!(merged.nodes[&4].content, "mine");
+    assert_eq!(merged.nodes[&5].content, "theirs");
+    assert_eq!(merged.nodes[&1].children, vec![2, 4, 5]);
+    assert_eq!(merged.max_id, 5);
+
+    // changed after it was deleted elsewhere, so it comes back
+    b.nodes.get_mut(&3).unwrap().meta.mtime = 210;
+    let merged = merge(&a, &b);
+    assert_eq!(merged.nodes[&1].children, vec![2, 3, 4, 5]);
+    assert!(merged.tombstones.is_empty());
+
+    // starting a timer doesn't count as an edit, but still decides a tie
+    b.nodes.get_mut(&1).unwrap().meta.timer_start = Some(260);
+    assert_eq!(describe(&merge(&a, &b).nodes), describe(&merge(&b, &a).nodes));
+    assert_eq!(merge(&a, &b).nodes[&1].meta.timer_start, Some(260));
+
+    // as do tags, places and colors, which sort only as a whole node
+    let (mut x, mut y) = (copy(), copy());
+    x.nodes.get_mut(&2).unwrap().meta.tags.insert("who".to_owned(), "ann".to_owned());
+    y.nodes.get_mut(&2).unwrap().meta.tags.insert("who".to_owned(), "bob".to_owned());
+    x.nodes.get_mut(&3).unwrap().meta.gps = (1.5, 2.5);
+    x.nodes.get_mut(&1).unwrap().color = "red".to_owned();
+    y.nodes.get_mut(&1).unwrap().color = "blue".to_owned();
+    let (xy, yx) = (merge(&x, &y), merge(&y, &x));
+    assert_eq!(describe(&xy.nodes), describe(&yx.nodes));
+    assert_eq!(xy.nodes[&1].color, yx.nodes[&1].color);
+    assert_eq!(xy.nodes[&3].meta.gps, (1.5, 2.5));
+
+    // and merging a copy with itself changes nothing
+    let same = merge(&a, &a);
+    assert_eq!(describe(&same.nodes), describe(&a.nodes));
+    assert_eq!(same.tombstones, a.tombstones);
+}
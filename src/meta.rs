@@ -1,17 +1,111 @@
 use std::env;
-use std::io::{self, Read};
+use std::io::{self, BufRead, BufReader, Read, Write};
 use std::collections::HashMap;
+use std::net::{TcpStream, ToSocketAddrs};
 use std::num;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
 
 use hyper;
 use hyper::client::Client;
-use time;
+use regex::Regex;
+
+use clock;
+
+// how often the location is looked up again by default, in seconds
+pub const LOCATION_REFRESH_SECS: u64 = 15 * 60;
 
 lazy_static! {
-    static ref LOC: (f32, f32) = gps_query().unwrap_or_else(|e| {
-                error!("failed to get gps: {:?}", e);
-                (0.0, 0.0)
-            });
+    static ref LOCATION: Mutex<LocationCache> = Mutex::new(LocationCache::default());
+}
+
+// where we are, as last looked up, shared by every node made in this process
+#[derive(Debug)]
+struct LocationCache {
+    location: Option<(f32, f32)>,
+    // when the last lookup finished, whether or not it worked
+    checked_at: Option<u64>,
+    looking_up: bool,
+    refresh_secs: u64,
+}
+
+impl Default for LocationCache {
+    fn default() -> LocationCache {
+        LocationCache {
+            location: None,
+            checked_at: None,
+            looking_up: false,
+            refresh_secs: LOCATION_REFRESH_SECS,
+        }
+    }
+}
+
+impl LocationCache {
+    fn is_stale(&self, now: u64) -> bool {
+        match self.checked_at {
+            Some(checked_at) => now.saturating_sub(checked_at) >= self.refresh_secs,
+            None => true,
+        }
+    }
+
+    // the location we know of, calling start to look it up again if it's
+    // stale and no lookup is under way
+    fn location_at<F>(&mut self, now: u64, start: F) -> Option<(f32, f32)>
+        where F: FnOnce()
+    {
+        if self.is_stale(now) && !self.looking_up {
+            self.looking_up = true;
+            start();
+        }
+        self.location
+    }
+
+    fn remember(&mut self, location: (f32, f32)) {
+        if self.location.is_none() {
+            self.location = Some(location);
+        }
+    }
+
+    // a failed lookup keeps the old location, and isn't retried any sooner
+    // than a successful one
+    fn looked_up(&mut self, now: u64, result: Result<(f32, f32), GpsError>) {
+        self.looking_up = false;
+        self.checked_at = Some(now);
+        match result {
+            Ok(location) => self.location = Some(location),
+            Err(GpsError::Disabled) => {}
+            Err(e) => warn!("failed to get gps: {:?}", e),
+        }
+    }
+}
+
+// where new nodes are being made, looked up in the background at most every
+// refresh interval. None until the first lookup or a loaded file says.
+pub fn current_location() -> Option<(f32, f32)> {
+    let now = clock::now();
+    LOCATION.lock().unwrap().location_at(now, || {
+        thread::spawn(|| {
+            let result = LocationChain::from_env().locate();
+            LOCATION.lock().unwrap().looked_up(clock::now(), result);
+        });
+    })
+}
+
+// the location as last known, without looking it up
+pub fn last_location() -> Option<(f32, f32)> {
+    LOCATION.lock().unwrap().location
+}
+
+// a location saved by an earlier session, for stamping nodes until a
+// lookup succeeds
+pub fn remember_location(location: (f32, f32)) {
+    LOCATION.lock().unwrap().remember(location);
+}
+
+pub fn set_location_refresh(secs: u64) {
+    LOCATION.lock().unwrap().refresh_secs = secs;
 }
 
 #[derive(Debug, Clone)]
@@ -22,64 +116,366 @@ pub struct Meta {
     pub due: Option<u64>,
     pub gps: (f32, f32),
     pub tags: HashMap<String, String>,
+    // seconds spent on this node over all stopped timer runs
+    pub tracked: u64,
+    // when the currently running timer was started
+    pub timer_start: Option<u64>,
+    // the due time we last notified about
+    pub notified: Option<u64>,
+    // expected seconds of work, from `est:` in the content
+    pub estimate: Option<u64>,
+    // files the node refers to, by path. they're never read, only opened.
+    pub attachments: Vec<String>,
 }
 
 impl Default for Meta {
     fn default() -> Meta {
-        let now = time::get_time().sec as u64;
+        let now = clock::now();
         Meta {
             ctime: now,
             mtime: now,
             finish_time: None,
             due: None,
-            gps: *LOC,
+            gps: current_location().unwrap_or((0.0, 0.0)),
             tags: HashMap::new(),
+            tracked: 0,
+            timer_start: None,
+            notified: None,
+            estimate: None,
+            attachments: vec![],
         }
     }
 }
 
 impl Meta {
     pub fn bump_mtime(&mut self) {
-        self.mtime = time::get_time().sec as u64;
+        self.mtime = clock::now();
     }
 
     pub fn finish(&mut self) {
-        self.finish_time = Some(time::get_time().sec as u64);
+        let now = clock::now();
+        self.stop_timer(now);
+        self.finish_time = Some(now);
     }
 
     pub fn unfinish(&mut self) {
         self.finish_time = None;
     }
 
+    // when the node was last changed. files from before that was kept
+    // have no mtime, so it's when it was made.
+    pub fn touched(&self) -> u64 {
+        if self.mtime == 0 { self.ctime } else { self.mtime }
+    }
+
     pub fn at(&self) -> u64 {
         self.finish_time.unwrap_or(self.mtime)
     }
-}
 
-fn gps_query() -> Result<(f32, f32), GpsError> {
-    if env::var("LOCATION_QUERY").is_err() {
-        info!("GPS lookup disabled. Enable by setting LOCATION_QUERY env var.");
-        return Err(GpsError::Other("GPS lookup disabled".to_owned()));
+    pub fn start_timer(&mut self, now: u64) {
+        if self.timer_start.is_none() {
+            self.timer_start = Some(now);
+        }
+    }
+
+    pub fn stop_timer(&mut self, now: u64) {
+        if let Some(start) = self.timer_start.take() {
+            self.tracked += now.saturating_sub(start);
+        }
+    }
+
+    pub fn timer_running(&self) -> bool {
+        self.timer_start.is_some()
+    }
+
+    // total seconds tracked, including any timer that is still running
+    pub fn tracked_at(&self, now: u64) -> u64 {
+        let running = self.timer_start.map(|start| now.saturating_sub(start)).unwrap_or(0);
+        self.tracked + running
+    }
+
+    // true once per due time, after it has passed
+    pub fn due_unnotified(&self, now: u64) -> bool {
+        match self.due {
+            Some(due) => due <= now && self.notified != Some(due) && self.finish_time.is_none(),
+            None => false,
+        }
     }
-    let client = Client::new();
-    let mut res = client.get("http://ipinfo.io/loc").send()?;
-    let mut text_res = String::new();
-    res.read_to_string(&mut text_res)?;
-    let parts = text_res.trim().split(',').collect::<Vec<_>>();
+}
+
+pub fn format_duration(secs: u64) -> String {
+    format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
 
-    if parts.len() == 2 {
-        let lat = parts[0].parse::<f32>()?;
-        let lon = parts[1].parse::<f32>()?;
-        Ok((lat, lon))
+// how long ago then was, in the largest unit that fits at least once:
+// `5m`, `3h`, `6d`, `2w`, `4mo` or `1y`. under a minute is `now`.
+pub fn humanize_age(now: u64, then: u64) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+    let secs = now.saturating_sub(then);
+    if secs < MINUTE {
+        "now".to_owned()
+    } else if secs < HOUR {
+        format!("{}m", secs / MINUTE)
+    } else if secs < DAY {
+        format!("{}h", secs / HOUR)
+    } else if secs < 7 * DAY {
+        format!("{}d", secs / DAY)
+    } else if secs < 30 * DAY {
+        format!("{}w", secs / (7 * DAY))
+    } else if secs < 365 * DAY {
+        format!("{}mo", secs / (30 * DAY))
     } else {
-        let err_string = format!("unable to parse response: {:?}", text_res);
-        let error = GpsError::Other(err_string);
-        Err(error)
+        format!("{}y", secs / (365 * DAY))
+    }
+}
+
+// how long a provider gets to answer, unless the configuration says
+const LOCATION_TIMEOUT_SECS: u64 = 5;
+
+// somewhere to find out where we are
+pub trait LocationProvider {
+    fn name(&self) -> &str;
+    fn locate(&self, timeout: Duration) -> Result<(f32, f32), GpsError>;
+}
+
+// a guess from the internet-facing IP, which is far off behind a VPN
+pub struct IpInfo {
+    pub url: String,
+}
+
+impl Default for IpInfo {
+    fn default() -> IpInfo {
+        IpInfo { url: "http://ipinfo.io/loc".to_owned() }
+    }
+}
+
+impl LocationProvider for IpInfo {
+    fn name(&self) -> &str {
+        "ipinfo"
+    }
+
+    fn locate(&self, timeout: Duration) -> Result<(f32, f32), GpsError> {
+        let mut client = Client::new();
+        client.set_read_timeout(Some(timeout));
+        client.set_write_timeout(Some(timeout));
+        let mut res = client.get(&*self.url).send()?;
+        let mut text_res = String::new();
+        res.read_to_string(&mut text_res)?;
+        parse_lat_lon(&text_res)
+    }
+}
+
+// a local gpsd, asked to watch in json until it reports a fix
+pub struct Gpsd {
+    pub addr: String,
+}
+
+impl Default for Gpsd {
+    fn default() -> Gpsd {
+        Gpsd { addr: "127.0.0.1:2947".to_owned() }
+    }
+}
+
+impl LocationProvider for Gpsd {
+    fn name(&self) -> &str {
+        "gpsd"
+    }
+
+    fn locate(&self, timeout: Duration) -> Result<(f32, f32), GpsError> {
+        let deadline = Instant::now() + timeout;
+        let addr = self.addr
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| GpsError::Other(format!("no address for {}", self.addr)))?;
+        let mut stream = TcpStream::connect_timeout(&addr, timeout)?;
+        stream.set_read_timeout(Some(timeout))?;
+        stream.write_all(b"?WATCH={\"enable\":true,\"json\":true};\n")?;
+        read_gpsd(BufReader::new(stream), deadline)
+    }
+}
+
+// the first position in gpsd's reports, which start with its version and
+// devices and may have no fix for a while
+fn read_gpsd<R: BufRead>(reader: R, deadline: Instant) -> Result<(f32, f32), GpsError> {
+    lazy_static! {
+        static ref RE_LAT: Regex = Regex::new(r#""lat":(-?[0-9.eE+-]+)"#).unwrap();
+        static ref RE_LON: Regex = Regex::new(r#""lon":(-?[0-9.eE+-]+)"#).unwrap();
+    }
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.contains("\"class\":\"TPV\"") {
+            let field = |re: &Regex| re.captures(&line).and_then(|c| c.at(1)).map(|n| n.parse());
+            if let (Some(lat), Some(lon)) = (field(&RE_LAT), field(&RE_LON)) {
+                return Ok((lat?, lon?));
+            }
+        }
+        if Instant::now() >= deadline {
+            break;
+        }
+    }
+    Err(GpsError::Other("gpsd has no fix".to_owned()))
+}
+
+// whatever a command prints, like CoreLocationCLI on a mac
+pub struct CommandLocation {
+    pub command: String,
+}
+
+impl LocationProvider for CommandLocation {
+    fn name(&self) -> &str {
+        "command"
+    }
+
+    fn locate(&self, timeout: Duration) -> Result<(f32, f32), GpsError> {
+        let deadline = Instant::now() + timeout;
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(&self.command)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(GpsError::Other(format!("{} timed out", self.command)));
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+        if !status.success() {
+            return Err(GpsError::Other(format!("{} failed: {}", self.command, status)));
+        }
+        let mut out = String::new();
+        child.stdout.take().unwrap().read_to_string(&mut out)?;
+        parse_lat_lon(&out)
+    }
+}
+
+// a fixed place from the configuration, for a desk that doesn't move
+pub struct StaticLocation(pub (f32, f32));
+
+impl LocationProvider for StaticLocation {
+    fn name(&self) -> &str {
+        "static"
+    }
+
+    fn locate(&self, _: Duration) -> Result<(f32, f32), GpsError> {
+        Ok(self.0)
+    }
+}
+
+// `lat,lon` or `lat lon`
+fn parse_lat_lon(text: &str) -> Result<(f32, f32), GpsError> {
+    let parts: Vec<&str> = text.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|p| !p.is_empty())
+        .collect();
+    if parts.len() != 2 {
+        return Err(GpsError::Other(format!("unable to parse response: {:?}", text)));
+    }
+    let (lat, lon) = (parts[0].parse::<f32>()?, parts[1].parse::<f32>()?);
+    if lat.abs() > 90.0 || lon.abs() > 180.0 {
+        return Err(GpsError::Other(format!("not on earth: {:?}", text)));
+    }
+    Ok((lat, lon))
+}
+
+// providers tried in order until one knows, each for at most its timeout
+pub struct LocationChain {
+    pub providers: Vec<(Box<LocationProvider>, Duration)>,
+}
+
+impl LocationChain {
+    pub fn from_env() -> LocationChain {
+        LocationChain::probe(|name| env::var(name).ok())
+    }
+
+    // LOCATION_PROVIDERS lists them in order, like `gpsd:2,command,ipinfo`
+    // with optional timeouts in seconds. without it, LOCATION_COMMAND,
+    // LOCATION_QUERY and LOCATION_STATIC turn on the command, ipinfo and
+    // static providers, in that order.
+    pub fn probe<F>(var: F) -> LocationChain
+        where F: Fn(&str) -> Option<String>
+    {
+        let names = var("LOCATION_PROVIDERS").unwrap_or_else(|| {
+            let implied = [("LOCATION_COMMAND", "command"),
+                           ("LOCATION_QUERY", "ipinfo"),
+                           ("LOCATION_STATIC", "static")];
+            implied.iter()
+                .filter(|&&(name, _)| var(name).is_some())
+                .map(|&(_, provider)| provider)
+                .collect::<Vec<_>>()
+                .join(",")
+        });
+
+        let mut providers: Vec<(Box<LocationProvider>, Duration)> = vec![];
+        for entry in names.split(',').map(|e| e.trim()).filter(|e| !e.is_empty()) {
+            let mut parts = entry.splitn(2, ':');
+            let name = parts.next().unwrap();
+            let secs = parts.next()
+                .and_then(|secs| secs.parse().ok())
+                .unwrap_or(LOCATION_TIMEOUT_SECS);
+            let provider: Box<LocationProvider> = match name {
+                "ipinfo" => Box::new(IpInfo::default()),
+                "gpsd" => {
+                    Box::new(Gpsd { addr: var("LOCATION_GPSD").unwrap_or(Gpsd::default().addr) })
+                }
+                "command" => {
+                    match var("LOCATION_COMMAND") {
+                        Some(command) => Box::new(CommandLocation { command }),
+                        None => {
+                            warn!("the command location provider needs LOCATION_COMMAND");
+                            continue;
+                        }
+                    }
+                }
+                "static" => {
+                    match var("LOCATION_STATIC").map(|l| parse_lat_lon(&l)) {
+                        Some(Ok(location)) => Box::new(StaticLocation(location)),
+                        _ => {
+                            warn!("the static location provider needs LOCATION_STATIC=lat,lon");
+                            continue;
+                        }
+                    }
+                }
+                _ => {
+                    warn!("unknown location provider {}", name);
+                    continue;
+                }
+            };
+            providers.push((provider, Duration::from_secs(secs)));
+        }
+        LocationChain { providers }
+    }
+
+    pub fn locate(&self) -> Result<(f32, f32), GpsError> {
+        if self.providers.is_empty() {
+            debug!("GPS lookup disabled. Enable by setting LOCATION_QUERY env var.");
+            return Err(GpsError::Disabled);
+        }
+        let mut last_error = None;
+        for &(ref provider, timeout) in &self.providers {
+            match provider.locate(timeout) {
+                Ok(location) => return Ok(location),
+                Err(e) => {
+                    debug!("location provider {} failed: {:?}", provider.name(), e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error.unwrap())
     }
 }
 
 #[derive(Debug)]
-enum GpsError {
+pub enum GpsError {
+    Disabled,
     Hyper(hyper::Error),
     Io(io::Error),
     Parse(num::ParseFloatError),
@@ -104,3 +500,181 @@ impl From<num::ParseFloatError> for GpsError {
         GpsError::Parse(err)
     }
 }
+
+#[test]
+fn test_timer() {
+    let mut meta = Meta::default();
+    meta.start_timer(100);
+    assert_eq!(meta.tracked_at(100), 0);
+    assert_eq!(meta.tracked_at(160), 60);
+
+    // starting an already running timer keeps the original start
+    meta.start_timer(130);
+    meta.stop_timer(200);
+    assert_eq!(meta.tracked_at(10_000), 100);
+    assert!(!meta.timer_running());
+
+    // a timer that was running when saved keeps counting after a load
+    meta.start_timer(1000);
+    let reloaded = meta.clone();
+    assert_eq!(reloaded.tracked_at(4600), 3700);
+    assert_eq!(format_duration(reloaded.tracked_at(4600)), "1:01:40");
+
+    // clocks that went backwards don't subtract time
+    meta.stop_timer(900);
+    assert_eq!(meta.tracked, 100);
+}
+
+#[test]
+fn test_humanize_age() {
+    let day = 24 * 60 * 60;
+    let ages = [(0, "now"), (59, "now"), (60, "1m"), (3599, "59m"), (3600, "1h"),
+                (day - 1, "23h"), (day, "1d"), (7 * day - 1, "6d"), (7 * day, "1w"),
+                (30 * day - 1, "4w"), (30 * day, "1mo"), (365 * day - 1, "12mo"),
+                (365 * day, "1y"), (800 * day, "2y")];
+    for &(secs, age) in &ages {
+        assert_eq!(humanize_age(1_000_000_000 + secs, 1_000_000_000), age, "{}s", secs);
+    }
+    // a clock that went backwards
+    assert_eq!(humanize_age(100, 200), "now");
+}
+
+#[test]
+fn test_location_cache() {
+    use std::cell::Cell;
+
+    let mut cache = LocationCache {
+        refresh_secs: 60,
+        ..LocationCache::default()
+    };
+    let lookups = Cell::new(0);
+    let lookup = || lookups.set(lookups.get() + 1);
+
+    // one lookup at a time, with what a saved file said until it's done
+    assert_eq!(cache.location_at(1000, lookup), None);
+    cache.remember((9.0, 9.0));
+    assert_eq!(cache.location_at(1001, lookup), Some((9.0, 9.0)));
+    assert_eq!(lookups.get(), 1);
+    cache.looked_up(1002, Ok((1.5, 2.5)));
+    // a lookup beats anything loaded later
+    cache.remember((9.0, 9.0));
+    assert_eq!(cache.location_at(1003, lookup), Some((1.5, 2.5)));
+    assert_eq!(cache.location_at(1061, lookup), Some((1.5, 2.5)));
+    assert_eq!(lookups.get(), 1);
+
+    // stale after the refresh interval, and a failure keeps what was known
+    // until the next interval
+    assert_eq!(cache.location_at(1062, lookup), Some((1.5, 2.5)));
+    assert_eq!(lookups.get(), 2);
+    cache.looked_up(1070, Err(GpsError::Other("offline".to_owned())));
+    assert_eq!(cache.location_at(1100, lookup), Some((1.5, 2.5)));
+    assert_eq!(lookups.get(), 2);
+    cache.location_at(1130, lookup);
+    assert_eq!(lookups.get(), 3);
+}
+
+#[test]
+fn test_parse_lat_lon() {
+    assert_eq!(parse_lat_lon("52.5200,13.4050\n").unwrap(), (52.52, 13.405));
+    assert_eq!(parse_lat_lon("-33.9 151.2").unwrap(), (-33.9, 151.2));
+    assert!(parse_lat_lon("<html>rate limited</html>").is_err());
+    assert!(parse_lat_lon("52.5,13.4,7").is_err());
+    assert!(parse_lat_lon("95.0,13.4").is_err());
+}
+
+#[test]
+fn test_gpsd() {
+    use std::net::TcpListener;
+
+    let session = concat!(
+        r#"{"class":"VERSION","release":"3.17","proto_major":3,"proto_minor":12}"#, "\n",
+        r#"{"class":"DEVICES","devices":[{"class":"DEVICE","path":"/dev/ttyUSB0"}]}"#, "\n",
+        r#"{"class":"WATCH","enable":true,"json":true}"#, "\n",
+        r#"{"class":"TPV","device":"/dev/ttyUSB0","mode":1}"#, "\n",
+        r#"{"class":"SKY","device":"/dev/ttyUSB0","satellites":[]}"#, "\n",
+        r#"{"class":"TPV","device":"/dev/ttyUSB0","mode":3,"#,
+        r#""lat":48.137154,"lon":-11.576124}"#, "\n");
+    let later = Instant::now() + Duration::from_secs(10);
+    assert_eq!(read_gpsd(session.as_bytes(), later).unwrap(), (48.137154, -11.576124));
+    let no_fix = session.lines().take(4).collect::<Vec<_>>().join("\n");
+    assert!(read_gpsd(no_fix.as_bytes(), later).is_err());
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let gpsd = Gpsd { addr: listener.local_addr().unwrap().to_string() };
+    let server = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut watch = String::new();
+        BufReader::new(&stream).read_line(&mut watch).unwrap();
+        (&stream).write_all(session.as_bytes()).unwrap();
+        watch
+    });
+    assert_eq!(gpsd.locate(Duration::from_secs(5)).unwrap(), (48.137154, -11.576124));
+    assert!(server.join().unwrap().starts_with("?WATCH={\"enable\":true"));
+}
+
+#[test]
+fn test_ipinfo() {
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let ipinfo = IpInfo { url: format!("http://{}/loc", listener.local_addr().unwrap()) };
+    thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut line = String::new();
+        let mut reader = BufReader::new(&stream);
+        while reader.read_line(&mut line).unwrap() > 0 && !line.ends_with("\r\n\r\n") {}
+        (&stream)
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 16\r\nConnection: close\r\n\r\n\
+                         40.7128,-74.0060")
+            .unwrap();
+    });
+    assert_eq!(ipinfo.locate(Duration::from_secs(5)).unwrap(), (40.7128, -74.006));
+}
+
+#[test]
+fn test_command_location() {
+    let command = |command: &str| CommandLocation { command: command.to_owned() };
+    let timeout = Duration::from_secs(5);
+    assert_eq!(command("echo 1.5,2.5").locate(timeout).unwrap(), (1.5, 2.5));
+    assert!(command("echo 1.5,2.5; exit 1").locate(timeout).is_err());
+    assert!(command("echo somewhere").locate(timeout).is_err());
+
+    let started = Instant::now();
+    assert!(command("sleep 5").locate(Duration::from_millis(100)).is_err());
+    assert!(started.elapsed() < Duration::from_secs(4));
+}
+
+#[test]
+fn test_location_chain() {
+    fn probe(vars: &[(&str, &str)]) -> LocationChain {
+        LocationChain::probe(|name| {
+            vars.iter().find(|&&(n, _)| n == name).map(|&(_, value)| value.to_owned())
+        })
+    }
+    let names = |chain: &LocationChain| {
+        chain.providers
+            .iter()
+            .map(|&(ref p, timeout)| format!("{}:{}", p.name(), timeout.as_secs()))
+            .collect::<Vec<_>>()
+    };
+
+    assert!(probe(&[]).providers.is_empty());
+    match probe(&[]).locate() {
+        Err(GpsError::Disabled) => {}
+        other => panic!("{:?}", other),
+    }
+    assert_eq!(names(&probe(&[("LOCATION_QUERY", "1"), ("LOCATION_STATIC", "1,2")])),
+               vec!["ipinfo:5", "static:5"]);
+    // unusable ones are left out
+    let chain = probe(&[("LOCATION_PROVIDERS", "gpsd:2, command, nowhere, static:1"),
+                        ("LOCATION_STATIC", "1.5,2.5")]);
+    assert_eq!(names(&chain), vec!["gpsd:2", "static:1"]);
+
+    // a failing provider falls through to the next one
+    let chain = LocationChain {
+        providers: vec![(Box::new(CommandLocation { command: "exit 1".to_owned() }),
+                         Duration::from_secs(5)),
+                        (Box::new(StaticLocation((1.5, 2.5))), Duration::from_secs(5))],
+    };
+    assert_eq!(chain.locate().unwrap(), (1.5, 2.5));
+}
@@ -0,0 +1,63 @@
+use Coords;
+
+// in screen cells
+pub const WIDTH: u16 = 20;
+pub const HEIGHT: u16 = 8;
+
+// how many canvas cells each minimap cell stands for, rounding up so the
+// whole canvas fits
+pub fn block_size((width, height): Coords) -> Coords {
+    let blocks = |len: u16, cells: u16| len.saturating_sub(1) / cells + 1;
+    (blocks(width, WIDTH), blocks(height, HEIGHT))
+}
+
+// the minimap cell covering canvas coords, if they're on the canvas
+pub fn cell_of(canvas: Coords, (x, y): Coords) -> Option<Coords> {
+    let (block_w, block_h) = block_size(canvas);
+    let cell = (x / block_w, y / block_h);
+    if cell.0 < WIDTH && cell.1 < HEIGHT {
+        Some(cell)
+    } else {
+        None
+    }
+}
+
+// the top left canvas coords of a minimap cell
+pub fn canvas_of(canvas: Coords, (cell_x, cell_y): Coords) -> Coords {
+    let (block_w, block_h) = block_size(canvas);
+    (cell_x * block_w, cell_y * block_h)
+}
+
+// rows of minimap cells, each set when any of its canvas cells is occupied
+pub fn downscale<I>(canvas: Coords, occupied: I) -> Vec<Vec<bool>>
+    where I: IntoIterator<Item = Coords>
+{
+    let mut grid = vec![vec![false; WIDTH as usize]; HEIGHT as usize];
+    for coords in occupied {
+        if let Some((x, y)) = cell_of(canvas, coords) {
+            grid[y as usize][x as usize] = true;
+        }
+    }
+    grid
+}
+
+#[test]
+fn test_downscale() {
+    assert_eq!(block_size((40, 16)), (2, 2));
+    assert_eq!(block_size((41, 17)), (3, 3));
+    assert_eq!(block_size((5, 3)), (1, 1));
+
+    let grid = downscale((40, 16), vec![(0, 0), (1, 1), (39, 15), (2, 5), (40, 16)]);
+    let set: Vec<(usize, usize)> = grid.iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.iter().enumerate().filter(|&(_, &on)| on).map(move |(x, _)| (x, y))
+        })
+        .collect();
+    assert_eq!(set, vec![(0, 0), (1, 2), (19, 7)]);
+
+    // clicking a cell goes back to the start of the block it covers
+    let canvas = (100, 200);
+    assert_eq!(cell_of(canvas, (57, 130)), Some((11, 5)));
+    assert_eq!(canvas_of(canvas, (11, 5)), (55, 125));
+}
@@ -0,0 +1,101 @@
+use termion::event::{Event, Key};
+
+use NodeID;
+use command::ExportFormat;
+use substitute::Substitution;
+
+// a question on the bottom line that the next key answers. while one is
+// open it sees every event first, so a click can't change what it's asking
+// about.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Modal {
+    // a subtree awaiting y/n before being deleted, and how many nodes it has
+    Delete(NodeID, usize),
+    // an export awaiting whether it's of the selected subtree or the whole
+    // file
+    Export(ExportFormat, NodeID),
+    // the window key was pressed, and the next one says what to do
+    Window,
+    // search matches awaiting y/n before being deleted
+    BulkDelete(Vec<NodeID>),
+    // selected children awaiting y/n before being deleted, and how many
+    // nodes they have in all
+    DeleteSelection(Vec<NodeID>, usize),
+    // a replacement awaiting y/n before being made in these nodes
+    Substitute(Substitution, Vec<NodeID>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Step {
+    // the event means nothing here and is dropped, leaving the question up
+    Ignore,
+    Cancel,
+    Answer(Key),
+}
+
+impl Modal {
+    pub fn step(&self, evt: &Event) -> Step {
+        let key = match *evt {
+            Event::Key(Key::Esc) => return Step::Cancel,
+            Event::Key(key) => key,
+            _ => return Step::Ignore,
+        };
+        let answers: &[Key] = match *self {
+            Modal::Delete(..) | Modal::BulkDelete(_) | Modal::DeleteSelection(..) |
+            Modal::Substitute(..) => {
+                &[Key::Char('y'), Key::Char('n')]
+            }
+            Modal::Export(..) => &[Key::Char('s'), Key::Char('w')],
+            Modal::Window => &[Key::Char('v'), Key::Char('w'), Key::Ctrl('w'), Key::Char('q')],
+        };
+        if answers.contains(&key) {
+            Step::Answer(key)
+        } else {
+            Step::Ignore
+        }
+    }
+
+    pub fn prompt(&self) -> String {
+        match *self {
+            Modal::Delete(_, count) => format!("delete {} nodes? (y/n)", count),
+            Modal::Export(..) => "export (s)elected subtree or (w)hole file?".to_owned(),
+            Modal::Window => "window: v splits, w goes to the other pane, q closes".to_owned(),
+            Modal::BulkDelete(ref matches) => {
+                format!("delete {} matches and their children? (y/n)", matches.len())
+            }
+            Modal::DeleteSelection(ref children, count) => {
+                format!("delete {} children, {} nodes in all? (y/n)", children.len(), count)
+            }
+            Modal::Substitute(ref sub, ref nodes) => {
+                let s = if nodes.len() == 1 { "" } else { "s" };
+                format!("replace \"{}\" with \"{}\" in {} node{}? (y/n)",
+                        sub.pattern,
+                        sub.replacement,
+                        nodes.len(),
+                        s)
+            }
+        }
+    }
+}
+
+#[test]
+fn test_modal_steps() {
+    use termion::event::{MouseButton, MouseEvent};
+
+    let click = Event::Mouse(MouseEvent::Press(MouseButton::Left, 3, 4));
+    let delete = Modal::Delete(7, 12);
+    assert_eq!(delete.step(&click), Step::Ignore);
+    assert_eq!(delete.step(&Event::Key(Key::Char('x'))), Step::Ignore);
+    assert_eq!(delete.step(&Event::Key(Key::Char('y'))), Step::Answer(Key::Char('y')));
+    assert_eq!(delete.step(&Event::Key(Key::Esc)), Step::Cancel);
+
+    let export = Modal::Export(ExportFormat::Html, 7);
+    assert_eq!(export.step(&Event::Key(Key::Char('y'))), Step::Ignore);
+    assert_eq!(export.step(&Event::Key(Key::Char('w'))), Step::Answer(Key::Char('w')));
+    assert_eq!(Modal::Window.step(&Event::Key(Key::Ctrl('w'))), Step::Answer(Key::Ctrl('w')));
+    assert_eq!(Modal::Window.step(&click), Step::Ignore);
+    assert_eq!(Modal::BulkDelete(vec![1, 2]).prompt(),
+               "delete 2 matches and their children? (y/n)");
+    assert_eq!(Modal::DeleteSelection(vec![1, 2], 5).prompt(),
+               "delete 2 children, 5 nodes in all? (y/n)");
+}
@@ -15,6 +15,11 @@ pub struct Node {
     pub free_text: Option<String>,
     pub color: String,
     pub auto_arrange: bool,
+    // shown above the tree when this is an anchor
+    pub title: Option<String>,
+    // an anchor drawn as its one line, leaving the
+    // collapsed flags of everything under it alone
+    pub iconified: bool,
 }
 
 impl Default for Node {
@@ -33,6 +38,8 @@ impl Default for Node {
             free_text: None,
             color: random_fg_color(),
             auto_arrange: true,
+            title: None,
+            iconified: false,
         }
     }
 }
@@ -54,4 +61,41 @@ impl Node {
     pub fn toggle_hide_stricken(&mut self) {
         self.hide_stricken = !self.hide_stricken;
     }
+
+    // what the tag index reads: the content, and the tags kept in the meta
+    // as `#tag` or `#key=value`
+    pub fn tag_text(&self) -> String {
+        let mut text = self.content.clone();
+        for (key, value) in &self.meta.tags {
+            if value.is_empty() || value.contains(char::is_whitespace) {
+                text.push_str(&format!(" #{}", key));
+            } else {
+                text.push_str(&format!(" #{}={}", key, value));
+            }
+        }
+        text
+    }
+
+    // words in the content, leaving out tags like #draft or #prio=2
+    pub fn word_count(&self) -> usize {
+        self.content
+            .split_whitespace()
+            .filter(|word| !(word.starts_with('#') && word.len() > 1))
+            .count()
+    }
+}
+
+#[test]
+fn test_word_count() {
+    let count = |content: &str| {
+        Node {
+            content: content.to_owned(),
+            ..Node::default()
+        }.word_count()
+    };
+    assert_eq!(count(""), 0);
+    assert_eq!(count("   "), 0);
+    assert_eq!(count("#draft #prio=2"), 0);
+    assert_eq!(count("chapter one #draft"), 2);
+    assert_eq!(count("# heading\u{3000}with\u{a0}ideographic space"), 5);
 }
@@ -0,0 +1,39 @@
+use std::process::{Command, Stdio};
+use std::thread;
+
+use NodeID;
+
+pub trait Notifier {
+    fn notify(&mut self, node_id: NodeID, text: &str);
+}
+
+// runs `command void <text>`, notify-send by default
+pub struct CommandNotifier {
+    pub command: String,
+}
+
+impl Default for CommandNotifier {
+    fn default() -> CommandNotifier {
+        CommandNotifier { command: "notify-send".to_owned() }
+    }
+}
+
+impl Notifier for CommandNotifier {
+    fn notify(&mut self, node_id: NodeID, text: &str) {
+        debug!("notifying about node {}", node_id);
+        let child = Command::new(&self.command)
+            .arg("void")
+            .arg(text)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        match child {
+            // reap it in the background so the UI never waits on it
+            Ok(mut child) => {
+                thread::spawn(move || child.wait());
+            }
+            Err(e) => error!("failed to run notification command {}: {}", self.command, e),
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use {NodeID, Screen};
+
+// outline numbers like 1, 1.2 and 1.2.3 for everything below root, from the
+// order of siblings, in the order they'd be read. root itself has none.
+// completed nodes out of sight under hide_stricken are skipped, along with
+// what's below them, unless number_hidden is set.
+pub fn number_tree(screen: &Screen, root: NodeID, number_hidden: bool) -> Vec<(NodeID, String)> {
+    let mut numbers = vec![];
+    number_children(screen, root, "", number_hidden, &mut numbers);
+    numbers
+}
+
+fn number_children(
+    screen: &Screen,
+    parent_id: NodeID,
+    prefix: &str,
+    number_hidden: bool,
+    numbers: &mut Vec<(NodeID, String)>,
+) {
+    let parent = match screen.nodes.get(&parent_id) {
+        Some(parent) => parent,
+        None => return,
+    };
+    let shown = parent.children.iter().filter(|&&child_id| {
+        let stricken = screen.with_node(child_id, |c| c.stricken) == Some(true);
+        number_hidden || !(parent.hide_stricken && stricken)
+    });
+    for (i, &child_id) in shown.enumerate() {
+        let number = format!("{}{}", prefix, i + 1);
+        numbers.push((child_id, number.clone()));
+        number_children(screen, child_id, &format!("{}.", number), number_hidden, numbers);
+    }
+}
+
+#[cfg(test)]
+fn numbering_fixture(nodes: &[(NodeID, NodeID)]) -> Screen {
+    use Node;
+
+    let mut screen = Screen::default();
+    for &(parent_id, id) in nodes {
+        let node = Node {
+            id,
+            parent_id,
+            content: format!("node {}", id),
+            ..Node::default()
+        };
+        screen.nodes.insert(id, node);
+        screen.nodes.get_mut(&parent_id).unwrap().children.push(id);
+    }
+    screen
+}
+
+#[test]
+fn test_number_deep_nesting() {
+    let screen = numbering_fixture(&[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 6), (0, 7)]);
+    let numbers = number_tree(&screen, 0, false);
+    assert_eq!(numbers[5], (6, "1.1.1.1.1.1".to_owned()));
+    assert_eq!(numbers[6], (7, "2".to_owned()));
+
+    // below a node, its children start over from 1
+    assert_eq!(number_tree(&screen, 4, false),
+               vec![(5, "1".to_owned()), (6, "1.1".to_owned())]);
+    assert!(number_tree(&screen, 6, false).is_empty());
+    assert!(number_tree(&screen, 99, false).is_empty());
+}
+
+#[test]
+fn test_number_reordering() {
+    let mut screen = numbering_fixture(&[(0, 1), (1, 2), (1, 3), (3, 4), (1, 5)]);
+    let number = |screen: &Screen, id| {
+        number_tree(screen, 0, false).into_iter().find(|&(n, _)| n == id).map(|(_, n)| n)
+    };
+    assert_eq!(number(&screen, 4), Some("1.2.1".to_owned()));
+    assert_eq!(number(&screen, 5), Some("1.3".to_owned()));
+
+    screen.nodes.get_mut(&1).unwrap().children = vec![5, 3, 2];
+    assert_eq!(number(&screen, 5), Some("1.1".to_owned()));
+    assert_eq!(number(&screen, 4), Some("1.2.1".to_owned()));
+    assert_eq!(number(&screen, 2), Some("1.3".to_owned()));
+
+    // completed nodes hidden from view are passed over, unless asked for
+    screen.nodes.get_mut(&5).unwrap().stricken = true;
+    assert_eq!(number(&screen, 2), Some("1.3".to_owned()));
+    screen.nodes.get_mut(&1).unwrap().hide_stricken = true;
+    assert_eq!(number(&screen, 5), None);
+    assert_eq!(number(&screen, 4), Some("1.1.1".to_owned()));
+    assert_eq!(number_tree(&screen, 0, true)[1], (5, "1.1".to_owned()));
+}
@@ -0,0 +1,82 @@
+use Coords;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    pub fn other(self) -> Side {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+}
+
+// a vertical split of the screen into a left pane, a divider column and a
+// right pane
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Layout {
+    // the left pane's width, or None for half of what's left after the
+    // divider
+    pub left: Option<u16>,
+}
+
+impl Layout {
+    // the column the divider is drawn in
+    pub fn divider(&self, (width, _): Coords) -> u16 {
+        let half = width.saturating_sub(1) / 2;
+        self.left.unwrap_or(half).min(width.saturating_sub(2)) + 1
+    }
+
+    // the first column of a side's pane, and its size
+    pub fn pane(&self, side: Side, dims: Coords) -> (u16, Coords) {
+        let divider = self.divider(dims);
+        match side {
+            Side::Left => (1, (divider - 1, dims.1)),
+            Side::Right => (divider + 1, (dims.0.saturating_sub(divider), dims.1)),
+        }
+    }
+
+    // the pane under screen coords, and the coords within it. the divider
+    // belongs to neither.
+    pub fn locate(&self, (x, y): Coords, dims: Coords) -> Option<(Side, Coords)> {
+        let divider = self.divider(dims);
+        if x < divider {
+            Some((Side::Left, (x, y)))
+        } else if x > divider {
+            Some((Side::Right, (x - divider, y)))
+        } else {
+            None
+        }
+    }
+
+    // screen coords as seen from a side's pane, kept inside it, for drags
+    // that wander over the divider
+    pub fn within(&self, side: Side, (x, y): Coords, dims: Coords) -> Coords {
+        let (start, (width, _)) = self.pane(side, dims);
+        (x.saturating_sub(start - 1).max(1).min(width.max(1)), y)
+    }
+}
+
+#[test]
+fn test_layout() {
+    let halves = Layout::default();
+    assert_eq!(halves.divider((81, 20)), 41);
+    assert_eq!(halves.pane(Side::Left, (81, 20)), (1, (40, 20)));
+    assert_eq!(halves.pane(Side::Right, (81, 20)), (42, (40, 20)));
+    assert_eq!(halves.pane(Side::Right, (80, 20)), (41, (40, 20)));
+    assert_eq!(halves.locate((40, 3), (81, 20)), Some((Side::Left, (40, 3))));
+    assert_eq!(halves.locate((41, 3), (81, 20)), None);
+    assert_eq!(halves.locate((42, 3), (81, 20)), Some((Side::Right, (1, 3))));
+    assert_eq!(halves.within(Side::Right, (10, 3), (81, 20)), (1, 3));
+    assert_eq!(halves.within(Side::Left, (70, 3), (81, 20)), (40, 3));
+
+    let narrow = Layout { left: Some(20) };
+    assert_eq!(narrow.pane(Side::Right, (81, 20)), (22, (60, 20)));
+    assert_eq!(narrow.locate((81, 1), (81, 20)), Some((Side::Right, (60, 1))));
+    // there's always a column for each pane
+    assert_eq!(Layout { left: Some(200) }.pane(Side::Right, (10, 5)), (10, (1, 5)));
+}
@@ -495,6 +495,11 @@ pub struct Meta {
     gps: ::protobuf::SingularPtrField<Gps>,
     tags: ::protobuf::RepeatedField<Tag>,
     due: ::std::option::Option<u64>,
+    tracked: ::std::option::Option<u64>,
+    timer_start: ::std::option::Option<u64>,
+    notified: ::std::option::Option<u64>,
+    estimate: ::std::option::Option<u64>,
+    attachments: ::protobuf::RepeatedField<::std::string::String>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -699,6 +704,147 @@ impl Meta {
     fn mut_due_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
         &mut self.due
     }
+
+    // optional uint64 tracked = 7;
+
+    pub fn clear_tracked(&mut self) {
+        self.tracked = ::std::option::Option::None;
+    }
+
+    pub fn has_tracked(&self) -> bool {
+        self.tracked.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_tracked(&mut self, v: u64) {
+        self.tracked = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_tracked(&self) -> u64 {
+        self.tracked.unwrap_or(0)
+    }
+
+    fn get_tracked_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.tracked
+    }
+
+    fn mut_tracked_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.tracked
+    }
+
+    // optional uint64 timer_start = 8;
+
+    pub fn clear_timer_start(&mut self) {
+        self.timer_start = ::std::option::Option::None;
+    }
+
+    pub fn has_timer_start(&self) -> bool {
+        self.timer_start.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_timer_start(&mut self, v: u64) {
+        self.timer_start = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_timer_start(&self) -> u64 {
+        self.timer_start.unwrap_or(0)
+    }
+
+    fn get_timer_start_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.timer_start
+    }
+
+    fn mut_timer_start_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.timer_start
+    }
+
+    // optional uint64 notified = 9;
+
+    pub fn clear_notified(&mut self) {
+        self.notified = ::std::option::Option::None;
+    }
+
+    pub fn has_notified(&self) -> bool {
+        self.notified.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_notified(&mut self, v: u64) {
+        self.notified = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_notified(&self) -> u64 {
+        self.notified.unwrap_or(0)
+    }
+
+    fn get_notified_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.notified
+    }
+
+    fn mut_notified_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.notified
+    }
+
+    // optional uint64 estimate = 10;
+
+    pub fn clear_estimate(&mut self) {
+        self.estimate = ::std::option::Option::None;
+    }
+
+    pub fn has_estimate(&self) -> bool {
+        self.estimate.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_estimate(&mut self, v: u64) {
+        self.estimate = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_estimate(&self) -> u64 {
+        self.estimate.unwrap_or(0)
+    }
+
+    fn get_estimate_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.estimate
+    }
+
+    fn mut_estimate_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.estimate
+    }
+
+    // repeated string attachments = 11;
+
+    pub fn clear_attachments(&mut self) {
+        self.attachments.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_attachments(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.attachments = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_attachments(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.attachments
+    }
+
+    // Take field
+    pub fn take_attachments(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.attachments, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_attachments(&self) -> &[::std::string::String] {
+        &self.attachments
+    }
+
+    fn get_attachments_for_reflect(&self) -> &::protobuf::RepeatedField<::std::string::String> {
+        &self.attachments
+    }
+
+    fn mut_attachments_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.attachments
+    }
 }
 
 impl ::protobuf::Message for Meta {
@@ -753,6 +899,37 @@ impl ::protobuf::Message for Meta {
                     let tmp = is.read_uint64()?;
                     self.due = ::std::option::Option::Some(tmp);
                 },
+                7 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    };
+                    let tmp = is.read_uint64()?;
+                    self.tracked = ::std::option::Option::Some(tmp);
+                },
+                8 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    };
+                    let tmp = is.read_uint64()?;
+                    self.timer_start = ::std::option::Option::Some(tmp);
+                },
+                9 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    };
+                    let tmp = is.read_uint64()?;
+                    self.notified = ::std::option::Option::Some(tmp);
+                },
+                10 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    };
+                    let tmp = is.read_uint64()?;
+                    self.estimate = ::std::option::Option::Some(tmp);
+                },
+                11 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.attachments)?;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -785,6 +962,21 @@ impl ::protobuf::Message for Meta {
         if let Some(v) = self.due {
             my_size += ::protobuf::rt::value_size(6, v, ::protobuf::wire_format::WireTypeVarint);
         };
+        if let Some(v) = self.tracked {
+            my_size += ::protobuf::rt::value_size(7, v, ::protobuf::wire_format::WireTypeVarint);
+        };
+        if let Some(v) = self.timer_start {
+            my_size += ::protobuf::rt::value_size(8, v, ::protobuf::wire_format::WireTypeVarint);
+        };
+        if let Some(v) = self.notified {
+            my_size += ::protobuf::rt::value_size(9, v, ::protobuf::wire_format::WireTypeVarint);
+        };
+        if let Some(v) = self.estimate {
+            my_size += ::protobuf::rt::value_size(10, v, ::protobuf::wire_format::WireTypeVarint);
+        };
+        for value in &self.attachments {
+            my_size += ::protobuf::rt::string_size(11, &value);
+        };
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -813,6 +1005,21 @@ impl ::protobuf::Message for Meta {
         if let Some(v) = self.due {
             os.write_uint64(6, v)?;
         };
+        if let Some(v) = self.tracked {
+            os.write_uint64(7, v)?;
+        };
+        if let Some(v) = self.timer_start {
+            os.write_uint64(8, v)?;
+        };
+        if let Some(v) = self.notified {
+            os.write_uint64(9, v)?;
+        };
+        if let Some(v) = self.estimate {
+            os.write_uint64(10, v)?;
+        };
+        for v in &self.attachments {
+            os.write_string(11, &v)?;
+        };
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -881,6 +1088,31 @@ impl ::protobuf::MessageStatic for Meta {
                     Meta::get_due_for_reflect,
                     Meta::mut_due_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "tracked",
+                    Meta::get_tracked_for_reflect,
+                    Meta::mut_tracked_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "timer_start",
+                    Meta::get_timer_start_for_reflect,
+                    Meta::mut_timer_start_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "notified",
+                    Meta::get_notified_for_reflect,
+                    Meta::mut_notified_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "estimate",
+                    Meta::get_estimate_for_reflect,
+                    Meta::mut_estimate_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "attachments",
+                    Meta::get_attachments_for_reflect,
+                    Meta::mut_attachments_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<Meta>(
                     "Meta",
                     fields,
@@ -899,6 +1131,11 @@ impl ::protobuf::Clear for Meta {
         self.clear_gps();
         self.clear_tags();
         self.clear_due();
+        self.clear_tracked();
+        self.clear_timer_start();
+        self.clear_notified();
+        self.clear_estimate();
+        self.clear_attachments();
         self.unknown_fields.clear();
     }
 }
@@ -931,6 +1168,8 @@ pub struct Node {
     parent_id: ::std::option::Option<u64>,
     free_text: ::protobuf::SingularField<::std::string::String>,
     auto_arrange: ::std::option::Option<bool>,
+    title: ::protobuf::SingularField<::std::string::String>,
+    iconified: ::std::option::Option<bool>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -1358,6 +1597,77 @@ impl Node {
     fn mut_auto_arrange_for_reflect(&mut self) -> &mut ::std::option::Option<bool> {
         &mut self.auto_arrange
     }
+
+    // optional string title = 14;
+
+    pub fn clear_title(&mut self) {
+        self.title.clear();
+    }
+
+    pub fn has_title(&self) -> bool {
+        self.title.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_title(&mut self, v: ::std::string::String) {
+        self.title = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_title(&mut self) -> &mut ::std::string::String {
+        if self.title.is_none() {
+            self.title.set_default();
+        };
+        self.title.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_title(&mut self) -> ::std::string::String {
+        self.title.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    pub fn get_title(&self) -> &str {
+        match self.title.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+
+    fn get_title_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.title
+    }
+
+    fn mut_title_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.title
+    }
+
+    // optional bool iconified = 15;
+
+    pub fn clear_iconified(&mut self) {
+        self.iconified = ::std::option::Option::None;
+    }
+
+    pub fn has_iconified(&self) -> bool {
+        self.iconified.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_iconified(&mut self, v: bool) {
+        self.iconified = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_iconified(&self) -> bool {
+        self.iconified.unwrap_or(false)
+    }
+
+    fn get_iconified_for_reflect(&self) -> &::std::option::Option<bool> {
+        &self.iconified
+    }
+
+    fn mut_iconified_for_reflect(&mut self) -> &mut ::std::option::Option<bool> {
+        &mut self.iconified
+    }
 }
 
 impl ::protobuf::Message for Node {
@@ -1477,6 +1787,16 @@ impl ::protobuf::Message for Node {
                     let tmp = is.read_bool()?;
                     self.auto_arrange = ::std::option::Option::Some(tmp);
                 },
+                14 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.title)?;
+                },
+                15 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    };
+                    let tmp = is.read_bool()?;
+                    self.iconified = ::std::option::Option::Some(tmp);
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -1529,6 +1849,12 @@ impl ::protobuf::Message for Node {
         if let Some(v) = self.auto_arrange {
             my_size += 2;
         };
+        if let Some(v) = self.title.as_ref() {
+            my_size += ::protobuf::rt::string_size(14, &v);
+        };
+        if let Some(v) = self.iconified {
+            my_size += 2;
+        };
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -1576,6 +1902,12 @@ impl ::protobuf::Message for Node {
         if let Some(v) = self.auto_arrange {
             os.write_bool(13, v)?;
         };
+        if let Some(v) = self.title.as_ref() {
+            os.write_string(14, &v)?;
+        };
+        if let Some(v) = self.iconified {
+            os.write_bool(15, v)?;
+        };
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -1679,6 +2011,16 @@ impl ::protobuf::MessageStatic for Node {
                     Node::get_auto_arrange_for_reflect,
                     Node::mut_auto_arrange_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "title",
+                    Node::get_title_for_reflect,
+                    Node::mut_title_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "iconified",
+                    Node::get_iconified_for_reflect,
+                    Node::mut_iconified_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<Node>(
                     "Node",
                     fields,
@@ -1704,6 +2046,8 @@ impl ::protobuf::Clear for Node {
         self.clear_parent_id();
         self.clear_free_text();
         self.clear_auto_arrange();
+        self.clear_title();
+        self.clear_iconified();
         self.unknown_fields.clear();
     }
 }
@@ -1946,6 +2290,12 @@ pub struct Screen {
     nodes: ::protobuf::RepeatedField<Node>,
     max_id: ::std::option::Option<u64>,
     arrows: ::protobuf::RepeatedField<Arrow>,
+    trash: ::protobuf::RepeatedField<TrashEntry>,
+    bookmarks: ::protobuf::RepeatedField<Bookmark>,
+    archive: ::protobuf::RepeatedField<TrashEntry>,
+    tombstones: ::protobuf::RepeatedField<Tombstone>,
+    location: ::protobuf::SingularPtrField<Gps>,
+    groups: ::protobuf::RepeatedField<Group>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -2061,99 +2411,377 @@ impl Screen {
     fn mut_arrows_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<Arrow> {
         &mut self.arrows
     }
-}
 
-impl ::protobuf::Message for Screen {
-    fn is_initialized(&self) -> bool {
-        if self.max_id.is_none() {
-            return false;
-        };
-        true
+    // repeated .void.TrashEntry trash = 4;
+
+    pub fn clear_trash(&mut self) {
+        self.trash.clear();
     }
 
-    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
-        while !is.eof()? {
-            let (field_number, wire_type) = is.read_tag_unpack()?;
-            match field_number {
-                1 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.nodes)?;
-                },
-                2 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    };
-                    let tmp = is.read_uint64()?;
-                    self.max_id = ::std::option::Option::Some(tmp);
-                },
-                3 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.arrows)?;
-                },
-                _ => {
-                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
-                },
-            };
-        }
-        ::std::result::Result::Ok(())
+    // Param is passed by value, moved
+    pub fn set_trash(&mut self, v: ::protobuf::RepeatedField<TrashEntry>) {
+        self.trash = v;
     }
 
-    // Compute sizes of nested messages
-    #[allow(unused_variables)]
-    fn compute_size(&self) -> u32 {
-        let mut my_size = 0;
-        for value in &self.nodes {
-            let len = value.compute_size();
-            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
-        };
-        if let Some(v) = self.max_id {
-            my_size += ::protobuf::rt::value_size(2, v, ::protobuf::wire_format::WireTypeVarint);
-        };
-        for value in &self.arrows {
-            let len = value.compute_size();
-            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
-        };
-        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
-        self.cached_size.set(my_size);
-        my_size
+    // Mutable pointer to the field.
+    pub fn mut_trash(&mut self) -> &mut ::protobuf::RepeatedField<TrashEntry> {
+        &mut self.trash
     }
 
-    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
-        for v in &self.nodes {
-            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
-            os.write_raw_varint32(v.get_cached_size())?;
-            v.write_to_with_cached_sizes(os)?;
-        };
-        if let Some(v) = self.max_id {
-            os.write_uint64(2, v)?;
-        };
-        for v in &self.arrows {
-            os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
-            os.write_raw_varint32(v.get_cached_size())?;
-            v.write_to_with_cached_sizes(os)?;
-        };
-        os.write_unknown_fields(self.get_unknown_fields())?;
-        ::std::result::Result::Ok(())
+    // Take field
+    pub fn take_trash(&mut self) -> ::protobuf::RepeatedField<TrashEntry> {
+        ::std::mem::replace(&mut self.trash, ::protobuf::RepeatedField::new())
     }
 
-    fn get_cached_size(&self) -> u32 {
-        self.cached_size.get()
+    pub fn get_trash(&self) -> &[TrashEntry] {
+        &self.trash
     }
 
-    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
-        &self.unknown_fields
+    fn get_trash_for_reflect(&self) -> &::protobuf::RepeatedField<TrashEntry> {
+        &self.trash
     }
 
-    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
-        &mut self.unknown_fields
+    fn mut_trash_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<TrashEntry> {
+        &mut self.trash
     }
 
-    fn as_any(&self) -> &::std::any::Any {
-        self as &::std::any::Any
+    // repeated .void.Bookmark bookmarks = 5;
+
+    pub fn clear_bookmarks(&mut self) {
+        self.bookmarks.clear();
     }
 
-    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
-        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    // Param is passed by value, moved
+    pub fn set_bookmarks(&mut self, v: ::protobuf::RepeatedField<Bookmark>) {
+        self.bookmarks = v;
     }
-}
+
+    // Mutable pointer to the field.
+    pub fn mut_bookmarks(&mut self) -> &mut ::protobuf::RepeatedField<Bookmark> {
+        &mut self.bookmarks
+    }
+
+    // Take field
+    pub fn take_bookmarks(&mut self) -> ::protobuf::RepeatedField<Bookmark> {
+        ::std::mem::replace(&mut self.bookmarks, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_bookmarks(&self) -> &[Bookmark] {
+        &self.bookmarks
+    }
+
+    fn get_bookmarks_for_reflect(&self) -> &::protobuf::RepeatedField<Bookmark> {
+        &self.bookmarks
+    }
+
+    fn mut_bookmarks_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<Bookmark> {
+        &mut self.bookmarks
+    }
+
+    // repeated .void.TrashEntry archive = 6;
+
+    pub fn clear_archive(&mut self) {
+        self.archive.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_archive(&mut self, v: ::protobuf::RepeatedField<TrashEntry>) {
+        self.archive = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_archive(&mut self) -> &mut ::protobuf::RepeatedField<TrashEntry> {
+        &mut self.archive
+    }
+
+    // Take field
+    pub fn take_archive(&mut self) -> ::protobuf::RepeatedField<TrashEntry> {
+        ::std::mem::replace(&mut self.archive, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_archive(&self) -> &[TrashEntry] {
+        &self.archive
+    }
+
+    fn get_archive_for_reflect(&self) -> &::protobuf::RepeatedField<TrashEntry> {
+        &self.archive
+    }
+
+    fn mut_archive_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<TrashEntry> {
+        &mut self.archive
+    }
+
+    // repeated .void.Tombstone tombstones = 7;
+
+    pub fn clear_tombstones(&mut self) {
+        self.tombstones.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_tombstones(&mut self, v: ::protobuf::RepeatedField<Tombstone>) {
+        self.tombstones = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_tombstones(&mut self) -> &mut ::protobuf::RepeatedField<Tombstone> {
+        &mut self.tombstones
+    }
+
+    // Take field
+    pub fn take_tombstones(&mut self) -> ::protobuf::RepeatedField<Tombstone> {
+        ::std::mem::replace(&mut self.tombstones, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_tombstones(&self) -> &[Tombstone] {
+        &self.tombstones
+    }
+
+    fn get_tombstones_for_reflect(&self) -> &::protobuf::RepeatedField<Tombstone> {
+        &self.tombstones
+    }
+
+    fn mut_tombstones_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<Tombstone> {
+        &mut self.tombstones
+    }
+
+    // optional .void.Gps location = 8;
+
+    pub fn clear_location(&mut self) {
+        self.location.clear();
+    }
+
+    pub fn has_location(&self) -> bool {
+        self.location.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_location(&mut self, v: Gps) {
+        self.location = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_location(&mut self) -> &mut Gps {
+        if self.location.is_none() {
+            self.location.set_default();
+        };
+        self.location.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_location(&mut self) -> Gps {
+        self.location.take().unwrap_or_else(|| Gps::new())
+    }
+
+    pub fn get_location(&self) -> &Gps {
+        self.location.as_ref().unwrap_or_else(|| Gps::default_instance())
+    }
+
+    fn get_location_for_reflect(&self) -> &::protobuf::SingularPtrField<Gps> {
+        &self.location
+    }
+
+    fn mut_location_for_reflect(&mut self) -> &mut ::protobuf::SingularPtrField<Gps> {
+        &mut self.location
+    }
+
+    // repeated .void.Group groups = 9;
+
+    pub fn clear_groups(&mut self) {
+        self.groups.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_groups(&mut self, v: ::protobuf::RepeatedField<Group>) {
+        self.groups = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_groups(&mut self) -> &mut ::protobuf::RepeatedField<Group> {
+        &mut self.groups
+    }
+
+    // Take field
+    pub fn take_groups(&mut self) -> ::protobuf::RepeatedField<Group> {
+        ::std::mem::replace(&mut self.groups, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_groups(&self) -> &[Group] {
+        &self.groups
+    }
+
+    fn get_groups_for_reflect(&self) -> &::protobuf::RepeatedField<Group> {
+        &self.groups
+    }
+
+    fn mut_groups_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<Group> {
+        &mut self.groups
+    }
+}
+
+impl ::protobuf::Message for Screen {
+    fn is_initialized(&self) -> bool {
+        if self.max_id.is_none() {
+            return false;
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.nodes)?;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    };
+                    let tmp = is.read_uint64()?;
+                    self.max_id = ::std::option::Option::Some(tmp);
+                },
+                3 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.arrows)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.trash)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.bookmarks)?;
+                },
+                6 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.archive)?;
+                },
+                7 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.tombstones)?;
+                },
+                8 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.location)?;
+                },
+                9 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.groups)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.nodes {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        if let Some(v) = self.max_id {
+            my_size += ::protobuf::rt::value_size(2, v, ::protobuf::wire_format::WireTypeVarint);
+        };
+        for value in &self.arrows {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        for value in &self.trash {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        for value in &self.bookmarks {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        for value in &self.archive {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        for value in &self.tombstones {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        if let Some(v) = self.location.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        for value in &self.groups {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.nodes {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        if let Some(v) = self.max_id {
+            os.write_uint64(2, v)?;
+        };
+        for v in &self.arrows {
+            os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        for v in &self.trash {
+            os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        for v in &self.bookmarks {
+            os.write_tag(5, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        for v in &self.archive {
+            os.write_tag(6, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        for v in &self.tombstones {
+            os.write_tag(7, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        if let Some(v) = self.location.as_ref() {
+            os.write_tag(8, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        for v in &self.groups {
+            os.write_tag(9, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
 
 impl ::protobuf::MessageStatic for Screen {
     fn new() -> Screen {
@@ -2183,6 +2811,36 @@ impl ::protobuf::MessageStatic for Screen {
                     Screen::get_arrows_for_reflect,
                     Screen::mut_arrows_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<TrashEntry>>(
+                    "trash",
+                    Screen::get_trash_for_reflect,
+                    Screen::mut_trash_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Bookmark>>(
+                    "bookmarks",
+                    Screen::get_bookmarks_for_reflect,
+                    Screen::mut_bookmarks_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<TrashEntry>>(
+                    "archive",
+                    Screen::get_archive_for_reflect,
+                    Screen::mut_archive_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Tombstone>>(
+                    "tombstones",
+                    Screen::get_tombstones_for_reflect,
+                    Screen::mut_tombstones_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Gps>>(
+                    "location",
+                    Screen::get_location_for_reflect,
+                    Screen::mut_location_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Group>>(
+                    "groups",
+                    Screen::get_groups_for_reflect,
+                    Screen::mut_groups_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<Screen>(
                     "Screen",
                     fields,
@@ -2198,6 +2856,12 @@ impl ::protobuf::Clear for Screen {
         self.clear_nodes();
         self.clear_max_id();
         self.clear_arrows();
+        self.clear_trash();
+        self.clear_bookmarks();
+        self.clear_archive();
+        self.clear_tombstones();
+        self.clear_location();
+        self.clear_groups();
         self.unknown_fields.clear();
     }
 }
@@ -2214,6 +2878,1804 @@ impl ::protobuf::reflect::ProtobufValue for Screen {
     }
 }
 
+#[derive(PartialEq,Clone,Default)]
+pub struct Tombstone {
+    // message fields
+    id: ::std::option::Option<u64>,
+    ctime: ::std::option::Option<u64>,
+    deleted_at: ::std::option::Option<u64>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for Tombstone {}
+
+impl Tombstone {
+    pub fn new() -> Tombstone {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static Tombstone {
+        static mut instance: ::protobuf::lazy::Lazy<Tombstone> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Tombstone,
+        };
+        unsafe {
+            instance.get(Tombstone::new)
+        }
+    }
+
+    // required uint64 id = 1;
+
+    pub fn clear_id(&mut self) {
+        self.id = ::std::option::Option::None;
+    }
+
+    pub fn has_id(&self) -> bool {
+        self.id.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_id(&mut self, v: u64) {
+        self.id = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_id(&self) -> u64 {
+        self.id.unwrap_or(0)
+    }
+
+    fn get_id_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.id
+    }
+
+    fn mut_id_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.id
+    }
+
+    // required uint64 ctime = 2;
+
+    pub fn clear_ctime(&mut self) {
+        self.ctime = ::std::option::Option::None;
+    }
+
+    pub fn has_ctime(&self) -> bool {
+        self.ctime.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ctime(&mut self, v: u64) {
+        self.ctime = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_ctime(&self) -> u64 {
+        self.ctime.unwrap_or(0)
+    }
+
+    fn get_ctime_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.ctime
+    }
+
+    fn mut_ctime_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.ctime
+    }
+
+    // required uint64 deleted_at = 3;
+
+    pub fn clear_deleted_at(&mut self) {
+        self.deleted_at = ::std::option::Option::None;
+    }
+
+    pub fn has_deleted_at(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_deleted_at(&mut self, v: u64) {
+        self.deleted_at = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_deleted_at(&self) -> u64 {
+        self.deleted_at.unwrap_or(0)
+    }
+
+    fn get_deleted_at_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.deleted_at
+    }
+
+    fn mut_deleted_at_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.deleted_at
+    }
+}
+
+impl ::protobuf::Message for Tombstone {
+    fn is_initialized(&self) -> bool {
+        if self.id.is_none() {
+            return false;
+        };
+        if self.ctime.is_none() {
+            return false;
+        };
+        if self.deleted_at.is_none() {
+            return false;
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    };
+                    let tmp = is.read_uint64()?;
+                    self.id = ::std::option::Option::Some(tmp);
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    };
+                    let tmp = is.read_uint64()?;
+                    self.ctime = ::std::option::Option::Some(tmp);
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    };
+                    let tmp = is.read_uint64()?;
+                    self.deleted_at = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(v) = self.id {
+            my_size += ::protobuf::rt::value_size(1, v, ::protobuf::wire_format::WireTypeVarint);
+        };
+        if let Some(v) = self.ctime {
+            my_size += ::protobuf::rt::value_size(2, v, ::protobuf::wire_format::WireTypeVarint);
+        };
+        if let Some(v) = self.deleted_at {
+            my_size += ::protobuf::rt::value_size(3, v, ::protobuf::wire_format::WireTypeVarint);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.id {
+            os.write_uint64(1, v)?;
+        };
+        if let Some(v) = self.ctime {
+            os.write_uint64(2, v)?;
+        };
+        if let Some(v) = self.deleted_at {
+            os.write_uint64(3, v)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for Tombstone {
+    fn new() -> Tombstone {
+        Tombstone::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<Tombstone>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "id",
+                    Tombstone::get_id_for_reflect,
+                    Tombstone::mut_id_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "ctime",
+                    Tombstone::get_ctime_for_reflect,
+                    Tombstone::mut_ctime_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "deleted_at",
+                    Tombstone::get_deleted_at_for_reflect,
+                    Tombstone::mut_deleted_at_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Tombstone>(
+                    "Tombstone",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for Tombstone {
+    fn clear(&mut self) {
+        self.clear_id();
+        self.clear_ctime();
+        self.clear_deleted_at();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Tombstone {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Tombstone {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct TrashEntry {
+    // message fields
+    root: ::std::option::Option<u64>,
+    parent_id: ::std::option::Option<u64>,
+    x: ::std::option::Option<u32>,
+    y: ::std::option::Option<u32>,
+    deleted_at: ::std::option::Option<u64>,
+    nodes: ::protobuf::RepeatedField<Node>,
+    arrows: ::protobuf::RepeatedField<Arrow>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for TrashEntry {}
+
+impl TrashEntry {
+    pub fn new() -> TrashEntry {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static TrashEntry {
+        static mut instance: ::protobuf::lazy::Lazy<TrashEntry> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const TrashEntry,
+        };
+        unsafe {
+            instance.get(TrashEntry::new)
+        }
+    }
+
+    // required uint64 root = 1;
+
+    pub fn clear_root(&mut self) {
+        self.root = ::std::option::Option::None;
+    }
+
+    pub fn has_root(&self) -> bool {
+        self.root.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_root(&mut self, v: u64) {
+        self.root = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_root(&self) -> u64 {
+        self.root.unwrap_or(0)
+    }
+
+    fn get_root_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.root
+    }
+
+    fn mut_root_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.root
+    }
+
+    // required uint64 parent_id = 2;
+
+    pub fn clear_parent_id(&mut self) {
+        self.parent_id = ::std::option::Option::None;
+    }
+
+    pub fn has_parent_id(&self) -> bool {
+        self.parent_id.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_parent_id(&mut self, v: u64) {
+        self.parent_id = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_parent_id(&self) -> u64 {
+        self.parent_id.unwrap_or(0)
+    }
+
+    fn get_parent_id_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.parent_id
+    }
+
+    fn mut_parent_id_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.parent_id
+    }
+
+    // required uint32 x = 3;
+
+    pub fn clear_x(&mut self) {
+        self.x = ::std::option::Option::None;
+    }
+
+    pub fn has_x(&self) -> bool {
+        self.x.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_x(&mut self, v: u32) {
+        self.x = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_x(&self) -> u32 {
+        self.x.unwrap_or(0)
+    }
+
+    fn get_x_for_reflect(&self) -> &::std::option::Option<u32> {
+        &self.x
+    }
+
+    fn mut_x_for_reflect(&mut self) -> &mut ::std::option::Option<u32> {
+        &mut self.x
+    }
+
+    // required uint32 y = 4;
+
+    pub fn clear_y(&mut self) {
+        self.y = ::std::option::Option::None;
+    }
+
+    pub fn has_y(&self) -> bool {
+        self.y.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_y(&mut self, v: u32) {
+        self.y = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_y(&self) -> u32 {
+        self.y.unwrap_or(0)
+    }
+
+    fn get_y_for_reflect(&self) -> &::std::option::Option<u32> {
+        &self.y
+    }
+
+    fn mut_y_for_reflect(&mut self) -> &mut ::std::option::Option<u32> {
+        &mut self.y
+    }
+
+    // required uint64 deleted_at = 5;
+
+    pub fn clear_deleted_at(&mut self) {
+        self.deleted_at = ::std::option::Option::None;
+    }
+
+    pub fn has_deleted_at(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_deleted_at(&mut self, v: u64) {
+        self.deleted_at = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_deleted_at(&self) -> u64 {
+        self.deleted_at.unwrap_or(0)
+    }
+
+    fn get_deleted_at_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.deleted_at
+    }
+
+    fn mut_deleted_at_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.deleted_at
+    }
+
+    // repeated .void.Node nodes = 6;
+
+    pub fn clear_nodes(&mut self) {
+        self.nodes.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_nodes(&mut self, v: ::protobuf::RepeatedField<Node>) {
+        self.nodes = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_nodes(&mut self) -> &mut ::protobuf::RepeatedField<Node> {
+        &mut self.nodes
+    }
+
+    // Take field
+    pub fn take_nodes(&mut self) -> ::protobuf::RepeatedField<Node> {
+        ::std::mem::replace(&mut self.nodes, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    fn get_nodes_for_reflect(&self) -> &::protobuf::RepeatedField<Node> {
+        &self.nodes
+    }
+
+    fn mut_nodes_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<Node> {
+        &mut self.nodes
+    }
+
+    // repeated .void.Arrow arrows = 7;
+
+    pub fn clear_arrows(&mut self) {
+        self.arrows.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_arrows(&mut self, v: ::protobuf::RepeatedField<Arrow>) {
+        self.arrows = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_arrows(&mut self) -> &mut ::protobuf::RepeatedField<Arrow> {
+        &mut self.arrows
+    }
+
+    // Take field
+    pub fn take_arrows(&mut self) -> ::protobuf::RepeatedField<Arrow> {
+        ::std::mem::replace(&mut self.arrows, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_arrows(&self) -> &[Arrow] {
+        &self.arrows
+    }
+
+    fn get_arrows_for_reflect(&self) -> &::protobuf::RepeatedField<Arrow> {
+        &self.arrows
+    }
+
+    fn mut_arrows_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<Arrow> {
+        &mut self.arrows
+    }
+}
+
+impl ::protobuf::Message for TrashEntry {
+    fn is_initialized(&self) -> bool {
+        if self.root.is_none() {
+            return false;
+        };
+        if self.parent_id.is_none() {
+            return false;
+        };
+        if self.x.is_none() {
+            return false;
+        };
+        if self.y.is_none() {
+            return false;
+        };
+        if self.deleted_at.is_none() {
+            return false;
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    };
+                    let tmp = is.read_uint64()?;
+                    self.root = ::std::option::Option::Some(tmp);
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    };
+                    let tmp = is.read_uint64()?;
+                    self.parent_id = ::std::option::Option::Some(tmp);
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    };
+                    let tmp = is.read_uint32()?;
+                    self.x = ::std::option::Option::Some(tmp);
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    };
+                    let tmp = is.read_uint32()?;
+                    self.y = ::std::option::Option::Some(tmp);
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    };
+                    let tmp = is.read_uint64()?;
+                    self.deleted_at = ::std::option::Option::Some(tmp);
+                },
+                6 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.nodes)?;
+                },
+                7 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.arrows)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(v) = self.root {
+            my_size += ::protobuf::rt::value_size(1, v, ::protobuf::wire_format::WireTypeVarint);
+        };
+        if let Some(v) = self.parent_id {
+            my_size += ::protobuf::rt::value_size(2, v, ::protobuf::wire_format::WireTypeVarint);
+        };
+        if let Some(v) = self.x {
+            my_size += ::protobuf::rt::value_size(3, v, ::protobuf::wire_format::WireTypeVarint);
+        };
+        if let Some(v) = self.y {
+            my_size += ::protobuf::rt::value_size(4, v, ::protobuf::wire_format::WireTypeVarint);
+        };
+        if let Some(v) = self.deleted_at {
+            my_size += ::protobuf::rt::value_size(5, v, ::protobuf::wire_format::WireTypeVarint);
+        };
+        for value in &self.nodes {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        for value in &self.arrows {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.root {
+            os.write_uint64(1, v)?;
+        };
+        if let Some(v) = self.parent_id {
+            os.write_uint64(2, v)?;
+        };
+        if let Some(v) = self.x {
+            os.write_uint32(3, v)?;
+        };
+        if let Some(v) = self.y {
+            os.write_uint32(4, v)?;
+        };
+        if let Some(v) = self.deleted_at {
+            os.write_uint64(5, v)?;
+        };
+        for v in &self.nodes {
+            os.write_tag(6, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        for v in &self.arrows {
+            os.write_tag(7, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for TrashEntry {
+    fn new() -> TrashEntry {
+        TrashEntry::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<TrashEntry>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "root",
+                    TrashEntry::get_root_for_reflect,
+                    TrashEntry::mut_root_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "parent_id",
+                    TrashEntry::get_parent_id_for_reflect,
+                    TrashEntry::mut_parent_id_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "x",
+                    TrashEntry::get_x_for_reflect,
+                    TrashEntry::mut_x_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "y",
+                    TrashEntry::get_y_for_reflect,
+                    TrashEntry::mut_y_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "deleted_at",
+                    TrashEntry::get_deleted_at_for_reflect,
+                    TrashEntry::mut_deleted_at_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Node>>(
+                    "nodes",
+                    TrashEntry::get_nodes_for_reflect,
+                    TrashEntry::mut_nodes_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Arrow>>(
+                    "arrows",
+                    TrashEntry::get_arrows_for_reflect,
+                    TrashEntry::mut_arrows_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<TrashEntry>(
+                    "TrashEntry",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for TrashEntry {
+    fn clear(&mut self) {
+        self.clear_root();
+        self.clear_parent_id();
+        self.clear_x();
+        self.clear_y();
+        self.clear_deleted_at();
+        self.clear_nodes();
+        self.clear_arrows();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for TrashEntry {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for TrashEntry {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Bookmark {
+    // message fields
+    slot: ::std::option::Option<u32>,
+    node: ::std::option::Option<u64>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for Bookmark {}
+
+impl Bookmark {
+    pub fn new() -> Bookmark {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static Bookmark {
+        static mut instance: ::protobuf::lazy::Lazy<Bookmark> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Bookmark,
+        };
+        unsafe {
+            instance.get(Bookmark::new)
+        }
+    }
+
+    // required uint32 slot = 1;
+
+    pub fn clear_slot(&mut self) {
+        self.slot = ::std::option::Option::None;
+    }
+
+    pub fn has_slot(&self) -> bool {
+        self.slot.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_slot(&mut self, v: u32) {
+        self.slot = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_slot(&self) -> u32 {
+        self.slot.unwrap_or(0)
+    }
+
+    fn get_slot_for_reflect(&self) -> &::std::option::Option<u32> {
+        &self.slot
+    }
+
+    fn mut_slot_for_reflect(&mut self) -> &mut ::std::option::Option<u32> {
+        &mut self.slot
+    }
+
+    // required uint64 node = 2;
+
+    pub fn clear_node(&mut self) {
+        self.node = ::std::option::Option::None;
+    }
+
+    pub fn has_node(&self) -> bool {
+        self.node.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_node(&mut self, v: u64) {
+        self.node = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_node(&self) -> u64 {
+        self.node.unwrap_or(0)
+    }
+
+    fn get_node_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.node
+    }
+
+    fn mut_node_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.node
+    }
+}
+
+impl ::protobuf::Message for Bookmark {
+    fn is_initialized(&self) -> bool {
+        if self.slot.is_none() {
+            return false;
+        };
+        if self.node.is_none() {
+            return false;
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    };
+                    let tmp = is.read_uint32()?;
+                    self.slot = ::std::option::Option::Some(tmp);
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    };
+                    let tmp = is.read_uint64()?;
+                    self.node = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(v) = self.slot {
+            my_size += ::protobuf::rt::value_size(1, v, ::protobuf::wire_format::WireTypeVarint);
+        };
+        if let Some(v) = self.node {
+            my_size += ::protobuf::rt::value_size(2, v, ::protobuf::wire_format::WireTypeVarint);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.slot {
+            os.write_uint32(1, v)?;
+        };
+        if let Some(v) = self.node {
+            os.write_uint64(2, v)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for Bookmark {
+    fn new() -> Bookmark {
+        Bookmark::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<Bookmark>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "slot",
+                    Bookmark::get_slot_for_reflect,
+                    Bookmark::mut_slot_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "node",
+                    Bookmark::get_node_for_reflect,
+                    Bookmark::mut_node_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Bookmark>(
+                    "Bookmark",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for Bookmark {
+    fn clear(&mut self) {
+        self.clear_slot();
+        self.clear_node();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Bookmark {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Bookmark {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Group {
+    // message fields
+    anchor: ::std::option::Option<u64>,
+    name: ::protobuf::SingularField<::std::string::String>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for Group {}
+
+impl Group {
+    pub fn new() -> Group {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static Group {
+        static mut instance: ::protobuf::lazy::Lazy<Group> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Group,
+        };
+        unsafe {
+            instance.get(Group::new)
+        }
+    }
+
+    // required uint64 anchor = 1;
+
+    pub fn clear_anchor(&mut self) {
+        self.anchor = ::std::option::Option::None;
+    }
+
+    pub fn has_anchor(&self) -> bool {
+        self.anchor.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_anchor(&mut self, v: u64) {
+        self.anchor = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_anchor(&self) -> u64 {
+        self.anchor.unwrap_or(0)
+    }
+
+    fn get_anchor_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.anchor
+    }
+
+    fn mut_anchor_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.anchor
+    }
+
+    // required string name = 2;
+
+    pub fn clear_name(&mut self) {
+        self.name.clear();
+    }
+
+    pub fn has_name(&self) -> bool {
+        self.name.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_name(&mut self, v: ::std::string::String) {
+        self.name = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_name(&mut self) -> &mut ::std::string::String {
+        if self.name.is_none() {
+            self.name.set_default();
+        };
+        self.name.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_name(&mut self) -> ::std::string::String {
+        self.name.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    pub fn get_name(&self) -> &str {
+        match self.name.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+
+    fn get_name_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.name
+    }
+
+    fn mut_name_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.name
+    }
+}
+
+impl ::protobuf::Message for Group {
+    fn is_initialized(&self) -> bool {
+        if self.anchor.is_none() {
+            return false;
+        };
+        if self.name.is_none() {
+            return false;
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    };
+                    let tmp = is.read_uint64()?;
+                    self.anchor = ::std::option::Option::Some(tmp);
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.name)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(v) = self.anchor {
+            my_size += ::protobuf::rt::value_size(1, v, ::protobuf::wire_format::WireTypeVarint);
+        };
+        if let Some(v) = self.name.as_ref() {
+            my_size += ::protobuf::rt::string_size(2, &v);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.anchor {
+            os.write_uint64(1, v)?;
+        };
+        if let Some(v) = self.name.as_ref() {
+            os.write_string(2, &v)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for Group {
+    fn new() -> Group {
+        Group::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<Group>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "anchor",
+                    Group::get_anchor_for_reflect,
+                    Group::mut_anchor_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "name",
+                    Group::get_name_for_reflect,
+                    Group::mut_name_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Group>(
+                    "Group",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for Group {
+    fn clear(&mut self) {
+        self.clear_anchor();
+        self.clear_name();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Group {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Group {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Session {
+    // message fields
+    selected: ::std::option::Option<u64>,
+    drawing_root: ::std::option::Option<u64>,
+    view_y: ::std::option::Option<u32>,
+    show_logs: ::std::option::Option<bool>,
+    macros: ::protobuf::RepeatedField<Macro>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for Session {}
+
+impl Session {
+    pub fn new() -> Session {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static Session {
+        static mut instance: ::protobuf::lazy::Lazy<Session> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Session,
+        };
+        unsafe {
+            instance.get(Session::new)
+        }
+    }
+
+    // optional uint64 selected = 1;
+
+    pub fn clear_selected(&mut self) {
+        self.selected = ::std::option::Option::None;
+    }
+
+    pub fn has_selected(&self) -> bool {
+        self.selected.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_selected(&mut self, v: u64) {
+        self.selected = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_selected(&self) -> u64 {
+        self.selected.unwrap_or(0)
+    }
+
+    fn get_selected_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.selected
+    }
+
+    fn mut_selected_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.selected
+    }
+
+    // required uint64 drawing_root = 2;
+
+    pub fn clear_drawing_root(&mut self) {
+        self.drawing_root = ::std::option::Option::None;
+    }
+
+    pub fn has_drawing_root(&self) -> bool {
+        self.drawing_root.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_drawing_root(&mut self, v: u64) {
+        self.drawing_root = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_drawing_root(&self) -> u64 {
+        self.drawing_root.unwrap_or(0)
+    }
+
+    fn get_drawing_root_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.drawing_root
+    }
+
+    fn mut_drawing_root_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.drawing_root
+    }
+
+    // required uint32 view_y = 3;
+
+    pub fn clear_view_y(&mut self) {
+        self.view_y = ::std::option::Option::None;
+    }
+
+    pub fn has_view_y(&self) -> bool {
+        self.view_y.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_view_y(&mut self, v: u32) {
+        self.view_y = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_view_y(&self) -> u32 {
+        self.view_y.unwrap_or(0)
+    }
+
+    fn get_view_y_for_reflect(&self) -> &::std::option::Option<u32> {
+        &self.view_y
+    }
+
+    fn mut_view_y_for_reflect(&mut self) -> &mut ::std::option::Option<u32> {
+        &mut self.view_y
+    }
+
+    // required bool show_logs = 4;
+
+    pub fn clear_show_logs(&mut self) {
+        self.show_logs = ::std::option::Option::None;
+    }
+
+    pub fn has_show_logs(&self) -> bool {
+        self.show_logs.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_show_logs(&mut self, v: bool) {
+        self.show_logs = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_show_logs(&self) -> bool {
+        self.show_logs.unwrap_or(false)
+    }
+
+    fn get_show_logs_for_reflect(&self) -> &::std::option::Option<bool> {
+        &self.show_logs
+    }
+
+    fn mut_show_logs_for_reflect(&mut self) -> &mut ::std::option::Option<bool> {
+        &mut self.show_logs
+    }
+
+    // repeated .void.Macro macros = 5;
+
+    pub fn clear_macros(&mut self) {
+        self.macros.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_macros(&mut self, v: ::protobuf::RepeatedField<Macro>) {
+        self.macros = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_macros(&mut self) -> &mut ::protobuf::RepeatedField<Macro> {
+        &mut self.macros
+    }
+
+    // Take field
+    pub fn take_macros(&mut self) -> ::protobuf::RepeatedField<Macro> {
+        ::std::mem::replace(&mut self.macros, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_macros(&self) -> &[Macro] {
+        &self.macros
+    }
+
+    fn get_macros_for_reflect(&self) -> &::protobuf::RepeatedField<Macro> {
+        &self.macros
+    }
+
+    fn mut_macros_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<Macro> {
+        &mut self.macros
+    }
+}
+
+impl ::protobuf::Message for Session {
+    fn is_initialized(&self) -> bool {
+        if self.drawing_root.is_none() {
+            return false;
+        };
+        if self.view_y.is_none() {
+            return false;
+        };
+        if self.show_logs.is_none() {
+            return false;
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    };
+                    let tmp = is.read_uint64()?;
+                    self.selected = ::std::option::Option::Some(tmp);
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    };
+                    let tmp = is.read_uint64()?;
+                    self.drawing_root = ::std::option::Option::Some(tmp);
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    };
+                    let tmp = is.read_uint32()?;
+                    self.view_y = ::std::option::Option::Some(tmp);
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    };
+                    let tmp = is.read_bool()?;
+                    self.show_logs = ::std::option::Option::Some(tmp);
+                },
+                5 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.macros)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(v) = self.selected {
+            my_size += ::protobuf::rt::value_size(1, v, ::protobuf::wire_format::WireTypeVarint);
+        };
+        if let Some(v) = self.drawing_root {
+            my_size += ::protobuf::rt::value_size(2, v, ::protobuf::wire_format::WireTypeVarint);
+        };
+        if let Some(v) = self.view_y {
+            my_size += ::protobuf::rt::value_size(3, v, ::protobuf::wire_format::WireTypeVarint);
+        };
+        if let Some(v) = self.show_logs {
+            my_size += 2;
+        };
+        for value in &self.macros {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.selected {
+            os.write_uint64(1, v)?;
+        };
+        if let Some(v) = self.drawing_root {
+            os.write_uint64(2, v)?;
+        };
+        if let Some(v) = self.view_y {
+            os.write_uint32(3, v)?;
+        };
+        if let Some(v) = self.show_logs {
+            os.write_bool(4, v)?;
+        };
+        for v in &self.macros {
+            os.write_tag(5, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for Session {
+    fn new() -> Session {
+        Session::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<Session>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "selected",
+                    Session::get_selected_for_reflect,
+                    Session::mut_selected_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "drawing_root",
+                    Session::get_drawing_root_for_reflect,
+                    Session::mut_drawing_root_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "view_y",
+                    Session::get_view_y_for_reflect,
+                    Session::mut_view_y_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "show_logs",
+                    Session::get_show_logs_for_reflect,
+                    Session::mut_show_logs_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Macro>>(
+                    "macros",
+                    Session::get_macros_for_reflect,
+                    Session::mut_macros_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Session>(
+                    "Session",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for Session {
+    fn clear(&mut self) {
+        self.clear_selected();
+        self.clear_drawing_root();
+        self.clear_view_y();
+        self.clear_show_logs();
+        self.clear_macros();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Session {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Session {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Macro {
+    // message fields
+    register: ::protobuf::SingularField<::std::string::String>,
+    events: ::protobuf::RepeatedField<::std::vec::Vec<u8>>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for Macro {}
+
+impl Macro {
+    pub fn new() -> Macro {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static Macro {
+        static mut instance: ::protobuf::lazy::Lazy<Macro> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Macro,
+        };
+        unsafe {
+            instance.get(Macro::new)
+        }
+    }
+
+    // required string register = 1;
+
+    pub fn clear_register(&mut self) {
+        self.register.clear();
+    }
+
+    pub fn has_register(&self) -> bool {
+        self.register.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_register(&mut self, v: ::std::string::String) {
+        self.register = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_register(&mut self) -> &mut ::std::string::String {
+        if self.register.is_none() {
+            self.register.set_default();
+        };
+        self.register.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_register(&mut self) -> ::std::string::String {
+        self.register.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    pub fn get_register(&self) -> &str {
+        match self.register.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+
+    fn get_register_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.register
+    }
+
+    fn mut_register_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.register
+    }
+
+    // repeated bytes events = 2;
+
+    pub fn clear_events(&mut self) {
+        self.events.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_events(&mut self, v: ::protobuf::RepeatedField<::std::vec::Vec<u8>>) {
+        self.events = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_events(&mut self) -> &mut ::protobuf::RepeatedField<::std::vec::Vec<u8>> {
+        &mut self.events
+    }
+
+    // Take field
+    pub fn take_events(&mut self) -> ::protobuf::RepeatedField<::std::vec::Vec<u8>> {
+        ::std::mem::replace(&mut self.events, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_events(&self) -> &[::std::vec::Vec<u8>] {
+        &self.events
+    }
+
+    fn get_events_for_reflect(&self) -> &::protobuf::RepeatedField<::std::vec::Vec<u8>> {
+        &self.events
+    }
+
+    fn mut_events_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<::std::vec::Vec<u8>> {
+        &mut self.events
+    }
+}
+
+impl ::protobuf::Message for Macro {
+    fn is_initialized(&self) -> bool {
+        if self.register.is_none() {
+            return false;
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.register)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_repeated_bytes_into(wire_type, is, &mut self.events)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(v) = self.register.as_ref() {
+            my_size += ::protobuf::rt::string_size(1, &v);
+        };
+        for value in &self.events {
+            my_size += ::protobuf::rt::bytes_size(2, &value);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.register.as_ref() {
+            os.write_string(1, &v)?;
+        };
+        for v in &self.events {
+            os.write_bytes(2, &v)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for Macro {
+    fn new() -> Macro {
+        Macro::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<Macro>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "register",
+                    Macro::get_register_for_reflect,
+                    Macro::mut_register_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "events",
+                    Macro::get_events_for_reflect,
+                    Macro::mut_events_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Macro>(
+                    "Macro",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for Macro {
+    fn clear(&mut self) {
+        self.clear_register();
+        self.clear_events();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Macro {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Macro {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
 static file_descriptor_proto_data: &'static [u8] = &[
     0x0a, 0x18, 0x69, 0x6e, 0x63, 0x6c, 0x75, 0x64, 0x65, 0x2f, 0x64, 0x61, 0x74, 0x61, 0x5f, 0x6d,
     0x6f, 0x64, 0x65, 0x6c, 0x2e, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x12, 0x04, 0x76, 0x6f, 0x69, 0x64,
@@ -2222,7 +4684,7 @@ static file_descriptor_proto_data: &'static [u8] = &[
     0x75, 0x65, 0x18, 0x02, 0x20, 0x02, 0x28, 0x09, 0x52, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x22,
     0x29, 0x0a, 0x03, 0x47, 0x70, 0x73, 0x12, 0x10, 0x0a, 0x03, 0x6c, 0x61, 0x74, 0x18, 0x01, 0x20,
     0x02, 0x28, 0x02, 0x52, 0x03, 0x6c, 0x61, 0x74, 0x12, 0x10, 0x0a, 0x03, 0x6c, 0x6f, 0x6e, 0x18,
-    0x02, 0x20, 0x02, 0x28, 0x02, 0x52, 0x03, 0x6c, 0x6f, 0x6e, 0x22, 0xa1, 0x01, 0x0a, 0x04, 0x4d,
+    0x02, 0x20, 0x02, 0x28, 0x02, 0x52, 0x03, 0x6c, 0x6f, 0x6e, 0x22, 0xb6, 0x02, 0x0a, 0x04, 0x4d,
     0x65, 0x74, 0x61, 0x12, 0x14, 0x0a, 0x05, 0x63, 0x74, 0x69, 0x6d, 0x65, 0x18, 0x01, 0x20, 0x02,
     0x28, 0x04, 0x52, 0x05, 0x63, 0x74, 0x69, 0x6d, 0x65, 0x12, 0x14, 0x0a, 0x05, 0x6d, 0x74, 0x69,
     0x6d, 0x65, 0x18, 0x02, 0x20, 0x02, 0x28, 0x04, 0x52, 0x05, 0x6d, 0x74, 0x69, 0x6d, 0x65, 0x12,
@@ -2232,171 +4694,398 @@ static file_descriptor_proto_data: &'static [u8] = &[
     0x76, 0x6f, 0x69, 0x64, 0x2e, 0x47, 0x70, 0x73, 0x52, 0x03, 0x67, 0x70, 0x73, 0x12, 0x1d, 0x0a,
     0x04, 0x74, 0x61, 0x67, 0x73, 0x18, 0x04, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x09, 0x2e, 0x76, 0x6f,
     0x69, 0x64, 0x2e, 0x54, 0x61, 0x67, 0x52, 0x04, 0x74, 0x61, 0x67, 0x73, 0x12, 0x10, 0x0a, 0x03,
-    0x64, 0x75, 0x65, 0x18, 0x06, 0x20, 0x01, 0x28, 0x04, 0x52, 0x03, 0x64, 0x75, 0x65, 0x22, 0xda,
-    0x02, 0x0a, 0x04, 0x4e, 0x6f, 0x64, 0x65, 0x12, 0x0e, 0x0a, 0x02, 0x69, 0x64, 0x18, 0x01, 0x20,
-    0x02, 0x28, 0x04, 0x52, 0x02, 0x69, 0x64, 0x12, 0x1e, 0x0a, 0x04, 0x6d, 0x65, 0x74, 0x61, 0x18,
-    0x02, 0x20, 0x02, 0x28, 0x0b, 0x32, 0x0a, 0x2e, 0x76, 0x6f, 0x69, 0x64, 0x2e, 0x4d, 0x65, 0x74,
-    0x61, 0x52, 0x04, 0x6d, 0x65, 0x74, 0x61, 0x12, 0x12, 0x0a, 0x04, 0x74, 0x65, 0x78, 0x74, 0x18,
-    0x03, 0x20, 0x02, 0x28, 0x09, 0x52, 0x04, 0x74, 0x65, 0x78, 0x74, 0x12, 0x1a, 0x0a, 0x08, 0x63,
-    0x68, 0x69, 0x6c, 0x64, 0x72, 0x65, 0x6e, 0x18, 0x04, 0x20, 0x03, 0x28, 0x04, 0x52, 0x08, 0x63,
-    0x68, 0x69, 0x6c, 0x64, 0x72, 0x65, 0x6e, 0x12, 0x1c, 0x0a, 0x09, 0x63, 0x6f, 0x6c, 0x6c, 0x61,
-    0x70, 0x73, 0x65, 0x64, 0x18, 0x05, 0x20, 0x02, 0x28, 0x08, 0x52, 0x09, 0x63, 0x6f, 0x6c, 0x6c,
-    0x61, 0x70, 0x73, 0x65, 0x64, 0x12, 0x1a, 0x0a, 0x08, 0x73, 0x74, 0x72, 0x69, 0x63, 0x6b, 0x65,
-    0x6e, 0x18, 0x06, 0x20, 0x02, 0x28, 0x08, 0x52, 0x08, 0x73, 0x74, 0x72, 0x69, 0x63, 0x6b, 0x65,
-    0x6e, 0x12, 0x23, 0x0a, 0x0d, 0x68, 0x69, 0x64, 0x65, 0x5f, 0x73, 0x74, 0x72, 0x69, 0x63, 0x6b,
-    0x65, 0x6e, 0x18, 0x07, 0x20, 0x02, 0x28, 0x08, 0x52, 0x0c, 0x68, 0x69, 0x64, 0x65, 0x53, 0x74,
-    0x72, 0x69, 0x63, 0x6b, 0x65, 0x6e, 0x12, 0x0c, 0x0a, 0x01, 0x78, 0x18, 0x08, 0x20, 0x02, 0x28,
-    0x0d, 0x52, 0x01, 0x78, 0x12, 0x0c, 0x0a, 0x01, 0x79, 0x18, 0x09, 0x20, 0x02, 0x28, 0x0d, 0x52,
-    0x01, 0x79, 0x12, 0x1a, 0x0a, 0x08, 0x73, 0x65, 0x6c, 0x65, 0x63, 0x74, 0x65, 0x64, 0x18, 0x0a,
-    0x20, 0x02, 0x28, 0x08, 0x52, 0x08, 0x73, 0x65, 0x6c, 0x65, 0x63, 0x74, 0x65, 0x64, 0x12, 0x1b,
-    0x0a, 0x09, 0x70, 0x61, 0x72, 0x65, 0x6e, 0x74, 0x5f, 0x69, 0x64, 0x18, 0x0b, 0x20, 0x02, 0x28,
-    0x04, 0x52, 0x08, 0x70, 0x61, 0x72, 0x65, 0x6e, 0x74, 0x49, 0x64, 0x12, 0x1b, 0x0a, 0x09, 0x66,
-    0x72, 0x65, 0x65, 0x5f, 0x74, 0x65, 0x78, 0x74, 0x18, 0x0c, 0x20, 0x01, 0x28, 0x09, 0x52, 0x08,
-    0x66, 0x72, 0x65, 0x65, 0x54, 0x65, 0x78, 0x74, 0x12, 0x21, 0x0a, 0x0c, 0x61, 0x75, 0x74, 0x6f,
-    0x5f, 0x61, 0x72, 0x72, 0x61, 0x6e, 0x67, 0x65, 0x18, 0x0d, 0x20, 0x02, 0x28, 0x08, 0x52, 0x0b,
-    0x61, 0x75, 0x74, 0x6f, 0x41, 0x72, 0x72, 0x61, 0x6e, 0x67, 0x65, 0x22, 0x3d, 0x0a, 0x05, 0x41,
-    0x72, 0x72, 0x6f, 0x77, 0x12, 0x1b, 0x0a, 0x09, 0x66, 0x72, 0x6f, 0x6d, 0x5f, 0x6e, 0x6f, 0x64,
-    0x65, 0x18, 0x01, 0x20, 0x02, 0x28, 0x04, 0x52, 0x08, 0x66, 0x72, 0x6f, 0x6d, 0x4e, 0x6f, 0x64,
-    0x65, 0x12, 0x17, 0x0a, 0x07, 0x74, 0x6f, 0x5f, 0x6e, 0x6f, 0x64, 0x65, 0x18, 0x02, 0x20, 0x02,
-    0x28, 0x04, 0x52, 0x06, 0x74, 0x6f, 0x4e, 0x6f, 0x64, 0x65, 0x22, 0x66, 0x0a, 0x06, 0x53, 0x63,
-    0x72, 0x65, 0x65, 0x6e, 0x12, 0x20, 0x0a, 0x05, 0x6e, 0x6f, 0x64, 0x65, 0x73, 0x18, 0x01, 0x20,
-    0x03, 0x28, 0x0b, 0x32, 0x0a, 0x2e, 0x76, 0x6f, 0x69, 0x64, 0x2e, 0x4e, 0x6f, 0x64, 0x65, 0x52,
-    0x05, 0x6e, 0x6f, 0x64, 0x65, 0x73, 0x12, 0x15, 0x0a, 0x06, 0x6d, 0x61, 0x78, 0x5f, 0x69, 0x64,
-    0x18, 0x02, 0x20, 0x02, 0x28, 0x04, 0x52, 0x05, 0x6d, 0x61, 0x78, 0x49, 0x64, 0x12, 0x23, 0x0a,
-    0x06, 0x61, 0x72, 0x72, 0x6f, 0x77, 0x73, 0x18, 0x03, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x0b, 0x2e,
-    0x76, 0x6f, 0x69, 0x64, 0x2e, 0x41, 0x72, 0x72, 0x6f, 0x77, 0x52, 0x06, 0x61, 0x72, 0x72, 0x6f,
-    0x77, 0x73, 0x4a, 0xb8, 0x10, 0x0a, 0x06, 0x12, 0x04, 0x00, 0x00, 0x30, 0x01, 0x0a, 0x08, 0x0a,
-    0x01, 0x0c, 0x12, 0x03, 0x00, 0x00, 0x12, 0x0a, 0x08, 0x0a, 0x01, 0x02, 0x12, 0x03, 0x02, 0x08,
-    0x0c, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x00, 0x12, 0x04, 0x04, 0x00, 0x07, 0x01, 0x0a, 0x0a, 0x0a,
-    0x03, 0x04, 0x00, 0x01, 0x12, 0x03, 0x04, 0x08, 0x0b, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02,
-    0x00, 0x12, 0x03, 0x05, 0x02, 0x1a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x04, 0x12,
-    0x03, 0x05, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x05, 0x12, 0x03, 0x05,
-    0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x01, 0x12, 0x03, 0x05, 0x12, 0x15,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x03, 0x12, 0x03, 0x05, 0x18, 0x19, 0x0a, 0x0b,
-    0x0a, 0x04, 0x04, 0x00, 0x02, 0x01, 0x12, 0x03, 0x06, 0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x00, 0x02, 0x01, 0x04, 0x12, 0x03, 0x06, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02,
-    0x01, 0x05, 0x12, 0x03, 0x06, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x01,
-    0x12, 0x03, 0x06, 0x12, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x03, 0x12, 0x03,
-    0x06, 0x1a, 0x1b, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x01, 0x12, 0x04, 0x09, 0x00, 0x0c, 0x01, 0x0a,
-    0x0a, 0x0a, 0x03, 0x04, 0x01, 0x01, 0x12, 0x03, 0x09, 0x08, 0x0b, 0x0a, 0x0b, 0x0a, 0x04, 0x04,
-    0x01, 0x02, 0x00, 0x12, 0x03, 0x0a, 0x02, 0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x00,
-    0x04, 0x12, 0x03, 0x0a, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x00, 0x05, 0x12,
-    0x03, 0x0a, 0x0b, 0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x00, 0x01, 0x12, 0x03, 0x0a,
-    0x11, 0x14, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x00, 0x03, 0x12, 0x03, 0x0a, 0x17, 0x18,
-    0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02, 0x01, 0x12, 0x03, 0x0b, 0x02, 0x19, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x01, 0x02, 0x01, 0x04, 0x12, 0x03, 0x0b, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x01, 0x02, 0x01, 0x05, 0x12, 0x03, 0x0b, 0x0b, 0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02,
-    0x01, 0x01, 0x12, 0x03, 0x0b, 0x11, 0x14, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x01, 0x03,
-    0x12, 0x03, 0x0b, 0x17, 0x18, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x02, 0x12, 0x04, 0x0e, 0x00, 0x15,
-    0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x02, 0x01, 0x12, 0x03, 0x0e, 0x08, 0x0c, 0x0a, 0x0b, 0x0a,
-    0x04, 0x04, 0x02, 0x02, 0x00, 0x12, 0x03, 0x0f, 0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02,
-    0x02, 0x00, 0x04, 0x12, 0x03, 0x0f, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x00,
-    0x05, 0x12, 0x03, 0x0f, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x00, 0x01, 0x12,
-    0x03, 0x0f, 0x12, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x00, 0x03, 0x12, 0x03, 0x0f,
-    0x1a, 0x1b, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x02, 0x02, 0x01, 0x12, 0x03, 0x10, 0x02, 0x1c, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x01, 0x04, 0x12, 0x03, 0x10, 0x02, 0x0a, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x02, 0x02, 0x01, 0x05, 0x12, 0x03, 0x10, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x02, 0x02, 0x01, 0x01, 0x12, 0x03, 0x10, 0x12, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02,
-    0x01, 0x03, 0x12, 0x03, 0x10, 0x1a, 0x1b, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x02, 0x02, 0x02, 0x12,
-    0x03, 0x11, 0x02, 0x22, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x02, 0x04, 0x12, 0x03, 0x11,
-    0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x02, 0x05, 0x12, 0x03, 0x11, 0x0b, 0x11,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x02, 0x01, 0x12, 0x03, 0x11, 0x12, 0x1d, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x02, 0x02, 0x02, 0x03, 0x12, 0x03, 0x11, 0x20, 0x21, 0x0a, 0x0b, 0x0a, 0x04,
-    0x04, 0x02, 0x02, 0x03, 0x12, 0x03, 0x12, 0x02, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02,
-    0x03, 0x04, 0x12, 0x03, 0x12, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x03, 0x06,
-    0x12, 0x03, 0x12, 0x0b, 0x0e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x03, 0x01, 0x12, 0x03,
-    0x12, 0x0f, 0x12, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x03, 0x03, 0x12, 0x03, 0x12, 0x15,
-    0x16, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x02, 0x02, 0x04, 0x12, 0x03, 0x13, 0x02, 0x18, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x02, 0x02, 0x04, 0x04, 0x12, 0x03, 0x13, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x02, 0x02, 0x04, 0x06, 0x12, 0x03, 0x13, 0x0b, 0x0e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02,
-    0x02, 0x04, 0x01, 0x12, 0x03, 0x13, 0x0f, 0x13, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x04,
-    0x03, 0x12, 0x03, 0x13, 0x16, 0x17, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x02, 0x02, 0x05, 0x12, 0x03,
-    0x14, 0x02, 0x1a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x05, 0x04, 0x12, 0x03, 0x14, 0x02,
-    0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x05, 0x05, 0x12, 0x03, 0x14, 0x0b, 0x11, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x05, 0x01, 0x12, 0x03, 0x14, 0x12, 0x15, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x02, 0x02, 0x05, 0x03, 0x12, 0x03, 0x14, 0x18, 0x19, 0x0a, 0x0a, 0x0a, 0x02, 0x04,
-    0x03, 0x12, 0x04, 0x17, 0x00, 0x25, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x03, 0x01, 0x12, 0x03,
-    0x17, 0x08, 0x0c, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02, 0x00, 0x12, 0x03, 0x18, 0x02, 0x19,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x00, 0x04, 0x12, 0x03, 0x18, 0x02, 0x0a, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x03, 0x02, 0x00, 0x05, 0x12, 0x03, 0x18, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x03, 0x02, 0x00, 0x01, 0x12, 0x03, 0x18, 0x12, 0x14, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03,
-    0x02, 0x00, 0x03, 0x12, 0x03, 0x18, 0x17, 0x18, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02, 0x01,
-    0x12, 0x03, 0x19, 0x02, 0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x01, 0x04, 0x12, 0x03,
-    0x19, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x01, 0x06, 0x12, 0x03, 0x19, 0x0b,
-    0x0f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x01, 0x01, 0x12, 0x03, 0x19, 0x10, 0x14, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x01, 0x03, 0x12, 0x03, 0x19, 0x17, 0x18, 0x0a, 0x0b, 0x0a,
-    0x04, 0x04, 0x03, 0x02, 0x02, 0x12, 0x03, 0x1a, 0x02, 0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03,
-    0x02, 0x02, 0x04, 0x12, 0x03, 0x1a, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x02,
-    0x05, 0x12, 0x03, 0x1a, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x02, 0x01, 0x12,
-    0x03, 0x1a, 0x12, 0x16, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x02, 0x03, 0x12, 0x03, 0x1a,
-    0x19, 0x1a, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02, 0x03, 0x12, 0x03, 0x1b, 0x02, 0x1f, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x03, 0x04, 0x12, 0x03, 0x1b, 0x02, 0x0a, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x03, 0x02, 0x03, 0x05, 0x12, 0x03, 0x1b, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x03, 0x02, 0x03, 0x01, 0x12, 0x03, 0x1b, 0x12, 0x1a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02,
-    0x03, 0x03, 0x12, 0x03, 0x1b, 0x1d, 0x1e, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02, 0x04, 0x12,
-    0x03, 0x1c, 0x02, 0x1e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x04, 0x04, 0x12, 0x03, 0x1c,
-    0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x04, 0x05, 0x12, 0x03, 0x1c, 0x0b, 0x0f,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x04, 0x01, 0x12, 0x03, 0x1c, 0x10, 0x19, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x03, 0x02, 0x04, 0x03, 0x12, 0x03, 0x1c, 0x1c, 0x1d, 0x0a, 0x0b, 0x0a, 0x04,
-    0x04, 0x03, 0x02, 0x05, 0x12, 0x03, 0x1d, 0x02, 0x1d, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02,
-    0x05, 0x04, 0x12, 0x03, 0x1d, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x05, 0x05,
-    0x12, 0x03, 0x1d, 0x0b, 0x0f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x05, 0x01, 0x12, 0x03,
-    0x1d, 0x10, 0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x05, 0x03, 0x12, 0x03, 0x1d, 0x1b,
-    0x1c, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02, 0x06, 0x12, 0x03, 0x1e, 0x02, 0x22, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x03, 0x02, 0x06, 0x04, 0x12, 0x03, 0x1e, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x03, 0x02, 0x06, 0x05, 0x12, 0x03, 0x1e, 0x0b, 0x0f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03,
-    0x02, 0x06, 0x01, 0x12, 0x03, 0x1e, 0x10, 0x1d, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x06,
-    0x03, 0x12, 0x03, 0x1e, 0x20, 0x21, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02, 0x07, 0x12, 0x03,
-    0x1f, 0x02, 0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x07, 0x04, 0x12, 0x03, 0x1f, 0x02,
-    0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x07, 0x05, 0x12, 0x03, 0x1f, 0x0b, 0x11, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x07, 0x01, 0x12, 0x03, 0x1f, 0x12, 0x13, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x03, 0x02, 0x07, 0x03, 0x12, 0x03, 0x1f, 0x16, 0x17, 0x0a, 0x0b, 0x0a, 0x04, 0x04,
-    0x03, 0x02, 0x08, 0x12, 0x03, 0x20, 0x02, 0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x08,
-    0x04, 0x12, 0x03, 0x20, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x08, 0x05, 0x12,
-    0x03, 0x20, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x08, 0x01, 0x12, 0x03, 0x20,
-    0x12, 0x13, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x08, 0x03, 0x12, 0x03, 0x20, 0x16, 0x17,
-    0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02, 0x09, 0x12, 0x03, 0x21, 0x02, 0x1e, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x03, 0x02, 0x09, 0x04, 0x12, 0x03, 0x21, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x03, 0x02, 0x09, 0x05, 0x12, 0x03, 0x21, 0x0b, 0x0f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02,
-    0x09, 0x01, 0x12, 0x03, 0x21, 0x10, 0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x09, 0x03,
-    0x12, 0x03, 0x21, 0x1b, 0x1d, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02, 0x0a, 0x12, 0x03, 0x22,
-    0x02, 0x21, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x0a, 0x04, 0x12, 0x03, 0x22, 0x02, 0x0a,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x0a, 0x05, 0x12, 0x03, 0x22, 0x0b, 0x11, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x03, 0x02, 0x0a, 0x01, 0x12, 0x03, 0x22, 0x12, 0x1b, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x03, 0x02, 0x0a, 0x03, 0x12, 0x03, 0x22, 0x1e, 0x20, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03,
-    0x02, 0x0b, 0x12, 0x03, 0x23, 0x02, 0x21, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x0b, 0x04,
-    0x12, 0x03, 0x23, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x0b, 0x05, 0x12, 0x03,
-    0x23, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x0b, 0x01, 0x12, 0x03, 0x23, 0x12,
-    0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x0b, 0x03, 0x12, 0x03, 0x23, 0x1e, 0x20, 0x0a,
-    0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02, 0x0c, 0x12, 0x03, 0x24, 0x02, 0x22, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x03, 0x02, 0x0c, 0x04, 0x12, 0x03, 0x24, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03,
-    0x02, 0x0c, 0x05, 0x12, 0x03, 0x24, 0x0b, 0x0f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x0c,
-    0x01, 0x12, 0x03, 0x24, 0x10, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x0c, 0x03, 0x12,
-    0x03, 0x24, 0x1f, 0x21, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x04, 0x12, 0x04, 0x27, 0x00, 0x2a, 0x01,
-    0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x04, 0x01, 0x12, 0x03, 0x27, 0x08, 0x0d, 0x0a, 0x0b, 0x0a, 0x04,
-    0x04, 0x04, 0x02, 0x00, 0x12, 0x03, 0x28, 0x02, 0x20, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x04, 0x02,
-    0x00, 0x04, 0x12, 0x03, 0x28, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x04, 0x02, 0x00, 0x05,
-    0x12, 0x03, 0x28, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x04, 0x02, 0x00, 0x01, 0x12, 0x03,
-    0x28, 0x12, 0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x04, 0x02, 0x00, 0x03, 0x12, 0x03, 0x28, 0x1e,
-    0x1f, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x04, 0x02, 0x01, 0x12, 0x03, 0x29, 0x02, 0x1e, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x04, 0x02, 0x01, 0x04, 0x12, 0x03, 0x29, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x04, 0x02, 0x01, 0x05, 0x12, 0x03, 0x29, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x04,
-    0x02, 0x01, 0x01, 0x12, 0x03, 0x29, 0x12, 0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x04, 0x02, 0x01,
-    0x03, 0x12, 0x03, 0x29, 0x1c, 0x1d, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x05, 0x12, 0x04, 0x2c, 0x00,
-    0x30, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x05, 0x01, 0x12, 0x03, 0x2c, 0x08, 0x0e, 0x0a, 0x0b,
-    0x0a, 0x04, 0x04, 0x05, 0x02, 0x00, 0x12, 0x03, 0x2d, 0x02, 0x1a, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x05, 0x02, 0x00, 0x04, 0x12, 0x03, 0x2d, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02,
-    0x00, 0x06, 0x12, 0x03, 0x2d, 0x0b, 0x0f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x00, 0x01,
-    0x12, 0x03, 0x2d, 0x10, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x00, 0x03, 0x12, 0x03,
-    0x2d, 0x18, 0x19, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x05, 0x02, 0x01, 0x12, 0x03, 0x2e, 0x02, 0x1d,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x01, 0x04, 0x12, 0x03, 0x2e, 0x02, 0x0a, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x05, 0x02, 0x01, 0x05, 0x12, 0x03, 0x2e, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x05, 0x02, 0x01, 0x01, 0x12, 0x03, 0x2e, 0x12, 0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05,
-    0x02, 0x01, 0x03, 0x12, 0x03, 0x2e, 0x1b, 0x1c, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x05, 0x02, 0x02,
-    0x12, 0x03, 0x2f, 0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x02, 0x04, 0x12, 0x03,
-    0x2f, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x02, 0x06, 0x12, 0x03, 0x2f, 0x0b,
-    0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x02, 0x01, 0x12, 0x03, 0x2f, 0x11, 0x17, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x02, 0x03, 0x12, 0x03, 0x2f, 0x1a, 0x1b,
+    0x64, 0x75, 0x65, 0x18, 0x06, 0x20, 0x01, 0x28, 0x04, 0x52, 0x03, 0x64, 0x75, 0x65, 0x12, 0x18,
+    0x0a, 0x07, 0x74, 0x72, 0x61, 0x63, 0x6b, 0x65, 0x64, 0x18, 0x07, 0x20, 0x01, 0x28, 0x04, 0x52,
+    0x07, 0x74, 0x72, 0x61, 0x63, 0x6b, 0x65, 0x64, 0x12, 0x1f, 0x0a, 0x0b, 0x74, 0x69, 0x6d, 0x65,
+    0x72, 0x5f, 0x73, 0x74, 0x61, 0x72, 0x74, 0x18, 0x08, 0x20, 0x01, 0x28, 0x04, 0x52, 0x0a, 0x74,
+    0x69, 0x6d, 0x65, 0x72, 0x53, 0x74, 0x61, 0x72, 0x74, 0x12, 0x1a, 0x0a, 0x08, 0x6e, 0x6f, 0x74,
+    0x69, 0x66, 0x69, 0x65, 0x64, 0x18, 0x09, 0x20, 0x01, 0x28, 0x04, 0x52, 0x08, 0x6e, 0x6f, 0x74,
+    0x69, 0x66, 0x69, 0x65, 0x64, 0x12, 0x1a, 0x0a, 0x08, 0x65, 0x73, 0x74, 0x69, 0x6d, 0x61, 0x74,
+    0x65, 0x18, 0x0a, 0x20, 0x01, 0x28, 0x04, 0x52, 0x08, 0x65, 0x73, 0x74, 0x69, 0x6d, 0x61, 0x74,
+    0x65, 0x12, 0x20, 0x0a, 0x0b, 0x61, 0x74, 0x74, 0x61, 0x63, 0x68, 0x6d, 0x65, 0x6e, 0x74, 0x73,
+    0x18, 0x0b, 0x20, 0x03, 0x28, 0x09, 0x52, 0x0b, 0x61, 0x74, 0x74, 0x61, 0x63, 0x68, 0x6d, 0x65,
+    0x6e, 0x74, 0x73, 0x22, 0x8e, 0x03, 0x0a, 0x04, 0x4e, 0x6f, 0x64, 0x65, 0x12, 0x0e, 0x0a, 0x02,
+    0x69, 0x64, 0x18, 0x01, 0x20, 0x02, 0x28, 0x04, 0x52, 0x02, 0x69, 0x64, 0x12, 0x1e, 0x0a, 0x04,
+    0x6d, 0x65, 0x74, 0x61, 0x18, 0x02, 0x20, 0x02, 0x28, 0x0b, 0x32, 0x0a, 0x2e, 0x76, 0x6f, 0x69,
+    0x64, 0x2e, 0x4d, 0x65, 0x74, 0x61, 0x52, 0x04, 0x6d, 0x65, 0x74, 0x61, 0x12, 0x12, 0x0a, 0x04,
+    0x74, 0x65, 0x78, 0x74, 0x18, 0x03, 0x20, 0x02, 0x28, 0x09, 0x52, 0x04, 0x74, 0x65, 0x78, 0x74,
+    0x12, 0x1a, 0x0a, 0x08, 0x63, 0x68, 0x69, 0x6c, 0x64, 0x72, 0x65, 0x6e, 0x18, 0x04, 0x20, 0x03,
+    0x28, 0x04, 0x52, 0x08, 0x63, 0x68, 0x69, 0x6c, 0x64, 0x72, 0x65, 0x6e, 0x12, 0x1c, 0x0a, 0x09,
+    0x63, 0x6f, 0x6c, 0x6c, 0x61, 0x70, 0x73, 0x65, 0x64, 0x18, 0x05, 0x20, 0x02, 0x28, 0x08, 0x52,
+    0x09, 0x63, 0x6f, 0x6c, 0x6c, 0x61, 0x70, 0x73, 0x65, 0x64, 0x12, 0x1a, 0x0a, 0x08, 0x73, 0x74,
+    0x72, 0x69, 0x63, 0x6b, 0x65, 0x6e, 0x18, 0x06, 0x20, 0x02, 0x28, 0x08, 0x52, 0x08, 0x73, 0x74,
+    0x72, 0x69, 0x63, 0x6b, 0x65, 0x6e, 0x12, 0x23, 0x0a, 0x0d, 0x68, 0x69, 0x64, 0x65, 0x5f, 0x73,
+    0x74, 0x72, 0x69, 0x63, 0x6b, 0x65, 0x6e, 0x18, 0x07, 0x20, 0x02, 0x28, 0x08, 0x52, 0x0c, 0x68,
+    0x69, 0x64, 0x65, 0x53, 0x74, 0x72, 0x69, 0x63, 0x6b, 0x65, 0x6e, 0x12, 0x0c, 0x0a, 0x01, 0x78,
+    0x18, 0x08, 0x20, 0x02, 0x28, 0x0d, 0x52, 0x01, 0x78, 0x12, 0x0c, 0x0a, 0x01, 0x79, 0x18, 0x09,
+    0x20, 0x02, 0x28, 0x0d, 0x52, 0x01, 0x79, 0x12, 0x1a, 0x0a, 0x08, 0x73, 0x65, 0x6c, 0x65, 0x63,
+    0x74, 0x65, 0x64, 0x18, 0x0a, 0x20, 0x02, 0x28, 0x08, 0x52, 0x08, 0x73, 0x65, 0x6c, 0x65, 0x63,
+    0x74, 0x65, 0x64, 0x12, 0x1b, 0x0a, 0x09, 0x70, 0x61, 0x72, 0x65, 0x6e, 0x74, 0x5f, 0x69, 0x64,
+    0x18, 0x0b, 0x20, 0x02, 0x28, 0x04, 0x52, 0x08, 0x70, 0x61, 0x72, 0x65, 0x6e, 0x74, 0x49, 0x64,
+    0x12, 0x1b, 0x0a, 0x09, 0x66, 0x72, 0x65, 0x65, 0x5f, 0x74, 0x65, 0x78, 0x74, 0x18, 0x0c, 0x20,
+    0x01, 0x28, 0x09, 0x52, 0x08, 0x66, 0x72, 0x65, 0x65, 0x54, 0x65, 0x78, 0x74, 0x12, 0x21, 0x0a,
+    0x0c, 0x61, 0x75, 0x74, 0x6f, 0x5f, 0x61, 0x72, 0x72, 0x61, 0x6e, 0x67, 0x65, 0x18, 0x0d, 0x20,
+    0x02, 0x28, 0x08, 0x52, 0x0b, 0x61, 0x75, 0x74, 0x6f, 0x41, 0x72, 0x72, 0x61, 0x6e, 0x67, 0x65,
+    0x12, 0x14, 0x0a, 0x05, 0x74, 0x69, 0x74, 0x6c, 0x65, 0x18, 0x0e, 0x20, 0x01, 0x28, 0x09, 0x52,
+    0x05, 0x74, 0x69, 0x74, 0x6c, 0x65, 0x12, 0x1c, 0x0a, 0x09, 0x69, 0x63, 0x6f, 0x6e, 0x69, 0x66,
+    0x69, 0x65, 0x64, 0x18, 0x0f, 0x20, 0x01, 0x28, 0x08, 0x52, 0x09, 0x69, 0x63, 0x6f, 0x6e, 0x69,
+    0x66, 0x69, 0x65, 0x64, 0x22, 0x3d, 0x0a, 0x05, 0x41, 0x72, 0x72, 0x6f, 0x77, 0x12, 0x1b, 0x0a,
+    0x09, 0x66, 0x72, 0x6f, 0x6d, 0x5f, 0x6e, 0x6f, 0x64, 0x65, 0x18, 0x01, 0x20, 0x02, 0x28, 0x04,
+    0x52, 0x08, 0x66, 0x72, 0x6f, 0x6d, 0x4e, 0x6f, 0x64, 0x65, 0x12, 0x17, 0x0a, 0x07, 0x74, 0x6f,
+    0x5f, 0x6e, 0x6f, 0x64, 0x65, 0x18, 0x02, 0x20, 0x02, 0x28, 0x04, 0x52, 0x06, 0x74, 0x6f, 0x4e,
+    0x6f, 0x64, 0x65, 0x22, 0xe5, 0x02, 0x0a, 0x06, 0x53, 0x63, 0x72, 0x65, 0x65, 0x6e, 0x12, 0x20,
+    0x0a, 0x05, 0x6e, 0x6f, 0x64, 0x65, 0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x0a, 0x2e,
+    0x76, 0x6f, 0x69, 0x64, 0x2e, 0x4e, 0x6f, 0x64, 0x65, 0x52, 0x05, 0x6e, 0x6f, 0x64, 0x65, 0x73,
+    0x12, 0x15, 0x0a, 0x06, 0x6d, 0x61, 0x78, 0x5f, 0x69, 0x64, 0x18, 0x02, 0x20, 0x02, 0x28, 0x04,
+    0x52, 0x05, 0x6d, 0x61, 0x78, 0x49, 0x64, 0x12, 0x23, 0x0a, 0x06, 0x61, 0x72, 0x72, 0x6f, 0x77,
+    0x73, 0x18, 0x03, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x0b, 0x2e, 0x76, 0x6f, 0x69, 0x64, 0x2e, 0x41,
+    0x72, 0x72, 0x6f, 0x77, 0x52, 0x06, 0x61, 0x72, 0x72, 0x6f, 0x77, 0x73, 0x12, 0x26, 0x0a, 0x05,
+    0x74, 0x72, 0x61, 0x73, 0x68, 0x18, 0x04, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x10, 0x2e, 0x76, 0x6f,
+    0x69, 0x64, 0x2e, 0x54, 0x72, 0x61, 0x73, 0x68, 0x45, 0x6e, 0x74, 0x72, 0x79, 0x52, 0x05, 0x74,
+    0x72, 0x61, 0x73, 0x68, 0x12, 0x2c, 0x0a, 0x09, 0x62, 0x6f, 0x6f, 0x6b, 0x6d, 0x61, 0x72, 0x6b,
+    0x73, 0x18, 0x05, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x0e, 0x2e, 0x76, 0x6f, 0x69, 0x64, 0x2e, 0x42,
+    0x6f, 0x6f, 0x6b, 0x6d, 0x61, 0x72, 0x6b, 0x52, 0x09, 0x62, 0x6f, 0x6f, 0x6b, 0x6d, 0x61, 0x72,
+    0x6b, 0x73, 0x12, 0x2a, 0x0a, 0x07, 0x61, 0x72, 0x63, 0x68, 0x69, 0x76, 0x65, 0x18, 0x06, 0x20,
+    0x03, 0x28, 0x0b, 0x32, 0x10, 0x2e, 0x76, 0x6f, 0x69, 0x64, 0x2e, 0x54, 0x72, 0x61, 0x73, 0x68,
+    0x45, 0x6e, 0x74, 0x72, 0x79, 0x52, 0x07, 0x61, 0x72, 0x63, 0x68, 0x69, 0x76, 0x65, 0x12, 0x2f,
+    0x0a, 0x0a, 0x74, 0x6f, 0x6d, 0x62, 0x73, 0x74, 0x6f, 0x6e, 0x65, 0x73, 0x18, 0x07, 0x20, 0x03,
+    0x28, 0x0b, 0x32, 0x0f, 0x2e, 0x76, 0x6f, 0x69, 0x64, 0x2e, 0x54, 0x6f, 0x6d, 0x62, 0x73, 0x74,
+    0x6f, 0x6e, 0x65, 0x52, 0x0a, 0x74, 0x6f, 0x6d, 0x62, 0x73, 0x74, 0x6f, 0x6e, 0x65, 0x73, 0x12,
+    0x25, 0x0a, 0x08, 0x6c, 0x6f, 0x63, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x18, 0x08, 0x20, 0x01, 0x28,
+    0x0b, 0x32, 0x09, 0x2e, 0x76, 0x6f, 0x69, 0x64, 0x2e, 0x47, 0x70, 0x73, 0x52, 0x08, 0x6c, 0x6f,
+    0x63, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x12, 0x23, 0x0a, 0x06, 0x67, 0x72, 0x6f, 0x75, 0x70, 0x73,
+    0x18, 0x09, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x0b, 0x2e, 0x76, 0x6f, 0x69, 0x64, 0x2e, 0x47, 0x72,
+    0x6f, 0x75, 0x70, 0x52, 0x06, 0x67, 0x72, 0x6f, 0x75, 0x70, 0x73, 0x22, 0x50, 0x0a, 0x09, 0x54,
+    0x6f, 0x6d, 0x62, 0x73, 0x74, 0x6f, 0x6e, 0x65, 0x12, 0x0e, 0x0a, 0x02, 0x69, 0x64, 0x18, 0x01,
+    0x20, 0x02, 0x28, 0x04, 0x52, 0x02, 0x69, 0x64, 0x12, 0x14, 0x0a, 0x05, 0x63, 0x74, 0x69, 0x6d,
+    0x65, 0x18, 0x02, 0x20, 0x02, 0x28, 0x04, 0x52, 0x05, 0x63, 0x74, 0x69, 0x6d, 0x65, 0x12, 0x1d,
+    0x0a, 0x0a, 0x64, 0x65, 0x6c, 0x65, 0x74, 0x65, 0x64, 0x5f, 0x61, 0x74, 0x18, 0x03, 0x20, 0x02,
+    0x28, 0x04, 0x52, 0x09, 0x64, 0x65, 0x6c, 0x65, 0x74, 0x65, 0x64, 0x41, 0x74, 0x22, 0xbf, 0x01,
+    0x0a, 0x0a, 0x54, 0x72, 0x61, 0x73, 0x68, 0x45, 0x6e, 0x74, 0x72, 0x79, 0x12, 0x12, 0x0a, 0x04,
+    0x72, 0x6f, 0x6f, 0x74, 0x18, 0x01, 0x20, 0x02, 0x28, 0x04, 0x52, 0x04, 0x72, 0x6f, 0x6f, 0x74,
+    0x12, 0x1b, 0x0a, 0x09, 0x70, 0x61, 0x72, 0x65, 0x6e, 0x74, 0x5f, 0x69, 0x64, 0x18, 0x02, 0x20,
+    0x02, 0x28, 0x04, 0x52, 0x08, 0x70, 0x61, 0x72, 0x65, 0x6e, 0x74, 0x49, 0x64, 0x12, 0x0c, 0x0a,
+    0x01, 0x78, 0x18, 0x03, 0x20, 0x02, 0x28, 0x0d, 0x52, 0x01, 0x78, 0x12, 0x0c, 0x0a, 0x01, 0x79,
+    0x18, 0x04, 0x20, 0x02, 0x28, 0x0d, 0x52, 0x01, 0x79, 0x12, 0x1d, 0x0a, 0x0a, 0x64, 0x65, 0x6c,
+    0x65, 0x74, 0x65, 0x64, 0x5f, 0x61, 0x74, 0x18, 0x05, 0x20, 0x02, 0x28, 0x04, 0x52, 0x09, 0x64,
+    0x65, 0x6c, 0x65, 0x74, 0x65, 0x64, 0x41, 0x74, 0x12, 0x20, 0x0a, 0x05, 0x6e, 0x6f, 0x64, 0x65,
+    0x73, 0x18, 0x06, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x0a, 0x2e, 0x76, 0x6f, 0x69, 0x64, 0x2e, 0x4e,
+    0x6f, 0x64, 0x65, 0x52, 0x05, 0x6e, 0x6f, 0x64, 0x65, 0x73, 0x12, 0x23, 0x0a, 0x06, 0x61, 0x72,
+    0x72, 0x6f, 0x77, 0x73, 0x18, 0x07, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x0b, 0x2e, 0x76, 0x6f, 0x69,
+    0x64, 0x2e, 0x41, 0x72, 0x72, 0x6f, 0x77, 0x52, 0x06, 0x61, 0x72, 0x72, 0x6f, 0x77, 0x73, 0x22,
+    0x32, 0x0a, 0x08, 0x42, 0x6f, 0x6f, 0x6b, 0x6d, 0x61, 0x72, 0x6b, 0x12, 0x12, 0x0a, 0x04, 0x73,
+    0x6c, 0x6f, 0x74, 0x18, 0x01, 0x20, 0x02, 0x28, 0x0d, 0x52, 0x04, 0x73, 0x6c, 0x6f, 0x74, 0x12,
+    0x12, 0x0a, 0x04, 0x6e, 0x6f, 0x64, 0x65, 0x18, 0x02, 0x20, 0x02, 0x28, 0x04, 0x52, 0x04, 0x6e,
+    0x6f, 0x64, 0x65, 0x22, 0x33, 0x0a, 0x05, 0x47, 0x72, 0x6f, 0x75, 0x70, 0x12, 0x16, 0x0a, 0x06,
+    0x61, 0x6e, 0x63, 0x68, 0x6f, 0x72, 0x18, 0x01, 0x20, 0x02, 0x28, 0x04, 0x52, 0x06, 0x61, 0x6e,
+    0x63, 0x68, 0x6f, 0x72, 0x12, 0x12, 0x0a, 0x04, 0x6e, 0x61, 0x6d, 0x65, 0x18, 0x02, 0x20, 0x02,
+    0x28, 0x09, 0x52, 0x04, 0x6e, 0x61, 0x6d, 0x65, 0x22, 0xa1, 0x01, 0x0a, 0x07, 0x53, 0x65, 0x73,
+    0x73, 0x69, 0x6f, 0x6e, 0x12, 0x1a, 0x0a, 0x08, 0x73, 0x65, 0x6c, 0x65, 0x63, 0x74, 0x65, 0x64,
+    0x18, 0x01, 0x20, 0x01, 0x28, 0x04, 0x52, 0x08, 0x73, 0x65, 0x6c, 0x65, 0x63, 0x74, 0x65, 0x64,
+    0x12, 0x21, 0x0a, 0x0c, 0x64, 0x72, 0x61, 0x77, 0x69, 0x6e, 0x67, 0x5f, 0x72, 0x6f, 0x6f, 0x74,
+    0x18, 0x02, 0x20, 0x02, 0x28, 0x04, 0x52, 0x0b, 0x64, 0x72, 0x61, 0x77, 0x69, 0x6e, 0x67, 0x52,
+    0x6f, 0x6f, 0x74, 0x12, 0x15, 0x0a, 0x06, 0x76, 0x69, 0x65, 0x77, 0x5f, 0x79, 0x18, 0x03, 0x20,
+    0x02, 0x28, 0x0d, 0x52, 0x05, 0x76, 0x69, 0x65, 0x77, 0x59, 0x12, 0x1b, 0x0a, 0x09, 0x73, 0x68,
+    0x6f, 0x77, 0x5f, 0x6c, 0x6f, 0x67, 0x73, 0x18, 0x04, 0x20, 0x02, 0x28, 0x08, 0x52, 0x08, 0x73,
+    0x68, 0x6f, 0x77, 0x4c, 0x6f, 0x67, 0x73, 0x12, 0x23, 0x0a, 0x06, 0x6d, 0x61, 0x63, 0x72, 0x6f,
+    0x73, 0x18, 0x05, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x0b, 0x2e, 0x76, 0x6f, 0x69, 0x64, 0x2e, 0x4d,
+    0x61, 0x63, 0x72, 0x6f, 0x52, 0x06, 0x6d, 0x61, 0x63, 0x72, 0x6f, 0x73, 0x22, 0x3b, 0x0a, 0x05,
+    0x4d, 0x61, 0x63, 0x72, 0x6f, 0x12, 0x1a, 0x0a, 0x08, 0x72, 0x65, 0x67, 0x69, 0x73, 0x74, 0x65,
+    0x72, 0x18, 0x01, 0x20, 0x02, 0x28, 0x09, 0x52, 0x08, 0x72, 0x65, 0x67, 0x69, 0x73, 0x74, 0x65,
+    0x72, 0x12, 0x16, 0x0a, 0x06, 0x65, 0x76, 0x65, 0x6e, 0x74, 0x73, 0x18, 0x02, 0x20, 0x03, 0x28,
+    0x0c, 0x52, 0x06, 0x65, 0x76, 0x65, 0x6e, 0x74, 0x73, 0x4a, 0xba, 0x24, 0x0a, 0x06, 0x12, 0x04,
+    0x00, 0x00, 0x6e, 0x01, 0x0a, 0x08, 0x0a, 0x01, 0x0c, 0x12, 0x03, 0x00, 0x00, 0x12, 0x0a, 0x08,
+    0x0a, 0x01, 0x02, 0x12, 0x03, 0x02, 0x08, 0x0c, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x00, 0x12, 0x04,
+    0x04, 0x00, 0x07, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x00, 0x01, 0x12, 0x03, 0x04, 0x08, 0x0b,
+    0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x00, 0x12, 0x03, 0x05, 0x02, 0x1a, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x00, 0x02, 0x00, 0x04, 0x12, 0x03, 0x05, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x00, 0x02, 0x00, 0x05, 0x12, 0x03, 0x05, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02,
+    0x00, 0x01, 0x12, 0x03, 0x05, 0x12, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x03,
+    0x12, 0x03, 0x05, 0x18, 0x19, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x01, 0x12, 0x03, 0x06,
+    0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x04, 0x12, 0x03, 0x06, 0x02, 0x0a,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x05, 0x12, 0x03, 0x06, 0x0b, 0x11, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x01, 0x12, 0x03, 0x06, 0x12, 0x17, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x00, 0x02, 0x01, 0x03, 0x12, 0x03, 0x06, 0x1a, 0x1b, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x01,
+    0x12, 0x04, 0x09, 0x00, 0x0c, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x01, 0x01, 0x12, 0x03, 0x09,
+    0x08, 0x0b, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02, 0x00, 0x12, 0x03, 0x0a, 0x02, 0x19, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x00, 0x04, 0x12, 0x03, 0x0a, 0x02, 0x0a, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x01, 0x02, 0x00, 0x05, 0x12, 0x03, 0x0a, 0x0b, 0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x01, 0x02, 0x00, 0x01, 0x12, 0x03, 0x0a, 0x11, 0x14, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02,
+    0x00, 0x03, 0x12, 0x03, 0x0a, 0x17, 0x18, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02, 0x01, 0x12,
+    0x03, 0x0b, 0x02, 0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x01, 0x04, 0x12, 0x03, 0x0b,
+    0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x01, 0x05, 0x12, 0x03, 0x0b, 0x0b, 0x10,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x01, 0x01, 0x12, 0x03, 0x0b, 0x11, 0x14, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x01, 0x02, 0x01, 0x03, 0x12, 0x03, 0x0b, 0x17, 0x18, 0x0a, 0x0a, 0x0a, 0x02,
+    0x04, 0x02, 0x12, 0x04, 0x0e, 0x00, 0x1b, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x02, 0x01, 0x12,
+    0x03, 0x0e, 0x08, 0x0c, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x02, 0x02, 0x00, 0x12, 0x03, 0x0f, 0x02,
+    0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x00, 0x04, 0x12, 0x03, 0x0f, 0x02, 0x0a, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x00, 0x05, 0x12, 0x03, 0x0f, 0x0b, 0x11, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x02, 0x02, 0x00, 0x01, 0x12, 0x03, 0x0f, 0x12, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x02, 0x02, 0x00, 0x03, 0x12, 0x03, 0x0f, 0x1a, 0x1b, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x02, 0x02,
+    0x01, 0x12, 0x03, 0x10, 0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x01, 0x04, 0x12,
+    0x03, 0x10, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x01, 0x05, 0x12, 0x03, 0x10,
+    0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x01, 0x01, 0x12, 0x03, 0x10, 0x12, 0x17,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x01, 0x03, 0x12, 0x03, 0x10, 0x1a, 0x1b, 0x0a, 0x0b,
+    0x0a, 0x04, 0x04, 0x02, 0x02, 0x02, 0x12, 0x03, 0x11, 0x02, 0x22, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x02, 0x02, 0x02, 0x04, 0x12, 0x03, 0x11, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02,
+    0x02, 0x05, 0x12, 0x03, 0x11, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x02, 0x01,
+    0x12, 0x03, 0x11, 0x12, 0x1d, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x02, 0x03, 0x12, 0x03,
+    0x11, 0x20, 0x21, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x02, 0x02, 0x03, 0x12, 0x03, 0x12, 0x02, 0x17,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x03, 0x04, 0x12, 0x03, 0x12, 0x02, 0x0a, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x02, 0x02, 0x03, 0x06, 0x12, 0x03, 0x12, 0x0b, 0x0e, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x02, 0x02, 0x03, 0x01, 0x12, 0x03, 0x12, 0x0f, 0x12, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02,
+    0x02, 0x03, 0x03, 0x12, 0x03, 0x12, 0x15, 0x16, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x02, 0x02, 0x04,
+    0x12, 0x03, 0x13, 0x02, 0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x04, 0x04, 0x12, 0x03,
+    0x13, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x04, 0x06, 0x12, 0x03, 0x13, 0x0b,
+    0x0e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x04, 0x01, 0x12, 0x03, 0x13, 0x0f, 0x13, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x04, 0x03, 0x12, 0x03, 0x13, 0x16, 0x17, 0x0a, 0x0b, 0x0a,
+    0x04, 0x04, 0x02, 0x02, 0x05, 0x12, 0x03, 0x14, 0x02, 0x1a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02,
+    0x02, 0x05, 0x04, 0x12, 0x03, 0x14, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x05,
+    0x05, 0x12, 0x03, 0x14, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x05, 0x01, 0x12,
+    0x03, 0x14, 0x12, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x05, 0x03, 0x12, 0x03, 0x14,
+    0x18, 0x19, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x02, 0x02, 0x06, 0x12, 0x03, 0x15, 0x02, 0x1e, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x06, 0x04, 0x12, 0x03, 0x15, 0x02, 0x0a, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x02, 0x02, 0x06, 0x05, 0x12, 0x03, 0x15, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x02, 0x02, 0x06, 0x01, 0x12, 0x03, 0x15, 0x12, 0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02,
+    0x06, 0x03, 0x12, 0x03, 0x15, 0x1c, 0x1d, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x02, 0x02, 0x07, 0x12,
+    0x03, 0x16, 0x02, 0x22, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x07, 0x04, 0x12, 0x03, 0x16,
+    0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x07, 0x05, 0x12, 0x03, 0x16, 0x0b, 0x11,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x07, 0x01, 0x12, 0x03, 0x16, 0x12, 0x1d, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x02, 0x02, 0x07, 0x03, 0x12, 0x03, 0x16, 0x20, 0x21, 0x0a, 0x0b, 0x0a, 0x04,
+    0x04, 0x02, 0x02, 0x08, 0x12, 0x03, 0x17, 0x02, 0x1f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02,
+    0x08, 0x04, 0x12, 0x03, 0x17, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x08, 0x05,
+    0x12, 0x03, 0x17, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x08, 0x01, 0x12, 0x03,
+    0x17, 0x12, 0x1a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x08, 0x03, 0x12, 0x03, 0x17, 0x1d,
+    0x1e, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x02, 0x02, 0x09, 0x12, 0x03, 0x18, 0x02, 0x20, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x02, 0x02, 0x09, 0x04, 0x12, 0x03, 0x18, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x02, 0x02, 0x09, 0x05, 0x12, 0x03, 0x18, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02,
+    0x02, 0x09, 0x01, 0x12, 0x03, 0x18, 0x12, 0x1a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x09,
+    0x03, 0x12, 0x03, 0x18, 0x1d, 0x1f, 0x0a, 0x30, 0x0a, 0x04, 0x04, 0x02, 0x02, 0x0a, 0x12, 0x03,
+    0x1a, 0x02, 0x23, 0x1a, 0x23, 0x20, 0x66, 0x69, 0x6c, 0x65, 0x73, 0x20, 0x74, 0x68, 0x65, 0x20,
+    0x6e, 0x6f, 0x64, 0x65, 0x20, 0x72, 0x65, 0x66, 0x65, 0x72, 0x73, 0x20, 0x74, 0x6f, 0x2c, 0x20,
+    0x62, 0x79, 0x20, 0x70, 0x61, 0x74, 0x68, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x0a,
+    0x04, 0x12, 0x03, 0x1a, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x0a, 0x05, 0x12,
+    0x03, 0x1a, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x0a, 0x01, 0x12, 0x03, 0x1a,
+    0x12, 0x1d, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x0a, 0x03, 0x12, 0x03, 0x1a, 0x20, 0x22,
+    0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x03, 0x12, 0x04, 0x1d, 0x00, 0x2f, 0x01, 0x0a, 0x0a, 0x0a, 0x03,
+    0x04, 0x03, 0x01, 0x12, 0x03, 0x1d, 0x08, 0x0c, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02, 0x00,
+    0x12, 0x03, 0x1e, 0x02, 0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x00, 0x04, 0x12, 0x03,
+    0x1e, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x00, 0x05, 0x12, 0x03, 0x1e, 0x0b,
+    0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x00, 0x01, 0x12, 0x03, 0x1e, 0x12, 0x14, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x00, 0x03, 0x12, 0x03, 0x1e, 0x17, 0x18, 0x0a, 0x0b, 0x0a,
+    0x04, 0x04, 0x03, 0x02, 0x01, 0x12, 0x03, 0x1f, 0x02, 0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03,
+    0x02, 0x01, 0x04, 0x12, 0x03, 0x1f, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x01,
+    0x06, 0x12, 0x03, 0x1f, 0x0b, 0x0f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x01, 0x01, 0x12,
+    0x03, 0x1f, 0x10, 0x14, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x01, 0x03, 0x12, 0x03, 0x1f,
+    0x17, 0x18, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02, 0x02, 0x12, 0x03, 0x20, 0x02, 0x1b, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x02, 0x04, 0x12, 0x03, 0x20, 0x02, 0x0a, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x03, 0x02, 0x02, 0x05, 0x12, 0x03, 0x20, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x03, 0x02, 0x02, 0x01, 0x12, 0x03, 0x20, 0x12, 0x16, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02,
+    0x02, 0x03, 0x12, 0x03, 0x20, 0x19, 0x1a, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02, 0x03, 0x12,
+    0x03, 0x21, 0x02, 0x1f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x03, 0x04, 0x12, 0x03, 0x21,
+    0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x03, 0x05, 0x12, 0x03, 0x21, 0x0b, 0x11,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x03, 0x01, 0x12, 0x03, 0x21, 0x12, 0x1a, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x03, 0x02, 0x03, 0x03, 0x12, 0x03, 0x21, 0x1d, 0x1e, 0x0a, 0x0b, 0x0a, 0x04,
+    0x04, 0x03, 0x02, 0x04, 0x12, 0x03, 0x22, 0x02, 0x1e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02,
+    0x04, 0x04, 0x12, 0x03, 0x22, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x04, 0x05,
+    0x12, 0x03, 0x22, 0x0b, 0x0f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x04, 0x01, 0x12, 0x03,
+    0x22, 0x10, 0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x04, 0x03, 0x12, 0x03, 0x22, 0x1c,
+    0x1d, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02, 0x05, 0x12, 0x03, 0x23, 0x02, 0x1d, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x03, 0x02, 0x05, 0x04, 0x12, 0x03, 0x23, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x03, 0x02, 0x05, 0x05, 0x12, 0x03, 0x23, 0x0b, 0x0f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03,
+    0x02, 0x05, 0x01, 0x12, 0x03, 0x23, 0x10, 0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x05,
+    0x03, 0x12, 0x03, 0x23, 0x1b, 0x1c, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02, 0x06, 0x12, 0x03,
+    0x24, 0x02, 0x22, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x06, 0x04, 0x12, 0x03, 0x24, 0x02,
+    0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x06, 0x05, 0x12, 0x03, 0x24, 0x0b, 0x0f, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x06, 0x01, 0x12, 0x03, 0x24, 0x10, 0x1d, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x03, 0x02, 0x06, 0x03, 0x12, 0x03, 0x24, 0x20, 0x21, 0x0a, 0x0b, 0x0a, 0x04, 0x04,
+    0x03, 0x02, 0x07, 0x12, 0x03, 0x25, 0x02, 0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x07,
+    0x04, 0x12, 0x03, 0x25, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x07, 0x05, 0x12,
+    0x03, 0x25, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x07, 0x01, 0x12, 0x03, 0x25,
+    0x12, 0x13, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x07, 0x03, 0x12, 0x03, 0x25, 0x16, 0x17,
+    0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02, 0x08, 0x12, 0x03, 0x26, 0x02, 0x18, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x03, 0x02, 0x08, 0x04, 0x12, 0x03, 0x26, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x03, 0x02, 0x08, 0x05, 0x12, 0x03, 0x26, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02,
+    0x08, 0x01, 0x12, 0x03, 0x26, 0x12, 0x13, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x08, 0x03,
+    0x12, 0x03, 0x26, 0x16, 0x17, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02, 0x09, 0x12, 0x03, 0x27,
+    0x02, 0x1e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x09, 0x04, 0x12, 0x03, 0x27, 0x02, 0x0a,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x09, 0x05, 0x12, 0x03, 0x27, 0x0b, 0x0f, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x03, 0x02, 0x09, 0x01, 0x12, 0x03, 0x27, 0x10, 0x18, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x03, 0x02, 0x09, 0x03, 0x12, 0x03, 0x27, 0x1b, 0x1d, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03,
+    0x02, 0x0a, 0x12, 0x03, 0x28, 0x02, 0x21, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x0a, 0x04,
+    0x12, 0x03, 0x28, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x0a, 0x05, 0x12, 0x03,
+    0x28, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x0a, 0x01, 0x12, 0x03, 0x28, 0x12,
+    0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x0a, 0x03, 0x12, 0x03, 0x28, 0x1e, 0x20, 0x0a,
+    0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02, 0x0b, 0x12, 0x03, 0x29, 0x02, 0x21, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x03, 0x02, 0x0b, 0x04, 0x12, 0x03, 0x29, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03,
+    0x02, 0x0b, 0x05, 0x12, 0x03, 0x29, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x0b,
+    0x01, 0x12, 0x03, 0x29, 0x12, 0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x0b, 0x03, 0x12,
+    0x03, 0x29, 0x1e, 0x20, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02, 0x0c, 0x12, 0x03, 0x2a, 0x02,
+    0x22, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x0c, 0x04, 0x12, 0x03, 0x2a, 0x02, 0x0a, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x0c, 0x05, 0x12, 0x03, 0x2a, 0x0b, 0x0f, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x03, 0x02, 0x0c, 0x01, 0x12, 0x03, 0x2a, 0x10, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x03, 0x02, 0x0c, 0x03, 0x12, 0x03, 0x2a, 0x1f, 0x21, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02,
+    0x0d, 0x12, 0x03, 0x2c, 0x02, 0x1d, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x0d, 0x04, 0x12,
+    0x03, 0x2c, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x0d, 0x05, 0x12, 0x03, 0x2c,
+    0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x0d, 0x01, 0x12, 0x03, 0x2c, 0x12, 0x17,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x0d, 0x03, 0x12, 0x03, 0x2c, 0x1a, 0x1c, 0x0a, 0x2e,
+    0x0a, 0x04, 0x04, 0x03, 0x02, 0x0e, 0x12, 0x03, 0x2e, 0x02, 0x1f, 0x1a, 0x21, 0x20, 0x61, 0x6e,
+    0x20, 0x61, 0x6e, 0x63, 0x68, 0x6f, 0x72, 0x20, 0x64, 0x72, 0x61, 0x77, 0x6e, 0x20, 0x61, 0x73,
+    0x20, 0x69, 0x74, 0x73, 0x20, 0x6f, 0x6e, 0x65, 0x20, 0x6c, 0x69, 0x6e, 0x65, 0x0a, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x03, 0x02, 0x0e, 0x04, 0x12, 0x03, 0x2e, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x03, 0x02, 0x0e, 0x05, 0x12, 0x03, 0x2e, 0x0b, 0x0f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03,
+    0x02, 0x0e, 0x01, 0x12, 0x03, 0x2e, 0x10, 0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x0e,
+    0x03, 0x12, 0x03, 0x2e, 0x1c, 0x1e, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x04, 0x12, 0x04, 0x31, 0x00,
+    0x34, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x04, 0x01, 0x12, 0x03, 0x31, 0x08, 0x0d, 0x0a, 0x0b,
+    0x0a, 0x04, 0x04, 0x04, 0x02, 0x00, 0x12, 0x03, 0x32, 0x02, 0x20, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x04, 0x02, 0x00, 0x04, 0x12, 0x03, 0x32, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x04, 0x02,
+    0x00, 0x05, 0x12, 0x03, 0x32, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x04, 0x02, 0x00, 0x01,
+    0x12, 0x03, 0x32, 0x12, 0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x04, 0x02, 0x00, 0x03, 0x12, 0x03,
+    0x32, 0x1e, 0x1f, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x04, 0x02, 0x01, 0x12, 0x03, 0x33, 0x02, 0x1e,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x04, 0x02, 0x01, 0x04, 0x12, 0x03, 0x33, 0x02, 0x0a, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x04, 0x02, 0x01, 0x05, 0x12, 0x03, 0x33, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x04, 0x02, 0x01, 0x01, 0x12, 0x03, 0x33, 0x12, 0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x04,
+    0x02, 0x01, 0x03, 0x12, 0x03, 0x33, 0x1c, 0x1d, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x05, 0x12, 0x04,
+    0x36, 0x00, 0x43, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x05, 0x01, 0x12, 0x03, 0x36, 0x08, 0x0e,
+    0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x05, 0x02, 0x00, 0x12, 0x03, 0x37, 0x02, 0x1a, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x05, 0x02, 0x00, 0x04, 0x12, 0x03, 0x37, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x05, 0x02, 0x00, 0x06, 0x12, 0x03, 0x37, 0x0b, 0x0f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02,
+    0x00, 0x01, 0x12, 0x03, 0x37, 0x10, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x00, 0x03,
+    0x12, 0x03, 0x37, 0x18, 0x19, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x05, 0x02, 0x01, 0x12, 0x03, 0x38,
+    0x02, 0x1d, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x01, 0x04, 0x12, 0x03, 0x38, 0x02, 0x0a,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x01, 0x05, 0x12, 0x03, 0x38, 0x0b, 0x11, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x05, 0x02, 0x01, 0x01, 0x12, 0x03, 0x38, 0x12, 0x18, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x05, 0x02, 0x01, 0x03, 0x12, 0x03, 0x38, 0x1b, 0x1c, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x05,
+    0x02, 0x02, 0x12, 0x03, 0x39, 0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x02, 0x04,
+    0x12, 0x03, 0x39, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x02, 0x06, 0x12, 0x03,
+    0x39, 0x0b, 0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x02, 0x01, 0x12, 0x03, 0x39, 0x11,
+    0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x02, 0x03, 0x12, 0x03, 0x39, 0x1a, 0x1b, 0x0a,
+    0x0b, 0x0a, 0x04, 0x04, 0x05, 0x02, 0x03, 0x12, 0x03, 0x3a, 0x02, 0x20, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x05, 0x02, 0x03, 0x04, 0x12, 0x03, 0x3a, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05,
+    0x02, 0x03, 0x06, 0x12, 0x03, 0x3a, 0x0b, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x03,
+    0x01, 0x12, 0x03, 0x3a, 0x16, 0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x03, 0x03, 0x12,
+    0x03, 0x3a, 0x1e, 0x1f, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x05, 0x02, 0x04, 0x12, 0x03, 0x3b, 0x02,
+    0x22, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x04, 0x04, 0x12, 0x03, 0x3b, 0x02, 0x0a, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x04, 0x06, 0x12, 0x03, 0x3b, 0x0b, 0x13, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x05, 0x02, 0x04, 0x01, 0x12, 0x03, 0x3b, 0x14, 0x1d, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x05, 0x02, 0x04, 0x03, 0x12, 0x03, 0x3b, 0x20, 0x21, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x05, 0x02,
+    0x05, 0x12, 0x03, 0x3d, 0x02, 0x22, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x05, 0x04, 0x12,
+    0x03, 0x3d, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x05, 0x06, 0x12, 0x03, 0x3d,
+    0x0b, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x05, 0x01, 0x12, 0x03, 0x3d, 0x16, 0x1d,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x05, 0x03, 0x12, 0x03, 0x3d, 0x20, 0x21, 0x0a, 0x0b,
+    0x0a, 0x04, 0x04, 0x05, 0x02, 0x06, 0x12, 0x03, 0x3f, 0x02, 0x24, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x05, 0x02, 0x06, 0x04, 0x12, 0x03, 0x3f, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02,
+    0x06, 0x06, 0x12, 0x03, 0x3f, 0x0b, 0x14, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x06, 0x01,
+    0x12, 0x03, 0x3f, 0x15, 0x1f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x06, 0x03, 0x12, 0x03,
+    0x3f, 0x22, 0x23, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x05, 0x02, 0x07, 0x12, 0x03, 0x41, 0x02, 0x1c,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x07, 0x04, 0x12, 0x03, 0x41, 0x02, 0x0a, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x05, 0x02, 0x07, 0x06, 0x12, 0x03, 0x41, 0x0b, 0x0e, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x05, 0x02, 0x07, 0x01, 0x12, 0x03, 0x41, 0x0f, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05,
+    0x02, 0x07, 0x03, 0x12, 0x03, 0x41, 0x1a, 0x1b, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x05, 0x02, 0x08,
+    0x12, 0x03, 0x42, 0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x08, 0x04, 0x12, 0x03,
+    0x42, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x08, 0x06, 0x12, 0x03, 0x42, 0x0b,
+    0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x08, 0x01, 0x12, 0x03, 0x42, 0x11, 0x17, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x08, 0x03, 0x12, 0x03, 0x42, 0x1a, 0x1b, 0x0a, 0x0a, 0x0a,
+    0x02, 0x04, 0x06, 0x12, 0x04, 0x45, 0x00, 0x49, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x06, 0x01,
+    0x12, 0x03, 0x45, 0x08, 0x11, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x06, 0x02, 0x00, 0x12, 0x03, 0x46,
+    0x02, 0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x00, 0x04, 0x12, 0x03, 0x46, 0x02, 0x0a,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x00, 0x05, 0x12, 0x03, 0x46, 0x0b, 0x11, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x06, 0x02, 0x00, 0x01, 0x12, 0x03, 0x46, 0x12, 0x14, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x06, 0x02, 0x00, 0x03, 0x12, 0x03, 0x46, 0x17, 0x18, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x06,
+    0x02, 0x01, 0x12, 0x03, 0x47, 0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x01, 0x04,
+    0x12, 0x03, 0x47, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x01, 0x05, 0x12, 0x03,
+    0x47, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x01, 0x01, 0x12, 0x03, 0x47, 0x12,
+    0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x01, 0x03, 0x12, 0x03, 0x47, 0x1a, 0x1b, 0x0a,
+    0x0b, 0x0a, 0x04, 0x04, 0x06, 0x02, 0x02, 0x12, 0x03, 0x48, 0x02, 0x21, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x06, 0x02, 0x02, 0x04, 0x12, 0x03, 0x48, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06,
+    0x02, 0x02, 0x05, 0x12, 0x03, 0x48, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x02,
+    0x01, 0x12, 0x03, 0x48, 0x12, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x02, 0x03, 0x12,
+    0x03, 0x48, 0x1f, 0x20, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x07, 0x12, 0x04, 0x4b, 0x00, 0x53, 0x01,
+    0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x07, 0x01, 0x12, 0x03, 0x4b, 0x08, 0x12, 0x0a, 0x0b, 0x0a, 0x04,
+    0x04, 0x07, 0x02, 0x00, 0x12, 0x03, 0x4c, 0x02, 0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02,
+    0x00, 0x04, 0x12, 0x03, 0x4c, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x00, 0x05,
+    0x12, 0x03, 0x4c, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x00, 0x01, 0x12, 0x03,
+    0x4c, 0x12, 0x16, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x00, 0x03, 0x12, 0x03, 0x4c, 0x19,
+    0x1a, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x07, 0x02, 0x01, 0x12, 0x03, 0x4d, 0x02, 0x20, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x07, 0x02, 0x01, 0x04, 0x12, 0x03, 0x4d, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x07, 0x02, 0x01, 0x05, 0x12, 0x03, 0x4d, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07,
+    0x02, 0x01, 0x01, 0x12, 0x03, 0x4d, 0x12, 0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x01,
+    0x03, 0x12, 0x03, 0x4d, 0x1e, 0x1f, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x07, 0x02, 0x02, 0x12, 0x03,
+    0x4e, 0x02, 0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x02, 0x04, 0x12, 0x03, 0x4e, 0x02,
+    0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x02, 0x05, 0x12, 0x03, 0x4e, 0x0b, 0x11, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x02, 0x01, 0x12, 0x03, 0x4e, 0x12, 0x13, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x07, 0x02, 0x02, 0x03, 0x12, 0x03, 0x4e, 0x16, 0x17, 0x0a, 0x0b, 0x0a, 0x04, 0x04,
+    0x07, 0x02, 0x03, 0x12, 0x03, 0x4f, 0x02, 0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x03,
+    0x04, 0x12, 0x03, 0x4f, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x03, 0x05, 0x12,
+    0x03, 0x4f, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x03, 0x01, 0x12, 0x03, 0x4f,
+    0x12, 0x13, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x03, 0x03, 0x12, 0x03, 0x4f, 0x16, 0x17,
+    0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x07, 0x02, 0x04, 0x12, 0x03, 0x50, 0x02, 0x21, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x07, 0x02, 0x04, 0x04, 0x12, 0x03, 0x50, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x07, 0x02, 0x04, 0x05, 0x12, 0x03, 0x50, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02,
+    0x04, 0x01, 0x12, 0x03, 0x50, 0x12, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x04, 0x03,
+    0x12, 0x03, 0x50, 0x1f, 0x20, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x07, 0x02, 0x05, 0x12, 0x03, 0x51,
+    0x02, 0x1a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x05, 0x04, 0x12, 0x03, 0x51, 0x02, 0x0a,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x05, 0x06, 0x12, 0x03, 0x51, 0x0b, 0x0f, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x07, 0x02, 0x05, 0x01, 0x12, 0x03, 0x51, 0x10, 0x15, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x07, 0x02, 0x05, 0x03, 0x12, 0x03, 0x51, 0x18, 0x19, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x07,
+    0x02, 0x06, 0x12, 0x03, 0x52, 0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x06, 0x04,
+    0x12, 0x03, 0x52, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x06, 0x06, 0x12, 0x03,
+    0x52, 0x0b, 0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x06, 0x01, 0x12, 0x03, 0x52, 0x11,
+    0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x06, 0x03, 0x12, 0x03, 0x52, 0x1a, 0x1b, 0x0a,
+    0x0a, 0x0a, 0x02, 0x04, 0x08, 0x12, 0x04, 0x55, 0x00, 0x58, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04,
+    0x08, 0x01, 0x12, 0x03, 0x55, 0x08, 0x10, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x08, 0x02, 0x00, 0x12,
+    0x03, 0x56, 0x02, 0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x00, 0x04, 0x12, 0x03, 0x56,
+    0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x00, 0x05, 0x12, 0x03, 0x56, 0x0b, 0x11,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x00, 0x01, 0x12, 0x03, 0x56, 0x12, 0x16, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x08, 0x02, 0x00, 0x03, 0x12, 0x03, 0x56, 0x19, 0x1a, 0x0a, 0x0b, 0x0a, 0x04,
+    0x04, 0x08, 0x02, 0x01, 0x12, 0x03, 0x57, 0x02, 0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02,
+    0x01, 0x04, 0x12, 0x03, 0x57, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x01, 0x05,
+    0x12, 0x03, 0x57, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x01, 0x01, 0x12, 0x03,
+    0x57, 0x12, 0x16, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x01, 0x03, 0x12, 0x03, 0x57, 0x19,
+    0x1a, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x09, 0x12, 0x04, 0x5b, 0x00, 0x5e, 0x01, 0x0a, 0x0a, 0x0a,
+    0x03, 0x04, 0x09, 0x01, 0x12, 0x03, 0x5b, 0x08, 0x0d, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x09, 0x02,
+    0x00, 0x12, 0x03, 0x5c, 0x02, 0x1d, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x00, 0x04, 0x12,
+    0x03, 0x5c, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x00, 0x05, 0x12, 0x03, 0x5c,
+    0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x00, 0x01, 0x12, 0x03, 0x5c, 0x12, 0x18,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x00, 0x03, 0x12, 0x03, 0x5c, 0x1b, 0x1c, 0x0a, 0x0b,
+    0x0a, 0x04, 0x04, 0x09, 0x02, 0x01, 0x12, 0x03, 0x5d, 0x02, 0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x09, 0x02, 0x01, 0x04, 0x12, 0x03, 0x5d, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02,
+    0x01, 0x05, 0x12, 0x03, 0x5d, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x01, 0x01,
+    0x12, 0x03, 0x5d, 0x12, 0x16, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x01, 0x03, 0x12, 0x03,
+    0x5d, 0x19, 0x1a, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x0a, 0x12, 0x04, 0x62, 0x00, 0x68, 0x01, 0x0a,
+    0x0a, 0x0a, 0x03, 0x04, 0x0a, 0x01, 0x12, 0x03, 0x62, 0x08, 0x0f, 0x0a, 0x0b, 0x0a, 0x04, 0x04,
+    0x0a, 0x02, 0x00, 0x12, 0x03, 0x63, 0x02, 0x1f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x00,
+    0x04, 0x12, 0x03, 0x63, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x00, 0x05, 0x12,
+    0x03, 0x63, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x00, 0x01, 0x12, 0x03, 0x63,
+    0x12, 0x1a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x00, 0x03, 0x12, 0x03, 0x63, 0x1d, 0x1e,
+    0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x0a, 0x02, 0x01, 0x12, 0x03, 0x64, 0x02, 0x23, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x0a, 0x02, 0x01, 0x04, 0x12, 0x03, 0x64, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x0a, 0x02, 0x01, 0x05, 0x12, 0x03, 0x64, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0a, 0x02,
+    0x01, 0x01, 0x12, 0x03, 0x64, 0x12, 0x1e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x01, 0x03,
+    0x12, 0x03, 0x64, 0x21, 0x22, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x0a, 0x02, 0x02, 0x12, 0x03, 0x65,
+    0x02, 0x1d, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x02, 0x04, 0x12, 0x03, 0x65, 0x02, 0x0a,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x02, 0x05, 0x12, 0x03, 0x65, 0x0b, 0x11, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x0a, 0x02, 0x02, 0x01, 0x12, 0x03, 0x65, 0x12, 0x18, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x0a, 0x02, 0x02, 0x03, 0x12, 0x03, 0x65, 0x1b, 0x1c, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x0a,
+    0x02, 0x03, 0x12, 0x03, 0x66, 0x02, 0x1e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x03, 0x04,
+    0x12, 0x03, 0x66, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x03, 0x05, 0x12, 0x03,
+    0x66, 0x0b, 0x0f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x03, 0x01, 0x12, 0x03, 0x66, 0x10,
+    0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x03, 0x03, 0x12, 0x03, 0x66, 0x1c, 0x1d, 0x0a,
+    0x0b, 0x0a, 0x04, 0x04, 0x0a, 0x02, 0x04, 0x12, 0x03, 0x67, 0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x0a, 0x02, 0x04, 0x04, 0x12, 0x03, 0x67, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0a,
+    0x02, 0x04, 0x06, 0x12, 0x03, 0x67, 0x0b, 0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x04,
+    0x01, 0x12, 0x03, 0x67, 0x11, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x04, 0x03, 0x12,
+    0x03, 0x67, 0x1a, 0x1b, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x0b, 0x12, 0x04, 0x6b, 0x00, 0x6e, 0x01,
+    0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x0b, 0x01, 0x12, 0x03, 0x6b, 0x08, 0x0d, 0x0a, 0x0b, 0x0a, 0x04,
+    0x04, 0x0b, 0x02, 0x00, 0x12, 0x03, 0x6c, 0x02, 0x1f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0b, 0x02,
+    0x00, 0x04, 0x12, 0x03, 0x6c, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0b, 0x02, 0x00, 0x05,
+    0x12, 0x03, 0x6c, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0b, 0x02, 0x00, 0x01, 0x12, 0x03,
+    0x6c, 0x12, 0x1a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0b, 0x02, 0x00, 0x03, 0x12, 0x03, 0x6c, 0x1d,
+    0x1e, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x0b, 0x02, 0x01, 0x12, 0x03, 0x6d, 0x02, 0x1c, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x0b, 0x02, 0x01, 0x04, 0x12, 0x03, 0x6d, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x0b, 0x02, 0x01, 0x05, 0x12, 0x03, 0x6d, 0x0b, 0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0b,
+    0x02, 0x01, 0x01, 0x12, 0x03, 0x6d, 0x11, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0b, 0x02, 0x01,
+    0x03, 0x12, 0x03, 0x6d, 0x1a, 0x1b,
 ];
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
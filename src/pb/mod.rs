@@ -1,3 +1,4 @@
 mod data_model;
 
-pub use self::data_model::{Tag, Meta, Node, Gps, Screen, Arrow};
+pub use self::data_model::{Tag, Meta, Node, Gps, Screen, Arrow, TrashEntry,
+                           Bookmark, Group, Session, Macro, Tombstone};
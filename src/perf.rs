@@ -0,0 +1,127 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+// how many of the latest timings are kept for each operation
+const SAMPLES: usize = 128;
+
+// the latest timings of one operation, oldest overwritten first
+#[derive(Debug, Clone, Default)]
+pub struct Samples {
+    ring: Vec<Duration>,
+    next: usize,
+    // every one recorded, not just those still in the ring
+    pub count: u64,
+}
+
+impl Samples {
+    pub fn record(&mut self, took: Duration) {
+        if self.ring.len() < SAMPLES {
+            self.ring.push(took);
+        } else {
+            self.ring[self.next] = took;
+        }
+        self.next = (self.next + 1) % SAMPLES;
+        self.count += 1;
+    }
+
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ring.is_empty()
+    }
+
+    pub fn mean(&self) -> Option<Duration> {
+        if self.ring.is_empty() {
+            return None;
+        }
+        let total: Duration = self.ring.iter().sum();
+        Some(total / self.ring.len() as u32)
+    }
+
+    pub fn max(&self) -> Option<Duration> {
+        self.ring.iter().max().cloned()
+    }
+
+    // nearest rank, so the 50th of 1, 2, 3, 4 is 2
+    pub fn percentile(&self, p: u8) -> Option<Duration> {
+        if self.ring.is_empty() {
+            return None;
+        }
+        let mut sorted = self.ring.clone();
+        sorted.sort();
+        let rank = (f64::from(p.min(100)) / 100.0 * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.max(1) - 1])
+    }
+}
+
+// timings of the operations worth watching when the ui feels slow, by name
+#[derive(Debug, Clone, Default)]
+pub struct Perf {
+    pub ops: BTreeMap<&'static str, Samples>,
+}
+
+impl Perf {
+    pub fn record(&mut self, op: &'static str, took: Duration) {
+        self.ops.entry(op).or_default().record(took);
+    }
+
+    // a line for each operation, for the log pane
+    pub fn summary(&self) -> Vec<String> {
+        self.ops
+            .iter()
+            .filter(|&(_, s)| !s.is_empty())
+            .map(|(op, s)| {
+                format!("{}: avg {} p95 {} max {} over {} of {}",
+                        op,
+                        millis(s.mean().unwrap()),
+                        millis(s.percentile(95).unwrap()),
+                        millis(s.max().unwrap()),
+                        s.len(),
+                        s.count)
+            })
+            .collect()
+    }
+}
+
+fn millis(d: Duration) -> String {
+    let micros = d.as_secs() * 1_000_000 + u64::from(d.subsec_nanos()) / 1000;
+    format!("{}.{:03}ms", micros / 1000, micros % 1000)
+}
+
+#[test]
+fn test_samples() {
+    let ms = Duration::from_millis;
+    let mut samples = Samples::default();
+    assert_eq!(samples.mean(), None);
+    assert_eq!(samples.percentile(50), None);
+    for i in 1..5 {
+        samples.record(ms(i));
+    }
+    assert_eq!(samples.mean(), Some(Duration::from_micros(2500)));
+    assert_eq!(samples.max(), Some(ms(4)));
+    assert_eq!(samples.percentile(50), Some(ms(2)));
+    assert_eq!(samples.percentile(95), Some(ms(4)));
+    assert_eq!(samples.percentile(0), Some(ms(1)));
+
+    // the oldest are dropped once the ring is full
+    for _ in 0..SAMPLES {
+        samples.record(ms(10));
+    }
+    assert_eq!(samples.len(), SAMPLES);
+    assert_eq!(samples.count, SAMPLES as u64 + 4);
+    assert_eq!(samples.mean(), Some(ms(10)));
+    samples.record(ms(30));
+    assert_eq!(samples.max(), Some(ms(30)));
+    assert_eq!(samples.percentile(50), Some(ms(10)));
+
+    let mut perf = Perf::default();
+    assert!(perf.summary().is_empty());
+    perf.record("save", Duration::from_micros(1500));
+    perf.record("draw", ms(12));
+    assert_eq!(perf.summary(),
+               vec!["draw: avg 12.000ms p95 12.000ms max 12.000ms over 1 of 1",
+                    "save: avg 1.500ms p95 1.500ms max 1.500ms over 1 of 1"]);
+    assert_eq!(perf.ops.get("draw").map(|s| s.count), Some(1));
+}
@@ -1,26 +1,36 @@
-use std::cmp;
+use std::cmp::{self, min};
+use std::collections::{BTreeMap, HashMap};
 
-pub fn plot_sparkline<T>(nums_in: Vec<T>) -> String
+use time;
+
+use {Glyphs, Screen};
+
+pub fn plot_sparkline<T>(nums_in: Vec<T>, glyphs: &Glyphs) -> String
     where T: Into<i64>
 {
-    const BARS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
-
+    let bars = &glyphs.bars;
     let nums: Vec<_> = nums_in.into_iter().map(|n| n.into()).collect();
     let max = nums.iter().max().unwrap();
 
     let mut ret = String::new();
     for n in &nums {
         if *max as i64 == 0 {
-            ret.push(BARS[0]);
+            ret.push(bars[0]);
         } else {
-            let idx = (BARS.len() - 1) as i64 * n / max;
-            ret.push(BARS[idx as usize]);
+            let idx = (bars.len() - 1) as i64 * n / max;
+            ret.push(bars[idx as usize]);
         }
     }
     ret
 }
 
-pub fn bounded_count_sparkline<T>(nums_in: Vec<T>, start: T, end: T, bars: usize) -> String
+pub fn bounded_count_sparkline<T>(
+    nums_in: Vec<T>,
+    start: T,
+    end: T,
+    bars: usize,
+    glyphs: &Glyphs,
+) -> String
     where T: Into<i64>
 {
     if bars == 0 {
@@ -45,5 +55,354 @@ pub fn bounded_count_sparkline<T>(nums_in: Vec<T>, start: T, end: T, bars: usize
         let idx = (n - start) / step;
         counts[cmp::min(idx, bars - 1)] += 1;
     }
-    plot_sparkline(counts)
+    plot_sparkline(counts, glyphs)
+}
+
+const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep",
+                            "Oct", "Nov", "Dec"];
+const WEEKDAYS: [char; 7] = ['S', 'M', 'T', 'W', 'T', 'F', 'S'];
+
+// a line of month labels followed by one line per weekday, sunday first,
+// with a column per week ending in the current one. each cell is shaded
+// by how many nodes were created or completed on that local day.
+pub fn heatmap(screen: &Screen, weeks: usize) -> Vec<String> {
+    let now = time::now();
+    let events: Vec<u64> = screen.nodes
+        .values()
+        .filter(|n| n.id != 0)
+        .flat_map(|n| Some(n.meta.ctime).into_iter().chain(n.meta.finish_time))
+        .collect();
+    heatmap_at(
+        &events,
+        now.to_timespec().sec as u64,
+        now.tm_utcoff as i64,
+        weeks,
+        screen.glyphs(),
+    )
+}
+
+fn heatmap_at(
+    events: &[u64],
+    now: u64,
+    utc_offset: i64,
+    weeks: usize,
+    glyphs: &Glyphs,
+) -> Vec<String> {
+    let local_day = |ts: u64| local_day(ts, utc_offset);
+
+    let mut counts: HashMap<i64, u64> = HashMap::new();
+    for &ts in events {
+        *counts.entry(local_day(ts)).or_insert(0) += 1;
+    }
+
+    let today = local_day(now);
+    let first_day = today - weekday(today) - 7 * (weeks as i64 - 1);
+    let day_at = |week: usize, row: usize| first_day + 7 * week as i64 + row as i64;
+    let max = (0..weeks)
+        .flat_map(|week| (0..7).map(move |row| day_at(week, row)))
+        .filter_map(|day| counts.get(&day))
+        .max()
+        .cloned()
+        .unwrap_or(0);
+
+    // label each month at the first week that ends in it, if it fits
+    let mut labels = vec![' '; weeks];
+    let changes: Vec<usize> = (0..weeks)
+        .filter(|&week| week == 0 || month(day_at(week, 6)) != month(day_at(week - 1, 6)))
+        .collect();
+    for (i, &week) in changes.iter().enumerate() {
+        let next = changes.get(i + 1).cloned().unwrap_or(weeks + 1);
+        if week + 3 <= weeks && week + 4 <= next {
+            for (j, c) in MONTHS[month(day_at(week, 6))].chars().enumerate() {
+                labels[week + j] = c;
+            }
+        }
+    }
+
+    let mut lines = vec![format!("  {}", labels.into_iter().collect::<String>())];
+    for (row, weekday) in WEEKDAYS.iter().enumerate() {
+        let mut line = format!("{} ", weekday);
+        for week in 0..weeks {
+            let day = day_at(week, row);
+            let count = if day > today { 0 } else { counts.get(&day).cloned().unwrap_or(0) };
+            let shade = if count == 0 {
+                0
+            } else {
+                // any activity at all gets at least the lightest shade
+                ((4 * count - 1) / max + 1) as usize
+            };
+            line.push(glyphs.shades[shade]);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+// seconds since the epoch to days since the epoch, starting at local midnight
+fn local_day(ts: u64, utc_offset: i64) -> i64 {
+    let secs = ts as i64 + utc_offset;
+    if secs >= 0 { secs / 86400 } else { (secs - 86399) / 86400 }
+}
+
+// how many events fell on each of the last `days` local days, oldest first
+pub fn daily_counts(events: &[u64], now: u64, utc_offset: i64, days: usize) -> Vec<u64> {
+    let today = local_day(now, utc_offset);
+    let mut counts = vec![0; days];
+    for &ts in events {
+        let age = today - local_day(ts, utc_offset);
+        if age >= 0 && (age as usize) < days {
+            counts[days - 1 - age as usize] += 1;
+        }
+    }
+    counts
+}
+
+// the last `width` values scaled against the largest of them, padded on
+// the left when there are fewer values than cells
+pub fn sparkline(values: &[u64], width: usize, glyphs: &Glyphs) -> String {
+    // even a zero gets the lowest bar
+    let ticks = &glyphs.bars[1..];
+
+    let shown = &values[values.len().saturating_sub(width)..];
+    let max = shown.iter().cloned().max().unwrap_or(0);
+
+    let mut ret: String = (shown.len()..width).map(|_| ' ').collect();
+    for &v in shown {
+        if max == 0 || v == 0 {
+            ret.push(ticks[0]);
+        } else {
+            ret.push(ticks[((8 * v - 1) / max) as usize]);
+        }
+    }
+    ret
+}
+
+// a bar width cells long, filled as far as done goes towards total. it's
+// only empty when nothing is done and only full when everything is.
+pub fn progress_bar(done: usize, total: usize, width: usize, glyphs: &Glyphs) -> String {
+    let filled = if total == 0 || width == 0 {
+        0
+    } else if done >= total {
+        width
+    } else {
+        let rounded = (done * width + total / 2) / total;
+        min(cmp::max(rounded, min(done, 1)), width - 1)
+    };
+    let (full, empty) = (glyphs.shades[3], glyphs.shades[1]);
+    (0..width).map(|i| if i < filled { full } else { empty }).collect()
+}
+
+// days since the epoch to day of the week, sunday being 0
+fn weekday(day: i64) -> i64 {
+    // the epoch was a thursday
+    ((day + 4) % 7 + 7) % 7
+}
+
+// days since the epoch to month of the year, january being 0
+fn month(day: i64) -> usize {
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = day + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    (if mp < 10 { mp + 2 } else { mp - 10 }) as usize
+}
+
+#[test]
+fn test_heatmap_cells() {
+    use capabilities::UNICODE;
+
+    // wednesday 2017-03-08 12:00 UTC
+    let now = 1488974400;
+    let day = 86400;
+    let sunday_feb_26 = now - 10 * day;
+    let mut events = vec![sunday_feb_26; 4];
+    events.push(now - day);
+    events.push(now - day);
+    // 02:00 UTC on wednesday, which is still tuesday further west
+    events.push(now - 10 * 60 * 60);
+    // monday 2017-01-16
+    events.push(now - 51 * day);
+    // too old to show up
+    events.push(now - 100 * day);
+
+    assert_eq!(
+        heatmap_at(&events, now, 0, 8, &UNICODE),
+        vec![
+            "    Feb   ",
+            "S       █ ",
+            "M ░       ",
+            "T        ▒",
+            "W        ░",
+            "T         ",
+            "F         ",
+            "S         ",
+        ]
+    );
+
+    let shifted = heatmap_at(&events, now, -5 * 60 * 60, 8, &UNICODE);
+    assert_eq!(shifted[3], "T        ▓");
+    assert_eq!(shifted[4], "W         ");
+
+    // no activity at all
+    for line in &heatmap_at(&[], now, 0, 8, &UNICODE)[1..] {
+        assert_eq!(line[1..].trim(), "");
+    }
+}
+
+#[test]
+fn test_sparkline() {
+    use capabilities::{ASCII, UNICODE};
+
+    assert_eq!(sparkline(&[], 4, &UNICODE), "    ");
+    assert_eq!(sparkline(&[0, 0, 0], 3, &UNICODE), "▁▁▁");
+    assert_eq!(sparkline(&[1, 2, 3, 4, 5, 6, 7, 8], 8, &UNICODE), "▁▂▃▄▅▆▇█");
+    assert_eq!(sparkline(&[0, 1, 16], 3, &UNICODE), "▁▁█");
+    assert_eq!(sparkline(&[3, 3], 2, &UNICODE), "██");
+
+    // only the most recent values fit
+    assert_eq!(sparkline(&[100, 0, 4, 8], 2, &UNICODE), "▄█");
+    assert_eq!(sparkline(&[8], 3, &UNICODE), "  █");
+    assert_eq!(sparkline(&[8], 0, &UNICODE), "");
+
+    assert_eq!(sparkline(&[1, 2, 3, 4, 5, 6, 7, 8], 8, &ASCII), "..--==##");
+}
+
+#[test]
+fn test_progress_bar() {
+    use capabilities::{ASCII, UNICODE};
+
+    assert_eq!(progress_bar(0, 9, 8, &UNICODE), "░░░░░░░░");
+    assert_eq!(progress_bar(9, 9, 8, &UNICODE), "▓▓▓▓▓▓▓▓");
+    assert_eq!(progress_bar(4, 9, 8, &UNICODE), "▓▓▓▓░░░░");
+    assert_eq!(progress_bar(1, 2, 4, &UNICODE), "▓▓░░");
+
+    // halves round up, but a little done is never nothing and almost
+    // everything is never all of it
+    assert_eq!(progress_bar(1, 16, 4, &UNICODE), "▓░░░");
+    assert_eq!(progress_bar(1, 8, 4, &UNICODE), "▓░░░");
+    assert_eq!(progress_bar(3, 8, 4, &UNICODE), "▓▓░░");
+    assert_eq!(progress_bar(99, 100, 4, &UNICODE), "▓▓▓░");
+
+    assert_eq!(progress_bar(2, 4, 0, &UNICODE), "");
+    assert_eq!(progress_bar(0, 0, 3, &UNICODE), "░░░");
+    assert_eq!(progress_bar(1, 2, 4, &ASCII), "++..");
+}
+
+#[test]
+fn test_daily_counts() {
+    // wednesday 2017-03-08 12:00 UTC
+    let now = 1488974400;
+    let day = 86400;
+    let events = vec![now, now - 11 * 60 * 60, now - 13 * 60 * 60, now - 3 * day, now - 4 * day];
+    assert_eq!(daily_counts(&events, now, 0, 4), vec![1, 0, 1, 2]);
+
+    // at UTC+2 the early one is already on wednesday
+    assert_eq!(daily_counts(&events, now, 2 * 60 * 60, 4), vec![1, 0, 0, 3]);
+    assert_eq!(daily_counts(&events, now, 0, 0), Vec::<u64>::new());
+}
+
+// fit a width x height grid around the points, with a margin, and bin
+// them into its cells.  keys are (row, col), values index into points.
+// (0.0, 0.0) marks a failed location lookup and is left out.
+pub fn gps_bins(
+    points: &[(f32, f32)],
+    width: usize,
+    height: usize,
+) -> BTreeMap<(usize, usize), Vec<usize>> {
+    let mut bins = BTreeMap::new();
+    let known: Vec<usize> = (0..points.len())
+        .filter(|&i| points[i] != (0.0, 0.0))
+        .collect();
+    if known.is_empty() || width == 0 || height == 0 {
+        return bins;
+    }
+
+    // the narrowest band of longitudes holding every point starts just
+    // after the widest gap between them, which may cross the antimeridian
+    let mut lons: Vec<f32> = known.iter().map(|&i| points[i].1).collect();
+    lons.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mut west = lons[0];
+    let mut widest_gap = lons[0] + 360.0 - lons[lons.len() - 1];
+    for pair in lons.windows(2) {
+        if pair[1] - pair[0] > widest_gap {
+            widest_gap = pair[1] - pair[0];
+            west = pair[1];
+        }
+    }
+    let east_of_west = |lon: f32| ((lon - west) % 360.0 + 360.0) % 360.0;
+
+    let north = known.iter().map(|&i| points[i].0).fold(-90.0, f32::max);
+    let south = known.iter().map(|&i| points[i].0).fold(90.0, f32::min);
+
+    // a tenth of the extent on each side, which is at least a degree
+    // so that lone points end up centered
+    let margin = |extent: f32| (extent.max(1.0) - extent) / 2.0 + extent.max(1.0) / 10.0;
+    let lon_span = 360.0 - widest_gap;
+    let lat_span = north - south;
+    let lon_margin = margin(lon_span);
+    let lat_margin = margin(lat_span);
+    for i in known {
+        let (lat, lon) = points[i];
+        let x = (east_of_west(lon) + lon_margin) / (lon_span + 2.0 * lon_margin);
+        let y = (north + lat_margin - lat) / (lat_span + 2.0 * lat_margin);
+        let col = (x * (width - 1) as f32).round() as usize;
+        let row = (y * (height - 1) as f32).round() as usize;
+        bins.entry((min(row, height - 1), min(col, width - 1)))
+            .or_insert_with(Vec::new)
+            .push(i);
+    }
+    bins
+}
+
+// each binned cell shows how many points landed in it
+pub fn gps_map(
+    bins: &BTreeMap<(usize, usize), Vec<usize>>,
+    width: usize,
+    height: usize,
+    glyphs: &Glyphs,
+) -> Vec<String> {
+    let mut grid = vec![vec![glyphs.map_empty; width]; height];
+    for (&(row, col), points) in bins {
+        grid[row][col] = if points.len() > 9 {
+            '*'
+        } else {
+            (b'0' + points.len() as u8) as char
+        };
+    }
+    grid.into_iter().map(|row| row.into_iter().collect()).collect()
+}
+
+#[test]
+fn test_gps_bins_across_antimeridian() {
+    use capabilities::UNICODE;
+
+    let points = vec![(10.0, 178.0),
+                      (0.0, 0.0),
+                      (20.0, 179.0),
+                      (15.0, -179.0),
+                      (15.0, -179.0)];
+    let bins = gps_bins(&points, 37, 13);
+
+    // 3 degrees of longitude plus margins across 36 columns, and
+    // 10 degrees of latitude plus margins down 12 rows
+    let expected: BTreeMap<(usize, usize), Vec<usize>> =
+        vec![((1, 13), vec![2]), ((6, 33), vec![3, 4]), ((11, 3), vec![0])]
+            .into_iter()
+            .collect();
+    assert_eq!(bins, expected);
+
+    let map = gps_map(&bins, 37, 13, &UNICODE);
+    assert_eq!(map.len(), 13);
+    assert_eq!(map[6].chars().nth(33), Some('2'));
+    assert_eq!(map[0], "·".repeat(37));
+
+    // nothing but failed lookups
+    assert!(gps_bins(&[(0.0, 0.0)], 10, 10).is_empty());
+
+    // a single point sits in the middle
+    let single = gps_bins(&[(51.5, -0.1)], 11, 11);
+    assert_eq!(single.keys().collect::<Vec<_>>(), vec![&(5, 5)]);
 }
@@ -0,0 +1,104 @@
+use NodeID;
+
+pub const WORK_S: u64 = 25 * 60;
+pub const BREAK_S: u64 = 5 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Work,
+    Break,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    // a pomodoro was completed and the break has started
+    WorkDone,
+    // the break is over, and with it this pomodoro
+    BreakDone,
+}
+
+#[derive(Debug, Clone)]
+pub struct Pomodoro {
+    pub node: NodeID,
+    pub phase: Phase,
+    ends_at: u64,
+    // time left in the phase when it was paused
+    paused: Option<u64>,
+}
+
+impl Pomodoro {
+    pub fn start(node: NodeID, now: u64) -> Pomodoro {
+        Pomodoro {
+            node,
+            phase: Phase::Work,
+            ends_at: now + WORK_S,
+            paused: None,
+        }
+    }
+
+    pub fn remaining(&self, now: u64) -> u64 {
+        self.paused.unwrap_or_else(|| self.ends_at.saturating_sub(now))
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.is_some()
+    }
+
+    pub fn pause(&mut self, now: u64) {
+        if self.paused.is_none() {
+            self.paused = Some(self.remaining(now));
+        }
+    }
+
+    pub fn resume(&mut self, now: u64) {
+        if let Some(remaining) = self.paused.take() {
+            self.ends_at = now + remaining;
+        }
+    }
+
+    // advance to the next phase if the current one is over
+    pub fn tick(&mut self, now: u64) -> Option<Transition> {
+        if self.is_paused() || now < self.ends_at {
+            return None;
+        }
+        match self.phase {
+            Phase::Work => {
+                self.phase = Phase::Break;
+                self.ends_at = now + BREAK_S;
+                Some(Transition::WorkDone)
+            }
+            Phase::Break => Some(Transition::BreakDone),
+        }
+    }
+
+    pub fn indicator(&self, now: u64) -> String {
+        let remaining = self.remaining(now);
+        let icon = match self.phase {
+            Phase::Work => "work",
+            Phase::Break => "break",
+        };
+        let paused = if self.is_paused() { " (paused)" } else { "" };
+        format!("{} {}:{:02}{}", icon, remaining / 60, remaining % 60, paused)
+    }
+}
+
+#[test]
+fn test_pomodoro_phases() {
+    let mut pomodoro = Pomodoro::start(7, 1000);
+    assert_eq!(pomodoro.indicator(1000), "work 25:00");
+    assert_eq!(pomodoro.tick(1000 + WORK_S - 1), None);
+
+    // pausing stops the clock
+    pomodoro.pause(1060);
+    assert_eq!(pomodoro.tick(1000 + WORK_S * 2), None);
+    assert_eq!(pomodoro.indicator(5000), "work 24:00 (paused)");
+    pomodoro.resume(2000);
+    assert_eq!(pomodoro.remaining(2000), WORK_S - 60);
+
+    let work_end = 2000 + WORK_S - 60;
+    assert_eq!(pomodoro.tick(work_end), Some(Transition::WorkDone));
+    assert_eq!(pomodoro.phase, Phase::Break);
+    assert_eq!(pomodoro.indicator(work_end + 1), "break 4:59");
+    assert_eq!(pomodoro.tick(work_end + BREAK_S - 1), None);
+    assert_eq!(pomodoro.tick(work_end + BREAK_S), Some(Transition::BreakDone));
+}
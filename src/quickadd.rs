@@ -0,0 +1,355 @@
+use time;
+
+use {Node, estimate};
+
+// tags that mean something where they're written, like queries and
+// `#prio=2`, so they stay in the content
+const KEPT: &[&str] = &["limit", "tagged", "rev", "done", "open", "since", "until", "plot",
+                        "rollup", "n", "prio"];
+
+const DAYS: &[&str] = &["monday", "tuesday", "wednesday", "thursday", "friday", "saturday",
+                        "sunday"];
+
+const DAY_SECS: u64 = 60 * 60 * 24;
+
+// what's taken off the end of a node typed like
+// `fix login bug #backend due:fri est:2h`
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuickAdd {
+    pub content: String,
+    pub due: Option<u64>,
+    // Some(0) clears the estimate
+    pub estimate: Option<u64>,
+    // `#tag`, `#key=value` and any other `key:value`, in the order written
+    pub tags: Vec<(String, String)>,
+}
+
+impl QuickAdd {
+    // the tokens after the text, in any order, up to the first word that
+    // isn't one. nothing is taken if that would leave no text.
+    pub fn parse(text: &str, now: u64) -> QuickAdd {
+        let mut quick_add = QuickAdd {
+            content: text.to_owned(),
+            due: None,
+            estimate: None,
+            tags: vec![],
+        };
+        let tokens = tokenize(text);
+        let mut kept = vec![];
+        let mut start = text.len();
+        for &(at, token) in tokens.iter().rev() {
+            let (key, value) = match split_token(token) {
+                Some(split) => split,
+                None => break,
+            };
+            match key {
+                Key::Tag(key) if KEPT.contains(&key) => kept.push(token),
+                Key::Due => {
+                    match parse_date(&value, now) {
+                        // the last one written wins
+                        Some(due) => quick_add.due = quick_add.due.or(Some(due)),
+                        None => {
+                            warn!("couldn't read the date in {}", token);
+                            break;
+                        }
+                    }
+                }
+                Key::Est => {
+                    match estimate::parse(&value) {
+                        Some(secs) => quick_add.estimate = quick_add.estimate.or(Some(secs)),
+                        None => {
+                            warn!("couldn't read the estimate in {}", token);
+                            break;
+                        }
+                    }
+                }
+                Key::Tag(key) |
+                Key::Other(key) => quick_add.tags.push((key.to_owned(), value)),
+            }
+            start = at;
+        }
+
+        let rest = text[..start].trim_end();
+        if rest.is_empty() {
+            return QuickAdd {
+                content: text.to_owned(),
+                due: None,
+                estimate: None,
+                tags: vec![],
+            };
+        }
+        kept.reverse();
+        quick_add.tags.reverse();
+        quick_add.content = Some(rest).into_iter().chain(kept).collect::<Vec<_>>().join(" ");
+        quick_add
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.due.is_none() && self.estimate.is_none() && self.tags.is_empty()
+    }
+
+    pub fn apply(&self, node: &mut Node) {
+        node.content = self.content.clone();
+        if self.due.is_some() {
+            node.meta.due = self.due;
+        }
+        if let Some(secs) = self.estimate {
+            node.meta.estimate = if secs > 0 { Some(secs) } else { None };
+        }
+        for tag in &self.tags {
+            node.meta.tags.insert(tag.0.clone(), tag.1.clone());
+        }
+    }
+
+    // the tokens again, in a form that parses back to the same thing
+    pub fn tokens(&self) -> String {
+        let mut tokens: Vec<String> = self.tags
+            .iter()
+            .map(|tag| if tag.1.is_empty() {
+                format!("#{}", tag.0)
+            } else if tag.1.contains(char::is_whitespace) || tag.1.contains('"') {
+                format!("{}:\"{}\"", tag.0, tag.1.replace('"', ""))
+            } else {
+                format!("#{}={}", tag.0, tag.1)
+            })
+            .collect();
+        if let Some(due) = self.due {
+            let tm = time::at_utc(time::Timespec::new(due as i64, 0));
+            let format = if due % DAY_SECS == 0 {
+                "%Y-%m-%d"
+            } else {
+                "\"%Y-%m-%d %H:%M\""
+            };
+            tokens.push(format!("due:{}", time::strftime(format, &tm).unwrap()));
+        }
+        if let Some(secs) = self.estimate {
+            tokens.push(format!("est:{}", estimate::format(secs)));
+        }
+        tokens.join(" ")
+    }
+}
+
+enum Key<'a> {
+    Tag(&'a str),
+    Due,
+    Est,
+    Other(&'a str),
+}
+
+// words with where they start, where a `"` keeps going to the next one
+// whatever is in between
+fn tokenize(text: &str) -> Vec<(usize, &str)> {
+    let mut tokens = vec![];
+    let mut start = None;
+    let mut quoted = false;
+    for (idx, c) in text.char_indices() {
+        match (start, c) {
+            (_, '"') => {
+                quoted = !quoted;
+                start = start.or(Some(idx));
+            }
+            (Some(at), c) if c.is_whitespace() && !quoted => {
+                tokens.push((at, &text[at..idx]));
+                start = None;
+            }
+            (None, c) if !c.is_whitespace() => start = Some(idx),
+            _ => {}
+        }
+    }
+    if let Some(at) = start {
+        tokens.push((at, &text[at..]));
+    }
+    tokens
+}
+
+// `#tag`, `#key=value` or `key:value`, with quotes around the value taken
+// off. keys start with a letter, so times like 10:30 and links aren't
+// tokens.
+fn split_token(token: &str) -> Option<(Key<'_>, String)> {
+    if let Some(tag) = token.strip_prefix('#') {
+        let (key, value) = match tag.find('=') {
+            Some(idx) => (&tag[..idx], &tag[idx + 1..]),
+            None => (tag, ""),
+        };
+        if key.is_empty() || key.contains('"') {
+            return None;
+        }
+        return Some((Key::Tag(key), value.to_owned()));
+    }
+    let idx = token.find(':')?;
+    let (key, value) = (&token[..idx], &token[idx + 1..]);
+    let value = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')).unwrap_or(value);
+    let word = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+    if !key.starts_with(char::is_alphabetic) || !key.chars().all(word) ||
+       value.trim().is_empty() || value.starts_with("//") || value.contains('"') {
+        return None;
+    }
+    let key = match key {
+        "due" => Key::Due,
+        "est" => Key::Est,
+        _ => Key::Other(key),
+    };
+    Some((key, value.trim().to_owned()))
+}
+
+// `today`, `tomorrow`, a day of the week like `fri` (the next one from
+// today on), `next fri` (a week after that), `2024-05-01` with an optional
+// `14:00`, or a span from now like `3d`. days start at midnight UTC.
+pub fn parse_date(text: &str, now: u64) -> Option<u64> {
+    let text = text.trim().to_lowercase();
+    let today = now - now % DAY_SECS;
+    if let Ok(tm) = time::strptime(&text, "%Y-%m-%d %H:%M")
+        .or_else(|_| time::strptime(&text, "%Y-%m-%d")) {
+        let secs = tm.to_timespec().sec;
+        return if secs >= 0 { Some(secs as u64) } else { None };
+    }
+    match &*text {
+        "today" => return Some(today),
+        "tomorrow" => return Some(today + DAY_SECS),
+        _ => {}
+    }
+    let (weeks, day) = match text.strip_prefix("next ") {
+        Some(day) => (1, day.trim()),
+        None => (0, &*text),
+    };
+    // short names are any start of the full one, from three letters
+    if let Some(weekday) = DAYS.iter().position(|d| day.len() >= 3 && d.starts_with(day)) {
+        // the first of January 1970 was a Thursday
+        let todays = ((today / DAY_SECS + 3) % 7) as usize;
+        let ahead = (weekday + 7 - todays) % 7;
+        return Some(today + (ahead as u64 + weeks * 7) * DAY_SECS);
+    }
+    if weeks > 0 {
+        return None;
+    }
+    let unit = text.chars().last()?;
+    let n: u64 = text[..text.len() - unit.len_utf8()].parse().ok()?;
+    let unit = match unit {
+        'h' => 60 * 60,
+        'd' => DAY_SECS,
+        'w' => DAY_SECS * 7,
+        'm' => DAY_SECS * 30,
+        'y' => DAY_SECS * 365,
+        _ => return None,
+    };
+    Some(now + n * unit)
+}
+
+#[test]
+fn test_parse_date() {
+    // Friday 2026-10-16 13:00 UTC
+    let now = 1_760_619_600 + 365 * DAY_SECS;
+    let today = now - now % DAY_SECS;
+    assert_eq!(time::at_utc(time::Timespec::new(today as i64, 0)).tm_wday, 5);
+
+    assert_eq!(parse_date("today", now), Some(today));
+    assert_eq!(parse_date("Tomorrow", now), Some(today + DAY_SECS));
+    assert_eq!(parse_date("fri", now), Some(today));
+    assert_eq!(parse_date("friday", now), Some(today));
+    assert_eq!(parse_date("sat", now), Some(today + DAY_SECS));
+    assert_eq!(parse_date("thu", now), Some(today + 6 * DAY_SECS));
+    assert_eq!(parse_date("tues", now), Some(today + 4 * DAY_SECS));
+    assert_eq!(parse_date("next tuesday", now), Some(today + 11 * DAY_SECS));
+    assert_eq!(parse_date("next  fri", now), Some(today + 7 * DAY_SECS));
+    assert_eq!(parse_date("fr", now), None);
+    assert_eq!(parse_date("next", now), None);
+    assert_eq!(parse_date("next 3d", now), None);
+
+    assert_eq!(parse_date("1970-01-02", now), Some(DAY_SECS));
+    assert_eq!(parse_date("1970-01-01 01:30", now), Some(5400));
+    assert_eq!(parse_date("1969-12-31", now), None);
+    assert_eq!(parse_date("2h", now), Some(now + 2 * 60 * 60));
+    assert_eq!(parse_date("3d", now), Some(now + 3 * DAY_SECS));
+    assert_eq!(parse_date("2w", now), Some(now + 14 * DAY_SECS));
+    assert_eq!(parse_date("1m", now), Some(now + 30 * DAY_SECS));
+    assert_eq!(parse_date("1y", now), Some(now + 365 * DAY_SECS));
+    assert_eq!(parse_date("3x", now), None);
+    assert_eq!(parse_date("d", now), None);
+    assert_eq!(parse_date("", now), None);
+    assert_eq!(parse_date("soon", now), None);
+}
+
+#[test]
+fn test_quick_add() {
+    let now = 1_760_619_600 + 365 * DAY_SECS;
+    let today = now - now % DAY_SECS;
+    let parse = |text: &str| QuickAdd::parse(text, now);
+    let tags = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+        pairs.iter().map(|&(k, v)| (k.to_owned(), v.to_owned())).collect()
+    };
+
+    let parsed = parse("fix login bug #backend due:fri est:2h");
+    assert_eq!(parsed.content, "fix login bug");
+    assert_eq!(parsed.due, Some(today));
+    assert_eq!(parsed.estimate, Some(2 * 60 * 60));
+    assert_eq!(parsed.tags, tags(&[("backend", "")]));
+
+    // any order gives the same
+    let mut reordered = parse("fix login bug est:2h due:fri #backend");
+    assert_eq!(reordered, parsed);
+    reordered = parse("fix login bug   due:fri   #backend est:2h  ");
+    assert_eq!(reordered, parsed);
+
+    // unknown keys are tags as written, and quotes hold spaces
+    let parsed = parse("call bob who:\"bob smith\" #prio=2 room:4b due:\"next tuesday\" #x=y");
+    assert_eq!(parsed.content, "call bob #prio=2");
+    assert_eq!(parsed.due, Some(today + 11 * DAY_SECS));
+    assert_eq!(parsed.tags, tags(&[("who", "bob smith"), ("room", "4b"), ("x", "y")]));
+    let parsed = parse("call bob due:\"1970-01-01 01:30\"");
+    assert_eq!(parsed.due, Some(5400));
+
+    // the last of two wins, for tags too
+    let parsed = parse("thing due:tomorrow due:today est:1h est:30m #a=1 #a=2");
+    assert_eq!((parsed.due, parsed.estimate), (Some(today), Some(30 * 60)));
+    let mut node = Node::default();
+    parsed.apply(&mut node);
+    assert_eq!(node.meta.tags["a"], "2");
+    // est:0m clears
+    let parsed = parse("thing est:0m");
+    assert_eq!(parsed.estimate, Some(0));
+    node.meta.estimate = Some(60);
+    parsed.apply(&mut node);
+    assert_eq!((&*node.content, node.meta.estimate, node.meta.due), ("thing", None, Some(today)));
+
+    // only the end is looked at, up to the first plain word
+    let parsed = parse("#draft fix #123 in auth due:fri");
+    assert_eq!(parsed.content, "#draft fix #123 in auth");
+    assert_eq!(parsed.tags, vec![]);
+    let parsed = parse("meet at 10:30 see http://example.com ratio:");
+    assert!(parsed.is_empty());
+    assert_eq!(parsed.content, "meet at 10:30 see http://example.com ratio:");
+
+    // a value that doesn't read stops there, and nothing before it is taken
+    let parsed = parse("thing due:someday #a");
+    assert_eq!(parsed.content, "thing due:someday");
+    assert_eq!(parsed.tags, tags(&[("a", "")]));
+    assert_eq!(parse("thing #a est:lots").tags, vec![]);
+    assert_eq!(parse("thing due:\"next").content, "thing due:\"next");
+
+    // nothing is taken when there'd be no text left, like in queries
+    for text in &["#task", "#plot=done #since=7d", "due:fri est:2h", "", "   "] {
+        let parsed = parse(text);
+        assert!(parsed.is_empty(), "{}", text);
+        assert_eq!(parsed.content, *text);
+    }
+    let parsed = parse("done this week #plot=done #since=7d #topic");
+    assert_eq!(parsed.content, "done this week #plot=done #since=7d");
+    assert_eq!(parsed.tags, tags(&[("topic", "")]));
+    assert_eq!(parse("café #tä est:1h").content, "café");
+}
+
+#[test]
+fn test_quick_add_tokens() {
+    let now = 1_760_619_600;
+    let texts = ["fix login bug #backend due:fri est:2h",
+                 "call bob who:\"bob smith\" due:\"2025-01-02 10:15\" #x=y",
+                 "thing est:1d4h due:3d",
+                 "nothing here"];
+    for text in &texts {
+        let parsed = QuickAdd::parse(text, now);
+        let again = QuickAdd::parse(&format!("{} {}", parsed.content, parsed.tokens()), now);
+        assert_eq!(again, parsed, "{}", text);
+    }
+    assert_eq!(QuickAdd::parse(texts[0], now).tokens(), "#backend due:2025-10-17 est:2h");
+    assert_eq!(QuickAdd::parse("nothing here", now).tokens(), "");
+}
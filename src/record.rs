@@ -0,0 +1,142 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+
+use Coords;
+use input::{Input, decode_event, encode_event};
+
+// what happened to a screen, in the order it happened, so that a session
+// can be played back for a bug report
+#[derive(Debug, Clone, PartialEq)]
+pub enum Entry {
+    Input(Input),
+    // the terminal's size, at the start and whenever it changes
+    Size(Coords),
+    // a batch of input was done with, and the screen drawn
+    Frame,
+}
+
+// kept beside the work file, like the session
+pub fn path(work_path: &str) -> String {
+    format!("{}.events", work_path)
+}
+
+// appends each entry to a file as it happens, so a crash keeps them all
+#[derive(Debug)]
+pub struct Recorder {
+    path: String,
+    file: File,
+}
+
+impl Recorder {
+    // a recording starts over each time, as it only makes sense from the
+    // state the work file was opened in
+    pub fn create(path: &str) -> io::Result<Recorder> {
+        let file = OpenOptions::new().write(true).create(true).truncate(true).open(path)?;
+        Ok(Recorder {
+            path: path.to_owned(),
+            file,
+        })
+    }
+
+    // at is the millisecond it happened at, which replaying holds the
+    // clock to
+    pub fn record(&mut self, at: u64, entry: &Entry) {
+        let line = format!("{} {}\n", at, encode(entry));
+        if let Err(e) = self.file.write_all(line.as_bytes()) {
+            warn!("failed to record an event to {}: {}", self.path, e);
+        }
+    }
+}
+
+// one line, without the time it happened at
+pub fn encode(entry: &Entry) -> String {
+    match *entry {
+        Entry::Input(Input::Event(ref evt)) => format!("event {}", hex(&encode_event(evt))),
+        Entry::Input(Input::Paste(ref text)) => format!("paste {}", hex(text.as_bytes())),
+        Entry::Input(Input::Tick) => "tick".to_owned(),
+        Entry::Size((width, height)) => format!("size {} {}", width, height),
+        Entry::Frame => "frame".to_owned(),
+    }
+}
+
+// a line as written by a Recorder, with the millisecond it was written at
+pub fn decode(line: &str) -> Result<(u64, Entry), String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let at = words.first()
+        .and_then(|at| at.parse().ok())
+        .ok_or_else(|| "no time at the start".to_owned())?;
+    let entry = match words[1..] {
+        ["event", bytes] => {
+            let bytes = unhex(bytes)?;
+            Entry::Input(Input::Event(decode_event(&bytes).ok_or("not an event")?))
+        }
+        ["paste", text] => {
+            let text = String::from_utf8(unhex(text)?).map_err(|_| "paste isn't utf-8")?;
+            Entry::Input(Input::Paste(text))
+        }
+        // a paste of nothing
+        ["paste"] => Entry::Input(Input::Paste(String::new())),
+        ["tick"] => Entry::Input(Input::Tick),
+        ["size", width, height] => {
+            match (width.parse(), height.parse()) {
+                (Ok(width), Ok(height)) => Entry::Size((width, height)),
+                _ => return Err("size isn't two numbers".to_owned()),
+            }
+        }
+        ["frame"] => Entry::Frame,
+        _ => return Err(format!("can't read {:?}", line)),
+    };
+    Ok((at, entry))
+}
+
+// each entry with the millisecond it happened at
+pub fn load(path: &str) -> Result<Vec<(u64, Entry)>, String> {
+    let file = File::open(path).map_err(|e| format!("failed to open {}: {}", path, e))?;
+    let mut entries = vec![];
+    for (n, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| format!("failed to read {}: {}", path, e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        entries.push(decode(&line).map_err(|e| format!("{} line {}: {}", path, n + 1, e))?);
+    }
+    Ok(entries)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn unhex(text: &str) -> Result<Vec<u8>, String> {
+    let not_hex = || format!("not hex: {}", text);
+    text.as_bytes()
+        .chunks(2)
+        .map(|pair| match ::std::str::from_utf8(pair) {
+            Ok(pair) if pair.len() == 2 => u8::from_str_radix(pair, 16).map_err(|_| not_hex()),
+            _ => Err(not_hex()),
+        })
+        .collect()
+}
+
+#[test]
+fn test_entry_encoding() {
+    use termion::event::{Event, Key, MouseButton, MouseEvent};
+
+    let entries = vec![
+        Entry::Size((80, 24)),
+        Entry::Input(Input::Event(Event::Key(Key::Char(' ')))),
+        Entry::Input(Input::Event(Event::Key(Key::Ctrl('i')))),
+        Entry::Input(Input::Event(Event::Mouse(MouseEvent::Press(MouseButton::Left, 3, 9)))),
+        Entry::Input(Input::Paste("two\nlines é".to_owned())),
+        Entry::Input(Input::Paste(String::new())),
+        Entry::Frame,
+    ];
+    for entry in entries {
+        let line = format!("1500000000123 {}", encode(&entry));
+        assert_eq!(decode(&line), Ok((1500000000123, entry)));
+    }
+    assert_eq!(encode(&Entry::Input(Input::Event(Event::Key(Key::Char('x'))))), "event 78");
+    assert!(decode("soon frame").is_err());
+    assert!(decode("1 event 7").is_err());
+    assert_eq!(decode("1 dance"), Err("can't read \"1 dance\"".to_owned()));
+}
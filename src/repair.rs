@@ -0,0 +1,364 @@
+use std::cmp;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+
+use {Coords, Node, NodeID};
+
+// what was put right in a map as it was loaded, by kind
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Repairs {
+    pub missing_children: usize,
+    pub duplicate_ids: usize,
+    pub anchors_moved: usize,
+    pub recovered: usize,
+}
+
+impl Repairs {
+    pub fn is_empty(&self) -> bool {
+        *self == Repairs::default()
+    }
+}
+
+impl fmt::Display for Repairs {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let counts = [(self.missing_children, "child that didn't exist", "children that didn't exist"),
+                      (self.duplicate_ids, "duplicate id", "duplicate ids"),
+                      (self.anchors_moved, "anchor off the map", "anchors off the map"),
+                      (self.recovered, "unreachable node", "unreachable nodes")];
+        let found: Vec<String> = counts.iter()
+            .filter(|&&(n, _, _)| n > 0)
+            .map(|&(n, one, many)| format!("{} {}", n, if n == 1 { one } else { many }))
+            .collect();
+        write!(f, "repaired the map: {}", found.join(", "))
+    }
+}
+
+// the nodes of a map as they were saved, made whole again. older builds
+// could save children that didn't exist, give two nodes the same id, put
+// anchors at 0, and leave nodes that no anchor leads to, which would
+// otherwise be lost the next time the map was saved.
+pub fn repair(nodes: Vec<Node>,
+              arrows: &mut Vec<(NodeID, NodeID)>,
+              max_id: &mut NodeID)
+              -> (HashMap<NodeID, Node>, Repairs) {
+    let (mut nodes, duplicate_ids) = unique_ids(nodes, arrows, max_id);
+    let repairs = Repairs {
+        duplicate_ids,
+        missing_children: drop_missing_children(&mut nodes),
+        anchors_moved: clamp_anchors(&mut nodes),
+        recovered: recover_unreachable(&mut nodes, max_id),
+    };
+    (nodes, repairs)
+}
+
+// a node whose id was already taken gets a new one, unless it was made
+// first. it takes the place in its parent that the parent listed it in
+// after the node that kept the id, and the children that name it as their
+// parent and aren't also listed by the node that kept the id. arrows to the
+// id could have been made to any of them, so each new id gets its own copy
+// of them; bookmarks can only lead to one, and stay with the first.
+pub fn unique_ids(nodes: Vec<Node>,
+                  arrows: &mut Vec<(NodeID, NodeID)>,
+                  max_id: &mut NodeID)
+                  -> (HashMap<NodeID, Node>, usize) {
+    let mut copies: HashMap<NodeID, Vec<Node>> = HashMap::new();
+    let mut order = vec![];
+    for node in nodes {
+        if !copies.contains_key(&node.id) {
+            order.push(node.id);
+        }
+        copies.entry(node.id).or_default().push(node);
+    }
+    *max_id = cmp::max(*max_id, order.iter().cloned().max().unwrap_or(0));
+
+    let mut unique = HashMap::new();
+    let mut renumbered = vec![];
+    for id in order {
+        let mut copies = copies.remove(&id).unwrap();
+        copies.sort_by_key(|n| n.meta.ctime);
+        let mut copies = copies.into_iter();
+        let first = copies.next().unwrap();
+        for mut copy in copies {
+            *max_id += 1;
+            copy.id = *max_id;
+            renumbered.push((id, copy.id));
+            unique.insert(copy.id, copy);
+        }
+        unique.insert(id, first);
+    }
+
+    for &(old, new) in &renumbered {
+        let (parent_id, children) = {
+            let copy = &unique[&new];
+            (copy.parent_id, copy.children.clone())
+        };
+        let (kept_parent, kept_children) = {
+            let kept = &unique[&old];
+            (kept.parent_id, kept.children.clone())
+        };
+        if let Some(parent) = unique.get_mut(&parent_id) {
+            let skip = if kept_parent == parent_id { 1 } else { 0 };
+            if let Some(entry) = parent.children.iter_mut().filter(|c| **c == old).nth(skip) {
+                *entry = new;
+            }
+        }
+        let mut own = vec![];
+        for child_id in children {
+            if kept_children.contains(&child_id) {
+                continue;
+            }
+            if let Some(child) = unique.get_mut(&child_id) {
+                if child.parent_id == old {
+                    child.parent_id = new;
+                }
+            }
+            own.push(child_id);
+        }
+        unique.get_mut(&new).unwrap().children = own;
+
+        let twins: Vec<(NodeID, NodeID)> = arrows.iter()
+            .filter(|&&(from, to)| from == old || to == old)
+            .map(|&(from, to)| {
+                (if from == old { new } else { from }, if to == old { new } else { to })
+            })
+            .collect();
+        arrows.extend(twins);
+    }
+    (unique, renumbered.len())
+}
+
+// children that aren't in the map, or are the root
+pub fn drop_missing_children(nodes: &mut HashMap<NodeID, Node>) -> usize {
+    let ids: HashSet<NodeID> = nodes.keys().cloned().collect();
+    let mut dropped = 0;
+    for node in nodes.values_mut() {
+        let before = node.children.len();
+        node.children.retain(|c| *c != 0 && ids.contains(c));
+        dropped += before - node.children.len();
+    }
+    dropped
+}
+
+// anchors at row or column 0 can't be drawn or clicked. they're moved to 1,
+// and down past any anchor already there.
+pub fn clamp_anchors(nodes: &mut HashMap<NodeID, Node>) -> usize {
+    let anchors = nodes.get(&0).map(|root| root.children.clone()).unwrap_or_default();
+    let off = |coords: Coords| coords.0 < 1 || coords.1 < 1;
+    let (mut moved, mut taken): (Vec<NodeID>, HashSet<Coords>) = (vec![], HashSet::new());
+    for id in anchors {
+        match nodes.get(&id) {
+            Some(anchor) if off(anchor.rooted_coords) => moved.push(id),
+            Some(anchor) => {
+                taken.insert(anchor.rooted_coords);
+            }
+            None => {}
+        }
+    }
+    for &id in &moved {
+        let anchor = nodes.get_mut(&id).unwrap();
+        let (x, y) = anchor.rooted_coords;
+        let mut coords = (cmp::max(x, 1), cmp::max(y, 1));
+        while taken.contains(&coords) {
+            coords.1 += 1;
+        }
+        taken.insert(coords);
+        anchor.rooted_coords = coords;
+    }
+    moved.len()
+}
+
+// nodes no anchor leads to, whose parents are gone or never listed them,
+// are put under a new anchor below the others. the ones no other lost node
+// lists go there first, keeping what's under them, and then the lowest id
+// of any cycle that's left.
+pub fn recover_unreachable(nodes: &mut HashMap<NodeID, Node>, max_id: &mut NodeID) -> usize {
+    let reachable = reachable_from(nodes, 0);
+    let mut lost: BTreeSet<NodeID> =
+        nodes.keys().cloned().filter(|id| !reachable.contains(id)).collect();
+    if lost.is_empty() {
+        return 0;
+    }
+    let recovered = lost.len();
+
+    let anchors = nodes.get(&0).map(|root| root.children.clone()).unwrap_or_default();
+    let lowest = anchors.iter()
+        .filter_map(|id| nodes.get(id))
+        .map(|anchor| anchor.rooted_coords.1)
+        .max()
+        .unwrap_or(1);
+    *max_id += 1;
+    let anchor_id = *max_id;
+    nodes.insert(anchor_id,
+                 Node {
+                     id: anchor_id,
+                     parent_id: 0,
+                     content: "Recovered".to_owned(),
+                     rooted_coords: (1, lowest + 1),
+                     ..Node::default()
+                 });
+    if let Some(root) = nodes.get_mut(&0) {
+        root.children.push(anchor_id);
+    }
+
+    while !lost.is_empty() {
+        let listed: HashSet<NodeID> =
+            lost.iter().flat_map(|id| nodes[id].children.clone()).collect();
+        let top = *lost.iter().find(|id| !listed.contains(id)).unwrap_or_else(|| {
+            lost.iter().next().unwrap()
+        });
+        for id in &lost {
+            nodes.get_mut(id).unwrap().children.retain(|&c| c != top);
+        }
+        nodes.get_mut(&top).unwrap().parent_id = anchor_id;
+        nodes.get_mut(&anchor_id).unwrap().children.push(top);
+
+        let mut to_visit = vec![top];
+        while let Some(id) = to_visit.pop() {
+            lost.remove(&id);
+            let children = nodes[&id].children.clone();
+            for child_id in children {
+                if lost.contains(&child_id) {
+                    nodes.get_mut(&child_id).unwrap().parent_id = id;
+                    to_visit.push(child_id);
+                }
+            }
+        }
+    }
+    recovered
+}
+
+fn reachable_from(nodes: &HashMap<NodeID, Node>, root: NodeID) -> HashSet<NodeID> {
+    let mut seen = HashSet::new();
+    let mut to_visit = vec![root];
+    while let Some(id) = to_visit.pop() {
+        if seen.insert(id) {
+            to_visit.extend(nodes.get(&id).map_or(&[][..], |n| &n.children[..]));
+        }
+    }
+    seen
+}
+
+#[cfg(test)]
+fn fixture(nodes: &[(NodeID, NodeID, &[NodeID])]) -> Vec<Node> {
+    nodes.iter()
+        .map(|&(id, parent_id, children)| {
+            Node {
+                id,
+                parent_id,
+                children: children.to_vec(),
+                content: format!("node {}", id),
+                rooted_coords: (1, 2 * id as u16),
+                ..Node::default()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+fn by_id(nodes: Vec<Node>) -> HashMap<NodeID, Node> {
+    nodes.into_iter().map(|n| (n.id, n)).collect()
+}
+
+#[test]
+fn test_drop_missing_children() {
+    let mut nodes = by_id(fixture(&[(0, 0, &[1, 7]), (1, 0, &[2, 8, 0]), (2, 1, &[])]));
+    assert_eq!(drop_missing_children(&mut nodes), 3);
+    assert_eq!(nodes[&0].children, vec![1]);
+    assert_eq!(nodes[&1].children, vec![2]);
+    assert_eq!(drop_missing_children(&mut nodes), 0);
+}
+
+#[test]
+fn test_unique_ids() {
+    // two nodes 2, the later one under 3 with a child of its own, and an
+    // arrow and bookmark to the id
+    let mut nodes = fixture(&[(0, 0, &[1, 3]),
+                              (1, 0, &[2]),
+                              (2, 1, &[]),
+                              (3, 0, &[2]),
+                              (2, 3, &[4]),
+                              (4, 2, &[])]);
+    nodes[2].meta.ctime = 100;
+    nodes[4].meta.ctime = 200;
+    let mut arrows = vec![(1, 2), (4, 3)];
+    let mut max_id = 3;
+    let (nodes, renumbered) = unique_ids(nodes, &mut arrows, &mut max_id);
+    assert_eq!(renumbered, 1);
+    assert_eq!(max_id, 5);
+    assert_eq!(nodes.len(), 6);
+    assert_eq!((nodes[&2].parent_id, nodes[&2].children.clone()), (1, vec![]));
+    assert_eq!((nodes[&5].parent_id, nodes[&5].children.clone()), (3, vec![4]));
+    assert_eq!(nodes[&1].children, vec![2]);
+    assert_eq!(nodes[&3].children, vec![5]);
+    assert_eq!(nodes[&4].parent_id, 5);
+    assert_eq!(arrows, vec![(1, 2), (4, 3), (1, 5)]);
+
+    // both under the same parent, listed twice
+    let nodes = fixture(&[(0, 0, &[1]), (1, 0, &[2, 2]), (2, 1, &[]), (2, 1, &[])]);
+    let (nodes, renumbered) = unique_ids(nodes, &mut vec![], &mut 2);
+    assert_eq!(renumbered, 1);
+    assert_eq!(nodes[&1].children, vec![2, 3]);
+    assert_eq!(nodes[&3].parent_id, 1);
+}
+
+#[test]
+fn test_clamp_anchors() {
+    let mut nodes = by_id(fixture(&[(0, 0, &[1, 2, 3]), (1, 0, &[]), (2, 0, &[]), (3, 0, &[])]));
+    nodes.get_mut(&1).unwrap().rooted_coords = (0, 0);
+    nodes.get_mut(&2).unwrap().rooted_coords = (1, 1);
+    nodes.get_mut(&3).unwrap().rooted_coords = (0, 1);
+    assert_eq!(clamp_anchors(&mut nodes), 2);
+    assert_eq!(nodes[&1].rooted_coords, (1, 2));
+    assert_eq!(nodes[&2].rooted_coords, (1, 1));
+    assert_eq!(nodes[&3].rooted_coords, (1, 3));
+    assert_eq!(clamp_anchors(&mut nodes), 0);
+}
+
+#[test]
+fn test_recover_unreachable() {
+    // 2 lost its parent, keeping 3 under it, and 4 and 5 are each other's
+    // parent
+    let mut nodes = by_id(fixture(&[(0, 0, &[1]),
+                                    (1, 0, &[]),
+                                    (2, 9, &[3]),
+                                    (3, 2, &[]),
+                                    (4, 5, &[5]),
+                                    (5, 4, &[4])]));
+    let mut max_id = 5;
+    assert_eq!(recover_unreachable(&mut nodes, &mut max_id), 4);
+    assert_eq!(max_id, 6);
+    assert_eq!(nodes[&0].children, vec![1, 6]);
+    let recovered = &nodes[&6];
+    assert_eq!((recovered.content.as_str(), recovered.parent_id), ("Recovered", 0));
+    assert_eq!(recovered.rooted_coords, (1, 3));
+    assert_eq!(recovered.children, vec![2, 4]);
+    assert_eq!((nodes[&2].parent_id, nodes[&3].parent_id), (6, 2));
+    assert_eq!((nodes[&4].parent_id, nodes[&4].children.clone()), (6, vec![5]));
+    assert_eq!((nodes[&5].parent_id, nodes[&5].children.clone()), (4, vec![]));
+    assert_eq!(recover_unreachable(&mut nodes, &mut max_id), 0);
+}
+
+#[test]
+fn test_repair() {
+    let whole = fixture(&[(0, 0, &[1]), (1, 0, &[2]), (2, 1, &[])]);
+    let (nodes, repairs) = repair(whole.clone(), &mut vec![], &mut 2);
+    assert!(repairs.is_empty());
+    for node in whole {
+        assert_eq!(format!("{:?}", nodes[&node.id]), format!("{:?}", node));
+    }
+
+    let mut broken = fixture(&[(0, 0, &[1, 9]), (1, 0, &[]), (1, 0, &[]), (2, 3, &[])]);
+    broken[1].rooted_coords = (0, 0);
+    let (nodes, repairs) = repair(broken, &mut vec![], &mut 2);
+    assert_eq!(repairs,
+               Repairs {
+                   missing_children: 1,
+                   duplicate_ids: 1,
+                   anchors_moved: 1,
+                   recovered: 2,
+               });
+    assert_eq!(repairs.to_string(),
+               "repaired the map: 1 child that didn't exist, 1 duplicate id, 1 anchor off the \
+                map, 2 unreachable nodes");
+    assert_eq!(nodes.len(), 5);
+}
@@ -1,17 +1,22 @@
 use std;
 use std::cmp::{max, min};
+use std::cell::RefCell;
 use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet};
 use std::env;
 use std::fmt::Write as FmtWrite;
-use std::fs::{File, OpenOptions, remove_file, rename};
-use std::io::{self, Error, ErrorKind, Read, Seek, SeekFrom, Stdout, Write, stdin, stdout};
+use std::fs::{self, File, OpenOptions, remove_file, rename};
+use std::io::{Error, ErrorKind, Read, Stdout, Write, stdin, stdout};
+use std::path::Path;
+use std::panic;
 use std::process;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
 
-use termion::{clear, color, cursor, style, terminal_size};
-use termion::event::{Event, Key};
+use termion::{clear, color, cursor, style};
+use termion::event::{Event, Key, MouseButton, MouseEvent};
 use termion::input::{MouseTerminal, TermRead};
 use termion::raw::{IntoRawMode, RawTerminal};
-use termion::screen::AlternateScreen;
+use termion::screen::{ToAlternateScreen, ToMainScreen};
 
 use libc::getpid;
 use rand::{self, Rng};
@@ -19,15 +24,152 @@ use regex::Regex;
 use time;
 use unicode_segmentation::UnicodeSegmentation;
 
-use {Action, Config, Coords, Dir, Node, NodeID, Pack, TagDB, cost, dateparse, distances, logging,
-     plot, random_fg_color, re_matches, serialization};
+use {Action, Capabilities, CommandNotifier, Config, Coords, Dir, Glyphs, Node, NodeID, Notifier,
+     OutlineStyle, Pack, Pomodoro, PomodoroTransition, Rendering, Session, SortBy, TagDB, Trash,
+     TrashEntry, clipboard, cost, dateparse, distances, estimate, export, format_duration,
+     humanize_age, clock, input, logging, plot, random_fg_color, layout, minimap, re_matches,
+     serialization, session, sort, task, url};
+use command::{self, Command, ExportFormat, Setting};
+use config::{Mode, Progress};
+use input::Input;
+use frame::{self, Grid};
+use menu::Menu;
+use detail::{self, Detail};
+use complete::Completion;
+use quickadd::QuickAdd;
+use startup::Recent;
+use pane::{Layout, Side};
+use perf::Perf;
+use record::{Entry, Recorder};
+use modal::{Modal, Step};
+use numbering::number_tree;
+use termsize::{SizeSource, TerminalSize};
+use hooks::{Hook, run_hook};
+use substitute::Substitution;
+use error;
+use crypto;
+use merge::{self, Tombstones};
+use sync::Peer;
+use repair::Repairs;
+
+// days of completions shown in the status bar
+const ACTIVITY_DAYS: usize = 14;
+
+// nodes listed in the banner of what came due while void was closed
+const MISSED_SHOWN: usize = 10;
+
+// command lines remembered for Up and Down in the command prompt
+const COMMAND_HISTORY: usize = 100;
+
+// the cells kept free for an anchor placed without a mouse, with a margin
+// around them so that it doesn't touch its neighbours
+const ANCHOR_ROOM: Coords = (12, 1);
+const ANCHOR_MARGIN: u16 = 1;
+
+// the widest a parent's progress bar is drawn, when there's room
+const PROGRESS_WIDTH: usize = 8;
+
+// how wide the map may be drawn when it's printed rather than shown
+const CANVAS_WIDTH: u16 = 1000;
+// and how far down a node may be put. ones past either edge, which clicks
+// reported far off the screen used to make, are brought back on load.
+const CANVAS_HEIGHT: u16 = 10000;
+
+// how far past the edge of the terminal a mouse event is moved onto it,
+// as one that was just made smaller can still report a few cells beyond.
+// anything further out is dropped.
+const MOUSE_SLACK: u16 = 16;
+
+// the longest gap between the clicks of a double click
+const DOUBLE_CLICK_MS: u64 = 400;
+
+// a split leaving either pane narrower than this is refused, as there'd
+// be no room to lay anchors out side by side
+const MIN_PANE_WIDTH: u16 = 20;
+
+// how long `:archive` leaves finished subtrees alone by default
+const ARCHIVE_AFTER_DAYS: u64 = 30;
+
+// how many of the nodes left alone longest the stale report lists
+const STALE_COUNT: usize = 20;
+
+// styles for the text of completed nodes, with dimming for terminals that
+// can't strike through
+const STRIKE: &str = "\x1b[9m";
+const NO_STRIKE: &str = "\x1b[29m";
+const DIM: &str = "\x1b[2m";
+const NO_DIM: &str = "\x1b[22m";
+
+// where a pane looks and what it has selected. the focused pane's are the
+// screen's own fields, and the other one's wait here.
+#[derive(Default)]
+struct Pane {
+    view_y: u16,
+    selected: Option<NodeID>,
+    lookup: HashMap<Coords, NodeID>,
+    drawn_at: HashMap<NodeID, Coords>,
+    folds: HashMap<NodeID, (Coords, u16)>,
+    placements: HashMap<NodeID, Placement>,
+    placed_rows: HashMap<u16, Vec<(u16, u16)>>,
+    selected_fold: Option<NodeID>,
+    lowest_drawn: u16,
+}
+
+// something drawn on the map that can be selected: a node, or the fold line
+// under a parent hiding completed children, which stands in for them but
+// isn't a node itself
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Row {
+    Node(NodeID),
+    Fold(NodeID),
+}
+
+// where a node goes, worked out for the whole map before anything is drawn
+// so that what's out of view can be skipped. its first row is in drawn_at.
+#[derive(Debug, Clone, Copy)]
+struct Placement {
+    // the rows its own content takes
+    rows: u16,
+    // the rows it and everything shown below it take, fold line included
+    height: u16,
+    // how wide its first row is, as drawn last time or else near enough
+    width: u16,
+}
+
+// where letting go of a drag would put the selected node
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Drop {
+    // under another node
+    Parent(NodeID),
+    // its whole tree, by moving the anchor to these coords
+    Shift(NodeID, Coords),
+    // out on its own, as an anchor at these coords
+    Place(Coords),
+}
+
+struct Split {
+    layout: Layout,
+    focus: Side,
+    // the whole terminal, of which dims is the focused pane's part
+    dims: Coords,
+    other: Pane,
+}
 
 pub struct Screen {
     pub max_id: u64,
     pub nodes: HashMap<NodeID, Node>,
     pub arrows: Vec<(NodeID, NodeID)>,
     pub work_path: Option<String>,
+    // the work files opened last, offered by `:open`
+    pub recent: Recent,
+    // set when the work file is saved encrypted
+    pub key: Option<crypto::Key>,
     pub config: Config,
+    pub rendering: Rendering,
+    pub capabilities: Capabilities,
+
+    // deleting a subtree with more descendants than this asks first
+    pub delete_confirm_threshold: usize,
 
     // screen dimensions as detected during the current draw() cycle
     pub dims: Coords,
@@ -36,25 +178,88 @@ pub struct Screen {
     // non-pub members are ephemeral
     drawing_root: NodeID,
     show_logs: bool,
+    show_heatmap: bool,
+    // node and word counts for the selected subtree in the status bar
+    show_counts: bool,
+    // how long ago each node was made, or finished, after its text
+    show_ages: bool,
+    show_minimap: bool,
+    // rulers, hit-test edges and what the last click landed on
+    show_debug: bool,
+    // screen coords of the last mouse event and the node under them
+    last_mouse: Option<(Coords, Option<NodeID>)>,
     selected: Option<NodeID>,
+    // the selected node, once something's been typed into it. only then is
+    // quick-add syntax taken out of it when it's unselected.
+    typed_in: Option<NodeID>,
     cut: Option<NodeID>,
     drawing_arrow: Option<NodeID>,
     lookup: HashMap<Coords, NodeID>,
     drawn_at: HashMap<NodeID, Coords>,
+    // the fold lines drawn, by the parent each stands for, with where it
+    // starts and how wide it is. they're laid out and hit like nodes but
+    // kept out of lookup, which only holds real ones.
+    folds: HashMap<NodeID, (Coords, u16)>,
+    // every node shown, whether or not it's in view. lookup only has what
+    // was drawn, so clicks find nothing that isn't on the screen.
+    placements: HashMap<NodeID, Placement>,
+    // where each row's nodes start and about how wide they are, for telling
+    // what's taken out of view, where lookup has nothing
+    placed_rows: HashMap<u16, Vec<(u16, u16)>>,
+    // the nodes formatted and printed in the last frame, which leaves out
+    // everything out of view
+    formatted: Vec<NodeID>,
+    // the parent whose fold line is selected, instead of a node
+    selected_fold: Option<NodeID>,
+    // parents whose completed children are shown for now, dimmed, after
+    // enter on their fold line
+    revealed: HashSet<NodeID>,
     dragging_from: Option<Coords>,
     dragging_to: Option<Coords>,
-    stdout: Option<MouseTerminal<RawTerminal<AlternateScreen<Stdout>>>>,
+    // the terminal has reported the mouse moving with a button down, so a
+    // drag can show where it would drop
+    mouse_motion: bool,
+    // a right button drag from a node, which draws an arrow to wherever
+    // it's released, and where it was last held
+    arrow_drag: Option<(NodeID, Option<Coords>)>,
+    stdout: Option<MouseTerminal<RawTerminal<Stdout>>>,
+    // the terminal's alternate screen is showing. it stays up through
+    // prompts and help, and is left for $EDITOR and on the way out, so the
+    // shell's screen comes back as it was.
+    alternate: bool,
+    // what the terminal shows since the last draw, which the next one is
+    // compared against
+    last_frame: Option<Grid>,
     lowest_drawn: u16,
     // where we start drawing from
     view_y: u16,
     // when we drill down then pop up, we should go to last focus, stored here
     focus_stack: Vec<(NodeID, NodeID, u16)>,
+    // subtrees shown alone in focus mode, innermost last, with the view_y
+    // to restore when leaving each one
+    focus_mode: Vec<(NodeID, u16)>,
     last_search: Option<(String, NodeID)>,
+    // recently selected nodes, for jumping back and forth
+    history: Vec<NodeID>,
+    // our position in history while moving back and forth
+    history_cursor: usize,
+
+    // deleted subtrees, restorable with undo or from the trash browser
+    pub trash: Trash,
+    // finished subtrees moved out of the way. they're kept in the work file
+    // but not drawn, searched or exported
+    pub archive: Vec<TrashEntry>,
 
-    // undo info
-    undo_stack: Vec<NodeID>,
-    // needs to be separate, as recursive deletion of nodes causes ordering issues
-    undo_nodes: HashMap<NodeID, Node>,
+    // numbered slots for quickly jumping to frequently visited nodes
+    pub bookmarks: HashMap<u8, NodeID>,
+
+    // the group each grouped anchor is in. dragging one of a group's anchors
+    // moves the rest of it too
+    pub groups: HashMap<NodeID, String>,
+
+    // deleted nodes, so that merging with a copy that still has them
+    // doesn't bring them back
+    pub tombstones: Tombstones,
 
     // nodes created specifically for rendering an augmented view
     ephemeral_nodes: HashMap<NodeID, Node>,
@@ -66,11 +271,138 @@ pub struct Screen {
 
     pub tag_db: TagDB,
 
-    // timer for double clicks
-    last_click_ms: u64,
+    // when a node or blank space was last clicked, for spotting double clicks
+    last_click: Option<(u64, NodeID)>,
+    last_blank_click: Option<(u64, Coords)>,
 
     // grapheme calculation is expensive
     grapheme_cache: HashMap<NodeID, usize>,
+    // the outline number drawn before each node, when numbering is on
+    numbers: HashMap<NodeID, String>,
+    // how much is done of what's under each parent under the drawing root,
+    // out of how much, counted once a frame when progress bars are on
+    progress: HashMap<NodeID, (usize, usize)>,
+
+    // set when nodes or arrows change, cleared on save
+    dirty: bool,
+
+    // a question awaiting its answer, which gets every event until then
+    modal: Option<Modal>,
+    // a second pane beside this one on the same map
+    split: Option<Split>,
+    // everything the last search found, while waiting for what to do with
+    // it
+    matches: Option<Vec<NodeID>>,
+    // the children of the selected node picked out to be acted on together,
+    // until the next key that isn't one of those actions. empty otherwise.
+    selection: Vec<NodeID>,
+
+    pomodoro: Option<Pomodoro>,
+
+    // invert the terminal for one frame when a pomodoro phase ends
+    flash: bool,
+
+    pub notifier: Box<Notifier>,
+    pub size_source: Box<SizeSource>,
+    // what size_source said last, kept for when it fails
+    last_size: Option<Coords>,
+
+    // command that links in nodes are opened with
+    pub url_opener: String,
+
+    // modification time and length of the work file when we last read or
+    // wrote it, to notice other programs changing it
+    work_file_stamp: Option<(SystemTime, u64)>,
+
+    // the work file changed on disk while we had unsaved changes
+    conflict: bool,
+
+    // another void that changes are swapped with every so often
+    pub sync: Option<Peer>,
+
+    // where input goes, when it's being kept for replaying later
+    pub recorder: Option<Recorder>,
+
+    // the node whose links are being cycled through, and the last one opened
+    last_url: Option<(NodeID, usize)>,
+
+    // overdue nodes announced in the banner until dismissed
+    notices: Vec<String>,
+    // nodes that came due while void wasn't running, listed at startup
+    // until dismissed
+    missed: Vec<String>,
+
+    // cursor into the queue view of open leaves, while it's shown
+    queue: Option<usize>,
+    // column and row of the card under the cursor on the board, while
+    // it's shown
+    board: Option<(usize, usize)>,
+    // cursor into the agenda of due nodes, while it's shown
+    agenda: Option<usize>,
+    // cursor into the report of nodes left untouched longest, while it's
+    // shown
+    stale: Option<usize>,
+
+    // completions per day for the status bar sparkline, and the minute
+    // they were counted in
+    activity: Vec<u64>,
+    activity_minute: u64,
+
+    // the highlighted place on the location map, while it's shown
+    gps_view: Option<usize>,
+
+    // the right click menu, which gets every event while it's open
+    menu: Option<Menu>,
+
+    // the form for the selected node's meta, which also takes every event
+    detail: Option<Detail>,
+
+    // tags that could finish the one being typed. it only takes the keys
+    // for picking one, and typing goes on as usual.
+    completion: Option<Completion>,
+
+    // latest random picks, avoided when re-rolling
+    recent_picks: Vec<NodeID>,
+    pick_tag: Option<String>,
+
+    // the other open files in tab order, with this one missing at `tab`
+    tabs: Vec<Screen>,
+    tab: usize,
+
+    // earlier command lines, oldest first
+    command_history: Vec<String>,
+    // shown in place of the status bar until the next key
+    command_error: Option<String>,
+    // set by `:q!` to leave without saving
+    discard_changes: bool,
+
+    // the register being recorded into, and the events so far
+    recording: Option<(char, Vec<Event>)>,
+    macros: HashMap<char, Vec<Event>>,
+    // set while a macro plays, which keeps prompts from waiting on the
+    // terminal and macros from setting off more macros
+    replaying: bool,
+
+    // only looked at when the config asks for modal editing
+    mode: Mode,
+    // set by the first half of a normal mode dd
+    delete_armed: bool,
+
+    // timings shown by `:perf`. a cell, as arrows are routed while
+    // drawing through a shared borrow.
+    perf: RefCell<Perf>,
+    // the log pane as it was last formatted
+    log_pane: LogPane,
+}
+
+// the separator and lines of the log pane, kept for as long as the
+// terminal's size, the fill glyph and the logs themselves stay the same
+#[derive(Default)]
+struct LogPane {
+    key: Option<(Coords, char, usize)>,
+    text: String,
+    // how many times it had to be formatted
+    renders: usize,
 }
 
 impl Default for Screen {
@@ -79,33 +411,110 @@ impl Default for Screen {
         root.content = "home".to_owned();
         let mut screen = Screen {
             config: Config::default(),
+            delete_confirm_threshold: 5,
             arrows: vec![],
             selected: None,
+            typed_in: None,
             cut: None,
             drawing_arrow: None,
             nodes: HashMap::new(),
             lookup: HashMap::new(),
             drawn_at: HashMap::new(),
+            folds: HashMap::new(),
+            placements: HashMap::new(),
+            placed_rows: HashMap::new(),
+            formatted: vec![],
+            selected_fold: None,
+            revealed: HashSet::new(),
             show_logs: false,
+            show_heatmap: false,
+            show_counts: false,
+            show_ages: false,
+            show_minimap: true,
+            show_debug: false,
+            last_mouse: None,
             drawing_root: 0,
             stdout: None,
+            alternate: false,
+            last_frame: None,
             dragging_from: None,
             dragging_to: None,
+            mouse_motion: false,
+            arrow_drag: None,
             work_path: None,
+            recent: Recent::default(),
+            key: None,
+            rendering: Rendering::default(),
+            capabilities: Capabilities::default(),
             max_id: 0,
             dims: (1, 1),
             lowest_drawn: 0,
             view_y: 0,
             focus_stack: vec![],
+            focus_mode: vec![],
             is_test: false,
             last_search: None,
-            undo_stack: vec![],
-            undo_nodes: HashMap::new(),
+            history: vec![],
+            history_cursor: 0,
+            trash: Trash::default(),
+            archive: vec![],
+            bookmarks: HashMap::new(),
+            groups: HashMap::new(),
+            tombstones: Tombstones::new(),
             ephemeral_nodes: HashMap::new(),
             ephemeral_max_id: std::u64::MAX,
             tag_db: TagDB::default(),
-            last_click_ms: 0,
+            last_click: None,
+            last_blank_click: None,
             grapheme_cache: HashMap::new(),
+            numbers: HashMap::new(),
+            progress: HashMap::new(),
+            dirty: false,
+            modal: None,
+            split: None,
+            recorder: None,
+            perf: RefCell::new(Perf::default()),
+            log_pane: LogPane::default(),
+            matches: None,
+            selection: vec![],
+            pomodoro: None,
+            flash: false,
+            notifier: Box::new(CommandNotifier::default()),
+            size_source: Box::new(TerminalSize),
+            last_size: None,
+            url_opener: if cfg!(target_os = "macos") {
+                "open".to_owned()
+            } else {
+                "xdg-open".to_owned()
+            },
+            last_url: None,
+            work_file_stamp: None,
+            conflict: false,
+            sync: None,
+            notices: vec![],
+            missed: vec![],
+            queue: None,
+            board: None,
+            agenda: None,
+            stale: None,
+            activity: vec![],
+            activity_minute: 0,
+            gps_view: None,
+            menu: None,
+            detail: None,
+            completion: None,
+            recent_picks: vec![],
+            pick_tag: None,
+            tabs: vec![],
+            tab: 0,
+            command_history: vec![],
+            command_error: None,
+            discard_changes: false,
+            recording: None,
+            macros: HashMap::new(),
+            replaying: false,
+            mode: Mode::Normal,
+            delete_armed: false,
         };
         screen.nodes.insert(0, root);
         screen
@@ -113,16 +522,18 @@ impl Default for Screen {
 }
 
 impl Screen {
-    fn help(&mut self) {
+    fn help(&mut self) -> error::Result<()> {
         self.cleanup();
-        print!("{}{}{}\n", cursor::Goto(1, 1), clear::All, self.config);
-        self.start_raw_mode();
+        out!("{}{}{}\n", cursor::Goto(1, 1), clear::All, self.config);
+        self.start_raw_mode()?;
         if let Err(_) = self.single_key_prompt("") {
             // likely here because of testing
         }
+        Ok(())
     }
 
-    fn new_node_id(&mut self) -> NodeID {
+    // every node gets its id here, so that none is ever handed out twice
+    pub fn next_id(&mut self) -> NodeID {
         self.max_id += 1;
         assert!(self.max_id < self.ephemeral_max_id);
         self.max_id
@@ -136,9 +547,10 @@ impl Screen {
 
     fn new_node(&mut self) -> NodeID {
         let mut node = Node::default();
-        let id = self.new_node_id();
+        let id = self.next_id();
         node.id = id;
         self.nodes.insert(id, node);
+        self.dirty = true;
         id
     }
 
@@ -151,7 +563,9 @@ impl Screen {
     fn with_node_mut<B, F>(&mut self, k: NodeID, mut f: F) -> Option<B>
         where F: FnMut(&mut Node) -> B
     {
+        let dirty = &mut self.dirty;
         self.nodes.get_mut(&k).map(|mut node| {
+            *dirty = true;
             node.meta.bump_mtime();
             f(&mut node)
         })
@@ -160,75 +574,350 @@ impl Screen {
     fn with_node_mut_no_meta<B, F>(&mut self, k: NodeID, mut f: F) -> Option<B>
         where F: FnMut(&mut Node) -> B
     {
-        self.nodes.get_mut(&k).map(|mut node| f(&mut node))
+        let dirty = &mut self.dirty;
+        self.nodes.get_mut(&k).map(|mut node| {
+            *dirty = true;
+            f(&mut node)
+        })
+    }
+
+    // selection is not persisted, so it does not dirty the screen
+    fn set_selected(&mut self, k: NodeID, selected: bool) -> Option<()> {
+        self.nodes.get_mut(&k).map(|node| node.selected = selected)
     }
 
     // return of false signals to the caller that we are done in this view
+    pub fn handle_input(&mut self, input: Input) -> bool {
+        // everything an input does happens at the moment it's recorded at,
+        // which is also the moment it's replayed at
+        let at = clock::millis();
+        clock::held_at(at, || {
+            if input != Input::Tick {
+                self.record(Entry::Input(input.clone()));
+            }
+            self.take_input(input)
+        })
+    }
+
+    fn take_input(&mut self, input: Input) -> bool {
+        match input {
+            Input::Event(evt) => self.handle_event(evt),
+            // a question on the bottom line isn't answered by pasting
+            Input::Paste(_) if self.modal.is_some() => true,
+            Input::Paste(text) => {
+                self.completion = None;
+                self.paste(&text);
+                true
+            }
+            Input::Tick => {
+                self.tick();
+                true
+            }
+        }
+    }
+
+    // whatever goes wrong handling it is logged, and void carries on
     pub fn handle_event(&mut self, evt: Event) -> bool {
-        match self.config.map(evt) {
-            Some(e) => {
-                match e {
-                    Action::LeftClick(x, y) => {
-                        let internal_coords = self.screen_to_internal_xy((x, y));
-                        self.click_screen(internal_coords)
-                    }
-                    Action::RightClick(_, _) => {
-                        self.pop_focus();
-                    }
-                    Action::Release(x, y) => {
-                        let internal_coords = self.screen_to_internal_xy((x, y));
-                        self.release(internal_coords)
-                    }
-                    Action::Char(c) => {
-                        if self.selected.is_some() {
-                            self.append(c);
-                        } else {
-                            if c == '/' {
-                                self.search_forward();
-                            } else if c == '?' {
-                                self.search_backward();
-                            } else {
-                                self.prefix_jump_to(c.to_string());
-                            }
-                        }
+        let keep_going = match self.dispatch_event(evt) {
+            Ok(keep_going) => keep_going,
+            Err(e) => {
+                error!("{}", e);
+                true
+            }
+        };
+        if cfg!(debug_assertions) {
+            if let Err(e) = self.check_invariants() {
+                error!("broken invariant: {}", e);
+            }
+        }
+        keep_going
+    }
+
+    fn dispatch_event(&mut self, evt: Event) -> error::Result<bool> {
+        self.command_error = None;
+        if let Some((_, ref mut events)) = self.recording {
+            if !self.replaying {
+                events.push(evt.clone());
+            }
+        }
+        let evt = match self.clamp_mouse(evt) {
+            Some(evt) => evt,
+            None => return Ok(true),
+        };
+        if let Some(modal) = self.modal.take() {
+            // the question stays up until it's answered or escaped
+            match modal.step(&evt) {
+                Step::Ignore => self.modal = Some(modal),
+                Step::Cancel => info!("cancelled"),
+                Step::Answer(key) => self.answer(modal, key)?,
+            }
+            return Ok(true);
+        }
+        let evt = match self.route_to_pane(evt) {
+            Some(evt) => evt,
+            None => return Ok(true),
+        };
+        if self.conflict {
+            match self.config.map_in(evt.clone(), self.mode()) {
+                Some(action @ Action::LoadTheirs) | Some(action @ Action::KeepMine) => {
+                    return self.perform(action);
+                }
+                _ => {}
+            }
+        }
+        if self.queue.is_some() {
+            return Ok(self.handle_queue_event(evt));
+        }
+        if self.board.is_some() {
+            return Ok(self.handle_board_event(evt));
+        }
+        if self.agenda.is_some() {
+            return Ok(self.handle_agenda_event(evt));
+        }
+        if self.stale.is_some() {
+            return Ok(self.handle_stale_event(evt));
+        }
+        if self.matches.is_some() {
+            return Ok(self.handle_matches_event(evt));
+        }
+        if !self.selection.is_empty() && self.handle_selection_event(&evt) {
+            return Ok(true);
+        }
+        if self.gps_view.is_some() {
+            return Ok(self.handle_gps_event(evt));
+        }
+        if self.menu.is_some() {
+            return self.handle_menu_event(evt);
+        }
+        if self.detail.is_some() {
+            return Ok(self.handle_detail_event(evt));
+        }
+        if self.completion.is_some() && self.handle_completion_event(&evt) {
+            return Ok(true);
+        }
+        if self.dragging_from.is_some() && evt == Event::Key(Key::Esc) {
+            // nothing moves until the release, so this leaves it where it was
+            self.dragging_from = None;
+            self.dragging_to = None;
+            info!("cancelled drag");
+            return Ok(true);
+        }
+        match self.config.map_in(evt, self.mode()) {
+            Some(action) => {
+                if action != Action::DeleteTwice {
+                    self.delete_armed = false;
+                }
+                self.perform(action)
+            }
+            None => {
+                warn!("received unknown input");
+                Ok(true)
+            }
+        }
+    }
+
+    fn mode(&self) -> Mode {
+        if self.config.modal {
+            self.mode
+        } else {
+            Mode::Insert
+        }
+    }
+
+    // carry out an action from a key binding or the command line. a false
+    // return means we're done in this view
+    fn perform(&mut self, action: Action) -> error::Result<bool> {
+        match action {
+            Action::LeftClick(x, y) |
+            Action::RightClick(x, y) |
+            Action::MiddleClick(x, y) |
+            Action::Release(x, y) => {
+                let internal_coords = self.screen_to_internal_xy((x, y));
+                self.last_mouse = Some(((x, y), self.lookup(internal_coords).cloned()));
+            }
+            _ => {}
+        }
+        match action {
+            Action::LeftClick(x, y) => {
+                // the minimap sits over the canvas, so it gets first look
+                if let Some(cell) = self.minimap_cell((x, y)) {
+                    let (_, y) = minimap::canvas_of(self.minimap_canvas(), cell);
+                    self.view_y = y.saturating_sub(self.dims.1 / 2);
+                    return Ok(true);
+                }
+                let internal_coords = self.screen_to_internal_xy((x, y));
+                self.click_screen(internal_coords)
+            }
+            Action::RightClick(x, y) => self.open_menu((x, y)),
+            Action::NewAnchor(x, y) => {
+                let internal_coords = self.screen_to_internal_xy((x, y));
+                self.unselect();
+                self.create_anchor(internal_coords);
+                self.mode = Mode::Insert;
+            }
+            Action::MiddleClick(x, y) => {
+                let internal_coords = self.screen_to_internal_xy((x, y));
+                if let Some(&node_id) = self.lookup(internal_coords) {
+                    self.toggle_stricken_node(node_id);
+                }
+            }
+            Action::Release(x, y) => {
+                let internal_coords = self.screen_to_internal_xy((x, y));
+                self.release(internal_coords)
+            }
+            Action::Drag(x, y) => {
+                self.mouse_motion = true;
+                if self.dragging_from.is_some() {
+                    self.dragging_to = Some(self.screen_to_internal_xy((x, y)));
+                }
+            }
+            Action::Char(c) => {
+                if self.selected.is_some() && self.mode() == Mode::Insert {
+                    self.append(c);
+                } else if c == '/' {
+                    self.search_forward();
+                } else if c == '?' {
+                    self.search_backward();
+                } else if c == ':' {
+                    return self.enter_cmd();
+                } else if self.selected.is_none() {
+                    self.prefix_jump_to(c.to_string());
+                }
+            }
+            Action::Help => self.help()?,
+            Action::UnselectRet => {
+                // a selected fold line is let go of like a node
+                if self.selected_fold.is_some() {
+                    self.selected_fold = None;
+                } else if self.unselect().is_none() {
+                    if self.focus_root().is_none() {
+                        return Ok(false);
                     }
-                    Action::Help => self.help(),
-                    Action::UnselectRet => return self.unselect().is_some(),
-                    Action::ScrollUp => self.scroll_up(),
-                    Action::ScrollDown => self.scroll_down(),
-                    Action::DeleteSelected => self.delete_selected(true),
-                    Action::SelectUp => self.select_up(),
-                    Action::SelectDown => self.select_down(),
-                    Action::SelectLeft => self.select_left(),
-                    Action::SelectRight => self.select_right(),
-                    Action::EraseChar => self.backspace(),
-                    Action::CreateSibling => self.create_sibling(),
-                    Action::CreateChild => self.create_child(),
-                    Action::CreateFreeNode => self.create_free_node(),
-                    Action::ExecSelected => self.exec_selected(),
-                    Action::DrillDown => self.drill_down(),
-                    Action::PopUp => self.pop_focus(),
-                    Action::PrefixJump => self.prefix_jump_prompt(),
-                    Action::ToggleCompleted => self.toggle_stricken(),
-                    Action::ToggleHideCompleted => self.toggle_hide_stricken(),
-                    Action::Arrow => self.add_or_remove_arrow(),
-                    Action::AutoArrange => self.toggle_auto_arrange(),
-                    Action::ToggleCollapsed => self.toggle_collapsed(),
-                    Action::Quit => return false,
-                    Action::Save => self.save(),
-                    Action::ToggleShowLogs => self.toggle_show_logs(),
-                    Action::EnterCmd => self.enter_cmd(),
-                    Action::FindTask => self.auto_task(),
-                    Action::YankPasteNode => self.cut_paste(),
-                    Action::RaiseSelected => self.raise_selected(),
-                    Action::LowerSelected => self.lower_selected(),
-                    Action::Search => self.search_forward(),
-                    Action::UndoDelete => self.undo_delete(),
-                }
-            }
-            None => warn!("received unknown input"),
+                    self.leave_focus_mode();
+                }
+            }
+            Action::ScrollUp => self.scroll_up(),
+            Action::ScrollDown => self.scroll_down(),
+            Action::DeleteSelected => self.delete_selected(true),
+            Action::SelectUp => self.select_up(),
+            Action::SelectDown => self.select_down(),
+            Action::SelectLeft => self.select_left(),
+            Action::SelectRight => self.select_right(),
+            Action::EraseChar => self.backspace(),
+            Action::InsertNewline => self.insert_newline(),
+            // enter is taken by a selected fold line
+            Action::CreateSibling if self.selected_fold.is_some() => self.toggle_fold(),
+            // new nodes are for typing into, like vim's o
+            Action::CreateSibling => {
+                self.create_sibling();
+                self.mode = Mode::Insert;
+            }
+            Action::InsertSibling => {
+                self.insert_sibling();
+                self.mode = Mode::Insert;
+            }
+            Action::CreateChild => {
+                self.create_child();
+                self.mode = Mode::Insert;
+            }
+            Action::CreateFreeNode => {
+                self.create_free_node();
+                self.mode = Mode::Insert;
+            }
+            Action::PlaceAnchor => {
+                self.place_anchor();
+                self.mode = Mode::Insert;
+            }
+            Action::ExecSelected => self.exec_selected()?,
+            Action::DrillDown => self.drill_down(),
+            Action::PopUp => self.pop_focus(),
+            Action::PrefixJump => self.prefix_jump_prompt(),
+            Action::ToggleCompleted => self.toggle_stricken(),
+            Action::ToggleHideCompleted => self.toggle_hide_stricken(),
+            Action::Arrow => self.add_or_remove_arrow(),
+            Action::AutoArrange => self.toggle_auto_arrange(),
+            Action::ToggleCollapsed => self.toggle_collapsed(),
+            Action::Recolor => self.recolor(),
+            Action::Quit => return Ok(false),
+            Action::Save => self.save()?,
+            Action::ToggleShowLogs => self.toggle_show_logs(),
+            Action::EnterCmd => return self.enter_cmd(),
+            Action::FindTask => self.auto_task(),
+            Action::YankPasteNode => self.cut_paste(),
+            Action::RaiseSelected => self.raise_selected(),
+            Action::LowerSelected => self.lower_selected(),
+            Action::Search => self.search_forward(),
+            Action::SearchMatches => self.enter_matches(),
+            Action::SelectChildren => self.select_children(),
+            Action::AddTag => {
+                if let Some(selected_id) = self.selected {
+                    self.prompt_tag(&[selected_id]);
+                }
+            }
+            Action::UndoDelete => self.undo_delete(),
+            Action::ShowTrash => self.show_trash(),
+            Action::ShowArchive => self.show_archive(),
+            Action::HistoryBack => self.history_back(),
+            Action::HistoryForward => self.history_forward(),
+            Action::SetBookmark => self.bookmark_prompt(),
+            Action::JumpToBookmark(slot) => self.jump_to_bookmark(slot),
+            Action::FocusMode => self.toggle_focus_mode(),
+            Action::ToggleTimer => self.toggle_timer(),
+            Action::Pomodoro => self.toggle_pomodoro(),
+            Action::CancelPomodoro => self.cancel_pomodoro(),
+            Action::DismissNotices => self.dismiss_notices(),
+            Action::ToggleQueue => self.queue = Some(0),
+            Action::ToggleBoard => self.board = Some((0, 0)),
+            Action::ToggleAgenda => self.agenda = Some(0),
+            Action::ToggleStale => self.stale = Some(0),
+            Action::PickNext => self.pick_next(),
+            Action::ToggleHeatmap => self.show_heatmap = !self.show_heatmap,
+            Action::ToggleCounts => self.show_counts = !self.show_counts,
+            Action::ToggleAges => {
+                self.show_ages = !self.show_ages;
+                self.grapheme_cache.clear();
+            }
+            Action::ToggleMinimap => self.show_minimap = !self.show_minimap,
+            Action::ToggleDebug => self.show_debug = !self.show_debug,
+            Action::SetTitle => self.title_prompt(),
+            Action::ToggleGroup => self.toggle_group(),
+            Action::ToggleIconified => self.toggle_iconified(),
+            Action::ToggleGpsMap => self.gps_view = Some(0),
+            Action::EditDetails => self.open_detail(),
+            Action::CopySubtree => self.copy_subtree(),
+            Action::OpenUrl => self.open_url(),
+            Action::AddAttachment => self.attachment_prompt(),
+            Action::OpenAttachment => self.open_attachment(),
+            Action::ExportIcal => self.export_prompt(ExportFormat::Ical)?,
+            Action::ExportHtml => self.export_prompt(ExportFormat::Html)?,
+            Action::NewTab => self.open_tab_prompt(),
+            Action::NextTab => self.cycle_tab(true),
+            Action::PrevTab => self.cycle_tab(false),
+            Action::RecordMacro => self.toggle_recording(),
+            Action::ReplayMacro => return Ok(self.replay_prompt()),
+            Action::InsertMode => self.mode = Mode::Insert,
+            Action::NormalMode => self.mode = Mode::Normal,
+            Action::Window => self.modal = Some(Modal::Window),
+            Action::LoadTheirs => {
+                if self.conflict {
+                    self.reload();
+                }
+            }
+            Action::KeepMine => {
+                if self.conflict {
+                    info!("keeping unsaved changes, saving will overwrite the file");
+                    self.conflict = false;
+                    self.work_file_stamp = self.read_work_file_stamp();
+                }
+            }
+            Action::DeleteTwice => {
+                if self.delete_armed {
+                    self.delete_selected(true);
+                }
+                self.delete_armed = !self.delete_armed;
+            }
         }
-        true
+        Ok(true)
     }
 
     fn exists(&self, node_id: NodeID) -> bool {
@@ -260,13 +949,17 @@ impl Screen {
             // 3. set parent_id pointer
             // 4. bust grapheme cache
             let old_parent = self.parent(node_id).unwrap();
-            self.with_node_mut_no_meta(old_parent, |op| op.children.retain(|c| c != &node_id))
+            self.with_node_mut(old_parent, |op| op.children.retain(|c| c != &node_id))
                 .unwrap();
-            self.with_node_mut_no_meta(parent_id, |np| np.children.push(node_id))
+            self.with_node_mut(parent_id, |np| np.children.push(node_id))
                 .unwrap();
-            self.with_node_mut_no_meta(node_id, |s| s.parent_id = parent_id)
+            self.with_node_mut(node_id, |s| s.parent_id = parent_id)
                 .unwrap();
             self.grapheme_cache.remove(&node_id);
+            // only anchors are grouped or iconified, and it's under another
+            // node now
+            self.groups.remove(&node_id);
+            self.with_node_mut_no_meta(node_id, |s| s.iconified = false);
         }
     }
 
@@ -359,14 +1052,15 @@ impl Screen {
         )
     }
 
-    fn single_key_prompt(&mut self, prompt: &str) -> io::Result<Key> {
+    fn single_key_prompt(&mut self, prompt: &str) -> error::Result<Key> {
         trace!("prompt({})", prompt);
-        if self.is_test {
-            return Err(Error::new(ErrorKind::Other, "can't prompt in test"));
+        if self.is_test || self.replaying {
+            return Err(Error::new(ErrorKind::Other, "can't prompt in test or macro").into());
         }
 
+        let _stdin = input::lock_stdin();
         let stdin: Box<Read> = Box::new(stdin());
-        print!(
+        out!(
             "{}{}{}{}",
             cursor::Goto(0, self.dims.1),
             style::Invert,
@@ -374,20 +1068,27 @@ impl Screen {
             prompt
         );
         self.flush();
-        let res = stdin.keys().nth(0).unwrap();
+        let res = stdin.keys()
+            .nth(0)
+            .unwrap_or_else(|| Err(Error::new(ErrorKind::UnexpectedEof, "stdin closed")));
         debug!("read prompt: {:?}", res);
-        print!("{}", style::Reset);
-        res
+        out!("{}", style::Reset);
+        Ok(res?)
     }
 
-    fn prompt(&mut self, prompt: &str) -> io::Result<Option<String>> {
+    fn prompt(&mut self, prompt: &str) -> error::Result<Option<String>> {
+        self.prompt_with(prompt, false)
+    }
+
+    fn prompt_with(&mut self, prompt: &str, secret: bool) -> error::Result<Option<String>> {
         trace!("prompt({})", prompt);
-        if self.is_test {
-            return Err(Error::new(ErrorKind::Other, "can't prompt in test"));
+        if self.is_test || self.replaying {
+            return Err(Error::new(ErrorKind::Other, "can't prompt in test or macro").into());
         }
 
+        let _stdin = input::lock_stdin();
         let mut stdin: Box<Read> = Box::new(stdin());
-        print!(
+        out!(
             "{}{}{}{}{}",
             style::Invert,
             cursor::Goto(0, self.dims.1),
@@ -396,18 +1097,301 @@ impl Screen {
             cursor::Show
         );
         self.cleanup();
+        if secret {
+            let res = stdin.read_passwd(&mut stdout());
+            self.start_raw_mode()?;
+            out!("{}", style::Reset);
+            return Ok(res?);
+        }
         let res = stdin.read_line();
-        self.start_raw_mode();
+        self.start_raw_mode()?;
         debug!("read prompt: {:?}", res);
-        print!("{}", style::Reset);
-        res
+        out!("{}", style::Reset);
+        Ok(res?)
     }
 
-    fn enter_cmd(&mut self) {
+    fn enter_cmd(&mut self) -> error::Result<bool> {
         trace!("enter_cmd()");
-        if let Ok(Some(cmd)) = self.prompt("cmd: ") {
-            debug!("received command {:?}", cmd);
+        let line = match self.command_prompt() {
+            Some(line) => line,
+            None => return Ok(true),
+        };
+        debug!("received command {:?}", line);
+        match command::parse(&line) {
+            Ok(cmd) => self.run_command(cmd),
+            Err(e) => {
+                self.command_error = Some(e);
+                Ok(true)
+            }
+        }
+    }
+
+    // a false return means we're done, like for perform()
+    fn run_command(&mut self, cmd: Command) -> error::Result<bool> {
+        trace!("run_command({:?})", cmd);
+        match cmd {
+            Command::Action(action) => return self.perform(action),
+            Command::Write(None) => self.save()?,
+            Command::Write(Some(ref path)) if self.work_path.is_none() => {
+                self.work_path = Some(command::expand_home(path));
+                self.save()?;
+            }
+            Command::Write(Some(path)) => {
+                self.write_file(&command::expand_home(&path), Screen::saved_bytes)?
+            }
+            Command::Quit { force } => {
+                self.discard_changes = force;
+                return Ok(false);
+            }
+            Command::Open(path) => self.open_tab(&command::expand_home(&path)),
+            Command::Merge(path) => self.merge_file(&command::expand_home(&path)),
+            Command::Export(format, path) => {
+                self.write_file(&command::expand_home(&path),
+                                |s| export::export_as(s, format, None).into_bytes())?;
+            }
+            Command::Set(Setting::HideStricken, on) => {
+                let root = self.drawing_root;
+                self.with_node_mut_no_meta(root, |n| n.hide_stricken = on);
+                // a completed anchor can't stay selected out of sight
+                if let Some(selected_id) = self.selected {
+                    if !self.is_shown(selected_id) {
+                        self.unselect();
+                    }
+                }
+                self.relayout();
+            }
+            Command::Set(Setting::AutoArrange, on) => {
+                let root = self.drawing_root;
+                self.with_node_mut_no_meta(root, |n| n.auto_arrange = on);
+            }
+            Command::Set(Setting::Logs, on) => self.show_logs = on,
+            Command::Set(Setting::Modal, on) => {
+                self.config.modal = on;
+                self.mode = Mode::Normal;
+            }
+            Command::Set(Setting::Numbering, on) => {
+                self.config.numbering = on;
+                self.refresh_numbers();
+                self.relayout();
+            }
+            Command::LogLevel(level) => {
+                logging::set_level(level);
+                info!("log level set to {}", level);
+            }
+            Command::Archive(days) => {
+                self.archive_stricken(days.unwrap_or(ARCHIVE_AFTER_DAYS));
+            }
+            Command::Highlight(tag, highlight) => {
+                self.config.set_highlight(&tag, highlight);
+                if self.config.key_file().is_none() {
+                    info!("highlights last until void exits unless KEYFILE is set");
+                } else if let Err(e) = self.config.save_highlights() {
+                    error!("failed to save highlights to the key file: {}", e);
+                }
+            }
+            Command::Stale => self.stale = Some(0),
+            Command::Substitute(sub) => self.substitute_prompt(sub),
+            Command::Perf => {
+                let summary = self.perf.borrow().summary();
+                if summary.is_empty() {
+                    info!("nothing has been timed yet");
+                }
+                for line in &summary {
+                    info!("{}", line);
+                }
+                self.show_logs = true;
+            }
+            Command::Sort { by, reverse, recursive } => {
+                match self.selected {
+                    Some(selected_id) => self.sort_children(selected_id, by, reverse, recursive),
+                    None => warn!("select a node to sort its children"),
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    fn sort_children(&mut self, node_id: NodeID, by: SortBy, reverse: bool, recursive: bool) {
+        let children = self.with_node(node_id, |n| n.children.clone()).unwrap_or_default();
+        let sorted: Vec<NodeID> = {
+            let mut nodes: Vec<&Node> =
+                children.iter().filter_map(|id| self.nodes.get(id)).collect();
+            sort::sort(&mut nodes, by, reverse);
+            nodes.into_iter().map(|n| n.id).collect()
+        };
+        self.with_node_mut(node_id, |n| n.children = sorted.clone());
+        if recursive {
+            for child_id in sorted {
+                self.sort_children(child_id, by, reverse, true);
+            }
+        }
+    }
+
+    // a line editor on the bottom row, with tab completion and Up/Down
+    // through earlier lines. None if it was abandoned.
+    fn command_prompt(&mut self) -> Option<String> {
+        trace!("command_prompt()");
+        if self.is_test || self.replaying {
+            return None;
+        }
+
+        let mut line = String::new();
+        // position in the history, which is its length for the new line
+        let mut cursor = self.command_history.len();
+        let mut draft = String::new();
+        let mut hint = String::new();
+        let _stdin = input::lock_stdin();
+        let stdin = stdin();
+        let mut keys = stdin.keys();
+        loop {
+            if !hint.is_empty() && self.dims.1 > 1 {
+                out!(
+                    "{}{}{}{}",
+                    cursor::Goto(1, self.dims.1 - 1),
+                    clear::CurrentLine,
+                    hint,
+                    style::Reset
+                );
+            }
+            out!(
+                "{}{}{}:{}{}",
+                cursor::Goto(1, self.dims.1),
+                style::Reset,
+                clear::CurrentLine,
+                line,
+                cursor::Show
+            );
+            self.flush();
+
+            let key = match keys.next() {
+                Some(Ok(key)) => key,
+                _ => return None,
+            };
+            hint.clear();
+            match key {
+                Key::Char('\n') => break,
+                Key::Esc | Key::Ctrl('c') => {
+                    out!("{}", cursor::Hide);
+                    return None;
+                }
+                // backing out of an empty line closes it, like vim
+                Key::Backspace if line.is_empty() => {
+                    out!("{}", cursor::Hide);
+                    return None;
+                }
+                Key::Backspace => {
+                    line.pop();
+                }
+                Key::Char('\t') => {
+                    let completions = command::complete(&line, &self.recent.files);
+                    if completions.len() == 1 {
+                        line = completions[0].clone();
+                        if !line.ends_with('/') {
+                            line.push(' ');
+                        }
+                    } else if !completions.is_empty() {
+                        line = command::common_prefix(&completions);
+                        let names: Vec<&str> =
+                            completions.iter().filter_map(|c| c.rsplit(' ').next()).collect();
+                        hint = names.join("  ");
+                    }
+                }
+                Key::Up if cursor > 0 => {
+                    if cursor == self.command_history.len() {
+                        draft = line.clone();
+                    }
+                    cursor -= 1;
+                    line = self.command_history[cursor].clone();
+                }
+                Key::Down if cursor < self.command_history.len() => {
+                    cursor += 1;
+                    line = self.command_history.get(cursor).cloned().unwrap_or_else(|| draft.clone());
+                }
+                Key::Char(c) => line.push(c),
+                _ => {}
+            }
+        }
+        out!("{}", cursor::Hide);
+
+        let line = line.trim().to_owned();
+        if line.is_empty() {
+            return None;
+        }
+        self.command_history.retain(|l| l != &line);
+        self.command_history.push(line.clone());
+        if self.command_history.len() > COMMAND_HISTORY {
+            self.command_history.remove(0);
+        }
+        Some(line)
+    }
+
+    // a line editor on the bottom row for a path, where Tab finishes the
+    // file or directory being typed. None if it was abandoned.
+    fn path_prompt(&mut self, prompt: &str) -> Option<String> {
+        trace!("path_prompt({})", prompt);
+        if self.is_test || self.replaying {
+            return None;
+        }
+
+        let mut line = String::new();
+        let mut hint = String::new();
+        let _stdin = input::lock_stdin();
+        let stdin = stdin();
+        let mut keys = stdin.keys();
+        loop {
+            if !hint.is_empty() && self.dims.1 > 1 {
+                out!(
+                    "{}{}{}{}",
+                    cursor::Goto(1, self.dims.1 - 1),
+                    clear::CurrentLine,
+                    hint,
+                    style::Reset
+                );
+            }
+            out!(
+                "{}{}{}{}{}{}",
+                cursor::Goto(1, self.dims.1),
+                style::Reset,
+                clear::CurrentLine,
+                prompt,
+                line,
+                cursor::Show
+            );
+            self.flush();
+
+            let key = match keys.next() {
+                Some(Ok(key)) => key,
+                _ => return None,
+            };
+            hint.clear();
+            match key {
+                Key::Char('\n') => break,
+                Key::Esc | Key::Ctrl('c') => {
+                    out!("{}", cursor::Hide);
+                    return None;
+                }
+                Key::Backspace => {
+                    line.pop();
+                }
+                Key::Char('\t') => {
+                    let completions = command::complete_path(&command::expand_home(&line));
+                    if completions.len() == 1 {
+                        line = completions[0].clone();
+                    } else if !completions.is_empty() {
+                        line = command::common_prefix(&completions);
+                        let dir = line.rfind('/').map_or(0, |idx| idx + 1);
+                        let names: Vec<&str> = completions.iter().map(|c| &c[dir..]).collect();
+                        hint = names.join("  ");
+                    }
+                }
+                Key::Char(c) => line.push(c),
+                _ => {}
+            }
         }
+        out!("{}", cursor::Hide);
+
+        let line = line.trim().to_owned();
+        if line.is_empty() { None } else { Some(line) }
     }
 
     fn search_forward(&mut self) {
@@ -441,12 +1425,10 @@ impl Screen {
                 self.last_search.take();
             }
 
-            let mut f = |n: &Node| n.content.find(&*query).map(|idx| (idx, n.id));
-            let mut candidates = self.recursive_child_filter_map(self.drawing_root, &mut f);
+            let candidates = self.search_matches(&query);
             if candidates.is_empty() {
                 return;
             }
-            candidates.sort();
             let choice = if let Some((_, last_choice)) = self.last_search.take() {
                 let idx = candidates
                     .iter()
@@ -466,6 +1448,183 @@ impl Screen {
         }
     }
 
+    // nodes at or below the view with query in their content or title, in
+    // the order search goes through them. collapsed subtrees and completed
+    // nodes that are hidden count too.
+    fn search_matches(&self, query: &str) -> Vec<(usize, NodeID)> {
+        // titles are searched too, after the content of the same node
+        let mut f = |n: &Node| {
+            n.content
+                .find(query)
+                .or_else(|| n.title.as_ref().and_then(|t| t.find(query)))
+                .map(|idx| (idx, n.id))
+        };
+        let root = self.focus_root().unwrap_or(self.drawing_root);
+        let mut candidates = self.recursive_child_filter_map(root, &mut f);
+        candidates.sort();
+        candidates
+    }
+
+    fn enter_matches(&mut self) {
+        let query = match self.last_search {
+            Some((ref query, _)) => query.clone(),
+            None => {
+                info!("search for something first");
+                return;
+            }
+        };
+        let matches: Vec<NodeID> =
+            self.search_matches(&query).into_iter().map(|(_, id)| id).collect();
+        if matches.is_empty() {
+            info!("nothing matches {}", query);
+        } else {
+            self.matches = Some(matches);
+        }
+    }
+
+    fn handle_matches_event(&mut self, evt: Event) -> bool {
+        let matches = self.matches.take().unwrap_or_default();
+        match self.handle_bulk_event(matches, evt, |matches, _| Modal::BulkDelete(matches)) {
+            None | Some((_, Some(Action::SearchMatches))) => {}
+            Some((_, Some(Action::Quit))) => return false,
+            Some((matches, _)) => self.matches = Some(matches),
+        }
+        true
+    }
+
+    fn select_children(&mut self) {
+        let children = match self.selected {
+            Some(selected_id) => self.with_node(selected_id, |n| n.children.clone()).unwrap(),
+            None => return,
+        };
+        if children.is_empty() {
+            info!("nothing under it to select");
+        }
+        self.selection = children;
+    }
+
+    // whether evt was one of the things done to every selected child. any
+    // other event leaves the selection with only the parent selected again,
+    // and is handled as usual.
+    fn handle_selection_event(&mut self, evt: &Event) -> bool {
+        let selection = std::mem::take(&mut self.selection);
+        self.handle_bulk_event(selection, evt.clone(), Modal::DeleteSelection).is_none()
+    }
+
+    // does what evt is bound to with every one of ids, when it's something
+    // that can be: striking, tagging, or deleting once ask_delete's question
+    // is answered. ids are handed back with the action otherwise, for the
+    // caller to decide what becomes of them.
+    fn handle_bulk_event(&mut self,
+                         ids: Vec<NodeID>,
+                         evt: Event,
+                         ask_delete: fn(Vec<NodeID>, usize) -> Modal)
+                         -> Option<(Vec<NodeID>, Option<Action>)> {
+        let action = self.config.map_in(evt, self.mode());
+        match action {
+            Some(Action::ToggleCompleted) => {
+                let open: Vec<NodeID> = ids.into_iter()
+                    .filter(|&id| self.with_node(id, |n| !n.stricken) == Some(true))
+                    .collect();
+                let struck = self.perform_on_all(&open, Action::ToggleCompleted);
+                info!("struck {} nodes", struck);
+            }
+            Some(Action::AddTag) => self.prompt_tag(&ids),
+            Some(Action::DeleteSelected) | Some(Action::DeleteTwice) => {
+                let count = ids.iter().map(|&id| self.subtree_size(id)).sum();
+                self.modal = Some(ask_delete(ids, count));
+            }
+            Some(Action::UnselectRet) => {}
+            _ => return Some((ids, action)),
+        }
+        None
+    }
+
+    // the keys handle_bulk_event takes, as they're bound
+    fn bulk_keys(&self) -> String {
+        let mode = self.mode();
+        let keys = [(Action::ToggleCompleted, "strike"),
+                    (Action::AddTag, "tag"),
+                    (Action::DeleteSelected, "delete")];
+        keys.iter()
+            .filter_map(|&(action, what)| {
+                self.config.key_for(action, mode).map(|key| format!("{} {}", key, what))
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn prompt_tag(&mut self, ids: &[NodeID]) {
+        if let Ok(Some(tag)) = self.prompt("tag: ") {
+            let tag = tag.trim().trim_start_matches('#');
+            if !tag.is_empty() {
+                let tagged = self.tag_all(ids, tag);
+                info!("tagged {} nodes #{}", tagged, tag);
+            }
+        }
+    }
+
+    // does f with each node selected in turn, skipping any that are gone
+    // by then, like the children of one deleted before them. the selection
+    // and its history are put back afterwards. returns how many nodes f
+    // was done with.
+    fn apply_to_all<F>(&mut self, ids: &[NodeID], mut f: F) -> usize
+        where F: FnMut(&mut Screen)
+    {
+        let selected = self.selected;
+        let history = (self.history.clone(), self.history_cursor);
+        let mut count = 0;
+        for &node_id in ids {
+            if self.exists(node_id) {
+                self.select_node(node_id);
+                f(self);
+                count += 1;
+            }
+        }
+        match selected {
+            Some(node_id) if self.exists(node_id) => self.select_node(node_id),
+            _ => {
+                self.unselect();
+            }
+        }
+        self.history = history.0;
+        self.history_cursor = history.1;
+        count
+    }
+
+    fn perform_on_all(&mut self, ids: &[NodeID], action: Action) -> usize {
+        self.apply_to_all(ids, |s| {
+            if let Err(e) = s.perform(action) {
+                error!("{}", e);
+            }
+        })
+    }
+
+    // adds #tag to the end of each node that doesn't have it yet
+    fn tag_all(&mut self, ids: &[NodeID], tag: &str) -> usize {
+        let word = format!("#{}", tag);
+        let untagged: Vec<NodeID> = ids.iter()
+            .cloned()
+            .filter(|&id| {
+                self.with_node(id, |n| !n.content.split_whitespace().any(|w| w == word)) ==
+                Some(true)
+            })
+            .collect();
+        self.apply_to_all(&untagged, |s| {
+            let node_id = s.selected.unwrap();
+            let content = s.with_node_mut(node_id, |n| {
+                    if !n.content.is_empty() {
+                        n.content.push(' ');
+                    }
+                    n.content.push_str(&word);
+                    n.content.clone()
+                })
+                .unwrap();
+            s.tag_db.reindex(node_id, content);
+            s.grapheme_cache.remove(&node_id);
+        })
+    }
+
     fn prefix_jump_prompt(&mut self) {
         trace!("prefix_jump_prompt()");
 
@@ -497,13 +1656,13 @@ impl Screen {
             chars.split("").skip(1).zip(nodes.into_iter()).collect();
 
         // clear the prompt
-        print!("{}{}", cursor::Goto(1, self.dims.1), clear::AfterCursor);
+        out!("{}{}", cursor::Goto(1, self.dims.1), clear::AfterCursor);
 
         // print the hilighted char at each choice
         for (&c, &node_id) in &mapping {
             let &coords = self.drawn_at(node_id).unwrap();
             let (x, y) = self.internal_to_screen_xy(coords).unwrap();
-            print!("{}{}{}{}", cursor::Goto(x, y), style::Invert, c, style::Reset);
+            out!("{}{}{}{}", cursor::Goto(x, y), style::Invert, c, style::Reset);
         }
 
         // read the choice
@@ -525,34 +1684,34 @@ impl Screen {
         self.drawn_at
             .keys()
             .filter(|&node_id| self.node_is_visible(*node_id).unwrap())
+            .filter(|&node_id| self.is_shown(*node_id))
             .filter(|&node_id| filter(*node_id))
             .cloned()
             .collect()
     }
 
-    fn exec_selected(&mut self) {
+    fn exec_selected(&mut self) -> error::Result<()> {
         if self.is_test {
             // tests generate many randomly named nodes, so we don't
             // want to accidentally execute rm -rf /
-            return;
+            return Ok(());
         }
         if let Some(selected_id) = self.selected {
             let content_opt = self.with_node(selected_id, |n| n.content.clone());
             if content_opt.is_none() {
                 error!("tried to exec deleted node");
-                return;
+                return Ok(());
             }
             let content = content_opt.unwrap();
 
             info!("executing command: {}", content);
             if content.is_empty() {
                 error!("cannot execute empty command");
-                return;
+                return Ok(());
             }
 
             if content.starts_with("txt:") {
-                self.exec_text_editor(selected_id);
-                return;
+                return self.exec_text_editor(selected_id);
             }
 
             if content.starts_with("http") {
@@ -573,9 +1732,10 @@ impl Screen {
                 }
             }
         }
+        Ok(())
     }
 
-    fn exec_text_editor(&mut self, node_id: NodeID) {
+    fn exec_text_editor(&mut self, node_id: NodeID) -> error::Result<()> {
         let text = self.with_node(node_id, |n| n.free_text.clone())
             .unwrap()
             .unwrap_or("".to_owned());
@@ -590,47 +1750,31 @@ impl Screen {
         }
 
         // create new tmp file
-        let mut f = OpenOptions::new()
+        OpenOptions::new()
             .write(true)
             .create_new(true)
             .open(&path)
-            .unwrap();
-        f.write_all(text.as_bytes()).unwrap();
-        f.seek(SeekFrom::Start(0)).unwrap();
-
-        // have raw mode destructor run
-        self.cleanup();
-
-        // open text editor
-        let ed = env::var("EDITOR").unwrap_or("vim".to_owned());
-        process::Command::new(ed)
-            .arg(&path)
-            .spawn()
-            .expect("failed to open text editor")
-            .wait()
-            .unwrap();
-
-        // read new data
-        let mut data = vec![];
-        {
-            let mut f = File::open(&path).unwrap();
-            f.read_to_end(&mut data).unwrap();
-            // f closed as it slides out of scope
-        }
-        let new_text = String::from_utf8(data).unwrap();
+            .and_then(|mut f| f.write_all(text.as_bytes()))
+            .map_err(error::Error::file(&path))?;
 
-        remove_file(&path).unwrap();
+        // open text editor, and whatever it did, restore raw mode
+        let edited = self.suspend(|| edit_file(&path))?;
+        let _ = remove_file(&path);
+        let new_text = edited?;
 
         // set node's saved text
         self.with_node_mut(node_id, |n| n.free_text = Some(new_text.clone()))
             .unwrap();
-
-        // restore raw mode
-        self.start_raw_mode();
+        Ok(())
     }
 
     pub fn arrange(&mut self) {
         trace!("arrange");
+        if self.focus_root().is_some() {
+            // the focused subtree is always drawn at the top left
+            return;
+        }
+        self.refresh_numbers();
         let mut real_estate = Pack {
             children: None,
             top: 2, // leave room for header
@@ -649,16 +1793,44 @@ impl Screen {
         node_dims.sort_by_key(|&(_, (_, y))| y);
         node_dims.reverse();
 
+        // trees too wide for the screen go below all the others, one under
+        // the next, rather than staying where another one may be put now
+        let mut placed = vec![];
+        let mut unplaced = vec![];
+        let mut below = 2;
         for (node_id, dims) in node_dims {
             // add some spacing around this tree to space out
             // placement a little bit
             let padded_dims = (dims.0 + 2, dims.1 + 2);
-            if let Some((x, y)) = real_estate.insert(padded_dims) {
-                self.with_node_mut_no_meta(node_id, |n| n.rooted_coords = (x, y))
-                    .unwrap();
+            match real_estate.insert(padded_dims) {
+                Some(coords) => {
+                    below = max(below, coords.1.saturating_add(padded_dims.1));
+                    placed.push((node_id, coords));
+                }
+                None => unplaced.push((node_id, padded_dims.1)),
             }
         }
-    }
+        for (node_id, height) in unplaced {
+            placed.push((node_id, (1, below)));
+            below = below.saturating_add(height);
+        }
+        for (node_id, coords) in placed {
+            let moved = self.with_node(node_id, |n| n.rooted_coords != coords).unwrap();
+            if moved {
+                self.with_node_mut_no_meta(node_id, |n| n.rooted_coords = coords)
+                    .unwrap();
+            }
+        }
+    }
+
+    // node_id and everything below it, collapsed or not
+    pub fn subtree_size(&self, node_id: NodeID) -> usize {
+        self.recursive_child_filter_map(node_id, &mut |_| Some(())).len()
+    }
+
+    pub fn subtree_word_count(&self, node_id: NodeID) -> usize {
+        self.recursive_child_filter_map(node_id, &mut |n| Some(n.word_count())).iter().sum()
+    }
 
     pub fn recursive_child_filter_map<F, B>(
         &self,
@@ -685,7 +1857,10 @@ impl Screen {
     }
 
     fn drawable_subtree_dims(&mut self, node_id: NodeID) -> Option<(u16, u16)> {
-        if let Some(widths) = self.drawable_subtree_widths(node_id, 0) {
+        if let Some(mut widths) = self.drawable_subtree_widths(node_id, 0) {
+            if let Some(title) = self.anchor_title(node_id) {
+                widths.push(UnicodeSegmentation::graphemes(&*title, true).count() as u16);
+            }
             let height = widths.len() as u16;
             let max_width = widths.into_iter().max().unwrap();
             Some((max_width, height))
@@ -698,19 +1873,27 @@ impl Screen {
         let raw_node_opt = self.with_node(node_id, |n| n.clone());
         if let Some(raw_node) = raw_node_opt {
             let node = self.format_node(&raw_node);
-            let width = 1 + (3 * depth as u16) + node.content.len() as u16;
-            let mut ret = vec![width];
-            let hide_stricken = self.with_node(node_id, |n| n.hide_stricken).unwrap();
-            if !node.collapsed {
-                for &child in &node.children {
-                    let stricken = self.with_node(child, |c| c.stricken).unwrap();
-                    if !(hide_stricken && stricken) {
-                        // ASSUMES node.children are all valid
-                        let mut child_widths = self.drawable_subtree_widths(child, depth + 1)
-                            .unwrap();
-                        ret.append(&mut child_widths);
-                    }
-                }
+            // the number goes before the first line only
+            let number = self.numbers.get(&node_id).map_or(0, |n| n.len() as u16 + 1);
+            let mut ret: Vec<u16> = layout::lines(&node.content)
+                .iter()
+                .enumerate()
+                .map(|(i, line)| {
+                    let number = if i == 0 { number } else { 0 };
+                    1 + (3 * depth as u16) + number + line.len() as u16
+                })
+                .collect();
+            for child in self.shown_children(node_id) {
+                // ASSUMES node.children are all valid
+                let mut child_widths = self.drawable_subtree_widths(child, depth + 1).unwrap();
+                ret.append(&mut child_widths);
+            }
+            let folded = self.folded(node_id);
+            if folded > 0 {
+                let revealed = self.fold_open(node_id);
+                let text = fold_text(folded, revealed, self.glyphs());
+                let width = UnicodeSegmentation::graphemes(&*text, true).count() as u16;
+                ret.push(3 * (depth as u16 + 1) + width);
             }
             Some(ret)
         } else {
@@ -721,20 +1904,24 @@ impl Screen {
     pub fn flush(&mut self) {
         trace!("flush()");
         if let Some(mut s) = self.stdout.take() {
-            s.flush().unwrap();
+            if let Err(e) = s.flush() {
+                error!("failed to write to the terminal: {}", e);
+            }
             self.stdout = Some(s);
         }
     }
 
     fn unselect(&mut self) -> Option<NodeID> {
         trace!("unselect()");
+        self.selected_fold = None;
         lazy_static! {
             static ref RE_DATE: Regex = Regex::new(r"\[(\S+)\]").unwrap();
+            static ref RE_EST: Regex = Regex::new(r"\s*\best:(\S+)").unwrap();
         }
         if let Some(selected_id) = self.selected {
             // nuke node if it's empty and has no children
-            let deletable = self.with_node_mut_no_meta(selected_id, |mut n| {
-                n.selected = false;
+            self.set_selected(selected_id, false);
+            let deletable = self.with_node(selected_id, |n| {
                 n.content.is_empty() && n.children.is_empty()
             }).unwrap_or(false);
             if deletable {
@@ -742,29 +1929,63 @@ impl Screen {
                 return None;
             }
 
-            self.with_node_mut_no_meta(selected_id, |mut n| {
-                // if parseable date, change date
-                if let Some(date) = re_matches::<String>(&RE_DATE, &*n.content).iter().nth(0) {
-                    if let Some(date) = dateparse(date.clone()) {
-                        n.content = RE_DATE.replace(&*n.content, "").trim_right().to_owned();
-                        if n.meta.finish_time.is_some() {
-                            n.meta.finish_time = Some(date);
-                        } else {
-                            let now_in_s = time::get_time().sec as u64;
-                            let future_date = now_in_s + (now_in_s - date);
-                            n.meta.due = Some(future_date);
+            // `fix bug #backend due:fri est:2h` leaves `fix bug`
+            if self.typed_in.take() == Some(selected_id) {
+                let now = clock::now();
+                let quick_add = self.with_node(selected_id, |n| QuickAdd::parse(&n.content, now))
+                    .filter(|quick_add| !quick_add.is_empty());
+                if let Some(quick_add) = quick_add {
+                    info!("took {} out of the node", quick_add.tokens());
+                    let text = self.with_node_mut_no_meta(selected_id, |n| {
+                            quick_add.apply(n);
+                            n.tag_text()
+                        })
+                        .unwrap();
+                    self.grapheme_cache.remove(&selected_id);
+                    self.tag_db.reindex(selected_id, text);
+                }
+            }
+
+            let has_date = self.with_node(selected_id, |n| RE_DATE.is_match(&*n.content))
+                .unwrap_or(false);
+            if has_date {
+                self.with_node_mut_no_meta(selected_id, |mut n| {
+                    // if parseable date, change date
+                    if let Some(date) = re_matches::<String>(&RE_DATE, &*n.content).iter().nth(0) {
+                        if let Some(date) = dateparse(date.clone()) {
+                            n.content = RE_DATE.replace(&*n.content, "").trim_right().to_owned();
+                            if n.meta.finish_time.is_some() {
+                                n.meta.finish_time = Some(date);
+                            } else {
+                                let now_in_s = clock::now();
+                                let future_date = now_in_s + (now_in_s - date);
+                                n.meta.due = Some(future_date);
+                            }
                         }
                     }
-                }
 
-            });
+                });
+            }
+
+            // est:2h becomes the node's estimate, and est:0m clears it
+            let estimate = self.with_node(selected_id, |n| {
+                    re_matches::<String>(&RE_EST, &*n.content).into_iter().next()
+                })
+                .and_then(|est| est)
+                .and_then(|est| estimate::parse(&est));
+            if let Some(secs) = estimate {
+                self.with_node_mut_no_meta(selected_id, |n| {
+                    n.content = RE_EST.replace(&*n.content, "").trim().to_owned();
+                    n.meta.estimate = if secs > 0 { Some(secs) } else { None };
+                });
+            }
         }
         self.selected.take()
     }
 
     fn internal_to_screen_xy(&self, coords: Coords) -> Option<Coords> {
-        // + 2 compensates for header
-        if coords.1 < self.view_y + 2 || coords.1 > self.view_y + self.dims.1 {
+        // + 2 compensates for header, and the bottom row is the status bar
+        if coords.1 < self.view_y + 2 || coords.1 >= self.view_y + self.dims.1 {
             // coords are above or below screen
             None
         } else {
@@ -777,7 +1998,7 @@ impl Screen {
     }
 
     fn coords_are_visible(&self, (_, y): Coords) -> bool {
-        visible(self.view_y + 1, self.dims.1, y)
+        visible(self.view_y + 1, self.dims.1.saturating_sub(1), y)
     }
 
     fn node_is_visible(&self, node: NodeID) -> Option<bool> {
@@ -788,17 +2009,168 @@ impl Screen {
         }
     }
 
+    // whether parent_id leaves child_id out of the drawing: all of its
+    // children when it's collapsed, and the completed ones when it hides
+    // them, unless they've been revealed from its fold line. the drawing
+    // root's hide_stricken is :set hidestricken, which hides completed
+    // anchors, and it draws its anchors even if collapsed. an iconified
+    // anchor hides everything under it.
+    fn hides_child(&self, parent_id: NodeID, child_id: NodeID) -> bool {
+        let parent = match self.nodes.get(&parent_id) {
+            Some(parent) => parent,
+            None => return false,
+        };
+        let stricken = self.with_node(child_id, |c| c.stricken).unwrap_or(false);
+        (parent.collapsed && parent_id != self.drawing_root) || self.iconifies(parent_id) ||
+            (stricken && parent.hide_stricken && !self.fold_open(parent_id))
+    }
+
+    // how many completed children the fold line under node_id stands for,
+    // hidden or revealed. there's none under the drawing root, whose
+    // anchors have nothing to hang it from, or under a collapsed node,
+    // which hides everything anyway, or under an iconified anchor.
+    fn folded(&self, node_id: NodeID) -> usize {
+        let node = match self.nodes.get(&node_id) {
+            Some(node) => node,
+            None => return 0,
+        };
+        if !node.hide_stricken || node.collapsed || node_id == self.drawing_root ||
+           self.iconifies(node_id) {
+            return 0;
+        }
+        node.children
+            .iter()
+            .filter(|&&c| self.with_node(c, |c| c.stricken) == Some(true))
+            .count()
+    }
+
+    // whether node_id is drawn as its first line alone. the flag only
+    // counts on an anchor, and focus mode shows the whole tree anyway.
+    fn iconifies(&self, node_id: NodeID) -> bool {
+        match self.nodes.get(&node_id) {
+            Some(node) => {
+                node.iconified && node.parent_id == self.drawing_root &&
+                self.focus_root().is_none()
+            }
+            None => false,
+        }
+    }
+
+    // where arrows to node_id are drawn to: the iconified anchor it's
+    // hidden in, if it is, or else the node itself
+    fn arrow_end(&self, node_id: NodeID) -> NodeID {
+        let mut ptr = node_id;
+        while ptr != 0 && ptr != self.drawing_root {
+            match self.parent(ptr) {
+                Some(parent_id) if self.iconifies(parent_id) => return parent_id,
+                Some(parent_id) => ptr = parent_id,
+                None => break,
+            }
+        }
+        node_id
+    }
+
+    // whether enter was pressed on the fold line under parent_id to show
+    // what's behind it. the drawing root has none, even if it had one
+    // before being drilled into.
+    fn fold_open(&self, parent_id: NodeID) -> bool {
+        parent_id != self.drawing_root && self.revealed.contains(&parent_id)
+    }
+
+    // a completed child shown, dimmed, only because its fold was opened
+    fn is_revealed(&self, node_id: NodeID) -> bool {
+        match self.nodes.get(&node_id) {
+            Some(node) => node.stricken && self.fold_open(node.parent_id),
+            None => false,
+        }
+    }
+
+    // the parent whose fold line is drawn over coords
+    fn fold_under(&self, (x, y): Coords) -> Option<NodeID> {
+        self.folds
+            .iter()
+            .find(|&(_, &((fx, fy), width))| fy == y && x >= fx && x < fx + width)
+            .map(|(&parent_id, _)| parent_id)
+    }
+
+    fn select_fold(&mut self, parent_id: NodeID) {
+        trace!("select_fold({})", parent_id);
+        self.unselect();
+        self.selected_fold = Some(parent_id);
+    }
+
+    // enter on a fold line shows the completed children behind it, and
+    // again hides them
+    fn toggle_fold(&mut self) {
+        let parent_id = match self.selected_fold {
+            Some(parent_id) => parent_id,
+            None => return,
+        };
+        if self.folded(parent_id) == 0 {
+            // what it stood for was since finished with
+            self.selected_fold = None;
+            return;
+        }
+        if !self.revealed.remove(&parent_id) {
+            self.revealed.insert(parent_id);
+        }
+        self.relayout();
+    }
+
+    // the ancestor keeping node_id out of sight, if it's hidden at all. this
+    // is what drawing, layout, clicks and navigation all go by.
+    fn hidden_under(&self, node_id: NodeID) -> Option<NodeID> {
+        let mut ptr = node_id;
+        while ptr != 0 && ptr != self.drawing_root {
+            let parent_id = self.parent(ptr)?;
+            if self.hides_child(parent_id, ptr) {
+                return Some(parent_id);
+            }
+            ptr = parent_id;
+        }
+        None
+    }
+
+    fn is_shown(&self, node_id: NodeID) -> bool {
+        self.hidden_under(node_id).is_none()
+    }
+
+    // the children that are drawn under node_id, in order
+    fn shown_children(&self, node_id: NodeID) -> Vec<NodeID> {
+        self.with_node(node_id, |n| n.children.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|&child| !self.hides_child(node_id, child))
+            .collect()
+    }
+
+    // lays the map out again without showing it, so that clicks and
+    // navigation before the next draw don't find nodes that were just hidden
+    fn relayout(&mut self) {
+        frame::capture(|| {
+            self.forget_drawn();
+            self.draw_children_of_root();
+        });
+    }
+
     fn try_select(&mut self, coords: Coords) -> Option<NodeID> {
+        let start = Instant::now();
+        let selected = self.select_at(coords);
+        self.perf.borrow_mut().record("hit test", start.elapsed());
+        selected
+    }
+
+    fn select_at(&mut self, coords: Coords) -> Option<NodeID> {
         trace!("try_select({:?})", coords);
         if self.dragging_from.is_none() {
             self.unselect();
-            if let Some(&node_id) = self.lookup(coords) {
-                return self.with_node_mut_no_meta(node_id, |mut node| {
+            if let Some(node_id) = self.lookup(coords).cloned().filter(|&id| self.is_shown(id)) {
+                return self.set_selected(node_id, true).map(|_| {
                     trace!("selected node {} at {:?}", node_id, coords);
-                    node.selected = true;
                     node_id
                 }).and_then(|id| {
                         self.selected = Some(node_id);
+                        self.record_selection(node_id);
                         self.dragging_from = Some(coords);
                         self.dragging_to = Some(coords);
                         Some(id)
@@ -818,1430 +2190,5283 @@ impl Screen {
     fn toggle_stricken(&mut self) {
         trace!("toggle_stricken()");
         if let Some(selected_id) = self.selected {
-            self.with_node_mut(selected_id, |node| node.toggle_stricken());
+            self.toggle_stricken_node(selected_id);
         }
     }
 
-    fn toggle_hide_stricken(&mut self) {
-        trace!("toggle_hide_stricken()");
+    fn toggle_stricken_node(&mut self, node_id: NodeID) {
+        let stricken = self.with_node_mut(node_id, |node| {
+            node.toggle_stricken();
+            node.stricken
+        });
+        // its parent may count hidden completed children
+        if let Some(parent_id) = self.parent(node_id) {
+            self.grapheme_cache.remove(&parent_id);
+            // and if it hides them, the selection goes up to where it's seen
+            let hides = self.with_node(parent_id, |p| p.hide_stricken).unwrap_or(false);
+            let hidden = hides && !self.fold_open(parent_id);
+            if stricken == Some(true) && hidden && self.selected == Some(node_id) {
+                if parent_id == self.drawing_root {
+                    self.unselect();
+                } else {
+                    self.select_node(parent_id);
+                }
+            }
+            // its fold line counts them either way
+            if hides {
+                self.relayout();
+            }
+        }
+    }
+
+    fn toggle_timer(&mut self) {
+        trace!("toggle_timer()");
         if let Some(selected_id) = self.selected {
-            self.with_node_mut(selected_id, |node| node.toggle_hide_stricken());
+            let now = clock::now();
+            let running = self.with_node(selected_id, |n| n.meta.timer_running())
+                .unwrap_or(false);
+            // only one timer runs at a time
+            for node_id in self.running_timers() {
+                self.with_node_mut_no_meta(node_id, |n| n.meta.stop_timer(now));
+                self.grapheme_cache.remove(&node_id);
+            }
+            if !running {
+                self.with_node_mut_no_meta(selected_id, |n| n.meta.start_timer(now));
+            }
         }
     }
 
-    fn delete_recursive(&mut self, node_id: NodeID) {
-        trace!("delete_recursive({})", node_id);
-        if let Some(node) = self.nodes.remove(&node_id) {
-            // clean up any arrow state
-            self.arrows.retain(|&(ref from, ref to)| {
-                from != &node_id && to != &node_id
-            });
+    fn toggle_pomodoro(&mut self) {
+        trace!("toggle_pomodoro()");
+        let now = clock::now();
+        if let Some(ref mut pomodoro) = self.pomodoro {
+            if pomodoro.is_paused() {
+                pomodoro.resume(now);
+            } else {
+                pomodoro.pause(now);
+            }
+            return;
+        }
+        if let Some(selected_id) = self.selected {
+            self.pomodoro = Some(Pomodoro::start(selected_id, now));
+        }
+    }
 
-            // remove from tag_db
-            self.tag_db.remove(node_id);
+    fn cancel_pomodoro(&mut self) {
+        trace!("cancel_pomodoro()");
+        self.pomodoro = None;
+    }
 
-            for child_id in &node.children {
-                self.delete_recursive(*child_id);
-            }
+    // called at least once a second from the event loop
+    pub fn tick(&mut self) {
+        let now = clock::now();
+        self.tick_at(now);
+    }
 
-            self.undo_nodes.insert(node_id, node);
-        }
+    fn tick_at(&mut self, now: u64) {
+        self.tick_pomodoro(now);
+        self.notify_due(now);
+        self.check_work_file();
+        self.sync_with_peer(now);
     }
 
-    fn delete_selected(&mut self, reselect: bool) {
-        trace!("delete_selected()");
-        if let Some(selected_id) = self.selected.take() {
-            let (_, height) = self.drawable_subtree_dims(selected_id).unwrap();
-            let coords = self.drawn_at.remove(&selected_id);
-            // remove ref from parent
-            if let Some(parent_id) = self.parent(selected_id) {
-                trace!("deleting node {} from parent {}", selected_id, parent_id);
-                self.with_node_mut_no_meta(parent_id, |p| p.children.retain(|c| c != &selected_id))
-                    .unwrap();
+    // take in the map sent back by the last swap with the peer, and start
+    // the next one if it's time
+    fn sync_with_peer(&mut self, now: u64) {
+        let (addr, finished) = match self.sync {
+            Some(ref mut peer) => (peer.addr.clone(), peer.finished()),
+            None => return,
+        };
+        match finished {
+            Some((sent, Ok(data))) => {
+                let received = data.len();
+                match serialization::deserialize_screen(data) {
+                    Ok(theirs) => {
+                        self.merge(&theirs);
+                        if let Some(ref mut peer) = self.sync {
+                            peer.last_success = Some(now);
+                        }
+                        info!("synced with {}: sent {} bytes, got {}", addr, sent, received);
+                    }
+                    Err(e) => error!("sync with {} sent back garbage: {:?}", addr, e),
+                }
             }
-            // remove children
-            self.delete_recursive(selected_id);
-            if let Some((x, y)) = coords {
-                if reselect {
-                    self.click_select((x, y + height));
+            Some((_, Err(e))) => {
+                let last = self.sync.as_ref().and_then(|peer| peer.last_success);
+                match last {
+                    Some(at) => {
+                        error!("sync with {} failed: {} (last synced {} ago)",
+                               addr,
+                               e,
+                               format_duration(now.saturating_sub(at)))
+                    }
+                    None => error!("sync with {} failed: {}", addr, e),
                 }
             }
-            self.undo_stack.push(selected_id);
+            None => {}
         }
-    }
 
-    fn undo_delete(&mut self) {
-        if let Some(node_id) = self.undo_stack.pop() {
-            self.recursive_restore(node_id).unwrap();
-            self.select_node(node_id);
+        if self.sync.as_ref().map(|peer| peer.is_due(now)) == Some(true) {
+            // maps go over the wire in the clear
+            if self.key.is_some() {
+                error!("not syncing with {}: the map is encrypted", addr);
+                self.sync = None;
+                return;
+            }
+            let data = serialization::serialize_screen(self);
+            if let Some(ref mut peer) = self.sync {
+                peer.start(now, data);
+            }
         }
     }
 
-    fn recursive_restore(&mut self, node_id: NodeID) -> Result<(), ()> {
-        if let Some(node) = self.undo_nodes.remove(&node_id) {
-            self.with_node_mut_no_meta(node.parent_id, |p| if !p.children.contains(&node.id) {
-                p.children.push(node.id);
-            }).unwrap();
-            let children = node.children.clone();
-            self.nodes.insert(node_id, node);
-            for &child in &children {
-                self.recursive_restore(child)?;
+    fn toggle_recording(&mut self) {
+        trace!("toggle_recording()");
+        if let Some((_, ref mut events)) = self.recording {
+            // leave out the key that stopped it
+            events.pop();
+        }
+        if self.recording.is_some() {
+            self.stop_recording();
+        } else if let Ok(Key::Char(register)) = self.single_key_prompt("record into register: ") {
+            if register.is_alphabetic() {
+                self.start_recording(register);
             }
-            Ok(())
-        } else {
-            Err(())
         }
     }
 
-    pub fn should_auto_arrange(&self) -> bool {
-        self.with_node(self.drawing_root, |n| n.auto_arrange)
-            .unwrap()
+    pub fn start_recording(&mut self, register: char) {
+        self.recording = Some((register, vec![]));
     }
 
-    fn toggle_auto_arrange(&mut self) {
-        let root = self.drawing_root;
-        self.with_node_mut_no_meta(root, |mut n| n.auto_arrange = !n.auto_arrange)
-            .unwrap()
+    pub fn stop_recording(&mut self) {
+        if let Some((register, events)) = self.recording.take() {
+            info!("recorded {} events into register {}", events.len(), register);
+            self.macros.insert(register, events);
+        }
     }
 
-    pub fn run(&mut self) {
-        self.start_raw_mode();
-        self.dims = terminal_size().unwrap();
-        self.draw();
-        let stdin = stdin();
-        for c in stdin.events() {
-            let evt = c.unwrap();
-
-            self.dims = terminal_size().unwrap();
-
-            let should_break = !self.handle_event(evt);
+    // a count and then the register, like vim's 5@a
+    fn replay_prompt(&mut self) -> bool {
+        let mut count = String::new();
+        loop {
+            match self.single_key_prompt(&format!("replay register: {}", count)) {
+                Ok(Key::Char(c)) if c.is_ascii_digit() => count.push(c),
+                Ok(Key::Char(register)) => {
+                    return self.replay_macro(register, count.parse().unwrap_or(1));
+                }
+                _ => return true,
+            }
+        }
+    }
 
-            self.draw();
+    // a false return means the replayed events ended the session
+    pub fn replay_macro(&mut self, register: char, count: usize) -> bool {
+        self.replay_macro_with(register, count, |_, _| {})
+    }
 
-            if self.should_auto_arrange() {
-                self.arrange();
+    // replays like replay_macro, handing each event to after once it's been
+    // handled, so that every step of a replay can be looked at
+    pub fn replay_macro_with<F>(&mut self, register: char, count: usize, mut after: F) -> bool
+        where F: FnMut(&Screen, &Event)
+    {
+        trace!("replay_macro({}, {})", register, count);
+        if self.replaying {
+            return true;
+        }
+        let events = match self.macros.get(&register) {
+            Some(events) => events.clone(),
+            None => {
+                warn!("nothing recorded in register {}", register);
+                return true;
+            }
+        };
+        self.replaying = true;
+        let mut keep_going = true;
+        'replay: for _ in 0..count {
+            for evt in &events {
+                let handled = self.handle_event(evt.clone());
+                after(self, evt);
+                if !handled {
+                    keep_going = false;
+                    break 'replay;
+                }
+                // movement depends on where the last event left things
                 self.draw();
             }
+        }
+        self.replaying = false;
+        keep_going
+    }
+
+    fn open_tab_prompt(&mut self) {
+        if let Ok(Some(path)) = self.prompt("open in new tab: ") {
+            if !path.is_empty() {
+                self.open_tab(&path);
+            }
+        }
+    }
 
-            // if selected not visible, try to make it visible
-            self.scroll_to_selected();
+    pub fn open_tab(&mut self, path: &str) {
+        let is_open = |s: &Screen| s.work_path.as_ref().map(String::as_str) == Some(path);
+        if is_open(self) || self.tabs.iter().any(is_open) {
+            warn!("{} is already open", path);
+            return;
+        }
 
-            if should_break {
-                self.cleanup();
-                self.save();
-                break;
+        run_hook(&self.config, Hook::Open, path);
+        let mut data = vec![];
+        let read = File::open(path).and_then(|mut f| f.read_to_end(&mut data));
+        let mut screen = match read {
+            Err(ref e) if e.kind() == ErrorKind::NotFound => Screen::default(),
+            Err(e) => {
+                error!("failed to open {}: {}", path, e);
+                return;
+            }
+            Ok(_) if data.is_empty() => Screen::default(),
+            Ok(_) => {
+                match self.decode_map(data, true) {
+                    Ok(screen) => screen,
+                    Err(e) => {
+                        error!("failed to open {}: {}", path, e);
+                        return;
+                    }
+                }
             }
+        };
+        screen.work_path = Some(path.to_owned());
+        screen.restore_session();
+        self.recent.add(path);
+
+        // new tabs go right after the active one
+        let idx = self.tab;
+        self.tabs.insert(idx, screen);
+        self.switch_tab(idx + 1);
+        info!("opened {} in a new tab", path);
+    }
+
+    fn cycle_tab(&mut self, forward: bool) {
+        let count = self.tabs.len() + 1;
+        let to = if forward {
+            (self.tab + 1) % count
+        } else {
+            (self.tab + count - 1) % count
+        };
+        self.switch_tab(to);
+    }
+
+    // splits the screen into two panes on the same map, the new one
+    // starting out scrolled where this one is
+    pub fn split(&mut self, layout: Layout) {
+        if self.split.is_some() {
+            return;
         }
-        trace!("leaving stdin.events() loop");
-        print!("{}{}", cursor::Goto(1, 1), clear::All);
+        let narrowest = min(layout.pane(Side::Left, self.dims).1 .0,
+                            layout.pane(Side::Right, self.dims).1 .0);
+        if narrowest < MIN_PANE_WIDTH {
+            warn!("too narrow to split");
+            return;
+        }
+        let other = Pane {
+            view_y: self.view_y,
+            ..Pane::default()
+        };
+        let dims = self.dims;
+        self.split = Some(Split {
+            layout,
+            focus: Side::Left,
+            dims,
+            other,
+        });
+        self.dims = layout.pane(Side::Left, dims).1;
     }
 
-    fn toggle_collapsed(&mut self) {
-        trace!("toggle_collapsed()");
-        if let Some(selected_id) = self.selected {
-            self.with_node_mut_no_meta(selected_id, |node| node.toggle_collapsed());
+    // keeps the focused pane
+    fn close_split(&mut self) {
+        if let Some(split) = self.split.take() {
+            self.dims = split.dims;
         }
     }
 
-    fn toggle_show_logs(&mut self) {
-        self.show_logs = !self.show_logs;
+    fn focus_other_pane(&mut self) {
+        self.swap_pane();
+        if let Some(ref mut split) = self.split {
+            split.focus = split.focus.other();
+            self.dims = split.layout.pane(split.focus, split.dims).1;
+        }
     }
 
-    fn create_child(&mut self) {
+    // trades the focused pane's view for the other one's, marking which
+    // node is selected to match
+    fn swap_pane(&mut self) {
+        let mut other = match self.split {
+            Some(ref mut split) => std::mem::take(&mut split.other),
+            None => return,
+        };
+        if let Some(selected_id) = self.selected {
+            self.set_selected(selected_id, false);
+        }
+        std::mem::swap(&mut self.view_y, &mut other.view_y);
+        std::mem::swap(&mut self.selected, &mut other.selected);
+        std::mem::swap(&mut self.lookup, &mut other.lookup);
+        std::mem::swap(&mut self.drawn_at, &mut other.drawn_at);
+        std::mem::swap(&mut self.folds, &mut other.folds);
+        std::mem::swap(&mut self.placements, &mut other.placements);
+        std::mem::swap(&mut self.placed_rows, &mut other.placed_rows);
+        std::mem::swap(&mut self.selected_fold, &mut other.selected_fold);
+        std::mem::swap(&mut self.lowest_drawn, &mut other.lowest_drawn);
+        // it may have been deleted from the other pane, or collapsed out of
+        // sight there, when what hid it is selected instead
         if let Some(mut selected_id) = self.selected {
-            if self.with_node(selected_id, |n| n.content.is_empty())
-                .unwrap()
-            {
-                // we may have hit tab after enter by accident,
-                // so go forward a level by selecting the previous
-                // child of the current parent
-                let parent_id = self.parent(selected_id).unwrap();
-                if parent_id == self.drawing_root {
-                    // don't want to create a sibling of the drawing root
-                    // because that's not underneath the drawing root
-                    return;
-                }
+            while let Some(parent_id) = self.hidden_under(selected_id) {
+                selected_id = parent_id;
+            }
+            self.selected = Some(selected_id);
+            if self.set_selected(selected_id, true).is_none() {
+                self.selected = None;
+            }
+        }
+        if let Some(ref mut split) = self.split {
+            split.other = other;
+        }
+        // bust grapheme cache on new view
+        self.grapheme_cache.clear();
+    }
 
-                let above = self.with_node(parent_id, |parent| {
-                    let idx = parent
-                        .children
-                        .iter()
-                        .position(|&e| e == selected_id)
-                        .unwrap();
-                    parent.children[max(idx, 1) - 1]
-                }).unwrap();
+    // asks how big the terminal is now. when it can't say, the last size it
+    // gave is kept, or the configured fallback if it never gave one.
+    pub fn refresh_size(&mut self) {
+        let dims = match self.size_source.size() {
+            Ok(dims) => {
+                self.last_size = Some(dims);
+                dims
+            }
+            Err(e) => {
+                let dims = self.last_size.unwrap_or(self.config.fallback_size);
+                debug!("failed to get the terminal size, using {:?}: {}", dims, e);
+                dims
+            }
+        };
+        self.resize(dims);
+    }
 
-                self.select_node(above);
-                selected_id = above;
+    // the terminal's size, of which a split gives the focused pane a part
+    fn resize(&mut self, dims: Coords) {
+        let old = self.split.as_ref().map_or(self.dims, |split| split.dims);
+        if dims != old {
+            self.record(Entry::Size(dims));
+        }
+        self.dims = match self.split {
+            Some(ref mut split) => {
+                split.dims = dims;
+                split.layout.pane(split.focus, dims).1
             }
-            let selected_id = selected_id;
+            None => dims,
+        };
+    }
 
-            let node_id = self.new_node();
-            self.with_node_mut_no_meta(node_id, |node| node.parent_id = selected_id);
-            let added = self.with_node_mut_no_meta(
-                selected_id,
-                |selected| { selected.children.push(node_id); },
-            );
-            if added.is_some() {
-                self.select_node(node_id);
-            } else {
-                self.delete_recursive(node_id);
+    // does what the open question was answered with
+    fn answer(&mut self, modal: Modal, key: Key) -> error::Result<()> {
+        match (modal, key) {
+            (Modal::Delete(node_id, count), Key::Char('y')) if self.selected == Some(node_id) => {
+                info!("deleting {} nodes", count);
+                self.delete_selected_unconfirmed(true);
+            }
+            (Modal::Delete(_, count), _) => info!("cancelled deletion of {} nodes", count),
+            (Modal::Export(format, node_id), Key::Char('s')) => {
+                return self.export_file(format, Some(node_id));
+            }
+            (Modal::Export(format, _), _) => return self.export_file(format, None),
+            (Modal::Window, Key::Char('v')) => self.split(Layout::default()),
+            (Modal::Window, Key::Char('q')) => self.close_split(),
+            (Modal::Window, _) => self.focus_other_pane(),
+            (Modal::BulkDelete(matches), Key::Char('y')) => {
+                let deleted = self.apply_to_all(&matches, |s| s.delete_selected_unconfirmed(false));
+                info!("deleted {} nodes", deleted);
+            }
+            (Modal::BulkDelete(matches), _) => {
+                info!("cancelled deletion of {} matches", matches.len())
+            }
+            (Modal::DeleteSelection(children, _), Key::Char('y')) => {
+                let deleted =
+                    self.apply_to_all(&children, |s| s.delete_selected_unconfirmed(false));
+                info!("deleted {} nodes", deleted);
+            }
+            (Modal::DeleteSelection(_, count), _) => info!("cancelled deletion of {} nodes", count),
+            (Modal::Substitute(sub, nodes), Key::Char('y')) => self.substitute(&sub, &nodes),
+            (Modal::Substitute(_, nodes), _) => {
+                info!("cancelled replacing in {} nodes", nodes.len())
             }
         }
+        Ok(())
     }
 
-    fn create_sibling(&mut self) {
-        if let Some(mut selected_id) = self.selected {
-            if self.with_node(selected_id, |n| n.content.is_empty())
-                .unwrap()
-            {
-                // we just hit enter twice, so go back a level
-                let sel_parent = self.parent(selected_id).unwrap();
-                if sel_parent == self.drawing_root {
-                    // don't want to create a sibling of the drawing root
-                    // because that's not underneath the drawing root
-                    return;
-                }
-                self.select_node(sel_parent);
-                selected_id = sel_parent;
+    // mouse events are kept to the terminal, whatever a confused terminal
+    // or a fuzzer says, so that nothing's put where it can't be seen
+    fn clamp_mouse(&self, evt: Event) -> Option<Event> {
+        let (width, height) = self.split.as_ref().map_or(self.dims, |split| split.dims);
+        let clamp = |x: u16, y: u16| if x > width.saturating_add(MOUSE_SLACK) ||
+                                        y > height.saturating_add(MOUSE_SLACK) {
+            debug!("dropped a mouse event at {:?}, off the screen", (x, y));
+            None
+        } else {
+            Some((max(min(x, width), 1), max(min(y, height), 1)))
+        };
+        let clamped = match evt {
+            Event::Mouse(MouseEvent::Press(button, x, y)) => {
+                clamp(x, y).map(|(x, y)| MouseEvent::Press(button, x, y))
             }
-            let selected_id = selected_id;
+            Event::Mouse(MouseEvent::Release(x, y)) => {
+                clamp(x, y).map(|(x, y)| MouseEvent::Release(x, y))
+            }
+            Event::Mouse(MouseEvent::Hold(x, y)) => {
+                clamp(x, y).map(|(x, y)| MouseEvent::Hold(x, y))
+            }
+            evt => return Some(evt),
+        };
+        clamped.map(Event::Mouse)
+    }
 
-            if let Some(parent_id) = self.parent(selected_id) {
-                if parent_id == self.drawing_root {
-                    self.create_child();
-                    return;
+    // with a split, mouse events go to the pane under them, which takes the
+    // focus, and are counted from its corner. drags stay in the pane they
+    // started in, and the divider takes nothing.
+    fn route_to_pane(&mut self, evt: Event) -> Option<Event> {
+        let (layout, focus, dims) = match self.split {
+            Some(ref split) if !self.list_view_open() && !self.overlay_open() => {
+                (split.layout, split.focus, split.dims)
+            }
+            _ => return Some(evt),
+        };
+        let routed = match evt {
+            Event::Mouse(MouseEvent::Press(button, x, y)) => {
+                let (side, (x, y)) = layout.locate((x, y), dims)?;
+                if side != focus {
+                    self.focus_other_pane();
                 }
-                let node_id = self.new_node();
+                MouseEvent::Press(button, x, y)
+            }
+            Event::Mouse(MouseEvent::Release(x, y)) => {
+                let (x, y) = layout.within(focus, (x, y), dims);
+                MouseEvent::Release(x, y)
+            }
+            Event::Mouse(MouseEvent::Hold(x, y)) => {
+                let (x, y) = layout.within(focus, (x, y), dims);
+                MouseEvent::Hold(x, y)
+            }
+            evt => return Some(evt),
+        };
+        Some(Event::Mouse(routed))
+    }
 
-                self.with_node_mut_no_meta(node_id, |node| node.parent_id = parent_id);
-                let added = self.with_node_mut_no_meta(parent_id, |parent| {
-                    // it's possible that selected_id has been deleted by now
-                    // due to it being empty when we entered the function
-                    // (double enter for going up a level)
-                    let idx = parent
-                        .children
-                        .iter()
-                        .position(|&e| e == selected_id)
-                        .unwrap_or(0);
-                    parent.children.insert(idx + 1, node_id);
-                });
-                if added.is_some() {
-                    self.select_node(node_id);
-                } else {
-                    self.delete_recursive(node_id);
+    fn switch_tab(&mut self, to: usize) {
+        let from = self.tab;
+        if to == from || to > self.tabs.len() {
+            return;
+        }
+        // panes are a way of looking at one file
+        self.close_split();
+        let mut other = self.tabs.remove(if to < from { to } else { to - 1 });
+        std::mem::swap(self, &mut other);
+        self.swap_window_state(&mut other);
+        // `other` now holds the tab we're leaving
+        self.tabs.insert(if from < to { from } else { from - 1 }, other);
+        self.tab = to;
+    }
+
+    // everything about the terminal and the user's settings stays put when
+    // switching tabs, only the file's own state moves
+    fn swap_window_state(&mut self, other: &mut Screen) {
+        std::mem::swap(&mut self.config, &mut other.config);
+        std::mem::swap(&mut self.rendering, &mut other.rendering);
+        std::mem::swap(&mut self.capabilities, &mut other.capabilities);
+        std::mem::swap(&mut self.delete_confirm_threshold, &mut other.delete_confirm_threshold);
+        std::mem::swap(&mut self.dims, &mut other.dims);
+        std::mem::swap(&mut self.is_test, &mut other.is_test);
+        std::mem::swap(&mut self.show_logs, &mut other.show_logs);
+        std::mem::swap(&mut self.stdout, &mut other.stdout);
+        std::mem::swap(&mut self.alternate, &mut other.alternate);
+        std::mem::swap(&mut self.last_frame, &mut other.last_frame);
+        std::mem::swap(&mut self.notifier, &mut other.notifier);
+        std::mem::swap(&mut self.size_source, &mut other.size_source);
+        std::mem::swap(&mut self.last_size, &mut other.last_size);
+        std::mem::swap(&mut self.url_opener, &mut other.url_opener);
+        std::mem::swap(&mut self.tabs, &mut other.tabs);
+        std::mem::swap(&mut self.command_history, &mut other.command_history);
+        std::mem::swap(&mut self.recent, &mut other.recent);
+        std::mem::swap(&mut self.recorder, &mut other.recorder);
+    }
+
+    // the work file's name, marked when there are unsaved changes
+    fn file_name(&self) -> Option<String> {
+        self.work_path.as_ref().map(|work_path| {
+            let name = Path::new(work_path)
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| work_path.clone());
+            let marker = if self.dirty { "*" } else { "" };
+            format!("{}{}", name, marker)
+        })
+    }
+
+    // names of every open file, with the active one bracketed
+    fn tab_strip(&self) -> String {
+        let name = |s: &Screen| s.file_name().unwrap_or_else(|| "untitled".to_owned());
+        let mut names: Vec<String> = self.tabs.iter().map(name).collect();
+        names.insert(self.tab, format!("[{}]", name(self)));
+        names.join(" ")
+    }
+
+    fn read_work_file_stamp(&self) -> Option<(SystemTime, u64)> {
+        self.work_path
+            .as_ref()
+            .and_then(|path| fs::metadata(path).ok())
+            .and_then(|meta| meta.modified().ok().map(|mtime| (mtime, meta.len())))
+    }
+
+    // reload the work file if something else wrote to it, unless that
+    // would throw away changes that haven't been saved yet
+    fn check_work_file(&mut self) {
+        let stamp = match self.read_work_file_stamp() {
+            Some(stamp) => stamp,
+            None => return,
+        };
+        match self.work_file_stamp {
+            None => self.work_file_stamp = Some(stamp),
+            Some(known) if known == stamp => {}
+            Some(_) if self.dirty => {
+                if !self.conflict {
+                    warn!("work file changed on disk while there are unsaved changes");
+                    self.conflict = true;
                 }
             }
+            Some(_) => self.reload(),
         }
     }
 
-    fn create_free_node(&mut self) {
-        let min_width = self.dims.0 / 3;
-        let mut y_cursor = self.view_y + 2;
-        let mut from_x = None;
-        'outer: loop {
-            trace!("in create_free_node loop");
-            // go down until we find a spot
-            // that's wide enough, then create
-            // an anchor there.
-            let mut width = 0;
-            if self.dims.0 < 2 {
-                from_x = Some(1);
-                break;
-            }
-            for x in 1..self.dims.0 {
-                if self.lookup((x, y_cursor)).is_none() {
-                    width += 1;
-                    if from_x.is_none() {
-                        from_x = Some(x)
-                    }
-                    if width >= min_width {
-                        break 'outer;
-                    }
-                } else {
-                    from_x = None;
-                    width = 0;
-                }
+    fn reload(&mut self) {
+        trace!("reload()");
+        self.conflict = false;
+        self.work_file_stamp = self.read_work_file_stamp();
+        let path = match self.work_path {
+            Some(ref path) => path.clone(),
+            None => return,
+        };
+        let mut data = vec![];
+        if let Err(e) = File::open(&path).and_then(|mut f| f.read_to_end(&mut data)) {
+            error!("failed to reload {}: {}", path, e);
+            return;
+        }
+        let loaded = match self.decode_map(data, false) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                error!("failed to reload {}: {}", path, e);
+                return;
             }
-            y_cursor += 1;
+        };
+
+        self.max_id = loaded.max_id;
+        self.nodes = loaded.nodes;
+        self.arrows = loaded.arrows;
+        self.trash = loaded.trash;
+        self.archive = loaded.archive;
+        self.bookmarks = loaded.bookmarks;
+        self.groups = loaded.groups;
+        self.tombstones = loaded.tombstones;
+        self.tag_db = loaded.tag_db;
+        self.dirty = false;
+        self.grapheme_cache.clear();
+        self.modal = None;
+        self.matches = None;
+        self.keep_view();
+        info!("reloaded {} after it changed on disk", path);
+    }
+
+    // takes in the changes made to another copy of the work file, keeping
+    // our own. trash and archive are left as they are.
+    pub fn merge(&mut self, other: &Screen) {
+        trace!("merge()");
+        let merged = merge::merge(self, other);
+        self.max_id = merged.max_id;
+        self.nodes = merged.nodes;
+        self.arrows = merged.arrows;
+        self.bookmarks = merged.bookmarks;
+        self.groups = merged.groups;
+        self.tombstones = merged.tombstones;
+        self.tag_db = TagDB::default();
+        for node in self.nodes.values() {
+            self.tag_db.reindex(node.id, node.tag_text());
+        }
+        // anchors from the two copies can have been put in the same place.
+        // the one made later moves down, like a pasted one would, newest
+        // first so that which copy was ours makes no difference.
+        let mut anchors = self.with_node(0, |n| n.children.clone()).unwrap_or_default();
+        anchors.sort_by_key(|id| (self.nodes[id].meta.ctime, *id));
+        for &id in anchors.iter().rev() {
+            let coords = self.nodes[&id].rooted_coords;
+            let unclaimed = self.unclaimed_anchor_coords(id, 0, coords);
+            self.nodes.get_mut(&id).unwrap().rooted_coords = unclaimed;
+        }
+        self.dirty = true;
+        self.grapheme_cache.clear();
+        self.matches = None;
+        self.keep_view();
+        // a question that's up stays up through a sync, unless what it was
+        // asked about is gone
+        let gone = match self.modal {
+            Some(Modal::Delete(node_id, _)) |
+            Some(Modal::Export(_, node_id)) => !self.exists(node_id),
+            _ => false,
+        };
+        if gone {
+            self.modal = None;
         }
-        self.create_anchor((from_x.unwrap(), y_cursor));
     }
 
-    fn create_anchor(&mut self, coords: Coords) {
-        let root = self.drawing_root;
-        let node_id = self.new_node();
-        self.with_node_mut_no_meta(node_id, |node| {
-            node.rooted_coords = coords;
-            node.parent_id = root;
-        });
-        self.with_node_mut_no_meta(root, |root| root.children.push(node_id));
-        self.select_node(node_id);
+    fn merge_file(&mut self, path: &str) {
+        let mut data = vec![];
+        if let Err(e) = File::open(path).and_then(|mut f| f.read_to_end(&mut data)) {
+            error!("failed to merge {}: {}", path, e);
+            return;
+        }
+        match self.decode_map(data, true) {
+            Ok(other) => {
+                self.merge(&other);
+                info!("merged {}", path);
+            }
+            Err(e) => error!("failed to merge {}: {}", path, e),
+        }
     }
 
-    fn backspace(&mut self) {
-        trace!("backspace");
-        if let Some(selected_id) = self.selected {
-            if let Some(content) = self.with_node_mut(selected_id, |node| {
-                let content = node.content.clone();
-                let chars = content.chars();
-                let oldlen = chars.clone().count();
-                let truncated: String = chars.take(max(oldlen, 1) - 1).collect();
-                node.content = truncated;
-                node.content.clone()
-            })
-            {
-                self.grapheme_cache.remove(&selected_id);
-                self.tag_db.reindex(selected_id, content);
+    // a saved map, decrypted with our key if it was saved with it, or else
+    // with a passphrase asked for when ask is set
+    fn decode_map(&mut self, data: Vec<u8>, ask: bool) -> Result<Screen, String> {
+        if !crypto::is_sealed(&data) {
+            return serialization::deserialize_screen(data).map_err(|e| format!("{:?}", e));
+        }
+        if let Some(key) = self.key.clone() {
+            if let Ok(plaintext) = crypto::open_with(&data, &key) {
+                let mut screen = serialization::deserialize_screen(plaintext)
+                    .map_err(|e| format!("{:?}", e))?;
+                screen.key = Some(key);
+                return Ok(screen);
             }
         }
+        if !ask {
+            return Err("encrypted with another passphrase".to_owned());
+        }
+        match self.prompt_with("passphrase: ", true) {
+            Ok(Some(passphrase)) => serialization::deserialize_sealed_screen(&data, &passphrase),
+            _ => Err("no passphrase given".to_owned()),
+        }
     }
 
-    fn append(&mut self, c: char) {
-        trace!("append({})", c);
+    // after the nodes were replaced, keep looking at the same place if it
+    // survived
+    fn keep_view(&mut self) {
+        if !self.exists(self.drawing_root) {
+            self.drawing_root = 0;
+            self.focus_stack.clear();
+        }
         if let Some(selected_id) = self.selected {
-            if let Some(content) = self.with_node_mut(selected_id, |node| {
-                node.content.push(c);
-                node.content.clone()
-            })
-            {
-                self.grapheme_cache.remove(&selected_id);
-                self.tag_db.reindex(selected_id, content);
+            if self.exists(selected_id) {
+                self.set_selected(selected_id, true);
+            } else {
+                self.selected = None;
             }
         }
+        if !self.cut.map(|id| self.exists(id)).unwrap_or(true) {
+            self.cut = None;
+        }
+        if !self.drawing_arrow.map(|id| self.exists(id)).unwrap_or(true) {
+            self.drawing_arrow = None;
+        }
     }
 
-    pub fn drawn_at(&self, node_id: NodeID) -> Option<&Coords> {
-        self.drawn_at.get(&node_id)
+    fn tick_pomodoro(&mut self, now: u64) {
+        let transition = match self.pomodoro {
+            Some(ref mut pomodoro) => pomodoro.tick(now),
+            None => return,
+        };
+        match transition {
+            Some(PomodoroTransition::WorkDone) => {
+                let node_id = self.pomodoro.as_ref().unwrap().node;
+                self.with_node_mut_no_meta(node_id, |n| {
+                    let count = n.meta
+                        .tags
+                        .get("pomodoros")
+                        .and_then(|c| c.parse::<u64>().ok())
+                        .unwrap_or(0);
+                    n.meta.tags.insert("pomodoros".to_owned(), (count + 1).to_string());
+                });
+                self.flash = true;
+            }
+            Some(PomodoroTransition::BreakDone) => {
+                self.pomodoro = None;
+                self.flash = true;
+            }
+            None => {}
+        }
     }
 
-    pub fn lookup(&self, coords: Coords) -> Option<&NodeID> {
-        self.lookup.get(&coords)
+    fn notify_due(&mut self, now: u64) {
+        let mut notices = self.notify(now);
+        self.notices.append(&mut notices);
     }
 
-    fn lineage(&self, node_id: NodeID) -> Vec<NodeID> {
-        let mut lineage = vec![node_id];
-        let mut cursor = node_id;
-        while let Some(parent) = self.parent(cursor) {
-            lineage.push(parent);
-            if parent == 0 {
-                break;
-            }
-            cursor = parent;
+    // what came due since the last run, before the first tick gets to it
+    pub fn notify_missed(&mut self, now: u64) {
+        self.missed = self.notify(now);
+        if !self.missed.is_empty() {
+            info!("{} nodes came due while void was closed", self.missed.len());
         }
-        lineage.reverse();
-        lineage
     }
 
-    // returns true if a is a parent of b
-    fn is_parent(&self, a: NodeID, b: NodeID) -> bool {
-        trace!("is_parent({}, {})", a, b);
-        let mut ptr = b;
-        loop {
-            trace!("loop in is_parent");
-            if ptr == a {
-                return true;
-            } else if ptr == 0 {
-                // we've reached the top, and 0 is not the parent (a)
-                // because we did not return in the last clause
-                return false;
-            }
-            ptr = self.parent(ptr).unwrap_or_else(|| {
-                self.with_node(b, |n| error!("orphan node, have it cleaned up: {:?}", n));
-                0
-            });
+    // rings the bell and runs the notifier for what became due, returning
+    // their contents. each due time is stamped on its node so that it's
+    // only notified once, even across restarts.
+    fn notify(&mut self, now: u64) -> Vec<String> {
+        let due = task::became_due(self, now);
+        if !due.is_empty() {
+            out!("\x07");
         }
+        let mut contents = vec![];
+        for node_id in due {
+            let content = self.with_node_mut_no_meta(node_id, |n| {
+                n.meta.notified = n.meta.due;
+                n.content.clone()
+            }).unwrap();
+            self.notifier.notify(node_id, &content);
+            contents.push(content);
+        }
+        contents
     }
 
-    fn anchor(&self, node_id: NodeID) -> Result<NodeID, String> {
-        if let None = self.drawn_at(node_id) {
-            return Err("node not drawn on this screen".to_owned());
+    // a banner over the bottom of the screen, above the one for notices
+    fn draw_missed(&self) {
+        let shown = min(self.missed.len(), MISSED_SHOWN);
+        let more = self.missed.len() - shown;
+        let rows = shown + 1 + if more > 0 { 1 } else { 0 };
+        if self.missed.is_empty() || self.dims.1 as usize <= rows + 3 {
+            return;
         }
-
-        // find the "root" just below self.drawing_root to mod
-        // the rooted_coords for.
-        let mut ptr = node_id;
-        loop {
-            let id = self.parent(ptr).ok_or("node has no parent")?;
-            trace!(
-                "anchor loop id: {} ptr: {} selected: {} root: {}",
-                id,
-                ptr,
-                node_id,
-                self.drawing_root
-            );
-            if id != self.drawing_root {
-                ptr = id;
-            } else {
-                break;
-            }
+        let width = self.dims.0 as usize;
+        let mut lines = vec![format!("came due while void was closed ({}):", self.missed.len())];
+        lines.extend(self.missed[..shown].iter().map(|content| format!("  {}", content)));
+        if more > 0 {
+            lines.push(format!("  and {} more", more));
+        }
+        let top = self.dims.1 as usize - 1 - rows;
+        for (idx, line) in lines.iter().enumerate() {
+            let line = truncate_right(&printable(line, self.glyphs()), width, self.glyphs());
+            out!("{}{}{}{}{}{}",
+                 cursor::Goto(1, (top + idx) as u16),
+                 clear::CurrentLine,
+                 color::Fg(color::Red),
+                 if idx == 0 { format!("{}", style::Invert) } else { String::new() },
+                 line,
+                 style::Reset);
         }
-        Ok(ptr)
     }
 
-    fn parent(&self, node_id: NodeID) -> Option<NodeID> {
-        trace!("parent({})", node_id);
-        self.with_node(node_id, |n| n.parent_id)
+    fn dismiss_notices(&mut self) {
+        trace!("dismiss_notices()");
+        self.notices.clear();
+        self.missed.clear();
     }
 
-    fn move_selected(&mut self, from: Coords, to: Coords) {
-        trace!("move_selected({:?}, {:?})", from, to);
-        let dx = to.0 as i16 - from.0 as i16;
-        let dy = to.1 as i16 - from.1 as i16;
+    fn running_timers(&self) -> Vec<NodeID> {
+        self.nodes
+            .values()
+            .filter(|n| n.meta.timer_running())
+            .map(|n| n.id)
+            .collect()
+    }
 
-        let selected_id = if let Some(selected_id) = self.selected {
-            if self.is_parent(self.drawing_root, selected_id) {
-                selected_id
-            } else {
-                // selected node is not a child of drawing_root
-                debug!("selected node is not child of drawing_root");
-                return;
-            }
-        } else {
-            // nothing to drag, no work to do
-            trace!("leaving move_selected, no work to do");
-            return;
+    // the nodes in view that a replacement would be made in: under the
+    // focus in focus mode, or the node drilled into
+    fn substitution_scope(&self, sub: &Substitution) -> Vec<NodeID> {
+        let (root, with_root) = match self.focus_root() {
+            Some(focus_root) => (focus_root, true),
+            None => (self.drawing_root, false),
         };
-        if let Some(&new_parent) = self.lookup(to) {
-            if !self.is_parent(selected_id, new_parent) {
-                self.reparent(selected_id, new_parent);
+        self.recursive_child_filter_map(root, &mut |n| {
+            if (with_root || n.id != root) && sub.matches(&n.content) {
+                Some(n.id)
             } else {
-                // we're here because we released the drag
-                // with the cursor over a child, so rather
-                // than create a cycle, we move the subtree.
-                let ptr = self.anchor(selected_id).unwrap();
-                trace!("move selected 2");
-                self.with_node_mut_no_meta(ptr, |mut root| {
-                    let (ox, oy) = root.rooted_coords;
-                    let nx = max(ox as i16 + dx, 1) as u16;
-                    let ny = max(oy as i16 + dy, 1) as u16;
-                    root.rooted_coords = (nx, ny);
-                }).unwrap();
+                None
             }
+        })
+    }
 
+    // asks before replacing, saying how many nodes would change
+    fn substitute_prompt(&mut self, sub: Substitution) {
+        let nodes = self.substitution_scope(&sub);
+        if nodes.is_empty() {
+            info!("no nodes have \"{}\"", sub.pattern);
         } else {
-            // destination is not another node, so redraw selected at coords
-            // 1. remove from old parent's children
-            // 2. add to drawing_root's children
-            // 3. update rooted_coords
-            let old_parent = self.parent(selected_id).unwrap();
-            self.with_node_mut_no_meta(old_parent, |op| op.children.retain(|c| c != &selected_id))
-                .unwrap();
-            let root = self.drawing_root;
-            self.with_node_mut_no_meta(root, |dr| dr.children.push(selected_id))
-                .unwrap();
-            self.with_node_mut_no_meta(selected_id, |s| {
-                s.rooted_coords = to;
-                s.parent_id = root;
-            }).unwrap();
+            self.modal = Some(Modal::Substitute(sub, nodes));
         }
-        trace!("leaving move_selected");
-    }
-
-    fn pop_focus(&mut self) {
-        // bust grapheme cache on new view
-        self.grapheme_cache.clear();
-        self.unselect();
-        let (root, selected, view_y) = self.focus_stack.pop().unwrap_or((0, 0, 0));
-        self.drawing_root = root;
-        self.view_y = view_y;
-        self.select_node(selected);
     }
 
-    fn drill_down(&mut self) {
-        trace!("drill_down()");
-        // bust grapheme cache on new view
-        self.grapheme_cache.clear();
-        if let Some(selected_id) = self.unselect() {
-            if selected_id != self.drawing_root {
-                let breadcrumb = (self.drawing_root, selected_id, self.view_y);
-                self.focus_stack.push(breadcrumb);
-                self.drawing_root = selected_id;
-                self.view_y = 0;
+    fn substitute(&mut self, sub: &Substitution, nodes: &[NodeID]) {
+        let (mut changed, mut replaced) = (0, 0);
+        for &node_id in nodes {
+            // it may have changed since it was counted
+            let content = self.with_node(node_id, |n| sub.apply(&n.content)).and_then(|c| c);
+            if let Some((content, count)) = content {
+                let text = self.with_node_mut(node_id, |n| {
+                        n.content = content.clone();
+                        n.tag_text()
+                    })
+                    .unwrap();
+                self.tag_db.reindex(node_id, text);
+                self.grapheme_cache.remove(&node_id);
+                changed += 1;
+                replaced += count;
             }
         }
+        info!("replaced {} matches in {} nodes", replaced, changed);
     }
 
-    fn click_select(&mut self, coords: Coords) -> Option<NodeID> {
-        trace!("click_select({:?})", coords);
-        let result = self.try_select(coords);
-        self.dragging_from.take();
-        self.dragging_to.take();
-        result
+    fn toggle_hide_stricken(&mut self) {
+        trace!("toggle_hide_stricken()");
+        if let Some(selected_id) = self.selected {
+            self.with_node_mut(selected_id, |node| node.toggle_hide_stricken());
+            // hiding them again starts with the fold closed
+            self.revealed.remove(&selected_id);
+            self.grapheme_cache.remove(&selected_id);
+            self.relayout();
+        }
     }
 
-    fn scroll_up(&mut self) {
-        self.view_y = max(self.view_y, self.dims.1 / 2) - self.dims.1 / 2;
-        self.unselect();
-    }
+    // removes node_id and its children, moving them into removed
+    fn delete_recursive(&mut self, node_id: NodeID, removed: &mut TrashEntry) {
+        trace!("delete_recursive({})", node_id);
+        if let Some(node) = self.nodes.remove(&node_id) {
+            // clean up any arrow state
+            let (touching, others) = self.arrows.drain(..).partition(|&(from, to)| {
+                from == node_id || to == node_id
+            });
+            self.arrows = others;
+            removed.arrows.extend(touching);
 
-    fn scroll_down(&mut self) {
-        if self.lowest_drawn > self.view_y + self.dims.1 {
-            self.view_y = min(self.view_y + self.dims.1 / 2, self.lowest_drawn);
-            self.unselect();
-        }
-    }
+            // remove from tag_db
+            self.tag_db.remove(node_id);
 
-    fn scroll_to_selected(&mut self) -> bool {
-        if let Some(selected_id) = self.selected {
-            self.scroll_to_node(selected_id)
-        } else {
-            false
-        }
-    }
+            self.bookmarks.retain(|_, id| *id != node_id);
+            self.groups.remove(&node_id);
+            self.tombstones.insert((node_id, node.meta.ctime), clock::now());
 
-    fn scroll_to_node(&mut self, node_id: NodeID) -> bool {
-        if let Some(visible) = self.node_is_visible(node_id) {
-            let &(_, y) = self.drawn_at(node_id).unwrap();
-            if !visible {
-                // move only if necessary
-                self.view_y = max(y - 1, self.dims.1 / 2) - self.dims.1 / 2;
-                self.draw();
-                return true;
+            for child_id in &node.children {
+                self.delete_recursive(*child_id, removed);
             }
-        }
-        false
-    }
 
-    fn zoom_select(&mut self, node_id: NodeID) {
-        if !self.exists(node_id) {
-            return;
-        }
-        // jump to highest view where node is visible
-        let mut cursor = node_id;
-        loop {
-            trace!("in auto_task loop");
-            let parent = self.parent(cursor).unwrap();
-            let collapsed = self.with_node(parent, |p| p.collapsed).unwrap();
-            cursor = parent;
-            if parent == 0 || collapsed {
-                break;
-            }
+            removed.nodes.insert(node_id, node);
         }
-
-        // save old location and jump
-        let old_select = self.unselect().unwrap_or(0);
-        let breadcrumb = (self.drawing_root, old_select, self.view_y);
-        self.focus_stack.push(breadcrumb);
-        self.drawing_root = cursor;
-        self.select_node(node_id);
-        self.draw();
     }
 
-    fn raise_selected(&mut self) {
+    fn delete_selected(&mut self, reselect: bool) {
+        trace!("delete_selected()");
         if let Some(selected_id) = self.selected {
-            if !self.exists(selected_id) {
-                warn!("tried to raise deleted node");
-                return;
-            }
-            let parent_id = self.parent(selected_id).unwrap();
-            if parent_id == self.drawing_root {
-                // principle: don't modify things that are above the visible scope
+            let count = self.recursive_child_filter_map(selected_id, &mut |_| Some(())).len();
+            if count > self.delete_confirm_threshold + 1 {
+                self.modal = Some(Modal::Delete(selected_id, count));
                 return;
             }
-            self.with_node_mut_no_meta(parent_id, |mut parent| {
-                let idx = parent
-                    .children
-                    .iter()
-                    .position(|&e| e == selected_id)
-                    .unwrap();
-                let to = max(idx, 1) - 1;
-                parent.children.swap(idx, to);
-            });
         }
+        self.delete_selected_unconfirmed(reselect);
     }
 
-    fn lower_selected(&mut self) {
-        if let Some(selected_id) = self.selected {
-            if !self.exists(selected_id) {
-                warn!("tried to lower deleted node");
-                return;
+    fn delete_selected_unconfirmed(&mut self, reselect: bool) {
+        trace!("delete_selected_unconfirmed()");
+        if let Some(selected_id) = self.selected.take() {
+            let (_, height) = self.drawable_subtree_dims(selected_id).unwrap();
+            let coords = self.drawn_at.remove(&selected_id);
+            let (parent_id, rooted_coords, empty) = self.with_node_mut_no_meta(selected_id, |n| {
+                    n.selected = false;
+                    (n.parent_id, n.rooted_coords, n.content.is_empty() && n.children.is_empty())
+                })
+                .unwrap();
+            // remove ref from parent
+            trace!("deleting node {} from parent {}", selected_id, parent_id);
+            self.with_node_mut(parent_id, |p| p.children.retain(|c| c != &selected_id));
+            // remove children
+            let mut removed = TrashEntry::new(selected_id, parent_id, coords.unwrap_or(rooted_coords));
+            self.delete_recursive(selected_id, &mut removed);
+            // the other pane can be drilled down into what's gone
+            self.keep_view();
+            if let Some((x, y)) = coords {
+                if reselect {
+                    self.click_select((x, y + height));
+                }
             }
-            let parent_id = self.parent(selected_id).unwrap();
-            if parent_id == self.drawing_root {
-                // principle: don't modify things that are above the visible scope
-                return;
+            // blank nodes are cleaned up on unselect, and not worth keeping
+            if !empty {
+                self.trash.push(removed);
             }
-            self.with_node_mut_no_meta(parent_id, |mut parent| {
-                let idx = parent
-                    .children
-                    .iter()
-                    .position(|&e| e == selected_id)
-                    .unwrap();
-                let len = parent.children.len();
-                if len > 1 {
-                    let to = min(idx, len - 2) + 1;
-                    parent.children.swap(idx, to);
-                }
-            });
         }
     }
 
-    fn select_up(&mut self) {
-        let view_y = self.view_y;
-        let height = self.dims.1;
-        self.select_relative(|(l1, _), (l2, _)| {
-            let is_up = l1.1 > l2.1;
-            let (diff_x, diff_y) = distances(l1, l2);
-            if is_up {
-                let visible = visible(view_y, height, l2.1);
-                Some((!visible, diff_x, diff_y))
-            } else {
-                None
-            }
-        });
+    fn undo_delete(&mut self) {
+        if let Some(entry) = self.trash.pop() {
+            let parent_id = entry.parent_id;
+            let node_id = self.restore_trash_entry(entry, Some(parent_id));
+            self.select_node(node_id);
+        }
     }
 
-    fn select_down(&mut self) {
-        let view_y = self.view_y;
-        let height = self.dims.1;
-        self.select_relative(|(l1, _), (l2, _)| {
-            let is_down = l1.1 < l2.1;
-            let (diff_x, diff_y) = distances(l1, l2);
-            if is_down {
-                let visible = visible(view_y, height, l2.1);
-                Some((!visible, diff_x, diff_y))
-            } else {
-                None
+    // put a trashed subtree back under parent_id, or as a new anchor in
+    // the current view near where it was deleted if no parent is given or
+    // the parent no longer exists.
+    fn restore_trash_entry(&mut self, entry: TrashEntry, parent_id: Option<NodeID>) -> NodeID {
+        trace!("restore_trash_entry({})", entry.root);
+        let root = entry.root;
+        let parent_id = parent_id.and_then(|p| if self.exists(p) { Some(p) } else { None });
+        let anchor_coords = if parent_id.is_none() {
+            let coords = self.nearest_free_coords(entry.coords);
+            Some(self.unclaimed_anchor_coords(root, self.drawing_root, coords))
+        } else {
+            None
+        };
+        for (id, node) in entry.nodes {
+            // trash kept across a reload can be newer than the file
+            self.max_id = max(self.max_id, id);
+            self.tombstones.remove(&(id, node.meta.ctime));
+            self.tag_db.reindex(id, node.tag_text());
+            self.nodes.insert(id, node);
+        }
+        for (from, to) in entry.arrows {
+            if self.exists(from) && self.exists(to) && !self.arrows.contains(&(from, to)) {
+                self.arrows.push((from, to));
+            }
+        }
+
+        let parent_id = parent_id.unwrap_or(self.drawing_root);
+        // back under the drawing root, the old spot may have been taken since
+        let anchor_coords = anchor_coords.or_else(|| if parent_id == self.drawing_root {
+            let coords = self.with_node(root, |n| n.rooted_coords).unwrap_or((1, 1));
+            Some(self.unclaimed_anchor_coords(root, parent_id, coords))
+        } else {
+            None
+        });
+        self.with_node_mut(root, |n| {
+            n.parent_id = parent_id;
+            if let Some(coords) = anchor_coords {
+                n.rooted_coords = coords;
             }
         });
+        self.with_node_mut(parent_id, |p| if !p.children.contains(&root) {
+            p.children.push(root);
+        });
+        root
     }
 
-    fn select_left(&mut self) {
-        self.select_relative(|(l1, _), (_, r2)| {
-            let is_left = l1.0 > r2.0;
-            let (diff_x, diff_y) = distances(l1, r2);
-            if is_left {
-                Some((diff_y, diff_x))
-            } else {
-                None
+    // the closest row to coords, within the current view, where nothing is drawn
+    fn nearest_free_coords(&self, (x, y): Coords) -> Coords {
+        let top = self.view_y + 2;
+        let y = max(y, top);
+        let x = max(min(x, max(self.dims.0, 2) - 1), 1);
+        for dy in 0..max(self.dims.1, 1) {
+            if !self.occupied((x, y + dy)) {
+                return (x, y + dy);
             }
-        })
+            if y >= top + dy && !self.occupied((x, y - dy)) {
+                return (x, y - dy);
+            }
+        }
+        (x, max(self.lowest_drawn + 2, y))
     }
 
-    fn select_right(&mut self) {
-        self.select_relative(|(_, r1), (l2, _)| {
-            let is_right = r1.0 < l2.0;
-            let (diff_x, diff_y) = distances(r1, l2);
-            if is_right {
-                Some((diff_y, diff_x))
-            } else {
-                None
+    fn show_trash(&mut self) {
+        trace!("show_trash()");
+        self.trash.expire();
+        let chars = "1234567890abcdefghij";
+        let now = clock::now();
+
+        let mut listing = format!("{}{}trash:\r\n", cursor::Goto(1, 1), clear::All);
+        if self.trash.is_empty() {
+            listing.push_str("    (empty)\r\n");
+        }
+        for (c, entry) in chars.chars().zip(self.trash.entries()) {
+            write!(
+                listing,
+                "    {}: {} ({} nodes, deleted {} ago)\r\n",
+                c,
+                entry.content(),
+                entry.nodes.len(),
+                format_age(now, entry.deleted_at)
+            ).unwrap();
+        }
+
+        out!("{}", listing);
+
+        let choice = match self.single_key_prompt("restore which? (E to empty the trash): ") {
+            Ok(Key::Char(c)) => c,
+            _ => return,
+        };
+        if choice == 'E' {
+            info!("emptied {} items from trash", self.trash.len());
+            self.trash.clear();
+        } else if let Some(idx) = chars.chars().position(|c| c == choice) {
+            if let Some(entry) = self.trash.take(idx) {
+                let node_id = self.restore_trash_entry(entry, None);
+                self.select_node(node_id);
             }
-        });
+        }
     }
 
-    fn select_relative<F, O: Ord + Clone>(&mut self, filter_cost: F)
-        where F: FnMut((Coords, Coords), (Coords, Coords)) -> Option<O>
-    {
-        if let Some(node_id) = self.find_relative_node(filter_cost) {
-            self.select_node(node_id);
+    // moves stricken subtrees finished more than `days` ago into the
+    // archive, returning how many were moved
+    pub fn archive_stricken(&mut self, days: u64) -> usize {
+        trace!("archive_stricken({})", days);
+        let cutoff = clock::now().saturating_sub(days * 24 * 60 * 60);
+        let mut roots = vec![];
+        self.old_stricken_roots(0, cutoff, &mut roots);
+
+        // leave alone whatever we're drilled down into
+        let viewed = self.lineage(self.drawing_root);
+        roots.retain(|root| !viewed.contains(root));
+
+        for &root in &roots {
+            let (parent_id, rooted_coords) =
+                self.with_node(root, |n| (n.parent_id, n.rooted_coords)).unwrap();
+            let coords = self.drawn_at(root).cloned().unwrap_or(rooted_coords);
+            self.with_node_mut_no_meta(parent_id, |p| p.children.retain(|&c| c != root));
+            let mut entry = TrashEntry::new(root, parent_id, coords);
+            self.delete_recursive(root, &mut entry);
+            self.archive.push(entry);
+        }
+        if self.selected.map(|id| self.exists(id)) == Some(false) {
+            self.selected = None;
         }
+        info!("archived {} subtrees finished over {} days ago", roots.len(), days);
+        roots.len()
     }
 
-    fn find_relative_node<F, O: Ord + Clone>(&mut self, mut filter_cost: F) -> Option<NodeID>
-        where F: FnMut((Coords, Coords), (Coords, Coords)) -> Option<O>
-    {
-        let default_coords = (self.dims.0 / 2, self.dims.1 / 2);
-        let rel_def_coords = self.screen_to_internal_xy(default_coords);
-
-        let cur = self.selected
-            .and_then(|s| self.bounds_for_lookup(s))
-            .unwrap_or((rel_def_coords, rel_def_coords));
-
-        let mut node_costs = vec![];
-        for node_id in self.drawn_at.keys() {
-            if let Some(bounds) = self.bounds_for_lookup(*node_id) {
-                if let Some(cost) = filter_cost(cur, bounds) {
-                    node_costs.push((node_id, cost));
-                }
+    // the highest stricken nodes below node_id that were finished before cutoff
+    fn old_stricken_roots(&self, node_id: NodeID, cutoff: u64, roots: &mut Vec<NodeID>) {
+        if let Some(node) = self.nodes.get(&node_id) {
+            let old = node.meta.finish_time.map(|t| t < cutoff) == Some(true);
+            if node_id != 0 && node.stricken && old {
+                roots.push(node_id);
+                return;
+            }
+            for &child_id in &node.children {
+                self.old_stricken_roots(child_id, cutoff, roots);
             }
         }
-        node_costs.sort_by_key(|&(_, ref cost)| cost.clone());
-        node_costs.iter().nth(0).map(|&(&id, _)| id)
     }
 
-    fn select_node(&mut self, node_id: NodeID) {
-        trace!("select_node({})", node_id);
-        self.unselect();
-        if node_id != 0 {
-            // it's possible that unselecting above actually caused
-            // this node to be deleted, due to its parent (previous
-            // selection) being empty.  To account for this, we need
-            // to only set self.selected to node_id if the with_node
-            // succeeds.
-            self.with_node_mut_no_meta(node_id, |mut node| node.selected = true)
-                .map(|_| self.selected = Some(node_id));
+    // put an archived subtree back under its old parent, or as a new anchor
+    // if that's gone
+    fn restore_archived(&mut self, idx: usize) -> Option<NodeID> {
+        if idx >= self.archive.len() {
+            return None;
         }
+        let entry = self.archive.remove(idx);
+        let parent_id = entry.parent_id;
+        Some(self.restore_trash_entry(entry, Some(parent_id)))
     }
 
-    fn click_screen(&mut self, coords: Coords) {
-        trace!("click_screen({:?})", coords);
-        if coords.0 > self.dims.0 || coords.1 > self.view_y + self.dims.1 {
-            warn!("click way off-screen");
-            return;
+    fn show_archive(&mut self) {
+        trace!("show_archive()");
+        let chars = "1234567890abcdefghijklmnopqrstuvwxyz";
+        let now = clock::now();
+
+        let mut listing = format!("{}{}archive:\r\n", cursor::Goto(1, 1), clear::All);
+        if self.archive.is_empty() {
+            listing.push_str("    (empty)\r\n");
         }
-        let old = self.selected;
-        let new = self.try_select(coords);
-        if old.is_none() && self.dragging_from.is_none() {
-            self.create_anchor(coords);
+        // most recently archived first
+        let entries = self.archive.iter().enumerate().rev();
+        for (c, (_, entry)) in chars.chars().zip(entries) {
+            let finished = entry.nodes
+                .get(&entry.root)
+                .and_then(|n| n.meta.finish_time)
+                .unwrap_or(entry.deleted_at);
+            write!(
+                listing,
+                "    {}: {} ({} nodes, done {} ago)\r\n",
+                c,
+                entry.content(),
+                entry.nodes.len(),
+                format_age(now, finished)
+            ).unwrap();
+        }
+        if self.archive.len() > chars.len() {
+            write!(listing, "    ...and {} more\r\n", self.archive.len() - chars.len()).unwrap();
         }
 
-        // double click logic:
-        // set click time, drill-down if we're below double click threshold
-        let now = time::get_time();
-        let now_ms = (now.sec as u64 * 1000) + (now.nsec as u64 / 1_000_000);
-
-        let elapsed = now_ms - self.last_click_ms;
+        out!("{}", listing);
 
-        if new.is_some() {
-            self.last_click_ms = now_ms;
+        let choice = match self.single_key_prompt("restore which? ") {
+            Ok(Key::Char(c)) => c,
+            _ => return,
+        };
+        if let Some(pos) = chars.chars().position(|c| c == choice) {
+            let idx = self.archive.len().wrapping_sub(pos + 1);
+            if let Some(node_id) = self.restore_archived(idx) {
+                self.select_node(node_id);
+            }
         }
+    }
 
-        // if we double click, drill-down on it
-        if new == old && elapsed <= 500 && new.is_some() {
-            self.drill_down();
+    fn handle_queue_event(&mut self, evt: Event) -> bool {
+        let leaves = task::open_leaves(self);
+        let last = leaves.len().saturating_sub(1);
+        let cursor = min(self.queue.unwrap_or(0), last);
+        match evt {
+            Event::Key(Key::Up) => self.queue = Some(cursor.saturating_sub(1)),
+            Event::Key(Key::Down) => self.queue = Some(min(cursor + 1, last)),
+            Event::Key(Key::Char('\n')) => {
+                self.queue = None;
+                if let Some(&node_id) = leaves.get(cursor) {
+                    self.reveal_and_select(node_id);
+                }
+            }
+            Event::Key(Key::Ctrl('x')) => {
+                if let Some(&node_id) = leaves.get(cursor) {
+                    self.with_node_mut(node_id, |n| n.toggle_stricken());
+                }
+            }
+            Event::Key(Key::Esc) => self.queue = None,
+            _ => {
+                match self.config.map(evt) {
+                    Some(Action::ToggleQueue) => self.queue = None,
+                    Some(Action::Quit) => return false,
+                    _ => {}
+                }
+            }
         }
+        true
     }
 
-    fn release(&mut self, to: Coords) {
-        trace!("release({:?})", to);
-        if to.0 > self.dims.0 || to.1 > self.view_y + self.dims.1 {
-            warn!("release way off-screen");
-            return;
-        }
-        if let Some(from) = self.dragging_from.take() {
-            self.dragging_to.take();
-            self.move_selected(from, to);
-        }
-        trace!("leaving release");
+    // the agenda's nodes in the order they're listed
+    fn agenda_nodes(&self) -> Vec<NodeID> {
+        let now = clock::now();
+        task::agenda(self, now).into_iter().flat_map(|(_, ids)| ids).collect()
     }
 
-    pub fn assert_node_consistency(&self) {
-        // a child should be a child of at most one node
-        debug!("testing that no nodes have multiple parents");
-        let mut seen = HashSet::new();
-        let mut to_view = vec![0];
-        let mut leaf_children = vec![];
-        while let Some(node_id) = to_view.pop() {
-            self.with_node(node_id, |n| {
-                for &c in &n.children {
-                    assert!(!seen.contains(&c));
-                    seen.insert(c);
+    fn handle_agenda_event(&mut self, evt: Event) -> bool {
+        let nodes = self.agenda_nodes();
+        let last = nodes.len().saturating_sub(1);
+        let cursor = min(self.agenda.unwrap_or(0), last);
+        match evt {
+            Event::Key(Key::Up) => self.agenda = Some(cursor.saturating_sub(1)),
+            Event::Key(Key::Down) => self.agenda = Some(min(cursor + 1, last)),
+            Event::Key(Key::Char('\n')) => {
+                self.agenda = None;
+                if let Some(&node_id) = nodes.get(cursor) {
+                    self.reveal_and_select(node_id);
                 }
-                if n.children.is_empty() {
-                    leaf_children.push(node_id);
+            }
+            Event::Key(Key::Ctrl('x')) => {
+                if let Some(&node_id) = nodes.get(cursor) {
+                    self.toggle_stricken_node(node_id);
                 }
-            });
+            }
+            Event::Key(Key::Esc) => self.agenda = None,
+            _ => {
+                match self.config.map(evt) {
+                    Some(Action::ToggleAgenda) => self.agenda = None,
+                    Some(Action::Quit) => return false,
+                    _ => {}
+                }
+            }
         }
+        true
+    }
 
-        // no loops, no orphans
-        debug!("testing that 0 is the ancestor of all nodes");
-        for &node_id in self.nodes.keys() {
-            assert!(self.is_parent(0, node_id));
+    fn handle_stale_event(&mut self, evt: Event) -> bool {
+        let nodes = task::stale(self, STALE_COUNT);
+        let last = nodes.len().saturating_sub(1);
+        let cursor = min(self.stale.unwrap_or(0), last);
+        match evt {
+            Event::Key(Key::Up) => self.stale = Some(cursor.saturating_sub(1)),
+            Event::Key(Key::Down) => self.stale = Some(min(cursor + 1, last)),
+            Event::Key(Key::Char('\n')) => {
+                self.stale = None;
+                if let Some(&node_id) = nodes.get(cursor) {
+                    self.reveal_and_select(node_id);
+                }
+            }
+            Event::Key(Key::Esc) => self.stale = None,
+            _ => {
+                match self.config.map(evt) {
+                    Some(Action::ToggleStale) => self.stale = None,
+                    Some(Action::Quit) => return false,
+                    _ => {}
+                }
+            }
         }
+        true
+    }
 
-
-        debug!("testing that all arrows are existing nodes");
-        // no arrows that don't exist
-        for &(ref a, ref b) in &self.arrows {
-            assert!(self.nodes.get(a).is_some());
-            assert!(self.nodes.get(b).is_some());
-        }
+    // the cursor kept on a card, or the top of an empty column
+    fn board_cursor(&self, board: &[Vec<NodeID>]) -> (usize, usize) {
+        let (column, row) = self.board.unwrap_or((0, 0));
+        let column = min(column, board.len().saturating_sub(1));
+        let row = min(row, board.get(column).map(|c| c.len()).unwrap_or(0).saturating_sub(1));
+        (column, row)
     }
 
-    pub fn save(&self) {
-        trace!("save()");
-        self.assert_node_consistency();
-        let data = serialization::serialize_screen(self);
-        if let Some(ref path) = self.work_path {
-            let mut tmp_path = path.clone();
-            tmp_path.push_str(".tmp");
-            if let Ok(_) = remove_file(&tmp_path) {
-                warn!("removed stale tmp file");
+    // left and right carry the card under the cursor to the next column,
+    // and tab looks at the next column without moving anything
+    fn handle_board_event(&mut self, evt: Event) -> bool {
+        let columns = self.config.board.clone();
+        let board = task::board(self, &columns);
+        let (column, row) = self.board_cursor(&board);
+        let card = board[column].get(row).cloned();
+        match evt {
+            Event::Key(Key::Up) => self.board = Some((column, row.saturating_sub(1))),
+            Event::Key(Key::Down) => self.board = Some((column, row + 1)),
+            Event::Key(Key::Char('\t')) => self.board = Some(((column + 1) % board.len(), 0)),
+            Event::Key(Key::Left) |
+            Event::Key(Key::Right) => {
+                let to = if evt == Event::Key(Key::Left) {
+                    column.saturating_sub(1)
+                } else {
+                    min(column + 1, columns.len() - 1)
+                };
+                match card {
+                    Some(node_id) if to != column => {
+                        let text = self.with_node_mut(node_id, |n| {
+                                task::move_card(n, &columns, to);
+                                n.tag_text()
+                            })
+                            .unwrap();
+                        self.grapheme_cache.remove(&node_id);
+                        self.tag_db.reindex(node_id, text);
+                        // the cursor goes along with it
+                        let moved = task::board(self, &columns);
+                        let row = moved[to].iter().position(|&id| id == node_id).unwrap_or(0);
+                        self.board = Some((to, row));
+                    }
+                    Some(_) => {}
+                    None => self.board = Some((to, 0)),
+                }
+            }
+            Event::Key(Key::Char('\n')) => {
+                self.board = None;
+                if let Some(node_id) = card {
+                    self.reveal_and_select(node_id);
+                }
+            }
+            Event::Key(Key::Esc) => self.board = None,
+            _ => {
+                match self.config.map(evt) {
+                    Some(Action::ToggleBoard) => self.board = None,
+                    Some(Action::Quit) => return false,
+                    _ => {}
+                }
             }
-            let mut f = File::create(&tmp_path).unwrap();
-            f.write_all(&*data).unwrap();
-            f.sync_all().unwrap();
-            rename(tmp_path, path).unwrap();
-            info!("saved work to {}", path);
         }
+        true
     }
 
-    pub fn cleanup(&mut self) {
-        trace!("cleanup()");
-        print!("{}", cursor::Show);
-        self.stdout.take().unwrap().flush().unwrap();
+    fn open_menu(&mut self, coords: Coords) {
+        let internal_coords = self.screen_to_internal_xy(coords);
+        let node = self.lookup(internal_coords).cloned();
+        self.arrow_drag = node.map(|node_id| (node_id, None));
+        let menu = match node {
+            Some(node_id) => Menu::for_node(node_id, coords, self.drawing_arrow.is_some()),
+            None => Menu::for_space(coords, self.cut.is_some(), self.drawing_root != 0),
+        };
+        self.menu = Some(menu);
     }
 
-    pub fn start_raw_mode(&mut self) {
-        if self.stdout.is_none() {
-            self.stdout = Some(MouseTerminal::from(AlternateScreen::from(stdout()).into_raw_mode().unwrap()));
+    fn handle_menu_event(&mut self, evt: Event) -> error::Result<bool> {
+        let mut menu = self.menu.take().unwrap();
+        if self.arrow_drag.is_some() {
+            match evt {
+                Event::Mouse(MouseEvent::Hold(x, y)) => {
+                    let internal_coords = self.screen_to_internal_xy((x, y));
+                    self.arrow_drag.as_mut().unwrap().1 = Some(internal_coords);
+                    self.menu = Some(menu);
+                    return Ok(true);
+                }
+                // letting go anywhere but the source node or the menu ends
+                // the drag along with the menu
+                Event::Mouse(MouseEvent::Release(x, y)) => {
+                    let internal_coords = self.screen_to_internal_xy((x, y));
+                    let from = self.arrow_drag.unwrap().0;
+                    let on_source = self.lookup(internal_coords) == Some(&from);
+                    if on_source || menu.contains(self.dims, (x, y)) {
+                        self.arrow_drag = None;
+                        self.menu = Some(menu);
+                    } else {
+                        self.release(internal_coords);
+                    }
+                    return Ok(true);
+                }
+                _ => self.arrow_drag = None,
+            }
+        }
+        match evt {
+            Event::Key(Key::Up) => menu.up(),
+            Event::Key(Key::Down) => menu.down(),
+            Event::Key(Key::Char('\n')) => return self.choose_from_menu(menu),
+            Event::Key(Key::Esc) => return Ok(true),
+            Event::Mouse(MouseEvent::Press(MouseButton::Left, x, y)) => {
+                if let Some(idx) = menu.item_at(self.dims, (x, y)) {
+                    menu.cursor = idx;
+                    return self.choose_from_menu(menu);
+                } else if !menu.contains(self.dims, (x, y)) {
+                    return Ok(true);
+                }
+            }
+            // right clicking somewhere else opens a menu there instead
+            Event::Mouse(MouseEvent::Press(MouseButton::Right, x, y))
+                if !menu.contains(self.dims, (x, y)) => {
+                self.open_menu((x, y));
+                return Ok(true);
+            }
+            _ => {}
         }
+        self.menu = Some(menu);
+        Ok(true)
     }
 
-    pub fn occupied(&self, coords: Coords) -> bool {
-        self.lookup.contains_key(&coords)
+    // items act on the node the menu was opened on, as if it was
+    // selected and the item's key was pressed
+    fn choose_from_menu(&mut self, menu: Menu) -> error::Result<bool> {
+        let (_, action) = menu.items[menu.cursor];
+        match menu.node {
+            Some(node_id) => self.select_node(node_id),
+            None => {
+                self.unselect();
+            }
+        }
+        let cut = self.cut;
+        let ret = self.perform(action);
+        // pasting onto blank space leaves the subtree where it was clicked
+        if let (Action::YankPasteNode, Some(cut)) = (action, cut) {
+            if self.parent(cut) == Some(self.drawing_root) {
+                let coords = self.screen_to_internal_xy(menu.at);
+                let coords = self.unclaimed_anchor_coords(cut, self.drawing_root, coords);
+                self.with_node_mut_no_meta(cut, |node| node.rooted_coords = coords);
+            }
+        }
+        ret
     }
 
-    pub fn add_or_remove_arrow(&mut self) {
-        if self.drawing_arrow.is_none() {
-            self.drawing_arrow = self.selected;
+    fn draw_menu(&self) {
+        let menu = match self.menu {
+            Some(ref menu) => menu,
+            None => return,
+        };
+        // it's out of the way while an arrow is being dragged out
+        if let Some((_, Some(_))) = self.arrow_drag {
             return;
         }
-        let from = self.drawing_arrow.take().unwrap();
-        if let Some(arrow) = self.selected.map(|to| (from, to)) {
-            let (from, to) = arrow;
-            if self.nodes.get(&from).is_some() && self.nodes.get(&to).is_some() {
-                let contains = self.arrows.iter().fold(
-                    false,
-                    |acc, &(ref nl1, ref nl2)| if nl1 == &from &&
-                        nl2 == &to
-                    {
-                        true
-                    } else {
-                        false || acc
-                    },
-                );
-                if contains {
-                    self.arrows.retain(|e| e != &arrow);
-                } else {
-                    self.arrows.push(arrow);
-                }
+        let ((x, y), (w, h)) = menu.rect(self.dims);
+        if w > self.dims.0 || h > self.dims.1 {
+            return;
+        }
+        let g = self.glyphs();
+        let bar: String = (2..w).map(|_| g.horizontal).collect();
+        let mut out =
+            format!("{}{}{}{}{}", cursor::Goto(x, y), style::Reset, g.top_left, bar, g.top_right);
+        for (idx, &(label, _)) in menu.items.iter().enumerate() {
+            let label = format!(" {:1$} ", label, w as usize - 4);
+            write!(out, "{}{}", cursor::Goto(x, y + 1 + idx as u16), g.vertical).unwrap();
+            if idx == menu.cursor {
+                write!(out, "{}{}{}", style::Invert, label, style::Reset).unwrap();
+            } else {
+                out.push_str(&label);
             }
+            out.push(g.vertical);
         }
+        write!(out, "{}{}{}{}", cursor::Goto(x, y + h - 1), g.bottom_left, bar, g.bottom_right)
+            .unwrap();
+        out!("{}", out);
     }
 
-    // NB correctness depends on invariant of the leftmost element being the
-    // value in self.drawn_at
-    fn bounds_for_lookup(&self, node_id: NodeID) -> Option<(Coords, Coords)> {
-        if let Some(&left) = self.drawn_at(node_id) {
-            let mut rx = left.0;
-            while let Some(&cursor) = self.lookup((rx + 1, left.1)) {
-                if cursor == node_id {
-                    rx += 1;
-                } else {
-                    break;
+    fn open_detail(&mut self) {
+        match self.selected {
+            Some(selected_id) => {
+                self.detail = self.with_node(selected_id,
+                                             |n| Detail::new(selected_id, &n.meta, &n.color));
+            }
+            None => info!("select a node to see its details"),
+        }
+    }
+
+    // nothing is saved until enter, and a field that doesn't parse keeps
+    // the panel open with what was typed into the rest
+    fn handle_detail_event(&mut self, evt: Event) -> bool {
+        let mut detail = self.detail.take().unwrap();
+        match evt {
+            Event::Key(Key::Up) => detail.up(),
+            Event::Key(Key::Down) |
+            Event::Key(Key::Char('\t')) => detail.down(),
+            Event::Key(Key::Backspace) => detail.pop(),
+            Event::Key(Key::Char('\n')) => {
+                if let Some(edits) = detail.edits() {
+                    let text = self.with_node_mut(detail.node, |n| {
+                        edits.apply(n);
+                        n.tag_text()
+                    });
+                    if let Some(text) = text {
+                        self.tag_db.reindex(detail.node, text);
+                    }
+                    return true;
+                }
+            }
+            Event::Key(Key::Esc) => return true,
+            Event::Key(Key::Char(c)) => detail.push(c),
+            _ => {
+                match self.config.map(evt) {
+                    Some(Action::EditDetails) => return true,
+                    Some(Action::Quit) => return false,
+                    _ => {}
                 }
             }
-            // intentionally add 1 to the right side to prevent cluttering
-            let right = (rx + 1, left.1);
-            Some((left, right))
-        } else {
-            None
         }
+        self.detail = Some(detail);
+        true
     }
 
-    // *
-    // *
-    // *┌──┐
-    // *│  Drawing Functionality
-    // *└──────────────>
-    // *
-    // *
+    fn draw_detail(&self) {
+        let detail = match self.detail {
+            Some(ref detail) => detail,
+            None => return,
+        };
+        let ((x, y), (w, h)) = detail.rect(self.dims);
+        if (w as usize) < detail::LABEL_WIDTH + 8 || h > self.dims.1 {
+            return;
+        }
+        let g = self.glyphs();
+        let inner = w as usize - 4;
+        let room = inner - detail::LABEL_WIDTH;
+        // the end of what doesn't fit is kept, since that's where typing goes
+        let fit = |text: &str, width: usize| {
+            let skip = text.chars().count().saturating_sub(width);
+            format!("{:1$}", text.chars().skip(skip).collect::<String>(), width)
+        };
 
-    pub fn draw(&mut self) {
-        trace!("draw()");
+        let mut rows = vec![];
+        for (idx, &(field, ref text)) in detail.fields.iter().enumerate() {
+            let label = format!("{:1$}", field.label(), detail::LABEL_WIDTH);
+            if idx == detail.cursor {
+                let value = fit(&format!("{}{}", text, g.cursor), room);
+                rows.push(format!("{}{}{}{}", label, style::Invert, value, style::Reset));
+            } else {
+                rows.push(format!("{}{}", label, fit(text, room)));
+            }
+            if let Some(ref error) = detail.errors[idx] {
+                rows.push(format!("{}{}{}{}",
+                                  " ".repeat(detail::LABEL_WIDTH),
+                                  color::Fg(color::Red),
+                                  fit(error, room),
+                                  style::Reset));
+            }
+        }
+        rows.push(fit(detail::HINT, inner));
+
+        let bar: String = (2..w).map(|_| g.horizontal).collect();
+        let mut out =
+            format!("{}{}{}{}{}", cursor::Goto(x, y), style::Reset, g.top_left, bar, g.top_right);
+        for (idx, row) in rows.iter().enumerate() {
+            let at = cursor::Goto(x, y + 1 + idx as u16);
+            write!(out, "{}{} {} {}", at, g.vertical, row, g.vertical).unwrap();
+        }
+        write!(out, "{}{}{}{}", cursor::Goto(x, y + h - 1), g.bottom_left, bar, g.bottom_right)
+            .unwrap();
+        out!("{}", out);
+    }
 
-        // let before = time::get_time();
+    // true if the event was for the completion. typing and erasing go on
+    // to the node and change what's offered, and anything else closes it.
+    fn handle_completion_event(&mut self, evt: &Event) -> bool {
+        let mut completion = self.completion.take().unwrap();
+        match *evt {
+            Event::Key(Key::Up) => completion.up(),
+            Event::Key(Key::Down) => completion.down(),
+            Event::Key(Key::Char('\t')) |
+            Event::Key(Key::Char('\n')) => {
+                let node_id = completion.node;
+                let text = self.with_node_mut(node_id, |n| {
+                    n.content = completion.accept(&n.content);
+                    n.tag_text()
+                });
+                if let Some(text) = text {
+                    self.grapheme_cache.remove(&node_id);
+                    self.tag_db.reindex(node_id, text);
+                }
+                return true;
+            }
+            Event::Key(Key::Esc) => return true,
+            Event::Key(Key::Char(_)) |
+            Event::Key(Key::Backspace) => {
+                self.completion = Some(completion);
+                return false;
+            }
+            _ => return false,
+        }
+        self.completion = Some(completion);
+        true
+    }
 
-        // clean up before a fresh drawing
-        self.ephemeral_max_id = std::u64::MAX;
-        self.ephemeral_nodes.clear();
-        self.lookup.clear();
-        self.drawn_at.clear();
-        self.lowest_drawn = 0;
-        print!("{}", clear::All);
+    fn draw_completion(&self) {
+        let completion = match self.completion {
+            Some(ref completion) => completion,
+            None => return,
+        };
+        let at = match self.drawn_at(completion.node)
+            .and_then(|&coords| self.internal_to_screen_xy(coords)) {
+            Some(at) => at,
+            None => return,
+        };
+        let ((x, y), (w, h)) = completion.rect(at, self.dims);
+        if w > self.dims.0 || h > self.dims.1 {
+            return;
+        }
+        let g = self.glyphs();
+        let bar: String = (2..w).map(|_| g.horizontal).collect();
+        let mut out =
+            format!("{}{}{}{}{}", cursor::Goto(x, y), style::Reset, g.top_left, bar, g.top_right);
+        for (idx, tag) in completion.items.iter().enumerate() {
+            let label = format!(" #{:1$} ", tag, w as usize - 5);
+            write!(out, "{}{}", cursor::Goto(x, y + 1 + idx as u16), g.vertical).unwrap();
+            if idx == completion.cursor {
+                write!(out, "{}{}{}", style::Invert, label, style::Reset).unwrap();
+            } else {
+                out.push_str(&label);
+            }
+            out.push(g.vertical);
+        }
+        write!(out, "{}{}{}{}", cursor::Goto(x, y + h - 1), g.bottom_left, bar, g.bottom_right)
+            .unwrap();
+        out!("{}", out);
+    }
 
-        // print visible nodes
-        self.draw_children_of_root();
+    // rows left over for the map after the header and the listing below it
+    fn gps_map_dims(&self) -> (usize, usize) {
+        (self.dims.0 as usize, self.dims.1.saturating_sub(7) as usize)
+    }
 
-        // TODO figure out why header doesn't get shown
-        // when a root node is NOT drawn at 1,1
-        // (this only happens when draw_header() is above
-        // the call to draw_children_of_root()...
-        self.draw_header();
+    // locations of every node, excluding the root
+    fn gps_points(&self) -> (Vec<NodeID>, Vec<(f32, f32)>) {
+        let mut ids: Vec<NodeID> = self.nodes.keys().cloned().filter(|&id| id != 0).collect();
+        ids.sort();
+        let points = ids.iter().map(|id| self.nodes[id].meta.gps).collect();
+        (ids, points)
+    }
 
-        // print logs
-        if self.show_logs && self.dims.0 > 4 && self.dims.1 > 7 {
-            let mut sep = format!(
-                "{}{}logs{}",
-                cursor::Goto(0, self.dims.1 - 6),
-                style::Invert,
-                style::Reset
-            );
-            for _ in 0..self.dims.0 - 4 {
-                sep.push('█');
+    fn handle_gps_event(&mut self, evt: Event) -> bool {
+        let (width, height) = self.gps_map_dims();
+        let (ids, points) = self.gps_points();
+        let bins = plot::gps_bins(&points, width, height);
+        let last = bins.len().saturating_sub(1);
+        let current = min(self.gps_view.unwrap_or(0), last);
+        match evt {
+            Event::Key(Key::Up) |
+            Event::Key(Key::Left) => self.gps_view = Some(current.saturating_sub(1)),
+            Event::Key(Key::Down) |
+            Event::Key(Key::Right) => self.gps_view = Some(min(current + 1, last)),
+            Event::Key(Key::Char('\n')) => {
+                self.gps_view = None;
+                if let Some(members) = bins.values().nth(current) {
+                    self.reveal_and_select(ids[members[0]]);
+                }
             }
-            println!("{}", sep);
-            {
-                let logs = logging::read_logs();
-                for msg in logs.iter().rev() {
-                    let line_width = min(msg.len(), self.dims.0 as usize);
-                    println!("\r{}", msg[..line_width as usize].to_owned());
+            Event::Key(Key::Esc) => self.gps_view = None,
+            _ => {
+                match self.config.map(evt) {
+                    Some(Action::ToggleGpsMap) => self.gps_view = None,
+                    Some(Action::Quit) => return false,
+                    _ => {}
                 }
             }
         }
+        true
+    }
 
-        // print arrows
-        for &(ref from, ref to) in &self.arrows {
-            let (path, (direction1, direction2)) = self.path_between_nodes(*from, *to);
-            self.draw_path(path, direction1, direction2);
+    fn draw_gps_map(&mut self) {
+        trace!("draw_gps_map()");
+        let (width, height) = self.gps_map_dims();
+        let (ids, points) = self.gps_points();
+        let bins = plot::gps_bins(&points, width, height);
+        let current = min(self.gps_view.unwrap_or(0), bins.len().saturating_sub(1));
+        self.gps_view = Some(current);
+
+        let located: usize = bins.values().map(|members| members.len()).sum();
+        let mut out = format!(
+            "{}{}locations: {} nodes in {} places{}",
+            cursor::Goto(1, 1),
+            style::Invert,
+            located,
+            bins.len(),
+            style::Reset
+        );
+        if height == 0 {
+            out!("{}", out);
+            return;
         }
 
-        // conditionally print drag dest arrow
-        if let Some(from) = self.dragging_from {
-            // we only care if we're dragging a node
-            if let Some(from_node) = self.lookup(from) {
-                // we're either dragging to a new parent node, or to a new space
-                if let Some(to) = self.dragging_to {
-                    if let Some(to_node) = self.lookup(to) {
-                        let (path, (direction1, direction2)) =
-                            self.path_between_nodes(*from_node, *to_node);
-                        self.draw_path(path, direction1, direction2);
-                    } else {
-                        let (path, (direction1, direction2)) =
-                            self.path_from_node_to_point(*from_node, to);
-                        self.draw_path(path, direction1, direction2);
-                    }
-                } else {
-                    warn!("dragging_from set, but NOT dragging_to");
-                }
+        let map = plot::gps_map(&bins, width, height, self.glyphs());
+        for (row, line) in map.iter().enumerate() {
+            write!(out, "{}{}", cursor::Goto(1, row as u16 + 2), line).unwrap();
+        }
+
+        if let Some((&(row, col), members)) = bins.iter().nth(current) {
+            let marker = map[row].chars().nth(col).unwrap();
+            write!(
+                out,
+                "{}{}{}{}",
+                cursor::Goto(col as u16 + 1, row as u16 + 2),
+                style::Invert,
+                marker,
+                style::Reset
+            ).unwrap();
+            for (i, &member) in members.iter().take(5).enumerate() {
+                let content = self.with_node(ids[member], |n| n.content.clone()).unwrap();
+                write!(
+                    out,
+                    "{}{}",
+                    cursor::Goto(1, (height + 3 + i) as u16),
+                    truncate_left(&content, width, self.glyphs())
+                ).unwrap();
             }
         }
+        out!("{}", out);
+    }
 
-        // show scrollbar if we've drawn anything below the bottom of the screen
-        if self.lowest_drawn > self.dims.1 {
-            self.draw_scrollbar();
+    fn copy_subtree(&mut self) {
+        trace!("copy_subtree()");
+        if let Some(selected_id) = self.selected {
+            let text = export::outline(self, selected_id, OutlineStyle::Text);
+            if clipboard::copy_with_tool(&text) {
+                info!("copied {} lines", text.lines().count());
+                return;
+            }
+            let (seq, truncated) = clipboard::osc52(&text);
+            if truncated {
+                warn!(
+                    "subtree is too large for the terminal clipboard, copied the first {} bytes",
+                    clipboard::OSC52_LIMIT / 4 * 3
+                );
+            } else {
+                info!("copied {} lines", text.lines().count());
+            }
+            out!("{}", seq);
         }
+    }
 
-        print!("{}", cursor::Hide);
-        self.flush();
+    // writes an export next to the work file, as <work_path>.<extension>
+    // with a node selected, asks first whether to export just its subtree
+    fn export_prompt(&mut self, format: ExportFormat) -> error::Result<()> {
+        match self.selected {
+            Some(selected_id) => self.modal = Some(Modal::Export(format, selected_id)),
+            None => self.export_file(format, None)?,
+        }
+        Ok(())
+    }
 
-        // let after = time::get_time();
+    fn export_file(&self, format: ExportFormat, root: Option<NodeID>) -> error::Result<()> {
+        trace!("export_file({:?}, {:?})", format, root);
+        let extension = match format {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Text => "txt",
+            ExportFormat::Html => "html",
+            ExportFormat::Ical => "ics",
+            ExportFormat::Csv => "csv",
+        };
+        let path = match self.work_path {
+            Some(ref work_path) => format!("{}.{}", work_path, extension),
+            None => {
+                warn!("no work file to export next to");
+                return Ok(());
+            }
+        };
+        self.write_file(&path, |s| export::export_as(s, format, root).into_bytes())
+    }
 
-        // debug!("draw time: {}", after - before);
+    fn write_file<F>(&self, path: &str, contents: F) -> error::Result<()>
+        where F: Fn(&Screen) -> Vec<u8>
+    {
+        File::create(path)
+            .and_then(|mut f| f.write_all(&contents(self)))
+            .map_err(error::Error::file(path))?;
+        info!("wrote {}", path);
+        Ok(())
     }
 
-    fn draw_scrollbar(&self) {
-        let bar_height = max(self.dims.1, 1) - 1;
-        let normalized_lowest = max(self.lowest_drawn, 1) as f64;
-        let fraction_viewable = self.dims.1 as f64 / normalized_lowest;
-        let shade_start_fraction = self.view_y as f64 / normalized_lowest;
+    fn open_url(&mut self) {
+        trace!("open_url()");
+        let selected_id = match self.selected {
+            Some(selected_id) => selected_id,
+            None => return,
+        };
+        let urls = self.with_node(selected_id, |n| url::find_urls(&n.content)).unwrap();
+        if urls.is_empty() {
+            info!("no link in selected node");
+            return;
+        }
+        // pressing again opens the next link in the node
+        let idx = match self.last_url {
+            Some((node_id, last)) if node_id == selected_id => (last + 1) % urls.len(),
+            _ => 0,
+        };
+        self.last_url = Some((selected_id, idx));
 
-        let shade_amount = (bar_height as f64 * fraction_viewable) as usize;
-        let shade_start = (bar_height as f64 * shade_start_fraction) as usize;
-        let shade_end = shade_start + shade_amount;
+        info!("opening {}", urls[idx]);
+        if let Err(e) = self.run_opener(&urls[idx]) {
+            error!("failed to run {} {}: {}", self.url_opener, urls[idx], e);
+        }
+    }
 
-        for (i, y) in (2..bar_height + 2).enumerate() {
-            if i >= shade_start && i < shade_end {
-                print!("{}┃", cursor::Goto(self.dims.0, y));
-            } else {
-                print!("{}│", cursor::Goto(self.dims.0, y));
+    // hands target to the opener without waiting for it
+    fn run_opener(&self, target: &str) -> std::io::Result<()> {
+        let mut child = process::Command::new(&self.url_opener)
+            .arg(target)
+            .stdin(process::Stdio::null())
+            .stdout(process::Stdio::null())
+            .stderr(process::Stdio::null())
+            .spawn()?;
+        thread::spawn(move || child.wait());
+        Ok(())
+    }
+
+    fn attachment_prompt(&mut self) {
+        trace!("attachment_prompt()");
+        let selected_id = match self.selected {
+            Some(selected_id) => selected_id,
+            None => return,
+        };
+        if let Some(path) = self.path_prompt("attach file: ") {
+            self.attach(selected_id, &path);
+        }
+    }
+
+    // paths are kept whole, so they mean the same file wherever void is
+    // started from. one that isn't there yet is attached all the same.
+    fn attach(&mut self, node_id: NodeID, path: &str) {
+        let path = command::expand_home(path.trim());
+        let path = match env::current_dir() {
+            Ok(ref cwd) if Path::new(&path).is_relative() => {
+                cwd.join(&path).to_string_lossy().into_owned()
             }
+            _ => path,
+        };
+        if !Path::new(&path).exists() {
+            warn!("{} doesn't exist yet", path);
+        }
+        let attached = self.with_node(node_id, |n| n.meta.attachments.contains(&path));
+        if attached == Some(false) {
+            self.with_node_mut(node_id, |n| n.meta.attachments.push(path.clone()));
+            self.grapheme_cache.remove(&node_id);
         }
     }
 
-    fn draw_children_of_root(&mut self) {
-        trace!("draw_children_of_root()");
-        let anchors = self.with_node(self.drawing_root, |n| n.children.clone())
-            .unwrap();
-        trace!("drawing children of root({}): {:?}", self.drawing_root, anchors);
-        for child_id in anchors {
-            let child_coords = self.with_node(child_id, |n| n.rooted_coords).unwrap();
-            let child_color = self.with_node(child_id, |n| n.color.clone()).unwrap();
-            let hide_stricken = self.with_node(self.drawing_root, |n| n.hide_stricken)
-                .unwrap();
-            self.draw_node(
-                child_id,
-                "".to_owned(),
-                child_coords,
-                false,
-                hide_stricken,
-                child_color,
-            );
+    fn open_attachment(&mut self) {
+        trace!("open_attachment()");
+        let selected_id = match self.selected {
+            Some(selected_id) => selected_id,
+            None => return,
+        };
+        let path = match self.with_node(selected_id, |n| n.meta.attachments.first().cloned()) {
+            Some(Some(path)) => path,
+            _ => {
+                info!("no file attached to selected node");
+                return;
+            }
+        };
+        if !Path::new(&path).exists() {
+            warn!("can't open {}: it isn't there", path);
+            return;
+        }
+        info!("opening {}", path);
+        if let Err(e) = self.run_opener(&path) {
+            warn!("failed to run {} {}: {}", self.url_opener, path, e);
         }
     }
 
-    // recursively draw node and children, returning how many have been drawn
-    fn draw_node(
-        &mut self,
-        node_id: NodeID,
-        prefix: String,
-        internal_coords: Coords,
-        last: bool,
-        hide_stricken: bool,
-        color: String,
-    ) -> usize {
-        trace!("draw_node({})", node_id);
-        let mut ephemeral = false;
-        let raw_node = self.nodes
-            .get(&node_id)
-            .or_else(|| {
-                ephemeral = true;
-                self.ephemeral_nodes.get(&node_id)
-            })
-            .cloned()
-            .unwrap();
-        let node = if raw_node.selected {
-            let mut formatted = self.format_node(&raw_node);
-            formatted.content = raw_node.content;
-            formatted
+    // whether any of a node's attachments can't be found
+    fn broken_attachment(&self, node: &Node) -> bool {
+        node.meta.attachments.iter().any(|path| !Path::new(path).exists())
+    }
+
+    fn pick_next(&mut self) {
+        trace!("pick_next()");
+        // pressing again while the last pick is still selected re-rolls
+        let rerolling = self.selected.is_some() &&
+            self.selected == self.recent_picks.last().cloned();
+        if !rerolling {
+            self.recent_picks.clear();
+            self.pick_tag = match self.prompt("pick from tag (blank for any): ") {
+                Ok(Some(tag)) => {
+                    let tag = tag.trim().trim_left_matches('#');
+                    if tag.is_empty() { None } else { Some(tag.to_owned()) }
+                }
+                Ok(None) => return,
+                Err(_) => None,
+            };
+        }
+
+        let picked = task::pick_next(
+            self,
+            self.pick_tag.as_ref().map(String::as_str),
+            &self.recent_picks,
+            &mut rand::thread_rng(),
+        );
+        if let Some(node_id) = picked {
+            let content = self.with_node(node_id, |n| n.content.clone()).unwrap();
+            info!("next action: {}", content);
+            self.recent_picks.push(node_id);
+            if self.recent_picks.len() > task::RECENT_PICKS {
+                self.recent_picks.remove(0);
+            }
+            self.reveal_and_select(node_id);
         } else {
-            self.format_node(&raw_node)
-        };
-        if node.stricken && hide_stricken {
-            return 0;
+            info!("no open tasks to pick from");
         }
+    }
 
-        let reset = &*format!("{}", color::Fg(color::Reset));
-        let mut pre_meta = String::new();
-        let mut buf = String::new();
+    fn draw_queue(&mut self) {
+        trace!("draw_queue()");
+        let leaves = task::open_leaves(self);
+        let cursor = min(self.queue.unwrap_or(0), leaves.len().saturating_sub(1));
+        self.queue = Some(cursor);
 
-        // only actually print it if we're in-view
-        if let Some((x, y)) = self.internal_to_screen_xy(internal_coords) {
-            write!(pre_meta, "{}{}", cursor::Goto(x, y), color).unwrap();
-            if node.selected {
-                write!(&mut pre_meta, "{}", style::Invert).unwrap();
+        let now = clock::now();
+        let lines: Vec<(Option<NodeID>, String)> =
+            leaves.iter().map(|&node_id| (Some(node_id), self.list_line(node_id, now))).collect();
+        self.draw_list(&format!("queue: {} open", leaves.len()), &lines, cursor);
+    }
+
+    // the due nodes under a heading for each group
+    fn draw_agenda(&mut self) {
+        trace!("draw_agenda()");
+        let now = clock::now();
+        let groups = task::agenda(self, now);
+        let count = groups.iter().map(|group| group.1.len()).sum::<usize>();
+        let cursor = min(self.agenda.unwrap_or(0), count.saturating_sub(1));
+        self.agenda = Some(cursor);
+
+        let mut lines = vec![];
+        let mut cursor_line = 0;
+        let mut listed = 0;
+        for (group, ids) in groups {
+            if !lines.is_empty() {
+                lines.push((None, String::new()));
             }
-            write!(&mut buf, "{}", pre_meta).unwrap();
-            write!(&mut buf, "{}", prefix).unwrap();
-            if prefix != "" {
-                // only anchor will have blank prefix
-                if last {
-                    write!(&mut buf, "└─").unwrap();
-                } else {
-                    write!(&mut buf, "├─").unwrap();
+            lines.push((None, group.label().to_owned()));
+            for node_id in ids {
+                if listed == cursor {
+                    cursor_line = lines.len();
                 }
+                listed += 1;
+                lines.push((Some(node_id), self.list_line(node_id, now)));
             }
-            if node.stricken {
-                write!(&mut buf, "☠").unwrap();
-            } else if node.collapsed {
-                write!(&mut buf, "⊞").unwrap();
-            } else if node.hide_stricken {
-                write!(&mut buf, "⚔").unwrap();
-            } else {
-                write!(&mut buf, " ").unwrap();
-            }
-            // keep color for selected & tree root Fg
-            if !node.selected && prefix != "" {
-                write!(&mut buf, "{}", reset).unwrap();
-            }
-
-            write!(&mut buf, "{}", node.content).unwrap();
-
-            let max_width = (max(self.dims.0, 1 + x) - 1 - x) as usize;
-            let visible_graphemes = self.grapheme_cache.get(&node.id).cloned().unwrap_or_else(
-                || {
-                    let visible = buf.replace(reset, "").replace(&*pre_meta, "");
-                    let vg = UnicodeSegmentation::graphemes(&*visible, true).count();
-                    self.grapheme_cache.insert(node.id, vg.clone());
-                    vg
-                },
-            );
-            if visible_graphemes > max_width {
-                let buf_clone = buf.clone();
-                let chars = buf_clone.chars();
-                let width = chars.clone().count();
-                let new_size = width - (visible_graphemes - max_width);
-                buf = chars.take(new_size).collect();
-                buf.push('…');
-            }
-
-            print!("{}{}", buf, style::Reset);
         }
+        self.draw_list(&format!("agenda: {} due", count), &lines, cursor_line);
+    }
 
-        let visible_graphemes = self.grapheme_cache.get(&node.id).cloned().unwrap_or_else(
-            || {
-                let visible = buf.replace(reset, "").replace(&*pre_meta, "");
-                let vg = UnicodeSegmentation::graphemes(&*visible, true).count();
-                self.grapheme_cache.insert(node.id, vg.clone());
-                vg
-            },
-        );
+    // the nodes left alone longest, each after how long it's been
+    fn draw_stale(&mut self) {
+        trace!("draw_stale()");
+        let nodes = task::stale(self, STALE_COUNT);
+        let cursor = min(self.stale.unwrap_or(0), nodes.len().saturating_sub(1));
+        self.stale = Some(cursor);
+
+        let now = clock::now();
+        let lines: Vec<(Option<NodeID>, String)> = nodes.iter()
+            .map(|&node_id| {
+                let touched = self.with_node(node_id, |n| n.meta.touched()).unwrap();
+                let age = humanize_age(now, touched);
+                (Some(node_id), format!("{:>4} {}", age, self.list_line(node_id, now)))
+            })
+            .collect();
+        self.draw_list(&format!("stale: {} untouched longest", nodes.len()), &lines, cursor);
+    }
 
-        self.drawn_at.insert(node_id, internal_coords);
-        for x in (internal_coords.0..(internal_coords.0 + visible_graphemes as u16)).rev() {
-            trace!("inserting {:?} at {:?}", node_id, internal_coords);
-            self.lookup.insert((x, internal_coords.1), node_id);
+    // the path to a node, followed by when it's due
+    fn list_line(&self, node_id: NodeID, now: u64) -> String {
+        let names: Vec<String> = self.lineage(node_id)
+            .into_iter()
+            .filter(|&id| id != 0)
+            .filter_map(|id| self.with_node(id, |n| n.content.clone()))
+            .collect();
+        let mut line = names.join(&format!(" {} ", self.glyphs().path_separator));
+        if let Some(due) = self.with_node(node_id, |n| n.meta.due).unwrap() {
+            if due <= now {
+                write!(line, " (overdue {})", format_age(now, due)).unwrap();
+            } else {
+                write!(line, " (due in {})", format_age(due, now)).unwrap();
+            }
         }
+        line
+    }
 
-        if internal_coords.1 > self.lowest_drawn {
-            self.lowest_drawn = internal_coords.1;
-        }
-        let mut prefix = prefix;
-        if last {
-            prefix.push_str("   ");
-        } else if prefix == "" {
-            prefix.push_str(" ");
-        } else {
-            prefix.push_str("│  ");
-        }
-        let prefix = prefix;
-
-        let mut drawn = 1;
-        if !node.collapsed {
-            let n_children = node.children.len();
-            for (n, &child) in node.children.iter().enumerate() {
-                let last = n + 1 == n_children;
-                let child_coords = (internal_coords.0, internal_coords.1 + drawn as u16);
-                let child_drew = self.draw_node(
-                    child,
-                    prefix.clone(),
-                    child_coords,
-                    last,
-                    node.hide_stricken,
-                    color.clone(),
-                );
-                drawn += child_drew;
+    // a full screen list below a title, scrolled to keep the cursor line
+    // in view. lines without a node are headings.
+    fn draw_list(&self, title: &str, lines: &[(Option<NodeID>, String)], cursor: usize) {
+        let width = self.dims.0 as usize;
+        let height = max(self.dims.1, 2) as usize - 1;
+        let offset = (cursor + 1).saturating_sub(height);
+
+        let mut out = format!("{}{}{}{}", cursor::Goto(1, 1), style::Invert, title, style::Reset);
+        for (idx, line) in lines.iter().enumerate().skip(offset).take(height) {
+            let (node, line) = (line.0, truncate_left(&line.1, width, self.glyphs()));
+            write!(out, "{}", cursor::Goto(1, (idx - offset) as u16 + 2)).unwrap();
+            if node.is_none() {
+                write!(out, "{}{}{}", style::Bold, line, style::Reset).unwrap();
+            } else if idx == cursor {
+                write!(out, "{}{}{}", style::Invert, line, style::Reset).unwrap();
+            } else {
+                out.push_str(&line);
             }
         }
-        drawn
+        out!("{}", out);
     }
 
-    fn draw_path(&self, internal_path: Vec<Coords>, start_dir: Dir, dest_dir: Dir) {
-        let path: Vec<_> = internal_path
-            .iter()
-            .filter_map(|&c| self.internal_to_screen_xy(c))
-            .collect();
-        trace!("draw_path({:?}, {:?}, {:?})", path, start_dir, dest_dir);
-        print!("{}", random_fg_color());
-        if path.len() == 1 {
-            print!("{} ↺", cursor::Goto(path[0].0, path[0].1))
-        } else if path.len() > 1 {
-            let first = if path[1].1 > path[0].1 {
-                match start_dir {
-                    Dir::R => '┐',
-                    Dir::L => '┌',
-                }
-            } else if path[1].1 < path[0].1 {
-                match start_dir {
-                    Dir::R => '┘',
-                    Dir::L => '└',
-                }
+    // a column for each of the board's tags, with each card's path below
+    // it
+    fn draw_board(&mut self) {
+        trace!("draw_board()");
+        let columns = self.config.board.clone();
+        let board = task::board(self, &columns);
+        let cursor = self.board_cursor(&board);
+        self.board = Some(cursor);
+
+        let width = self.dims.0 as usize / columns.len();
+        // a cell between columns
+        let inner = max(width, 2) - 1;
+        let cards = (max(self.dims.1, 3) as usize - 1) / 2;
+        let separator = format!(" {} ", self.glyphs().path_separator);
+        let mut out = String::new();
+        for (column, (tag, ids)) in columns.iter().zip(&board).enumerate() {
+            let x = (column * width) as u16 + 1;
+            let header = format!("#{} ({})", tag, ids.len());
+            let header = truncate_right(&header, inner, self.glyphs());
+            write!(out, "{}{}", cursor::Goto(x, 1), style::Bold).unwrap();
+            if column == cursor.0 {
+                write!(out, "{}{}", style::Invert, header).unwrap();
             } else {
-                '─'
-            };
+                out.push_str(&header);
+            }
+            write!(out, "{}", style::Reset).unwrap();
 
-            print!("{}{}", cursor::Goto(path[0].0, path[0].1), first);
-            for items in path.windows(3) {
-                let (p, this, n) = (items[0], items[1], items[2]);
-                let c = if p.0 == n.0 {
-                    '│'
-                } else if p.1 == n.1 {
-                    '─'
-                } else if (this.1 < p.1 && this.0 < n.0) || (this.0 < p.0 && this.1 < n.1) {
-                    '┌' // up+right or left+down
-                } else if (this.0 > p.0 && this.1 > n.1) || (this.1 > p.1 && this.0 > n.0) {
-                    '┘' // right+up or down+left
-                } else if (this.0 > p.0 && this.1 < n.1) || (this.1 < p.1 && this.0 > n.0) {
-                    '┐' // right+down or up+left
+            let offset = if column == cursor.0 {
+                (cursor.1 + 1).saturating_sub(cards)
+            } else {
+                0
+            };
+            for (idx, &node_id) in ids.iter().enumerate().skip(offset).take(cards) {
+                let y = ((idx - offset) * 2) as u16 + 2;
+                let lineage = self.lineage(node_id);
+                let content = self.with_node(node_id, |n| n.content.clone()).unwrap();
+                let content = truncate_right(&printable(&content, self.glyphs()),
+                                             inner,
+                                             self.glyphs());
+                write!(out, "{}", cursor::Goto(x, y)).unwrap();
+                if (column, idx) == cursor {
+                    write!(out, "{}{}{}", style::Invert, content, style::Reset).unwrap();
                 } else {
-                    '└' // down+right or left+up
-                };
-
-                print!("{}{}", cursor::Goto(this.0, this.1), c)
+                    out.push_str(&content);
+                }
+                let path: Vec<String> = lineage[..lineage.len() - 1]
+                    .iter()
+                    .filter(|&&id| id != 0)
+                    .filter_map(|&id| self.with_node(id, |n| printable(&n.content, self.glyphs())))
+                    .collect();
+                let path = truncate_left(&path.join(&separator), inner, self.glyphs());
+                write!(out, "{}{}{}{}", cursor::Goto(x, y + 1), DIM, path, style::Reset).unwrap();
             }
-            let (end_x, end_y) = (path[path.len() - 1].0, path[path.len() - 1].1);
-            let end_char = match dest_dir {
-                Dir::L => '>',
-                Dir::R => '<',
-            };
-            print!("{}{}", cursor::Goto(end_x, end_y), end_char);
         }
-        print!("{}", color::Fg(color::Reset));
+        out!("{}", out);
     }
 
-    fn draw_header(&self) {
-        trace!("draw_header()");
-        let mut header_text = self.with_node(self.drawing_root, |node| node.content.clone())
-            .unwrap();
+    pub fn should_auto_arrange(&self) -> bool {
+        self.with_node(self.drawing_root, |n| n.auto_arrange)
+            .unwrap()
+    }
 
-        if self.should_auto_arrange() {
-            header_text.push_str(" [auto-arrange] ");
+    fn toggle_auto_arrange(&mut self) {
+        let root = self.drawing_root;
+        self.with_node_mut_no_meta(root, |mut n| n.auto_arrange = !n.auto_arrange)
+            .unwrap()
+    }
+
+    // only fails when the terminal can't be taken over to begin with
+    pub fn run(&mut self) -> error::Result<()> {
+        self.start_raw_mode()?;
+        // a panic in the main thread ends void. the shell's screen comes
+        // back first, or the message would be lost with the alternate one.
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if thread::current().name() == Some("main") {
+                print!("{}{}{}", input::DISABLE_MOUSE, cursor::Show, ToMainScreen);
+                let _ = stdout().flush();
+            }
+            default_hook(info);
+        }));
+        if !self.capabilities.mouse {
+            info!("no mouse here: add anchors with place_anchor (M-N) or :anchor <column> <row>");
         }
+        self.refresh_size();
+        self.notify_missed(clock::now());
+        self.draw();
+        let tick = Duration::from_millis(input::TICK_MS);
+        let reader = input::Reader::stdin();
+        loop {
+            // input is followed by a tick, and a tick comes on its own when
+            // there's none, so countdowns keep moving
+            let (inputs, mut should_break) = match reader.wait(tick) {
+                Some(inputs) => (inputs, false),
+                None => (vec![], true),
+            };
 
-        let (plot, finished_today) = self.last_week_of_done_tasks();
-        let plot_line = format!("│{}│({} today)", plot, finished_today);
+            self.refresh_size();
 
-        header_text.push_str(&*plot_line);
+            if !self.feed(inputs) {
+                should_break = true;
+            }
 
-        if self.dims.0 > header_text.len() as u16 && self.dims.1 > 1 {
-            let mut sep = format!(
-                "{}{}{}{}",
-                cursor::Goto(0, 1),
-                style::Invert,
-                header_text,
-                style::Reset
-            );
-            let text_len = header_text.chars().count();
-            for _ in 0..(max(self.dims.0 as usize, text_len) - text_len) {
-                sep.push('█');
+            if should_break {
+                // stop reading before the terminal goes back to normal
+                drop(reader);
+                self.leave_terminal();
+                // an empty node left selected on the way out isn't kept
+                if self.selected.into_iter().any(|id| self.is_blank(id)) {
+                    self.unselect();
+                }
+                if !self.discard_changes {
+                    self.save_all();
+                }
+                // every open file, not only the one in view
+                let tabs = self.tabs.iter().filter_map(|tab| tab.work_path.as_ref());
+                for path in self.work_path.iter().chain(tabs) {
+                    run_hook(&self.config, Hook::Exit, path);
+                }
+                break;
             }
-            println!("{}", sep);
         }
+        trace!("leaving event loop");
+        out!("{}{}", cursor::Goto(1, 1), clear::All);
+        Ok(())
     }
 
-    fn path_from_node_to_point(&self, start: NodeID, to: Coords) -> (Vec<Coords>, (Dir, Dir)) {
-        // TODO this is mostly copypasta from path_between_nodes, DRY
-        trace!("getting path between node {} and point {:?}", start, to);
-        let startbounds = self.bounds_for_lookup(start);
-        if startbounds.is_none() {
-            trace!("path_from_node_to_point exiting early, point not drawn");
-            return (vec![], (Dir::R, Dir::R));
+    // a batch of input from the terminal, then a fresh frame. false once
+    // it's been asked to quit.
+    pub fn feed(&mut self, inputs: Vec<Input>) -> bool {
+        let mut keep_going = true;
+        let mut handled = false;
+        for input in inputs {
+            handled |= input != Input::Tick;
+            if !self.handle_input(input) {
+                keep_going = false;
+                break;
+            }
         }
-        let (s1, s2) = startbounds.unwrap();
-        let init = (self.path(s2, to), (Dir::R, Dir::R));
-        let paths = vec![(self.path(s1, to), (Dir::L, Dir::R))];
-        paths.into_iter().fold(
-            init,
-            |(spath, sdirs), (path, dirs)| if path.len() <
-                spath.len()
-            {
-                (path, dirs)
-            } else {
-                (spath, sdirs)
-            },
-        )
+        if handled {
+            self.record(Entry::Frame);
+        }
+        self.settle();
+        keep_going
     }
 
-    fn path_between_nodes(&self, start: NodeID, to: NodeID) -> (Vec<Coords>, (Dir, Dir)) {
-        trace!("getting path between nodes {} and {}", start, to);
-        let startbounds = self.bounds_for_lookup(start);
-        let tobounds = self.bounds_for_lookup(to);
-        if startbounds.is_none() || tobounds.is_none() {
-            trace!("path_between_nodes exiting early, point not drawn");
-            return (vec![], (Dir::R, Dir::R));
+    fn settle(&mut self) {
+        self.draw();
+
+        if self.should_auto_arrange() {
+            self.arrange();
+            self.draw();
         }
-        let (s1, s2) = startbounds.unwrap();
-        let (t1, t2) = tobounds.unwrap();
 
-        let init = (self.path(s2, t2), (Dir::R, Dir::R));
-        let paths = vec![
-            (self.path(s1, t2), (Dir::L, Dir::R)),
-            (self.path(s2, t1), (Dir::R, Dir::L)),
-            (self.path(s1, t1), (Dir::L, Dir::L)),
-        ];
-        paths.into_iter().fold(
-            init,
-            |(spath, sdirs), (path, dirs)| if path.len() <
-                spath.len()
-            {
-                (path, dirs)
-            } else {
-                (spath, sdirs)
-            },
-        )
+        // if selected not visible, try to make it visible
+        self.scroll_to_selected();
     }
 
-    fn path(&self, start: Coords, dest: Coords) -> Vec<Coords> {
-        trace!(
-            "path({:?}, {:?} (screen size: {} x {})",
-            start,
-            dest,
-            self.dims.0,
-            self.dims.1
-        );
-        fn perms(c: Coords) -> Vec<Coords> {
-            vec![
-                (c.0 + 1, c.1),
-                (max(c.0, 1) - 1, c.1),
-                (c.0, c.1 + 1),
-                // we ensure Y is >= 1, since Goto will panic otherwise
-                (c.0, max(c.1, 2) - 1),
-            ]
+    fn record(&mut self, entry: Entry) {
+        if let Some(ref mut recorder) = self.recorder {
+            recorder.record(clock::millis(), &entry);
         }
-        // maps from location to previous location
-        let mut visited: HashMap<Coords, Coords> = HashMap::new();
-        let mut pq = BinaryHeap::new();
+    }
 
-        let mut cursor = start;
-        trace!("starting draw");
-        while cursor != dest {
-            for neighbor in perms(cursor) {
-                if (!(neighbor.0 >= self.dims.0) && !(neighbor.1 >= self.dims.1 + self.view_y) &&
-                        !self.occupied(neighbor) || neighbor == dest) &&
-                    !visited.contains_key(&neighbor)
-                {
-                    let c = std::u16::MAX - cost(neighbor, dest);
-                    pq.push((c, neighbor));
-                    visited.insert(neighbor, cursor);
-                }
+    // plays back a recording without a terminal, the way run() took it in,
+    // returning what was broken after each entry that broke something.
+    // each entry is played at the time it was recorded at. prompts read
+    // the terminal directly, so they were never recorded.
+    pub fn replay_recording(&mut self, entries: &[(u64, Entry)]) -> Vec<String> {
+        let mut broken = vec![];
+        frame::capture(|| {
+            let mut entries = entries.iter().enumerate().peekable();
+            // run() starts by drawing at the terminal's size
+            if let Some(&(_, &(at, Entry::Size(dims)))) = entries.peek() {
+                clock::held_at(at, || self.resize(dims));
+                entries.next();
             }
-            if let Some((_, coords)) = pq.pop() {
-                cursor = coords;
-            } else {
-                trace!("no path, possible node overlap");
-                return vec![];
+            self.draw();
+            for (n, &(at, ref entry)) in entries {
+                let keep_going = clock::held_at(at, || match *entry {
+                    Entry::Input(ref input) => self.handle_input(input.clone()),
+                    Entry::Size(dims) => {
+                        self.resize(dims);
+                        true
+                    }
+                    Entry::Frame => {
+                        self.settle();
+                        true
+                    }
+                });
+                if let Err(e) = self.check_invariants() {
+                    broken.push(format!("after entry {}: {}", n + 1, e));
+                }
+                if !keep_going {
+                    break;
+                }
             }
-            // for tracing: show entire search path
-            // self.draw_path(visited.clone().keys().map(|k| *k).collect());
-        }
-        trace!("done draw, starting backtrack");
+        });
+        broken
+    }
 
-        let mut back_cursor = dest;
-        let mut path = vec![dest];
-        while back_cursor != start {
-            let prev = visited.get(&back_cursor).unwrap();
-            path.push(*prev);
-            back_cursor = *prev;
+    fn toggle_collapsed(&mut self) {
+        trace!("toggle_collapsed()");
+        if let Some(selected_id) = self.selected {
+            self.with_node_mut_no_meta(selected_id, |node| node.toggle_collapsed());
+            // the count of hidden completed children moves between it and
+            // its fold line
+            self.grapheme_cache.remove(&selected_id);
+            self.relayout();
         }
-        path.reverse();
-        trace!("leaving path()");
-        path
     }
 
-    fn last_week_of_done_tasks(&self) -> (String, usize) {
-        let now = time::get_time().sec as u64;
-        let day_in_sec = 60 * 60 * 24;
-        let last_week = now - (day_in_sec * 7);
-        let tasks_finished_in_last_week = self.recursive_child_filter_map(0, &mut |n: &Node| {
-            let f = n.meta.finish_time;
-            if let Some(t) = f {
-                if t > last_week { Some(t) } else { None }
-            } else {
-                None
-            }
-        });
-        let mut counts = BTreeMap::new();
-        for d in 0..7 {
-            let t = now - (d * day_in_sec);
-            let normalized_t = t / day_in_sec * day_in_sec;
-            counts.insert(normalized_t, 0);
-        }
-        for t in &tasks_finished_in_last_week {
-            let normalized_t = t / day_in_sec * day_in_sec;
-            let cur = counts.remove(&normalized_t).unwrap_or(0);
-            counts.insert(normalized_t, cur + 1);
+    fn recolor(&mut self) {
+        if let Some(selected_id) = self.selected {
+            self.with_node_mut(selected_id, |node| node.color = random_fg_color());
         }
-        let today_normalized = now / day_in_sec * day_in_sec;
-        let counts_clone = counts.clone();
-        let finished_today = counts_clone.get(&today_normalized).unwrap();
-        let week_line: Vec<i64> = counts.into_iter().map(|(_, v)| v).collect();
-        let plot = plot::plot_sparkline(week_line);
-        (plot, *finished_today as usize)
     }
 
-    fn format_node(&mut self, raw_node: &Node) -> Node {
-        lazy_static! {
-            //// general subtree population and modification
-            // limit shows the top N results.
-            static ref RE_LIMIT: Regex = Regex::new(r"#limit=(\d+)").unwrap();
-            static ref RE_TAGGED: Regex = Regex::new(r"#tagged=(\S+)").unwrap();
-            static ref RE_REV: Regex = Regex::new(r"#rev\b").unwrap();
-            static ref RE_DONE: Regex = Regex::new(r"#done\b").unwrap();
-            static ref RE_OPEN: Regex = Regex::new(r"#open\b").unwrap();
-            // since defaults to last week
-            static ref RE_SINCE: Regex = Regex::new(r"#since=(\S+)").unwrap();
-            // until defaults until now
-            static ref RE_UNTIL: Regex = Regex::new(r"#until=(\S+)").unwrap();
+    fn toggle_show_logs(&mut self) {
+        self.show_logs = !self.show_logs;
+    }
 
-            //// plot specific
-            // plot can be {new,done}
-            static ref RE_PLOT: Regex = Regex::new(r"#plot=(\S+)").unwrap();
-            // n is the number of buckets
-            static ref RE_N: Regex = Regex::new(r"#n=(\d+)").unwrap();
-        }
+    fn create_child(&mut self) {
+        if let Some(mut selected_id) = self.selected {
+            if self.with_node(selected_id, |n| n.content.is_empty())
+                .unwrap()
+            {
+                // we may have hit tab after enter by accident,
+                // so go forward a level by selecting the previous
+                // child of the current parent
+                let parent_id = self.parent(selected_id).unwrap();
+                if parent_id == self.drawing_root {
+                    // don't want to create a sibling of the drawing root
+                    // because that's not underneath the drawing root
+                    return;
+                }
 
-        // TODO detect and avoid cycles
-        // NB avoid cycles
-        let mut node = raw_node.clone();
+                let above = self.with_node(parent_id, |parent| {
+                    let idx = parent
+                        .children
+                        .iter()
+                        .position(|&e| e == selected_id)
+                        .unwrap();
+                    parent.children[max(idx, 1) - 1]
+                }).unwrap();
 
-        // for tagged queries, AND queries together
-        let mut tagged_children: Option<HashSet<NodeID>> = None;
-        for tag in &re_matches::<String>(&RE_TAGGED, &*node.content) {
-            let children = self.tag_db.tag_to_nodes(tag);
-            if let Some(children_acc) = tagged_children {
-                let children_new = children.into_iter().collect();
-                let intersection = children_acc.intersection(&children_new);
-                tagged_children = Some(intersection.cloned().collect());
+                self.select_node(above);
+                selected_id = above;
+            }
+            let selected_id = selected_id;
+
+            let node_id = self.new_node();
+            self.with_node_mut(node_id, |node| node.parent_id = selected_id);
+            // a child can't be typed into out of sight
+            let added = self.with_node_mut(selected_id, |selected| {
+                selected.children.push(node_id);
+                selected.collapsed = false;
+                selected.iconified = false;
+            });
+            if added.is_some() {
+                self.select_node(node_id);
             } else {
-                tagged_children = Some(children.into_iter().collect());
+                self.delete_recursive(node_id, &mut TrashEntry::new(node_id, 0, (0, 0)));
             }
         }
-        let queried_nodes = tagged_children
-            .map(|tc| tc.into_iter().collect())
-            .unwrap_or(vec![]);
+    }
 
-        let mut since_opt = None;
-        let mut until_opt = None;
-        if RE_DONE.is_match(&*node.content) {
-            for child in node.children.clone() {
-                let done = self.with_node(child, |c| c.stricken).unwrap();
-                if !done {
-                    node.children.retain(|&c| c != child);
+    fn create_sibling(&mut self) {
+        if let Some(mut selected_id) = self.selected {
+            if self.with_node(selected_id, |n| n.content.is_empty())
+                .unwrap()
+            {
+                // we just hit enter twice, so go back a level
+                let sel_parent = self.parent(selected_id).unwrap();
+                if sel_parent == self.drawing_root {
+                    // don't want to create a sibling of the drawing root
+                    // because that's not underneath the drawing root
+                    return;
                 }
+                self.select_node(sel_parent);
+                selected_id = sel_parent;
             }
-        }
-        if RE_OPEN.is_match(&*node.content) {
-            for child in node.children.clone() {
-                let open = self.with_node(child, |c| !c.stricken).unwrap();
-                if !open {
-                    node.children.retain(|&c| c != child);
+            let selected_id = selected_id;
+
+            if let Some(parent_id) = self.parent(selected_id) {
+                if parent_id == self.drawing_root {
+                    self.create_child();
+                    return;
                 }
-            }
-        }
-        if let Some(since) = re_matches::<String>(&RE_SINCE, &*node.content).iter().nth(
-            0,
-        )
-        {
-            since_opt = dateparse(since.clone());
-            if let Some(cutoff) = since_opt {
-                let mut new = vec![];
-                for &c in &node.children {
-                    let valid = self.with_node(c, |c| c.meta.mtime >= cutoff).unwrap_or(
-                        false,
-                    );
-                    if valid {
-                        new.push(c);
-                    }
+                let node_id = self.new_node();
+
+                self.with_node_mut(node_id, |node| node.parent_id = parent_id);
+                let added = self.with_node_mut(parent_id, |parent| {
+                    // it's possible that selected_id has been deleted by now
+                    // due to it being empty when we entered the function
+                    // (double enter for going up a level)
+                    let idx = parent
+                        .children
+                        .iter()
+                        .position(|&e| e == selected_id)
+                        .unwrap_or(0);
+                    parent.children.insert(idx + 1, node_id);
+                });
+                if added.is_some() {
+                    self.select_node(node_id);
+                } else {
+                    self.delete_recursive(node_id, &mut TrashEntry::new(node_id, 0, (0, 0)));
                 }
-                node.children = new;
             }
         }
-        if let Some(until) = re_matches::<String>(&RE_UNTIL, &*node.content).iter().nth(
-            0,
-        )
-        {
-            until_opt = dateparse(until.clone());
-            if let Some(cutoff) = until_opt {
-                let mut new = vec![];
-                for &c in &node.children {
-                    let valid = self.with_node(c, |c| c.meta.mtime <= cutoff).unwrap_or(
-                        false,
-                    );
-                    if valid {
-                        new.push(c);
+    }
+
+    // a new node right after the selected one. unlike create_sibling, this
+    // never goes up a level, and next to an anchor it makes another anchor
+    fn insert_sibling(&mut self) {
+        let selected_id = match self.selected {
+            Some(selected_id) => selected_id,
+            None => return,
+        };
+        let parent_id = match self.parent(selected_id) {
+            Some(parent_id) => parent_id,
+            None => return,
+        };
+        let coords = if parent_id == self.drawing_root {
+            // on the row just below the selected anchor's tree
+            let (x, y) = self.with_node(selected_id, |n| n.rooted_coords).unwrap();
+            let height = self.drawable_subtree_dims(selected_id).map_or(1, |(_, h)| h);
+            Some((x, y.saturating_add(height)))
+        } else {
+            None
+        };
+
+        let node_id = self.new_node();
+        self.with_node_mut(node_id, |node| {
+            node.parent_id = parent_id;
+            if let Some(coords) = coords {
+                node.rooted_coords = coords;
+            }
+        });
+        self.with_node_mut(parent_id, |parent| {
+            let idx = parent.children.iter().position(|&c| c == selected_id).unwrap();
+            parent.children.insert(idx + 1, node_id);
+        });
+        self.select_node(node_id);
+    }
+
+    fn create_free_node(&mut self) {
+        let min_width = self.dims.0 / 3;
+        let mut y_cursor = self.view_y + 2;
+        let mut from_x = None;
+        'outer: loop {
+            trace!("in create_free_node loop");
+            // go down until we find a spot
+            // that's wide enough, then create
+            // an anchor there.
+            let mut width = 0;
+            if self.dims.0 < 2 {
+                from_x = Some(1);
+                break;
+            }
+            for x in 1..self.dims.0 {
+                if self.lookup((x, y_cursor)).is_none() {
+                    width += 1;
+                    if from_x.is_none() {
+                        from_x = Some(x)
+                    }
+                    if width >= min_width {
+                        break 'outer;
                     }
+                } else {
+                    from_x = None;
+                    width = 0;
                 }
-                node.children = new;
             }
+            y_cursor += 1;
         }
-        if RE_REV.is_match(&*node.content) {
-            node.children = node.children.into_iter().rev().collect();
-        }
-        if let Some(&limit) = re_matches(&RE_LIMIT, &*node.content).iter().nth(0) {
-            node.children.truncate(limit);
-        }
-
-        let re_n = re_matches::<usize>(&RE_N, &*node.content);
-        let n_opt = re_n.iter().nth(0);
-        if let Some(plot) = re_matches::<String>(&RE_PLOT, &*node.content).iter().nth(0) {
-            let now = time::get_time().sec as u64;
-            let buckets = n_opt.cloned().unwrap_or(7);
-            let since = since_opt.unwrap_or_else(|| now - 60 * 60 * 24 * 7);
-            let until = until_opt.unwrap_or_else(|| now);
+        self.create_anchor((from_x.unwrap(), y_cursor));
+    }
 
-            node.content = match plot.as_str() {
-                "done" => self.plot(queried_nodes, PlotType::Done, buckets, since, until),
-                "new" => self.plot(queried_nodes, PlotType::New, buckets, since, until),
-                _ => node.content,
-            };
-        }
-        node
+    // an anchor placed without a mouse, as close to the selected anchor, or
+    // else the middle of the view, as there's room for it
+    fn place_anchor(&mut self) {
+        let view = ((1, self.view_y + 2),
+                    (max(self.dims.0, 2) - 1, self.view_y + max(self.dims.1, 3) - 2));
+        let near = self.selected
+            .and_then(|selected_id| self.anchor(selected_id).ok())
+            .and_then(|anchor| self.drawn_at(anchor).cloned())
+            .unwrap_or((self.dims.0 / 2, self.view_y + self.dims.1 / 2));
+        let room = (ANCHOR_ROOM.0 + 2 * ANCHOR_MARGIN, ANCHOR_ROOM.1 + 2 * ANCHOR_MARGIN);
+        let coords = match layout::free_spot(|coords| self.occupied(coords), view, room, near) {
+            Some((x, y)) => (x + ANCHOR_MARGIN, y + ANCHOR_MARGIN),
+            // the view is full, so below everything
+            None => (1, self.lowest_drawn + 2),
+        };
+        self.unselect();
+        self.create_anchor(coords);
     }
 
-    fn plot(
+    fn create_anchor(&mut self, coords: Coords) {
+        if let Some(focus_root) = self.focus_root() {
+            // nothing outside of the focused subtree is shown
+            let node_id = self.new_node();
+            self.with_node_mut(node_id, |node| node.parent_id = focus_root);
+            // and it can't be typed into out of sight
+            self.with_node_mut(focus_root, |root| {
+                root.children.push(node_id);
+                root.collapsed = false;
+                root.iconified = false;
+            });
+            self.select_node(node_id);
+            return;
+        }
+        let root = self.drawing_root;
+        let node_id = self.new_node();
+        let coords = self.unclaimed_anchor_coords(node_id, root, coords);
+        self.with_node_mut(node_id, |node| {
+            node.rooted_coords = coords;
+            node.parent_id = root;
+        });
+        self.with_node_mut(root, |root| root.children.push(node_id));
+        self.select_node(node_id);
+    }
+
+    // a loaded map that had to be repaired is saved again, repaired
+    pub fn note_repairs(&mut self, repairs: &Repairs) {
+        if !repairs.is_empty() {
+            warn!("{}", repairs);
+            self.dirty = true;
+        }
+    }
+
+    // nodes placed past the edges of the canvas, where they can never be
+    // seen or clicked, are moved back to the left edge or below the others
+    pub fn recall_strays(&mut self) {
+        let stray = |&(x, y): &Coords| x > CANVAS_WIDTH || y > CANVAS_HEIGHT;
+        let mut strays: Vec<NodeID> = self.nodes
+            .values()
+            .filter(|n| n.id != 0 && stray(&n.rooted_coords))
+            .map(|n| n.id)
+            .collect();
+        strays.sort();
+        for node_id in strays {
+            let (parent_id, was) = match self.nodes.get(&node_id) {
+                Some(node) => (node.parent_id, node.rooted_coords),
+                None => continue,
+            };
+            let lowest = self.with_node(parent_id, |n| n.children.clone())
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|&id| self.with_node(id, |n| n.rooted_coords))
+                .filter(|coords| !stray(coords))
+                .map(|(_, y)| y)
+                .max()
+                .unwrap_or(1);
+            let x = if was.0 > CANVAS_WIDTH { 1 } else { was.0 };
+            let y = if was.1 > CANVAS_HEIGHT { lowest + 1 } else { was.1 };
+            let coords = self.unclaimed_anchor_coords(node_id, parent_id, (x, y));
+            if let Some(node) = self.nodes.get_mut(&node_id) {
+                node.rooted_coords = coords;
+            }
+            // saved where it can be reached from now on
+            self.dirty = true;
+            warn!("moved node {} from {:?} to {:?}, out of reach as it was",
+                  node_id,
+                  was,
+                  coords);
+        }
+    }
+
+    // coords, or the first row below them that no other anchor under root
+    // is at. what's drawn can be behind, as on a screen too small to draw
+    // on, so the anchors themselves have the last word.
+    fn unclaimed_anchor_coords(&self, node_id: NodeID, root: NodeID, coords: Coords) -> Coords {
+        let taken: HashSet<Coords> = self.with_node(root, |n| n.children.clone())
+            .unwrap_or_default()
+            .iter()
+            .filter(|&&id| id != node_id)
+            .filter_map(|&id| self.with_node(id, |n| n.rooted_coords))
+            .collect();
+        let mut coords = coords;
+        while taken.contains(&coords) {
+            coords.1 += 1;
+        }
+        coords
+    }
+
+    fn backspace(&mut self) {
+        trace!("backspace");
+        if let Some(selected_id) = self.selected {
+            if let Some((content, text)) = self.with_node_mut(selected_id, |node| {
+                let content = node.content.clone();
+                let chars = content.chars();
+                let oldlen = chars.clone().count();
+                let truncated: String = chars.take(max(oldlen, 1) - 1).collect();
+                node.content = truncated;
+                (node.content.clone(), node.tag_text())
+            })
+            {
+                self.typed_in = Some(selected_id);
+                self.grapheme_cache.remove(&selected_id);
+                self.tag_db.reindex(selected_id, text);
+                self.completion = Completion::new(selected_id, &content, &self.tag_db);
+            }
+        }
+    }
+
+    fn append(&mut self, c: char) {
+        trace!("append({:?})", c);
+        // stray escape fragments and the like would end up in the escape
+        // sequences we draw with
+        let c = if c == '\t' { ' ' } else { c };
+        if c.is_control() {
+            debug!("ignoring control character {:?}", c);
+            return;
+        }
+        if let Some(selected_id) = self.selected {
+            if let Some((content, text)) = self.with_node_mut(selected_id, |node| {
+                node.content.push(c);
+                (node.content.clone(), node.tag_text())
+            })
+            {
+                self.typed_in = Some(selected_id);
+                self.grapheme_cache.remove(&selected_id);
+                self.tag_db.reindex(selected_id, text);
+                self.completion = Completion::new(selected_id, &content, &self.tag_db);
+            }
+        }
+    }
+
+    // the one control character allowed in content, for nodes that hold
+    // a paragraph rather than a title
+    fn insert_newline(&mut self) {
+        if let Some(selected_id) = self.selected {
+            self.with_node_mut(selected_id, |node| node.content.push('\n'));
+            self.grapheme_cache.remove(&selected_id);
+        }
+    }
+
+    // a single pasted line is typed into the selected node, while each
+    // line of a longer paste becomes a child of it
+    pub fn paste(&mut self, text: &str) {
+        trace!("paste({} bytes)", text.len());
+        let selected_id = match self.selected {
+            Some(selected_id) => selected_id,
+            None => return,
+        };
+        let lines: Vec<&str> = text.split(&['\r', '\n'][..])
+            .filter(|l| !l.trim().is_empty())
+            .collect();
+        if lines.len() == 1 {
+            for c in lines[0].chars() {
+                self.append(c);
+            }
+            return;
+        }
+        info!("pasted {} lines as children", lines.len());
+        for line in lines {
+            let line: String = line.chars()
+                .map(|c| if c == '\t' { ' ' } else { c })
+                .filter(|c| !c.is_control())
+                .collect();
+            let line = &*line;
+            let node_id = self.new_node();
+            self.with_node_mut(node_id, |n| {
+                n.parent_id = selected_id;
+                n.content = line.to_owned();
+            });
+            self.with_node_mut(selected_id, |n| n.children.push(node_id));
+            self.tag_db.reindex(node_id, line.to_owned());
+        }
+    }
+
+    pub fn drawn_at(&self, node_id: NodeID) -> Option<&Coords> {
+        self.drawn_at.get(&node_id)
+    }
+
+    pub fn lookup(&self, coords: Coords) -> Option<&NodeID> {
+        self.lookup.get(&coords)
+    }
+
+    fn lineage(&self, node_id: NodeID) -> Vec<NodeID> {
+        let mut lineage = vec![node_id];
+        let mut cursor = node_id;
+        while let Some(parent) = self.parent(cursor) {
+            lineage.push(parent);
+            if parent == 0 {
+                break;
+            }
+            cursor = parent;
+        }
+        lineage.reverse();
+        lineage
+    }
+
+    // the nodes from the anchor below the current drawing root down to
+    // node_id, inclusive.  if node_id is not beneath the drawing root,
+    // the path starts below the real root instead.
+    pub fn path_to_node(&self, node_id: NodeID) -> Vec<NodeID> {
+        if !self.exists(node_id) || node_id == self.drawing_root {
+            return vec![];
+        }
+        let lineage = self.lineage(node_id);
+        let start = lineage
+            .iter()
+            .position(|&id| id == self.drawing_root)
+            .or_else(|| lineage.iter().position(|&id| id == 0))
+            .map(|idx| idx + 1)
+            .unwrap_or(0);
+        lineage[start..].to_vec()
+    }
+
+    // returns true if a is a parent of b
+    fn is_parent(&self, a: NodeID, b: NodeID) -> bool {
+        trace!("is_parent({}, {})", a, b);
+        let mut ptr = b;
+        loop {
+            trace!("loop in is_parent");
+            if ptr == a {
+                return true;
+            } else if ptr == 0 {
+                // we've reached the top, and 0 is not the parent (a)
+                // because we did not return in the last clause
+                return false;
+            }
+            ptr = self.parent(ptr).unwrap_or_else(|| {
+                self.with_node(b, |n| error!("orphan node, have it cleaned up: {:?}", n));
+                0
+            });
+        }
+    }
+
+    fn anchor(&self, node_id: NodeID) -> Result<NodeID, String> {
+        if let None = self.drawn_at(node_id) {
+            return Err("node not drawn on this screen".to_owned());
+        }
+
+        // find the "root" just below self.drawing_root to mod
+        // the rooted_coords for.
+        let mut ptr = node_id;
+        loop {
+            let id = self.parent(ptr).ok_or("node has no parent")?;
+            trace!(
+                "anchor loop id: {} ptr: {} selected: {} root: {}",
+                id,
+                ptr,
+                node_id,
+                self.drawing_root
+            );
+            if id != self.drawing_root {
+                ptr = id;
+            } else {
+                break;
+            }
+        }
+        Ok(ptr)
+    }
+
+    fn parent(&self, node_id: NodeID) -> Option<NodeID> {
+        trace!("parent({})", node_id);
+        self.with_node(node_id, |n| n.parent_id)
+    }
+
+    fn move_selected(&mut self, from: Coords, to: Coords) {
+        trace!("move_selected({:?}, {:?})", from, to);
+        let selected_id = match self.selected {
+            Some(selected_id) => selected_id,
+            None => return,
+        };
+        match self.drop_target(from, to) {
+            Some(Drop::Parent(new_parent)) => self.reparent(selected_id, new_parent),
+            Some(Drop::Shift(anchor, moved)) => {
+                let from = self.with_node(anchor, |root| root.rooted_coords).unwrap();
+                self.with_node_mut_no_meta(anchor, |root| root.rooted_coords = moved).unwrap();
+                self.move_group(anchor, from, moved);
+            }
+            Some(Drop::Place(to)) => {
+                // 1. remove from old parent's children
+                // 2. add to drawing_root's children
+                // 3. update rooted_coords
+                let old_parent = self.parent(selected_id).unwrap();
+                let from = self.with_node(selected_id, |s| s.rooted_coords).unwrap();
+                self.with_node_mut(old_parent, |op| op.children.retain(|c| c != &selected_id))
+                    .unwrap();
+                let root = self.drawing_root;
+                self.with_node_mut(root, |dr| dr.children.push(selected_id))
+                    .unwrap();
+                self.with_node_mut(selected_id, |s| {
+                    s.rooted_coords = to;
+                    s.parent_id = root;
+                }).unwrap();
+                // an anchor being moved, rather than a node becoming one
+                if old_parent == root {
+                    self.move_group(selected_id, from, to);
+                }
+            }
+            None => {}
+        }
+        trace!("leaving move_selected");
+    }
+
+    // moves the anchors grouped with one that was dragged from `from` to
+    // `to` by as much. each stops at the top and left edges on its own, so
+    // one reaching an edge doesn't hold the rest back.
+    fn move_group(&mut self, anchor: NodeID, from: Coords, to: Coords) {
+        let name = match self.groups.get(&anchor) {
+            Some(name) => name.clone(),
+            None => return,
+        };
+        let parent = self.parent(anchor);
+        let members: Vec<NodeID> = self.groups
+            .iter()
+            .filter(|&(&id, group)| *group == name && id != anchor && self.parent(id) == parent)
+            .map(|(&id, _)| id)
+            .collect();
+        let dx = to.0 as i32 - from.0 as i32;
+        let dy = to.1 as i32 - from.1 as i32;
+        for member in members {
+            self.with_node_mut_no_meta(member, |m| {
+                let (x, y) = m.rooted_coords;
+                m.rooted_coords = (max(x as i32 + dx, 1) as u16, max(y as i32 + dy, 1) as u16);
+            });
+        }
+    }
+
+    // what letting go of a drag from `from` at `to` would do with the
+    // selected node, worked out without doing it so that it can be drawn
+    // while the drag goes on
+    fn drop_target(&self, from: Coords, to: Coords) -> Option<Drop> {
+        let dx = to.0 as i16 - from.0 as i16;
+        let dy = to.1 as i16 - from.1 as i16;
+
+        let focus_root = self.focus_root();
+        let selected_id = if let Some(selected_id) = self.selected {
+            if let Some(focus_root) = focus_root {
+                if selected_id == focus_root || !self.is_parent(focus_root, selected_id) {
+                    debug!("can only move nodes beneath the focus root");
+                    return None;
+                }
+                selected_id
+            } else if self.is_parent(self.drawing_root, selected_id) {
+                selected_id
+            } else {
+                // selected node is not a child of drawing_root
+                debug!("selected node is not child of drawing_root");
+                return None;
+            }
+        } else {
+            // nothing to drag, no work to do
+            return None;
+        };
+        // dropping on a fold line puts it in with what's behind it
+        if let Some(new_parent) = self.lookup(to).cloned().or_else(|| self.fold_under(to)) {
+            if !self.is_parent(selected_id, new_parent) {
+                Some(Drop::Parent(new_parent))
+            } else if focus_root.is_none() {
+                // the cursor is over a child, so rather than create a
+                // cycle, the subtree moves
+                let ptr = self.anchor(selected_id).unwrap();
+                let (ox, oy) = self.with_node(ptr, |root| root.rooted_coords).unwrap();
+                let moved = (max(ox as i16 + dx, 1) as u16, max(oy as i16 + dy, 1) as u16);
+                Some(Drop::Shift(ptr, self.unclaimed_anchor_coords(ptr, self.drawing_root, moved)))
+            } else {
+                None
+            }
+        } else if let Some(focus_root) = focus_root {
+            // nothing outside of the focus is drawn, so keep it within the focus
+            Some(Drop::Parent(focus_root))
+        } else {
+            // destination is not another node, so it goes where it's dropped
+            Some(Drop::Place(self.unclaimed_anchor_coords(selected_id, self.drawing_root, to)))
+        }
+    }
+
+    fn pop_focus(&mut self) {
+        // bust grapheme cache on new view
+        self.grapheme_cache.clear();
+        self.focus_mode.clear();
+        self.unselect();
+        let (root, selected, view_y) = self.focus_stack.pop().unwrap_or((0, 0, 0));
+        self.drawing_root = root;
+        self.view_y = view_y;
+        self.select_node(selected);
+    }
+
+    fn drill_down(&mut self) {
+        trace!("drill_down()");
+        // bust grapheme cache on new view
+        self.grapheme_cache.clear();
+        if let Some(selected_id) = self.unselect() {
+            if selected_id != self.drawing_root {
+                self.focus_mode.clear();
+                let breadcrumb = (self.drawing_root, selected_id, self.view_y);
+                self.focus_stack.push(breadcrumb);
+                self.drawing_root = selected_id;
+                self.view_y = 0;
+            }
+        }
+    }
+
+    fn click_select(&mut self, coords: Coords) -> Option<NodeID> {
+        trace!("click_select({:?})", coords);
+        let result = self.try_select(coords);
+        self.dragging_from.take();
+        self.dragging_to.take();
+        result
+    }
+
+    fn scroll_up(&mut self) {
+        self.view_y = max(self.view_y, self.dims.1 / 2) - self.dims.1 / 2;
+        self.unselect();
+    }
+
+    fn scroll_down(&mut self) {
+        if self.lowest_drawn > self.view_y + self.dims.1 {
+            self.view_y = min(self.view_y + self.dims.1 / 2, self.lowest_drawn);
+            self.unselect();
+        }
+    }
+
+    fn scroll_to_selected(&mut self) -> bool {
+        if let Some(selected_id) = self.selected {
+            self.scroll_to_node(selected_id)
+        } else {
+            false
+        }
+    }
+
+    fn scroll_to_node(&mut self, node_id: NodeID) -> bool {
+        if let Some(visible) = self.node_is_visible(node_id) {
+            let &(_, y) = self.drawn_at(node_id).unwrap();
+            if !visible {
+                // move only if necessary
+                self.view_y = max(y - 1, self.dims.1 / 2) - self.dims.1 / 2;
+                self.draw();
+                return true;
+            }
+        }
+        false
+    }
+
+    fn zoom_select(&mut self, node_id: NodeID) {
+        if !self.exists(node_id) {
+            return;
+        }
+        if let Some(focus_root) = self.focus_root() {
+            if self.is_parent(focus_root, node_id) {
+                self.select_node(node_id);
+                return;
+            }
+            self.focus_mode.clear();
+        }
+        // jump to highest view where node is visible
+        let mut cursor = node_id;
+        loop {
+            trace!("in auto_task loop");
+            let parent = self.parent(cursor).unwrap();
+            let collapsed = self.with_node(parent, |p| p.collapsed).unwrap();
+            cursor = parent;
+            if parent == 0 || collapsed {
+                break;
+            }
+        }
+
+        // save old location and jump
+        let old_select = self.unselect().unwrap_or(0);
+        let breadcrumb = (self.drawing_root, old_select, self.view_y);
+        self.focus_stack.push(breadcrumb);
+        self.drawing_root = cursor;
+        self.select_node(node_id);
+        self.draw();
+    }
+
+    fn raise_selected(&mut self) {
+        if let Some(selected_id) = self.selected {
+            if !self.exists(selected_id) {
+                warn!("tried to raise deleted node");
+                return;
+            }
+            let parent_id = self.parent(selected_id).unwrap();
+            if parent_id == self.drawing_root {
+                // principle: don't modify things that are above the visible scope
+                return;
+            }
+            self.with_node_mut(parent_id, |mut parent| {
+                let idx = parent
+                    .children
+                    .iter()
+                    .position(|&e| e == selected_id)
+                    .unwrap();
+                let to = max(idx, 1) - 1;
+                parent.children.swap(idx, to);
+            });
+        }
+    }
+
+    fn lower_selected(&mut self) {
+        if let Some(selected_id) = self.selected {
+            if !self.exists(selected_id) {
+                warn!("tried to lower deleted node");
+                return;
+            }
+            let parent_id = self.parent(selected_id).unwrap();
+            if parent_id == self.drawing_root {
+                // principle: don't modify things that are above the visible scope
+                return;
+            }
+            self.with_node_mut(parent_id, |mut parent| {
+                let idx = parent
+                    .children
+                    .iter()
+                    .position(|&e| e == selected_id)
+                    .unwrap();
+                let len = parent.children.len();
+                if len > 1 {
+                    let to = min(idx, len - 2) + 1;
+                    parent.children.swap(idx, to);
+                }
+            });
+        }
+    }
+
+    fn select_up(&mut self) {
+        let view_y = self.view_y;
+        let height = self.dims.1;
+        self.select_relative(|(l1, _), (l2, _)| {
+            let is_up = l1.1 > l2.1;
+            let (diff_x, diff_y) = distances(l1, l2);
+            if is_up {
+                let visible = visible(view_y, height, l2.1);
+                Some((!visible, diff_x, diff_y))
+            } else {
+                None
+            }
+        });
+    }
+
+    fn select_down(&mut self) {
+        let view_y = self.view_y;
+        let height = self.dims.1;
+        self.select_relative(|(l1, _), (l2, _)| {
+            let is_down = l1.1 < l2.1;
+            let (diff_x, diff_y) = distances(l1, l2);
+            if is_down {
+                let visible = visible(view_y, height, l2.1);
+                Some((!visible, diff_x, diff_y))
+            } else {
+                None
+            }
+        });
+    }
+
+    fn select_left(&mut self) {
+        self.select_relative(|(l1, _), (_, r2)| {
+            let is_left = l1.0 > r2.0;
+            let (diff_x, diff_y) = distances(l1, r2);
+            if is_left {
+                Some((diff_y, diff_x))
+            } else {
+                None
+            }
+        })
+    }
+
+    fn select_right(&mut self) {
+        self.select_relative(|(_, r1), (l2, _)| {
+            let is_right = r1.0 < l2.0;
+            let (diff_x, diff_y) = distances(r1, l2);
+            if is_right {
+                Some((diff_y, diff_x))
+            } else {
+                None
+            }
+        });
+    }
+
+    fn select_relative<F, O: Ord + Clone>(&mut self, filter_cost: F)
+        where F: FnMut((Coords, Coords), (Coords, Coords)) -> Option<O>
+    {
+        match self.find_relative_row(filter_cost) {
+            Some(Row::Node(node_id)) => self.select_node(node_id),
+            Some(Row::Fold(parent_id)) => self.select_fold(parent_id),
+            None => {}
+        }
+    }
+
+    fn find_relative_row<F, O: Ord + Clone>(&mut self, mut filter_cost: F) -> Option<Row>
+        where F: FnMut((Coords, Coords), (Coords, Coords)) -> Option<O>
+    {
+        let default_coords = (self.dims.0 / 2, self.dims.1 / 2);
+        let rel_def_coords = self.screen_to_internal_xy(default_coords);
+
+        let cur = self.selected
+            .map(Row::Node)
+            .or_else(|| self.selected_fold.map(Row::Fold))
+            .and_then(|row| self.row_bounds(row))
+            .unwrap_or((rel_def_coords, rel_def_coords));
+
+        let nodes = self.drawn_at.keys().filter(|&&id| self.is_shown(id)).map(|&id| Row::Node(id));
+        let rows: Vec<Row> = nodes.chain(self.folds.keys().map(|&id| Row::Fold(id))).collect();
+        let mut row_costs = vec![];
+        for row in rows {
+            if let Some(bounds) = self.row_bounds(row) {
+                if let Some(cost) = filter_cost(cur, bounds) {
+                    row_costs.push((row, cost));
+                }
+            }
+        }
+        row_costs.sort_by_key(|&(_, ref cost)| cost.clone());
+        row_costs.into_iter().next().map(|(row, _)| row)
+    }
+
+    fn select_node(&mut self, node_id: NodeID) {
+        trace!("select_node({})", node_id);
+        self.unselect();
+        if node_id != 0 {
+            // it's possible that unselecting above actually caused
+            // this node to be deleted, due to its parent (previous
+            // selection) being empty.  To account for this, we need
+            // to only set self.selected to node_id if the with_node
+            // succeeds.
+            if self.set_selected(node_id, true).is_some() {
+                self.selected = Some(node_id);
+                self.record_selection(node_id);
+            }
+        }
+    }
+
+    fn record_selection(&mut self, node_id: NodeID) {
+        if self.history.get(self.history_cursor) == Some(&node_id) {
+            // we got here by moving through history
+            return;
+        }
+        // a new jump forgets where we could have gone forward to
+        if !self.history.is_empty() {
+            self.history.truncate(self.history_cursor + 1);
+        }
+        // walking along siblings only remembers where we stopped
+        let last_parent = self.history.last().and_then(|&last| self.parent(last));
+        if last_parent.is_some() && last_parent == self.parent(node_id) {
+            self.history.pop();
+        }
+        self.history.push(node_id);
+        if self.history.len() > 100 {
+            self.history.remove(0);
+        }
+        self.history_cursor = self.history.len() - 1;
+    }
+
+    fn history_back(&mut self) {
+        trace!("history_back()");
+        let found = (0..min(self.history_cursor, self.history.len()))
+            .rev()
+            .find(|&idx| self.exists(self.history[idx]));
+        if let Some(idx) = found {
+            self.history_cursor = idx;
+            let node_id = self.history[idx];
+            self.reveal_and_select(node_id);
+        }
+    }
+
+    fn history_forward(&mut self) {
+        trace!("history_forward()");
+        let found = (self.history_cursor + 1..self.history.len())
+            .find(|&idx| self.exists(self.history[idx]));
+        if let Some(idx) = found {
+            self.history_cursor = idx;
+            let node_id = self.history[idx];
+            self.reveal_and_select(node_id);
+        }
+    }
+
+    // only anchors in the current view have their titles drawn
+    fn anchor_title(&self, node_id: NodeID) -> Option<String> {
+        if self.focus_root().is_some() || self.parent(node_id) != Some(self.drawing_root) ||
+           self.iconifies(node_id) {
+            return None;
+        }
+        self.with_node(node_id, |n| n.title.clone()).and_then(|title| title)
+    }
+
+    fn title_prompt(&mut self) {
+        let selected_id = match self.selected {
+            Some(selected_id) => selected_id,
+            None => return,
+        };
+        if self.focus_root().is_some() || self.parent(selected_id) != Some(self.drawing_root) {
+            warn!("only anchors can have titles");
+            return;
+        }
+        if let Ok(Some(title)) = self.prompt("title (empty to remove): ") {
+            self.set_title(selected_id, &title);
+        }
+    }
+
+    fn set_title(&mut self, node_id: NodeID, title: &str) {
+        let title = title.trim();
+        let title = if title.is_empty() {
+            None
+        } else {
+            Some(title.to_owned())
+        };
+        self.with_node_mut(node_id, |n| n.title = title.clone());
+    }
+
+    fn toggle_group(&mut self) {
+        trace!("toggle_group()");
+        let selected_id = match self.selected {
+            Some(selected_id) => selected_id,
+            None => return,
+        };
+        if self.focus_root().is_some() || self.parent(selected_id) != Some(self.drawing_root) {
+            warn!("only anchors can be grouped");
+            return;
+        }
+        if let Some(name) = self.groups.remove(&selected_id) {
+            info!("took the anchor out of {}", name);
+        } else if let Ok(Some(name)) = self.prompt("group: ") {
+            let name = name.trim();
+            if name.is_empty() {
+                return;
+            }
+            self.groups.insert(selected_id, name.to_owned());
+        } else {
+            return;
+        }
+        self.grapheme_cache.remove(&selected_id);
+        self.dirty = true;
+    }
+
+    // draws the selected anchor as one line, or all of it again. what was
+    // collapsed under it stays as it was either way.
+    fn toggle_iconified(&mut self) {
+        trace!("toggle_iconified()");
+        let selected_id = match self.selected {
+            Some(selected_id) => selected_id,
+            None => return,
+        };
+        if self.focus_root().is_some() || self.parent(selected_id) != Some(self.drawing_root) {
+            warn!("only anchors can be iconified");
+            return;
+        }
+        self.with_node_mut_no_meta(selected_id, |n| n.iconified = !n.iconified);
+        self.grapheme_cache.remove(&selected_id);
+        self.dirty = true;
+        self.relayout();
+    }
+
+    fn bookmark_prompt(&mut self) {
+        trace!("bookmark_prompt()");
+        if let Some(selected_id) = self.selected {
+            if let Ok(Key::Char(c)) = self.single_key_prompt("bookmark slot (0-9): ") {
+                if let Some(slot) = c.to_digit(10) {
+                    self.set_bookmark(slot as u8, selected_id);
+                }
+            }
+        }
+    }
+
+    fn set_bookmark(&mut self, slot: u8, node_id: NodeID) {
+        // a node only lives in one slot at a time
+        self.bookmarks.retain(|_, id| *id != node_id);
+        if let Some(old) = self.bookmarks.insert(slot, node_id) {
+            self.grapheme_cache.remove(&old);
+        }
+        self.grapheme_cache.remove(&node_id);
+        self.dirty = true;
+    }
+
+    fn bookmark_slot(&self, node_id: NodeID) -> Option<u8> {
+        self.bookmarks
+            .iter()
+            .find(|&(_, &id)| id == node_id)
+            .map(|(&slot, _)| slot)
+    }
+
+    fn jump_to_bookmark(&mut self, slot: u8) {
+        trace!("jump_to_bookmark({})", slot);
+        if let Some(&node_id) = self.bookmarks.get(&slot) {
+            if self.exists(node_id) {
+                self.reveal_and_select(node_id);
+            } else {
+                self.bookmarks.remove(&slot);
+            }
+        }
+    }
+
+    // select a node, expanding its ancestors and changing the view if it
+    // is not beneath the current drawing root
+    fn reveal_and_select(&mut self, node_id: NodeID) {
+        let mut cursor = node_id;
+        while let Some(parent) = self.parent(cursor) {
+            if self.with_node(parent, |p| p.collapsed).unwrap_or(false) {
+                self.with_node_mut_no_meta(parent, |p| p.collapsed = false);
+                self.grapheme_cache.remove(&parent);
+            }
+            if self.with_node(parent, |p| p.iconified).unwrap_or(false) {
+                self.with_node_mut_no_meta(parent, |p| p.iconified = false);
+                self.grapheme_cache.remove(&parent);
+            }
+            // and opening the fold a completed node is behind
+            if self.hides_child(parent, cursor) {
+                self.revealed.insert(parent);
+            }
+            if parent == 0 {
+                break;
+            }
+            cursor = parent;
+        }
+        // the drawing root itself isn't selectable, so pop out to where it's drawn
+        if node_id != self.drawing_root && self.is_parent(self.drawing_root, node_id) {
+            if let Some(focus_root) = self.focus_root() {
+                if !self.is_parent(focus_root, node_id) {
+                    self.focus_mode.clear();
+                }
+            }
+            self.select_node(node_id);
+        } else {
+            self.zoom_select(node_id);
+        }
+    }
+
+    // the innermost focused subtree that still exists
+    fn focus_root(&self) -> Option<NodeID> {
+        self.focus_mode
+            .iter()
+            .rev()
+            .map(|&(node_id, _)| node_id)
+            .find(|&node_id| self.exists(node_id))
+    }
+
+    fn toggle_focus_mode(&mut self) {
+        trace!("toggle_focus_mode()");
+        self.prune_focus_mode();
+        match (self.selected, self.focus_root()) {
+            (Some(selected_id), Some(focus_root)) if selected_id != focus_root => {
+                self.enter_focus_mode(selected_id)
+            }
+            (Some(selected_id), None) => self.enter_focus_mode(selected_id),
+            (_, Some(_)) => self.leave_focus_mode(),
+            (None, None) => {}
+        }
+    }
+
+    fn enter_focus_mode(&mut self, node_id: NodeID) {
+        self.focus_mode.push((node_id, self.view_y));
+        self.view_y = 0;
+        self.grapheme_cache.clear();
+    }
+
+    fn leave_focus_mode(&mut self) {
+        self.prune_focus_mode();
+        if let Some((_, view_y)) = self.focus_mode.pop() {
+            self.view_y = view_y;
+        }
+        self.grapheme_cache.clear();
+    }
+
+    // drop focus roots that have been deleted, restoring the view they hid
+    fn prune_focus_mode(&mut self) {
+        while let Some(&(node_id, view_y)) = self.focus_mode.last() {
+            if self.exists(node_id) {
+                break;
+            }
+            self.focus_mode.pop();
+            self.view_y = view_y;
+        }
+    }
+
+    fn click_screen(&mut self, coords: Coords) {
+        self.click_screen_at(coords, clock::millis());
+    }
+
+    fn click_screen_at(&mut self, coords: Coords, now: u64) {
+        trace!("click_screen({:?})", coords);
+        if coords.0 > self.dims.0 || coords.1 > self.view_y + self.dims.1 {
+            warn!("click way off-screen");
+            return;
+        }
+        if let Some(parent_id) = self.fold_under(coords) {
+            self.select_fold(parent_id);
+            self.last_click = None;
+            self.last_blank_click = None;
+            return;
+        }
+        let new = self.try_select(coords);
+
+        // a single click on blank space only deselects, so that stray
+        // clicks don't litter the map with empty anchors. it takes a
+        // double click to make one.
+        if new.is_none() && self.dragging_from.is_none() {
+            let double = match self.last_blank_click {
+                Some((at, last_coords)) => {
+                    last_coords == coords && now.saturating_sub(at) <= DOUBLE_CLICK_MS
+                }
+                None => false,
+            };
+            if double {
+                self.create_anchor(coords);
+                self.last_blank_click = None;
+            } else {
+                self.last_blank_click = Some((now, coords));
+            }
+        } else {
+            self.last_blank_click = None;
+        }
+
+        // a second click on the same node soon after the first toggles
+        // collapse. a click that made an anchor never starts one.
+        let double = match (self.last_click, new) {
+            (Some((at, last_id)), Some(new_id)) => {
+                last_id == new_id && now.saturating_sub(at) <= DOUBLE_CLICK_MS
+            }
+            _ => false,
+        };
+        if double {
+            self.toggle_collapsed();
+            self.last_click = None;
+        } else {
+            self.last_click = new.map(|id| (now, id));
+        }
+    }
+
+    fn release(&mut self, to: Coords) {
+        trace!("release({:?})", to);
+        if to.0 > self.dims.0 || to.1 > self.view_y + self.dims.1 {
+            warn!("release way off-screen");
+            return;
+        }
+        if let Some((from, _)) = self.arrow_drag.take() {
+            // over empty space or the source node this does nothing
+            match self.lookup(to).cloned() {
+                Some(to_id) if to_id != from => self.add_arrow(from, to_id),
+                _ => trace!("arrow drag cancelled"),
+            }
+        } else if let Some(from) = self.dragging_from.take() {
+            self.dragging_to.take();
+            self.move_selected(from, to);
+        }
+        trace!("leaving release");
+    }
+
+    fn add_arrow(&mut self, from: NodeID, to: NodeID) {
+        if !self.arrows.contains(&(from, to)) {
+            self.arrows.push((from, to));
+            self.dirty = true;
+        }
+    }
+
+    pub fn assert_node_consistency(&self) {
+        // a child should be a child of at most one node
+        debug!("testing that no nodes have multiple parents");
+        let mut seen = HashSet::new();
+        let mut to_view = vec![0];
+        let mut leaf_children = vec![];
+        while let Some(node_id) = to_view.pop() {
+            self.with_node(node_id, |n| {
+                for &c in &n.children {
+                    assert!(!seen.contains(&c));
+                    seen.insert(c);
+                }
+                if n.children.is_empty() {
+                    leaf_children.push(node_id);
+                }
+            });
+        }
+
+        // no loops, no orphans
+        debug!("testing that 0 is the ancestor of all nodes");
+        for &node_id in self.nodes.keys() {
+            assert!(self.is_parent(0, node_id));
+        }
+
+
+        debug!("testing that all arrows are existing nodes");
+        // no arrows that don't exist
+        for &(ref a, ref b) in &self.arrows {
+            assert!(self.nodes.get(a).is_some());
+            assert!(self.nodes.get(b).is_some());
+        }
+    }
+
+    // what should hold between events, like assert_node_consistency but
+    // saying which node broke it instead of panicking
+    pub fn check_invariants(&self) -> Result<(), String> {
+        let mut ids: Vec<NodeID> = self.nodes.keys().cloned().collect();
+        ids.sort();
+        for &id in &ids {
+            let node = &self.nodes[&id];
+            if let Some(child) = node.children.iter().find(|c| !self.nodes.contains_key(c)) {
+                return Err(format!("node {} has a child {} that doesn't exist", id, child));
+            }
+            if id > self.max_id {
+                return Err(format!("node {} is above max_id {}", id, self.max_id));
+            }
+            // following parents must reach the root without coming back
+            let mut ptr = id;
+            for _ in 0..self.nodes.len() {
+                if ptr == 0 {
+                    break;
+                }
+                ptr = match self.nodes.get(&ptr) {
+                    Some(n) => n.parent_id,
+                    None => return Err(format!("node {} has an ancestor {} that doesn't exist",
+                                               id,
+                                               ptr)),
+                };
+                if ptr == id {
+                    return Err(format!("node {} is its own ancestor", id));
+                }
+            }
+            if ptr != 0 {
+                return Err(format!("node {} is below a cycle", id));
+            }
+        }
+
+        let mut anchored = HashMap::new();
+        for &anchor in self.with_node(self.drawing_root, |n| n.children.clone())
+            .unwrap_or_default()
+            .iter() {
+            let coords = self.nodes[&anchor].rooted_coords;
+            if coords.0 < 1 || coords.1 < 1 {
+                return Err(format!("anchor {} is at {:?}, off the map", anchor, coords));
+            }
+            if let Some(other) = anchored.insert(coords, anchor) {
+                return Err(format!("anchors {} and {} are both at {:?}", other, anchor, coords));
+            }
+        }
+
+        for &(from, to) in &self.arrows {
+            if let Some(dead) = [from, to].iter().find(|id| !self.nodes.contains_key(id)) {
+                return Err(format!("arrow from {} to {} has a node {} that doesn't exist",
+                                   from,
+                                   to,
+                                   dead));
+            }
+        }
+
+        let flagged: Vec<NodeID> =
+            ids.iter().cloned().filter(|id| self.nodes[id].selected).collect();
+        if flagged.len() > 1 {
+            return Err(format!("nodes {:?} are all marked selected", flagged));
+        }
+        if let Some(selected_id) = self.selected {
+            let mut reachable = false;
+            let mut to_visit = vec![0];
+            while let Some(id) = to_visit.pop() {
+                if id == selected_id {
+                    reachable = true;
+                    break;
+                }
+                to_visit.extend(self.nodes.get(&id).map_or(&[][..], |n| &n.children[..]));
+            }
+            if !reachable {
+                return Err(format!("selected node {} can't be reached from the root",
+                                   selected_id));
+            }
+            // collapsed or hidden completed nodes aren't drawn
+            if let Some(parent_id) = self.hidden_under(selected_id) {
+                return Err(format!("selected node {} is hidden under node {}",
+                                   selected_id,
+                                   parent_id));
+            }
+        }
+        Ok(())
+    }
+
+    // a failed save leaves the work file as it was, and the changes unsaved
+    pub fn save(&mut self) -> error::Result<()> {
+        trace!("save()");
+        let start = Instant::now();
+        self.drop_blank_anchors();
+        self.assert_node_consistency();
+        let data = self.saved_bytes();
+        if let Some(ref path) = self.work_path {
+            let mut tmp_path = path.clone();
+            tmp_path.push_str(".tmp");
+            if let Ok(_) = remove_file(&tmp_path) {
+                warn!("removed stale tmp file");
+            }
+            File::create(&tmp_path)
+                .and_then(|mut f| f.write_all(&*data).and_then(|()| f.sync_all()))
+                .map_err(error::Error::file(&tmp_path))?;
+            rename(&tmp_path, path).map_err(error::Error::file(path))?;
+            info!("saved work to {}", path);
+            self.dirty = false;
+            self.save_session(path);
+            run_hook(&self.config, Hook::Save, path);
+        }
+        self.conflict = false;
+        self.work_file_stamp = self.read_work_file_stamp();
+        self.perf.borrow_mut().record("save", start.elapsed());
+        Ok(())
+    }
+
+    // what's written to the work file, encrypted when there's a key
+    fn saved_bytes(&self) -> Vec<u8> {
+        let data = serialization::serialize_screen(self);
+        match self.key {
+            Some(ref key) => crypto::seal(key, &data),
+            None => data,
+        }
+    }
+
+    fn is_blank(&self, node_id: NodeID) -> bool {
+        self.with_node(node_id, |n| n.content.is_empty() && n.children.is_empty())
+            .unwrap_or(false)
+    }
+
+    // anchors that were made and never typed into. the selected one is
+    // spared, as it may be about to get some content.
+    fn drop_blank_anchors(&mut self) {
+        let blank: Vec<NodeID> = self.with_node(0, |n| n.children.clone())
+            .unwrap()
+            .into_iter()
+            .filter(|&id| Some(id) != self.selected && self.is_blank(id))
+            .collect();
+        if blank.is_empty() {
+            return;
+        }
+        for &node_id in &blank {
+            self.with_node_mut_no_meta(0, |root| root.children.retain(|&c| c != node_id));
+            // no tombstone, as a copy that's still filling it in may sync
+            // with this one
+            self.nodes.remove(&node_id);
+            self.arrows.retain(|&(from, to)| from != node_id && to != node_id);
+            self.bookmarks.retain(|_, id| *id != node_id);
+            self.groups.remove(&node_id);
+        }
+        info!("dropped {} empty anchors", blank.len());
+    }
+
+    fn save_session(&self, work_path: &str) {
+        let session = Session {
+            selected: self.selected,
+            drawing_root: self.drawing_root,
+            view_y: self.view_y,
+            show_logs: self.show_logs,
+            macros: self.macros.clone(),
+        };
+        let data = serialization::serialize_session(&session);
+        let path = session::path(work_path);
+        if let Err(e) = File::create(&path).and_then(|mut f| f.write_all(&data)) {
+            warn!("failed to save session to {}: {}", path, e);
+        }
+    }
+
+    // go back to where the work file was left, as far as it still exists
+    pub fn restore_session(&mut self) {
+        let path = match self.work_path {
+            Some(ref work_path) => session::path(work_path),
+            None => return,
+        };
+        let mut data = vec![];
+        if File::open(&path).and_then(|mut f| f.read_to_end(&mut data)).is_err() {
+            return;
+        }
+        let session = match serialization::deserialize_session(&data) {
+            Ok(session) => session,
+            Err(e) => {
+                warn!("ignoring unreadable session {}: {:?}", path, e);
+                return;
+            }
+        };
+
+        if session.drawing_root != 0 && self.exists(session.drawing_root) {
+            // popping up leaves the drilled-into node selected
+            self.focus_stack = vec![(0, session.drawing_root, 0)];
+            self.drawing_root = session.drawing_root;
+        }
+        self.view_y = session.view_y;
+        self.show_logs = session.show_logs;
+        self.macros = session.macros;
+        if let Some(selected_id) = session.selected {
+            let visible = selected_id != self.drawing_root &&
+                          self.exists(selected_id) &&
+                          self.is_parent(self.drawing_root, selected_id);
+            if visible {
+                self.select_node(selected_id);
+            }
+        }
+    }
+
+    // the active tab is always saved, the others only if they have changes
+    fn save_all(&mut self) {
+        if let Err(e) = self.save() {
+            error!("failed to save: {}", e);
+        }
+        for tab in &mut self.tabs {
+            if !tab.dirty {
+                continue;
+            }
+            match tab.save() {
+                // tabs in the background don't hold the key file's settings
+                Ok(()) => {
+                    if let Some(ref path) = tab.work_path {
+                        run_hook(&self.config, Hook::Save, path);
+                    }
+                }
+                Err(e) => error!("failed to save: {}", e),
+            }
+        }
+    }
+
+    pub fn cleanup(&mut self) {
+        trace!("cleanup()");
+        out!("{}{}", input::DISABLE_BRACKETED_PASTE, cursor::Show);
+        if let Some(mut s) = self.stdout.take() {
+            if let Err(e) = s.flush() {
+                error!("failed to write to the terminal: {}", e);
+            }
+        }
+    }
+
+    // tests never take the terminal over, but go through the alternate
+    // screen the same way
+    pub fn start_raw_mode(&mut self) -> error::Result<()> {
+        if self.stdout.is_none() && !self.is_test {
+            let raw = stdout().into_raw_mode().map_err(error::Error::Terminal)?;
+            self.stdout = Some(MouseTerminal::from(raw));
+            out!("{}", input::ENABLE_BRACKETED_PASTE);
+            if !self.capabilities.mouse {
+                out!("{}", input::DISABLE_MOUSE);
+            }
+        }
+        if !self.alternate {
+            out!("{}", ToAlternateScreen);
+            self.alternate = true;
+        }
+        Ok(())
+    }
+
+    // back to the shell's screen and out of raw mode, for good or until
+    // start_raw_mode
+    pub fn leave_terminal(&mut self) {
+        if self.alternate {
+            out!("{}", ToMainScreen);
+            self.alternate = false;
+        }
+        self.cleanup();
+    }
+
+    // gives the terminal to another program while f runs, and takes it back
+    // once it's done
+    fn suspend<T, F>(&mut self, f: F) -> error::Result<T>
+        where F: FnOnce() -> T
+    {
+        self.leave_terminal();
+        let res = f();
+        self.start_raw_mode()?;
+        Ok(res)
+    }
+
+    pub fn occupied(&self, coords: Coords) -> bool {
+        if self.rows_in_view(coords.1, 1) {
+            return self.lookup.contains_key(&coords);
+        }
+        self.placed_rows
+            .get(&coords.1)
+            .map(|spans| spans.iter().any(|&(x, width)| coords.0 >= x && coords.0 < x + width))
+            == Some(true)
+    }
+
+    // the cells with something in them, as drawn where they're in view and
+    // as placed where they aren't. out of view, only every step'th cell of
+    // a node and its last are given, which is all a minimap needs.
+    fn occupied_cells(&self, step: u16) -> Vec<Coords> {
+        let mut cells: Vec<Coords> = self.lookup.keys().cloned().collect();
+        for (&y, spans) in &self.placed_rows {
+            if self.rows_in_view(y, 1) {
+                continue;
+            }
+            for &(x, width) in spans {
+                let last = x + max(width, 1) - 1;
+                cells.extend((x..last).step_by(max(step, 1) as usize).map(|x| (x, y)));
+                cells.push((last, y));
+            }
+        }
+        cells
+    }
+
+    pub fn add_or_remove_arrow(&mut self) {
+        if self.drawing_arrow.is_none() {
+            self.drawing_arrow = self.selected;
+            return;
+        }
+        let from = self.drawing_arrow.take().unwrap();
+        if let Some(arrow) = self.selected.map(|to| (from, to)) {
+            let (from, to) = arrow;
+            if self.nodes.get(&from).is_some() && self.nodes.get(&to).is_some() {
+                let contains = self.arrows.iter().fold(
+                    false,
+                    |acc, &(ref nl1, ref nl2)| if nl1 == &from &&
+                        nl2 == &to
+                    {
+                        true
+                    } else {
+                        false || acc
+                    },
+                );
+                if contains {
+                    self.arrows.retain(|e| e != &arrow);
+                } else {
+                    self.arrows.push(arrow);
+                }
+                self.dirty = true;
+            }
+        }
+    }
+
+    // NB correctness depends on invariant of the leftmost element being the
+    // value in self.drawn_at
+    fn row_bounds(&self, row: Row) -> Option<(Coords, Coords)> {
+        match row {
+            Row::Node(node_id) => self.bounds_for_lookup(node_id),
+            Row::Fold(parent_id) => {
+                // with the same room to the right as a node
+                self.folds.get(&parent_id).map(|&(left, width)| (left, (left.0 + width, left.1)))
+            }
+        }
+    }
+
+    fn bounds_for_lookup(&self, node_id: NodeID) -> Option<(Coords, Coords)> {
+        if let Some(&left) = self.drawn_at(node_id) {
+            if self.lookup(left) != Some(&node_id) {
+                // out of view, so it's only been placed
+                let width = self.placements.get(&node_id).map_or(1, |p| max(p.width, 1));
+                return Some((left, (left.0 + width, left.1)));
+            }
+            let mut rx = left.0;
+            while let Some(&cursor) = self.lookup((rx + 1, left.1)) {
+                if cursor == node_id {
+                    rx += 1;
+                } else {
+                    break;
+                }
+            }
+            // intentionally add 1 to the right side to prevent cluttering
+            let right = (rx + 1, left.1);
+            Some((left, right))
+        } else {
+            None
+        }
+    }
+
+    // *
+    // *
+    // *┌──┐
+    // *│  Drawing Functionality
+    // *└──────────────>
+    // *
+    // *
+
+    // the characters that the terminal can show
+    pub fn glyphs(&self) -> &'static Glyphs {
+        self.capabilities.glyphs()
+    }
+
+    // draws the frame off screen, then sends only the cells that differ from
+    // what's already there. the screen is cleared and drawn from scratch the
+    // first time, after a resize, and after anything else was printed.
+    pub fn draw(&mut self) {
+        trace!("draw()");
+        let start = Instant::now();
+        let next = match self.split.as_ref().map(|split| (split.layout, split.dims)) {
+            Some((layout, dims)) if !self.list_view_open() => self.draw_split(layout, dims),
+            Some((_, dims)) => {
+                // lists cover the whole terminal
+                let pane_dims = self.dims;
+                self.dims = dims;
+                let next = self.draw_grid(true);
+                self.dims = pane_dims;
+                next
+            }
+            None => self.draw_grid(true),
+        };
+        let stale = frame::take_stale();
+        let diff = match self.last_frame.take() {
+            Some(ref last) if !stale && last.dims == next.dims => last.diff(&next),
+            _ => format!("{}{}", clear::All, Grid::new(self.dims).diff(&next)),
+        };
+        frame::write(&diff);
+        self.last_frame = Some(next);
+        self.flush();
+        self.perf.borrow_mut().record("draw", start.elapsed());
+    }
+
+    fn draw_grid(&mut self, focused: bool) -> Grid {
+        let output = frame::capture(|| self.draw_frame(focused));
+        let output = self.rendering.restyle(&output);
+        let mut grid = Grid::new(self.dims);
+        grid.paint(&output);
+        grid
+    }
+
+    // each pane drawn on its own and laid side by side, the focused one
+    // last so that what it drew is what clicks are looked up in
+    fn draw_split(&mut self, layout: Layout, dims: Coords) -> Grid {
+        let focus = self.split.as_ref().map_or(Side::Left, |split| split.focus);
+        let mut grid = Grid::new(dims);
+        for &side in &[focus.other(), focus] {
+            let (x, pane_dims) = layout.pane(side, dims);
+            if side != focus {
+                self.swap_pane();
+            }
+            self.dims = pane_dims;
+            let pane = self.draw_grid(side == focus);
+            grid.blit(&pane, x);
+            if side != focus {
+                self.swap_pane();
+            }
+        }
+        let divider = layout.divider(dims);
+        let line: String = (1..dims.1 + 1)
+            .map(|y| format!("{}{}", cursor::Goto(divider, y), self.glyphs().vertical))
+            .collect();
+        grid.paint(&line);
+        grid
+    }
+
+    // what's drawn over the map and takes keys without knowing about clicks,
+    // so that a click meant for the map doesn't even change the pane
+    fn overlay_open(&self) -> bool {
+        self.matches.is_some() || self.detail.is_some()
+    }
+
+    // the views that take the whole terminal instead of drawing the map
+    fn list_view_open(&self) -> bool {
+        self.queue.is_some() || self.board.is_some() || self.agenda.is_some() ||
+        self.stale.is_some() || self.gps_view.is_some()
+    }
+
+    // the menu, completions and details belong to the focused pane
+    fn draw_frame(&mut self, focused: bool) {
+
+        // let before = time::get_time();
+
+        self.forget_drawn();
+        self.prune_focus_mode();
+        out!("{}", clear::All);
+
+        // reverse video plus the bell when a pomodoro phase ends
+        if self.flash {
+            self.flash = false;
+            out!("\x1b[?5h\x07");
+        } else {
+            out!("\x1b[?5l");
+        }
+
+        if self.list_view_open() {
+            if self.queue.is_some() {
+                self.draw_queue();
+            } else if self.agenda.is_some() {
+                self.draw_agenda();
+            } else if self.stale.is_some() {
+                self.draw_stale();
+            } else if self.board.is_some() {
+                self.draw_board();
+            } else {
+                self.draw_gps_map();
+            }
+            out!("{}", cursor::Hide);
+            return;
+        }
+
+        self.refresh_activity();
+        self.refresh_numbers();
+        self.refresh_progress();
+
+        // running timers change width every second
+        for node_id in self.running_timers() {
+            self.grapheme_cache.remove(&node_id);
+        }
+
+        // print visible nodes
+        self.draw_children_of_root();
+
+        // TODO figure out why header doesn't get shown
+        // when a root node is NOT drawn at 1,1
+        // (this only happens when draw_header() is above
+        // the call to draw_children_of_root()...
+        self.draw_header();
+
+        if self.show_logs && self.dims.0 > 4 && self.dims.1 > 7 {
+            self.draw_logs();
+        }
+
+        self.draw_arrows();
+
+        if let Some(from) = self.dragging_from {
+            self.draw_drag(from);
+        }
+
+        // preview an arrow being dragged out with the right button
+        if let Some((from, Some(to))) = self.arrow_drag {
+            let (path, (direction1, direction2)) = match self.lookup(to) {
+                Some(&to_node) if to_node != from => self.path_between_nodes(from, to_node),
+                _ => self.path_from_node_to_point(from, to),
+            };
+            let color = format!("{}{}", style::Bold, color::Fg(color::LightYellow));
+            self.draw_path(path, direction1, direction2, &color);
+        }
+
+        // show scrollbar if we've drawn anything below the bottom of the screen
+        if self.lowest_drawn > self.dims.1 {
+            self.draw_scrollbar();
+        }
+
+        if self.show_heatmap {
+            self.draw_heatmap();
+        }
+
+        self.draw_minimap();
+
+        if self.show_debug {
+            self.draw_debug_overlay();
+        }
+
+        if focused {
+            self.draw_menu();
+            self.draw_completion();
+            self.draw_detail();
+        }
+
+        self.draw_status_bar();
+
+        self.draw_missed();
+
+        if let Some(last) = self.notices.last() {
+            if self.dims.1 > 3 {
+                let more = if self.notices.len() > 1 {
+                    format!(" (+{} more)", self.notices.len() - 1)
+                } else {
+                    String::new()
+                };
+                out!(
+                    "{}{}{}{}due: {}{}{}",
+                    cursor::Goto(1, self.dims.1 - 1),
+                    clear::CurrentLine,
+                    color::Fg(color::Red),
+                    style::Invert,
+                    last,
+                    more,
+                    style::Reset
+                );
+            }
+        }
+
+        if self.conflict && self.dims.1 > 3 {
+            let key = |action, name: &str| {
+                self.config.key_for(action, self.mode()).unwrap_or_else(|| format!(":{}", name))
+            };
+            out!(
+                "{}{}{}{}file changed on disk: {} loads it and drops your changes, \
+                 {} keeps yours{}",
+                cursor::Goto(1, self.dims.1 - 1),
+                clear::CurrentLine,
+                color::Fg(color::Red),
+                style::Invert,
+                key(Action::LoadTheirs, "load_theirs"),
+                key(Action::KeepMine, "keep_mine"),
+                style::Reset
+            );
+        }
+
+        if let Some(ref e) = self.command_error {
+            out!(
+                "{}{}{}{}{}",
+                cursor::Goto(1, self.dims.1),
+                clear::CurrentLine,
+                color::Fg(color::Red),
+                e,
+                style::Reset
+            );
+        }
+
+        if let Some(ref matches) = self.matches {
+            out!(
+                "{}{}{}{} matches: {}, Esc leave",
+                cursor::Goto(1, self.dims.1),
+                clear::CurrentLine,
+                style::Reset,
+                matches.len(),
+                self.bulk_keys()
+            );
+        }
+
+        if !self.selection.is_empty() {
+            out!(
+                "{}{}{}{} children: {}, anything else leaves",
+                cursor::Goto(1, self.dims.1),
+                clear::CurrentLine,
+                style::Reset,
+                self.selection.len(),
+                self.bulk_keys()
+            );
+        }
+
+        if self.dragging_from.is_some() && !self.mouse_motion {
+            out!(
+                "{}{}{}dragging: release to place",
+                cursor::Goto(1, self.dims.1),
+                clear::CurrentLine,
+                style::Reset
+            );
+        }
+
+        if let Some(ref modal) = self.modal {
+            out!(
+                "{}{}{}{}",
+                cursor::Goto(1, self.dims.1),
+                clear::CurrentLine,
+                style::Reset,
+                modal.prompt()
+            );
+        }
+
+        out!("{}", cursor::Hide);
+
+        // let after = time::get_time();
+
+        // debug!("draw time: {}", after - before);
+    }
+
+    // clean up before a fresh drawing
+    fn forget_drawn(&mut self) {
+        self.ephemeral_max_id = std::u64::MAX;
+        self.ephemeral_nodes.clear();
+        self.lookup.clear();
+        self.drawn_at.clear();
+        self.folds.clear();
+        self.placements.clear();
+        self.placed_rows.clear();
+        self.formatted.clear();
+        self.lowest_drawn = 0;
+    }
+
+    // only between nodes inside the focus in focus mode
+    fn draw_arrows(&self) {
+        let focus_root = self.focus_root();
+        for &(ref from, ref to) in &self.arrows {
+            if let Some(focus_root) = focus_root {
+                if !self.is_parent(focus_root, *from) || !self.is_parent(focus_root, *to) {
+                    continue;
+                }
+            }
+            // both ends may be in the same iconified anchor, which has no
+            // arrow to itself
+            let (start, end) = (self.arrow_end(*from), self.arrow_end(*to));
+            if start == end && (start, end) != (*from, *to) {
+                continue;
+            }
+            let (path, (direction1, direction2)) = self.path_between_nodes(start, end);
+            let color = arrow_color(self.selected, (start, end));
+            self.draw_path(path, direction1, direction2, &color);
+        }
+    }
+
+    // the nodes and arrows alone, without the header, status bar or
+    // anything else, cropped to what's drawn. `void cat` prints this.
+    pub fn canvas(&mut self, styled: bool) -> String {
+        let (dims, view_y) = (self.dims, self.view_y);
+        self.dims = (CANVAS_WIDTH, std::u16::MAX);
+        self.view_y = 0;
+        self.forget_drawn();
+        let output = frame::capture(|| {
+            self.draw_children_of_root();
+            self.draw_arrows();
+        });
+        let output = self.rendering.restyle(&output);
+        let mut grid = Grid::new((CANVAS_WIDTH, self.lowest_drawn.saturating_add(1)));
+        grid.paint(&output);
+        self.dims = dims;
+        self.view_y = view_y;
+        grid.crop().text(styled)
+    }
+
+    // the log pane at the bottom, only formatted again once something's
+    // been logged or the terminal's changed size
+    fn draw_logs(&mut self) {
+        let key = (self.dims, self.glyphs().fill, logging::generation());
+        if self.log_pane.key != Some(key) {
+            let mut text = format!(
+                "{}{}logs{}",
+                cursor::Goto(0, self.dims.1 - 6),
+                style::Invert,
+                style::Reset
+            );
+            for _ in 0..self.dims.0 - 4 {
+                text.push(self.glyphs().fill);
+            }
+            text.push('\n');
+            // cut by characters, as a message can have any in it
+            for msg in logging::read_logs().iter().rev() {
+                let line: String = msg.chars().take(self.dims.0 as usize).collect();
+                write!(text, "\r{}\n", line).unwrap();
+            }
+            self.log_pane = LogPane {
+                key: Some(key),
+                text: text,
+                renders: self.log_pane.renders + 1,
+            };
+        }
+        out!("{}", self.log_pane.text);
+    }
+
+    fn draw_scrollbar(&self) {
+        let bar_height = max(self.dims.1, 1) - 1;
+        let normalized_lowest = max(self.lowest_drawn, 1) as f64;
+        let fraction_viewable = self.dims.1 as f64 / normalized_lowest;
+        let shade_start_fraction = self.view_y as f64 / normalized_lowest;
+
+        let shade_amount = (bar_height as f64 * fraction_viewable) as usize;
+        let shade_start = (bar_height as f64 * shade_start_fraction) as usize;
+        let shade_end = shade_start + shade_amount;
+
+        for (i, y) in (2..bar_height + 2).enumerate() {
+            if i >= shade_start && i < shade_end {
+                out!("{}{}", cursor::Goto(self.dims.0, y), self.glyphs().scroll_thumb);
+            } else {
+                out!("{}{}", cursor::Goto(self.dims.0, y), self.glyphs().vertical);
+            }
+        }
+    }
+
+    // top left screen cell of the minimap, which is only shown when the
+    // canvas doesn't fit on the screen and there's room beside it
+    fn minimap_origin(&self) -> Option<Coords> {
+        let room = self.dims.0 >= 3 * minimap::WIDTH && self.dims.1 >= 2 * minimap::HEIGHT + 2;
+        if self.show_minimap && room && self.lowest_drawn > self.dims.1 {
+            // left of the scrollbar, under the header
+            Some((self.dims.0 - minimap::WIDTH, 2))
+        } else {
+            None
+        }
+    }
+
+    fn minimap_canvas(&self) -> Coords {
+        (self.dims.0 + 1, max(self.lowest_drawn, self.view_y + self.dims.1) + 1)
+    }
+
+    fn minimap_cell(&self, (x, y): Coords) -> Option<Coords> {
+        self.minimap_origin().and_then(|(left, top)| {
+            let inside = x >= left && x < left + minimap::WIDTH && y >= top &&
+                         y < top + minimap::HEIGHT;
+            if inside { Some((x - left, y - top)) } else { None }
+        })
+    }
+
+    fn draw_minimap(&self) {
+        let (left, top) = match self.minimap_origin() {
+            Some(origin) => origin,
+            None => return,
+        };
+        let canvas = self.minimap_canvas();
+        let step = minimap::block_size(canvas).0;
+        let grid = minimap::downscale(canvas, self.occupied_cells(step));
+        let view_rows = (
+            minimap::cell_of(canvas, (0, self.view_y)).map_or(0, |(_, y)| y),
+            minimap::cell_of(canvas, (0, self.view_y + self.dims.1 - 1))
+                .map_or(minimap::HEIGHT - 1, |(_, y)| y),
+        );
+        let selected = self.selected
+            .and_then(|id| self.drawn_at(id))
+            .and_then(|&coords| minimap::cell_of(canvas, coords));
+
+        for (y, row) in grid.iter().enumerate() {
+            let y = y as u16;
+            let mut line = format!("{}", cursor::Goto(left, top + y));
+            // the rows on screen now are shown inverted
+            if y >= view_rows.0 && y <= view_rows.1 {
+                write!(line, "{}", style::Invert).unwrap();
+            }
+            for (x, &occupied) in row.iter().enumerate() {
+                if selected == Some((x as u16, y)) {
+                    write!(line,
+                           "{}{}{}",
+                           color::Fg(color::LightYellow),
+                           self.glyphs().minimap_selected,
+                           color::Fg(color::Reset))
+                        .unwrap();
+                } else {
+                    line.push(if occupied { self.glyphs().minimap_node } else { ' ' });
+                }
+            }
+            out!("{}{}", line, style::Reset);
+        }
+    }
+
+    // rulers along the top and left edges, and a mark just past the end of
+    // every run of cells that clicks resolve to the same node. drawn text
+    // is shaded in draw_node, so a mark inside shaded text or a gap before
+    // one shows hit-testing and drawing disagree.
+    fn draw_debug_overlay(&self) {
+        let mut columns = String::new();
+        for x in 1..=self.dims.0 {
+            columns.push_str(&(x % 10).to_string());
+        }
+        out!("{}{}{}{}", cursor::Goto(1, 1), style::Invert, columns, style::Reset);
+        for y in 2..self.dims.1 {
+            let row = (self.view_y + y) % 10;
+            out!("{}{}{}{}", cursor::Goto(1, y), style::Invert, row, style::Reset);
+        }
+
+        for (&(x, y), node_id) in &self.lookup {
+            let next = (x.saturating_add(1), y);
+            if self.lookup.get(&next) == Some(node_id) {
+                continue;
+            }
+            if let Some((x, y)) = self.internal_to_screen_xy(next) {
+                if x <= self.dims.0 {
+                    let mark = self.glyphs().cursor;
+                    out!("{}{}{}{}", cursor::Goto(x, y), color::Fg(color::Red), mark, style::Reset);
+                }
+            }
+        }
+    }
+
+    fn draw_children_of_root(&mut self) {
+        trace!("draw_children_of_root()");
+        if let Some(focus_root) = self.focus_root() {
+            let color = self.with_node(focus_root, |n| n.color.clone()).unwrap();
+            self.place_node(focus_root, (1, 2), 0);
+            self.draw_node(focus_root, "".to_owned(), (1, 2), false, color);
+            return;
+        }
+        let anchors = self.shown_children(self.drawing_root);
+        trace!("drawing children of root({}): {:?}", self.drawing_root, anchors);
+        for child_id in anchors {
+            let mut child_coords = self.with_node(child_id, |n| n.rooted_coords).unwrap();
+            let title = self.anchor_title(child_id);
+            let top = child_coords.1;
+            if title.is_some() {
+                child_coords.1 += 1;
+            }
+            // the whole tree is laid out first, and left at that when none
+            // of it is in view
+            let height = self.place_node(child_id, child_coords, 0);
+            if !self.rows_in_view(top, child_coords.1 - top + height) {
+                continue;
+            }
+            if let Some(title) = title {
+                self.draw_title(child_id, &title, (child_coords.0, top));
+            }
+            let child_color = self.with_node(child_id, |n| n.color.clone()).unwrap();
+            self.draw_node(child_id, "".to_owned(), child_coords, false, child_color);
+        }
+    }
+
+    // where node_id and everything shown below it go, worked out without
+    // formatting or printing any of it, returning how many rows they take
+    fn place_node(&mut self, node_id: NodeID, coords: Coords, depth: u16) -> u16 {
+        let (rows, first_line) = match self.nodes
+            .get(&node_id)
+            .or_else(|| self.ephemeral_nodes.get(&node_id)) {
+            // nothing that's added to content adds a line, so only content
+            // that already has more than one needs formatting to count them
+            Some(node) if !node.content.contains('\n') => (1, node.content.chars().count()),
+            Some(node) if node.selected => {
+                let first_line = node.content.split('\n').next().unwrap_or("").chars().count();
+                (layout::lines(&node.content).len(), first_line)
+            }
+            Some(node) => {
+                let node = node.clone();
+                let first_line = node.content.split('\n').next().unwrap_or("").chars().count();
+                (layout::lines(&self.format_node(&node).content).len(), first_line)
+            }
+            None => return 0,
+        };
+        // an iconified anchor keeps to its first line
+        let rows = if self.iconifies(node_id) { 1 } else { rows as u16 };
+        // as wide as drawable_subtree_widths reckons it, until it's drawn
+        let width = self.grapheme_cache
+            .get(&node_id)
+            .cloned()
+            .unwrap_or(1 + 3 * depth as usize + first_line) as u16;
+        self.drawn_at.insert(node_id, coords);
+        for y in coords.1..coords.1 + rows {
+            self.placed_rows.entry(y).or_default().push((coords.0, width));
+        }
+
+        let mut height = rows;
+        for child in self.shown_children(node_id) {
+            height += self.place_node(child, (coords.0, coords.1 + height), depth + 1);
+        }
+        let folded = self.folded(node_id);
+        if folded > 0 {
+            let fold_coords = (coords.0, coords.1 + height);
+            let text = fold_text(folded, self.fold_open(node_id), self.glyphs());
+            let width = 3 * (depth + 1) + text.chars().count() as u16;
+            self.folds.insert(node_id, (fold_coords, width));
+            self.placed_rows.entry(fold_coords.1).or_default().push((coords.0, width));
+            height += 1;
+        }
+        self.placements.insert(node_id, Placement { rows, height, width });
+        let bottom = coords.1 + height - 1;
+        if bottom > self.lowest_drawn {
+            self.lowest_drawn = bottom;
+        }
+        height
+    }
+
+    // whether any of the rows from top down are between the header and the
+    // status bar
+    fn rows_in_view(&self, top: u16, rows: u16) -> bool {
+        let (top, rows) = (u32::from(top), u32::from(rows));
+        let view_y = u32::from(self.view_y);
+        top + rows > view_y + 2 && top < view_y + u32::from(self.dims.1)
+    }
+
+    // a caption on the row above an anchor's tree. clicking it selects the
+    // anchor.
+    fn draw_title(&mut self, node_id: NodeID, title: &str, coords: Coords) {
+        let width = clip_width(&self.lookup, node_id, coords, self.dims.0);
+        let text = node_text(title, "", "", None, width, self.glyphs());
+        if let Some((x, y)) = self.internal_to_screen_xy(coords) {
+            out!("{}{}{}{}", cursor::Goto(x, y), style::Invert, text, style::Reset);
+        }
+        let drawn = UnicodeSegmentation::graphemes(&*text, true).count() as u16;
+        for x in coords.0..coords.0 + drawn {
+            self.lookup.insert((x, coords.1), node_id);
+        }
+    }
+
+    // the escapes for the first highlight rule matching one of the tags of
+    // node_id, leaving out the color if it has its own
+    fn highlight(&self, node_id: NodeID, own_color: bool) -> String {
+        if self.config.highlights.is_empty() {
+            return String::new();
+        }
+        match self.config.highlight(&self.tag_db.node_tags(node_id)) {
+            Some(highlight) if own_color => highlight.attributes.clone(),
+            Some(highlight) => {
+                format!("{}{}", highlight.color.as_deref().unwrap_or(""), highlight.attributes)
+            }
+            None => String::new(),
+        }
+    }
+
+    // recursively draw node and children, returning how many rows they take.
+    // they've been placed already, so what's out of view is passed over
+    // without being formatted.
+    fn draw_node(
+        &mut self,
+        node_id: NodeID,
+        prefix: String,
+        internal_coords: Coords,
+        last: bool,
+        color: String,
+    ) -> usize {
+        trace!("draw_node({})", node_id);
+        let placement = self.placements[&node_id];
+        if !self.rows_in_view(internal_coords.1, placement.height) {
+            return placement.height as usize;
+        }
+        if self.rows_in_view(internal_coords.1, placement.rows) {
+            self.draw_content(node_id, &prefix, internal_coords, last, &color);
+        }
+        let prefix = self.child_prefix(&prefix, last);
+
+        let mut drawn = placement.rows as usize;
+        // the last branch is the last one drawn, not the last child, and
+        // a fold line comes after all of them
+        let children = self.shown_children(node_id);
+        let folded = self.folded(node_id);
+        for (n, &child) in children.iter().enumerate() {
+            let last = n + 1 == children.len() && folded == 0;
+            let child_coords = (internal_coords.0, internal_coords.1 + drawn as u16);
+            drawn += self.draw_node(child, prefix.clone(), child_coords, last, color.clone());
+        }
+        if folded > 0 {
+            let fold_coords = (internal_coords.0, internal_coords.1 + drawn as u16);
+            if self.rows_in_view(fold_coords.1, 1) {
+                self.draw_fold(node_id, folded, &prefix, fold_coords, &color);
+            }
+            drawn += 1;
+        }
+        drawn
+    }
+
+    // one node's own rows, with the tree's lines before them, and where
+    // clicks on them land
+    fn draw_content(
+        &mut self,
+        node_id: NodeID,
+        prefix: &str,
+        internal_coords: Coords,
+        last: bool,
+        color: &str,
+    ) {
+        self.formatted.push(node_id);
+        let mut ephemeral = false;
+        let raw_node = self.nodes
+            .get(&node_id)
+            .or_else(|| {
+                ephemeral = true;
+                self.ephemeral_nodes.get(&node_id)
+            })
+            .cloned()
+            .unwrap();
+        let node = if raw_node.selected {
+            let mut formatted = self.format_node(&raw_node);
+            formatted.content = raw_node.content;
+            formatted
+        } else {
+            self.format_node(&raw_node)
+        };
+        let reset = &*format!("{}", color::Fg(color::Reset));
+        // anchors are drawn in their own color, which a highlight doesn't
+        // override
+        let highlight = self.highlight(node_id, prefix.is_empty());
+        let mut pre_meta = String::new();
+        let mut buf = String::new();
+        let max_width = clip_width(&self.lookup, node_id, internal_coords, self.dims.0);
+        let mut lines = layout::lines(&node.content);
+        let iconified = self.iconifies(node_id);
+        if iconified {
+            lines.truncate(1);
+        }
+        let done_style = if !node.stricken {
+            None
+        } else if self.is_revealed(node_id) {
+            Some((DIM, NO_DIM))
+        } else if self.config.strikethrough {
+            Some((STRIKE, NO_STRIKE))
+        } else {
+            Some((DIM, NO_DIM))
+        };
+
+        // only actually print it if we're in-view
+        if let Some((x, y)) = self.internal_to_screen_xy(internal_coords) {
+            write!(pre_meta, "{}{}", cursor::Goto(x, y), color).unwrap();
+            if self.show_debug {
+                write!(pre_meta, "{}", color::Bg(color::Blue)).unwrap();
+            }
+            if node.selected {
+                write!(&mut pre_meta, "{}", style::Invert).unwrap();
+            } else if self.selection.contains(&node_id) {
+                write!(&mut pre_meta, "{}", style::Underline).unwrap();
+            }
+            write!(&mut buf, "{}", pre_meta).unwrap();
+            write!(&mut buf, "{}", prefix).unwrap();
+            if prefix != "" {
+                // only anchor will have blank prefix
+                let g = self.glyphs();
+                let branch = if last { g.bottom_left } else { g.tee };
+                write!(&mut buf, "{}{}", branch, g.horizontal).unwrap();
+            }
+            if node.selected && !self.rendering.color {
+                write!(&mut buf, ">").unwrap();
+            } else if node.stricken {
+                write!(&mut buf, "{}", self.glyphs().stricken).unwrap();
+            } else if node.collapsed {
+                write!(&mut buf, "{}", self.glyphs().collapsed).unwrap();
+            } else if node.hide_stricken {
+                write!(&mut buf, "{}", self.glyphs().hide_stricken).unwrap();
+            } else {
+                write!(&mut buf, " ").unwrap();
+            }
+            // keep color for selected & tree root Fg
+            if !node.selected && prefix != "" {
+                write!(&mut buf, "{}", reset).unwrap();
+            }
+            // part of the head, so it's never struck through or cut short
+            if let Some(number) = self.numbers.get(&node_id) {
+                write!(&mut buf, "{} ", number).unwrap();
+            }
+
+            // completed children out of sight under hide_stricken, when
+            // there's no fold line to count them
+            let hidden = if node.hide_stricken && node.collapsed {
+                node.children
+                    .iter()
+                    .filter(|&&c| self.with_node(c, |c| c.stricken) == Some(true))
+                    .count()
+            } else {
+                0
+            };
+            let suffix = if iconified {
+                // what's in it changes without it being touched
+                self.grapheme_cache.remove(&node.id);
+                format!(" {}{}", self.glyphs().iconified, self.subtree_size(node_id) - 1)
+            } else if hidden > 0 {
+                format!(" {}{}", self.glyphs().done, hidden)
+            } else {
+                String::new()
+            };
+            // clicks on the age land on the node, like on the rest of it
+            let age = if !self.show_ages {
+                String::new()
+            } else if node.stricken {
+                let now = clock::now();
+                node.meta
+                    .finish_time
+                    .map(|at| format!(" (done {} ago)", humanize_age(now, at)))
+                    .unwrap_or_default()
+            } else {
+                format!(" ({})", humanize_age(clock::now(), node.meta.ctime))
+            };
+            if self.show_ages {
+                // ages grow longer as time goes by
+                self.grapheme_cache.remove(&node.id);
+            }
+            // as quiet as the age, which follows it
+            let dim_suffix = match self.groups.get(&node.id) {
+                Some(name) => format!(" {}{}{}", self.glyphs().group, name, age),
+                None => age,
+            };
+
+            let head = buf.replace(reset, "").replace(&*pre_meta, "");
+            let head_width = UnicodeSegmentation::graphemes(&*head, true).count();
+            let text_width = max_width.saturating_sub(head_width);
+            // the bar takes what room is left, and goes before the count
+            // when there's too little
+            let suffix = match self.progress.get(&node.id) {
+                Some(&(done, total)) if !iconified => {
+                    self.grapheme_cache.remove(&node.id);
+                    let count = format!(" {}/{}", done, total);
+                    let used = format!("{}{}{}{} ", lines[0], suffix, dim_suffix, count);
+                    let room = text_width
+                        .saturating_sub(UnicodeSegmentation::graphemes(&*used, true).count());
+                    let width = min(PROGRESS_WIDTH, room);
+                    if width >= 3 {
+                        let bar = plot::progress_bar(done, total, width, self.glyphs());
+                        format!("{} {}{}", suffix, bar, count)
+                    } else {
+                        format!("{}{}", suffix, count)
+                    }
+                }
+                _ => suffix,
+            };
+            self.grapheme_cache.entry(node.id).or_insert_with(|| {
+                let text = format!("{}{}{}", lines[0], suffix, dim_suffix);
+                head_width + UnicodeSegmentation::graphemes(&*text, true).count()
+            });
+            let text = node_text(&lines[0],
+                                 &suffix,
+                                 &dim_suffix,
+                                 done_style,
+                                 text_width,
+                                 self.glyphs());
+            // the paperclip goes red when a file it stands for is missing
+            let text = if !node.selected && self.broken_attachment(&node) {
+                let restore = if prefix.is_empty() { color } else { reset };
+                flag_glyph(&text,
+                           self.glyphs().attachment,
+                           &format!("{}", color::Fg(color::Red)),
+                           &format!("{}{}", restore, highlight))
+            } else {
+                text
+            };
+            buf.push_str(&highlight);
+            buf.push_str(&text);
+
+            out!("{}{}", buf, style::Reset);
+        }
+
+        let visible_graphemes = self.grapheme_cache.get(&node.id).cloned().unwrap_or_else(
+            || {
+                let visible = buf.replace(reset, "").replace(&*pre_meta, "");
+                let vg = UnicodeSegmentation::graphemes(&*visible, true).count();
+                self.grapheme_cache.insert(node.id, vg.clone());
+                vg
+            },
+        );
+
+        // long content is cut short on screen, and so is the area that
+        // clicks on it land in
+        let hit_width = min(visible_graphemes, max(max_width, 1)) as u16;
+        self.drawn_at.insert(node_id, internal_coords);
+        for x in (internal_coords.0..(internal_coords.0 + hit_width)).rev() {
+            trace!("inserting {:?} at {:?}", node_id, internal_coords);
+            self.lookup.insert((x, internal_coords.1), node_id);
+        }
+
+        // the rest of a multi-line node lines up under its first line, with
+        // the lines of the tree carried down beside it
+        let child_prefix = self.child_prefix(prefix, last);
+        let head_width = UnicodeSegmentation::graphemes(&*child_prefix, true).count();
+        for (i, line) in lines.iter().enumerate().skip(1) {
+            let coords = (internal_coords.0, internal_coords.1 + i as u16);
+            let max_width = clip_width(&self.lookup, node_id, coords, self.dims.0);
+            if let Some((x, y)) = self.internal_to_screen_xy(coords) {
+                let mut row = format!("{}{}", cursor::Goto(x, y), color);
+                if self.show_debug {
+                    write!(row, "{}", color::Bg(color::Blue)).unwrap();
+                }
+                if node.selected {
+                    write!(row, "{}", style::Invert).unwrap();
+                } else if self.selection.contains(&node_id) {
+                    write!(row, "{}", style::Underline).unwrap();
+                }
+                row.push_str(&child_prefix);
+                if !node.selected && !prefix.is_empty() {
+                    row.push_str(reset);
+                }
+                let text_width = max_width.saturating_sub(head_width);
+                row.push_str(&highlight);
+                row.push_str(&node_text(line, "", "", done_style, text_width, self.glyphs()));
+                out!("{}{}", row, style::Reset);
+            }
+            let width = head_width + UnicodeSegmentation::graphemes(&**line, true).count();
+            let hit_width = min(width, max(max_width, 1)) as u16;
+            for x in (coords.0..(coords.0 + hit_width)).rev() {
+                self.lookup.insert((x, coords.1), node_id);
+            }
+        }
+
+        let bottom = internal_coords.1 + lines.len() as u16 - 1;
+        if bottom > self.lowest_drawn {
+            self.lowest_drawn = bottom;
+        }
+    }
+
+    // what's drawn before the nodes below one with prefix in front of it
+    fn child_prefix(&self, prefix: &str, last: bool) -> String {
+        let mut child_prefix = prefix.to_owned();
+        if last {
+            child_prefix.push_str("   ");
+        } else if prefix == "" {
+            child_prefix.push_str(" ");
+        } else {
+            write!(child_prefix, "{}  ", self.glyphs().vertical).unwrap();
+        }
+        child_prefix
+    }
+
+    // the line under a parent that stands for its completed children,
+    // like `└─… 4 completed hidden`, selectable as if it were a node
+    fn draw_fold(
+        &mut self,
+        parent_id: NodeID,
+        count: usize,
+        prefix: &str,
+        coords: Coords,
+        color: &str,
+    ) {
+        let revealed = self.fold_open(parent_id);
+        let g = self.glyphs();
+        let text = fold_text(count, revealed, g);
+        let head = format!("{}{}{}", prefix, g.bottom_left, g.horizontal);
+        let max_width = clip_width(&self.lookup, parent_id, coords, self.dims.0);
+        let head_width = UnicodeSegmentation::graphemes(&*head, true).count();
+        let text = node_text(&text, "", "", None, max_width.saturating_sub(head_width), g);
+        if let Some((x, y)) = self.internal_to_screen_xy(coords) {
+            let style = if self.selected_fold == Some(parent_id) {
+                format!("{}", style::Invert)
+            } else {
+                DIM.to_owned()
+            };
+            out!("{}{}{}{}{}{}{}", cursor::Goto(x, y), color, head, color::Fg(color::Reset),
+                 style, text, style::Reset);
+        }
+        let width = head_width + UnicodeSegmentation::graphemes(&*text, true).count();
+        let width = min(width, max(max_width, 1)) as u16;
+        self.folds.insert(parent_id, (coords, width));
+        if coords.1 > self.lowest_drawn {
+            self.lowest_drawn = coords.1;
+        }
+    }
+
+    // where a drag would drop: an arrow to a new parent, or the outline of
+    // the tree where it would be placed. terminals that don't report the
+    // mouse moving only get a marker where it started, and a hint.
+    fn draw_drag(&mut self, from: Coords) {
+        let from_node = match self.lookup(from) {
+            Some(&from_node) => from_node,
+            None => return,
+        };
+        if !self.mouse_motion {
+            if let Some((x, y)) = self.internal_to_screen_xy(from) {
+                out!("{}{}{}{}", cursor::Goto(x, y), style::Invert, self.glyphs().pointer,
+                     style::Reset);
+            }
+            return;
+        }
+        let to = match self.dragging_to {
+            Some(to) if to != from => to,
+            _ => return,
+        };
+        match self.drop_target(from, to) {
+            Some(Drop::Parent(to_node)) => {
+                let (path, (direction1, direction2)) = self.path_between_nodes(from_node, to_node);
+                self.draw_path(path, direction1, direction2, &random_fg_color());
+            }
+            Some(Drop::Shift(anchor, coords)) => self.draw_ghost(anchor, coords),
+            Some(Drop::Place(coords)) => self.draw_ghost(from_node, coords),
+            None => {}
+        }
+    }
+
+    // the outline around the cells node_id's tree would take up with its
+    // corner at coords
+    fn draw_ghost(&mut self, node_id: NodeID, (x, y): Coords) {
+        let (width, height) = match self.drawable_subtree_dims(node_id) {
+            Some(dims) => dims,
+            None => return,
+        };
+        let g = self.glyphs();
+        let (left, top, right, bottom) = (x.saturating_sub(1), y.saturating_sub(1),
+                                          x + width, y + height);
+        let mut cells = vec![];
+        for cx in left..right + 1 {
+            let (upper, lower) = if cx == left {
+                (g.top_left, g.bottom_left)
+            } else if cx == right {
+                (g.top_right, g.bottom_right)
+            } else {
+                (g.horizontal, g.horizontal)
+            };
+            cells.push(((cx, top), upper));
+            cells.push(((cx, bottom), lower));
+        }
+        for cy in y..bottom {
+            cells.push(((left, cy), g.vertical));
+            cells.push(((right, cy), g.vertical));
+        }
+        let mut ghost = DIM.to_owned();
+        for ((cx, cy), c) in cells {
+            if let Some((sx, sy)) = self.internal_to_screen_xy((cx, cy)) {
+                write!(ghost, "{}{}", cursor::Goto(sx, sy), c).unwrap();
+            }
+        }
+        out!("{}{}", ghost, style::Reset);
+    }
+
+    fn draw_path(&self, internal_path: Vec<Coords>, start_dir: Dir, dest_dir: Dir, color: &str) {
+        let path: Vec<_> = internal_path
+            .iter()
+            .filter_map(|&c| self.internal_to_screen_xy(c))
+            .collect();
+        trace!("draw_path({:?}, {:?}, {:?})", path, start_dir, dest_dir);
+        let g = self.glyphs();
+        out!("{}", color);
+        if path.len() == 1 {
+            out!("{} {}", cursor::Goto(path[0].0, path[0].1), g.self_arrow)
+        } else if path.len() > 1 {
+            let first = if path[1].1 > path[0].1 {
+                match start_dir {
+                    Dir::R => g.top_right,
+                    Dir::L => g.top_left,
+                }
+            } else if path[1].1 < path[0].1 {
+                match start_dir {
+                    Dir::R => g.bottom_right,
+                    Dir::L => g.bottom_left,
+                }
+            } else {
+                g.horizontal
+            };
+
+            out!("{}{}", cursor::Goto(path[0].0, path[0].1), first);
+            for items in path.windows(3) {
+                let (p, this, n) = (items[0], items[1], items[2]);
+                let c = if p.0 == n.0 {
+                    g.vertical
+                } else if p.1 == n.1 {
+                    g.horizontal
+                } else if (this.1 < p.1 && this.0 < n.0) || (this.0 < p.0 && this.1 < n.1) {
+                    g.top_left // up+right or left+down
+                } else if (this.0 > p.0 && this.1 > n.1) || (this.1 > p.1 && this.0 > n.0) {
+                    g.bottom_right // right+up or down+left
+                } else if (this.0 > p.0 && this.1 < n.1) || (this.1 < p.1 && this.0 > n.0) {
+                    g.top_right // right+down or up+left
+                } else {
+                    g.bottom_left // down+right or left+up
+                };
+
+                out!("{}{}", cursor::Goto(this.0, this.1), c)
+            }
+            let (end_x, end_y) = (path[path.len() - 1].0, path[path.len() - 1].1);
+            let end_char = match dest_dir {
+                Dir::L => '>',
+                Dir::R => '<',
+            };
+            out!("{}{}", cursor::Goto(end_x, end_y), end_char);
+        }
+        out!("{}", style::Reset);
+    }
+
+    fn draw_header(&self) {
+        trace!("draw_header()");
+        let g = self.glyphs();
+        let mut header_text = self.with_node(self.drawing_root, |node| node.content.clone())
+            .unwrap();
+
+        if !self.tabs.is_empty() {
+            header_text = format!("{} {} {}", self.tab_strip(), g.vertical, header_text);
+        }
+
+        if self.should_auto_arrange() {
+            header_text.push_str(" [auto-arrange] ");
+        }
+
+        let (plot, finished_today) = self.last_week_of_done_tasks();
+        let plot_line = format!("{}{}{}({} today)", g.vertical, plot, g.vertical, finished_today);
+
+        header_text.push_str(&*plot_line);
+
+        if self.dims.0 > header_text.len() as u16 && self.dims.1 > 1 {
+            let mut sep = format!(
+                "{}{}{}{}",
+                cursor::Goto(0, 1),
+                style::Invert,
+                header_text,
+                style::Reset
+            );
+            let text_len = header_text.chars().count();
+            for _ in 0..(max(self.dims.0 as usize, text_len) - text_len) {
+                sep.push(self.glyphs().fill);
+            }
+            out!("{}\n", sep);
+        }
+    }
+
+    // numbers follow the order of siblings, so any edit can shift them.
+    // they're counted from the home screen, so a node keeps its number when
+    // drilled into.
+    fn refresh_numbers(&mut self) {
+        let numbers: HashMap<NodeID, String> = if self.config.numbering {
+            number_tree(self, 0, self.config.number_hidden).into_iter().collect()
+        } else {
+            HashMap::new()
+        };
+        if numbers != self.numbers {
+            self.grapheme_cache.clear();
+            self.numbers = numbers;
+        }
+    }
+
+    // one pass over the drawn tree, rather than one per parent with a bar
+    fn refresh_progress(&mut self) {
+        let mut progress = HashMap::new();
+        match self.config.progress {
+            Some(Progress::Descendants) => {
+                self.tally_progress(self.drawing_root, &mut progress);
+            }
+            Some(Progress::Children) => {
+                let root = self.drawing_root;
+                for node_id in self.recursive_child_filter_map(root, &mut |n| Some(n.id)) {
+                    let counts = self.progress_of(node_id, Progress::Children);
+                    if counts.1 > 0 {
+                        progress.insert(node_id, counts);
+                    }
+                }
+            }
+            None => {}
+        }
+        self.progress = progress;
+    }
+
+    // how many of node_id's descendants are done, and how many there are,
+    // noting the same for each of them that has any along the way
+    fn tally_progress(&self,
+                      node_id: NodeID,
+                      progress: &mut HashMap<NodeID, (usize, usize)>)
+                      -> (usize, usize) {
+        let (mut done, mut total) = (0, 0);
+        for child in self.with_node(node_id, |n| n.children.clone()).unwrap_or_default() {
+            let (child_done, child_total) = self.tally_progress(child, progress);
+            let stricken = self.with_node(child, |n| n.stricken).unwrap_or(false);
+            done += child_done + stricken as usize;
+            total += child_total + 1;
+        }
+        if total > 0 {
+            progress.insert(node_id, (done, total));
+        }
+        (done, total)
+    }
+
+    // how much of what's under node_id is done, out of how much
+    pub fn progress_of(&self, node_id: NodeID, scope: Progress) -> (usize, usize) {
+        let stricken: Vec<bool> = match scope {
+            Progress::Children => {
+                self.with_node(node_id, |n| n.children.clone())
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|&c| self.with_node(c, |c| c.stricken))
+                    .collect()
+            }
+            Progress::Descendants => {
+                // the first is node_id itself
+                self.recursive_child_filter_map(node_id, &mut |n| Some(n.stricken))
+                    .into_iter()
+                    .skip(1)
+                    .collect()
+            }
+        };
+        (stricken.iter().filter(|&&s| s).count(), stricken.len())
+    }
+
+    // recounting every node is too slow to do on every keystroke
+    fn refresh_activity(&mut self) {
+        let now = time::now();
+        let now_s = now.to_timespec().sec as u64;
+        if now_s / 60 == self.activity_minute && !self.activity.is_empty() {
+            return;
+        }
+        let finished: Vec<u64> = self.nodes.values().filter_map(|n| n.meta.finish_time).collect();
+        self.activity = plot::daily_counts(&finished, now_s, now.tm_utcoff as i64, ACTIVITY_DAYS);
+        self.activity_minute = now_s / 60;
+    }
+
+    // overlaid in the top right corner, below the header
+    fn draw_heatmap(&self) {
+        trace!("draw_heatmap()");
+        if self.dims.0 < 12 || self.dims.1 < 11 {
+            return;
+        }
+        // two columns for weekday names, two for a border
+        let weeks = min(52, self.dims.0 as usize - 4);
+        let x = self.dims.0 - weeks as u16 - 3;
+        for (i, line) in plot::heatmap(self, weeks).iter().enumerate() {
+            out!("{}{} {}", cursor::Goto(x, i as u16 + 2), style::Reset, line);
+        }
+    }
+
+    fn draw_status_bar(&self) {
+        trace!("draw_status_bar()");
+        if self.dims.1 < 3 {
+            return;
+        }
+        // sit just above the log separator when logs are shown
+        let y = if self.show_logs && self.dims.0 > 4 && self.dims.1 > 7 {
+            self.dims.1 - 7
+        } else {
+            self.dims.1
+        };
+        out!(
+            "{}{}{}{}",
+            cursor::Goto(1, y),
+            style::Invert,
+            self.status_text(),
+            style::Reset
+        );
+    }
+
+    fn status_text(&self) -> String {
+        let width = self.dims.0 as usize;
+        let mut fields = vec![];
+        let mut path = String::new();
+
+        if self.config.modal {
+            let mode = match self.mode {
+                Mode::Normal => "NORMAL",
+                Mode::Insert => "INSERT",
+            };
+            fields.push(mode.to_owned());
+        }
+
+        if let Some(selected_id) = self.selected {
+            let names: Vec<String> = self.path_to_node(selected_id)
+                .into_iter()
+                .filter_map(|id| {
+                    let name = self.with_node(id, |n| printable(&n.content, self.glyphs()))?;
+                    Some(match self.numbers.get(&id) {
+                        Some(number) => format!("{} {}", number, name),
+                        None => name,
+                    })
+                })
+                .collect();
+            path = names.join(&format!(" {} ", self.glyphs().path_separator));
+
+            let now = clock::now();
+            self.with_node(selected_id, |n| {
+                fields.push(format!("created {} ago", format_age(now, n.meta.ctime)));
+                if n.stricken {
+                    if let Some(finish_time) = n.meta.finish_time {
+                        fields.push(format!("done {} ago", format_age(now, finish_time)));
+                    }
+                }
+            });
+
+            let (estimated, spent) = estimate::rollup(self.recursive_child_filter_map(
+                selected_id,
+                &mut |n| Some((n.stricken, n.meta.estimate, n.meta.tracked_at(now))),
+            ));
+            if estimated > 0 {
+                fields.push(format!(
+                    "est {} / spent {}",
+                    estimate::format(estimated),
+                    estimate::format(spent)
+                ));
+            }
+
+            if self.show_counts {
+                fields.push(format!(
+                    "{} nodes, {} words",
+                    self.subtree_size(selected_id),
+                    self.subtree_word_count(selected_id)
+                ));
+            }
+
+            let tags = self.tag_db.node_tags(selected_id);
+            if !tags.is_empty() {
+                let tags: Vec<String> = tags.into_iter().map(|t| format!("#{}", t)).collect();
+                fields.push(tags.join(" "));
+            }
+        }
+
+        if let Some(ref pomodoro) = self.pomodoro {
+            fields.push(pomodoro.indicator(clock::now()));
+        }
+
+        if self.show_debug {
+            if let Some((coords, node_id)) = self.last_mouse {
+                let target = node_id.map_or("nothing".to_owned(), |id| format!("node {}", id));
+                fields.push(format!("mouse {:?} {} {}", coords, self.glyphs().pointer, target));
+            }
+        }
+
+        if let Some((register, _)) = self.recording {
+            fields.push(format!("recording @{}", register));
+        }
+
+        if let Some(name) = self.file_name() {
+            fields.push(name);
+        }
+
+        if width > 40 {
+            fields.push(plot::sparkline(&self.activity, ACTIVITY_DAYS, self.glyphs()));
+        }
+
+        let divider = format!(" {} ", self.glyphs().vertical);
+        let right = fields.join(&divider);
+        let right_len = UnicodeSegmentation::graphemes(&*right, true).count();
+
+        let mut status = if path.is_empty() {
+            right
+        } else if right.is_empty() {
+            truncate_left(&path, width, self.glyphs())
+        } else {
+            let path_width = width.saturating_sub(right_len + 3);
+            format!("{}{}{}", truncate_left(&path, path_width, self.glyphs()), divider, right)
+        };
+
+        let mut len = UnicodeSegmentation::graphemes(&*status, true).count();
+        if len > width {
+            status = UnicodeSegmentation::graphemes(&*status, true).take(width).collect();
+            len = width;
+        }
+        for _ in len..width {
+            status.push(' ');
+        }
+        status
+    }
+
+    fn path_from_node_to_point(&self, start: NodeID, to: Coords) -> (Vec<Coords>, (Dir, Dir)) {
+        // TODO this is mostly copypasta from path_between_nodes, DRY
+        trace!("getting path between node {} and point {:?}", start, to);
+        let startbounds = self.bounds_for_lookup(start);
+        if startbounds.is_none() {
+            trace!("path_from_node_to_point exiting early, point not drawn");
+            return (vec![], (Dir::R, Dir::R));
+        }
+        let (s1, s2) = startbounds.unwrap();
+        let init = (self.path(s2, to), (Dir::R, Dir::R));
+        let paths = vec![(self.path(s1, to), (Dir::L, Dir::R))];
+        paths.into_iter().fold(
+            init,
+            |(spath, sdirs), (path, dirs)| if path.len() <
+                spath.len()
+            {
+                (path, dirs)
+            } else {
+                (spath, sdirs)
+            },
+        )
+    }
+
+    fn path_between_nodes(&self, start: NodeID, to: NodeID) -> (Vec<Coords>, (Dir, Dir)) {
+        trace!("getting path between nodes {} and {}", start, to);
+        let startbounds = self.bounds_for_lookup(start);
+        let tobounds = self.bounds_for_lookup(to);
+        if startbounds.is_none() || tobounds.is_none() {
+            trace!("path_between_nodes exiting early, point not drawn");
+            return (vec![], (Dir::R, Dir::R));
+        }
+        let (s1, s2) = startbounds.unwrap();
+        let (t1, t2) = tobounds.unwrap();
+
+        let init = (self.path(s2, t2), (Dir::R, Dir::R));
+        let paths = vec![
+            (self.path(s1, t2), (Dir::L, Dir::R)),
+            (self.path(s2, t1), (Dir::R, Dir::L)),
+            (self.path(s1, t1), (Dir::L, Dir::L)),
+        ];
+        paths.into_iter().fold(
+            init,
+            |(spath, sdirs), (path, dirs)| if path.len() <
+                spath.len()
+            {
+                (path, dirs)
+            } else {
+                (spath, sdirs)
+            },
+        )
+    }
+
+    fn path(&self, start: Coords, dest: Coords) -> Vec<Coords> {
+        let started = Instant::now();
+        let path = self.find_path(start, dest);
+        self.perf.borrow_mut().record("path", started.elapsed());
+        path
+    }
+
+    fn find_path(&self, start: Coords, dest: Coords) -> Vec<Coords> {
+        trace!(
+            "path({:?}, {:?} (screen size: {} x {})",
+            start,
+            dest,
+            self.dims.0,
+            self.dims.1
+        );
+        fn perms(c: Coords) -> Vec<Coords> {
+            vec![
+                (c.0 + 1, c.1),
+                (max(c.0, 1) - 1, c.1),
+                (c.0, c.1 + 1),
+                // we ensure Y is >= 1, since Goto will panic otherwise
+                (c.0, max(c.1, 2) - 1),
+            ]
+        }
+        // maps from location to previous location
+        let mut visited: HashMap<Coords, Coords> = HashMap::new();
+        let mut pq = BinaryHeap::new();
+
+        let mut cursor = start;
+        trace!("starting draw");
+        while cursor != dest {
+            for neighbor in perms(cursor) {
+                if (!(neighbor.0 >= self.dims.0) && !(neighbor.1 >= self.dims.1 + self.view_y) &&
+                        !self.occupied(neighbor) || neighbor == dest) &&
+                    !visited.contains_key(&neighbor)
+                {
+                    let c = std::u16::MAX - cost(neighbor, dest);
+                    pq.push((c, neighbor));
+                    visited.insert(neighbor, cursor);
+                }
+            }
+            if let Some((_, coords)) = pq.pop() {
+                cursor = coords;
+            } else {
+                trace!("no path, possible node overlap");
+                return vec![];
+            }
+            // for tracing: show entire search path
+            // self.draw_path(visited.clone().keys().map(|k| *k).collect());
+        }
+        trace!("done draw, starting backtrack");
+
+        let mut back_cursor = dest;
+        let mut path = vec![dest];
+        while back_cursor != start {
+            let prev = visited.get(&back_cursor).unwrap();
+            path.push(*prev);
+            back_cursor = *prev;
+        }
+        path.reverse();
+        trace!("leaving path()");
+        path
+    }
+
+    fn last_week_of_done_tasks(&self) -> (String, usize) {
+        let now = clock::now();
+        let day_in_sec = 60 * 60 * 24;
+        let last_week = now - (day_in_sec * 7);
+        let tasks_finished_in_last_week = self.recursive_child_filter_map(0, &mut |n: &Node| {
+            let f = n.meta.finish_time;
+            if let Some(t) = f {
+                if t > last_week { Some(t) } else { None }
+            } else {
+                None
+            }
+        });
+        let mut counts = BTreeMap::new();
+        for d in 0..7 {
+            let t = now - (d * day_in_sec);
+            let normalized_t = t / day_in_sec * day_in_sec;
+            counts.insert(normalized_t, 0);
+        }
+        for t in &tasks_finished_in_last_week {
+            let normalized_t = t / day_in_sec * day_in_sec;
+            let cur = counts.remove(&normalized_t).unwrap_or(0);
+            counts.insert(normalized_t, cur + 1);
+        }
+        let today_normalized = now / day_in_sec * day_in_sec;
+        let counts_clone = counts.clone();
+        let finished_today = counts_clone.get(&today_normalized).unwrap();
+        let week_line: Vec<i64> = counts.into_iter().map(|(_, v)| v).collect();
+        let plot = plot::plot_sparkline(week_line, self.glyphs());
+        (plot, *finished_today as usize)
+    }
+
+    fn format_node(&mut self, raw_node: &Node) -> Node {
+        lazy_static! {
+            //// general subtree population and modification
+            // limit shows the top N results.
+            static ref RE_LIMIT: Regex = Regex::new(r"#limit=(\d+)").unwrap();
+            static ref RE_TAGGED: Regex = Regex::new(r"#tagged=(\S+)").unwrap();
+            static ref RE_REV: Regex = Regex::new(r"#rev\b").unwrap();
+            static ref RE_DONE: Regex = Regex::new(r"#done\b").unwrap();
+            static ref RE_OPEN: Regex = Regex::new(r"#open\b").unwrap();
+            // since defaults to last week
+            static ref RE_SINCE: Regex = Regex::new(r"#since=(\S+)").unwrap();
+            // until defaults until now
+            static ref RE_UNTIL: Regex = Regex::new(r"#until=(\S+)").unwrap();
+
+            //// plot specific
+            // plot can be {new,done}
+            static ref RE_PLOT: Regex = Regex::new(r"#plot=(\S+)").unwrap();
+
+            //// time tracking
+            // rollup shows the time tracked on the whole subtree
+            static ref RE_ROLLUP: Regex = Regex::new(r"#rollup\b").unwrap();
+            // n is the number of buckets
+            static ref RE_N: Regex = Regex::new(r"#n=(\d+)").unwrap();
+        }
+
+        // TODO detect and avoid cycles
+        // NB avoid cycles
+        let mut node = raw_node.clone();
+
+        // for tagged queries, AND queries together
+        let mut tagged_children: Option<HashSet<NodeID>> = None;
+        for tag in &re_matches::<String>(&RE_TAGGED, &*node.content) {
+            let children = self.tag_db.tag_to_nodes(tag);
+            if let Some(children_acc) = tagged_children {
+                let children_new = children.into_iter().collect();
+                let intersection = children_acc.intersection(&children_new);
+                tagged_children = Some(intersection.cloned().collect());
+            } else {
+                tagged_children = Some(children.into_iter().collect());
+            }
+        }
+        let queried_nodes = tagged_children
+            .map(|tc| tc.into_iter().collect())
+            .unwrap_or(vec![]);
+
+        let mut since_opt = None;
+        let mut until_opt = None;
+        if RE_DONE.is_match(&*node.content) {
+            for child in node.children.clone() {
+                let done = self.with_node(child, |c| c.stricken).unwrap();
+                if !done {
+                    node.children.retain(|&c| c != child);
+                }
+            }
+        }
+        if RE_OPEN.is_match(&*node.content) {
+            for child in node.children.clone() {
+                let open = self.with_node(child, |c| !c.stricken).unwrap();
+                if !open {
+                    node.children.retain(|&c| c != child);
+                }
+            }
+        }
+        if let Some(since) = re_matches::<String>(&RE_SINCE, &*node.content).iter().nth(
+            0,
+        )
+        {
+            since_opt = dateparse(since.clone());
+            if let Some(cutoff) = since_opt {
+                let mut new = vec![];
+                for &c in &node.children {
+                    let valid = self.with_node(c, |c| c.meta.mtime >= cutoff).unwrap_or(
+                        false,
+                    );
+                    if valid {
+                        new.push(c);
+                    }
+                }
+                node.children = new;
+            }
+        }
+        if let Some(until) = re_matches::<String>(&RE_UNTIL, &*node.content).iter().nth(
+            0,
+        )
+        {
+            until_opt = dateparse(until.clone());
+            if let Some(cutoff) = until_opt {
+                let mut new = vec![];
+                for &c in &node.children {
+                    let valid = self.with_node(c, |c| c.meta.mtime <= cutoff).unwrap_or(
+                        false,
+                    );
+                    if valid {
+                        new.push(c);
+                    }
+                }
+                node.children = new;
+            }
+        }
+        if RE_REV.is_match(&*node.content) {
+            node.children = node.children.into_iter().rev().collect();
+        }
+        if let Some(&limit) = re_matches(&RE_LIMIT, &*node.content).iter().nth(0) {
+            node.children.truncate(limit);
+        }
+
+        let re_n = re_matches::<usize>(&RE_N, &*node.content);
+        let n_opt = re_n.iter().nth(0);
+        if let Some(plot) = re_matches::<String>(&RE_PLOT, &*node.content).iter().nth(0) {
+            let now = clock::now();
+            let buckets = n_opt.cloned().unwrap_or(7);
+            let since = since_opt.unwrap_or_else(|| now - 60 * 60 * 24 * 7);
+            let until = until_opt.unwrap_or_else(|| now);
+
+            node.content = match plot.as_str() {
+                "done" => self.plot(queried_nodes, PlotType::Done, buckets, since, until),
+                "new" => self.plot(queried_nodes, PlotType::New, buckets, since, until),
+                _ => node.content,
+            };
+        }
+
+        let now = clock::now();
+        let tracked = if RE_ROLLUP.is_match(&*node.content) {
+            self.recursive_child_filter_map(node.id, &mut |n| Some(n.meta.tracked_at(now)))
+                .into_iter()
+                .sum()
+        } else {
+            node.meta.tracked_at(now)
+        };
+        if tracked > 0 || node.meta.timer_running() {
+            let g = self.glyphs();
+            let icon = if node.meta.timer_running() { g.timer_running } else { g.timer_stopped };
+            write!(node.content, " {}{}", icon, format_duration(tracked)).unwrap();
+        }
+        if let Some(estimate) = node.meta.estimate {
+            write!(node.content, " ({})", estimate::format(estimate)).unwrap();
+        }
+
+        if !url::find_urls(&raw_node.content).is_empty() {
+            write!(node.content, " {}", self.glyphs().link).unwrap();
+        }
+        if !node.meta.attachments.is_empty() {
+            write!(node.content, " {}", self.glyphs().attachment).unwrap();
+        }
+
+        if let Some(slot) = self.bookmark_slot(node.id) {
+            write!(node.content, " [{}]", slot).unwrap();
+        }
+        node
+    }
+
+    fn plot(
         &self,
         queried_nodes: Vec<NodeID>,
         kind: PlotType,
@@ -2268,23 +7493,3585 @@ impl Screen {
                     }
                 }
             });
-            nodes.append(&mut new);
+            nodes.append(&mut new);
+        }
+        let plot = plot::bounded_count_sparkline(nodes,
+                                                 since as i64,
+                                                 until as i64,
+                                                 buckets,
+                                                 self.glyphs());
+        format!("|{}|", plot)
+    }
+}
+
+enum SearchDirection {
+    Forward,
+    Backward,
+}
+
+enum PlotType {
+    New,
+    Done,
+}
+
+fn visible(view_y: u16, height: u16, y: u16) -> bool {
+    y > view_y && y < view_y + height
+}
+
+// arrows to or from the selected node stand out, and the rest fade back
+fn arrow_color(selected: Option<NodeID>, (from, to): (NodeID, NodeID)) -> String {
+    match selected {
+        None => random_fg_color(),
+        Some(id) if id == from || id == to => {
+            format!("{}{}", style::Bold, color::Fg(color::LightYellow))
+        }
+        Some(_) => format!("{}{}", style::Faint, color::Fg(color::LightBlack)),
+    }
+}
+
+// what a fold line says about the completed children behind it
+fn fold_text(count: usize, revealed: bool, glyphs: &Glyphs) -> String {
+    let state = if revealed { "shown" } else { "hidden" };
+    format!("{} {} completed {}", glyphs.ellipsis, count, state)
+}
+
+// a node's content, struck through or dimmed when it's done, then a plain
+// suffix, all cut to width graphemes with an ellipsis. the style is always
+// closed again right after the content, even when the cut falls inside it
+fn node_text(
+    content: &str,
+    suffix: &str,
+    dim_suffix: &str,
+    done_style: Option<(&str, &str)>,
+    width: usize,
+    glyphs: &Glyphs,
+) -> String {
+    let content = printable(content, glyphs);
+    let content: Vec<&str> = UnicodeSegmentation::graphemes(&*content, true).collect();
+    let suffix: Vec<&str> = UnicodeSegmentation::graphemes(suffix, true).collect();
+    let dim_suffix: Vec<&str> = UnicodeSegmentation::graphemes(dim_suffix, true).collect();
+    let cut = content.len() + suffix.len() + dim_suffix.len() > width;
+    let room = if cut { width.saturating_sub(1) } else { width };
+    let content = &content[..min(content.len(), room)];
+    let suffix = &suffix[..min(suffix.len(), room - content.len())];
+    let dim_suffix = &dim_suffix[..min(dim_suffix.len(), room - content.len() - suffix.len())];
+
+    let mut text = match done_style {
+        Some((start, end)) if !content.is_empty() => {
+            format!("{}{}{}", start, content.concat(), end)
+        }
+        _ => content.concat(),
+    };
+    text.push_str(&suffix.concat());
+    if !dim_suffix.is_empty() {
+        write!(text, "{}{}{}", DIM, dim_suffix.concat(), NO_DIM).unwrap();
+    }
+    if cut && width > 0 {
+        text.push(glyphs.ellipsis);
+    }
+    text
+}
+
+// text with the last of glyph in it drawn in style, and then restore
+fn flag_glyph(text: &str, glyph: char, style: &str, restore: &str) -> String {
+    match text.rfind(glyph) {
+        Some(idx) => {
+            let end = idx + glyph.len_utf8();
+            format!("{}{}{}{}{}", &text[..idx], style, glyph, restore, &text[end..])
+        }
+        None => text.to_owned(),
+    }
+}
+
+// how many cells a node drawn at coords has before it reaches the right
+// edge of the screen or comes within a cell of a node already drawn on the
+// same row
+// waits for $EDITOR to be done with the file, then reads back what it left
+fn edit_file(path: &str) -> error::Result<String> {
+    let ed = env::var("EDITOR").unwrap_or("vim".to_owned());
+    let status = process::Command::new(&ed).arg(path).status()?;
+    if !status.success() {
+        warn!("{} exited with {}", ed, status);
+    }
+    let data = fs::read(path).map_err(error::Error::file(path))?;
+    Ok(String::from_utf8(data)?)
+}
+
+fn clip_width(
+    lookup: &HashMap<Coords, NodeID>,
+    node_id: NodeID,
+    (x, y): Coords,
+    screen_width: u16,
+) -> usize {
+    let edge = (max(screen_width, 1 + x) - 1 - x) as usize;
+    (1..edge)
+        .find(|&dx| match lookup.get(&(x + dx as u16, y)) {
+            Some(&other) => other != node_id,
+            None => false,
+        })
+        .map(|dx| dx - 1)
+        .unwrap_or(edge)
+}
+
+// control characters that made it into content from older files or other
+// programs are shown as their control pictures, like ␛, rather than being
+// sent to the terminal
+fn printable(text: &str, glyphs: &Glyphs) -> String {
+    text.chars()
+        .map(|c| match c as u32 {
+            _ if c.is_control() && !glyphs.control_pictures => glyphs.replacement,
+            n @ 0x00..=0x1f => std::char::from_u32(0x2400 + n).unwrap(),
+            0x7f => '␡',
+            _ if c.is_control() => glyphs.replacement,
+            _ => c,
+        })
+        .collect()
+}
+
+// keep the rightmost graphemes of text, marking the cut with an ellipsis
+fn truncate_left(text: &str, width: usize, glyphs: &Glyphs) -> String {
+    let graphemes: Vec<&str> = UnicodeSegmentation::graphemes(text, true).collect();
+    if graphemes.len() <= width {
+        text.to_owned()
+    } else if width == 0 {
+        String::new()
+    } else {
+        let mut truncated = glyphs.ellipsis.to_string();
+        truncated.push_str(&*graphemes[graphemes.len() - (width - 1)..].concat());
+        truncated
+    }
+}
+
+// keep the leftmost graphemes of text, marking the cut with an ellipsis
+fn truncate_right(text: &str, width: usize, glyphs: &Glyphs) -> String {
+    let graphemes: Vec<&str> = UnicodeSegmentation::graphemes(text, true).collect();
+    if graphemes.len() <= width {
+        text.to_owned()
+    } else if width == 0 {
+        String::new()
+    } else {
+        let mut truncated = graphemes[..width - 1].concat();
+        truncated.push(glyphs.ellipsis);
+        truncated
+    }
+}
+
+fn format_age(now: u64, then: u64) -> String {
+    let secs = now.saturating_sub(then);
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 60 * 60 {
+        format!("{}m", secs / 60)
+    } else if secs < 60 * 60 * 24 {
+        format!("{}h", secs / (60 * 60))
+    } else {
+        format!("{}d", secs / (60 * 60 * 24))
+    }
+}
+
+#[test]
+fn test_path_to_node() {
+    fn add(screen: &mut Screen, parent_id: NodeID, content: &str) -> NodeID {
+        let id = screen.new_node();
+        screen.with_node_mut_no_meta(id, |n| {
+            n.parent_id = parent_id;
+            n.content = content.to_owned();
+        });
+        screen.with_node_mut_no_meta(parent_id, |p| p.children.push(id));
+        id
+    }
+
+    let mut screen = Screen::default();
+    let project = add(&mut screen, 0, "project");
+    let task = add(&mut screen, project, "task");
+    let subtask = add(&mut screen, task, "subtask");
+
+    assert_eq!(screen.path_to_node(subtask), vec![project, task, subtask]);
+    assert_eq!(screen.path_to_node(project), vec![project]);
+    assert_eq!(screen.path_to_node(0), vec![]);
+    assert_eq!(screen.path_to_node(1000), vec![]);
+
+    // drilling down shortens the path to start below the new root
+    screen.drawing_root = project;
+    assert_eq!(screen.path_to_node(subtask), vec![task, subtask]);
+    assert_eq!(screen.path_to_node(project), vec![]);
+}
+
+#[test]
+fn test_delete_confirmation() {
+    let mut screen = Screen::default();
+    screen.is_test = true;
+    let root = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children.push(root));
+    for i in 0..6 {
+        let child = screen.new_node();
+        screen.with_node_mut_no_meta(child, |n| {
+            n.parent_id = root;
+            n.content = format!("child {}", i);
+        });
+        screen.with_node_mut_no_meta(root, |n| n.children.push(child));
+    }
+    screen.with_node_mut_no_meta(root, |n| n.content = "project".to_owned());
+
+    // answering anything but y keeps the subtree
+    screen.select_node(root);
+    screen.handle_event(Event::Key(Key::Delete));
+    assert!(screen.exists(root));
+    screen.handle_event(Event::Key(Key::Char('n')));
+    assert!(screen.exists(root));
+    assert_eq!(screen.nodes.len(), 8);
+    assert_eq!(screen.with_node(root, |n| n.content.clone()).unwrap(), "project");
+
+    screen.handle_event(Event::Key(Key::Delete));
+    screen.handle_event(Event::Key(Key::Char('y')));
+    assert!(!screen.exists(root));
+    assert_eq!(screen.nodes.len(), 1);
+
+    // small subtrees are deleted without asking
+    screen.delete_confirm_threshold = 10;
+    screen.undo_delete();
+    screen.handle_event(Event::Key(Key::Delete));
+    assert!(!screen.exists(root));
+}
+
+#[test]
+fn test_trash_restore() {
+    let mut screen = Screen::default();
+    let project = screen.new_node();
+    let task = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children.push(project));
+    screen.with_node_mut_no_meta(project, |n| {
+        n.content = "project".to_owned();
+        n.children.push(task);
+    });
+    screen.with_node_mut_no_meta(task, |n| {
+        n.parent_id = project;
+        n.content = "task #cat".to_owned();
+    });
+    screen.tag_db.reindex(task, "task #cat".to_owned());
+
+    // undo puts the subtree back where it was
+    screen.select_node(task);
+    screen.delete_selected(false);
+    assert!(!screen.exists(task));
+    assert_eq!(screen.trash.len(), 1);
+    assert_eq!(screen.tag_db.tag_to_nodes("cat"), vec![]);
+    screen.undo_delete();
+    assert_eq!(screen.parent(task), Some(project));
+    assert_eq!(screen.tag_db.tag_to_nodes("cat"), vec![task]);
+    assert!(screen.trash.is_empty());
+
+    // restoring after the parent is gone makes a new anchor
+    screen.delete_selected(false);
+    screen.select_node(project);
+    screen.delete_selected(false);
+    assert_eq!(screen.trash.entries()[1].root, task);
+    let entry = screen.trash.take(1).unwrap();
+    screen.restore_trash_entry(entry, Some(project));
+    assert_eq!(screen.parent(task), Some(0));
+    assert!(screen.with_node(0, |n| n.children.contains(&task)).unwrap());
+
+    // emptied nodes are not worth keeping
+    let blank = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children.push(blank));
+    screen.select_node(blank);
+    screen.unselect();
+    assert!(!screen.exists(blank));
+    assert_eq!(screen.trash.len(), 1);
+}
+
+#[test]
+fn test_archive() {
+    let mut screen = Screen::default();
+    let ids: Vec<NodeID> = (0..4).map(|_| screen.new_node()).collect();
+    let (project, old, old_child, recent) = (ids[0], ids[1], ids[2], ids[3]);
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![project]);
+    screen.with_node_mut_no_meta(project, |n| n.children = vec![old, recent]);
+    screen.with_node_mut_no_meta(old, |n| n.children = vec![old_child]);
+    for &(id, parent_id) in &[(project, 0), (old, project), (old_child, old), (recent, project)] {
+        screen.with_node_mut_no_meta(id, |n| {
+            n.parent_id = parent_id;
+            n.content = "x".to_owned();
+        });
+    }
+    let now = time::get_time().sec as u64;
+    screen.with_node_mut_no_meta(old, |n| {
+        n.stricken = true;
+        n.meta.finish_time = Some(now - 40 * 24 * 60 * 60);
+    });
+    screen.with_node_mut_no_meta(recent, |n| {
+        n.stricken = true;
+        n.meta.finish_time = Some(now - 60);
+    });
+    screen.arrows = vec![(recent, old_child), (project, recent)];
+
+    assert_eq!(screen.archive_stricken(30), 1);
+    assert!(!screen.exists(old) && !screen.exists(old_child));
+    assert_eq!(screen.with_node(project, |n| n.children.clone()), Some(vec![recent]));
+    assert_eq!(screen.arrows, vec![(project, recent)]);
+
+    // survives a round trip through the work file
+    let mut screen = serialization::deserialize_screen(serialization::serialize_screen(&screen))
+        .unwrap();
+    assert_eq!(screen.archive.len(), 1);
+
+    // with the old parent gone it comes back as an anchor, and arrows from
+    // deleted nodes stay gone
+    screen.select_node(project);
+    screen.delete_selected(false);
+    assert_eq!(screen.restore_archived(0), Some(old));
+    assert_eq!(screen.parent(old), Some(0));
+    assert_eq!(screen.parent(old_child), Some(old));
+    assert!(screen.arrows.is_empty());
+    assert!(screen.archive.is_empty());
+}
+
+#[test]
+fn test_selection_history() {
+    fn add(screen: &mut Screen, parent_id: NodeID) -> NodeID {
+        let id = screen.new_node();
+        screen.with_node_mut_no_meta(id, |n| {
+            n.parent_id = parent_id;
+            n.content = format!("node {}", id);
+        });
+        screen.with_node_mut_no_meta(parent_id, |p| p.children.push(id));
+        id
+    }
+
+    let mut screen = Screen::default();
+    let a = add(&mut screen, 0);
+    let a1 = add(&mut screen, a);
+    let a2 = add(&mut screen, a);
+    let b = add(&mut screen, 0);
+    let b1 = add(&mut screen, b);
+
+    // stepping through siblings is recorded as a single jump
+    screen.select_node(a1);
+    screen.select_node(a2);
+    screen.select_node(b1);
+    screen.history_back();
+    assert_eq!(screen.selected, Some(a2));
+    screen.history_back();
+    assert_eq!(screen.selected, Some(a2));
+    screen.history_forward();
+    assert_eq!(screen.selected, Some(b1));
+
+    // going back expands collapsed ancestors
+    screen.with_node_mut_no_meta(a, |n| n.collapsed = true);
+    screen.history_back();
+    assert_eq!(screen.selected, Some(a2));
+    assert!(!screen.with_node(a, |n| n.collapsed).unwrap());
+
+    // deleted nodes are skipped
+    screen.select_node(b);
+    screen.select_node(a1);
+    screen.history_back();
+    assert_eq!(screen.selected, Some(b));
+    screen.delete_selected(false);
+    screen.select_node(a1);
+    screen.history_back();
+    assert_eq!(screen.selected, Some(a2));
+}
+
+#[test]
+fn test_bookmarks() {
+    let mut screen = Screen::default();
+    let inbox = screen.new_node();
+    let item = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children.push(inbox));
+    screen.with_node_mut_no_meta(inbox, |n| {
+        n.content = "inbox".to_owned();
+        n.children.push(item);
+        n.collapsed = true;
+    });
+    screen.with_node_mut_no_meta(item, |n| {
+        n.parent_id = inbox;
+        n.content = "item".to_owned();
+    });
+
+    screen.set_bookmark(1, item);
+    screen.set_bookmark(2, item);
+    assert_eq!(screen.bookmarks.len(), 1);
+    let raw = screen.nodes[&item].clone();
+    assert_eq!(screen.format_node(&raw).content, "item [2]");
+
+    screen.handle_event(Event::Key(Key::Alt('2')));
+    assert_eq!(screen.selected, Some(item));
+    assert!(!screen.with_node(inbox, |n| n.collapsed).unwrap());
+
+    screen.delete_selected(false);
+    assert!(screen.bookmarks.is_empty());
+}
+
+#[test]
+fn test_focus_mode() {
+    fn add(screen: &mut Screen, parent_id: NodeID, coords: Coords) -> NodeID {
+        let id = screen.new_node();
+        screen.with_node_mut_no_meta(id, |n| {
+            n.parent_id = parent_id;
+            n.content = format!("node {}", id);
+            n.rooted_coords = coords;
+        });
+        screen.with_node_mut_no_meta(parent_id, |p| p.children.push(id));
+        id
+    }
+
+    let mut screen = Screen::default();
+    screen.dims = (80, 24);
+    let a = add(&mut screen, 0, (10, 5));
+    let a1 = add(&mut screen, a, (0, 0));
+    let a11 = add(&mut screen, a1, (0, 0));
+    let b = add(&mut screen, 0, (40, 2));
+    let b1 = add(&mut screen, b, (0, 0));
+    screen.arrows.push((a1, b1));
+    screen.view_y = 3;
+
+    screen.select_node(a1);
+    screen.toggle_focus_mode();
+    screen.draw();
+    assert_eq!(screen.focus_root(), Some(a1));
+    assert_eq!(screen.view_y, 0);
+    assert_eq!(screen.drawn_at(a1), Some(&(1, 2)));
+    assert_eq!(screen.drawn_at(a11), Some(&(1, 3)));
+    assert_eq!(screen.drawn_at(a), None);
+    assert_eq!(screen.drawn_at(b1), None);
+    assert_eq!(screen.lookup((1, 3)), Some(&a11));
+
+    // nesting, then stepping back out one level at a time
+    screen.select_node(a11);
+    screen.toggle_focus_mode();
+    assert_eq!(screen.focus_root(), Some(a11));
+    screen.toggle_focus_mode();
+    assert_eq!(screen.focus_root(), Some(a1));
+
+    // escape with nothing selected leaves focus mode rather than quitting
+    screen.unselect();
+    assert!(screen.handle_event(Event::Key(Key::Esc)));
+    assert_eq!(screen.focus_root(), None);
+    assert_eq!(screen.view_y, 3);
+
+    // deleting the focus root falls back to the full canvas
+    screen.select_node(a1);
+    screen.toggle_focus_mode();
+    screen.delete_selected(false);
+    screen.draw();
+    assert_eq!(screen.focus_root(), None);
+    assert_eq!(screen.view_y, 3);
+    assert!(screen.drawn_at(b1).is_some());
+
+    // an anchor made in focus mode goes under the focus root, which is
+    // opened up so that it can be typed into
+    let mut screen = Screen {
+        is_test: true,
+        dims: (80, 24),
+        ..Screen::default()
+    };
+    let events = vec![
+        Event::Key(Key::Ctrl('n')),
+        Event::Key(Key::Ctrl('s')),
+        Event::Key(Key::Alt('f')),
+        Event::Key(Key::Char('w')),
+        Event::Mouse(MouseEvent::Press(MouseButton::Right, 12, 5)),
+        Event::Key(Key::Char('\n')),
+    ];
+    for evt in events {
+        assert!(screen.handle_event(evt));
+        assert_eq!(screen.check_invariants(), Ok(()));
+        screen.draw();
+    }
+    let root = screen.focus_root().unwrap();
+    assert_eq!(screen.parent(screen.selected.unwrap()), Some(root));
+    assert!(!screen.with_node(root, |n| n.collapsed).unwrap());
+}
+
+#[test]
+fn test_timer_switching() {
+    let mut screen = Screen::default();
+    let a = screen.new_node();
+    let b = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a, b]);
+
+    screen.select_node(a);
+    screen.toggle_timer();
+    assert_eq!(screen.running_timers(), vec![a]);
+
+    // starting another timer stops the first
+    screen.select_node(b);
+    screen.toggle_timer();
+    assert_eq!(screen.running_timers(), vec![b]);
+
+    // as does striking it
+    screen.toggle_stricken();
+    assert_eq!(screen.running_timers(), vec![]);
+
+    screen.select_node(a);
+    screen.toggle_timer();
+    screen.toggle_timer();
+    assert_eq!(screen.running_timers(), vec![]);
+}
+
+#[test]
+fn test_pomodoro_count() {
+    let mut screen = Screen::default();
+    let a = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a]);
+    screen.select_node(a);
+    screen.toggle_pomodoro();
+    let start = time::get_time().sec as u64;
+
+    // a finished pomodoro is counted and the break begins
+    screen.tick_at(start + 25 * 60);
+    assert_eq!(screen.with_node(a, |n| n.meta.tags["pomodoros"].clone()),
+               Some("1".to_owned()));
+    assert!(screen.pomodoro.is_some());
+
+    screen.tick_at(start + 30 * 60);
+    assert!(screen.pomodoro.is_none());
+}
+
+#[test]
+fn test_due_notifications() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct MockNotifier(Rc<RefCell<Vec<NodeID>>>);
+
+    impl Notifier for MockNotifier {
+        fn notify(&mut self, node_id: NodeID, _text: &str) {
+            self.0.borrow_mut().push(node_id);
+        }
+    }
+
+    let notified = Rc::new(RefCell::new(vec![]));
+    let mut screen = Screen::default();
+    screen.notifier = Box::new(MockNotifier(notified.clone()));
+    let a = screen.new_node();
+    let b = screen.new_node();
+    let c = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a, b, c]);
+    screen.with_node_mut_no_meta(a, |n| n.meta.due = Some(100));
+    screen.with_node_mut_no_meta(b, |n| n.meta.due = Some(200));
+    screen.with_node_mut_no_meta(c, |n| {
+        n.meta.due = Some(100);
+        n.meta.finish();
+    });
+
+    screen.tick_at(150);
+    assert_eq!(*notified.borrow(), vec![a]);
+
+    // each due time only fires once
+    screen.tick_at(250);
+    screen.tick_at(300);
+    assert_eq!(*notified.borrow(), vec![a, b]);
+    assert_eq!(screen.notices.len(), 2);
+
+    // but a new due time fires again
+    screen.with_node_mut_no_meta(a, |n| n.meta.due = Some(400));
+    screen.tick_at(400);
+    assert_eq!(*notified.borrow(), vec![a, b, a]);
+}
+
+#[test]
+fn test_missed_notifications() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct MockNotifier(Rc<RefCell<Vec<NodeID>>>);
+
+    impl Notifier for MockNotifier {
+        fn notify(&mut self, node_id: NodeID, _text: &str) {
+            self.0.borrow_mut().push(node_id);
+        }
+    }
+
+    let notified = Rc::new(RefCell::new(vec![]));
+    let mut screen = Screen {
+        is_test: true,
+        dims: (60, 20),
+        ..Screen::default()
+    };
+    let ids: Vec<NodeID> = (0..13).map(|_| screen.new_node()).collect();
+    screen.with_node_mut_no_meta(0, |n| n.children = ids.clone());
+    for (i, &id) in ids.iter().enumerate() {
+        screen.with_node_mut_no_meta(id, |n| {
+            n.content = format!("task {}", i);
+            n.meta.due = Some(100 + i as u64);
+        });
+    }
+    // told about last time
+    screen.with_node_mut_no_meta(ids[11], |n| n.meta.notified = n.meta.due);
+    screen.with_node_mut_no_meta(ids[12], |n| n.meta.due = Some(5000));
+
+    // what a saved map brings back on the next launch
+    let data = serialization::serialize_screen(&screen);
+    let mut screen = serialization::deserialize_screen(data).unwrap();
+    screen.is_test = true;
+    screen.dims = (60, 20);
+    screen.notifier = Box::new(MockNotifier(notified.clone()));
+    screen.notify_missed(1000);
+    assert_eq!(*notified.borrow(), ids[..11].to_vec());
+    assert_eq!(screen.missed.len(), 11);
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    assert!(text.contains("came due while void was closed (11):"), "{}", text);
+    assert!(text.contains("  task 9") && !text.contains("  task 10"));
+    assert!(text.contains("  and 1 more"));
+
+    // and stays quiet on the one after
+    let data = serialization::serialize_screen(&screen);
+    let mut screen = serialization::deserialize_screen(data).unwrap();
+    screen.notifier = Box::new(MockNotifier(notified.clone()));
+    screen.notify_missed(2000);
+    screen.tick_at(2000);
+    assert_eq!(notified.borrow().len(), 11);
+    assert!(screen.missed.is_empty() && screen.notices.is_empty());
+    screen.notify_missed(5000);
+    assert_eq!(notified.borrow().last(), Some(&ids[12]));
+    screen.dismiss_notices();
+    assert!(screen.missed.is_empty());
+}
+
+#[test]
+fn test_bracketed_paste() {
+    let mut screen = Screen::default();
+    let a = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a]);
+    screen.select_node(a);
+
+    let mut parser = input::InputParser::default();
+    let stream = b"x\x1b[200~ one\r\rtwo #tag\r\x1b[201~\x1b[200~!\x1b[201~";
+    for input in parser.feed(stream) {
+        assert!(screen.handle_input(input));
+    }
+
+    let (content, children, collapsed) =
+        screen.with_node(a, |n| (n.content.clone(), n.children.clone(), n.collapsed)).unwrap();
+    assert_eq!(content, "x!");
+    assert!(!collapsed);
+    let lines: Vec<String> = children
+        .iter()
+        .map(|&c| screen.with_node(c, |n| n.content.clone()).unwrap())
+        .collect();
+    assert_eq!(lines, vec![" one", "two #tag"]);
+    assert_eq!(screen.tag_db.tag_to_nodes("tag"), vec![children[1]]);
+    assert_eq!(screen.selected, Some(a));
+}
+
+#[test]
+fn test_reload_changed_work_file() {
+    let path = env::temp_dir().join(format!("void-reload-test-{}", unsafe { getpid() }));
+    let path = path.to_str().unwrap().to_owned();
+
+    let mut screen = Screen::default();
+    let a = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a]);
+    screen.with_node_mut_no_meta(a, |n| n.content = "mine".to_owned());
+    screen.work_path = Some(path.clone());
+    screen.save().unwrap();
+    screen.select_node(a);
+    screen.tick_at(0);
+    assert_eq!(screen.with_node(a, |n| n.content.clone()), Some("mine".to_owned()));
+
+    let (nodes, max_id) = (screen.nodes.clone(), screen.max_id);
+    let write_theirs = |content: &str| {
+        let mut theirs = Screen {
+            nodes: nodes.clone(),
+            max_id,
+            ..Screen::default()
+        };
+        theirs.nodes.get_mut(&a).unwrap().content = content.to_owned();
+        let mut f = File::create(&path).unwrap();
+        f.write_all(&serialization::serialize_screen(&theirs)).unwrap();
+    };
+
+    // nothing unsaved, so their version is loaded and the selection kept
+    write_theirs("theirs");
+    screen.tick_at(0);
+    assert_eq!(screen.with_node(a, |n| n.content.clone()), Some("theirs".to_owned()));
+    assert_eq!(screen.selected, Some(a));
+    assert!(!screen.conflict);
+
+    // with unsaved changes we ask instead
+    screen.append('!');
+    write_theirs("theirs again");
+    screen.tick_at(0);
+    assert!(screen.conflict);
+    assert_eq!(screen.with_node(a, |n| n.content.clone()), Some("theirs!".to_owned()));
+
+    screen.handle_event(Event::Key(Key::Alt('k')));
+    screen.tick_at(0);
+    assert!(!screen.conflict);
+    assert_eq!(screen.with_node(a, |n| n.content.clone()), Some("theirs!".to_owned()));
+
+    // the answers are keymap actions, so a key file can move them
+    let keys = format!("{}.keys", path);
+    fs::write(&keys, "load_theirs:M-L\n").unwrap();
+    screen.config = Config::parse_file(keys.clone()).unwrap();
+    screen.append('?');
+    write_theirs("theirs at last");
+    screen.tick_at(0);
+    assert!(screen.conflict);
+    screen.handle_event(Event::Key(Key::Alt('L')));
+    assert!(!screen.conflict);
+    assert_eq!(screen.with_node(a, |n| n.content.clone()), Some("theirs at last".to_owned()));
+
+    remove_file(&keys).unwrap();
+    remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_encrypted_save() {
+    let path = env::temp_dir().join(format!("void-encrypted-test-{}", unsafe { getpid() }));
+    let path = path.to_str().unwrap().to_owned();
+    let params = crypto::Params {
+        log_n: 4,
+        r: 1,
+        p: 1,
+    };
+    let key = crypto::Key::derive("sesame", params, [7; 16]).unwrap();
+
+    let mut screen = Screen::default();
+    let a = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a]);
+    screen.with_node_mut_no_meta(a, |n| n.content = "private".to_owned());
+    screen.work_path = Some(path.clone());
+    screen.key = Some(key.clone());
+    screen.save().unwrap();
+
+    let mut data = vec![];
+    File::open(&path).unwrap().read_to_end(&mut data).unwrap();
+    assert!(crypto::is_sealed(&data));
+    assert!(!String::from_utf8_lossy(&data).contains("private"));
+    assert!(serialization::deserialize_screen(data.clone()).is_err());
+    assert!(serialization::deserialize_sealed_screen(&data, "open up").is_err());
+    let loaded = serialization::deserialize_sealed_screen(&data, "sesame").unwrap();
+    assert_eq!(loaded.with_node(a, |n| n.content.clone()), Some("private".to_owned()));
+
+    // changes saved elsewhere with the same passphrase reload without asking
+    let mut theirs = loaded;
+    theirs.with_node_mut_no_meta(a, |n| n.content = "still private".to_owned());
+    let sealed = crypto::seal(&key, &serialization::serialize_screen(&theirs));
+    File::create(&path).unwrap().write_all(&sealed).unwrap();
+    screen.tick_at(0);
+    assert_eq!(screen.with_node(a, |n| n.content.clone()), Some("still private".to_owned()));
+
+    remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_tabs() {
+    let name = |tab: &str| format!("void-tabs-test-{}-{}", unsafe { getpid() }, tab);
+    let path = |tab: &str| env::temp_dir().join(name(tab)).to_str().unwrap().to_owned();
+    let (work, reading) = (path("work"), path("reading"));
+
+    let mut screen = Screen {
+        is_test: true,
+        work_path: Some(work.clone()),
+        ..Screen::default()
+    };
+    let a = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a]);
+    screen.select_node(a);
+    screen.arrows.push((0, a));
+    screen.rendering.color = false;
+    screen.capabilities.unicode = false;
+
+    screen.open_tab(&reading);
+    assert_eq!(screen.work_path, Some(reading.clone()));
+    assert!(screen.is_test);
+    assert!(!screen.rendering.color);
+    assert!(!screen.capabilities.unicode);
+    assert_eq!(screen.selected, None);
+    assert!(screen.arrows.is_empty());
+    assert_eq!(screen.tab_strip(), format!("{}* [{}]", name("work"), name("reading")));
+
+    let b = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![b]);
+    screen.select_node(b);
+
+    // back to the first tab, everything as it was left
+    screen.handle_event(Event::Unsupported(b"\x1b[6;5~".to_vec()));
+    assert_eq!(screen.tab, 0);
+    assert_eq!(screen.work_path, Some(work.clone()));
+    assert_eq!(screen.selected, Some(a));
+    assert_eq!(screen.arrows, vec![(0, a)]);
+    assert!(screen.tabs[0].selected == Some(b));
+
+    screen.open_tab(&reading);
+    assert_eq!(screen.tabs.len(), 1);
+
+    // only the active tab is saved, and exiting saves the rest
+    screen.save().unwrap();
+    assert!(Path::new(&work).exists());
+    assert!(!Path::new(&reading).exists());
+    screen.save_all();
+    assert!(Path::new(&reading).exists());
+
+    remove_file(&work).unwrap();
+    remove_file(&reading).unwrap();
+}
+
+#[test]
+fn test_save_hook() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = env::temp_dir().join(format!("void-hook-test-{}", unsafe { getpid() }));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let file = |name: &str| dir.join(name).to_str().unwrap().to_owned();
+    let (path, script, marker) = (file("work.db"), file("hook.sh"), file("marker"));
+    fs::write(&script, format!("#!/bin/sh\necho \"saved $1\" > {}\n", marker)).unwrap();
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let mut screen = Screen {
+        is_test: true,
+        work_path: Some(path.clone()),
+        ..Screen::default()
+    };
+    screen.config.on_save = Some(script);
+    screen.save().unwrap();
+    assert_eq!(fs::read_to_string(&marker).unwrap(), format!("saved {}\n", path));
+
+    // a hook that fails doesn't undo the save
+    fs::remove_file(&path).unwrap();
+    screen.config.on_save = Some("exit 3;".to_owned());
+    screen.save().unwrap();
+    assert!(Path::new(&path).exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_failed_save() {
+    // a work file under a regular file can't be written, even as root
+    let dir = env::temp_dir().join(format!("void-failed-save-test-{}", unsafe { getpid() }));
+    fs::write(&dir, "").unwrap();
+    let path = dir.join("work.db").to_str().unwrap().to_owned();
+
+    let mut screen = Screen {
+        is_test: true,
+        work_path: Some(path.clone()),
+        ..Screen::default()
+    };
+    let id = screen.new_node();
+    screen.with_node_mut_no_meta(id, |n| n.content = "unsaved".to_owned());
+    screen.with_node_mut_no_meta(0, |n| n.children.push(id));
+    screen.dirty = true;
+    match screen.save() {
+        Err(error::Error::File(ref failed, _)) => assert_eq!(failed, &format!("{}.tmp", path)),
+        other => panic!("expected a file error, got {:?}", other),
+    }
+
+    // through a key it's logged, and void keeps going with the map unsaved
+    assert!(screen.handle_event(Event::Key(Key::Ctrl('x'))));
+    assert!(screen.dirty);
+    assert!(screen.nodes.contains_key(&id));
+
+    fs::remove_file(&dir).unwrap();
+}
+
+#[test]
+fn test_restore_session() {
+    let path = env::temp_dir().join(format!("void-session-test-{}", unsafe { getpid() }));
+    let path = path.to_str().unwrap().to_owned();
+
+    let mut screen = Screen {
+        is_test: true,
+        work_path: Some(path.clone()),
+        ..Screen::default()
+    };
+    let (a, b) = (screen.new_node(), screen.new_node());
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a]);
+    screen.with_node_mut_no_meta(a, |n| n.children = vec![b]);
+    screen.with_node_mut_no_meta(b, |n| n.parent_id = a);
+    screen.select_node(a);
+    screen.drill_down();
+    screen.select_node(b);
+    screen.view_y = 3;
+    screen.toggle_show_logs();
+    screen.save().unwrap();
+
+    let load = || {
+        let mut data = vec![];
+        File::open(&path).unwrap().read_to_end(&mut data).unwrap();
+        let mut screen = serialization::deserialize_screen(data).unwrap();
+        screen.work_path = Some(path.clone());
+        screen.restore_session();
+        screen
+    };
+    let mut restored = load();
+    assert_eq!(restored.drawing_root, a);
+    assert_eq!(restored.selected, Some(b));
+    assert_eq!(restored.view_y, 3);
+    assert!(restored.show_logs);
+    restored.pop_focus();
+    assert_eq!((restored.drawing_root, restored.selected), (0, Some(a)));
+
+    // a node deleted by someone else is just not selected
+    screen.nodes.remove(&b);
+    screen.with_node_mut_no_meta(a, |n| n.children.clear());
+    let data = serialization::serialize_screen(&screen);
+    File::create(&path).unwrap().write_all(&data).unwrap();
+    let restored = load();
+    assert_eq!(restored.drawing_root, a);
+    assert_eq!(restored.selected, None);
+
+    remove_file(&path).unwrap();
+    remove_file(session::path(&path)).unwrap();
+}
+
+#[test]
+fn test_run_command() {
+    let path = env::temp_dir().join(format!("void-command-test-{}.md", unsafe { getpid() }));
+    let path = path.to_str().unwrap().to_owned();
+
+    let mut screen = Screen {
+        is_test: true,
+        ..Screen::default()
+    };
+    let a = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a]);
+    screen.with_node_mut_no_meta(a, |n| n.content = "groceries".to_owned());
+
+    assert!(screen.run_command(command::parse("set hidestricken").unwrap()).unwrap());
+    assert!(screen.with_node(0, |n| n.hide_stricken).unwrap());
+    assert!(screen.run_command(command::parse("toggle_show_logs").unwrap()).unwrap());
+    assert!(screen.show_logs);
+
+    screen.run_command(command::parse(&format!("export md {}", path)).unwrap()).unwrap();
+    let mut exported = String::new();
+    File::open(&path).unwrap().read_to_string(&mut exported).unwrap();
+    assert_eq!(exported, "- groceries\n");
+
+    assert!(!screen.run_command(command::parse("q!").unwrap()).unwrap());
+    assert!(screen.discard_changes);
+
+    remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_replay_macro() {
+    let mut screen = Screen {
+        is_test: true,
+        ..Screen::default()
+    };
+    let a = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a]);
+    screen.select_node(a);
+
+    screen.start_recording('a');
+    screen.handle_event(Event::Key(Key::Char('x')));
+    screen.handle_event(Event::Key(Key::Char('y')));
+    screen.stop_recording();
+    assert_eq!(screen.macros[&'a'].len(), 2);
+
+    assert!(screen.replay_macro('a', 3));
+    assert_eq!(screen.with_node(a, |n| n.content.clone()), Some("xyxyxyxy".to_owned()));
+
+    // a macro that ends the session stops replaying and says so
+    screen.macros.insert('q', vec![Event::Key(Key::Ctrl('c')), Event::Key(Key::Char('z'))]);
+    assert!(!screen.replay_macro('q', 2));
+    assert_eq!(screen.with_node(a, |n| n.content.clone()), Some("xyxyxyxy".to_owned()));
+    assert!(screen.replay_macro('b', 1));
+}
+
+#[test]
+fn test_modal_editing() {
+    let mut screen = Screen {
+        is_test: true,
+        ..Screen::default()
+    };
+    screen.run_command(Command::Set(Setting::Modal, true)).unwrap();
+    let a = screen.new_node();
+    let b = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a, b]);
+    screen.select_node(a);
+    let key = |c| Event::Key(Key::Char(c));
+
+    // normal mode keys don't type
+    screen.handle_event(key('x'));
+    assert_eq!(screen.with_node(a, |n| (n.content.clone(), n.stricken)),
+               Some((String::new(), true)));
+
+    // o opens a sibling to type into, and Esc goes back to normal mode
+    screen.handle_event(key('o'));
+    let c = screen.selected.unwrap();
+    screen.handle_event(key('d'));
+    screen.handle_event(Event::Key(Key::Esc));
+    screen.handle_event(key('d'));
+    assert_eq!(screen.with_node(c, |n| n.content.clone()), Some("d".to_owned()));
+
+    // a single d waits for another, anything between cancels it
+    screen.handle_event(key('j'));
+    screen.handle_event(key('d'));
+    assert!(screen.exists(c));
+    screen.handle_event(key('d'));
+    assert!(!screen.exists(c));
+}
+
+#[test]
+fn test_insert_sibling() {
+    let mut screen = Screen {
+        is_test: true,
+        ..Screen::default()
+    };
+    let anchor = screen.new_node();
+    let (a, b) = (screen.new_node(), screen.new_node());
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![anchor]);
+    screen.with_node_mut_no_meta(anchor, |n| {
+        n.rooted_coords = (4, 3);
+        n.children = vec![a, b];
+    });
+    for &id in &[a, b] {
+        screen.with_node_mut_no_meta(id, |n| {
+            n.parent_id = anchor;
+            n.content = "x".to_owned();
+        });
+    }
+    screen.with_node_mut_no_meta(anchor, |n| n.content = "anchor".to_owned());
+
+    // goes right after the selected node rather than at the end
+    screen.select_node(a);
+    screen.insert_sibling();
+    let new = screen.selected.unwrap();
+    screen.append('y');
+    assert_eq!(screen.with_node(anchor, |n| n.children.clone()), Some(vec![a, new, b]));
+    assert_eq!(screen.parent(new), Some(anchor));
+
+    // an anchor's sibling is an anchor on the row below its tree
+    screen.select_node(anchor);
+    screen.insert_sibling();
+    let new_anchor = screen.selected.unwrap();
+    assert_eq!(screen.with_node(0, |n| n.children.clone()), Some(vec![anchor, new_anchor]));
+    assert_eq!(screen.with_node(new_anchor, |n| n.rooted_coords), Some((4, 7)));
+}
+
+#[test]
+fn test_sort_children() {
+    let mut screen = Screen {
+        is_test: true,
+        ..Screen::default()
+    };
+    let ids: Vec<NodeID> = (0..5).map(|_| screen.new_node()).collect();
+    let (parent, b, a, c, d) = (ids[0], ids[1], ids[2], ids[3], ids[4]);
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![parent]);
+    screen.with_node_mut_no_meta(parent, |n| n.children = vec![b, a]);
+    screen.with_node_mut_no_meta(b, |n| n.children = vec![c, d]);
+    for &(id, content) in &[(parent, "books"), (b, "b"), (a, "a"), (c, "c"), (d, "d")] {
+        screen.with_node_mut_no_meta(id, |n| n.content = content.to_owned());
+    }
+    let children = |screen: &Screen, id| screen.with_node(id, |n| n.children.clone()).unwrap();
+
+    screen.select_node(parent);
+    screen.run_command(Command::Sort {
+        by: SortBy::Content,
+        reverse: false,
+        recursive: false,
+    }).unwrap();
+    assert_eq!(children(&screen, parent), vec![a, b]);
+    assert_eq!(screen.selected, Some(parent));
+
+    // only the direct children, unless asked
+    screen.run_command(Command::Sort {
+        by: SortBy::Content,
+        reverse: true,
+        recursive: false,
+    }).unwrap();
+    assert_eq!(children(&screen, b), vec![c, d]);
+    screen.run_command(Command::Sort {
+        by: SortBy::Content,
+        reverse: true,
+        recursive: true,
+    }).unwrap();
+    assert_eq!(children(&screen, parent), vec![b, a]);
+    assert_eq!(children(&screen, b), vec![d, c]);
+}
+
+#[test]
+fn test_subtree_counts() {
+    let mut screen = Screen::default();
+    let (chapter, scene, note) = (screen.new_node(), screen.new_node(), screen.new_node());
+    screen.with_node_mut_no_meta(chapter, |n| {
+        n.content = "chapter one #draft".to_owned();
+        n.children = vec![scene];
+        n.collapsed = true;
+    });
+    screen.with_node_mut_no_meta(scene, |n| {
+        n.content = "the long walk home".to_owned();
+        n.children = vec![note];
+    });
+    assert_eq!(screen.subtree_size(chapter), 3);
+    assert_eq!(screen.subtree_word_count(chapter), 6);
+    assert_eq!(screen.subtree_word_count(note), 0);
+
+    screen.show_counts = true;
+    screen.dims = (200, 20);
+    screen.select_node(scene);
+    assert!(screen.status_text().contains("2 nodes, 4 words"));
+}
+
+#[test]
+fn test_arrow_color() {
+    let bright = format!("{}{}", style::Bold, color::Fg(color::LightYellow));
+    let faint = format!("{}{}", style::Faint, color::Fg(color::LightBlack));
+    let arrow = (3, 4);
+    assert_eq!(arrow_color(Some(3), arrow), bright);
+    assert_eq!(arrow_color(Some(4), arrow), bright);
+    assert_eq!(arrow_color(Some(5), arrow), faint);
+}
+
+#[test]
+fn test_minimap_click() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (80, 24),
+        lowest_drawn: 200,
+        ..Screen::default()
+    };
+    let anchors = screen.with_node(0, |n| n.children.len()).unwrap();
+
+    // clicks on the minimap scroll rather than making anchors
+    assert_eq!(screen.minimap_origin(), Some((60, 2)));
+    screen.perform(Action::LeftClick(60, 2 + 7)).unwrap();
+    assert_eq!(screen.with_node(0, |n| n.children.len()), Some(anchors));
+    // the bottom row covers canvas rows from 7 * 26, where 26 is 201 / 8
+    // rounded up, and ends up centered
+    assert_eq!(screen.view_y, 7 * 26 - 12);
+
+    screen.perform(Action::ToggleMinimap).unwrap();
+    assert_eq!(screen.minimap_cell((60, 2)), None);
+}
+
+#[test]
+fn test_debug_mouse_status() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (80, 24),
+        ..Screen::default()
+    };
+    screen.lookup.insert((5, 3), 7);
+    screen.perform(Action::ToggleDebug).unwrap();
+    screen.perform(Action::RightClick(5, 3)).unwrap();
+    assert!(screen.status_text().contains("mouse (5, 3) → node 7"));
+    screen.perform(Action::Release(6, 3)).unwrap();
+    assert!(screen.status_text().contains("mouse (6, 3) → nothing"));
+}
+
+#[test]
+fn test_place_anchor() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (40, 12),
+        ..Screen::default()
+    };
+    let a = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a]);
+    screen.with_node_mut_no_meta(a, |n| {
+        n.content = "plans".to_owned();
+        n.rooted_coords = (30, 5);
+    });
+    screen.draw();
+    screen.select_node(a);
+
+    // the nearest room is above the selected anchor, with a row to spare
+    screen.perform(Action::PlaceAnchor).unwrap();
+    let b = screen.selected.unwrap();
+    assert!(b != a && screen.parent(b) == Some(0) && screen.mode == Mode::Insert);
+    assert_eq!(screen.with_node(b, |n| n.rooted_coords), Some((24, 3)));
+
+    // around the middle of the view when nothing is selected
+    screen.with_node_mut_no_meta(b, |n| n.content = "b".to_owned());
+    screen.unselect();
+    screen.draw();
+    screen.perform(Action::PlaceAnchor).unwrap();
+    let c = screen.selected.unwrap();
+    assert_eq!(screen.with_node(c, |n| n.rooted_coords), Some((14, 6)));
+}
+
+#[test]
+fn test_detail_panel() {
+    use colors::parse_color;
+
+    let mut screen = Screen {
+        is_test: true,
+        dims: (80, 24),
+        ..Screen::default()
+    };
+    let a = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a]);
+    screen.with_node_mut_no_meta(a, |n| {
+        n.content = "taxes".to_owned();
+        n.color = String::new();
+        n.meta.mtime = 0;
+        n.meta.tags.insert("old".to_owned(), String::new());
+    });
+    screen.select_node(a);
+    screen.dirty = false;
+    let keys = |screen: &mut Screen, keys: &[Key]| for key in keys {
+        assert!(screen.handle_event(Event::Key(*key)));
+    };
+    let typed = |text: &str| text.chars().map(Key::Char).collect::<Vec<Key>>();
+
+    // a bad date is pointed out, and the other fields keep what was typed
+    keys(&mut screen, &[Key::Alt('D')]);
+    keys(&mut screen, &typed("someday"));
+    keys(&mut screen, &[Key::Down]);
+    keys(&mut screen, &typed("1h30m"));
+    keys(&mut screen, &[Key::Down, Key::Backspace, Key::Backspace, Key::Backspace]);
+    keys(&mut screen, &typed("errand, who=bob"));
+    keys(&mut screen, &[Key::Down]);
+    keys(&mut screen, &typed("cyan"));
+    keys(&mut screen, &[Key::Char('\n')]);
+    {
+        let detail = screen.detail.as_ref().unwrap();
+        assert_eq!(detail.fields[detail.cursor].0, detail::Field::Due);
+        assert!(detail.errors[detail.cursor].is_some());
+        assert_eq!(detail.fields[4].1, "1h30m");
+    }
+    assert_eq!(screen.with_node(a, |n| n.meta.estimate), Some(None));
+    assert!(!screen.dirty);
+    screen.draw();
+
+    for _ in 0.."someday".len() {
+        keys(&mut screen, &[Key::Backspace]);
+    }
+    keys(&mut screen, &typed("2030-01-02 03:04"));
+    keys(&mut screen, &[Key::Char('\n')]);
+    assert!(screen.detail.is_none() && screen.dirty);
+    let meta = screen.with_node(a, |n| n.meta.clone()).unwrap();
+    assert_eq!(meta.due, Some(1893553440));
+    assert_eq!(meta.estimate, Some(5400));
+    assert_eq!(meta.tags.len(), 2);
+    assert_eq!(meta.tags["who"], "bob");
+    assert!(meta.mtime > 0);
+    assert_eq!(screen.with_node(a, |n| n.color.clone()), parse_color("cyan"));
+
+    // escape throws edits away
+    keys(&mut screen, &[Key::Alt('D'), Key::Down, Key::Backspace, Key::Esc]);
+    assert_eq!(screen.with_node(a, |n| n.meta.estimate), Some(Some(5400)));
+}
+
+#[test]
+fn test_tag_completion() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (80, 24),
+        ..Screen::default()
+    };
+    let ids: Vec<NodeID> = (0..4).map(|_| screen.new_node()).collect();
+    screen.with_node_mut_no_meta(0, |n| n.children = ids.clone());
+    let contents = ["milk #errand", "post #errand #email", "tea #täglich", ""];
+    for (&id, &content) in ids.iter().zip(&contents) {
+        screen.with_node_mut_no_meta(id, |n| n.content = content.to_owned());
+        screen.tag_db.reindex(id, content.to_owned());
+    }
+    let typing = ids[3];
+    screen.select_node(typing);
+    let keys = |screen: &mut Screen, keys: &[Key]| for key in keys {
+        assert!(screen.handle_event(Event::Key(*key)));
+    };
+    let typed = |text: &str| text.chars().map(Key::Char).collect::<Vec<Key>>();
+    let content = |screen: &Screen| screen.with_node(typing, |n| n.content.clone()).unwrap();
+
+    // the most used first, narrowed down as typing goes on
+    keys(&mut screen, &typed("café #"));
+    assert_eq!(screen.completion.as_ref().unwrap().items, vec!["errand", "email", "täglich"]);
+    keys(&mut screen, &typed("e"));
+    assert_eq!(screen.completion.as_ref().unwrap().items, vec!["errand", "email"]);
+    screen.draw();
+    keys(&mut screen, &[Key::Down, Key::Char('\t')]);
+    assert_eq!(content(&screen), "café #email");
+    assert!(screen.completion.is_none() && screen.selected == Some(typing));
+    assert_eq!(screen.tag_db.node_tags(typing), vec!["email"]);
+
+    // enter takes it too, instead of making a sibling
+    keys(&mut screen, &typed(" #tä"));
+    keys(&mut screen, &[Key::Char('\n')]);
+    assert_eq!(content(&screen), "café #email #täglich");
+    assert_eq!(screen.nodes.len(), 5);
+
+    // escape only closes the list
+    keys(&mut screen, &typed(" #e"));
+    keys(&mut screen, &[Key::Esc]);
+    assert!(screen.completion.is_none() && screen.selected == Some(typing));
+    keys(&mut screen, &typed("rr"));
+    assert_eq!(screen.completion.as_ref().unwrap().items, vec!["errand"]);
+    keys(&mut screen, &[Key::Backspace; 4]);
+    assert!(screen.completion.is_none());
+}
+
+#[test]
+fn test_quick_add() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (80, 24),
+        ..Screen::default()
+    };
+    let ids: Vec<NodeID> = (0..2).map(|_| screen.new_node()).collect();
+    screen.with_node_mut_no_meta(0, |n| n.children = ids.clone());
+    screen.with_node_mut_no_meta(ids[0], |n| n.content = "old #task".to_owned());
+    screen.tag_db.reindex(ids[0], "old #task".to_owned());
+
+    // enter makes a sibling, which commits what was typed
+    screen.select_node(ids[1]);
+    for c in "fix login bug #backend who:\"bob smith\" est:2h due:tomorrow".chars() {
+        screen.handle_event(Event::Key(Key::Char(c)));
+    }
+    screen.handle_event(Event::Key(Key::Char('\n')));
+    let node = screen.nodes[&ids[1]].clone();
+    assert_eq!(node.content, "fix login bug");
+    assert_eq!(node.meta.estimate, Some(2 * 60 * 60));
+    assert!(node.meta.due.unwrap() > time::get_time().sec as u64);
+    assert_eq!((&*node.meta.tags["backend"], &*node.meta.tags["who"]), ("", "bob smith"));
+    assert_eq!(screen.tag_db.tag_to_nodes("backend"), vec![ids[1]]);
+    let detail = Detail::new(ids[1], &node.meta, &node.color);
+    assert!(detail.fields.contains(&(detail::Field::Tags, "backend, who=bob smith".to_owned())));
+    assert!(detail.fields.contains(&(detail::Field::Estimate, "2h".to_owned())));
+
+    // nodes that were only passed over are left alone
+    screen.select_node(ids[0]);
+    screen.select_node(ids[1]);
+    assert_eq!(screen.nodes[&ids[0]].content, "old #task");
+    assert!(screen.nodes[&ids[0]].meta.tags.is_empty());
+}
+
+#[test]
+fn test_board() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (60, 12),
+        ..Screen::default()
+    };
+    let ids: Vec<NodeID> = (0..4).map(|_| screen.new_node()).collect();
+    let contents = ["sprint", "fix login #todo", "write docs #todo", "release #done"];
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![ids[0]]);
+    screen.with_node_mut_no_meta(ids[0], |n| n.children = ids[1..].to_vec());
+    for (&id, &content) in ids.iter().zip(&contents) {
+        screen.with_node_mut_no_meta(id, |n| {
+            n.parent_id = if id == ids[0] { 0 } else { ids[0] };
+            n.content = content.to_owned();
+        });
+        screen.tag_db.reindex(id, content.to_owned());
+    }
+    let key = |screen: &mut Screen, key: Key| assert!(screen.handle_event(Event::Key(key)));
+
+    key(&mut screen, Key::Alt('b'));
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    let lines: Vec<&str> = text.lines().collect();
+    assert!(lines[0].starts_with("#todo (2)"), "{}", text);
+    assert!(lines[0].contains("#doing (0)") && lines[0].contains("#done (1)"));
+    assert!(lines[1].starts_with("fix login #todo") && lines[1].contains("release #done"));
+    assert!(lines[2].starts_with("sprint"));
+
+    // the card goes along, and so does the cursor
+    key(&mut screen, Key::Down);
+    key(&mut screen, Key::Right);
+    assert_eq!(screen.nodes[&ids[2]].content, "write docs #doing");
+    assert_eq!(screen.tag_db.tag_to_nodes("doing"), vec![ids[2]]);
+    assert_eq!(screen.board, Some((1, 0)));
+    key(&mut screen, Key::Right);
+    key(&mut screen, Key::Left);
+    key(&mut screen, Key::Left);
+    assert_eq!(screen.nodes[&ids[2]].content, "write docs #todo");
+    // tab only looks
+    key(&mut screen, Key::Char('\t'));
+    key(&mut screen, Key::Char('\t'));
+    assert_eq!(screen.board, Some((2, 0)));
+
+    key(&mut screen, Key::Char('\n'));
+    assert!(screen.board.is_none());
+    assert_eq!(screen.selected, Some(ids[3]));
+}
+
+#[test]
+fn test_agenda() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (60, 12),
+        ..Screen::default()
+    };
+    let now = time::get_time().sec as u64;
+    let day = 60 * 60 * 24;
+    let ids: Vec<NodeID> = (0..4).map(|_| screen.new_node()).collect();
+    let nodes = [("taxes", Some(now - 3 * day)), ("renew passport", Some(now + 40 * day)),
+                 ("someday", None), ("dentist", Some(now + 60 * day))];
+    screen.with_node_mut_no_meta(0, |n| n.children = ids.clone());
+    for (&id, &(content, due)) in ids.iter().zip(&nodes) {
+        screen.with_node_mut_no_meta(id, |n| {
+            n.content = content.to_owned();
+            n.meta.due = due;
+        });
+    }
+    let key = |screen: &mut Screen, key: Key| assert!(screen.handle_event(Event::Key(key)));
+
+    key(&mut screen, Key::Alt('y'));
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    let lines: Vec<&str> = text.lines().map(|l| l.trim_end()).collect();
+    assert_eq!(lines[..6].to_vec(),
+               vec!["agenda: 3 due",
+                    "Overdue",
+                    "taxes (overdue 3d)",
+                    "",
+                    "Later",
+                    "renew passport (due in 40d)"]);
+    assert!(!text.contains("someday"));
+
+    // finished nodes drop off
+    key(&mut screen, Key::Down);
+    key(&mut screen, Key::Ctrl('x'));
+    assert!(screen.nodes[&ids[1]].stricken);
+    key(&mut screen, Key::Char('\n'));
+    assert!(screen.agenda.is_none());
+    assert_eq!(screen.selected, Some(ids[3]));
+}
+
+#[test]
+fn test_stale() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (60, 12),
+        ..Screen::default()
+    };
+    let now = time::get_time().sec as u64;
+    let day = 60 * 60 * 24;
+    let ids: Vec<NodeID> = (0..4).map(|_| screen.new_node()).collect();
+    let nodes = [("garden", 40 * day), ("fence", 3 * day), ("gutters", 400 * day), ("mow", 0)];
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![ids[0], ids[2], ids[3]]);
+    screen.with_node_mut_no_meta(ids[0], |n| n.children = vec![ids[1]]);
+    screen.with_node_mut_no_meta(ids[1], |n| n.parent_id = ids[0]);
+    for (&id, &(content, ago)) in ids.iter().zip(&nodes) {
+        screen.with_node_mut_no_meta(id, |n| {
+            n.content = content.to_owned();
+            n.meta.mtime = now - ago;
+        });
+    }
+    // saved before mtimes were kept
+    screen.with_node_mut_no_meta(ids[2], |n| {
+        n.meta.ctime = n.meta.mtime;
+        n.meta.mtime = 0;
+    });
+    let key = |screen: &mut Screen, key: Key| assert!(screen.handle_event(Event::Key(key)));
+
+    assert!(screen.run_command(command::parse("stale").unwrap()).unwrap());
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    let lines: Vec<&str> = text.lines().map(|l| l.trim_end()).collect();
+    let fence = format!("  3d garden {} fence", screen.glyphs().path_separator);
+    assert_eq!(lines[..5].to_vec(),
+               vec!["stale: 4 untouched longest",
+                    "  1y gutters",
+                    " 1mo garden",
+                    &fence,
+                    " now mow"]);
+
+    key(&mut screen, Key::Down);
+    key(&mut screen, Key::Down);
+    key(&mut screen, Key::Char('\n'));
+    assert!(screen.stale.is_none());
+    assert_eq!(screen.selected, Some(ids[1]));
+
+    // changing what's under a node counts as touching it
+    screen.with_node_mut_no_meta(ids[0], |n| n.meta.mtime = 1);
+    screen.create_sibling();
+    assert!(screen.with_node(ids[0], |n| n.meta.mtime).unwrap() >= now);
+    key(&mut screen, Key::Alt('u'));
+    assert!(screen.stale.is_some());
+    key(&mut screen, Key::Alt('u'));
+    assert!(screen.stale.is_none());
+}
+
+#[test]
+fn test_tag_highlights() {
+    use colors::parse_color;
+
+    let mut screen = Screen {
+        is_test: true,
+        dims: (60, 10),
+        ..Screen::default()
+    };
+    let ids: Vec<NodeID> = (0..4).map(|_| screen.new_node()).collect();
+    let contents = ["launch #urgent", "fix login #urgent #waiting", "vendor #waiting", "plain"];
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![ids[0]]);
+    screen.with_node_mut_no_meta(ids[0], |n| {
+        n.children = ids[1..].to_vec();
+        n.rooted_coords = (1, 2);
+        n.color = parse_color("green").unwrap();
+    });
+    for (&id, &content) in ids.iter().zip(&contents) {
+        screen.with_node_mut_no_meta(id, |n| n.content = content.to_owned());
+        screen.tag_db.reindex(id, content.to_owned());
+    }
+    for line in &["highlight #urgent red bold", "highlight #waiting underline"] {
+        assert!(screen.run_command(command::parse(line).unwrap()).unwrap());
+    }
+    screen.draw();
+
+    let style_of = |screen: &Screen, text: &str| {
+        let grid = screen.last_frame.as_ref().unwrap();
+        let rows = grid.text(false);
+        let (y, row) = rows.lines().enumerate().find(|&(_, row)| row.contains(text)).unwrap();
+        let x = row[..row.find(text).unwrap()].chars().count();
+        grid.style_at((x as u16 + 1, y as u16 + 1)).unwrap().to_owned()
+    };
+    let (red, green) = (parse_color("red").unwrap(), parse_color("green").unwrap());
+    let (bold, underline) = (format!("{}", style::Bold), format!("{}", style::Underline));
+
+    // the anchor keeps its own color, and the first matching rule wins
+    let launch = style_of(&screen, "launch");
+    assert!(launch.contains(&green) && launch.contains(&bold) && !launch.contains(&red));
+    let fix = style_of(&screen, "fix login");
+    assert!(fix.contains(&red) && fix.contains(&bold) && !fix.contains(&underline));
+    let vendor = style_of(&screen, "vendor");
+    assert!(vendor.contains(&underline) && !vendor.contains(&bold));
+    assert!(!style_of(&screen, "plain").contains(&bold));
+
+    assert!(screen.run_command(command::parse("highlight #urgent none").unwrap()).unwrap());
+    screen.draw();
+    assert!(style_of(&screen, "fix login").contains(&underline));
+}
+
+#[test]
+fn test_mouse_clicks() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (80, 24),
+        ..Screen::default()
+    };
+    let (a, b) = (screen.new_node(), screen.new_node());
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a, b]);
+    for &(id, y) in &[(a, 3), (b, 4)] {
+        screen.with_node_mut_no_meta(id, |n| {
+            n.content = "x".to_owned();
+            n.rooted_coords = (2, y);
+        });
+        screen.lookup.insert((2, y), id);
+    }
+    let click = |screen: &mut Screen, (x, y), ms: u64| {
+        screen.click_screen_at((x, y), 1_500_000_000_000 + ms);
+        screen.dragging_from = None;
+    };
+    let collapsed = |screen: &Screen, id| screen.with_node(id, |n| n.collapsed).unwrap();
+
+    click(&mut screen, (2, 3), 0);
+    click(&mut screen, (2, 3), 300);
+    assert!(collapsed(&screen, a));
+
+    // a third click starts over rather than toggling back
+    click(&mut screen, (2, 3), 500);
+    assert!(collapsed(&screen, a));
+
+    // too slow, or on another node
+    click(&mut screen, (2, 3), 2000);
+    click(&mut screen, (2, 3), 2500);
+    click(&mut screen, (2, 4), 2600);
+    assert!(collapsed(&screen, a) && !collapsed(&screen, b));
+
+    // double clicking empty space makes an anchor, which doesn't count as
+    // the first click on it
+    click(&mut screen, (40, 10), 3000);
+    click(&mut screen, (40, 10), 3100);
+    assert!(screen.selected.is_some() && screen.last_click.is_none());
+
+    screen.perform(Action::MiddleClick(2, 4)).unwrap();
+    assert!(screen.with_node(b, |n| n.stricken).unwrap());
+}
+
+#[test]
+fn test_blank_clicks() {
+    let path = env::temp_dir().join(format!("void-blank-test-{}", unsafe { getpid() }));
+    let path = path.to_str().unwrap().to_owned();
+    let mut screen = Screen {
+        is_test: true,
+        dims: (80, 24),
+        work_path: Some(path.clone()),
+        ..Screen::default()
+    };
+    let anchors = |screen: &Screen| screen.with_node(0, |n| n.children.len()).unwrap();
+    let start = 1_500_000_000_000;
+    for (i, &coords) in [(10, 5), (20, 5), (10, 5), (10, 6)].iter().enumerate() {
+        screen.click_screen_at(coords, start + i as u64 * 100);
+    }
+    screen.click_screen_at((30, 8), start + 1000);
+    screen.click_screen_at((30, 8), start + 2000);
+    assert_eq!(anchors(&screen), 0);
+
+    // a quick second click on the same cell makes an anchor
+    screen.click_screen_at((30, 8), start + 2100);
+    assert_eq!(anchors(&screen), 1);
+    let blank = screen.selected.unwrap();
+
+    // if it's never typed into, it isn't saved
+    screen.selected = None;
+    screen.save().unwrap();
+    let mut data = vec![];
+    File::open(&path).unwrap().read_to_end(&mut data).unwrap();
+    let saved = serialization::deserialize_screen(data).unwrap();
+    assert!(!saved.exists(blank));
+    assert_eq!(saved.with_node(0, |n| n.children.len()), Some(0));
+    remove_file(&path).unwrap();
+    let _ = remove_file(session::path(&path));
+}
+
+#[test]
+fn test_context_menu() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (80, 24),
+        ..Screen::default()
+    };
+    let a = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a]);
+    screen.with_node_mut_no_meta(a, |n| n.content = "a".to_owned());
+    screen.lookup.insert((2, 3), a);
+
+    // pick the first item, strike, with the keyboard
+    screen.perform(Action::RightClick(2, 3)).unwrap();
+    screen.handle_event(Event::Mouse(MouseEvent::Release(2, 3)));
+    assert!(screen.menu.is_some());
+    screen.handle_event(Event::Key(Key::Char('\n')));
+    assert!(screen.menu.is_none());
+    assert!(screen.with_node(a, |n| n.stricken).unwrap());
+    assert_eq!(screen.selected, Some(a));
+
+    // the second row of the box is collapse
+    screen.perform(Action::RightClick(2, 3)).unwrap();
+    screen.handle_event(Event::Mouse(MouseEvent::Press(MouseButton::Left, 4, 5)));
+    assert!(screen.with_node(a, |n| n.collapsed).unwrap());
+
+    screen.perform(Action::RightClick(2, 3)).unwrap();
+    screen.handle_event(Event::Key(Key::Down));
+    screen.handle_event(Event::Key(Key::Esc));
+    assert!(screen.menu.is_none());
+    assert!(screen.with_node(a, |n| n.collapsed).unwrap());
+
+    // blank space offers a new anchor there
+    screen.perform(Action::RightClick(40, 10)).unwrap();
+    screen.handle_event(Event::Key(Key::Char('\n')));
+    let anchor = screen.selected.unwrap();
+    assert!(anchor != a);
+    assert_eq!(screen.parent(anchor), Some(0));
+}
+
+#[test]
+fn test_multi_line_layout() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (40, 12),
+        ..Screen::default()
+    };
+    let anchor = screen.new_node();
+    let (one, para, two) = (screen.new_node(), screen.new_node(), screen.new_node());
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![anchor]);
+    for &(id, content) in &[
+        (anchor, "tasks"),
+        (one, "one"),
+        (para, "title\nsecond line\nthird"),
+        (two, "two"),
+    ] {
+        screen.with_node_mut_no_meta(id, |n| {
+            n.content = content.to_owned();
+            n.parent_id = if id == anchor { 0 } else { anchor };
+        });
+    }
+    screen.with_node_mut_no_meta(anchor, |n| {
+        n.children = vec![one, para, two];
+        n.rooted_coords = (1, 2);
+    });
+    screen.draw_children_of_root();
+
+    // which node each cell sends clicks to
+    let names: HashMap<NodeID, char> =
+        vec![(anchor, 'a'), (one, '1'), (para, 'p'), (two, '2')].into_iter().collect();
+    let grid: Vec<String> = (2..8)
+        .map(|y| {
+            let mut row: String = (1..20)
+                .map(|x| screen.lookup.get(&(x, y)).map(|id| names[id]).unwrap_or('.'))
+                .collect();
+            while row.ends_with('.') {
+                row.pop();
+            }
+            row
+        })
+        .collect();
+    assert_eq!(
+        grid,
+        vec![
+            "aaaaaa",
+            "1111111",
+            "ppppppppp",
+            "ppppppppppppppp",
+            "ppppppppp",
+            "2222222",
+        ]
+    );
+    assert_eq!(screen.drawn_at(two), Some(&(1, 7)));
+    assert_eq!(screen.drawable_subtree_dims(anchor), Some((15, 6)));
+
+    screen.select_node(two);
+    screen.select_up();
+    assert_eq!(screen.selected, Some(para));
+}
+
+#[test]
+fn test_anchor_titles() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (40, 12),
+        ..Screen::default()
+    };
+    let (anchor, child) = (screen.new_node(), screen.new_node());
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![anchor]);
+    screen.with_node_mut_no_meta(anchor, |n| {
+        n.content = "plans".to_owned();
+        n.children = vec![child];
+        n.rooted_coords = (3, 4);
+    });
+    screen.with_node_mut_no_meta(child, |n| {
+        n.content = "x".to_owned();
+        n.parent_id = anchor;
+    });
+    assert_eq!(screen.drawable_subtree_dims(anchor), Some((6, 2)));
+
+    screen.set_title(anchor, "  this week ");
+    screen.draw_children_of_root();
+    assert_eq!(screen.drawn_at(anchor), Some(&(3, 5)));
+    assert_eq!(screen.drawn_at(child), Some(&(3, 6)));
+    assert_eq!(screen.lookup.get(&(11, 4)), Some(&anchor));
+    assert_eq!(screen.lookup.get(&(12, 4)), None);
+    assert_eq!(screen.drawable_subtree_dims(anchor), Some((9, 3)));
+
+    // clicking the title selects the anchor
+    screen.click_screen((5, 4));
+    assert_eq!(screen.selected, Some(anchor));
+
+    let data = serialization::serialize_screen(&screen);
+    let screen = serialization::deserialize_screen(data).unwrap();
+    let title = |id| screen.with_node(id, |n| n.title.clone()).unwrap();
+    assert_eq!(title(anchor), Some("this week".to_owned()));
+    assert_eq!(title(child), None);
+}
+
+#[test]
+fn test_estimates() {
+    let mut screen = Screen {
+        dims: (200, 24),
+        ..Screen::default()
+    };
+    let (a, b, c) = (screen.new_node(), screen.new_node(), screen.new_node());
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a]);
+    screen.with_node_mut_no_meta(a, |n| n.children = vec![b, c]);
+    for &(id, content) in &[(a, "sprint"), (b, "write est:1h30m report"), (c, "review est:2h")] {
+        screen.with_node_mut_no_meta(id, |n| {
+            n.content = content.to_owned();
+            n.parent_id = if id == a { 0 } else { a };
+        });
+        screen.select_node(id);
+        screen.unselect();
+    }
+    assert_eq!(screen.with_node(b, |n| n.content.clone()).unwrap(), "write report");
+    assert_eq!(screen.with_node(b, |n| n.meta.estimate), Some(Some(90 * 60)));
+    screen.with_node_mut_no_meta(b, |n| n.meta.tracked = 45 * 60);
+
+    screen.select_node(a);
+    assert!(screen.status_text().contains("est 3h30m / spent 45m"));
+
+    // finished work drops out of the estimate but not the time spent
+    screen.with_node_mut_no_meta(c, |n| n.stricken = true);
+    assert!(screen.status_text().contains("est 1h30m / spent 45m"));
+
+    let raw = screen.with_node(b, |n| n.clone()).unwrap();
+    assert!(screen.format_node(&raw).content.ends_with("(1h30m)"));
+
+    let data = serialization::serialize_screen(&screen);
+    let screen = serialization::deserialize_screen(data).unwrap();
+    assert_eq!(screen.with_node(b, |n| n.meta.estimate), Some(Some(90 * 60)));
+    assert_eq!(screen.with_node(a, |n| n.meta.estimate), Some(None));
+}
+
+#[test]
+fn test_arrow_drag() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (80, 24),
+        ..Screen::default()
+    };
+    let (a, b) = (screen.new_node(), screen.new_node());
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a, b]);
+    screen.lookup.insert((2, 3), a);
+    screen.lookup.insert((30, 12), b);
+    let right_drag = |screen: &mut Screen, to: Coords| {
+        screen.handle_event(Event::Mouse(MouseEvent::Press(MouseButton::Right, 2, 3)));
+        screen.handle_event(Event::Mouse(MouseEvent::Hold(to.0, to.1)));
+        screen.handle_event(Event::Mouse(MouseEvent::Release(to.0, to.1)));
+    };
+
+    // dropping onto the source keeps the menu, as a plain right click does
+    right_drag(&mut screen, (2, 3));
+    assert!(screen.menu.is_some() && screen.arrows.is_empty());
+    screen.handle_event(Event::Key(Key::Esc));
+
+    right_drag(&mut screen, (50, 20));
+    assert!(screen.menu.is_none() && screen.arrows.is_empty());
+
+    right_drag(&mut screen, (30, 12));
+    assert!(screen.menu.is_none());
+    assert_eq!(screen.arrows, vec![(a, b)]);
+
+    // left drags go back to moving nodes
+    assert!(screen.arrow_drag.is_none());
+}
+
+#[test]
+fn test_attachments() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (80, 24),
+        ..Screen::default()
+    };
+    let a = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a]);
+    screen.with_node_mut_no_meta(a, |n| {
+        n.content = "taxes".to_owned();
+        n.rooted_coords = (2, 3);
+    });
+    let path = env::temp_dir().join(format!("void-attach-test-{}", unsafe { getpid() }));
+    let path = path.to_str().unwrap().to_owned();
+    File::create(&path).unwrap();
+
+    // attaching twice keeps one
+    screen.attach(a, &path);
+    screen.attach(a, &format!(" {} ", path));
+    let attachments =
+        |screen: &Screen| screen.with_node(a, |n| n.meta.attachments.clone()).unwrap();
+    assert_eq!(attachments(&screen), vec![path.clone()]);
+    let raw = screen.with_node(a, |n| n.clone()).unwrap();
+    assert_eq!(screen.format_node(&raw).content, "taxes 📎");
+    assert!(!screen.broken_attachment(&raw));
+    screen.draw();
+    assert!(screen.last_frame.as_ref().unwrap().text(false).contains("taxes 📎"));
+
+    // relative paths are made whole, and missing ones are still kept
+    screen.attach(a, "receipts/2016.pdf");
+    let relative = env::current_dir().unwrap().join("receipts/2016.pdf");
+    assert_eq!(attachments(&screen)[1], relative.to_str().unwrap());
+    let raw = screen.with_node(a, |n| n.clone()).unwrap();
+    assert!(screen.broken_attachment(&raw));
+
+    let data = serialization::serialize_screen(&screen);
+    let loaded = serialization::deserialize_screen(data).unwrap();
+    assert_eq!(attachments(&loaded), attachments(&screen));
+    remove_file(&path).unwrap();
+
+    let red = format!("{}", color::Fg(color::Red));
+    assert_eq!(flag_glyph("taxes 📎 (3d)", '📎', &red, "R"), format!("taxes {}📎R (3d)", red));
+    assert_eq!(flag_glyph("tax…", '📎', &red, "R"), "tax…");
+}
+
+#[test]
+fn test_iconified_anchors() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (80, 24),
+        ..Screen::default()
+    };
+    let (trip, flights, hotel, deposit, budget) = (screen.new_node(),
+                                                   screen.new_node(),
+                                                   screen.new_node(),
+                                                   screen.new_node(),
+                                                   screen.new_node());
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![trip, budget]);
+    screen.with_node_mut_no_meta(trip, |n| {
+        n.content = "trip".to_owned();
+        n.children = vec![flights, hotel];
+        n.rooted_coords = (2, 3);
+    });
+    screen.with_node_mut_no_meta(flights, |n| {
+        n.content = "flights".to_owned();
+        n.parent_id = trip;
+    });
+    screen.with_node_mut_no_meta(hotel, |n| {
+        n.content = "hotel".to_owned();
+        n.parent_id = trip;
+        n.children = vec![deposit];
+    });
+    screen.with_node_mut_no_meta(deposit, |n| {
+        n.content = "deposit".to_owned();
+        n.parent_id = hotel;
+    });
+    screen.with_node_mut_no_meta(budget, |n| {
+        n.content = "budget".to_owned();
+        n.rooted_coords = (40, 3);
+    });
+    screen.arrows = vec![(budget, deposit), (flights, deposit)];
+
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    assert!(text.contains("flights") && text.contains("deposit"));
+    assert_eq!(screen.placements[&trip].height, 4);
+
+    // only anchors can be iconified
+    screen.select_node(hotel);
+    screen.toggle_iconified();
+    assert!(!screen.with_node(hotel, |n| n.iconified).unwrap());
+    screen.toggle_collapsed();
+    screen.select_node(trip);
+    screen.toggle_iconified();
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    assert!(text.contains("trip ▣3"));
+    assert!(!text.contains("flights") && !text.contains("hotel"));
+    assert_eq!(screen.placements[&trip].height, 1);
+    assert_eq!(screen.lowest_drawn, 3);
+    let rows: HashSet<u16> = screen.lookup
+        .iter()
+        .filter(|&(_, &id)| id == trip)
+        .map(|(&(_, y), _)| y)
+        .collect();
+    assert_eq!(rows, vec![3].into_iter().collect());
+    assert!(!screen.lookup.values().any(|&id| id == flights || id == hotel));
+
+    // arrows into it end at the anchor, and ones inside it aren't drawn
+    assert_eq!(screen.arrow_end(deposit), trip);
+    assert_eq!(screen.arrow_end(budget), budget);
+    let (path, _) = screen.path_between_nodes(screen.arrow_end(budget), screen.arrow_end(deposit));
+    assert!(!path.is_empty());
+    let arrows = frame::capture(|| screen.draw_arrows());
+    assert!(!arrows.is_empty());
+
+    let data = serialization::serialize_screen(&screen);
+    let mut loaded = serialization::deserialize_screen(data).unwrap();
+    assert!(loaded.with_node(trip, |n| n.iconified).unwrap());
+    loaded.is_test = true;
+    loaded.dims = (80, 24);
+
+    // opening it again leaves hotel collapsed, as it was
+    loaded.select_node(trip);
+    loaded.toggle_iconified();
+    loaded.draw();
+    let text = loaded.last_frame.as_ref().unwrap().text(false);
+    assert!(text.contains("flights") && text.contains("hotel"));
+    assert!(!text.contains("deposit") && !text.contains("▣"));
+    assert!(loaded.with_node(hotel, |n| n.collapsed).unwrap());
+    assert_eq!(loaded.arrow_end(deposit), deposit);
+
+    // a child made under it opens it, rather than being typed into unseen
+    loaded.toggle_iconified();
+    loaded.create_child();
+    assert!(!loaded.with_node(trip, |n| n.iconified).unwrap());
+    let child = loaded.selected.unwrap();
+    assert_eq!(loaded.parent(child), Some(trip));
+    assert_eq!(loaded.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_log_pane_cached() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (80, 24),
+        show_logs: true,
+        ..Screen::default()
+    };
+    // nothing's logged in between, as tests don't install the logger
+    screen.draw();
+    screen.draw();
+    assert_eq!(screen.log_pane.renders, 1);
+    assert!(screen.last_frame.as_ref().unwrap().text(false).contains("logs█"));
+
+    // a new size needs a new separator
+    screen.dims = (100, 30);
+    screen.draw();
+    assert_eq!(screen.log_pane.renders, 2);
+    let frame = screen.last_frame.as_ref().unwrap().text(false);
+    assert!(frame.contains(&format!("logs{}", "█".repeat(96))));
+}
+
+#[test]
+fn test_mouse_off_screen() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (80, 24),
+        ..Screen::default()
+    };
+    let a = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a]);
+    screen.with_node_mut_no_meta(a, |n| {
+        n.content = "a".to_owned();
+        n.rooted_coords = (2, 3);
+    });
+    screen.draw();
+
+    // far past the edge, double clicks make nothing
+    for _ in 0..2 {
+        screen.handle_event(Event::Mouse(MouseEvent::Press(MouseButton::Left, 65000, 3)));
+        screen.handle_event(Event::Mouse(MouseEvent::Release(65000, 3)));
+    }
+    assert_eq!(screen.nodes.len(), 2);
+    assert_eq!(screen.last_mouse, None);
+
+    // a little past it, they're moved onto it
+    screen.handle_event(Event::Mouse(MouseEvent::Press(MouseButton::Left, 85, 0)));
+    assert_eq!(screen.last_mouse, Some(((80, 1), None)));
+
+    // dragging an anchor far off is dropped on the way, so it stays put
+    screen.handle_event(Event::Mouse(MouseEvent::Press(MouseButton::Left, 2, 3)));
+    screen.handle_event(Event::Mouse(MouseEvent::Hold(40000, 3)));
+    screen.handle_event(Event::Mouse(MouseEvent::Release(40000, 3)));
+    assert_eq!(screen.with_node(a, |n| n.rooted_coords), Some((2, 3)));
+}
+
+#[test]
+fn test_recall_strays() {
+    let mut screen = Screen::default();
+    let (a, b, c, d) = (screen.new_node(), screen.new_node(), screen.new_node(), screen.new_node());
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a, b, c, d]);
+    for &(id, coords) in &[(a, (5, 7)), (b, (65000, 3)), (c, (4, 60000)), (d, (65000, 7))] {
+        screen.with_node_mut_no_meta(id, |n| n.rooted_coords = coords);
+    }
+
+    let data = serialization::serialize_screen(&screen);
+    let loaded = serialization::deserialize_screen(data).unwrap();
+    assert!(loaded.dirty);
+    let coords = |id| loaded.with_node(id, |n| n.rooted_coords).unwrap();
+    assert_eq!(coords(a), (5, 7));
+    assert_eq!(coords(b), (1, 3));
+    // below the lowest anchor that was in reach
+    assert_eq!(coords(c), (4, 8));
+    // not on top of another
+    assert_eq!(coords(d), (1, 7));
+}
+
+#[test]
+fn test_alternate_screen() {
+    let mut screen = Screen {
+        is_test: true,
+        ..Screen::default()
+    };
+    let out = frame::capture(|| {
+        screen.start_raw_mode().unwrap();
+        // coming back from a prompt or help doesn't enter it again
+        screen.start_raw_mode().unwrap();
+        screen.suspend(|| out!("$EDITOR")).unwrap();
+        screen.leave_terminal();
+        screen.leave_terminal();
+    });
+    let mut seen: Vec<(usize, &str)> = vec![];
+    for &(ref text, name) in &[(ToAlternateScreen.to_string(), "enter"),
+                           (ToMainScreen.to_string(), "leave"),
+                           ("$EDITOR".to_owned(), "$EDITOR")] {
+        seen.extend(out.match_indices(text.as_str()).map(|(at, _)| (at, name)));
+    }
+    seen.sort();
+    let order: Vec<&str> = seen.into_iter().map(|(_, name)| name).collect();
+    assert_eq!(order, vec!["enter", "leave", "$EDITOR", "enter", "leave"]);
+}
+
+#[test]
+fn test_repair_on_load() {
+    let mut screen = Screen::default();
+    let (a, b, c) = (screen.new_node(), screen.new_node(), screen.new_node());
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a, c]);
+    screen.with_node_mut_no_meta(a, |n| n.children = vec![b]);
+    screen.with_node_mut_no_meta(b, |n| n.parent_id = a);
+    screen.with_node_mut_no_meta(c, |n| n.rooted_coords = (1, 10));
+
+    // a whole map loads as it was saved
+    let loaded = serialization::deserialize_screen(serialization::serialize_screen(&screen))
+        .unwrap();
+    assert!(!loaded.dirty);
+    assert_eq!(loaded.nodes.len(), 4);
+
+    // a child that's gone, an anchor at 0 and a node nothing leads to
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a]);
+    screen.with_node_mut_no_meta(a, |n| {
+        n.children.push(99);
+        n.rooted_coords = (0, 0);
+    });
+    let loaded = serialization::deserialize_screen(serialization::serialize_screen(&screen))
+        .unwrap();
+    assert!(loaded.dirty);
+    assert_eq!(loaded.check_invariants(), Ok(()));
+    assert_eq!(loaded.with_node(a, |n| (n.children.clone(), n.rooted_coords)),
+               Some((vec![b], (1, 1))));
+    let recovered = loaded.max_id;
+    assert_eq!(loaded.with_node(recovered, |n| (n.content.clone(), n.children.clone())),
+               Some(("Recovered".to_owned(), vec![c])));
+}
+
+#[test]
+fn test_select_children() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (80, 24),
+        ..Screen::default()
+    };
+    let ids = [screen.new_node(), screen.new_node(), screen.new_node(), screen.new_node()];
+    let (list, milk, eggs, bread) = (ids[0], ids[1], ids[2], ids[3]);
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![list]);
+    screen.with_node_mut_no_meta(list, |n| {
+        n.children = vec![milk, eggs, bread];
+        n.rooted_coords = (2, 3);
+    });
+    for (&id, &content) in ids.iter().zip(&["list", "milk", "eggs", "bread"]) {
+        screen.with_node_mut_no_meta(id, |n| n.content = content.to_owned());
+    }
+    for &id in &[milk, eggs, bread] {
+        screen.with_node_mut_no_meta(id, |n| n.parent_id = list);
+    }
+    screen.with_node_mut_no_meta(eggs, |n| n.stricken = true);
+    let struck = |screen: &Screen, id| screen.with_node(id, |n| n.stricken).unwrap();
+
+    screen.select_node(list);
+    screen.perform(Action::SelectChildren).unwrap();
+    screen.draw();
+    let underline = format!("{}", style::Underline);
+    for &id in &[milk, eggs, bread] {
+        let (x, y) = *screen.drawn_at(id).unwrap();
+        let style = screen.last_frame.as_ref().unwrap().style_at((x + 4, y)).unwrap().to_owned();
+        assert!(style.contains(&underline), "{:?}", style);
+    }
+    let (x, y) = *screen.drawn_at(list).unwrap();
+    assert!(!screen.last_frame.as_ref().unwrap().style_at((x + 1, y)).unwrap().contains(&underline));
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    assert!(text.contains("3 children: C-a strike, M-# tag, del delete"), "{}", text);
+
+    // all of them are struck, and eggs isn't brought back
+    screen.handle_event(Event::Key(Key::Ctrl('a')));
+    assert!(struck(&screen, milk) && struck(&screen, eggs) && struck(&screen, bread));
+    assert!(screen.selection.is_empty());
+    assert_eq!(screen.selected, Some(list));
+
+    // anything else leaves the selection, and is done as usual, like
+    // saving with C-x
+    let path = env::temp_dir().join(format!("void-select-children-test-{}", unsafe { getpid() }));
+    let path = path.to_str().unwrap().to_owned();
+    screen.work_path = Some(path.clone());
+    screen.dirty = true;
+    screen.perform(Action::SelectChildren).unwrap();
+    screen.handle_event(Event::Key(Key::Ctrl('x')));
+    assert!(screen.selection.is_empty());
+    assert!(!screen.dirty);
+    assert!(!struck(&screen, list) && struck(&screen, milk));
+    remove_file(&path).unwrap();
+    let _ = remove_file(session::path(&path));
+    screen.work_path = None;
+
+    screen.perform(Action::SelectChildren).unwrap();
+    screen.handle_event(Event::Key(Key::Delete));
+    assert_eq!(screen.modal, Some(Modal::DeleteSelection(vec![milk, eggs, bread], 3)));
+    screen.handle_event(Event::Key(Key::Char('y')));
+    assert!(!screen.exists(milk) && !screen.exists(eggs) && !screen.exists(bread));
+    assert_eq!(screen.with_node(list, |n| n.children.clone()), Some(vec![]));
+    assert_eq!(screen.selected, Some(list));
+}
+
+#[test]
+fn test_progress_bars() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (80, 24),
+        ..Screen::default()
+    };
+    let list = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![list]);
+    screen.with_node_mut_no_meta(list, |n| {
+        n.content = "list".to_owned();
+        n.rooted_coords = (2, 3);
+    });
+    // three groups of two, the first of them done along with its group
+    let mut items = vec![];
+    for i in 0..3 {
+        let group = screen.new_node();
+        screen.with_node_mut_no_meta(list, |n| n.children.push(group));
+        screen.with_node_mut_no_meta(group, |n| {
+            n.content = format!("group {}", i);
+            n.parent_id = list;
+            n.stricken = i == 0;
+        });
+        for _ in 0..2 {
+            let item = screen.new_node();
+            screen.with_node_mut_no_meta(group, |n| n.children.push(item));
+            screen.with_node_mut_no_meta(item, |n| {
+                n.content = "item".to_owned();
+                n.parent_id = group;
+                n.stricken = i == 0;
+            });
+            items.push(item);
         }
-        let plot = plot::bounded_count_sparkline(nodes, since as i64, until as i64, buckets);
-        format!("|{}|", plot)
     }
+    screen.with_node_mut_no_meta(items[2], |n| n.stricken = true);
+
+    // off by default
+    screen.draw();
+    assert!(!screen.last_frame.as_ref().unwrap().text(false).contains("/9"));
+
+    screen.config.progress = Some(Progress::Descendants);
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    assert!(text.contains("list ▓▓▓▓░░░░ 4/9"), "{}", text);
+    assert!(text.contains("group 1 ▓▓▓▓░░░░ 1/2"), "{}", text);
+    assert!(text.contains("group 0 ▓▓▓▓▓▓▓▓ 2/2"), "{}", text);
+    assert_eq!(screen.progress.get(&items[0]), None);
+
+    screen.config.progress = Some(Progress::Children);
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    assert!(text.contains("list ▓▓▓░░░░░ 1/3"), "{}", text);
+    assert_eq!(screen.progress_of(list, Progress::Descendants), (4, 9));
+
+    // narrower, the bar shrinks, and then there's only the count
+    screen.dims = (16, 24);
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    assert!(text.contains("list ▓░░ 1/3"), "{}", text);
+    screen.dims = (13, 24);
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    assert!(text.contains("list 1/3"), "{}", text);
+
+    // exports leave it out
+    assert!(!export::markdown(&screen).contains("1/3"));
 }
 
-enum SearchDirection {
-    Forward,
-    Backward,
+#[test]
+fn test_merge_moves_clashing_anchors() {
+    let map = |content: &str, ctime| {
+        let mut screen = Screen {
+            is_test: true,
+            ..Screen::default()
+        };
+        let id = screen.new_node();
+        screen.with_node_mut_no_meta(0, |n| n.children = vec![id]);
+        screen.with_node_mut_no_meta(id, |n| {
+            n.content = content.to_owned();
+            n.rooted_coords = (2, 3);
+            n.meta.ctime = ctime;
+        });
+        screen
+    };
+    let anchors = |screen: &Screen| {
+        let mut anchors: Vec<(String, Coords)> = screen.nodes
+            .values()
+            .filter(|n| n.parent_id == 0 && n.id != 0)
+            .map(|n| (n.content.clone(), n.rooted_coords))
+            .collect();
+        anchors.sort();
+        anchors
+    };
+
+    let mut ours = map("mine", 100);
+    ours.merge(&map("theirs", 200));
+    let mut others = map("theirs", 200);
+    others.merge(&map("mine", 100));
+    assert_eq!(anchors(&ours),
+               vec![("mine".to_owned(), (2, 3)), ("theirs".to_owned(), (2, 4))]);
+    assert_eq!(anchors(&others), anchors(&ours));
+    assert_eq!(ours.check_invariants(), Ok(()));
 }
 
-enum PlotType {
-    New,
-    Done,
+#[test]
+fn test_merge_keeps_question() {
+    let mut screen = Screen {
+        is_test: true,
+        ..Screen::default()
+    };
+    let project = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![project]);
+    screen.select_node(project);
+    screen.modal = Some(Modal::Delete(project, 1));
+    let theirs = serialization::deserialize_screen(serialization::serialize_screen(&screen))
+        .unwrap();
+    screen.merge(&theirs);
+    assert_eq!(screen.modal, Some(Modal::Delete(project, 1)));
+
+    // their copy deleted it, so there's nothing left to ask about
+    let mut theirs = theirs;
+    theirs.is_test = true;
+    theirs.select_node(project);
+    theirs.delete_selected_unconfirmed(true);
+    screen.merge(&theirs);
+    assert!(!screen.exists(project));
+    assert_eq!(screen.modal, None);
 }
 
-fn visible(view_y: u16, height: u16, y: u16) -> bool {
-    y > view_y && y < view_y + height
+#[test]
+fn test_arrange_too_wide() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (22, 49),
+        ..Screen::default()
+    };
+    let mut events = vec![Event::Key(Key::Ctrl('n'))];
+    events.extend("d=cat #tagged=cat ".chars().map(|c| Event::Key(Key::Char(c))));
+    events.push(Event::Mouse(MouseEvent::Press(MouseButton::Right, 12, 8)));
+    events.push(Event::Key(Key::Char('\n')));
+    for evt in events {
+        assert!(screen.handle_event(evt));
+        if screen.should_auto_arrange() {
+            screen.arrange();
+        }
+        screen.draw();
+        assert_eq!(screen.check_invariants(), Ok(()));
+    }
+
+    // an anchor too wide to fit anywhere goes under the ones that do
+    let anchors = screen.with_node(0, |n| n.children.clone()).unwrap();
+    assert_eq!(anchors.len(), 2);
+    let (wide, narrow) = (screen.nodes[&anchors[0]].rooted_coords,
+                          screen.nodes[&anchors[1]].rooted_coords);
+    assert!(wide.1 > narrow.1, "{:?} {:?}", wide, narrow);
+}
+
+#[test]
+fn test_narrow_split() {
+    let keys = vec![Key::Ctrl('w'),
+                    Key::Char('v'),
+                    Key::Ctrl('n'),
+                    Key::Ctrl('w'),
+                    Key::Ctrl('w'),
+                    Key::Right,
+                    Key::Char('w'),
+                    Key::Alt('z'),
+                    Key::Ctrl('n'),
+                    Key::Ctrl('w'),
+                    Key::Ctrl('w'),
+                    Key::Char('\t')];
+    // too narrow to split at all, or wide enough for each pane
+    for &width in &[30, 41, 80] {
+        let mut screen = Screen {
+            is_test: true,
+            dims: (width, 24),
+            ..Screen::default()
+        };
+        for &key in &keys {
+            screen.handle_event(Event::Key(key));
+            if screen.should_auto_arrange() {
+                screen.arrange();
+            }
+            screen.draw();
+            assert_eq!(screen.check_invariants(), Ok(()), "{} wide, after {:?}", width, key);
+        }
+        assert_eq!(screen.split.is_some(), width >= 41, "{} wide", width);
+    }
+}
+
+#[test]
+fn test_node_text() {
+    use capabilities::UNICODE;
+
+    let struck = Some((STRIKE, NO_STRIKE));
+    assert_eq!(node_text("buy milk", "", "", struck, 20, &UNICODE), "\x1b[9mbuy milk\x1b[29m");
+    assert_eq!(node_text("buy milk", "", "", None, 20, &UNICODE), "buy milk");
+    assert_eq!(node_text("groceries", " ✓2", "", None, 20, &UNICODE), "groceries ✓2");
+    assert_eq!(node_text("", "", "", struck, 20, &UNICODE), "");
+
+    // cut inside the content, the style still ends before the ellipsis
+    assert_eq!(node_text("buy milk", "", "", struck, 5, &UNICODE), "\x1b[9mbuy \x1b[29m…");
+    assert_eq!(node_text("groceries", " ✓2", "", struck, 11, &UNICODE),
+               "\x1b[9mgroceries\x1b[29m …");
+    assert_eq!(node_text("buy milk", "", "", struck, 1, &UNICODE), "…");
+
+    // ages are dimmed, and cut last
+    assert_eq!(node_text("groceries", " ✓2", " (3d)", None, 20, &UNICODE),
+               "groceries ✓2\x1b[2m (3d)\x1b[22m");
+    assert_eq!(node_text("groceries", "", " (3d)", None, 13, &UNICODE),
+               "groceries\x1b[2m (3\x1b[22m…");
+}
+
+#[test]
+fn test_age_hit_box() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (80, 24),
+        ..Screen::default()
+    };
+    let (a, b) = (screen.new_node(), screen.new_node());
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a]);
+    screen.with_node_mut_no_meta(a, |n| {
+        n.content = "plans".to_owned();
+        n.children = vec![b];
+        n.rooted_coords = (1, 2);
+        n.meta.ctime -= 3 * 24 * 60 * 60;
+    });
+    screen.with_node_mut_no_meta(b, |n| {
+        n.parent_id = a;
+        n.content = "call".to_owned();
+        n.stricken = true;
+        n.meta.finish_time = Some(n.meta.ctime - 2 * 7 * 24 * 60 * 60);
+    });
+    let hit_box = |screen: &Screen, node_id: NodeID| {
+        let (x, y) = screen.drawn_at[&node_id];
+        (x..screen.dims.0).take_while(|&x| screen.lookup.get(&(x, y)) == Some(&node_id)).count()
+    };
+    screen.draw();
+    let plain = (hit_box(&screen, a), hit_box(&screen, b));
+    assert_eq!(plain.0, " plans".len());
+
+    // the age is part of the node as far as clicks go
+    screen.perform(Action::ToggleAges).unwrap();
+    screen.draw();
+    let (x, y) = screen.drawn_at[&a];
+    assert_eq!(hit_box(&screen, a), " plans (3d)".len());
+    assert_eq!(hit_box(&screen, b), plain.1 + " (done 2w ago)".len());
+    assert_eq!(screen.lookup.get(&(x + 11, y)), None);
+
+    screen.perform(Action::ToggleAges).unwrap();
+    screen.draw();
+    assert_eq!((hit_box(&screen, a), hit_box(&screen, b)), plain);
+}
+
+#[test]
+fn test_clip_width() {
+    use capabilities::UNICODE;
+
+    let mut lookup = HashMap::new();
+    assert_eq!(clip_width(&lookup, 1, (1, 3), 80), 78);
+    assert_eq!(clip_width(&lookup, 1, (79, 3), 80), 0);
+    assert_eq!(clip_width(&lookup, 1, (90, 3), 80), 0);
+
+    // another node starting at x = 30 leaves a one cell gap
+    lookup.insert((30, 3), 2);
+    lookup.insert((12, 3), 1);
+    lookup.insert((20, 4), 2);
+    assert_eq!(clip_width(&lookup, 1, (10, 3), 80), 19);
+    assert_eq!(clip_width(&lookup, 2, (30, 3), 80), 49);
+    assert_eq!(clip_width(&lookup, 1, (28, 3), 80), 1);
+
+    // the text exactly filling the clipped width isn't cut
+    let url = "https://example.com/".repeat(15);
+    let width = clip_width(&lookup, 1, (10, 3), 80);
+    assert_eq!(node_text(&url[..width], "", "", None, width, &UNICODE), &url[..width]);
+    assert_eq!(node_text(&url[..width + 1], "", "", None, width, &UNICODE),
+               format!("{}…", &url[..width - 1]));
+}
+
+#[test]
+fn test_control_characters() {
+    use capabilities::{ASCII, UNICODE};
+
+    let mut screen = Screen::default();
+    let a = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a]);
+    screen.select_node(a);
+    for c in "a\x1b[2Jb\x07\u{9b}\tc".chars() {
+        screen.append(c);
+    }
+    assert_eq!(screen.with_node(a, |n| n.content.clone()).unwrap(), "a[2Jb c");
+
+    // content that has them anyway is drawn without them
+    let text = node_text("a\x1bb\x07", "", "", None, 20, &UNICODE);
+    assert_eq!(text, "a␛b␇");
+    assert!(!text.chars().any(|c| c.is_control()));
+    assert_eq!(node_text("a\x1bb\x07", "", "", None, 20, &ASCII), "a?b?");
+
+    // and so is the path to it on the status line
+    screen.dims = (80, 24);
+    screen.with_node_mut_no_meta(a, |n| n.content = "a\x1b[2Jb".to_owned());
+    let status = screen.status_text();
+    assert!(status.starts_with("a␛[2Jb"), "{}", status);
+    assert!(!status.chars().any(|c| c.is_control()), "{}", status);
+}
+
+#[test]
+fn test_truncate_left() {
+    use capabilities::{ASCII, UNICODE};
+
+    assert_eq!(truncate_left("a › b › c", 20, &UNICODE), "a › b › c");
+    assert_eq!(truncate_left("a › b › c", 6, &UNICODE), "…b › c");
+    assert_eq!(truncate_left("abc", 0, &UNICODE), "");
+    assert_eq!(truncate_left("abcdef", 3, &ASCII), "~ef");
+}
+
+#[test]
+fn test_incremental_draw() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (80, 24),
+        ..Screen::default()
+    };
+    let anchor = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![anchor]);
+    screen.with_node_mut_no_meta(anchor, |n| {
+        n.content = "tasks".to_owned();
+        n.rooted_coords = (3, 3);
+        // long enough ago for the status bar not to tick over mid-test
+        n.meta.ctime = 0;
+    });
+    screen.select_node(anchor);
+
+    let first = frame::capture(|| screen.draw());
+    assert!(first.starts_with(&format!("{}", clear::All)));
+    assert!(first.contains("tasks"));
+
+    // nothing changed, nothing sent
+    assert_eq!(frame::capture(|| screen.draw()), "");
+    assert_eq!(frame::capture(|| screen.draw()), "");
+
+    // one more character sends the new cell on its own, and the status bar
+    // where it shows the node's content
+    screen.handle_event(Event::Key(Key::Char('s')));
+    assert_eq!(screen.with_node(anchor, |n| n.content.clone()).unwrap(), "taskss");
+    let append = frame::capture(|| screen.draw());
+    let node_row: Vec<&str> = append.split("\x1b[").filter(|s| s.starts_with("3;")).collect();
+    assert_eq!(node_row, vec!["3;9H"]);
+    assert!(!append.contains(&format!("{}", clear::All)));
+    assert!(append.len() < 120 && append.len() * 4 < first.len(), "{:?}", append);
+
+    // anything printed outside of a draw means starting over
+    out!("{}prompt", cursor::Goto(1, 24));
+    assert!(frame::capture(|| screen.draw()).starts_with(&format!("{}", clear::All)));
+    screen.dims = (60, 20);
+    assert!(frame::capture(|| screen.draw()).starts_with(&format!("{}", clear::All)));
+    assert_eq!(frame::capture(|| screen.draw()), "");
+}
+
+#[test]
+fn test_canvas() {
+    let saved = include_bytes!("../test/golden/cat.db").to_vec();
+    let mut screen = serialization::deserialize_screen(saved).unwrap();
+    screen.dims = (20, 5);
+    assert_eq!(screen.canvas(false), include_str!("../test/golden/cat-canvas.txt"));
+    assert_eq!(export::text(&screen), include_str!("../test/golden/cat-outline.txt"));
+    // the terminal's size has nothing to do with it
+    assert_eq!(screen.dims, (20, 5));
+    let styled = screen.canvas(true);
+    assert!(styled.contains("\x1b["));
+    assert!(!styled.contains("\x1b[H") && !styled.contains("\x1b[2J"));
+}
+
+#[test]
+fn test_colorless_rendering() {
+    use colors::Theme;
+
+    fn render(rendering: Rendering) -> String {
+        let mut screen = Screen::default();
+        screen.dims = (80, 24);
+        screen.rendering = rendering;
+        let a = screen.new_node();
+        let a1 = screen.new_node();
+        let a2 = screen.new_node();
+        screen.with_node_mut_no_meta(0, |n| n.children = vec![a]);
+        screen.with_node_mut_no_meta(a, |n| {
+            n.content = "plans".to_owned();
+            n.rooted_coords = (2, 2);
+            n.children = vec![a1, a2];
+        });
+        screen.with_node_mut_no_meta(a1, |n| {
+            n.parent_id = a;
+            n.content = "done".to_owned();
+            n.stricken = true;
+        });
+        screen.with_node_mut_no_meta(a2, |n| {
+            n.parent_id = a;
+            n.content = "next".to_owned();
+        });
+        screen.select_node(a2);
+        let output = frame::capture(|| screen.draw());
+        assert!(!export::html(&screen).contains("\x1b["));
+        assert!(!export::text(&screen).contains("\x1b["));
+        output
+    }
+    fn styles(output: &str) -> Vec<String> {
+        let re = Regex::new("\x1b\\[([0-9;]*)m").unwrap();
+        re.captures_iter(output).map(|c| c.at(1).unwrap().to_owned()).collect()
+    }
+
+    let colored = render(Rendering::default());
+    assert!(styles(&colored).iter().any(|s| s.starts_with("38;5;")));
+    assert!(!colored.contains(">next"));
+
+    let plain = render(Rendering {
+        color: false,
+        theme: Theme::Default,
+    });
+    assert!(plain.contains(">next"));
+    assert!(plain.contains("\x1b[7m"));
+    assert!(styles(&plain).iter().all(|s| ["", "0", "7", "27"].contains(&&**s)));
+    // cursor movement is left alone
+    assert!(plain.contains(&format!("{}", cursor::Goto(3, 3))));
+}
+
+#[test]
+fn test_ascii_glyphs() {
+    let mut screen = Screen::default();
+    screen.dims = (80, 24);
+    screen.capabilities.unicode = false;
+    screen.show_logs = true;
+    let a = screen.new_node();
+    let a1 = screen.new_node();
+    let a2 = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a]);
+    screen.with_node_mut_no_meta(a, |n| {
+        n.content = "plans".to_owned();
+        n.rooted_coords = (2, 2);
+        n.children = vec![a1, a2];
+    });
+    screen.with_node_mut_no_meta(a1, |n| {
+        n.parent_id = a;
+        n.content = "done".to_owned();
+        n.stricken = true;
+    });
+    screen.with_node_mut_no_meta(a2, |n| {
+        n.parent_id = a;
+        n.content = "see https://example.com".to_owned();
+    });
+    screen.arrows.push((a1, a2));
+    screen.select_node(a2);
+
+    let output = frame::capture(|| screen.draw());
+    assert!(output.is_ascii(), "{:?}", output);
+    assert!(output.contains("+-x"));
+    assert!(output.contains("plans > see"));
+}
+
+#[test]
+fn test_search_matches() {
+    let mut screen = Screen::default();
+    screen.is_test = true;
+    let add = |screen: &mut Screen, parent_id: NodeID, content: &str| {
+        let id = screen.new_node();
+        screen.with_node_mut_no_meta(id, |n| {
+            n.parent_id = parent_id;
+            n.content = content.to_owned();
+        });
+        screen.with_node_mut_no_meta(parent_id, |p| p.children.push(id));
+        id
+    };
+    let list = add(&mut screen, 0, "errands");
+    let milk = add(&mut screen, list, "buy milk");
+    let car = add(&mut screen, list, "sell car");
+    let eggs = add(&mut screen, list, "buy eggs");
+    // out of sight, but still found
+    let bread = add(&mut screen, eggs, "buy bread");
+    screen.with_node_mut_no_meta(eggs, |n| n.collapsed = true);
+    screen.select_node(car);
+
+    screen.handle_event(Event::Key(Key::Alt('/')));
+    assert!(screen.matches.is_none());
+    screen.last_search = Some(("buy".to_owned(), milk));
+    screen.handle_event(Event::Key(Key::Alt('/')));
+    assert_eq!(screen.matches.as_ref().map(|m| m.len()), Some(3));
+    screen.handle_event(Event::Key(Key::Ctrl('a')));
+    assert!(screen.matches.is_none());
+    let struck = |screen: &Screen, id| screen.with_node(id, |n| n.stricken).unwrap();
+    assert!(struck(&screen, milk) && struck(&screen, eggs) && struck(&screen, bread));
+    assert!(!struck(&screen, car));
+    assert_eq!(screen.selected, Some(car));
+
+    assert_eq!(screen.tag_all(&[milk, bread], "shop"), 2);
+    assert_eq!(screen.tag_all(&[milk, eggs], "shop"), 1);
+    assert_eq!(screen.with_node(milk, |n| n.content.clone()).unwrap(), "buy milk #shop");
+    assert_eq!(screen.tag_db.node_tags(eggs), vec!["shop"]);
+
+    // deleting asks first
+    screen.handle_event(Event::Key(Key::Alt('/')));
+    screen.handle_event(Event::Key(Key::Delete));
+    screen.handle_event(Event::Key(Key::Char('n')));
+    assert!(screen.exists(milk));
+    screen.handle_event(Event::Key(Key::Alt('/')));
+    screen.handle_event(Event::Key(Key::Delete));
+    screen.handle_event(Event::Key(Key::Char('y')));
+    assert!(!screen.exists(milk) && !screen.exists(eggs) && !screen.exists(bread));
+    assert_eq!(screen.with_node(list, |n| n.children.clone()).unwrap(), vec![car]);
+    assert_eq!(screen.selected, Some(car));
+}
+
+#[test]
+fn test_export_prompt() {
+    let path = env::temp_dir().join(format!("void-export-test-{}", unsafe { getpid() }));
+    let path = path.to_str().unwrap().to_owned();
+    let mut screen = Screen {
+        is_test: true,
+        dims: (60, 12),
+        work_path: Some(path.clone()),
+        ..Screen::default()
+    };
+    let ids: Vec<NodeID> = (0..3).map(|_| screen.new_node()).collect();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![ids[0], ids[2]]);
+    screen.with_node_mut_no_meta(ids[0], |n| n.children = vec![ids[1]]);
+    for (&id, &content) in ids.iter().zip(&["trip", "pack", "taxes"]) {
+        screen.with_node_mut_no_meta(id, |n| {
+            n.parent_id = if id == ids[1] { ids[0] } else { 0 };
+            n.content = content.to_owned();
+        });
+    }
+    screen.arrows.push((ids[1], ids[2]));
+    let exported = || {
+        let mut html = String::new();
+        File::open(format!("{}.html", path)).unwrap().read_to_string(&mut html).unwrap();
+        remove_file(format!("{}.html", path)).unwrap();
+        html
+    };
+
+    // with nothing selected there's nothing to ask
+    assert!(screen.handle_event(Event::Key(Key::Alt('w'))));
+    assert!(exported().contains("taxes"));
+
+    screen.selected = Some(ids[0]);
+    assert!(screen.handle_event(Event::Key(Key::Alt('w'))));
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    assert!(text.contains("export (s)elected subtree or (w)hole file?"), "{}", text);
+    assert!(screen.handle_event(Event::Key(Key::Char('s'))));
+    let html = exported();
+    assert!(html.contains("<summary>trip</summary>") && !html.contains("<li>taxes"));
+    assert!(html.contains("<tr><td>trip › pack</td><td>taxes</td></tr>"));
+
+    assert!(screen.handle_event(Event::Key(Key::Alt('w'))));
+    assert!(screen.handle_event(Event::Key(Key::Char('w'))));
+    assert!(exported().contains(&format!("<li id=\"node-{}\">taxes</li>", ids[2])));
+
+    // anything else lets it go
+    assert!(screen.handle_event(Event::Key(Key::Alt('w'))));
+    assert!(screen.handle_event(Event::Key(Key::Esc)));
+    assert!(File::open(format!("{}.html", path)).is_err());
+    assert_eq!(screen.selected, Some(ids[0]));
+}
+
+#[test]
+fn test_split_panes() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (61, 16),
+        ..Screen::default()
+    };
+    let ids: Vec<NodeID> = (0..2).map(|_| screen.new_node()).collect();
+    screen.with_node_mut_no_meta(0, |n| n.children = ids.clone());
+    for (&id, &(content, y)) in ids.iter().zip(&[("backlog", 3), ("sprint", 12)]) {
+        screen.with_node_mut_no_meta(id, |n| {
+            n.content = content.to_owned();
+            n.rooted_coords = (2, y);
+            n.meta.ctime = 0;
+        });
+    }
+    let key = |screen: &mut Screen, key: Key| assert!(screen.handle_event(Event::Key(key)));
+    let click = |screen: &mut Screen, (x, y): Coords| {
+        assert!(screen.handle_event(Event::Mouse(MouseEvent::Press(MouseButton::Left, x, y))));
+        assert!(screen.handle_event(Event::Mouse(MouseEvent::Release(x, y))));
+    };
+    let focus = |screen: &Screen| screen.split.as_ref().map(|split| split.focus);
+
+    key(&mut screen, Key::Ctrl('w'));
+    key(&mut screen, Key::Char('v'));
+    assert_eq!((focus(&screen), screen.dims), (Some(Side::Left), (30, 16)));
+    key(&mut screen, Key::Ctrl('w'));
+    key(&mut screen, Key::Char('w'));
+    screen.view_y = 9;
+    key(&mut screen, Key::Ctrl('w'));
+    key(&mut screen, Key::Char('w'));
+    assert_eq!(screen.view_y, 0);
+
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    let lines: Vec<Vec<char>> = text.lines().map(|l| l.chars().collect()).collect();
+    let part = |y: usize, from: usize, to: usize| -> String {
+        lines[y - 1].iter().skip(from - 1).take(to + 1 - from).collect()
+    };
+    assert!(part(3, 1, 30).contains("backlog") && part(3, 32, 61).contains("sprint"), "{}", text);
+    assert!(part(12, 1, 30).contains("sprint") && !part(12, 32, 61).contains("sprint"));
+    let divider = screen.glyphs().vertical;
+    assert!(lines.iter().take(16).all(|line| line.get(30) == Some(&divider)), "{}", text);
+
+    // each pane keeps its own selection, and a click moves the focus
+    click(&mut screen, (3, 3));
+    assert_eq!(screen.selected, Some(ids[0]));
+    click(&mut screen, (34, 3));
+    assert_eq!((focus(&screen), screen.selected), (Some(Side::Right), Some(ids[1])));
+    key(&mut screen, Key::Ctrl('w'));
+    key(&mut screen, Key::Ctrl('w'));
+    assert_eq!((screen.selected, screen.view_y), (Some(ids[0]), 0));
+    assert!(screen.nodes[&ids[0]].selected && !screen.nodes[&ids[1]].selected);
+    click(&mut screen, (31, 5));
+    assert_eq!(focus(&screen), Some(Side::Left));
+
+    // an edit in one is there in the other
+    click(&mut screen, (34, 3));
+    key(&mut screen, Key::Char('x'));
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    assert!(text.lines().nth(11).unwrap().contains("sprintx"), "{}", text);
+
+    key(&mut screen, Key::Ctrl('w'));
+    key(&mut screen, Key::Char('q'));
+    assert_eq!((focus(&screen), screen.dims), (None, (61, 16)));
+    assert_eq!((screen.selected, screen.view_y), (Some(ids[1]), 9));
+
+    // deleting what the other pane is drilled down into takes it back out
+    key(&mut screen, Key::Ctrl('w'));
+    key(&mut screen, Key::Char('v'));
+    key(&mut screen, Key::Ctrl('w'));
+    key(&mut screen, Key::Char('w'));
+    screen.select_node(ids[1]);
+    screen.perform(Action::DrillDown).unwrap();
+    key(&mut screen, Key::Ctrl('w'));
+    key(&mut screen, Key::Char('w'));
+    assert_eq!((screen.selected, screen.drawing_root), (Some(ids[1]), ids[1]));
+    key(&mut screen, Key::Delete);
+    assert!(!screen.exists(ids[1]));
+    assert_eq!(screen.drawing_root, 0);
+    assert_eq!(screen.check_invariants(), Ok(()));
+    screen.draw();
+
+    // and what's collapsed in one is collapsed in the other, where what
+    // was selected under it gives way to it
+    let task = screen.new_node();
+    screen.with_node_mut_no_meta(task, |n| {
+        n.parent_id = ids[0];
+        n.content = "task".to_owned();
+    });
+    screen.with_node_mut_no_meta(ids[0], |n| n.children = vec![task]);
+    screen.select_node(task);
+    key(&mut screen, Key::Ctrl('w'));
+    key(&mut screen, Key::Char('w'));
+    screen.select_node(ids[0]);
+    key(&mut screen, Key::Ctrl('s'));
+    key(&mut screen, Key::Ctrl('w'));
+    key(&mut screen, Key::Char('w'));
+    assert_eq!(screen.selected, Some(ids[0]));
+    assert_eq!(screen.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_modals_ignore_clicks() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (60, 16),
+        ..Screen::default()
+    };
+    screen.delete_confirm_threshold = 0;
+    let ids: Vec<NodeID> = (0..3).map(|_| screen.new_node()).collect();
+    let (project, task, other) = (ids[0], ids[1], ids[2]);
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![project, other]);
+    for (&id, &(content, y)) in ids.iter().zip(&[("project", 3), ("task", 4), ("other", 10)]) {
+        screen.with_node_mut_no_meta(id, |n| {
+            n.content = content.to_owned();
+            n.rooted_coords = (2, y);
+        });
+    }
+    screen.with_node_mut_no_meta(project, |n| n.children = vec![task]);
+    screen.with_node_mut_no_meta(task, |n| n.parent_id = project);
+    screen.draw();
+    let key = |screen: &mut Screen, key: Key| assert!(screen.handle_event(Event::Key(key)));
+    let click = |screen: &mut Screen, (x, y): Coords| {
+        assert!(screen.handle_event(Event::Mouse(MouseEvent::Press(MouseButton::Left, x, y))));
+        assert!(screen.handle_event(Event::Mouse(MouseEvent::Release(x, y))));
+    };
+    // clicks on a node, and a double click on empty space, which would
+    // make an anchor
+    let poke = |screen: &mut Screen| {
+        click(screen, (3, 10));
+        click(screen, (40, 13));
+        click(screen, (40, 13));
+        assert!(screen.handle_input(Input::Paste("pasted".to_owned())));
+    };
+
+    screen.select_node(project);
+    key(&mut screen, Key::Delete);
+    assert_eq!(screen.modal, Some(Modal::Delete(project, 2)));
+    poke(&mut screen);
+    key(&mut screen, Key::Char('x'));
+    assert_eq!(screen.modal, Some(Modal::Delete(project, 2)));
+    assert_eq!((screen.selected, screen.nodes.len()), (Some(project), 4));
+    key(&mut screen, Key::Esc);
+    assert!(screen.modal.is_none() && screen.exists(project));
+    key(&mut screen, Key::Delete);
+    poke(&mut screen);
+    key(&mut screen, Key::Char('y'));
+    assert!(!screen.exists(project) && screen.exists(other));
+
+    screen.select_node(other);
+    screen.export_prompt(ExportFormat::Html).unwrap();
+    poke(&mut screen);
+    assert_eq!(screen.modal, Some(Modal::Export(ExportFormat::Html, other)));
+    key(&mut screen, Key::Esc);
+
+    key(&mut screen, Key::Ctrl('w'));
+    poke(&mut screen);
+    assert_eq!(screen.modal, Some(Modal::Window));
+    key(&mut screen, Key::Esc);
+    assert!(screen.modal.is_none() && screen.split.is_none());
+
+    screen.modal = Some(Modal::BulkDelete(vec![other]));
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    assert!(text.contains("delete 1 matches and their children? (y/n)"), "{}", text);
+    poke(&mut screen);
+    key(&mut screen, Key::Char('n'));
+    assert!(screen.modal.is_none() && screen.exists(other));
+    assert_eq!((screen.selected, screen.nodes.len()), (Some(other), 2));
+}
+
+#[test]
+fn test_drag_preview() {
+    let new_screen = || {
+        let mut screen = Screen {
+            is_test: true,
+            dims: (60, 16),
+            ..Screen::default()
+        };
+        let (a, b) = (screen.new_node(), screen.new_node());
+        screen.with_node_mut_no_meta(0, |n| n.children = vec![a]);
+        screen.with_node_mut_no_meta(a, |n| {
+            n.content = "plans".to_owned();
+            n.rooted_coords = (2, 3);
+            n.children = vec![b];
+        });
+        screen.with_node_mut_no_meta(b, |n| {
+            n.content = "trip".to_owned();
+            n.parent_id = a;
+        });
+        screen.draw();
+        (screen, a)
+    };
+    let mouse = |screen: &mut Screen, evt| assert!(screen.handle_event(Event::Mouse(evt)));
+    let text = |screen: &mut Screen| {
+        screen.draw();
+        let text = screen.last_frame.as_ref().unwrap().text(false);
+        text.lines().map(|l| l.chars().collect()).collect::<Vec<Vec<char>>>()
+    };
+
+    // the outline follows the mouse, and nothing moves until the release
+    let (mut screen, a) = new_screen();
+    let g = screen.glyphs();
+    mouse(&mut screen, MouseEvent::Press(MouseButton::Left, 3, 3));
+    mouse(&mut screen, MouseEvent::Hold(20, 8));
+    let lines = text(&mut screen);
+    assert_eq!(lines[6][18], g.top_left);
+    assert_eq!(lines[9][18], g.bottom_left);
+    assert_eq!(screen.with_node(a, |n| n.rooted_coords), Some((2, 3)));
+
+    // escape puts it back
+    assert!(screen.handle_event(Event::Key(Key::Esc)));
+    mouse(&mut screen, MouseEvent::Release(20, 8));
+    assert_eq!(screen.with_node(a, |n| n.rooted_coords), Some((2, 3)));
+    assert!(!text(&mut screen)[6].contains(&g.top_left));
+
+    mouse(&mut screen, MouseEvent::Press(MouseButton::Left, 3, 3));
+    mouse(&mut screen, MouseEvent::Hold(20, 8));
+    mouse(&mut screen, MouseEvent::Release(20, 8));
+    assert_eq!(screen.with_node(a, |n| n.rooted_coords), Some((20, 8)));
+
+    // without motion, there's a marker where it started and a hint
+    let (mut screen, a) = new_screen();
+    mouse(&mut screen, MouseEvent::Press(MouseButton::Left, 3, 3));
+    let lines = text(&mut screen);
+    assert_eq!(lines[2][2], g.pointer);
+    let hint: String = lines[15].iter().collect();
+    assert_eq!(hint.trim_end(), "dragging: release to place");
+    mouse(&mut screen, MouseEvent::Release(20, 8));
+    assert_eq!(screen.with_node(a, |n| n.rooted_coords), Some((20, 8)));
+}
+
+#[test]
+fn test_group_drag() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (60, 16),
+        ..Screen::default()
+    };
+    let anchors = [("rocket", (12, 6)), ("fuel", (4, 12)), ("pad", (30, 2)), ("crew", (40, 12))];
+    let ids: Vec<NodeID> = anchors.iter().map(|_| screen.new_node()).collect();
+    screen.with_node_mut_no_meta(0, |n| n.children = ids.clone());
+    for (&id, &(content, coords)) in ids.iter().zip(&anchors) {
+        screen.with_node_mut_no_meta(id, |n| {
+            n.content = content.to_owned();
+            n.rooted_coords = coords;
+        });
+    }
+    let (rocket, fuel, pad, crew) = (ids[0], ids[1], ids[2], ids[3]);
+    for &id in &[rocket, fuel, pad] {
+        screen.groups.insert(id, "launch".to_owned());
+    }
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    assert!(text.contains("rocket ◆launch"));
+    assert!(!text.contains("crew ◆"));
+
+    // six left and two up, which takes fuel past the left edge and pad past
+    // the top, so each stops there while the rest of their move goes on
+    let mouse = |screen: &mut Screen, evt| assert!(screen.handle_event(Event::Mouse(evt)));
+    mouse(&mut screen, MouseEvent::Press(MouseButton::Left, 13, 6));
+    mouse(&mut screen, MouseEvent::Hold(6, 4));
+    mouse(&mut screen, MouseEvent::Release(6, 4));
+    let coords = |screen: &Screen, id| screen.with_node(id, |n| n.rooted_coords).unwrap();
+    assert_eq!(coords(&screen, rocket), (6, 4));
+    assert_eq!(coords(&screen, fuel), (1, 10));
+    assert_eq!(coords(&screen, pad), (24, 1));
+    assert_eq!(coords(&screen, crew), (40, 12));
+
+    // taken out of the group, it's left behind
+    screen.select_node(pad);
+    screen.handle_event(Event::Key(Key::Alt('G')));
+    assert_eq!(screen.groups.get(&pad), None);
+    screen.unselect();
+    screen.draw();
+    mouse(&mut screen, MouseEvent::Press(MouseButton::Left, 7, 4));
+    mouse(&mut screen, MouseEvent::Release(16, 5));
+    assert_eq!(coords(&screen, rocket), (16, 5));
+    assert_eq!(coords(&screen, fuel), (11, 11));
+    assert_eq!(coords(&screen, pad), (24, 1));
+
+    screen.select_node(fuel);
+    screen.delete_selected(false);
+    assert_eq!(screen.groups.keys().collect::<Vec<_>>(), vec![&rocket]);
+}
+
+// 50 anchors of 200 nodes each, two abreast and one above the other, each
+// with 20 tasks of 9 steps
+#[cfg(test)]
+fn large_map(dims: Coords) -> (Screen, Vec<NodeID>) {
+    let mut screen = Screen {
+        is_test: true,
+        dims,
+        ..Screen::default()
+    };
+    let mut anchors = vec![];
+    for a in 0..50 {
+        let anchor = screen.new_node();
+        screen.with_node_mut_no_meta(anchor, |n| {
+            n.content = format!("project {}", a);
+            n.rooted_coords = (2 + (a % 2) * 40, 2 + (a / 2) * 205);
+        });
+        for t in 0..20 {
+            let task = screen.new_node();
+            let steps: Vec<NodeID> = (0..9).map(|_| screen.new_node()).collect();
+            for (i, &step) in steps.iter().enumerate() {
+                screen.with_node_mut_no_meta(step, |n| {
+                    n.parent_id = task;
+                    n.content = format!("step {} of {}", i, t);
+                });
+            }
+            screen.with_node_mut_no_meta(task, |n| {
+                n.parent_id = anchor;
+                n.content = format!("task {} #t{}", t, t % 3);
+                n.children = steps.clone();
+            });
+            screen.with_node_mut_no_meta(anchor, |n| n.children.push(task));
+        }
+        anchors.push(anchor);
+    }
+    screen.with_node_mut_no_meta(0, |n| n.children = anchors.clone());
+    (screen, anchors)
+}
+
+#[test]
+fn test_cull_out_of_view() {
+    let (mut screen, anchors) = large_map((80, 24));
+    // how many nodes of each anchor's tree were formatted
+    let formatted = |screen: &Screen| {
+        let mut counts = BTreeMap::new();
+        for &id in &screen.formatted {
+            let anchor = screen.anchor(id).unwrap();
+            let a = anchors.iter().position(|&x| x == anchor).unwrap();
+            *counts.entry(a).or_insert(0) += 1;
+        }
+        counts.into_iter().collect::<Vec<_>>()
+    };
+
+    // the 22 rows between the header and the status bar, of the first two
+    screen.draw();
+    assert_eq!(formatted(&screen), vec![(0, 22), (1, 22)]);
+    assert!(screen.lookup.values().all(|&id| screen.anchor(id).unwrap() == anchors[0] ||
+                                           screen.anchor(id).unwrap() == anchors[1]));
+    // what isn't drawn is still placed, for scrolling and navigation
+    assert_eq!(screen.lowest_drawn, 2 + 24 * 205 + 200);
+    let last_step = screen.with_node(anchors[49], |n| *n.children.last().unwrap())
+        .and_then(|task| screen.with_node(task, |n| *n.children.last().unwrap()))
+        .unwrap();
+    assert_eq!(screen.drawn_at(last_step), Some(&(42, 5122)));
+
+    // the last row of the first two trees, and the top of the next two
+    screen.view_y = 200;
+    screen.draw();
+    assert_eq!(formatted(&screen), vec![(0, 1), (1, 1), (2, 17), (3, 17)]);
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    assert!(text.contains("step 8 of 19"));
+    assert!(text.contains("project 2"));
+    assert_eq!(screen.lookup((3, 207)), Some(&anchors[2]));
+    assert_eq!(screen.lookup((3, 2)), None);
+
+    // moving down into what's out of view brings it into view
+    let bottom = screen.lookup((42, 223)).cloned().unwrap();
+    screen.select_node(bottom);
+    screen.select_down();
+    assert_eq!(screen.drawn_at(screen.selected.unwrap()), Some(&(42, 224)));
+    assert!(screen.scroll_to_selected());
+    assert!(screen.view_y > 200);
+    assert!(screen.formatted.contains(&screen.selected.unwrap()));
+}
+
+// how long a frame takes to compose with the whole map in view against a
+// terminal's worth of it. run with
+// cargo test --release --lib bench_large_map -- --ignored --nocapture
+#[test]
+#[ignore]
+fn bench_large_map() {
+    let frame = |dims: Coords, view_y: u16| {
+        let (mut screen, _) = large_map(dims);
+        screen.view_y = view_y;
+        screen.draw();
+        let start = Instant::now();
+        for _ in 0..20 {
+            screen.draw();
+        }
+        start.elapsed() / 20
+    };
+    let whole = frame((80, 5200), 0);
+    let top = frame((80, 24), 0);
+    let middle = frame((80, 24), 2600);
+    println!("whole map in view: {:?}, top: {:?}, middle: {:?}", whole, top, middle);
+}
+
+#[test]
+fn test_perf() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (60, 16),
+        ..Screen::default()
+    };
+    let ids: Vec<NodeID> = (0..2).map(|_| screen.new_node()).collect();
+    screen.with_node_mut_no_meta(0, |n| n.children = ids.clone());
+    for (&id, &(content, y)) in ids.iter().zip(&[("from", 3), ("to", 8)]) {
+        screen.with_node_mut_no_meta(id, |n| {
+            n.content = content.to_owned();
+            n.rooted_coords = (2, y);
+        });
+    }
+    screen.arrows.push((ids[0], ids[1]));
+    screen.draw();
+    screen.draw();
+    screen.click_screen((3, 3));
+    let count = |screen: &Screen, op: &str| screen.perf.borrow().ops.get(op).map(|s| s.count);
+    assert_eq!(count(&screen, "draw"), Some(2));
+    assert_eq!(count(&screen, "hit test"), Some(1));
+    assert!(count(&screen, "path").unwrap() >= 2);
+    assert_eq!(count(&screen, "save"), None);
+
+    assert!(screen.run_command(command::parse("perf").unwrap()).unwrap());
+    assert!(screen.show_logs);
+}
+
+#[test]
+fn test_check_invariants() {
+    let mut screen = Screen {
+        is_test: true,
+        ..Screen::default()
+    };
+    let (a, b) = (screen.new_node(), screen.new_node());
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![a, b]);
+    for (&id, y) in [a, b].iter().zip(2..) {
+        screen.with_node_mut_no_meta(id, |n| {
+            n.content = "x".to_owned();
+            n.rooted_coords = (1, y);
+        });
+    }
+    assert_eq!(screen.check_invariants(), Ok(()));
+    let broken = |screen: &mut Screen, f: &dyn Fn(&mut Screen)| {
+        f(screen);
+        let err = screen.check_invariants().unwrap_err();
+        f(screen);
+        err
+    };
+
+    let swap_coords = |screen: &mut Screen| {
+        screen.with_node_mut_no_meta(b, |n| n.rooted_coords.1 ^= 1);
+    };
+    assert_eq!(broken(&mut screen, &swap_coords),
+               format!("anchors {} and {} are both at (1, 2)", a, b));
+    let flag = |screen: &mut Screen| {
+        screen.with_node_mut_no_meta(b, |n| n.selected = !n.selected);
+    };
+    screen.select_node(a);
+    assert_eq!(broken(&mut screen, &flag), format!("nodes [{}, {}] are all marked selected", a, b));
+    let collapse = |screen: &mut Screen| {
+        screen.with_node_mut_no_meta(a, |n| n.collapsed = !n.collapsed);
+    };
+    let c = screen.new_node();
+    screen.with_node_mut_no_meta(c, |n| n.parent_id = a);
+    screen.with_node_mut_no_meta(a, |n| n.children.push(c));
+    screen.select_node(c);
+    assert_eq!(broken(&mut screen, &collapse),
+               format!("selected node {} is hidden under node {}", c, a));
+
+    screen.arrows.push((a, 99));
+    assert_eq!(screen.check_invariants(),
+               Err(format!("arrow from {} to 99 has a node 99 that doesn't exist", a)));
+    screen.arrows.clear();
+    screen.with_node_mut_no_meta(b, |n| n.parent_id = c);
+    screen.with_node_mut_no_meta(a, |n| n.parent_id = b);
+    assert_eq!(screen.check_invariants(), Err(format!("node {} is its own ancestor", a)));
+}
+
+#[test]
+fn test_new_nodes_stay_in_sight() {
+    let mut screen = Screen {
+        is_test: true,
+        ..Screen::default()
+    };
+    // nothing is drawn on a screen this small, which used to stack anchors
+    screen.create_free_node();
+    screen.with_node_mut_no_meta(screen.selected.unwrap(), |n| n.content = "a".to_owned());
+    screen.create_free_node();
+    screen.with_node_mut_no_meta(screen.selected.unwrap(), |n| n.content = "b".to_owned());
+    assert_eq!(screen.check_invariants(), Ok(()));
+
+    let b = screen.selected.unwrap();
+    screen.toggle_collapsed();
+    screen.create_child();
+    assert!(!screen.with_node(b, |n| n.collapsed).unwrap());
+    screen.with_node_mut_no_meta(screen.selected.unwrap(), |n| n.content = "c".to_owned());
+    screen.with_node_mut_no_meta(b, |n| n.hide_stricken = true);
+    screen.toggle_stricken();
+    assert_eq!(screen.selected, Some(b));
+    assert_eq!(screen.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_hidden_nodes_stay_out_of_reach() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (40, 12),
+        ..Screen::default()
+    };
+    let ids: Vec<NodeID> = (0..4).map(|_| screen.new_node()).collect();
+    let (list, done, todo, later) = (ids[0], ids[1], ids[2], ids[3]);
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![list, later]);
+    screen.with_node_mut_no_meta(list, |n| {
+        n.children = vec![done, todo];
+        n.rooted_coords = (2, 2);
+    });
+    screen.with_node_mut_no_meta(later, |n| n.rooted_coords = (2, 6));
+    for (&id, &content) in ids.iter().zip(&["list", "done", "todo", "later"]) {
+        screen.with_node_mut_no_meta(id, |n| n.content = content.to_owned());
+    }
+    for &id in &[done, todo] {
+        screen.with_node_mut_no_meta(id, |n| n.parent_id = list);
+    }
+    screen.with_node_mut_no_meta(done, |n| n.stricken = true);
+    screen.draw();
+    assert_eq!(screen.drawn_at(done), Some(&(2, 3)));
+
+    // hiding it moves todo up into its row, even before the next draw
+    screen.click_screen((3, 2));
+    screen.release((3, 2));
+    screen.toggle_hide_stricken();
+    assert_eq!(screen.shown_children(list), vec![todo]);
+    assert_eq!(screen.hidden_under(done), Some(list));
+    screen.click_screen((9, 3));
+    screen.release((9, 3));
+    assert_eq!(screen.selected, Some(todo));
+    assert!(screen.find_visible_nodes(|_| true).iter().all(|&id| id != done));
+
+    // and the last branch is drawn to the fold line standing in for it
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    assert!(text.contains("├─ todo"), "{}", text);
+    assert!(text.contains("└─… 1 completed hidden"), "{}", text);
+
+    // arrows go from todo through the fold to the next anchor down, not to
+    // the hidden node
+    screen.select_down();
+    assert_eq!((screen.selected, screen.selected_fold), (None, Some(list)));
+    screen.select_down();
+    assert_eq!(screen.selected, Some(later));
+    screen.select_up();
+    screen.select_up();
+    assert_eq!(screen.selected, Some(todo));
+
+    // hiding completed anchors hides a selected one with them
+    screen.with_node_mut_no_meta(later, |n| n.stricken = true);
+    screen.select_node(later);
+    assert!(screen.run_command(command::parse("set hidestricken").unwrap()).unwrap());
+    assert_eq!(screen.selected, None);
+    assert!(!screen.is_shown(later));
+    screen.click_screen((3, 6));
+    assert_eq!(screen.selected, None);
+    assert_eq!(screen.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_fold_completed() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (40, 12),
+        ..Screen::default()
+    };
+    let ids: Vec<NodeID> = (0..5).map(|_| screen.new_node()).collect();
+    let (list, milk, eggs, bread, later) = (ids[0], ids[1], ids[2], ids[3], ids[4]);
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![list, later]);
+    screen.with_node_mut_no_meta(list, |n| {
+        n.children = vec![milk, eggs, bread];
+        n.rooted_coords = (2, 2);
+        n.hide_stricken = true;
+    });
+    screen.with_node_mut_no_meta(later, |n| n.rooted_coords = (2, 8));
+    for (&id, &content) in ids.iter().zip(&["list", "milk", "eggs", "bread", "later"]) {
+        screen.with_node_mut_no_meta(id, |n| n.content = content.to_owned());
+    }
+    for &id in &[milk, eggs, bread] {
+        screen.with_node_mut_no_meta(id, |n| n.parent_id = list);
+    }
+    for &id in &[milk, bread] {
+        screen.with_node_mut_no_meta(id, |n| n.stricken = true);
+    }
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    assert!(text.contains("└─… 2 completed hidden"), "{}", text);
+    assert!(!text.contains("milk"), "{}", text);
+    // it's part of the subtree's layout, so it's in the way of new anchors
+    assert_eq!(screen.drawable_subtree_dims(list).map(|(_, height)| height), Some(3));
+
+    // clicking it selects it, without selecting list or starting a drag
+    screen.click_screen((8, 4));
+    screen.release((8, 4));
+    assert_eq!((screen.selected, screen.selected_fold), (None, Some(list)));
+    screen.draw();
+    let invert = format!("{}", style::Invert);
+    assert!(screen.last_frame.as_ref().unwrap().style_at((6, 4)).unwrap().contains(&invert));
+
+    // enter shows what's behind it, dimmed, in its place among the rest
+    screen.handle_event(Event::Key(Key::Char('\n')));
+    assert_eq!(screen.shown_children(list), vec![milk, eggs, bread]);
+    assert_eq!(screen.drawn_at(milk), Some(&(2, 3)));
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    assert!(text.contains("└─… 2 completed shown"), "{}", text);
+    assert!(screen.last_frame.as_ref().unwrap().style_at((6, 3)).unwrap().contains(DIM));
+    assert_eq!(screen.selected_fold, Some(list));
+    assert_eq!(screen.nodes.len(), 6);
+
+    // the revealed ones can be clicked like any other node
+    screen.click_screen((6, 3));
+    screen.release((6, 3));
+    assert_eq!(screen.selected, Some(milk));
+
+    // and enter on the fold again hides them
+    screen.click_screen((8, 6));
+    screen.release((8, 6));
+    assert_eq!(screen.selected_fold, Some(list));
+    screen.handle_event(Event::Key(Key::Char('\n')));
+    assert_eq!(screen.shown_children(list), vec![eggs]);
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    assert!(text.contains("└─… 2 completed hidden"), "{}", text);
+    assert!(!text.contains("milk"), "{}", text);
+
+    // escape lets go of it without leaving
+    assert!(screen.handle_event(Event::Key(Key::Esc)));
+    assert_eq!((screen.selected, screen.selected_fold), (None, None));
+    assert_eq!(screen.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_substitute() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (80, 12),
+        ..Screen::default()
+    };
+    let ids: Vec<NodeID> = (0..4).map(|_| screen.new_node()).collect();
+    let (atlas, kickoff, review, notes) = (ids[0], ids[1], ids[2], ids[3]);
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![atlas, notes]);
+    screen.with_node_mut_no_meta(atlas, |n| {
+        n.children = vec![kickoff, review];
+        n.rooted_coords = (2, 2);
+    });
+    screen.with_node_mut_no_meta(notes, |n| n.rooted_coords = (2, 6));
+    let contents = ["Atlas", "Atlas kickoff, Atlas v2", "review #Atlas", "Atlas ideas"];
+    for (&id, &content) in ids.iter().zip(&contents) {
+        screen.with_node_mut_no_meta(id, |n| {
+            n.content = content.to_owned();
+            n.meta.mtime = 1;
+        });
+        screen.tag_db.reindex(id, content.to_owned());
+    }
+    for &id in &[kickoff, review] {
+        screen.with_node_mut_no_meta(id, |n| n.parent_id = atlas);
+    }
+    let substitute = |screen: &mut Screen, spec: &str| {
+        let command = command::parse(&format!("substitute {}", spec)).unwrap();
+        assert!(screen.run_command(command).unwrap());
+    };
+    let content = |screen: &Screen, id| screen.with_node(id, |n| n.content.clone()).unwrap();
+
+    // it asks first, saying how many nodes would change
+    substitute(&mut screen, "/Atlas/Aurora/");
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    assert!(text.contains("replace \"Atlas\" with \"Aurora\" in 4 nodes? (y/n)"), "{}", text);
+    assert!(screen.handle_event(Event::Key(Key::Char('n'))));
+    assert_eq!(content(&screen, kickoff), "Atlas kickoff, Atlas v2");
+
+    // in focus mode only the nodes under the focus are changed
+    screen.select_node(atlas);
+    screen.toggle_focus_mode();
+    substitute(&mut screen, "/Atlas/Aurora/");
+    assert!(screen.handle_event(Event::Key(Key::Char('y'))));
+    assert_eq!(content(&screen, atlas), "Aurora");
+    assert_eq!(content(&screen, kickoff), "Aurora kickoff, Aurora v2");
+    assert_eq!(content(&screen, notes), "Atlas ideas");
+    assert!(screen.with_node(review, |n| n.meta.mtime).unwrap() > 1);
+    assert_eq!(screen.with_node(notes, |n| n.meta.mtime), Some(1));
+    // tags are found under their new names
+    assert_eq!(screen.tag_db.tag_to_nodes("Aurora"), vec![review]);
+    assert!(screen.tag_db.tag_to_nodes("Atlas").is_empty());
+
+    // and a pattern found nowhere in view has nothing to ask about
+    substitute(&mut screen, "|ideas|plans|");
+    assert_eq!(screen.modal, None);
+    screen.leave_focus_mode();
+    substitute(&mut screen, "|ideas|plans|");
+    assert!(screen.handle_event(Event::Key(Key::Char('y'))));
+    assert_eq!(content(&screen, notes), "Atlas plans");
+}
+
+#[test]
+fn test_outline_numbers() {
+    let mut screen = Screen {
+        is_test: true,
+        dims: (80, 12),
+        ..Screen::default()
+    };
+    let ids: Vec<NodeID> = (0..4).map(|_| screen.new_node()).collect();
+    let (spec, intro, scope, goals) = (ids[0], ids[1], ids[2], ids[3]);
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![spec]);
+    screen.with_node_mut_no_meta(spec, |n| {
+        n.children = vec![intro, scope];
+        n.rooted_coords = (2, 2);
+    });
+    screen.with_node_mut_no_meta(scope, |n| n.children = vec![goals]);
+    for (&id, &content) in ids.iter().zip(&["spec", "intro", "scope", "goals"]) {
+        screen.with_node_mut_no_meta(id, |n| n.content = content.to_owned());
+    }
+    for &(id, parent_id) in &[(intro, spec), (scope, spec), (goals, scope)] {
+        screen.with_node_mut_no_meta(id, |n| n.parent_id = parent_id);
+    }
+    assert!(screen.run_command(command::parse("set numbers").unwrap()).unwrap());
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    let lines: Vec<&str> = text.lines().map(|l| l.trim_end()).collect();
+    assert!(lines[2].ends_with("1.1 intro"), "{}", text);
+    assert!(lines[4].ends_with("1.2.1 goals"), "{}", text);
+
+    // the number makes the node wider to click on
+    let end = lines[4].chars().count() as u16;
+    screen.click_screen((end, 5));
+    screen.release((end, 5));
+    assert_eq!(screen.selected, Some(goals));
+    let sep = screen.glyphs().path_separator;
+    let path = format!("1 spec {} 1.2 scope {} 1.2.1 goals", sep, sep);
+    assert!(screen.status_text().starts_with(&path), "{}", screen.status_text());
+
+    // and follows its siblings as they're moved about
+    screen.select_node(scope);
+    screen.raise_selected();
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    assert!(text.contains("1.1 scope") && text.contains("1.1.1 goals"), "{}", text);
+    assert!(text.contains("1.2 intro"), "{}", text);
+
+    assert!(screen.run_command(command::parse("set nonumbers").unwrap()).unwrap());
+    screen.draw();
+    let text = screen.last_frame.as_ref().unwrap().text(false);
+    assert!(!text.contains("1.1"), "{}", text);
+}
+
+#[test]
+fn test_size_fallback() {
+    use termsize::FixedSize;
+
+    struct NoTerminal;
+    impl SizeSource for NoTerminal {
+        fn size(&mut self) -> std::io::Result<Coords> {
+            Err(Error::new(ErrorKind::Other, "not a tty"))
+        }
+    }
+
+    let mut screen = Screen {
+        is_test: true,
+        size_source: Box::new(NoTerminal),
+        ..Screen::default()
+    };
+    let anchor = screen.new_node();
+    screen.with_node_mut_no_meta(0, |n| n.children = vec![anchor]);
+    screen.with_node_mut_no_meta(anchor, |n| {
+        n.content = "offline".to_owned();
+        n.rooted_coords = (2, 2);
+    });
+    screen.refresh_size();
+    screen.draw();
+    assert_eq!(screen.dims, (80, 24));
+    assert_eq!(screen.last_frame.as_ref().unwrap().dims, (80, 24));
+    assert!(screen.last_frame.as_ref().unwrap().text(false).contains("offline"));
+
+    // once the terminal has answered, what it said outlasts a failure
+    screen.size_source = Box::new(FixedSize((50, 10)));
+    screen.refresh_size();
+    screen.size_source = Box::new(NoTerminal);
+    screen.refresh_size();
+    screen.draw();
+    assert_eq!(screen.last_frame.as_ref().unwrap().dims, (50, 10));
+}
+
+#[test]
+fn test_replay_recording() {
+    use record;
+
+    let events = env::temp_dir().join(format!("void-replay-test-{}.events", unsafe { getpid() }));
+    let events = events.to_str().unwrap().to_owned();
+    let start = {
+        let mut screen = Screen::default();
+        let anchor = screen.new_node();
+        screen.with_node_mut_no_meta(0, |n| n.children = vec![anchor]);
+        screen.with_node_mut_no_meta(anchor, |n| {
+            n.content = "errands".to_owned();
+            n.rooted_coords = (3, 2);
+        });
+        serialization::serialize_screen(&screen)
+    };
+    let load = || {
+        let mut screen = serialization::deserialize_screen(start.clone()).unwrap();
+        screen.is_test = true;
+        screen
+    };
+    let key = |c| Input::Event(Event::Key(Key::Char(c)));
+    let mouse = |evt| Input::Event(Event::Mouse(evt));
+
+    let mut screen = load();
+    screen.recorder = Some(Recorder::create(&events).unwrap());
+    screen.resize((70, 20));
+    screen.draw();
+    let (x, y) = *screen.drawn_at(1).unwrap();
+    let batches = vec![
+        vec![mouse(MouseEvent::Press(MouseButton::Left, x + 1, y)),
+             mouse(MouseEvent::Release(x + 1, y))],
+        vec![key('\t'), key('m'), key('i'), key('l'), key('k')],
+        vec![Input::Paste("eggs\nbread".to_owned())],
+        vec![Input::Event(Event::Key(Key::Ctrl('a'))), Input::Tick],
+        vec![Input::Event(Event::Key(Key::Ctrl('n'))), key('c'), key('a'), key('r')],
+        vec![],
+        vec![mouse(MouseEvent::Press(MouseButton::Left, x + 1, y)),
+             mouse(MouseEvent::Hold(x + 20, y + 8)),
+             mouse(MouseEvent::Release(x + 20, y + 8))],
+        vec![Input::Event(Event::Key(Key::Esc))],
+    ];
+    for batch in batches {
+        assert!(screen.feed(batch));
+    }
+    let original = serialization::serialize_screen(&screen);
+    screen.recorder = None;
+
+    let entries = record::load(&events).unwrap();
+    assert_eq!(entries[0].1, Entry::Size((70, 20)));
+    assert_eq!(entries.iter().filter(|&&(_, ref e)| *e == Entry::Frame).count(), 7);
+
+    // played back a day later, it still happens at the times it was made at
+    let later = entries[0].0 + 24 * 60 * 60 * 1000;
+    let mut replayed = load();
+    let broken = clock::held_at(later, || replayed.replay_recording(&entries));
+    assert_eq!(broken, Vec::<String>::new());
+    assert_eq!(serialization::serialize_screen(&replayed), original);
+    assert_eq!(replayed.nodes.len(), 6);
+    remove_file(&events).unwrap();
 }
@@ -0,0 +1,22 @@
+use std::collections::HashMap;
+
+use termion::event::Event;
+
+use NodeID;
+
+// where the user left off in a work file, restored when it's opened again
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Session {
+    pub selected: Option<NodeID>,
+    pub drawing_root: NodeID,
+    pub view_y: u16,
+    pub show_logs: bool,
+    // recorded macros by register
+    pub macros: HashMap<char, Vec<Event>>,
+}
+
+// kept beside the work file instead of in it, so sharing the file doesn't
+// share how it was last viewed
+pub fn path(work_path: &str) -> String {
+    format!("{}.session", work_path)
+}
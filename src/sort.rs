@@ -0,0 +1,72 @@
+use std::cmp::Ordering;
+
+use Node;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortBy {
+    Content,
+    Created,
+    Due,
+    // unfinished first, otherwise keeping the current order
+    StrickenLast,
+}
+
+// nodes without a due date go after the dated ones, reversed or not
+pub fn compare(by: SortBy, reverse: bool, a: &Node, b: &Node) -> Ordering {
+    let ordering = match by {
+        SortBy::Content => a.content.to_lowercase().cmp(&b.content.to_lowercase()),
+        SortBy::Created => a.meta.ctime.cmp(&b.meta.ctime),
+        SortBy::Due => {
+            match (a.meta.due, b.meta.due) {
+                (Some(a_due), Some(b_due)) => a_due.cmp(&b_due),
+                (Some(_), None) => return Ordering::Less,
+                (None, Some(_)) => return Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+        }
+        SortBy::StrickenLast => a.stricken.cmp(&b.stricken),
+    };
+    if reverse { ordering.reverse() } else { ordering }
+}
+
+// stable, so ties keep their order
+pub fn sort(nodes: &mut [&Node], by: SortBy, reverse: bool) {
+    nodes.sort_by(|a, b| compare(by, reverse, a, b));
+}
+
+#[cfg(test)]
+fn sorted_ids(nodes: &[Node], by: SortBy, reverse: bool) -> Vec<u64> {
+    let mut refs: Vec<&Node> = nodes.iter().collect();
+    sort(&mut refs, by, reverse);
+    refs.into_iter().map(|n| n.id).collect()
+}
+
+#[test]
+fn test_sort() {
+    let node = |id, content: &str, ctime, due, stricken| {
+        let mut node = Node {
+            id,
+            content: content.to_owned(),
+            stricken,
+            ..Node::default()
+        };
+        node.meta.ctime = ctime;
+        node.meta.due = due;
+        node
+    };
+    let nodes = vec![
+        node(1, "dune", 30, None, true),
+        node(2, "Anathem", 10, Some(500), false),
+        node(3, "blindsight", 20, None, false),
+        node(4, "anathem", 10, Some(100), true),
+    ];
+
+    // case doesn't matter, and ties keep their order either way round
+    assert_eq!(sorted_ids(&nodes, SortBy::Content, false), vec![2, 4, 3, 1]);
+    assert_eq!(sorted_ids(&nodes, SortBy::Content, true), vec![1, 3, 2, 4]);
+    assert_eq!(sorted_ids(&nodes, SortBy::Created, false), vec![2, 4, 3, 1]);
+    assert_eq!(sorted_ids(&nodes, SortBy::Due, false), vec![4, 2, 1, 3]);
+    assert_eq!(sorted_ids(&nodes, SortBy::Due, true), vec![2, 4, 1, 3]);
+    assert_eq!(sorted_ids(&nodes, SortBy::StrickenLast, false), vec![2, 3, 1, 4]);
+    assert_eq!(sorted_ids(&nodes, SortBy::StrickenLast, true), vec![1, 4, 2, 3]);
+}
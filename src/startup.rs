@@ -0,0 +1,237 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use termion::{clear, cursor, style};
+use termion::event::Key;
+
+use command::expand_home;
+
+// no more are remembered than this
+const MAX_RECENT: usize = 10;
+
+// where void keeps its own files, like the list of recent ones
+pub fn config_dir() -> Option<PathBuf> {
+    match env::var("XDG_CONFIG_HOME") {
+        Ok(ref dir) if !dir.is_empty() => Some(Path::new(dir).join("void")),
+        _ => env::home_dir().map(|home| home.join(".config").join("void")),
+    }
+}
+
+// the work files opened last, newest first, kept in the config dir
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recent {
+    // None keeps the list only in memory
+    pub path: Option<PathBuf>,
+    pub files: Vec<String>,
+}
+
+impl Recent {
+    pub fn load(config_dir: &Path) -> Recent {
+        let path = config_dir.join("recent");
+        let files = fs::read_to_string(&path)
+            .map(|text| text.lines().filter(|l| !l.is_empty()).map(|l| l.to_owned()).collect())
+            .unwrap_or_default();
+        Recent {
+            path: Some(path),
+            files,
+        }
+    }
+
+    // moves file to the front, saving the list if it has a home
+    pub fn add(&mut self, file: &str) {
+        let file = absolute(file);
+        self.files.retain(|f| *f != file);
+        self.files.insert(0, file);
+        self.files.truncate(MAX_RECENT);
+        if let Some(ref path) = self.path {
+            let mut text = self.files.join("\n");
+            text.push('\n');
+            let written = path.parent().map_or(Ok(()), fs::create_dir_all)
+                .and_then(|()| fs::write(path, text));
+            if let Err(e) = written {
+                warn!("failed to save the recent files to {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    // the ones that are still there
+    pub fn existing(&self) -> Vec<String> {
+        self.files.iter().filter(|f| Path::new(f).exists()).cloned().collect()
+    }
+}
+
+// a path from the command line, key file or environment, as it will be
+// remembered
+fn absolute(file: &str) -> String {
+    let file = expand_home(file);
+    if Path::new(&file).is_absolute() {
+        return file;
+    }
+    match env::current_dir() {
+        Ok(dir) => dir.join(&file).to_string_lossy().into_owned(),
+        Err(_) => file,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Startup {
+    Open(String),
+    // more than one recent file, and nothing else to go on
+    Pick(Vec<String>),
+    // nothing was found, so the usual file is opened
+    Fallback,
+}
+
+// what to open when no file is named: the key file's `file:`, then
+// $VOID_FILE, then void.db in dir, whichever exists first. failing those,
+// the only recent file, or a choice of them.
+pub fn resolve(configured: Option<&str>, env_file: Option<&str>, dir: &Path, recent: &Recent)
+               -> Startup {
+    let local = dir.join("void.db").to_string_lossy().into_owned();
+    let defaults = configured.map(expand_home)
+        .into_iter()
+        .chain(env_file.filter(|f| !f.is_empty()).map(expand_home))
+        .chain(Some(local));
+    for file in defaults {
+        if Path::new(&file).exists() {
+            return Startup::Open(file);
+        }
+        debug!("no default work file at {}", file);
+    }
+    let mut existing = recent.existing();
+    match existing.len() {
+        0 => Startup::Fallback,
+        1 => Startup::Open(existing.remove(0)),
+        _ => Startup::Pick(existing),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Picked {
+    Waiting,
+    Chose(String),
+    Cancelled,
+}
+
+// a numbered list of recent files to start from
+#[derive(Debug, Clone)]
+pub struct Picker {
+    pub files: Vec<String>,
+    pub cursor: usize,
+}
+
+impl Picker {
+    pub fn new(files: Vec<String>) -> Picker {
+        Picker { files, cursor: 0 }
+    }
+
+    pub fn key(&mut self, key: Key) -> Picked {
+        match key {
+            Key::Up | Key::Char('k') => self.cursor = self.cursor.saturating_sub(1),
+            Key::Down | Key::Char('j') => {
+                self.cursor = (self.cursor + 1).min(self.files.len().saturating_sub(1))
+            }
+            Key::Char('\n') => return Picked::Chose(self.files[self.cursor].clone()),
+            Key::Char(c) if c.is_ascii_digit() => {
+                let n = c.to_digit(10).unwrap() as usize;
+                if n >= 1 && n <= self.files.len() {
+                    return Picked::Chose(self.files[n - 1].clone());
+                }
+            }
+            Key::Esc | Key::Char('q') | Key::Ctrl('c') => return Picked::Cancelled,
+            _ => {}
+        }
+        Picked::Waiting
+    }
+
+    pub fn draw(&self) -> String {
+        let mut out = format!("{}{}open which file? (enter opens, esc quits)",
+                              clear::All,
+                              cursor::Goto(1, 1));
+        for (i, file) in self.files.iter().enumerate() {
+            let (on, off) = if i == self.cursor {
+                (format!("{}", style::Invert), format!("{}", style::Reset))
+            } else {
+                (String::new(), String::new())
+            };
+            out.push_str(&format!("{}{}{:>2}. {}{}",
+                                  cursor::Goto(1, i as u16 + 3),
+                                  on,
+                                  i + 1,
+                                  file,
+                                  off));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+fn test_dir(name: &str) -> PathBuf {
+    use libc::getpid;
+
+    let dir = env::temp_dir().join(format!("void-{}-test-{}", name, unsafe { getpid() }));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_resolve() {
+    let dir = test_dir("startup");
+    let config = dir.join("config");
+    let file = |name: &str| {
+        let path = dir.join(name).to_string_lossy().into_owned();
+        fs::write(&path, "").unwrap();
+        path
+    };
+    let (configured, from_env) = (file("configured.db"), file("env.db"));
+    let (a, b) = (file("a.db"), file("b.db"));
+    let missing = dir.join("missing.db").to_string_lossy().into_owned();
+
+    let mut recent = Recent::load(&config);
+    assert_eq!(resolve(None, None, &dir, &recent), Startup::Fallback);
+    recent.add(&a);
+    assert_eq!(resolve(None, None, &dir, &recent), Startup::Open(a.clone()));
+    recent.add(&b);
+    recent.add(&missing);
+    // the list is kept in the config dir, and gone files aren't offered
+    let recent = Recent::load(&config);
+    assert_eq!(recent.files, vec![missing.clone(), b.clone(), a.clone()]);
+    assert_eq!(resolve(None, None, &dir, &recent), Startup::Pick(vec![b.clone(), a.clone()]));
+
+    let local = file("void.db");
+    assert_eq!(resolve(None, None, &dir, &recent), Startup::Open(local.clone()));
+    assert_eq!(resolve(None, Some(&from_env), &dir, &recent), Startup::Open(from_env.clone()));
+    assert_eq!(resolve(Some(&configured), Some(&from_env), &dir, &recent),
+               Startup::Open(configured));
+    assert_eq!(resolve(Some(&missing), Some(&from_env), &dir, &recent),
+               Startup::Open(from_env));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_recent() {
+    let dir = test_dir("recent");
+    let mut recent = Recent::load(&dir.join("void"));
+    for i in 0..12 {
+        recent.add(&format!("/maps/{}.db", i));
+    }
+    recent.add("/maps/3.db");
+    let recent = Recent::load(&dir.join("void"));
+    assert_eq!(recent.files.len(), MAX_RECENT);
+    assert_eq!(&recent.files[..3], &["/maps/3.db", "/maps/11.db", "/maps/10.db"]);
+    assert!(!recent.files.contains(&"/maps/1.db".to_owned()));
+
+    let mut picker = Picker::new(recent.files.clone());
+    assert_eq!(picker.key(Key::Up), Picked::Waiting);
+    assert_eq!(picker.key(Key::Down), Picked::Waiting);
+    assert_eq!(picker.key(Key::Char('\n')), Picked::Chose("/maps/11.db".to_owned()));
+    assert_eq!(picker.key(Key::Char('3')), Picked::Chose("/maps/10.db".to_owned()));
+    assert_eq!(picker.key(Key::Char('0')), Picked::Waiting);
+    assert_eq!(picker.key(Key::Esc), Picked::Cancelled);
+    assert!(picker.draw().contains(" 1. /maps/3.db"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,126 @@
+// what `:substitute /atlas/aurora/` replaces, and with what. the pattern
+// is plain text, matched exactly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Substitution {
+    pub pattern: String,
+    pub replacement: String,
+}
+
+impl Substitution {
+    // `/pattern/replacement/`, where the first character is the delimiter,
+    // like sed. a backslash keeps the delimiter or another backslash from
+    // meaning anything, and the last delimiter may be left off.
+    pub fn parse(spec: &str) -> Result<Substitution, String> {
+        let usage = || "usage: :substitute /pattern/replacement/".to_owned();
+        let mut chars = spec.chars();
+        let delimiter = match chars.next() {
+            Some(c) if !c.is_alphanumeric() && !c.is_whitespace() && c != '\\' => c,
+            _ => return Err(usage()),
+        };
+        let mut parts = vec![String::new()];
+        let mut escaped = false;
+        for c in chars {
+            if escaped {
+                if c != delimiter && c != '\\' {
+                    parts.last_mut().unwrap().push('\\');
+                }
+                parts.last_mut().unwrap().push(c);
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == delimiter {
+                parts.push(String::new());
+            } else {
+                parts.last_mut().unwrap().push(c);
+            }
+        }
+        if escaped {
+            parts.last_mut().unwrap().push('\\');
+        }
+        // nothing may follow the last delimiter
+        if parts.len() == 3 && parts[2].is_empty() {
+            parts.pop();
+        }
+        if parts.len() != 2 {
+            return Err(usage());
+        }
+        if parts[0].is_empty() {
+            return Err("nothing to replace".to_owned());
+        }
+        let replacement = parts.pop().unwrap();
+        let pattern = parts.pop().unwrap();
+        Ok(Substitution {
+            pattern,
+            replacement,
+        })
+    }
+
+    pub fn matches(&self, text: &str) -> bool {
+        text.contains(&*self.pattern)
+    }
+
+    // text with every match replaced, and how many there were, or None if
+    // there weren't any
+    pub fn apply(&self, text: &str) -> Option<(String, usize)> {
+        let count = text.matches(&*self.pattern).count();
+        if count == 0 {
+            None
+        } else {
+            Some((text.replace(&*self.pattern, &self.replacement), count))
+        }
+    }
+}
+
+#[test]
+fn test_parse_substitution() {
+    let sub = |pattern: &str, replacement: &str| {
+        Ok(Substitution {
+            pattern: pattern.to_owned(),
+            replacement: replacement.to_owned(),
+        })
+    };
+    assert_eq!(Substitution::parse("/Atlas/Aurora/"), sub("Atlas", "Aurora"));
+    assert_eq!(Substitution::parse("/Atlas/Aurora"), sub("Atlas", "Aurora"));
+    assert_eq!(Substitution::parse("/project atlas/"), sub("project atlas", ""));
+    assert_eq!(Substitution::parse("/a\\/b/a or b/"), sub("a/b", "a or b"));
+    assert_eq!(Substitution::parse("/and/\\/\\\\/"), sub("and", "/\\"));
+    assert_eq!(Substitution::parse("|a/b|c/d|"), sub("a/b", "c/d"));
+    // other escapes are left as they are
+    assert_eq!(Substitution::parse("/\\n/x/"), sub("\\n", "x"));
+    assert_eq!(Substitution::parse("/a/b\\"), sub("a", "b\\"));
+    assert_eq!(Substitution::parse("/ü/ue/"), sub("ü", "ue"));
+
+    assert_eq!(Substitution::parse("//x/"), Err("nothing to replace".to_owned()));
+    for spec in &["", "atlas/aurora/", "/a/b/c", "/a/b/c/", "/a", "\\a\\b\\"] {
+        assert!(Substitution::parse(spec).is_err(), "parsing {:?}", spec);
+    }
+}
+
+#[test]
+fn test_apply_substitution() {
+    let sub = |pattern: &str, replacement: &str| {
+        Substitution {
+            pattern: pattern.to_owned(),
+            replacement: replacement.to_owned(),
+        }
+    };
+    let atlas = sub("Atlas", "Aurora");
+    assert_eq!(atlas.apply("ship Atlas, then Atlas 2"),
+               Some(("ship Aurora, then Aurora 2".to_owned(), 2)));
+    assert_eq!(atlas.apply("atlas"), None);
+    assert!(!atlas.matches("ATLAS"));
+
+    // matches don't overlap, and what's put in isn't matched again
+    assert_eq!(sub("aa", "a").apply("aaaaa"), Some(("aaa".to_owned(), 2)));
+    assert_eq!(sub("a", "aa").apply("aba"), Some(("aabaa".to_owned(), 2)));
+    assert_eq!(sub("x", "").apply("x"), Some((String::new(), 1)));
+
+    // multi-byte text is matched by whole characters
+    assert_eq!(sub("café", "bar").apply("le café, un café"),
+               Some(("le bar, un bar".to_owned(), 2)));
+    assert_eq!(sub("日本", "東京").apply("日本語と日本"),
+               Some(("東京語と東京".to_owned(), 2)));
+    assert_eq!(sub("é", "e").apply("e\u{301}"), None);
+    assert_eq!(sub("🚀", "launch").apply("🚀🚀 #ship"),
+               Some(("launchlaunch #ship".to_owned(), 2)));
+}
@@ -0,0 +1,203 @@
+use std::io::{self, Error, ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::Duration;
+
+use Screen;
+use serialization;
+
+// how often a running void swaps its map with its peer's, in seconds
+pub const SYNC_SECS: u64 = 30;
+
+// a stuck peer is given up on after this many seconds
+const TIMEOUT_SECS: u64 = 10;
+
+// frames claiming to be bigger than this are garbage
+const MAX_FRAME: usize = 256 << 20;
+
+// nothing's taken in before the token is checked but a hello, which is
+// never anywhere near this big
+const MAX_HELLO: usize = 4 << 10;
+
+// the first frame a client sends, followed by the shared token
+const HELLO: &[u8] = b"void sync 1 ";
+
+// every frame is its length as four big-endian bytes, then that many bytes.
+// a client says hello and is told "ok" or "denied", then sends its saved
+// map and gets back the server's, merged with it.
+fn write_frame<W: Write>(stream: &mut W, data: &[u8]) -> io::Result<()> {
+    let len = data.len() as u32;
+    stream.write_all(&[(len >> 24) as u8, (len >> 16) as u8, (len >> 8) as u8, len as u8])?;
+    stream.write_all(data)?;
+    stream.flush()
+}
+
+fn read_frame<R: Read>(stream: &mut R, max: usize) -> io::Result<Vec<u8>> {
+    let mut len = [0; 4];
+    stream.read_exact(&mut len)?;
+    let len = len.iter().fold(0, |len, &b| len << 8 | b as usize);
+    if len > max {
+        return Err(Error::new(ErrorKind::InvalidData, format!("{} byte frame", len)));
+    }
+    let mut data = vec![0; len];
+    stream.read_exact(&mut data)?;
+    Ok(data)
+}
+
+fn set_timeouts(stream: &TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(TIMEOUT_SECS)))?;
+    stream.set_write_timeout(Some(Duration::from_secs(TIMEOUT_SECS)))
+}
+
+// send a saved map to the server at addr, returning the server's
+pub fn exchange(addr: &str, token: &str, data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut stream = TcpStream::connect(addr)?;
+    set_timeouts(&stream)?;
+    let mut hello = HELLO.to_vec();
+    hello.extend_from_slice(token.as_bytes());
+    write_frame(&mut stream, &hello)?;
+    match &*read_frame(&mut stream, MAX_HELLO)? {
+        b"ok" => {}
+        b"encrypted" => {
+            return Err(Error::new(ErrorKind::PermissionDenied, "the server's map is encrypted"))
+        }
+        _ => return Err(Error::new(ErrorKind::PermissionDenied, "token refused")),
+    }
+    write_frame(&mut stream, data)?;
+    read_frame(&mut stream, MAX_FRAME)
+}
+
+// compares every byte whatever the first difference, so how long a wrong
+// token takes to refuse says nothing about how much of it was right
+fn same_token(given: &[u8], token: &[u8]) -> bool {
+    given.len() == token.len() &&
+    given.iter().zip(token).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+// the end of a sync that others connect to, like `void serve`
+pub struct Server {
+    listener: TcpListener,
+    token: String,
+}
+
+impl Server {
+    pub fn bind(addr: &str, token: &str) -> io::Result<Server> {
+        Ok(Server {
+            listener: TcpListener::bind(addr)?,
+            token: token.to_owned(),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    // wait for the next client and merge its map into screen, sending back
+    // the result. returns how many bytes were received and sent. maps go
+    // over the wire in the clear, so an encrypted one is never sent.
+    pub fn answer(&self, screen: &mut Screen) -> io::Result<(usize, usize)> {
+        let (mut stream, from) = self.listener.accept()?;
+        set_timeouts(&stream)?;
+        let hello = read_frame(&mut stream, MAX_HELLO)?;
+        if !hello.starts_with(HELLO) || !same_token(&hello[HELLO.len()..], self.token.as_bytes()) {
+            write_frame(&mut stream, b"denied")?;
+            let msg = format!("{} sent the wrong token", from);
+            return Err(Error::new(ErrorKind::PermissionDenied, msg));
+        }
+        if screen.key.is_some() {
+            write_frame(&mut stream, b"encrypted")?;
+            let msg = format!("not sending an encrypted map to {}", from);
+            return Err(Error::new(ErrorKind::PermissionDenied, msg));
+        }
+        write_frame(&mut stream, b"ok")?;
+
+        let theirs = read_frame(&mut stream, MAX_FRAME)?;
+        let received = theirs.len();
+        let other = serialization::deserialize_screen(theirs)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, format!("{:?}", e)))?;
+        screen.merge(&other);
+        let ours = serialization::serialize_screen(screen);
+        write_frame(&mut stream, &ours)?;
+        Ok((received, ours.len()))
+    }
+}
+
+// a server that a running void swaps its map with every SYNC_SECS, in the
+// background so that a slow network doesn't hold up drawing
+pub struct Peer {
+    pub addr: String,
+    token: String,
+    // when the next swap is due
+    next_at: u64,
+    // bytes sent in the swap under way, and where its result arrives
+    pending: Option<(usize, Receiver<io::Result<Vec<u8>>>)>,
+    pub last_success: Option<u64>,
+}
+
+impl Peer {
+    pub fn new(addr: &str, token: &str) -> Peer {
+        Peer {
+            addr: addr.to_owned(),
+            token: token.to_owned(),
+            next_at: 0,
+            pending: None,
+            last_success: None,
+        }
+    }
+
+    pub fn is_due(&self, now: u64) -> bool {
+        self.pending.is_none() && now >= self.next_at
+    }
+
+    pub fn start(&mut self, now: u64, data: Vec<u8>) {
+        self.next_at = now + SYNC_SECS;
+        let (tx, rx) = mpsc::channel();
+        let (addr, token) = (self.addr.clone(), self.token.clone());
+        self.pending = Some((data.len(), rx));
+        thread::spawn(move || {
+            let _ = tx.send(exchange(&addr, &token, &data));
+        });
+    }
+
+    // the bytes sent and the server's map, once a swap has finished
+    pub fn finished(&mut self) -> Option<(usize, io::Result<Vec<u8>>)> {
+        let result = match self.pending {
+            Some((_, ref rx)) => {
+                match rx.try_recv() {
+                    Ok(result) => result,
+                    Err(TryRecvError::Empty) => return None,
+                    Err(TryRecvError::Disconnected) => {
+                        Err(Error::new(ErrorKind::ConnectionAborted, "sync thread died"))
+                    }
+                }
+            }
+            None => return None,
+        };
+        self.pending.take().map(|(sent, _)| (sent, result))
+    }
+}
+
+#[test]
+fn test_frames() {
+    let mut buf = vec![];
+    write_frame(&mut buf, b"hello").unwrap();
+    write_frame(&mut buf, b"").unwrap();
+    assert_eq!(&buf[..4], &[0, 0, 0, 5]);
+    let mut reader = &buf[..];
+    assert_eq!(read_frame(&mut reader, MAX_FRAME).unwrap(), b"hello");
+    assert_eq!(read_frame(&mut reader, MAX_FRAME).unwrap(), b"");
+    assert!(read_frame(&mut reader, MAX_FRAME).is_err());
+
+    let mut huge = &[0xff, 0xff, 0xff, 0xff][..];
+    assert_eq!(read_frame(&mut huge, MAX_FRAME).unwrap_err().kind(), ErrorKind::InvalidData);
+
+    // a hello is refused by its length alone, before anything is allocated
+    let mut big_hello = &[0, 1, 0, 0][..];
+    assert_eq!(read_frame(&mut big_hello, MAX_HELLO).unwrap_err().kind(),
+               ErrorKind::InvalidData);
+
+    assert!(same_token(b"hunter2", b"hunter2"));
+    assert!(!same_token(b"hunter3", b"hunter2"));
+    assert!(!same_token(b"hunter", b"hunter2"));
+}
@@ -65,8 +65,16 @@ impl TagDB {
     pub fn remove(&mut self, node: NodeID) {
         if let Some(tags_to_clean) = self.node_to_tags.remove(&node) {
             for tag in &tags_to_clean {
-                if let Some(mut nodes) = self.tag_to_nodes.get_mut(tag) {
-                    nodes.remove(&node);
+                let emptied = match self.tag_to_nodes.get_mut(tag) {
+                    Some(nodes) => {
+                        nodes.remove(&node);
+                        nodes.is_empty()
+                    }
+                    None => false,
+                };
+                // so that tags nobody uses anymore aren't offered
+                if emptied {
+                    self.tag_to_nodes.remove(tag);
                 }
             }
         }
@@ -80,6 +88,28 @@ impl TagDB {
         res.sort();
         res
     }
+
+    // tags starting with prefix, the most used first, not counting where
+    // they're used in node except, which is usually the one being typed in
+    pub fn complete(&self, prefix: &str, except: NodeID) -> Vec<String> {
+        let mut res: Vec<(usize, &String)> = self.tag_to_nodes
+            .iter()
+            .filter(|&(tag, _)| tag.starts_with(prefix))
+            .map(|(tag, nodes)| (nodes.len() - nodes.contains(&except) as usize, tag))
+            .filter(|&(uses, _)| uses > 0)
+            .collect();
+        res.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(b.1)));
+        res.into_iter().map(|(_, tag)| tag.clone()).collect()
+    }
+
+    pub fn node_tags(&self, node: NodeID) -> Vec<String> {
+        let mut res: Vec<String> = self.node_to_tags
+            .get(&node)
+            .map(|set| set.clone().into_iter().collect())
+            .unwrap_or_else(|| vec![]);
+        res.sort();
+        res
+    }
 }
 
 #[test]
@@ -95,3 +125,18 @@ fn test_basic_func() {
     assert_eq!(tdb.tag_to_nodes("yes=ok"), vec![3]);
     assert_eq!(tdb.tag_to_nodes("$"), vec![4]);
 }
+
+#[test]
+fn test_complete() {
+    let mut tdb = TagDB::default();
+    tdb.reindex(1, "#errand #email".to_owned());
+    tdb.reindex(2, "#errand #work".to_owned());
+    tdb.reindex(3, "#e".to_owned());
+    assert_eq!(tdb.complete("e", 3), vec!["errand", "email"]);
+    assert_eq!(tdb.complete("", 1), vec!["e", "errand", "work"]);
+
+    // tags go away with the last node that had them
+    tdb.reindex(2, "nothing".to_owned());
+    tdb.remove(3);
+    assert_eq!(tdb.complete("", 0), vec!["email", "errand"]);
+}
@@ -1,3 +1,8 @@
+use rand::Rng;
+use time::{self, Tm};
+
+use {Node, NodeID, Screen};
+
 // OODA
 
 // DRRP
@@ -5,3 +10,444 @@
 // research
 // plan
 // execute
+
+// every unstricken leaf below the root, soonest due first, then oldest.
+// children of stricken nodes count as done.
+pub fn open_leaves(screen: &Screen) -> Vec<NodeID> {
+    let mut leaves = vec![];
+    let mut to_explore = screen.with_node(0, |n| n.children.clone()).unwrap_or_default();
+    while let Some(node_id) = to_explore.pop() {
+        if let Some(node) = screen.nodes.get(&node_id) {
+            if node.stricken {
+                continue;
+            } else if node.children.is_empty() {
+                if !node.content.is_empty() {
+                    leaves.push(node_id);
+                }
+            } else {
+                to_explore.extend(node.children.iter().cloned());
+            }
+        }
+    }
+
+    leaves.sort_by_key(|&id| soonest_then_oldest(screen, id));
+    leaves
+}
+
+fn soonest_then_oldest(screen: &Screen, id: NodeID) -> (bool, Option<u64>, u64, NodeID) {
+    let meta = &screen.nodes[&id].meta;
+    // nodes without a due date go last
+    (meta.due.is_none(), meta.due, meta.ctime, id)
+}
+
+// up to count unstricken nodes that have gone untouched longest, least
+// recently changed first. children of stricken nodes count as done.
+pub fn stale(screen: &Screen, count: usize) -> Vec<NodeID> {
+    let mut open = vec![];
+    let mut to_explore = screen.with_node(0, |n| n.children.clone()).unwrap_or_default();
+    while let Some(node_id) = to_explore.pop() {
+        if let Some(node) = screen.nodes.get(&node_id) {
+            if node.stricken {
+                continue;
+            }
+            if !node.content.is_empty() {
+                open.push((node.meta.touched(), node_id));
+            }
+            to_explore.extend(node.children.iter().cloned());
+        }
+    }
+    open.sort();
+    open.into_iter().take(count).map(|(_, id)| id).collect()
+}
+
+// the nodes on each column of the board, which are the ones tagged with
+// that column's tag, in the same order as the queue
+pub fn board(screen: &Screen, columns: &[String]) -> Vec<Vec<NodeID>> {
+    let mut board = vec![vec![]; columns.len()];
+    for node in screen.nodes.values().filter(|n| n.id != 0) {
+        if let Some(column) = column_of(node, columns) {
+            board[column].push(node.id);
+        }
+    }
+    for cards in &mut board {
+        cards.sort_by_key(|&id| soonest_then_oldest(screen, id));
+    }
+    board
+}
+
+// the column a node goes in. one tagged for two columns goes in the later.
+pub fn column_of(node: &Node, columns: &[String]) -> Option<usize> {
+    columns.iter().rposition(|column| has_tag(node, column))
+}
+
+fn has_tag(node: &Node, tag: &str) -> bool {
+    node.meta.tags.contains_key(tag) ||
+    node.content.split_whitespace().any(|word| word.strip_prefix('#') == Some(tag))
+}
+
+// swaps whichever column tags a node has for the one of column `to`,
+// keeping it where the old one was written
+pub fn move_card(node: &mut Node, columns: &[String], to: usize) {
+    let is_column = |word: &str| {
+        word.strip_prefix('#').map(|tag| columns.iter().any(|c| c == tag)).unwrap_or(false)
+    };
+    let tag = format!("#{}", columns[to]);
+    let mut placed = false;
+    let words: Vec<&str> = node.content
+        .split(' ')
+        .filter_map(|word| if !is_column(word) {
+            Some(word)
+        } else if !placed {
+            placed = true;
+            Some(&*tag)
+        } else {
+            None
+        })
+        .collect();
+    node.content = words.join(" ");
+    for column in columns {
+        node.meta.tags.remove(column);
+    }
+    if !placed {
+        node.meta.tags.insert(columns[to].clone(), String::new());
+    }
+}
+
+// where a due date falls in the agenda, counted in calendar days from
+// today, so that days of 23 or 25 hours around DST changes don't matter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Agenda {
+    Overdue,
+    Today,
+    Tomorrow,
+    // the rest of the week, which ends on Sunday
+    ThisWeek,
+    Later,
+}
+
+impl Agenda {
+    pub fn label(&self) -> &'static str {
+        match *self {
+            Agenda::Overdue => "Overdue",
+            Agenda::Today => "Today",
+            Agenda::Tomorrow => "Tomorrow",
+            Agenda::ThisWeek => "This week",
+            Agenda::Later => "Later",
+        }
+    }
+
+    // both in local time. something due earlier today is still today's.
+    pub fn of(now: &Tm, due: &Tm) -> Agenda {
+        let today = day_number(now);
+        let day = day_number(due);
+        // the day after Sunday, as day 0 was a Thursday
+        let week_end = today - (today + 3).rem_euclid(7) + 7;
+        match day - today {
+            n if n < 0 => Agenda::Overdue,
+            0 => Agenda::Today,
+            1 => Agenda::Tomorrow,
+            _ if day < week_end => Agenda::ThisWeek,
+            _ => Agenda::Later,
+        }
+    }
+}
+
+// days since the first of January 1970 of the date in tm, whatever its
+// offset from UTC
+fn day_number(tm: &Tm) -> i64 {
+    let (year, month, day) = (tm.tm_year as i64 + 1900, tm.tm_mon as i64 + 1, tm.tm_mday as i64);
+    // years start in March here, so that leap days come last
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// every unstricken node with a due date, grouped by when it's due and
+// soonest first within each group, leaving out empty groups
+pub fn agenda(screen: &Screen, now: u64) -> Vec<(Agenda, Vec<NodeID>)> {
+    let local = |secs: u64| time::at(time::Timespec::new(secs as i64, 0));
+    let today = local(now);
+    let mut due: Vec<(Agenda, u64, u64, NodeID)> = screen.nodes
+        .values()
+        .filter(|n| n.id != 0 && !n.stricken)
+        .filter_map(|n| {
+            n.meta.due.map(|due| (Agenda::of(&today, &local(due)), due, n.meta.ctime, n.id))
+        })
+        .collect();
+    due.sort();
+    let mut groups: Vec<(Agenda, Vec<NodeID>)> = vec![];
+    for (group, _, _, id) in due {
+        match groups.last_mut() {
+            Some(last) if last.0 == group => last.1.push(id),
+            _ => groups.push((group, vec![id])),
+        }
+    }
+    groups
+}
+
+// the unfinished nodes whose due time passed since the one last notified
+// about, soonest first. a node due again later stays quiet until then.
+pub fn became_due(screen: &Screen, now: u64) -> Vec<NodeID> {
+    let mut due: Vec<(u64, NodeID)> = screen.nodes
+        .values()
+        .filter(|n| n.meta.due_unnotified(now))
+        .map(|n| (n.meta.due.unwrap(), n.id))
+        .collect();
+    due.sort();
+    due.into_iter().map(|(_, id)| id).collect()
+}
+
+// how many of the latest picks a re-roll avoids
+pub const RECENT_PICKS: usize = 3;
+
+// a random open leaf, optionally one tagged (or below a node tagged)
+// with `tag`. recent picks are avoided unless nothing else is left.
+pub fn pick_next<R: Rng>(
+    screen: &Screen,
+    tag: Option<&str>,
+    recent: &[NodeID],
+    rng: &mut R,
+) -> Option<NodeID> {
+    let tagged = tag.map(|tag| screen.tag_db.tag_to_nodes(tag));
+    let candidates: Vec<NodeID> = open_leaves(screen)
+        .into_iter()
+        .filter(|&id| match tagged {
+            Some(ref tagged) => lineage_contains(screen, id, tagged),
+            None => true,
+        })
+        .collect();
+    let fresh: Vec<NodeID> = candidates
+        .iter()
+        .cloned()
+        .filter(|id| !recent.contains(id))
+        .collect();
+    let pool = if fresh.is_empty() { candidates } else { fresh };
+    if pool.is_empty() {
+        None
+    } else {
+        Some(pool[rng.gen_range(0, pool.len())])
+    }
+}
+
+fn lineage_contains(screen: &Screen, node_id: NodeID, set: &[NodeID]) -> bool {
+    let mut cursor = node_id;
+    loop {
+        if set.contains(&cursor) {
+            return true;
+        }
+        match screen.nodes.get(&cursor) {
+            Some(node) if cursor != 0 => cursor = node.parent_id,
+            _ => return false,
+        }
+    }
+}
+
+#[cfg(test)]
+fn fixture(edges: &[(NodeID, NodeID)]) -> Screen {
+    use Node;
+
+    let mut screen = Screen::default();
+    for &(parent_id, id) in edges {
+        let mut node = Node {
+            id,
+            parent_id,
+            content: format!("node {}", id),
+            ..Node::default()
+        };
+        node.meta.ctime = 1000 - id;
+        screen.nodes.insert(id, node);
+        screen.nodes.get_mut(&parent_id).unwrap().children.push(id);
+    }
+    screen
+}
+
+#[test]
+fn test_open_leaves_order() {
+    let mut screen = fixture(&[(0, 1), (1, 2), (1, 3), (0, 4), (4, 5)]);
+    // due dates come first, soonest first
+    screen.nodes.get_mut(&5).unwrap().meta.due = Some(10);
+    screen.nodes.get_mut(&2).unwrap().meta.due = Some(20);
+    assert_eq!(open_leaves(&screen), vec![5, 2, 3]);
+
+    // then the oldest, and higher ids are older in the fixture
+    screen.nodes.get_mut(&5).unwrap().meta.due = None;
+    screen.nodes.get_mut(&2).unwrap().meta.due = None;
+    assert_eq!(open_leaves(&screen), vec![5, 3, 2]);
+}
+
+#[test]
+fn test_open_leaves_skips_done() {
+    let mut screen = fixture(&[(0, 1), (1, 2), (1, 3), (0, 4), (4, 5), (0, 6)]);
+    screen.nodes.get_mut(&3).unwrap().stricken = true;
+    // a stricken parent finishes its whole subtree
+    screen.nodes.get_mut(&4).unwrap().stricken = true;
+    // empty nodes aren't tasks
+    screen.nodes.get_mut(&6).unwrap().content.clear();
+    assert_eq!(open_leaves(&screen), vec![2]);
+
+    // a parent whose children are all stricken is not a leaf
+    screen.nodes.get_mut(&2).unwrap().stricken = true;
+    assert_eq!(open_leaves(&screen), Vec::<NodeID>::new());
+}
+
+#[test]
+fn test_stale() {
+    let mut screen = fixture(&[(0, 1), (1, 2), (1, 3), (0, 4), (4, 5), (0, 6)]);
+    for &(id, mtime) in &[(1, 500), (2, 100), (3, 300), (4, 200), (5, 50), (6, 0)] {
+        screen.nodes.get_mut(&id).unwrap().meta.mtime = mtime;
+    }
+    // without an mtime it's as old as it is, and ctime is 1000 - id
+    assert_eq!(stale(&screen, 20), vec![5, 2, 4, 3, 1, 6]);
+    assert_eq!(stale(&screen, 2), vec![5, 2]);
+
+    // done nodes, and everything under them, aren't nagged about, and
+    // neither are empty ones
+    screen.nodes.get_mut(&4).unwrap().stricken = true;
+    screen.nodes.get_mut(&1).unwrap().content.clear();
+    assert_eq!(stale(&screen, 20), vec![2, 3, 6]);
+}
+
+#[test]
+fn test_board() {
+    let columns: Vec<String> = vec!["todo".to_owned(), "doing".to_owned(), "done".to_owned()];
+    let mut screen = fixture(&[(0, 1), (1, 2), (1, 3), (0, 4), (4, 5), (0, 6)]);
+    let tagged = [(2, "a #todo"), (3, "b #doing #todo"), (5, "c #todos"), (6, "d #done")];
+    for &(id, content) in &tagged {
+        screen.nodes.get_mut(&id).unwrap().content = content.to_owned();
+    }
+    // tags kept in the meta count too
+    screen.nodes.get_mut(&4).unwrap().meta.tags.insert("todo".to_owned(), String::new());
+    screen.nodes.get_mut(&4).unwrap().meta.due = Some(10);
+    assert_eq!(board(&screen, &columns), vec![vec![4, 2], vec![3], vec![6]]);
+
+    // the tag is swapped where it was written
+    let mut node = screen.nodes[&3].clone();
+    move_card(&mut node, &columns, 2);
+    assert_eq!(node.content, "b #done");
+    assert_eq!(column_of(&node, &columns), Some(2));
+    let mut node = screen.nodes[&2].clone();
+    node.content = "#todo  first,  then more".to_owned();
+    move_card(&mut node, &columns, 1);
+    assert_eq!(node.content, "#doing  first,  then more");
+    let mut node = screen.nodes[&4].clone();
+    move_card(&mut node, &columns, 1);
+    assert_eq!(node.content, "node 4");
+    assert!(node.meta.tags.contains_key("doing") && !node.meta.tags.contains_key("todo"));
+    assert_eq!(column_of(&node, &columns), Some(1));
+}
+
+#[test]
+fn test_agenda_groups() {
+    // local times, as a UTC offset in hours would give them
+    let tm = |date: &str, offset: i32| {
+        let mut tm = time::strptime(date, "%Y-%m-%d %H:%M").unwrap();
+        tm.tm_utcoff = offset * 60 * 60;
+        tm
+    };
+    // a Wednesday
+    let now = tm("2026-10-14 13:00", 2);
+    let group = |due: &str| Agenda::of(&now, &tm(due, 2));
+    assert_eq!(group("2026-10-13 23:59"), Agenda::Overdue);
+    assert_eq!(group("2025-12-31 12:00"), Agenda::Overdue);
+    assert_eq!(group("2026-10-14 00:00"), Agenda::Today);
+    assert_eq!(group("2026-10-14 09:00"), Agenda::Today);
+    assert_eq!(group("2026-10-14 23:59"), Agenda::Today);
+    assert_eq!(group("2026-10-15 00:00"), Agenda::Tomorrow);
+    assert_eq!(group("2026-10-15 23:59"), Agenda::Tomorrow);
+    assert_eq!(group("2026-10-16 00:00"), Agenda::ThisWeek);
+    assert_eq!(group("2026-10-18 23:59"), Agenda::ThisWeek);
+    assert_eq!(group("2026-10-19 00:00"), Agenda::Later);
+    assert_eq!(group("2027-10-14 13:00"), Agenda::Later);
+
+    // on Saturday tomorrow ends the week, and on Sunday it's already next
+    // week
+    let saturday = tm("2026-10-17 08:00", 2);
+    assert_eq!(Agenda::of(&saturday, &tm("2026-10-18 20:00", 2)), Agenda::Tomorrow);
+    assert_eq!(Agenda::of(&saturday, &tm("2026-10-19 20:00", 2)), Agenda::Later);
+    let sunday = tm("2026-10-18 08:00", 2);
+    assert_eq!(Agenda::of(&sunday, &tm("2026-10-19 20:00", 2)), Agenda::Tomorrow);
+    assert_eq!(Agenda::of(&sunday, &tm("2026-10-20 20:00", 2)), Agenda::Later);
+
+    // the day clocks go back has 25 hours, so this is more than a day away
+    // but still tomorrow
+    let before = tm("2026-10-24 23:30", 2);
+    assert_eq!(Agenda::of(&before, &tm("2026-10-25 23:30", 1)), Agenda::Tomorrow);
+    // and the day they go forward has 23, so this is less than two days
+    // away but on Monday
+    let before = tm("2026-03-28 00:30", 1);
+    assert_eq!(Agenda::of(&before, &tm("2026-03-29 23:30", 2)), Agenda::Tomorrow);
+    assert_eq!(Agenda::of(&before, &tm("2026-03-30 00:10", 2)), Agenda::Later);
+    // leap days and the turn of the year
+    let new_year = tm("2027-12-31 22:00", 0);
+    assert_eq!(Agenda::of(&new_year, &tm("2028-01-01 01:00", 0)), Agenda::Tomorrow);
+    let leap = tm("2028-02-28 12:00", 0);
+    assert_eq!(Agenda::of(&leap, &tm("2028-02-29 12:00", 0)), Agenda::Tomorrow);
+    assert_eq!(Agenda::of(&leap, &tm("2028-03-01 12:00", 0)), Agenda::ThisWeek);
+    assert_eq!(day_number(&tm("1970-01-01 00:00", 0)), 0);
+    assert_eq!(day_number(&tm("1969-12-31 00:00", 0)), -1);
+}
+
+#[test]
+fn test_became_due() {
+    let mut screen = fixture(&[(0, 1), (0, 2), (0, 3), (0, 4), (0, 5), (0, 6)]);
+    let mut set = |id, due, notified| {
+        let meta = &mut screen.nodes.get_mut(&id).unwrap().meta;
+        meta.due = due;
+        meta.notified = notified;
+    };
+    set(1, Some(500), None);
+    set(2, Some(300), None);
+    // already told about
+    set(3, Some(400), Some(400));
+    // due again after now, having been told about the last time
+    set(4, Some(2000), Some(400));
+    // not due yet, or never
+    set(5, Some(1001), None);
+    set(6, None, None);
+    assert_eq!(became_due(&screen, 1000), vec![2, 1]);
+    assert_eq!(became_due(&screen, 2000), vec![2, 1, 5, 4]);
+
+    // struck before it came due
+    screen.nodes.get_mut(&1).unwrap().meta.finish_time = Some(450);
+    screen.nodes.get_mut(&1).unwrap().stricken = true;
+    assert_eq!(became_due(&screen, 1000), vec![2]);
+    // moved on to a later time that has passed too
+    screen.nodes.get_mut(&3).unwrap().meta.due = Some(900);
+    assert_eq!(became_due(&screen, 1000), vec![2, 3]);
+}
+
+#[test]
+fn test_pick_next() {
+    use rand::{SeedableRng, XorShiftRng};
+
+    let mut screen = fixture(&[(0, 1), (1, 2), (1, 3), (0, 4), (4, 5), (4, 6), (0, 7)]);
+    screen.nodes.get_mut(&1).unwrap().content = "work #job".to_owned();
+    screen.tag_db.reindex(1, "work #job".to_owned());
+    screen.nodes.get_mut(&6).unwrap().content = "call #job".to_owned();
+    screen.tag_db.reindex(6, "call #job".to_owned());
+
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    for _ in 0..50 {
+        let pick = pick_next(&screen, Some("job"), &[], &mut rng).unwrap();
+        assert!([2, 3, 6].contains(&pick));
+
+        // re-rolls avoid recent picks while there is anything else
+        let pick = pick_next(&screen, Some("job"), &[2, 3], &mut rng).unwrap();
+        assert_eq!(pick, 6);
+        let pick = pick_next(&screen, Some("job"), &[2, 3, 6], &mut rng).unwrap();
+        assert!([2, 3, 6].contains(&pick));
+    }
+    assert_eq!(pick_next(&screen, Some("nope"), &[], &mut rng), None);
+
+    // without a tag every open leaf is fair game
+    let mut seen = vec![];
+    for _ in 0..200 {
+        seen.push(pick_next(&screen, None, &[], &mut rng).unwrap());
+    }
+    seen.sort();
+    seen.dedup();
+    assert_eq!(seen, vec![2, 3, 5, 6, 7]);
+}
@@ -0,0 +1,48 @@
+use std::io;
+
+use termion::terminal_size;
+
+use Coords;
+
+// where the screen learns how big it is. asking can fail, as when stdout is
+// redirected or a multiplexer is detaching.
+pub trait SizeSource {
+    fn size(&mut self) -> io::Result<Coords>;
+}
+
+pub struct TerminalSize;
+
+impl SizeSource for TerminalSize {
+    fn size(&mut self) -> io::Result<Coords> {
+        terminal_size()
+    }
+}
+
+// the same size every time, for running without a terminal
+pub struct FixedSize(pub Coords);
+
+impl SizeSource for FixedSize {
+    fn size(&mut self) -> io::Result<Coords> {
+        Ok(self.0)
+    }
+}
+
+// `fallback_size: 100x30` in the key file
+pub fn parse(text: &str) -> Option<Coords> {
+    let mut parts = text.trim().splitn(2, 'x');
+    let width = parts.next()?.trim().parse().ok()?;
+    let height = parts.next()?.trim().parse().ok()?;
+    if width == 0 || height == 0 {
+        return None;
+    }
+    Some((width, height))
+}
+
+#[test]
+fn test_parse_size() {
+    assert_eq!(parse("80x24"), Some((80, 24)));
+    assert_eq!(parse(" 100 x 30 "), Some((100, 30)));
+    assert_eq!(parse("80"), None);
+    assert_eq!(parse("0x24"), None);
+    assert_eq!(parse("wide x tall"), None);
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use {Coords, Node, NodeID};
+use clock;
+
+// how many deleted subtrees are kept around for restoring
+const CAPACITY: usize = 20;
+
+// deletions older than this are dropped for good
+const EXPIRY_S: u64 = 60 * 60 * 24 * 30;
+
+#[derive(Debug, Clone)]
+pub struct TrashEntry {
+    pub root: NodeID,
+    pub parent_id: NodeID,
+    // where the subtree was drawn when it was deleted
+    pub coords: Coords,
+    pub deleted_at: u64,
+    pub nodes: HashMap<NodeID, Node>,
+    pub arrows: Vec<(NodeID, NodeID)>,
+}
+
+impl TrashEntry {
+    pub fn new(root: NodeID, parent_id: NodeID, coords: Coords) -> TrashEntry {
+        TrashEntry {
+            root,
+            parent_id,
+            coords,
+            deleted_at: clock::now(),
+            nodes: HashMap::new(),
+            arrows: vec![],
+        }
+    }
+
+    pub fn content(&self) -> String {
+        self.nodes
+            .get(&self.root)
+            .map(|n| n.content.clone())
+            .unwrap_or_default()
+    }
+}
+
+// deleted subtrees, oldest first
+#[derive(Debug, Clone, Default)]
+pub struct Trash {
+    entries: Vec<TrashEntry>,
+}
+
+impl Trash {
+    pub fn push(&mut self, entry: TrashEntry) {
+        self.entries.push(entry);
+        self.expire();
+    }
+
+    // remove the most recent deletion
+    pub fn pop(&mut self) -> Option<TrashEntry> {
+        self.entries.pop()
+    }
+
+    // remove a deletion by its position in `entries()`
+    pub fn take(&mut self, idx: usize) -> Option<TrashEntry> {
+        if idx < self.entries.len() {
+            let pos = self.entries.len() - 1 - idx;
+            Some(self.entries.remove(pos))
+        } else {
+            None
+        }
+    }
+
+    // most recent deletions first
+    pub fn entries(&self) -> Vec<&TrashEntry> {
+        self.entries.iter().rev().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn expire(&mut self) {
+        let now = clock::now();
+        self.expire_at(now);
+    }
+
+    fn expire_at(&mut self, now: u64) {
+        self.entries.retain(|e| now.saturating_sub(e.deleted_at) < EXPIRY_S);
+        while self.entries.len() > CAPACITY {
+            self.entries.remove(0);
+        }
+    }
+}
+
+#[test]
+fn test_trash_bounds() {
+    let mut trash = Trash::default();
+    for id in 0..(CAPACITY as u64 + 5) {
+        trash.push(TrashEntry::new(id, 0, (1, 2)));
+    }
+    assert_eq!(trash.len(), CAPACITY);
+    assert_eq!(trash.entries()[0].root, CAPACITY as u64 + 4);
+    assert_eq!(trash.take(0).unwrap().root, CAPACITY as u64 + 4);
+    assert_eq!(trash.pop().unwrap().root, CAPACITY as u64 + 3);
+
+    let now = clock::now();
+    trash.entries[0].deleted_at = now - EXPIRY_S - 1;
+    trash.expire_at(now);
+    assert_eq!(trash.len(), CAPACITY - 3);
+}
@@ -0,0 +1,61 @@
+// every http(s) url in text, in order.  punctuation ending a sentence
+// and closing brackets that the url didn't open are left off.
+pub fn find_urls(text: &str) -> Vec<String> {
+    let mut urls = vec![];
+    let mut rest = text;
+    while let Some(start) = next_url_start(rest) {
+        let candidate = &rest[start..];
+        let end = candidate
+            .find(|c: char| c.is_whitespace() || c == '<' || c == '>' || c == '"')
+            .unwrap_or(candidate.len());
+        let url = trim_url(&candidate[..end]);
+        if !url.ends_with("://") {
+            urls.push(url.to_owned());
+        }
+        rest = &candidate[end..];
+    }
+    urls
+}
+
+fn next_url_start(text: &str) -> Option<usize> {
+    match (text.find("http://"), text.find("https://")) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+fn trim_url(mut url: &str) -> &str {
+    loop {
+        let last = match url.chars().last() {
+            Some(c) => c,
+            None => return url,
+        };
+        let unbalanced = |open: char, close: char| {
+            url.matches(close).count() > url.matches(open).count()
+        };
+        let trim = match last {
+            '.' | ',' | ';' | ':' | '!' | '?' | '\'' => true,
+            ')' => unbalanced('(', ')'),
+            ']' => unbalanced('[', ']'),
+            _ => false,
+        };
+        if !trim {
+            return url;
+        }
+        url = &url[..url.len() - last.len_utf8()];
+    }
+}
+
+#[test]
+fn test_find_urls() {
+    assert_eq!(find_urls("no links here"), Vec::<String>::new());
+    assert_eq!(find_urls("see https://example.com/a?b=c."),
+               vec!["https://example.com/a?b=c"]);
+    assert_eq!(find_urls("(docs at http://example.com/x), then"),
+               vec!["http://example.com/x"]);
+    assert_eq!(find_urls("https://en.wikipedia.org/wiki/Rust_(programming_language)!"),
+               vec!["https://en.wikipedia.org/wiki/Rust_(programming_language)"]);
+    assert_eq!(find_urls("[ticket](https://t.co/1) and https://t.co/2;"),
+               vec!["https://t.co/1", "https://t.co/2"]);
+    assert_eq!(find_urls("bare http:// and <https://a.b/c>"), vec!["https://a.b/c"]);
+}
@@ -0,0 +1,186 @@
+use std::os::unix::io::AsRawFd;
+use std::fs::OpenOptions;
+
+use libc::dup2;
+
+use rand;
+use quickcheck::{Arbitrary, Gen, QuickCheck, StdGen};
+
+use voidmap::*;
+
+// the markdown, text, html and csv exports have no importer yet, so the
+// only way back into a map is the save format. these properties load an
+// arbitrary map back from it and check that nothing an exporter shows was
+// lost on the way. colors are the one allowance: they aren't compared, as
+// the exports don't show them either.
+
+const FORMATS: [ExportFormat; 4] =
+    [ExportFormat::Markdown, ExportFormat::Text, ExportFormat::Html, ExportFormat::Csv];
+
+// a node and everything under it, as an exporter would see it
+#[derive(Debug, Clone)]
+struct Tree {
+    content: String,
+    stricken: bool,
+    children: Vec<Tree>,
+}
+
+impl Tree {
+    fn arbitrary_at<G: Gen>(g: &mut G, depth: usize) -> Tree {
+        let breadth = if depth >= 3 { 0 } else { g.gen_range(0, 5 - depth) };
+        Tree {
+            content: content(g),
+            stricken: g.gen_range(0, 4) == 0,
+            children: (0..breadth).map(|_| Tree::arbitrary_at(g, depth + 1)).collect(),
+        }
+    }
+}
+
+impl Arbitrary for Tree {
+    fn arbitrary<G: Gen>(g: &mut G) -> Tree {
+        Tree::arbitrary_at(g, 0)
+    }
+
+    // drop a child, or let one of them take this node's place
+    fn shrink(&self) -> Box<Iterator<Item = Tree>> {
+        let mut smaller = vec![];
+        for (i, child) in self.children.iter().enumerate() {
+            let mut without = self.clone();
+            without.children.remove(i);
+            smaller.push(without);
+            smaller.push(child.clone());
+        }
+        Box::new(smaller.into_iter())
+    }
+}
+
+// words, tags, and the characters the exports give a meaning to
+fn content<G: Gen>(g: &mut G) -> String {
+    let words = ["task", "#task", "#prio=5", "#done", "a<b", "&amp;", "\"quoted\"",
+                 "a,b", "- [ ]", "**", "`tick`", "ünï", "⚡", "%"];
+    let n = g.gen_range(1, 4);
+    (0..n).map(|_| *g.choose(&words).unwrap()).collect::<Vec<_>>().join(" ")
+}
+
+// the anchors of a map
+#[derive(Debug, Clone)]
+struct Map(Vec<Tree>);
+
+impl Arbitrary for Map {
+    fn arbitrary<G: Gen>(g: &mut G) -> Map {
+        let anchors = g.gen_range(1, 4);
+        Map((0..anchors).map(|_| Tree::arbitrary(g)).collect())
+    }
+
+    fn shrink(&self) -> Box<Iterator<Item = Map>> {
+        let mut smaller = vec![];
+        for (i, anchor) in self.0.iter().enumerate() {
+            if self.0.len() > 1 {
+                let mut without = self.0.clone();
+                without.remove(i);
+                smaller.push(Map(without));
+            }
+            for shrunk in anchor.shrink() {
+                let mut with = self.0.clone();
+                with[i] = shrunk;
+                smaller.push(Map(with));
+            }
+        }
+        Box::new(smaller.into_iter())
+    }
+}
+
+fn build(map: &Map) -> Screen {
+    let mut screen = Screen::default();
+    screen.is_test = true;
+    for (i, anchor) in map.0.iter().enumerate() {
+        let id = plant(&mut screen, anchor, 0);
+        screen.nodes.get_mut(&id).unwrap().rooted_coords = (1, 2 + 20 * i as u16);
+    }
+    screen
+}
+
+fn plant(screen: &mut Screen, tree: &Tree, parent_id: NodeID) -> NodeID {
+    screen.max_id += 1;
+    let id = screen.max_id;
+    screen.nodes.insert(id, Node {
+        id,
+        parent_id,
+        content: tree.content.clone(),
+        stricken: tree.stricken,
+        ..Node::default()
+    });
+    screen.nodes.get_mut(&parent_id).unwrap().children.push(id);
+    screen.tag_db.reindex(id, tree.content.clone());
+    for child in &tree.children {
+        plant(screen, child, id);
+    }
+    id
+}
+
+// what's left of a map once ids and colors are forgotten
+fn shape(screen: &Screen, node_id: NodeID) -> String {
+    let node = &screen.nodes[&node_id];
+    let children: Vec<String> = node.children.iter().map(|&child| shape(screen, child)).collect();
+    format!("({:?} {} {:?} [{}])",
+            node.content,
+            node.stricken,
+            screen.tag_db.node_tags(node_id),
+            children.join(" "))
+}
+
+fn load(screen: &Screen) -> Screen {
+    let mut loaded = deserialize_screen(serialize_screen(screen)).unwrap();
+    loaded.is_test = true;
+    loaded
+}
+
+fn prop_reload_keeps_shape(map: Map) -> bool {
+    let screen = build(&map);
+    let loaded = load(&screen);
+    loaded.nodes.len() == screen.nodes.len() && shape(&loaded, 0) == shape(&screen, 0)
+}
+
+fn prop_exports_survive_reload(map: Map) -> bool {
+    let screen = build(&map);
+    let loaded = load(&screen);
+    FORMATS.iter().all(|&format| export_as(&loaded, format, None) == export_as(&screen, format, None))
+}
+
+fn prop_save_is_stable(map: Map) -> bool {
+    let screen = build(&map);
+    serialize_screen(&load(&screen)) == serialize_screen(&screen)
+}
+
+fn quickcheck_into_file() -> QuickCheck<StdGen<rand::ThreadRng>> {
+    // redirect stdout to quickcheck.out like the other properties do
+    let f = OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open("quickcheck.out")
+        .unwrap();
+    let fd = f.as_raw_fd();
+    unsafe {
+        dup2(fd, 1);
+    }
+
+    QuickCheck::new()
+        .gen(StdGen::new(rand::thread_rng(), 1))
+        .tests(500)
+        .max_tests(5_000)
+}
+
+#[test]
+fn qc_reload_keeps_shape() {
+    quickcheck_into_file().quickcheck(prop_reload_keeps_shape as fn(Map) -> bool);
+}
+
+#[test]
+fn qc_exports_survive_reload() {
+    quickcheck_into_file().quickcheck(prop_exports_survive_reload as fn(Map) -> bool);
+}
+
+#[test]
+fn qc_save_is_stable() {
+    quickcheck_into_file().quickcheck(prop_save_is_stable as fn(Map) -> bool);
+}
@@ -34,6 +34,9 @@ impl Arbitrary for Op {
                 Event::Key(Key::Ctrl('n')),
                 Event::Key(Key::Ctrl(c)),
                 Event::Key(Key::Ctrl(u)),
+                Event::Key(Key::Ctrl('w')),
+                Event::Key(Key::Alt(c)),
+                Event::Key(Key::Alt(u)),
                 Event::Key(Key::PageUp),
                 Event::Key(Key::PageDown),
                 Event::Key(Key::Esc),
@@ -44,6 +47,7 @@ impl Arbitrary for Op {
                 Event::Key(Key::Delete),
                 Event::Key(Key::Backspace),
                 Event::Mouse(MouseEvent::Press(MouseButton::Left, x, y)),
+                Event::Mouse(MouseEvent::Hold(x, y)),
                 Event::Mouse(MouseEvent::Release(x, y)),
             ];
         Op { event: g.choose(&*events).unwrap().clone() }
@@ -105,6 +109,84 @@ impl Arbitrary for Content {
 }
 
 
+// a press, some holds and a release, all near the top left where nodes
+// are drawn, often ending on the cell it started from
+#[derive(Debug, Clone)]
+struct Drag(Vec<Op>);
+
+impl Arbitrary for Drag {
+    fn arbitrary<G: Gen>(g: &mut G) -> Drag {
+        let button = *g.choose(&[MouseButton::Left, MouseButton::Right]).unwrap();
+        let (x, y) = (g.gen_range(1, 30), g.gen_range(1, 12));
+        let mut events = vec![Event::Mouse(MouseEvent::Press(button, x, y))];
+        let (mut hx, mut hy) = (x, y);
+        for _ in 0..g.gen_range(0, 4) {
+            if g.gen() {
+                hx = g.gen_range(1, 30);
+                hy = g.gen_range(1, 12);
+            }
+            events.push(Event::Mouse(MouseEvent::Hold(hx, hy)));
+        }
+        let end = if g.gen_range(0, 3) > 0 { (x, y) } else { (hx, hy) };
+        events.push(Event::Mouse(MouseEvent::Release(end.0, end.1)));
+        Drag(events.into_iter().map(|event| Op { event }).collect())
+    }
+}
+
+// C-w and what's done to the split panes with it
+#[derive(Debug, Clone)]
+struct Window(Vec<Op>);
+
+impl Arbitrary for Window {
+    fn arbitrary<G: Gen>(g: &mut G) -> Window {
+        let then = *g.choose(&[Key::Char('v'), Key::Char('w'), Key::Char('q'), Key::Ctrl('w')])
+            .unwrap();
+        Window(vec![Op { event: Event::Key(Key::Ctrl('w')) }, Op { event: Event::Key(then) }])
+    }
+}
+
+// tags, places and colors put on a copy without counting as an edit, so
+// that two copies can differ in nothing else
+#[derive(Debug, Clone)]
+struct Marks {
+    colors: usize,
+    tags: Vec<(usize, u8)>,
+    places: Vec<(usize, u8)>,
+}
+
+impl Arbitrary for Marks {
+    fn arbitrary<G: Gen>(g: &mut G) -> Marks {
+        let pick = |g: &mut G| {
+            (0..g.gen_range(0, 4)).map(|_| (g.gen::<usize>(), g.gen::<u8>())).collect()
+        };
+        Marks {
+            colors: g.gen(),
+            tags: pick(g),
+            places: pick(g),
+        }
+    }
+}
+
+impl Marks {
+    fn put_on(&self, screen: &mut Screen) {
+        let colors = ["red", "green", "blue"];
+        let mut ids: Vec<NodeID> = screen.nodes.keys().cloned().collect();
+        ids.sort();
+        for (i, id) in ids.iter().enumerate() {
+            let color = colors[self.colors.wrapping_add(i) % colors.len()];
+            screen.nodes.get_mut(id).unwrap().color = color.to_owned();
+        }
+        for &(which, value) in &self.tags {
+            let node = screen.nodes.get_mut(&ids[which % ids.len()]).unwrap();
+            node.meta.tags.insert(format!("k{}", value % 3), format!("v{}", value));
+        }
+        for &(which, value) in &self.places {
+            let node = screen.nodes.get_mut(&ids[which % ids.len()]).unwrap();
+            node.meta.gps = (value as f32, -(value as f32));
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 struct OpVec {
     ops: Vec<Op>,
@@ -114,11 +196,11 @@ impl Arbitrary for OpVec {
     fn arbitrary<G: Gen>(g: &mut G) -> OpVec {
         let mut ops = vec![];
         for _ in 0..g.gen_range(1, 100) {
-            if g.gen_range(0, 10) > 0 {
-                ops.push(Op::arbitrary(g));
-            } else {
-                let mut content = Content::arbitrary(g);
-                ops.append(&mut content.0);
+            match g.gen_range(0, 10) {
+                0 => ops.append(&mut Content::arbitrary(g).0),
+                1 => ops.append(&mut Drag::arbitrary(g).0),
+                2 => ops.append(&mut Window::arbitrary(g).0),
+                _ => ops.push(Op::arbitrary(g)),
             }
         }
         OpVec { ops: ops }
@@ -139,7 +221,7 @@ impl Arbitrary for OpVec {
 fn prop_handle_events(ops: OpVec, dims: (u16, u16)) -> bool {
     let mut screen = Screen::default();
     screen.is_test = true;
-    screen.start_raw_mode();
+    screen.start_raw_mode().unwrap();
     screen.draw();
     screen.dims = dims;
 
@@ -152,16 +234,197 @@ fn prop_handle_events(ops: OpVec, dims: (u16, u16)) -> bool {
 
         screen.draw();
         screen.assert_node_consistency();
+        if let Err(e) = screen.check_invariants() {
+            panic!("after {:?}: {}", op, e);
+        }
 
         if should_break {
             screen.cleanup();
-            screen.save();
+            screen.save().unwrap();
             break;
         }
     }
     true
 }
 
+fn prop_replay_events(ops: OpVec, dims: (u16, u16)) -> bool {
+    let mut screen = Screen::default();
+    screen.is_test = true;
+    screen.start_raw_mode().unwrap();
+    screen.draw();
+    screen.dims = dims;
+
+    screen.start_recording('q');
+    apply(&mut screen, &ops);
+    screen.stop_recording();
+
+    screen.replay_macro_with('q', 2, |screen, evt| {
+        if let Err(e) = screen.check_invariants() {
+            panic!("after replaying {:?}: {}", evt, e);
+        }
+    });
+    screen.draw();
+    screen.assert_node_consistency();
+    screen.cleanup();
+    true
+}
+
+// everything saved about the nodes, which are given a new color on load
+fn saved_nodes(screen: &Screen) -> Vec<String> {
+    let mut nodes: Vec<String> = screen.nodes
+        .values()
+        .map(|node| {
+            let mut node = node.clone();
+            node.color.clear();
+            let mut tags: Vec<_> = node.meta.tags.drain().collect();
+            tags.sort();
+            format!("{:?} {:?}", node, tags)
+        })
+        .collect();
+    nodes.sort();
+    nodes
+}
+
+fn prop_serialization_roundtrip(ops: OpVec, dims: (u16, u16)) -> bool {
+    let mut screen = Screen::default();
+    screen.is_test = true;
+    screen.dims = dims;
+
+    for op in &ops.ops {
+        if !screen.handle_event(op.event.clone()) {
+            break;
+        }
+        screen.draw();
+    }
+
+    let loaded = deserialize_screen(serialize_screen(&screen)).unwrap();
+    if loaded.nodes.values().any(|node| node.selected) {
+        return false;
+    }
+    // selection is kept in the session, so it's the one thing not loaded
+    for node in screen.nodes.values_mut() {
+        node.selected = false;
+    }
+    saved_nodes(&loaded) == saved_nodes(&screen) && loaded.arrows == screen.arrows &&
+    loaded.max_id == screen.max_id && loaded.bookmarks == screen.bookmarks &&
+    loaded.trash.len() == screen.trash.len()
+}
+
+// the same map, as another copy of the work file would see it
+fn copy(screen: &Screen) -> Screen {
+    let mut copy = deserialize_screen(serialize_screen(screen)).unwrap();
+    copy.is_test = true;
+    copy.dims = screen.dims;
+    copy
+}
+
+fn apply(screen: &mut Screen, ops: &OpVec) {
+    for op in &ops.ops {
+        let keep_going = screen.handle_event(op.event.clone());
+        if let Err(e) = screen.check_invariants() {
+            panic!("after {:?}: {}", op, e);
+        }
+        if !keep_going {
+            break;
+        }
+        screen.draw();
+    }
+}
+
+// the color of every node, which saved_nodes leaves out
+fn colors(screen: &Screen) -> Vec<(NodeID, String)> {
+    let mut colors: Vec<(NodeID, String)> =
+        screen.nodes.values().map(|node| (node.id, node.color.clone())).collect();
+    colors.sort();
+    colors
+}
+
+// which copy is merged into which doesn't change the result
+fn prop_merge_commutes(base: OpVec,
+                       a_ops: OpVec,
+                       b_ops: OpVec,
+                       a_marks: Marks,
+                       b_marks: Marks,
+                       dims: (u16, u16))
+                       -> bool {
+    let mut base_screen = Screen::default();
+    base_screen.is_test = true;
+    base_screen.dims = dims;
+    apply(&mut base_screen, &base);
+
+    let (mut a, mut b) = (copy(&base_screen), copy(&base_screen));
+    apply(&mut a, &a_ops);
+    apply(&mut b, &b_ops);
+    a_marks.put_on(&mut a);
+    b_marks.put_on(&mut b);
+
+    // colors are picked again on load, so they're put back on each copy
+    let mut ab = copy(&a);
+    a_marks.put_on(&mut ab);
+    ab.merge(&b);
+    let mut ba = copy(&b);
+    b_marks.put_on(&mut ba);
+    ba.merge(&a);
+    for merged in &[&ab, &ba] {
+        if let Err(e) = merged.check_invariants() {
+            panic!("after merging: {}", e);
+        }
+    }
+    saved_nodes(&ab) == saved_nodes(&ba) && colors(&ab) == colors(&ba) &&
+    ab.arrows == ba.arrows && ab.max_id == ba.max_id && ab.bookmarks == ba.bookmarks &&
+    ab.tombstones == ba.tombstones
+}
+
+// each node is filed under its own id, below max_id, with one parent
+fn ids_are_sound(screen: &Screen) -> bool {
+    let mut children: Vec<NodeID> =
+        screen.nodes.values().flat_map(|n| n.children.iter().cloned()).collect();
+    let count = children.len();
+    children.sort();
+    children.dedup();
+    let trashed = screen.trash
+        .entries()
+        .into_iter()
+        .chain(&screen.archive)
+        .flat_map(|e| e.nodes.keys());
+    children.len() == count &&
+    screen.nodes.iter().all(|(&id, node)| node.id == id && id <= screen.max_id) &&
+    trashed.into_iter().all(|&id| id <= screen.max_id)
+}
+
+// creating, deleting, reloading and importing another map never reuses an id
+fn prop_ids_stay_unique(edits: OpVec, more: OpVec, imported: OpVec, dims: (u16, u16)) -> bool {
+    let mut screen = Screen::default();
+    screen.is_test = true;
+    screen.dims = dims;
+    apply(&mut screen, &edits);
+    if !ids_are_sound(&screen) {
+        return false;
+    }
+
+    let mut screen = copy(&screen);
+    apply(&mut screen, &more);
+    if !ids_are_sound(&screen) {
+        return false;
+    }
+
+    // a separate map numbers its nodes from 1 too
+    let mut other = Screen::default();
+    other.is_test = true;
+    other.dims = dims;
+    apply(&mut other, &imported);
+    screen.merge(&other);
+    if let Err(e) = screen.check_invariants() {
+        panic!("after merging: {}", e);
+    }
+    if !ids_are_sound(&screen) {
+        return false;
+    }
+
+    apply(&mut screen, &edits);
+    ids_are_sound(&screen)
+}
+
 #[test]
 fn qc_input_events_dont_crash_void() {
     // redirect stdout to quickcheck.out to make travis happy
@@ -181,3 +444,79 @@ fn qc_input_events_dont_crash_void() {
         .max_tests(1_000_000)
         .quickcheck(prop_handle_events as fn(OpVec, (u16, u16)) -> bool);
 }
+
+#[test]
+fn qc_replayed_events_dont_crash_void() {
+    let f = OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open("quickcheck.out")
+        .unwrap();
+    let fd = f.as_raw_fd();
+    unsafe {
+        dup2(fd, 1);
+    }
+
+    QuickCheck::new()
+        .gen(StdGen::new(rand::thread_rng(), 1))
+        .tests(10_000)
+        .max_tests(100_000)
+        .quickcheck(prop_replay_events as fn(OpVec, (u16, u16)) -> bool);
+}
+
+#[test]
+fn qc_screens_survive_serialization() {
+    let f = OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open("quickcheck.out")
+        .unwrap();
+    let fd = f.as_raw_fd();
+    unsafe {
+        dup2(fd, 1);
+    }
+
+    QuickCheck::new()
+        .gen(StdGen::new(rand::thread_rng(), 1))
+        .tests(1_000)
+        .max_tests(10_000)
+        .quickcheck(prop_serialization_roundtrip as fn(OpVec, (u16, u16)) -> bool);
+}
+
+#[test]
+fn qc_merge_converges() {
+    let f = OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open("quickcheck.out")
+        .unwrap();
+    let fd = f.as_raw_fd();
+    unsafe {
+        dup2(fd, 1);
+    }
+
+    QuickCheck::new()
+        .gen(StdGen::new(rand::thread_rng(), 1))
+        .tests(1_000)
+        .max_tests(10_000)
+        .quickcheck(prop_merge_commutes as fn(OpVec, OpVec, OpVec, Marks, Marks, (u16, u16)) -> bool);
+}
+
+#[test]
+fn qc_ids_stay_unique() {
+    let f = OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open("quickcheck.out")
+        .unwrap();
+    let fd = f.as_raw_fd();
+    unsafe {
+        dup2(fd, 1);
+    }
+
+    QuickCheck::new()
+        .gen(StdGen::new(rand::thread_rng(), 1))
+        .tests(1_000)
+        .max_tests(10_000)
+        .quickcheck(prop_ids_stay_unique as fn(OpVec, OpVec, OpVec, (u16, u16)) -> bool);
+}
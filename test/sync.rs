@@ -0,0 +1,80 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use voidmap::*;
+
+fn screen_with(id: NodeID, content: &str) -> Screen {
+    let mut screen = Screen::default();
+    screen.is_test = true;
+    let node = Node {
+        id,
+        parent_id: 0,
+        content: content.to_owned(),
+        ..Node::default()
+    };
+    screen.nodes.insert(id, node);
+    screen.nodes.get_mut(&0).unwrap().children.push(id);
+    screen.max_id = id;
+    screen
+}
+
+#[test]
+fn sync_edits_propagate_both_ways() {
+    let server = Server::bind("127.0.0.1:0", "hunter2").unwrap();
+    let addr = server.local_addr().unwrap().to_string();
+    let mut served = screen_with(1, "from the server");
+    let mut client = screen_with(2, "from the client");
+    client.sync = Some(Peer::new(&addr, "hunter2"));
+
+    // the first tick starts a swap in the background, and a later one takes
+    // in what came back
+    client.tick();
+    assert!(server.answer(&mut served).unwrap().0 > 0);
+    let started = Instant::now();
+    while !client.nodes.contains_key(&1) {
+        assert!(started.elapsed() < Duration::from_secs(10), "sync never finished");
+        thread::sleep(Duration::from_millis(10));
+        client.tick();
+    }
+
+    assert_eq!(served.nodes[&2].content, "from the client");
+    assert_eq!(client.nodes[&1].content, "from the server");
+    assert_eq!(served.nodes[&0].children, vec![1, 2]);
+    assert_eq!(client.nodes[&0].children, vec![1, 2]);
+    assert!(client.sync.as_ref().unwrap().last_success.is_some());
+}
+
+#[test]
+fn sync_refuses_the_wrong_token() {
+    let server = Server::bind("127.0.0.1:0", "hunter2").unwrap();
+    let addr = server.local_addr().unwrap().to_string();
+    let mut served = screen_with(1, "private");
+
+    let data = serialize_screen(&screen_with(2, "intruder"));
+    let client = thread::spawn(move || exchange(&addr, "guess", &data));
+    assert!(server.answer(&mut served).is_err());
+    assert!(client.join().unwrap().is_err());
+    assert!(!served.nodes.contains_key(&2));
+}
+
+#[test]
+fn sync_refuses_an_encrypted_map() {
+    let server = Server::bind("127.0.0.1:0", "hunter2").unwrap();
+    let addr = server.local_addr().unwrap().to_string();
+
+    // a client with an encrypted map stops syncing without sending it
+    let mut client = screen_with(2, "secret");
+    client.key = Some(CryptoKey::generate("passphrase"));
+    client.sync = Some(Peer::new(&addr, "hunter2"));
+    client.tick();
+    assert!(client.sync.is_none());
+
+    // and a server with one turns clients away before they send theirs
+    let mut served = screen_with(1, "secret");
+    served.key = Some(CryptoKey::generate("passphrase"));
+    let data = serialize_screen(&screen_with(2, "from the client"));
+    let client = thread::spawn(move || exchange(&addr, "hunter2", &data));
+    assert!(server.answer(&mut served).is_err());
+    assert!(client.join().unwrap().is_err());
+    assert!(!served.nodes.contains_key(&2));
+}
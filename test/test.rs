@@ -4,3 +4,5 @@ extern crate termion;
 extern crate quickcheck;
 extern crate rand;
 mod screen;
+mod sync;
+mod roundtrip;